@@ -1,25 +1,47 @@
 use std::convert::TryFrom;
 use std::ffi::OsString;
 use std::io;
-use std::path::{Path, PathBuf};
-use std::sync::Arc;
-
-use storyteller::{EventHandler, EventListener, FinishProcessing};
-use tracing_appender::rolling::{RollingFileAppender, Rotation};
-
-use cargo_msrv::cli::{CargoCli, CargoMsrvOpts};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use storyteller::{EventHandler, EventListener, EventReporter, FinishProcessing};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{Layer, Registry};
+
+use cargo_msrv::chrome_trace::{self, ChromeTraceGuard};
+use cargo_msrv::cli::{CargoCli, CargoMsrvOpts, SubCommand};
 use cargo_msrv::error::CargoMSRVError;
 use cargo_msrv::exit_code::ExitCode;
+use cargo_msrv::log_file::{RotatingLogFile, DEFAULT_MAX_LOG_FILES, DEFAULT_MAX_LOG_SIZE};
+use cargo_msrv::manifest::human_output::configured_human_output;
+use cargo_msrv::manifest::metrics::configured_metrics;
+use cargo_msrv::manifest::mirror::configured_mirror;
+use cargo_msrv::otlp::{self, OtlpGuard};
 use cargo_msrv::reporter::{
-    DiscardOutputHandler, HumanProgressHandler, JsonHandler, MinimalOutputHandler, ReporterSetup,
+    AzureHandler, BuildkiteAnnotationHandler, DiscardOutputHandler, EventLogHandler,
+    EventSocketHandler, GitHubOutputHandler, GitLabHandler, HumanProgressHandler, JsonHandler,
+    MatrixReportHandler, MetricsHandler, MinimalOutputHandler, ReporterSetup,
+};
+use cargo_msrv::reporter::{Event, Reporter, TerminateWithFailure, Terminated};
+use cargo_msrv::rust::dist_server::{self, Mirror};
+use cargo_msrv::{
+    jobserver, run_app, signal, Context, OutputFormat, TracingOptions, TracingTargetOption,
 };
-use cargo_msrv::reporter::{Event, Reporter, TerminateWithFailure};
-use cargo_msrv::{run_app, Context, OutputFormat, TracingOptions, TracingTargetOption};
 
 fn main() {
     std::process::exit(
         match setup_opts_and_tracing(std::env::args_os) {
-            Ok((_guard, exit_code)) => exit_code,
+            Ok((guard, exit_code)) => {
+                if let Some(guard) = guard {
+                    guard.finish();
+                }
+
+                exit_code
+            }
             Err(err) => {
                 // Don't use `tracing::error!` here because maybe an issue with `tracing` setup is what
                 // caused this error in the first place
@@ -39,6 +61,26 @@ fn setup_opts_and_tracing<I: IntoIterator<Item = OsString>, F: FnOnce() -> I + C
     let matches = CargoCli::parse_args(args());
     let opts = matches.to_cargo_msrv_cli().to_opts();
 
+    let jobs = opts
+        .subcommand
+        .toolchain_opts()
+        .and_then(|toolchain_opts| toolchain_opts.jobs);
+
+    // SAFETY: no file has been opened yet, in particular the log file
+    unsafe { jobserver::install(jobs) }.map_err(SetupError::UnableToCreateJobserver)?;
+
+    let dist_server = opts
+        .subcommand
+        .toolchain_opts()
+        .and_then(|toolchain_opts| toolchain_opts.dist_server.clone());
+    let mirror = configured_mirror(&opts).map_err(SetupError::InvalidMirrorConfig)?;
+
+    dist_server::install(Mirror::resolve(
+        dist_server,
+        |var| std::env::var(var).ok(),
+        mirror,
+    ));
+
     // NB: We must collect the guard of the non-blocking tracing appender, since it will only live as
     // long as the lifetime of the worker guard. If we don't do this, the guard would be dropped after
     // the scope of `if !config.no_tracing() { ... }` ended, and as a result, anything logged in
@@ -47,14 +89,36 @@ fn setup_opts_and_tracing<I: IntoIterator<Item = OsString>, F: FnOnce() -> I + C
 
     let tracing_is_enabled = !opts.shared_opts.debug_output_opts.no_log;
 
-    if tracing_is_enabled {
-        let options = TracingOptions::new(
-            opts.shared_opts.debug_output_opts.log_target,
-            opts.shared_opts.debug_output_opts.log_level,
-        );
-
-        let tracing_config = TracingConfig::try_from_options(&options)?;
-        guard = Some(init_tracing(&tracing_config)?);
+    let tracing_config = if tracing_is_enabled {
+        let debug_output_opts = &opts.shared_opts.debug_output_opts;
+        let options =
+            TracingOptions::new(debug_output_opts.log_target, debug_output_opts.log_level)
+                .with_rotation(
+                    debug_output_opts
+                        .max_log_size
+                        .unwrap_or(DEFAULT_MAX_LOG_SIZE),
+                    debug_output_opts
+                        .max_log_files
+                        .unwrap_or(DEFAULT_MAX_LOG_FILES),
+                );
+
+        Some(TracingConfig::try_from_options(&options)?)
+    } else {
+        None
+    };
+
+    // The traces are exported, and recorded, regardless of the logs
+    let otlp_endpoint =
+        otlp::traces_endpoint(opts.shared_opts.debug_output_opts.otlp_endpoint.as_deref());
+
+    let trace_file = opts.shared_opts.debug_output_opts.trace_file.clone();
+
+    if tracing_config.is_some() || otlp_endpoint.is_some() || trace_file.is_some() {
+        guard = Some(init_tracing(
+            tracing_config.as_ref(),
+            otlp_endpoint,
+            trace_file,
+        )?);
     }
 
     setup_reporter(opts).map(|exit_code| (guard, exit_code))
@@ -66,33 +130,194 @@ fn setup_reporter(opts: CargoMsrvOpts) -> Result<ExitCode, SetupError> {
         "initializing"
     );
 
+    let started = Instant::now();
+    let panic_message = capture_panic_message();
+    // Besides the searches, bisect-lockfile stops gracefully too, so it restores the lockfile
+    let searches = matches!(
+        opts.subcommand,
+        SubCommand::Find(_) | SubCommand::Verify(_) | SubCommand::BisectLockfile(_)
+    );
+
     let setup = ReporterSetup;
     let (reporter, listener) = setup.create();
 
     tracing::info!("storyteller channel created");
 
     let output_format = opts.shared_opts.user_output_opts.effective_output_format();
-    let handler = WrappingHandler::from(output_format);
+    cargo_msrv::reporter::set_ascii(opts.shared_opts.user_output_opts.ascii());
+    cargo_msrv::reporter::set_locale(opts.shared_opts.user_output_opts.locale());
+    cargo_msrv::reporter::set_color(opts.shared_opts.user_output_opts.color());
+
+    let event_socket = opts
+        .shared_opts
+        .user_output_opts
+        .event_socket()
+        .map(|path| {
+            EventSocketHandler::connect(path).map_err(|error| {
+                SetupError::UnableToConnectEventSocket {
+                    path: path.to_path_buf(),
+                    error,
+                }
+            })
+        })
+        .transpose()?;
+
+    let event_log = opts
+        .shared_opts
+        .user_output_opts
+        .log_events()
+        .map(|path| {
+            EventLogHandler::create(path).map_err(|error| SetupError::UnableToCreateEventLog {
+                path: path.to_path_buf(),
+                error,
+            })
+        })
+        .transpose()?;
+
+    let github_output = opts
+        .shared_opts
+        .user_output_opts
+        .github_output()
+        .then(GitHubOutputHandler::from_env)
+        .flatten();
+
+    let buildkite_annotation = opts
+        .shared_opts
+        .user_output_opts
+        .buildkite_annotation()
+        .then(BuildkiteAnnotationHandler::from_env)
+        .flatten();
+
+    let matrix_report = opts
+        .shared_opts
+        .user_output_opts
+        .matrix_report()
+        .map(|(path, format)| MatrixReportHandler::new(path.to_path_buf(), format));
+
+    let metrics = configured_metrics(&opts)
+        .map_err(SetupError::InvalidMetricsConfig)?
+        .map(MetricsHandler::new);
+
+    let output = match output_format {
+        OutputFormat::Human => {
+            let config =
+                configured_human_output(&opts).map_err(SetupError::InvalidHumanOutputConfig)?;
+
+            if let Some(theme) = config.theme {
+                cargo_msrv::reporter::set_theme(theme);
+            }
+
+            match config.layout {
+                Some(layout) => {
+                    WrappingHandler::HumanProgress(HumanProgressHandler::with_layout(layout))
+                }
+                None => WrappingHandler::from(output_format),
+            }
+        }
+        OutputFormat::Json => WrappingHandler::Json(
+            JsonHandler::stderr().pretty(opts.shared_opts.user_output_opts.json_pretty()),
+        ),
+        OutputFormat::Gitlab => {
+            WrappingHandler::GitLab(GitLabHandler::stdout(manifest_path(&opts)))
+        }
+        OutputFormat::Azure => WrappingHandler::Azure(AzureHandler::stdout(manifest_path(&opts))),
+        _ => WrappingHandler::from(output_format),
+    };
+
+    let handler = StreamingHandler {
+        output,
+        event_socket,
+        event_log,
+        github_output,
+        buildkite_annotation,
+        matrix_report,
+        metrics,
+    };
     let finalizer = listener.run_handler(Arc::new(handler));
     tracing::info!("storyteller started handler");
     tracing::info!("starting execution");
 
-    let res = setup_context_and_run(opts, &reporter);
+    // Ctrl-C and SIGTERM stop the search, so the user output is still finished as well. Other
+    // subcommands are stopped right away, as usual. Either way, the processes spawned by
+    // cargo-msrv are killed as well.
+    if let Err(error) = signal::install_handler(searches) {
+        tracing::info!(%error, "unable to install the signal handler");
+    }
+
+    // A panic unwinds to here, so the user output is still finished, including the final
+    // `Terminated` event
+    let res = panic::catch_unwind(AssertUnwindSafe(|| setup_context_and_run(opts, &reporter)));
 
     tracing::info!("finished execution");
 
-    let exit_code = get_exit_code(res, &reporter)?;
+    let (exit_code, terminated) = match res {
+        Ok(res) => {
+            let exit_code = get_exit_code(res, &reporter)?;
+            (exit_code, Terminated::new(exit_code, started.elapsed()))
+        }
+        Err(_) => {
+            let message = panic_message
+                .lock()
+                .ok()
+                .and_then(|mut message| message.take());
+            (
+                ExitCode::Panic,
+                Terminated::panicked(message, started.elapsed()),
+            )
+        }
+    };
+
+    reporter
+        .report_event(terminated)
+        .map_err(|_| SetupError::StorytellerSend)?;
+
     disconnect_reporter(reporter)?;
     wait_for_user_output(finalizer)?;
 
     Ok(exit_code)
 }
 
+/// The Cargo manifest of the crate, as given on the command line, for the locations of the GitLab
+/// code quality report and the Azure Pipelines issues.
+fn manifest_path(opts: &CargoMsrvOpts) -> PathBuf {
+    let shared_opts = &opts.shared_opts;
+
+    match (&shared_opts.manifest_path, &shared_opts.path) {
+        (Some(manifest_path), _) => manifest_path.clone(),
+        (None, Some(path)) => path.join("Cargo.toml"),
+        (None, None) => PathBuf::from("Cargo.toml"),
+    }
+}
+
+/// Keep the message and location of a panic, to be reported by the final `Terminated` event,
+/// and then print the panic as usual.
+fn capture_panic_message() -> Arc<Mutex<Option<String>>> {
+    let captured = Arc::new(Mutex::new(None));
+
+    let hook_captured = Arc::clone(&captured);
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        if let Ok(mut message) = hook_captured.lock() {
+            message.get_or_insert_with(|| info.to_string());
+        }
+
+        default_hook(info);
+    }));
+
+    captured
+}
+
 fn setup_context_and_run(
     opts: CargoMsrvOpts,
     reporter: &impl Reporter,
 ) -> Result<(), CargoMSRVError> {
     let context = Context::try_from(opts)?;
+
+    // The root span of the exported traces
+    let span = tracing::info_span!("cargo-msrv", subcommand = context.reporting_name());
+    let _entered = span.enter();
+
     run_app(&context, reporter)
 }
 
@@ -104,11 +329,13 @@ fn get_exit_code(
     Ok(match result {
         Ok(_) => ExitCode::Success,
         Err(err) => {
+            let exit_code = ExitCode::of_error(&err);
+
             reporter
                 .report_event(TerminateWithFailure::new(err))
                 .map_err(|_| SetupError::StorytellerSend)?;
 
-            ExitCode::Failure
+            exit_code
         }
     })
 }
@@ -119,6 +346,8 @@ enum WrappingHandler {
     HumanProgress(HumanProgressHandler),
     Json(JsonHandler<io::Stderr>),
     Minimal(MinimalOutputHandler<io::Stdout, io::Stderr>),
+    GitLab(GitLabHandler<io::Stdout>),
+    Azure(AzureHandler<io::Stdout>),
     DiscardOutput(DiscardOutputHandler),
 }
 
@@ -130,6 +359,8 @@ impl EventHandler for WrappingHandler {
             WrappingHandler::HumanProgress(inner) => inner.handle(event),
             WrappingHandler::Json(inner) => inner.handle(event),
             WrappingHandler::Minimal(inner) => inner.handle(event),
+            WrappingHandler::GitLab(inner) => inner.handle(event),
+            WrappingHandler::Azure(inner) => inner.handle(event),
             WrappingHandler::DiscardOutput(inner) => inner.handle(event),
         }
     }
@@ -139,17 +370,97 @@ impl EventHandler for WrappingHandler {
             WrappingHandler::HumanProgress(inner) => inner.finish(),
             WrappingHandler::Json(inner) => inner.finish(),
             WrappingHandler::Minimal(inner) => inner.finish(),
+            WrappingHandler::GitLab(inner) => inner.finish(),
+            WrappingHandler::Azure(inner) => inner.finish(),
             WrappingHandler::DiscardOutput(inner) => inner.finish(),
         }
     }
 }
 
+/// Delegates the events to the handler of the user output, and if requested, also streams them
+/// over the event socket, records them to the event log, collects the outputs of the GitHub
+/// Actions step, annotates the Buildkite build, writes the matrix report, and sends the metrics of
+/// the run.
+struct StreamingHandler {
+    output: WrappingHandler,
+    event_socket: Option<EventSocketHandler>,
+    event_log: Option<EventLogHandler>,
+    github_output: Option<GitHubOutputHandler>,
+    buildkite_annotation: Option<BuildkiteAnnotationHandler>,
+    matrix_report: Option<MatrixReportHandler>,
+    metrics: Option<MetricsHandler>,
+}
+
+impl EventHandler for StreamingHandler {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        if let Some(event_socket) = &self.event_socket {
+            event_socket.handle(event.clone());
+        }
+
+        if let Some(event_log) = &self.event_log {
+            event_log.handle(event.clone());
+        }
+
+        if let Some(github_output) = &self.github_output {
+            github_output.handle(event.clone());
+        }
+
+        if let Some(buildkite_annotation) = &self.buildkite_annotation {
+            buildkite_annotation.handle(event.clone());
+        }
+
+        if let Some(matrix_report) = &self.matrix_report {
+            matrix_report.handle(event.clone());
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.handle(event.clone());
+        }
+
+        self.output.handle(event);
+    }
+
+    fn finish(&self) {
+        if let Some(event_socket) = &self.event_socket {
+            event_socket.finish();
+        }
+
+        if let Some(event_log) = &self.event_log {
+            event_log.finish();
+        }
+
+        if let Some(github_output) = &self.github_output {
+            github_output.finish();
+        }
+
+        if let Some(buildkite_annotation) = &self.buildkite_annotation {
+            buildkite_annotation.finish();
+        }
+
+        if let Some(matrix_report) = &self.matrix_report {
+            matrix_report.finish();
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.finish();
+        }
+
+        self.output.finish();
+    }
+}
+
 impl From<OutputFormat> for WrappingHandler {
     fn from(output_format: OutputFormat) -> Self {
         match output_format {
             OutputFormat::Human => Self::HumanProgress(HumanProgressHandler::default()),
             OutputFormat::Json => Self::Json(JsonHandler::stderr()),
             OutputFormat::Minimal => Self::Minimal(MinimalOutputHandler::stderr()),
+            OutputFormat::Gitlab => {
+                Self::GitLab(GitLabHandler::stdout(PathBuf::from("Cargo.toml")))
+            }
+            OutputFormat::Azure => Self::Azure(AzureHandler::stdout(PathBuf::from("Cargo.toml"))),
             OutputFormat::None => {
                 // To disable regular output. Useful when outputting logs to stdout, as the
                 //   regular output and the log output may otherwise interfere with each other.
@@ -182,50 +493,79 @@ fn wait_for_user_output(finalizer: impl FinishProcessing) -> Result<(), SetupErr
     Ok(())
 }
 
-fn init_tracing(tracing_config: &TracingConfig) -> Result<TracingGuard, SetupError> {
-    let level = tracing_config.level;
-
-    match &tracing_config.target {
-        // Log (non-blocking) to disk
-        TracingTarget::ToDisk(path) => {
-            let guard = init_tracing_to_file(path, level);
-
-            let folder = format!("{}", path.display());
-            tracing::debug!(log_folder = folder.as_str());
-
-            guard
+fn init_tracing(
+    tracing_config: Option<&TracingConfig>,
+    otlp_endpoint: Option<String>,
+    trace_file: Option<PathBuf>,
+) -> Result<TracingGuard, SetupError> {
+    let (log_layer, log_guard) = match tracing_config {
+        Some(tracing_config) => {
+            let (layer, guard) = log_layer(tracing_config)?;
+            (Some(layer), guard)
         }
-        // Log to stdout
-        TracingTarget::Stdout => init_tracing_to_stdout(level),
-    }
-}
+        None => (None, None),
+    };
 
-fn init_tracing_to_file(
-    log_folder: impl AsRef<Path>,
-    level: tracing::Level,
-) -> Result<TracingGuard, SetupError> {
-    let file_appender = RollingFileAppender::new(Rotation::DAILY, log_folder, "cargo-msrv-log");
-    let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+    let (otlp_layer, otlp_guard) = otlp_endpoint.map(otlp::layer).unzip();
+    let (chrome_trace_layer, chrome_trace_guard) = trace_file.map(chrome_trace::layer).unzip();
 
-    let subscriber = tracing_subscriber::fmt()
-        .json()
-        .with_max_level(level)
-        .with_writer(non_blocking)
-        .finish();
+    let subscriber = tracing_subscriber::registry()
+        .with(log_layer)
+        .with(otlp_layer)
+        .with(chrome_trace_layer);
 
     tracing::subscriber::set_global_default(subscriber)
         .map_err(|_| SetupError::UnableToInitTracing)?;
 
-    Ok(TracingGuard::NonBlockingGuard(guard))
+    if let Some(TracingTarget::ToDisk { folder, .. }) = tracing_config.map(|config| &config.target)
+    {
+        let folder = format!("{}", folder.display());
+        tracing::debug!(log_folder = folder.as_str());
+    }
+
+    Ok(TracingGuard {
+        log: log_guard,
+        otlp: otlp_guard,
+        chrome_trace: chrome_trace_guard,
+    })
 }
 
-fn init_tracing_to_stdout(level: tracing::Level) -> Result<TracingGuard, SetupError> {
-    let subscriber = tracing_subscriber::fmt().with_max_level(level).finish();
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
 
-    tracing::subscriber::set_global_default(subscriber)
-        .map_err(|_| SetupError::UnableToInitTracing)?;
+fn log_layer(
+    tracing_config: &TracingConfig,
+) -> Result<(BoxedLayer, Option<WorkerGuard>), SetupError> {
+    let level = LevelFilter::from_level(tracing_config.level);
+
+    let layer = match &tracing_config.target {
+        // Log (non-blocking) to disk
+        TracingTarget::ToDisk {
+            folder,
+            max_size,
+            max_files,
+        } => {
+            let log_file = RotatingLogFile::open(folder, *max_size, *max_files)
+                .map_err(|_| SetupError::UnableToAccessLogFolder)?;
+            let (non_blocking, guard) = tracing_appender::non_blocking(log_file);
+
+            let layer = tracing_subscriber::fmt::layer()
+                .json()
+                .with_writer(non_blocking)
+                .with_filter(level)
+                .boxed();
+
+            return Ok((layer, Some(guard)));
+        }
+        // Log to stderr
+        TracingTarget::Stderr => tracing_subscriber::fmt::layer()
+            .with_writer(io::stderr)
+            .with_filter(level)
+            .boxed(),
+        // Log to stdout
+        TracingTarget::Stdout => tracing_subscriber::fmt::layer().with_filter(level).boxed(),
+    };
 
-    Ok(TracingGuard::None)
+    Ok((layer, None))
 }
 
 struct TracingConfig {
@@ -235,7 +575,7 @@ struct TracingConfig {
 
 impl TracingConfig {
     fn try_from_options(config: &TracingOptions) -> Result<Self, SetupError> {
-        let target = TracingTarget::try_from_option(config.target())?;
+        let target = TracingTarget::try_from_options(config)?;
 
         Ok(Self {
             level: (*config.level()).into(),
@@ -245,25 +585,52 @@ impl TracingConfig {
 }
 
 enum TracingTarget {
-    ToDisk(PathBuf),
+    ToDisk {
+        folder: PathBuf,
+        max_size: u64,
+        max_files: usize,
+    },
+    Stderr,
     Stdout,
 }
 
 impl TracingTarget {
-    fn try_from_option(option: &TracingTargetOption) -> Result<Self, SetupError> {
-        match option {
-            TracingTargetOption::File => {
-                let folder = log_folder()?;
-                Ok(Self::ToDisk(folder))
-            }
+    fn try_from_options(options: &TracingOptions) -> Result<Self, SetupError> {
+        match options.target() {
+            TracingTargetOption::File => Ok(Self::ToDisk {
+                folder: log_folder()?,
+                max_size: options.max_log_size(),
+                max_files: options.max_log_files(),
+            }),
+            TracingTargetOption::Stderr => Ok(Self::Stderr),
             TracingTargetOption::Stdout => Ok(Self::Stdout),
         }
     }
 }
 
-enum TracingGuard {
-    NonBlockingGuard(#[allow(dead_code)] tracing_appender::non_blocking::WorkerGuard),
-    None,
+/// Flushes the logs, exports the traces, and writes the trace file, when finished.
+struct TracingGuard {
+    otlp: Option<OtlpGuard>,
+    chrome_trace: Option<ChromeTraceGuard>,
+    // Dropped last, so the failures of the other guards are logged when they're dropped
+    #[allow(dead_code)]
+    log: Option<WorkerGuard>,
+}
+
+impl TracingGuard {
+    fn finish(self) {
+        // The traces are a convenience, which should not fail cargo-msrv itself
+        let otlp = self.otlp.map(OtlpGuard::finish);
+        let chrome_trace = self.chrome_trace.map(ChromeTraceGuard::finish);
+
+        for error in [otlp, chrome_trace]
+            .into_iter()
+            .flatten()
+            .filter_map(Result::err)
+        {
+            eprintln!("{}", error);
+        }
+    }
 }
 
 fn log_folder() -> Result<PathBuf, SetupError> {
@@ -290,4 +657,22 @@ enum SetupError {
 
     #[error("Failure while waiting for unprocessed events to be processed")]
     StorytellerFinishEventProcessing,
+
+    #[error("Unable to connect to the event socket at '{}': {error}", path.display())]
+    UnableToConnectEventSocket { path: PathBuf, error: io::Error },
+
+    #[error("Unable to create the event log at '{}': {error}", path.display())]
+    UnableToCreateEventLog { path: PathBuf, error: io::Error },
+
+    #[error("{0}")]
+    InvalidHumanOutputConfig(CargoMSRVError),
+
+    #[error("{0}")]
+    InvalidMetricsConfig(CargoMSRVError),
+
+    #[error("{0}")]
+    InvalidMirrorConfig(CargoMSRVError),
+
+    #[error("Unable to create the jobserver of --jobs: {0}")]
+    UnableToCreateJobserver(io::Error),
 }
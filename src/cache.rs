@@ -0,0 +1,413 @@
+//! The global cache of cargo-msrv, which holds the fetched crates.io index files, the channel
+//! manifests of Rust releases, and the index of Rust releases of each release source.
+//!
+//! The cache is bounded by a maximum size. When a run of cargo-msrv leaves the cache larger than
+//! this maximum, the least recently used files are evicted, until it fits again. Since the cached
+//! clients use the modification time of a file to decide whether it is stale, the moment a file
+//! was last used is kept in a separate usage index, instead of being written to the file itself.
+
+use crate::error::{IoError, IoErrorSource, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The environment variable which supplies the maximum cache size, when the `--max-cache-size`
+/// argument is not given.
+pub const MAX_CACHE_SIZE_ENV_VAR: &str = "CARGO_MSRV_MAX_CACHE_SIZE";
+
+/// The maximum size of the cache, when none is configured: 1 GiB.
+pub const DEFAULT_MAX_CACHE_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// The file, within the cache directory, which records when each cached file was last used.
+const USAGE_FILE: &str = "usage.json";
+
+/// The usage index is first written to this file, and then moved in place, so a concurrent run
+/// never reads a partially written index.
+const USAGE_FILE_STAGED: &str = "usage.json.tmp";
+
+pub struct GlobalCache {
+    dir: Utf8PathBuf,
+    /// The files used during this run, by their path relative to the cache directory, which are
+    /// written to the usage index when the cache is dropped.
+    used: Mutex<BTreeMap<String, u64>>,
+}
+
+impl GlobalCache {
+    /// The cache in the cache directory of the user, if it can be located.
+    pub fn new() -> Option<Self> {
+        let dir = dirs::cache_dir()?.join("cargo-msrv");
+        let dir = Utf8PathBuf::from_path_buf(dir).ok()?;
+
+        Some(Self::at(dir))
+    }
+
+    pub fn at(dir: Utf8PathBuf) -> Self {
+        Self {
+            dir,
+            used: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+
+    /// The directory of a section of the cache, e.g. `crates-index`.
+    pub fn section(&self, section: &str) -> Utf8PathBuf {
+        self.dir.join(section)
+    }
+
+    /// Mark a cached file as used, so it is evicted after the files which were used less recently.
+    pub fn touch(&self, section: &str, name: &str) {
+        let mut used = self
+            .used
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        used.insert(format!("{}/{}", section, name), now());
+    }
+
+    /// The cached files, ordered from the least to the most recently used.
+    ///
+    /// Files which are not in the usage index, for example because they were cached by an older
+    /// version of cargo-msrv, were last used when they were last modified.
+    pub fn entries(&self) -> TResult<Vec<CacheEntry>> {
+        let usage = self.usage();
+        let mut entries = Vec::new();
+
+        if self.dir.is_dir() {
+            collect_entries(&self.dir, &self.dir, &usage, &mut entries)?;
+        }
+
+        entries.sort_by(|lhs, rhs| (lhs.last_used, &lhs.key).cmp(&(rhs.last_used, &rhs.key)));
+
+        Ok(entries)
+    }
+
+    /// Remove the least recently used files, until the combined size of the cached files no longer
+    /// exceeds `max_size` bytes. Returns the evicted files.
+    pub fn evict(&self, max_size: u64) -> TResult<Vec<CacheEntry>> {
+        let entries = self.entries()?;
+        let mut size = entries.iter().map(|entry| entry.bytes).sum::<u64>();
+
+        let mut evicted = Vec::new();
+
+        for entry in entries {
+            if size <= max_size {
+                break;
+            }
+
+            let path = self.dir.join(&entry.key);
+            std::fs::remove_file(&path).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::RemoveFile(path.clone()),
+            })?;
+
+            size -= entry.bytes;
+            evicted.push(entry);
+        }
+
+        if !evicted.is_empty() {
+            let mut usage = self.usage();
+
+            for entry in &evicted {
+                usage.remove(&entry.key);
+            }
+
+            self.write_usage(&usage)?;
+        }
+
+        Ok(evicted)
+    }
+
+    /// The usage index. An index which can't be read is treated like an empty index, so the
+    /// modification times of the files are used instead.
+    fn usage(&self) -> BTreeMap<String, u64> {
+        std::fs::read_to_string(self.dir.join(USAGE_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn write_usage(&self, usage: &BTreeMap<String, u64>) -> TResult<()> {
+        std::fs::create_dir_all(&self.dir).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::CreateDir(self.dir.clone()),
+        })?;
+
+        let staged = self.dir.join(USAGE_FILE_STAGED);
+        let contents = serde_json::to_string(usage).unwrap_or_default();
+
+        std::fs::write(&staged, contents).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::WriteFile(staged.clone()),
+        })?;
+
+        let path = self.dir.join(USAGE_FILE);
+        std::fs::rename(&staged, &path).map_err(|error| {
+            IoError {
+                error,
+                source: IoErrorSource::WriteFile(path),
+            }
+            .into()
+        })
+    }
+
+    /// Add the files used during this run to the usage index.
+    fn flush(&self) -> TResult<()> {
+        let used = std::mem::take(
+            &mut *self
+                .used
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+
+        if used.is_empty() {
+            return Ok(());
+        }
+
+        let mut usage = self.usage();
+        usage.extend(used);
+
+        self.write_usage(&usage)
+    }
+}
+
+impl Drop for GlobalCache {
+    fn drop(&mut self) {
+        // Keeping track of usage is best effort: when it fails, files are evicted based on their
+        // modification time instead.
+        if let Err(error) = self.flush() {
+            info!(%error, "unable to update the usage index of the cache");
+        }
+    }
+}
+
+/// A file in the cache.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CacheEntry {
+    /// The path of the file, relative to the cache directory, e.g. `crates-index/serde`.
+    pub key: String,
+    pub bytes: u64,
+    /// The moment the file was last used, in seconds since the Unix epoch.
+    pub last_used: u64,
+}
+
+impl CacheEntry {
+    /// The section of the cache which holds the file, e.g. `crates-index`.
+    pub fn section(&self) -> &str {
+        self.key.split('/').next().unwrap_or(&self.key)
+    }
+}
+
+fn collect_entries(
+    root: &Utf8Path,
+    dir: &Utf8Path,
+    usage: &BTreeMap<String, u64>,
+    entries: &mut Vec<CacheEntry>,
+) -> TResult<()> {
+    let read_dir = dir.read_dir_utf8().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadDir(dir.to_path_buf()),
+    })?;
+
+    for entry in read_dir {
+        let entry = entry.map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadDir(dir.to_path_buf()),
+        })?;
+
+        let path = entry.path();
+        let metadata = path.symlink_metadata().map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        })?;
+
+        if metadata.is_dir() {
+            collect_entries(root, path, usage, entries)?;
+            continue;
+        }
+
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+
+        let key = relative
+            .components()
+            .map(|component| component.as_str())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if key == USAGE_FILE || key == USAGE_FILE_STAGED {
+            continue;
+        }
+
+        let last_used = usage.get(&key).copied().unwrap_or_else(|| {
+            metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |since| since.as_secs())
+        });
+
+        entries.push(CacheEntry {
+            key,
+            bytes: metadata.len(),
+            last_used,
+        });
+    }
+
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/// Parse a size in bytes, e.g. `500MiB`, `2G` or `1.5 GB`.
+///
+/// The units `K`, `M`, `G` and `T` (and `KiB`, `MiB`, `GiB` and `TiB`) are powers of 1024, while
+/// `KB`, `MB`, `GB` and `TB` are powers of 1000. A number without a unit is a number of bytes.
+pub fn parse_size(input: &str) -> Result<u64, ParseSizeError> {
+    let error = || ParseSizeError {
+        input: input.to_string(),
+    };
+
+    let trimmed = input.trim();
+    let split = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(split);
+
+    let number = number.parse::<f64>().map_err(|_| error())?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "t" | "tib" => 1 << 40,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        _ => return Err(error()),
+    };
+
+    Ok((number * multiplier as f64) as u64)
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("'{input}' is not a valid size, expected a number of bytes, optionally followed by a unit like 'MiB' or 'GB'")]
+pub struct ParseSizeError {
+    input: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_file(cache: &GlobalCache, key: &str, bytes: usize) {
+        let path = cache.dir().join(key);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0; bytes]).unwrap();
+    }
+
+    #[yare::parameterized(
+        bytes = { "512", 512 },
+        bytes_unit = { "512B", 512 },
+        kibibytes = { "4K", 4096 },
+        mebibytes = { "500MiB", 500 * 1024 * 1024 },
+        gibibytes = { "2G", 2 * 1024 * 1024 * 1024 },
+        fractional = { "1.5 GiB", 1536 * 1024 * 1024 },
+        megabytes = { "3MB", 3_000_000 },
+        lowercase = { "2gb", 2_000_000_000 },
+    )]
+    fn parsed_size(input: &str, expected: u64) {
+        assert_eq!(parse_size(input).unwrap(), expected);
+    }
+
+    #[yare::parameterized(
+        empty = { "" },
+        no_number = { "MiB" },
+        unknown_unit = { "5 parsecs" },
+    )]
+    fn invalid_size(input: &str) {
+        assert!(parse_size(input).is_err());
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = GlobalCache::at(Utf8Path::from_path(dir.path()).unwrap().to_path_buf());
+
+        cache_file(&cache, "crates-index/serde", 100);
+        cache_file(&cache, "crates-index/syn", 100);
+        cache_file(&cache, "channel-manifests/channel-rust-1.60.0.toml", 100);
+
+        cache
+            .write_usage(&BTreeMap::from([
+                ("crates-index/serde".to_string(), 30),
+                ("crates-index/syn".to_string(), 10),
+                ("channel-manifests/channel-rust-1.60.0.toml".to_string(), 20),
+            ]))
+            .unwrap();
+
+        let evicted = cache.evict(150).unwrap();
+        let evicted = evicted
+            .iter()
+            .map(|entry| entry.key.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            evicted,
+            vec![
+                "crates-index/syn",
+                "channel-manifests/channel-rust-1.60.0.toml"
+            ]
+        );
+
+        let remaining = cache.entries().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].key, "crates-index/serde");
+        assert_eq!(remaining[0].section(), "crates-index");
+        assert_eq!(cache.usage().len(), 1);
+    }
+
+    #[test]
+    fn used_files_are_kept() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let dir = Utf8Path::from_path(dir.path()).unwrap().to_path_buf();
+
+        {
+            let cache = GlobalCache::at(dir.clone());
+            cache_file(&cache, "crates-index/serde", 100);
+            cache_file(&cache, "crates-index/syn", 100);
+            cache
+                .write_usage(&BTreeMap::from([
+                    ("crates-index/serde".to_string(), 10),
+                    ("crates-index/syn".to_string(), 20),
+                ]))
+                .unwrap();
+
+            cache.touch("crates-index", "serde");
+        }
+
+        let cache = GlobalCache::at(dir);
+        let evicted = cache.evict(100).unwrap();
+
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].key, "crates-index/syn");
+    }
+
+    #[test]
+    fn nothing_to_evict() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = GlobalCache::at(Utf8Path::from_path(dir.path()).unwrap().join("missing"));
+
+        assert!(cache.entries().unwrap().is_empty());
+        assert!(cache.evict(0).unwrap().is_empty());
+    }
+}
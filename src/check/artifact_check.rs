@@ -0,0 +1,32 @@
+use std::time::Instant;
+
+use crate::check::Check;
+use crate::run_artifacts::RunArtifacts;
+use crate::rust::Toolchain;
+use crate::{Outcome, TResult};
+
+/// Stores the output and the duration of each check as an artifact of the run, if artifacts
+/// are to be saved. Otherwise, the checks are passed through as is.
+pub struct ArtifactCheck<'a, C: Check> {
+    inner: C,
+    artifacts: Option<&'a RunArtifacts>,
+}
+
+impl<'a, C: Check> ArtifactCheck<'a, C> {
+    pub fn new(inner: C, artifacts: Option<&'a RunArtifacts>) -> Self {
+        Self { inner, artifacts }
+    }
+}
+
+impl<C: Check> Check for ArtifactCheck<'_, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let start = Instant::now();
+        let outcome = self.inner.check(toolchain)?;
+
+        if let Some(artifacts) = self.artifacts {
+            artifacts.record(&outcome, start.elapsed())?;
+        }
+
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,140 @@
+use crate::check::provided_toolchain_check::{
+    ProvidedToolchainCheck, ProviderCommand, ProviderRun,
+};
+use crate::check::RunCommand;
+use crate::context::{ContainerContext, EnvironmentContext};
+use crate::error::{IoError, IoErrorSource};
+use crate::external_command::container_command::ContainerRunCommand;
+use crate::reporter::event::Method;
+use crate::rust::Toolchain;
+use crate::{Reporter, TResult};
+use camino::Utf8Path;
+use std::process::Command;
+
+/// Checks a crate in a container, where the toolchain is provided by the container image, instead
+/// of being installed by rustup.
+pub type ContainerToolchainCheck<'reporter, 'env, R> =
+    ProvidedToolchainCheck<'reporter, 'env, R, ContainerContext>;
+
+impl ProviderCommand for ContainerContext {
+    fn command(
+        &self,
+        _reporter: &impl Reporter,
+        environment: &EnvironmentContext,
+        check_cmd: &RunCommand,
+        toolchain: &Toolchain,
+    ) -> TResult<ProviderRun> {
+        // The crate is mounted into the container, which requires an absolute path
+        let dir = environment.root();
+        let crate_root = dir.canonicalize_utf8().map_err(|error| IoError {
+            error,
+            source: IoErrorSource::Canonicalize(dir.to_path_buf()),
+        })?;
+
+        let (image, args) = engine_args(self, check_cmd, toolchain, &crate_root);
+
+        let mut command = Command::new(self.engine.program());
+        command.args(&args);
+
+        Ok(ProviderRun::delegated(
+            Method::container_run(self.engine, image, &args, &crate_root),
+            command,
+        ))
+    }
+}
+
+/// The image, and the arguments to the container engine, to run the check command for the given
+/// toolchain.
+fn engine_args(
+    container: &ContainerContext,
+    check_cmd: &RunCommand,
+    toolchain: &Toolchain,
+    crate_root: &Utf8Path,
+) -> (String, Vec<String>) {
+    let image = container.image(toolchain);
+
+    let args = ContainerRunCommand::new(container.engine, &image, crate_root)
+        .user(container.user)
+        .envs(check_cmd.envs_for(toolchain.version()))
+        .into_args(check_cmd.components());
+
+    (image, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::ContainerEngine;
+    use crate::semver;
+
+    #[yare::parameterized(
+        default_image = { "rust:{version}", "rust:1.56.0" },
+        custom_image = { "myregistry/rust:{version}-alpine", "myregistry/rust:1.56.0-alpine" },
+        with_target = { "rust-{target}:{version}", "rust-x86_64-unknown-linux-musl:1.56.0" },
+    )]
+    fn image_from_template(template: &str, expected: &str) {
+        let check_cmd = RunCommand::custom(vec!["cargo".to_string(), "check".to_string()]);
+        let container = ContainerContext {
+            engine: ContainerEngine::Podman,
+            image_template: template.to_string(),
+            user: None,
+        };
+
+        let toolchain = Toolchain::new(
+            semver::Version::new(1, 56, 0),
+            "x86_64-unknown-linux-musl",
+            &[],
+        );
+
+        let (image, args) =
+            engine_args(&container, &check_cmd, &toolchain, Utf8Path::new("/crate"));
+
+        assert_eq!(image, expected);
+        assert!(args.ends_with(&[image, "cargo".to_string(), "check".to_string()]));
+    }
+
+    #[yare::parameterized(
+        git_protocol = { 67, &["--env", "CARGO_REGISTRIES_CRATES_IO_PROTOCOL=git", "rust:1.67.0", "cargo", "check"] },
+        configured_protocol = { 68, &["--workdir", "/workspace", "rust:1.68.0", "cargo", "check"] },
+    )]
+    fn registry_protocol(minor: u64, expected: &[&str]) {
+        let check_cmd = RunCommand::custom(vec!["cargo".to_string(), "check".to_string()]);
+        let container = ContainerContext {
+            engine: ContainerEngine::Docker,
+            image_template: "rust:{version}".to_string(),
+            user: None,
+        };
+
+        let toolchain = Toolchain::new(
+            semver::Version::new(1, minor, 0),
+            "x86_64-unknown-linux-gnu",
+            &[],
+        );
+
+        let (_, args) = engine_args(&container, &check_cmd, &toolchain, Utf8Path::new("/crate"));
+
+        assert!(args.ends_with(&expected.iter().map(|s| s.to_string()).collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn vendored_cargo_home() {
+        let check_cmd = RunCommand::custom(vec!["cargo".to_string(), "check".to_string()])
+            .with_cargo_home(Some(Utf8Path::new("/crate/target/vendor/cargo-home")));
+        let container = ContainerContext {
+            engine: ContainerEngine::Docker,
+            image_template: "rust:{version}".to_string(),
+            user: None,
+        };
+
+        let toolchain = Toolchain::new(
+            semver::Version::new(1, 70, 0),
+            "x86_64-unknown-linux-gnu",
+            &[],
+        );
+
+        let (_, args) = engine_args(&container, &check_cmd, &toolchain, Utf8Path::new("/crate"));
+        let env = args.iter().position(|arg| arg == "--env").unwrap();
+
+        assert_eq!(args[env + 1], "CARGO_HOME=/crate/target/vendor/cargo-home");
+    }
+}
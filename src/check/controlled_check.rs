@@ -0,0 +1,34 @@
+use crate::check::Check;
+use crate::control::Control;
+use crate::reporter::Reporter;
+use crate::rust::Toolchain;
+use crate::{Outcome, TResult};
+
+/// Runs each check under the control channel, if any: the search may be paused before a check,
+/// a running check may be skipped, and the search may be aborted altogether.
+pub struct ControlledCheck<'a, R: Reporter, C: Check> {
+    reporter: &'a R,
+    inner: C,
+    control: Option<&'a Control>,
+}
+
+impl<'a, R: Reporter, C: Check> ControlledCheck<'a, R, C> {
+    pub fn new(reporter: &'a R, inner: C, control: Option<&'a Control>) -> Self {
+        Self {
+            reporter,
+            inner,
+            control,
+        }
+    }
+}
+
+impl<R: Reporter, C: Check> Check for ControlledCheck<'_, R, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        match self.control {
+            Some(control) => control.controlled(toolchain.version(), self.reporter, || {
+                self.inner.check(toolchain)
+            }),
+            None => self.inner.check(toolchain),
+        }
+    }
+}
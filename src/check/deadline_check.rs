@@ -0,0 +1,113 @@
+use crate::check::Check;
+use crate::deadline::Deadline;
+use crate::error::CargoMSRVError;
+use crate::external_command::interrupt::interrupt_running_commands;
+use crate::external_command::process_tree;
+use crate::rust::Toolchain;
+use crate::{Outcome, TResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Stops the search once its time budget expires, see `--max-duration`: no check starts after
+/// the deadline, and the check which is running at the deadline is interrupted, by killing the
+/// processes it spawned.
+pub struct DeadlineCheck<'a, C: Check> {
+    inner: C,
+    deadline: Option<&'a Deadline>,
+}
+
+impl<'a, C: Check> DeadlineCheck<'a, C> {
+    pub fn new(inner: C, deadline: Option<&'a Deadline>) -> Self {
+        Self { inner, deadline }
+    }
+}
+
+impl<C: Check> Check for DeadlineCheck<'_, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let Some(deadline) = self.deadline else {
+            return self.inner.check(toolchain);
+        };
+
+        let exceeded = || CargoMSRVError::MaxDurationExceeded {
+            budget: deadline.budget(),
+        };
+
+        if deadline.is_expired() {
+            return Err(exceeded());
+        }
+
+        let interrupted = AtomicBool::new(false);
+
+        let outcome = thread::scope(|scope| {
+            let (finished, watched) = mpsc::channel::<()>();
+
+            // Interrupts the check, unless it finishes before the deadline
+            let remaining = deadline.remaining();
+            let interrupted = &interrupted;
+            scope.spawn(move || {
+                if let Err(mpsc::RecvTimeoutError::Timeout) = watched.recv_timeout(remaining) {
+                    // The check may have finished right at the deadline
+                    if let Err(mpsc::TryRecvError::Disconnected) = watched.try_recv() {
+                        return;
+                    }
+
+                    interrupted.store(true, Ordering::SeqCst);
+                    interrupt_running_commands();
+                    process_tree::kill_all();
+                }
+            });
+
+            let outcome = self.inner.check(toolchain);
+            drop(finished);
+
+            outcome
+        });
+
+        // The interrupted check may have failed in any way, e.g. since its compiler was killed. A
+        // check which succeeded wasn't affected though: it finished before it was interrupted.
+        let succeeded = matches!(outcome, Ok(Outcome::Success(_)));
+        if interrupted.load(Ordering::SeqCst) && !succeeded {
+            return Err(exceeded());
+        }
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::semver;
+    use std::time::Duration;
+
+    fn toolchain() -> Toolchain {
+        Toolchain::new(semver::Version::new(1, 60, 0), "x", &[])
+    }
+
+    #[test]
+    fn within_budget() {
+        let deadline = Deadline::after(Duration::from_secs(3600));
+        let runner = DeadlineCheck::new(
+            TestRunner::with_ok("x", [&semver::Version::new(1, 60, 0)]),
+            Some(&deadline),
+        );
+
+        assert!(runner.check(&toolchain()).unwrap().is_success());
+    }
+
+    #[test]
+    fn expired() {
+        let deadline = Deadline::after(Duration::ZERO);
+        let runner = DeadlineCheck::new(
+            TestRunner::with_ok("x", [&semver::Version::new(1, 60, 0)]),
+            Some(&deadline),
+        );
+
+        assert!(matches!(
+            runner.check(&toolchain()),
+            Err(CargoMSRVError::MaxDurationExceeded { .. })
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+//! Parsing of the diagnostics in the output of a compatibility [`check`].
+//!
+//! [`check`]: crate::check::Check
+
+use std::fmt;
+
+/// Split the output of rustc and cargo into diagnostics, i.e. the blocks which start with an
+/// `error` or `warning` line. Cargo's status lines and the summaries of the diagnostics, such as
+/// "could not compile", are left out, since they differ regardless of the diagnostics.
+pub(crate) fn diagnostics(output: &str) -> Vec<String> {
+    let mut diagnostics = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in output.lines() {
+        let starts_diagnostic = line.starts_with("error") || line.starts_with("warning");
+        let ends_diagnostic = line.trim().is_empty() || is_cargo_status(line);
+
+        if starts_diagnostic || ends_diagnostic {
+            diagnostics.extend(current.take().map(|lines| lines.join("\n")));
+        }
+
+        if starts_diagnostic {
+            current = Some(vec![line]);
+        } else if let Some(lines) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+
+    diagnostics.extend(current.map(|lines| lines.join("\n")));
+    diagnostics.retain(|diagnostic| !is_summary(diagnostic));
+    diagnostics
+}
+
+/// Status lines of cargo, like `    Checking a v0.1.0`, are indented, and start with a capital,
+/// unlike the lines of a diagnostic.
+fn is_cargo_status(line: &str) -> bool {
+    line.starts_with(' ')
+        && line
+            .trim_start()
+            .starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// A target, like a library or benchmark, which failed to compile.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FailedTarget {
+    pub package: String,
+    pub kind: TargetKind,
+    /// The name of the target, unless it is the library or build script of the package.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl fmt::Display for FailedTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} `{}` of {}", self.kind, name, self.package),
+            None => write!(f, "{} of {}", self.kind, self.package),
+        }
+    }
+}
+
+/// The class of a target which failed to compile.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetKind {
+    Lib,
+    Bin,
+    Example,
+    Test,
+    Bench,
+    BuildScript,
+}
+
+impl fmt::Display for TargetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Lib => "library",
+            Self::Bin => "binary",
+            Self::Example => "example",
+            Self::Test => "test",
+            Self::Bench => "benchmark",
+            Self::BuildScript => "build script",
+        })
+    }
+}
+
+/// The targets which failed to compile, as summarized by cargo, like
+/// ``error: could not compile `a` (bench "speed") due to 1 previous error``. Cargo names the
+/// target since Rust 1.70; the output of older versions yields no targets.
+pub(crate) fn failed_targets(output: &str) -> Vec<FailedTarget> {
+    let mut targets = Vec::new();
+
+    for target in output.lines().filter_map(failed_target) {
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+
+    targets
+}
+
+fn failed_target(line: &str) -> Option<FailedTarget> {
+    let rest = line.strip_prefix("error: could not compile `")?;
+    let (package, rest) = rest.split_once('`')?;
+
+    // E.g. `lib`, `bin "a"`, `lib test`, `bench "speed"` or `build script`
+    let (target, _) = rest.trim_start().strip_prefix('(')?.split_once(')')?;
+
+    let kind = match target.split(' ').next()? {
+        // The unit tests of a library or binary
+        _ if target.ends_with(" test") => TargetKind::Test,
+        "lib" => TargetKind::Lib,
+        "bin" => TargetKind::Bin,
+        "example" => TargetKind::Example,
+        "test" => TargetKind::Test,
+        "bench" => TargetKind::Bench,
+        "build" => TargetKind::BuildScript,
+        _ => return None,
+    };
+
+    Some(FailedTarget {
+        package: package.to_string(),
+        kind,
+        name: target.split('"').nth(1).map(str::to_string),
+    })
+}
+
+fn is_summary(diagnostic: &str) -> bool {
+    diagnostic.starts_with("error: could not compile")
+        || diagnostic.starts_with("error: aborting due to")
+        || (diagnostic.starts_with("warning: `") && diagnostic.contains(" generated "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAILING: &str = r#"    Checking a v0.1.0 (/a)
+error[E0658]: use of unstable library feature 'once_cell'
+ --> src/lib.rs:1:5
+  |
+1 | use std::sync::OnceLock;
+  |     ^^^^^^^^^^^^^^^^^^^
+
+warning: unused variable: `x`
+ --> src/lib.rs:3:9
+
+error: could not compile `a` (lib) due to 1 previous error; 1 warning emitted
+"#;
+
+    #[test]
+    fn split_diagnostics() {
+        let diagnostics = diagnostics(FAILING);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].starts_with("error[E0658]"));
+        assert!(diagnostics[0].ends_with("^^^^^^^^^^^^^^^^^^^"));
+        assert_eq!(
+            diagnostics[1],
+            "warning: unused variable: `x`\n --> src/lib.rs:3:9"
+        );
+    }
+
+    #[yare::parameterized(
+        lib = { "error: could not compile `a` (lib) due to 1 previous error; 1 warning emitted", Some((TargetKind::Lib, None)) },
+        bench = { "error: could not compile `a` (bench \"speed\") due to 2 previous errors", Some((TargetKind::Bench, Some("speed"))) },
+        example = { "error: could not compile `a` (example \"demo\") due to 1 previous error", Some((TargetKind::Example, Some("demo"))) },
+        unit_tests = { "error: could not compile `a` (lib test) due to 1 previous error", Some((TargetKind::Test, None)) },
+        build_script = { "error: could not compile `a` (build script) due to 1 previous error", Some((TargetKind::BuildScript, None)) },
+        before_rust_1_70 = { "error: could not compile `a` due to previous error", None },
+        other_error = { "error[E0658]: use of unstable library feature 'once_cell'", None },
+    )]
+    fn failed(line: &str, expected: Option<(TargetKind, Option<&str>)>) {
+        assert_eq!(
+            failed_target(line),
+            expected.map(|(kind, name)| FailedTarget {
+                package: "a".to_string(),
+                kind,
+                name: name.map(str::to_string),
+            })
+        );
+    }
+
+    #[test]
+    fn failed_targets_once() {
+        let output =
+            format!("{FAILING}error: could not compile `a` (lib) due to 1 previous error\n");
+
+        assert_eq!(
+            failed_targets(&output),
+            vec![FailedTarget {
+                package: "a".to_string(),
+                kind: TargetKind::Lib,
+                name: None,
+            }]
+        );
+    }
+}
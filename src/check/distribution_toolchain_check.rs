@@ -0,0 +1,92 @@
+use crate::check::provided_toolchain_check::{
+    ProvidedToolchainCheck, ProviderCommand, ProviderRun,
+};
+use crate::check::RunCommand;
+use crate::context::EnvironmentContext;
+use crate::reporter::event::Method;
+use crate::rust::distribution_toolchain::DistributionToolchain;
+use crate::rust::Toolchain;
+use crate::{CargoMSRVError, Reporter, TResult};
+use std::process::Command;
+
+/// Checks a crate with a `rustc` and `cargo` pair which is not managed by rustup, for example the
+/// Rust toolchain packaged by a Linux distribution.
+///
+/// The toolchain is used as is: nothing is installed, and rustup is not required.
+pub type DistributionToolchainCheck<'reporter, 'env, R> =
+    ProvidedToolchainCheck<'reporter, 'env, R, DistributionToolchain>;
+
+impl ProviderCommand for DistributionToolchain {
+    fn command(
+        &self,
+        _reporter: &impl Reporter,
+        environment: &EnvironmentContext,
+        check_cmd: &RunCommand,
+        toolchain: &Toolchain,
+    ) -> TResult<ProviderRun> {
+        let cmd = substitute_cargo(self, check_cmd);
+        let dir = environment.root();
+        let rustc = self.rustc();
+
+        let (program, args) =
+            cmd.split_first()
+                .ok_or_else(|| CargoMSRVError::UnableToRunCheck {
+                    command: cmd.join(" "),
+                    cwd: dir.to_path_buf(),
+                })?;
+
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .current_dir(dir)
+            .env("RUSTC", rustc)
+            .env("CARGO", self.cargo())
+            .envs(check_cmd.envs_for(toolchain.version()));
+
+        Ok(ProviderRun::local(
+            Method::distribution_run(&cmd, dir, rustc),
+            command,
+        ))
+    }
+}
+
+/// The check command, where `cargo` is substituted by the `cargo` of the distribution.
+fn substitute_cargo<'a>(
+    distribution: &'a DistributionToolchain,
+    check_cmd: &'a RunCommand,
+) -> Vec<&'a str> {
+    let cargo = distribution.cargo().as_str();
+
+    check_cmd
+        .components()
+        .iter()
+        .enumerate()
+        .map(|(i, component)| match component.as_str() {
+            "cargo" if i == 0 => cargo,
+            component => component,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+    use camino::Utf8PathBuf;
+
+    #[yare::parameterized(
+        cargo = { &["cargo", "check"], &["/usr/bin/cargo-1.63", "check"] },
+        custom = { &["make", "check", "cargo"], &["make", "check", "cargo"] },
+    )]
+    fn substitutes_cargo(command: &[&str], expected: &[&str]) {
+        let distribution = DistributionToolchain::new(
+            Utf8PathBuf::from("/usr/bin/rustc-1.63"),
+            Utf8PathBuf::from("/usr/bin/cargo-1.63"),
+            semver::Version::new(1, 63, 0),
+            "x86_64-unknown-linux-gnu",
+        );
+        let check_cmd = RunCommand::custom(command.iter().map(|s| s.to_string()).collect());
+
+        assert_eq!(substitute_cargo(&distribution, &check_cmd), expected);
+    }
+}
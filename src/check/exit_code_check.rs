@@ -0,0 +1,154 @@
+use crate::check::Check;
+use crate::manifest::exit_codes::{ExitCodeMapping, MappedOutcome};
+use crate::reporter::event::{SearchControl, Warning};
+use crate::reporter::Reporter;
+use crate::rust::Toolchain;
+use crate::{CargoMSRVError, Outcome, TResult};
+
+/// Maps the exit code of each check to the outcome configured in the `exit-codes` table, see
+/// [`crate::manifest::exit_codes`]. A check of which the exit code is not configured, or unknown,
+/// keeps its outcome.
+pub struct ExitCodeCheck<'a, R: Reporter, C: Check> {
+    reporter: &'a R,
+    inner: C,
+    mapping: &'a ExitCodeMapping,
+}
+
+impl<'a, R: Reporter, C: Check> ExitCodeCheck<'a, R, C> {
+    pub fn new(reporter: &'a R, inner: C, mapping: &'a ExitCodeMapping) -> Self {
+        Self {
+            reporter,
+            inner,
+            mapping,
+        }
+    }
+}
+
+impl<R: Reporter, C: Check> Check for ExitCodeCheck<'_, R, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let outcome = self.inner.check(toolchain)?;
+
+        let Some(code) = outcome.exit_code() else {
+            return Ok(outcome);
+        };
+        let Some(mapped) = self.mapping.outcome(code) else {
+            return Ok(outcome);
+        };
+
+        let version = toolchain.version();
+
+        match mapped {
+            MappedOutcome::Pass if !outcome.is_success() => {
+                self.reporter
+                    .report_event(Warning::exit_code_mapped(version, code, mapped))?;
+
+                Ok(Outcome::new_success_with_output(
+                    toolchain.to_owned(),
+                    outcome.output().to_string(),
+                ))
+            }
+            MappedOutcome::Fail if outcome.is_success() => {
+                self.reporter
+                    .report_event(Warning::exit_code_mapped(version, code, mapped))?;
+
+                Ok(
+                    Outcome::new_failure(toolchain.to_owned(), outcome.output().to_string())
+                        .with_exit_code(Some(code)),
+                )
+            }
+            MappedOutcome::Pass | MappedOutcome::Fail => Ok(outcome),
+            MappedOutcome::Inconclusive => Err(CargoMSRVError::CheckInconclusive {
+                version: version.clone(),
+                exit_code: code,
+            }),
+            MappedOutcome::Skip => {
+                self.reporter.report_event(SearchControl::Skipped {
+                    version: version.clone(),
+                })?;
+                self.reporter.report_event(Warning::version_skipped(
+                    version,
+                    &format!("it exited with code {}", code),
+                ))?;
+
+                Err(CargoMSRVError::CheckSkipped {
+                    version: version.clone(),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use crate::semver;
+
+    /// A check which fails with the given exit code, or passes if it is `0`.
+    struct ExitingCheck(i32);
+
+    impl Check for ExitingCheck {
+        fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+            match self.0 {
+                0 => Ok(Outcome::new_success(toolchain.to_owned())),
+                code => Ok(Outcome::new_failure(toolchain.to_owned(), String::new())
+                    .with_exit_code(Some(code))),
+            }
+        }
+    }
+
+    fn mapping() -> ExitCodeMapping {
+        [
+            (0, MappedOutcome::Fail),
+            (2, MappedOutcome::Pass),
+            (3, MappedOutcome::Skip),
+            (4, MappedOutcome::Inconclusive),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[yare::parameterized(
+        mapped_fail = { 0, false },
+        mapped_pass = { 2, true },
+        unmapped = { 101, false },
+    )]
+    fn mapped(code: i32, expected: bool) {
+        let reporter = TestReporterWrapper::default();
+        let mapping = mapping();
+        let runner = ExitCodeCheck::new(reporter.get(), ExitingCheck(code), &mapping);
+
+        let toolchain = Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]);
+        let outcome = runner.check(&toolchain).unwrap();
+
+        assert_eq!(outcome.is_success(), expected);
+    }
+
+    #[test]
+    fn skip() {
+        let reporter = TestReporterWrapper::default();
+        let mapping = mapping();
+        let runner = ExitCodeCheck::new(reporter.get(), ExitingCheck(3), &mapping);
+
+        let toolchain = Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]);
+
+        assert!(matches!(
+            runner.check(&toolchain),
+            Err(CargoMSRVError::CheckSkipped { .. })
+        ));
+    }
+
+    #[test]
+    fn inconclusive() {
+        let reporter = TestReporterWrapper::default();
+        let mapping = mapping();
+        let runner = ExitCodeCheck::new(reporter.get(), ExitingCheck(4), &mapping);
+
+        let toolchain = Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]);
+
+        assert!(matches!(
+            runner.check(&toolchain),
+            Err(CargoMSRVError::CheckInconclusive { exit_code: 4, .. })
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::check::Check;
+use crate::context::EnvironmentContext;
+use crate::reporter::event::FuzzProjectResult;
+use crate::reporter::Reporter;
+use crate::rust::Toolchain;
+use crate::{Outcome, TResult};
+use camino::Utf8PathBuf;
+
+/// Checks the cargo-fuzz projects of the crate, like `fuzz/`, once the crate itself is
+/// compatible with a toolchain. The result of each project is reported separately; the toolchain
+/// is only compatible if every project is.
+pub struct FuzzCheck<'a, R: Reporter, C: Check> {
+    reporter: &'a R,
+    inner: C,
+    /// The manifest of each project, and its check.
+    projects: Vec<(Utf8PathBuf, C)>,
+}
+
+impl<'a, R: Reporter, C: Check> FuzzCheck<'a, R, C> {
+    pub fn new(reporter: &'a R, inner: C, projects: Vec<(Utf8PathBuf, C)>) -> Self {
+        Self {
+            reporter,
+            inner,
+            projects,
+        }
+    }
+
+    /// The check of the crate, and of each of the given cargo-fuzz projects, as created by
+    /// `new_check` for the environment of each.
+    pub fn for_projects<'env>(
+        reporter: &'a R,
+        environment: &'env EnvironmentContext,
+        projects: &'env [EnvironmentContext],
+        new_check: impl Fn(&'env EnvironmentContext) -> C,
+    ) -> Self {
+        let projects = projects
+            .iter()
+            .map(|project| (project.manifest(), new_check(project)))
+            .collect();
+
+        Self::new(reporter, new_check(environment), projects)
+    }
+}
+
+impl<R: Reporter, C: Check> Check for FuzzCheck<'_, R, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let outcome = self.inner.check(toolchain)?;
+
+        if !outcome.is_success() {
+            return Ok(outcome);
+        }
+
+        let mut first_failure = None;
+
+        for (manifest_path, check) in &self.projects {
+            let project_outcome = check.check(toolchain)?;
+
+            self.reporter.report_event(FuzzProjectResult::new(
+                toolchain.to_owned(),
+                manifest_path.clone(),
+                project_outcome.is_success(),
+            ))?;
+
+            if !project_outcome.is_success() && first_failure.is_none() {
+                first_failure = Some(project_outcome);
+            }
+        }
+
+        Ok(first_failure.unwrap_or(outcome))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use crate::semver::Version;
+    use crate::Event;
+
+    #[test]
+    fn fuzz_projects_checked_when_crate_is_compatible() {
+        let reporter = TestReporterWrapper::default();
+        let (v60, v61, v62) = (
+            Version::new(1, 60, 0),
+            Version::new(1, 61, 0),
+            Version::new(1, 62, 0),
+        );
+        let manifest_path = Utf8PathBuf::from("/crate/fuzz/Cargo.toml");
+
+        let check = FuzzCheck::new(
+            reporter.get(),
+            TestRunner::with_ok("x", [&v61, &v62]),
+            vec![(manifest_path.clone(), TestRunner::with_ok("x", [&v62]))],
+        );
+
+        let outcomes = [&v60, &v61, &v62]
+            .map(|version| {
+                check
+                    .check(&Toolchain::new(version.clone(), "x", &[]))
+                    .unwrap()
+            })
+            .map(|outcome| outcome.is_success());
+
+        assert_eq!(outcomes, [false, false, true]);
+
+        // The fuzz project isn't checked with Rust 1.60, since the crate itself is incompatible
+        assert_eq!(
+            check.projects[0].1.checked(),
+            vec![v61.clone(), v62.clone()]
+        );
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            [(v61, false), (v62, true)]
+                .map(
+                    |(version, is_compatible)| Event::unscoped(Message::FuzzProjectResult(
+                        FuzzProjectResult::new(
+                            Toolchain::new(version, "x", &[]),
+                            manifest_path.clone(),
+                            is_compatible
+                        )
+                    ))
+                )
+                .to_vec()
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use crate::check::Check;
+use crate::context::LockfileStrategy;
+use crate::error::{CargoMSRVError, IoError, IoErrorSource};
+use crate::lockfile::{self, LockfileHandler};
+use crate::reporter::event::LockfileIncompatible;
+use crate::reporter::Reporter;
+use crate::rust::Toolchain;
+use crate::{Outcome, TResult};
+use camino::Utf8PathBuf;
+
+/// Reports each toolchain of which the cargo can't read the lockfile, and handles the lockfile
+/// according to the strategy, if any, while the toolchain is checked.
+pub struct LockfileCheck<'a, R: Reporter, C: Check> {
+    reporter: &'a R,
+    inner: C,
+    lockfile: Option<Utf8PathBuf>,
+    strategy: Option<LockfileStrategy>,
+}
+
+impl<'a, R: Reporter, C: Check> LockfileCheck<'a, R, C> {
+    /// Without a lockfile, like when the lockfile is ignored, each toolchain is checked as is.
+    pub fn new(
+        reporter: &'a R,
+        inner: C,
+        lockfile: Option<Utf8PathBuf>,
+        strategy: Option<LockfileStrategy>,
+    ) -> Self {
+        Self {
+            reporter,
+            inner,
+            lockfile,
+            strategy,
+        }
+    }
+
+    fn check_downgraded(
+        &self,
+        toolchain: &Toolchain,
+        path: &Utf8PathBuf,
+        contents: &str,
+    ) -> TResult<Outcome> {
+        let write = |contents: &str| {
+            std::fs::write(path, contents).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::WriteFile(path.clone()),
+            })
+        };
+
+        write(&lockfile::downgrade(contents, toolchain.version()))?;
+        let outcome = self.inner.check(toolchain);
+
+        // Restored even if the check failed
+        write(contents)?;
+        outcome
+    }
+}
+
+impl<R: Reporter, C: Check> Check for LockfileCheck<'_, R, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let Some(path) = self.lockfile.as_ref().filter(|path| path.is_file()) else {
+            return self.inner.check(toolchain);
+        };
+
+        let contents = std::fs::read_to_string(path).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadFile(path.clone()),
+        })?;
+
+        let incompatible = lockfile::lockfile_version(&contents).and_then(|version| {
+            lockfile::readable_since(version)
+                .filter(|since| toolchain.version() < since)
+                .map(|since| (version, since))
+        });
+
+        let Some((lockfile_version, readable_since)) = incompatible else {
+            return self.inner.check(toolchain);
+        };
+
+        self.reporter.report_event(LockfileIncompatible::new(
+            toolchain.clone(),
+            lockfile_version,
+            readable_since.clone(),
+            self.strategy,
+        ))?;
+
+        match self.strategy {
+            None => self.inner.check(toolchain),
+            Some(LockfileStrategy::Fail) => Err(CargoMSRVError::LockfileIncompatible {
+                version: toolchain.version().clone(),
+                lockfile_version,
+                readable_since,
+            }),
+            Some(LockfileStrategy::Regenerate) => {
+                let handle = LockfileHandler::new(path).move_lockfile()?;
+                let outcome = self.inner.check(toolchain);
+
+                // The lockfile which was generated by the check is replaced by the original
+                handle.move_lockfile_back()?;
+                outcome
+            }
+            Some(LockfileStrategy::Downgrade) => self.check_downgraded(toolchain, path, &contents),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use crate::semver;
+
+    const LOCKFILE: &str = "version = 4\n\n[[package]]\nname = \"a\"\nversion = \"0.1.0\"\n";
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    fn lockfile(dir: &assert_fs::TempDir) -> Utf8PathBuf {
+        let path = Utf8PathBuf::from_path_buf(dir.path().join("Cargo.lock")).unwrap();
+        std::fs::write(&path, LOCKFILE).unwrap();
+        path
+    }
+
+    #[yare::parameterized(
+        report_only = { None },
+        regenerate = { Some(LockfileStrategy::Regenerate) },
+        downgrade = { Some(LockfileStrategy::Downgrade) },
+    )]
+    fn reported_and_restored(strategy: Option<LockfileStrategy>) {
+        let reporter = TestReporterWrapper::default();
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = lockfile(&dir);
+        let versions = [
+            semver::Version::new(1, 70, 0),
+            semver::Version::new(1, 78, 0),
+        ];
+
+        let check = LockfileCheck::new(
+            reporter.get(),
+            TestRunner::with_ok("x", &versions),
+            Some(path.clone()),
+            strategy,
+        );
+        assert!(check.check(&toolchain(78)).unwrap().is_success());
+        assert!(check.check(&toolchain(70)).unwrap().is_success());
+
+        let reported = reporter
+            .wait_for_events()
+            .into_iter()
+            .filter(|event| matches!(event.message(), Message::LockfileIncompatible(_)))
+            .count();
+
+        assert_eq!(reported, 1);
+        assert_eq!(std::fs::read_to_string(path).unwrap(), LOCKFILE);
+    }
+
+    #[test]
+    fn fail() {
+        let reporter = TestReporterWrapper::default();
+        let dir = assert_fs::TempDir::new().unwrap();
+        let versions = [semver::Version::new(1, 70, 0)];
+
+        let check = LockfileCheck::new(
+            reporter.get(),
+            TestRunner::with_ok("x", &versions),
+            Some(lockfile(&dir)),
+            Some(LockfileStrategy::Fail),
+        );
+
+        assert!(matches!(
+            check.check(&toolchain(70)),
+            Err(CargoMSRVError::LockfileIncompatible {
+                lockfile_version: 4,
+                ..
+            })
+        ));
+    }
+}
@@ -1,11 +1,45 @@
 use crate::rust::Toolchain;
 
+mod artifact_check;
+mod container_toolchain_check;
+mod controlled_check;
+mod deadline_check;
+pub(crate) mod diagnostics;
+mod distribution_toolchain_check;
+mod exit_code_check;
+mod fuzz_check;
+mod lockfile_check;
+mod nix_toolchain_check;
+mod predicate_check;
+mod provided_toolchain_check;
+mod provider_check;
+mod remote_toolchain_check;
+mod resumed_check;
 mod rustup_toolchain_check;
+mod signal_check;
+mod tarball_toolchain_check;
 #[cfg(test)]
 mod testing;
 
 use crate::{Outcome, TResult};
+pub use artifact_check::ArtifactCheck;
+pub use container_toolchain_check::ContainerToolchainCheck;
+pub use controlled_check::ControlledCheck;
+pub use deadline_check::DeadlineCheck;
+pub use distribution_toolchain_check::DistributionToolchainCheck;
+pub use exit_code_check::ExitCodeCheck;
+pub use fuzz_check::FuzzCheck;
+pub use lockfile_check::LockfileCheck;
+pub use nix_toolchain_check::NixToolchainCheck;
+pub use predicate_check::PredicateCheck;
+pub use provided_toolchain_check::ProvidedToolchainCheck;
+pub use provider_check::ProviderCheck;
+pub use remote_toolchain_check::RemoteToolchainCheck;
+pub use resumed_check::ResumedCheck;
+pub(crate) use rustup_toolchain_check::estimate_units;
 pub use rustup_toolchain_check::{RunCommand, RustupToolchainCheck};
+pub use signal_check::SignalCheck;
+pub use tarball_toolchain_check::TarballToolchainCheck;
 
 #[cfg(test)]
 pub use testing::TestRunner;
@@ -13,3 +47,9 @@ pub use testing::TestRunner;
 pub trait Check {
     fn check(&self, toolchain: &Toolchain) -> TResult<Outcome>;
 }
+
+impl<C: Check + ?Sized> Check for &C {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        (**self).check(toolchain)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::check::provided_toolchain_check::{
+    ProvidedToolchainCheck, ProviderCommand, ProviderRun,
+};
+use crate::check::RunCommand;
+use crate::context::{EnvironmentContext, NixContext, NixToolchainSource};
+use crate::external_command::nix_command::NixShellCommand;
+use crate::reporter::event::Method;
+use crate::rust::Toolchain;
+use crate::{Reporter, TResult};
+use std::process::Command;
+
+/// Checks a crate in a `nix shell`, where the toolchain is materialized by nix, instead of being
+/// installed by rustup.
+pub type NixToolchainCheck<'reporter, 'env, R> =
+    ProvidedToolchainCheck<'reporter, 'env, R, NixContext>;
+
+impl ProviderCommand for NixContext {
+    fn command(
+        &self,
+        _reporter: &impl Reporter,
+        environment: &EnvironmentContext,
+        check_cmd: &RunCommand,
+        toolchain: &Toolchain,
+    ) -> TResult<ProviderRun> {
+        let dir = environment.root();
+        let (_, args) = nix_args(self, check_cmd, toolchain);
+
+        let mut command = Command::new("nix");
+        command
+            .args(&args)
+            .envs(check_cmd.envs_for(toolchain.version()))
+            .current_dir(dir);
+
+        // The cargo in the nix shell inherits the jobserver from nix
+        Ok(ProviderRun::local(Method::nix_shell(&args, dir), command))
+    }
+}
+
+/// The source of the toolchain, and the arguments to `nix`, to run the check command for the
+/// given toolchain.
+fn nix_args(
+    nix: &NixContext,
+    check_cmd: &RunCommand,
+    toolchain: &Toolchain,
+) -> (NixToolchainSource, Vec<String>) {
+    let source = nix.source(toolchain);
+    let args = NixShellCommand::new(&source).into_args(check_cmd.components());
+
+    (source, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    #[yare::parameterized(
+        expression = {
+            NixToolchainSource::Expression(r#"pkgs.rust-bin.stable."{version}".minimal"#.to_string()),
+            NixToolchainSource::Expression(r#"pkgs.rust-bin.stable."1.56.0".minimal"#.to_string()),
+        },
+        installable = {
+            NixToolchainSource::Installable("github:owner/repo#rust-{version}-{target}".to_string()),
+            NixToolchainSource::Installable("github:owner/repo#rust-1.56.0-x86_64-unknown-linux-gnu".to_string()),
+        },
+    )]
+    fn source_from_template(template: NixToolchainSource, expected: NixToolchainSource) {
+        let check_cmd = RunCommand::custom(vec!["cargo".to_string(), "check".to_string()]);
+        let nix = NixContext {
+            source_template: template,
+        };
+
+        let toolchain = Toolchain::new(
+            semver::Version::new(1, 56, 0),
+            "x86_64-unknown-linux-gnu",
+            &[],
+        );
+
+        let (source, args) = nix_args(&nix, &check_cmd, &toolchain);
+
+        assert_eq!(source, expected);
+        assert!(args.ends_with(&[
+            "--command".to_string(),
+            "cargo".to_string(),
+            "check".to_string()
+        ]));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::check::Check;
+use crate::error::{IoError, IoErrorSource};
+use crate::external_command::process_tree;
+use crate::reporter::event::{SearchControl, Warning};
+use crate::reporter::Reporter;
+use crate::rust::Toolchain;
+use crate::{CargoMSRVError, Outcome, TResult};
+use camino::Utf8Path;
+use std::process::{Command, ExitStatus, Stdio};
+
+/// The exit code with which the predicate skips a Rust version, like with `git bisect run`.
+const SKIP_EXIT_CODE: i32 = 125;
+
+/// Lets a user-supplied script decide whether each check passes, fails or is skipped, see
+/// `--predicate`. The script is run after the check, with the Rust version, the target, the exit
+/// code of the check, and a file with the output of the check, and decides by its exit code:
+/// `0` passes the check, `125` skips it, and any other code up to `127` fails it.
+pub struct PredicateCheck<'a, R: Reporter, C: Check> {
+    reporter: &'a R,
+    inner: C,
+    predicate: Option<&'a Utf8Path>,
+}
+
+impl<'a, R: Reporter, C: Check> PredicateCheck<'a, R, C> {
+    pub fn new(reporter: &'a R, inner: C, predicate: Option<&'a Utf8Path>) -> Self {
+        Self {
+            reporter,
+            inner,
+            predicate,
+        }
+    }
+}
+
+impl<R: Reporter, C: Check> Check for PredicateCheck<'_, R, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let outcome = self.inner.check(toolchain)?;
+
+        let Some(predicate) = self.predicate else {
+            return Ok(outcome);
+        };
+
+        let version = toolchain.version();
+
+        match run_predicate(predicate, toolchain, &outcome)? {
+            Verdict::Pass if !outcome.is_success() => {
+                self.reporter
+                    .report_event(Warning::predicate_override(predicate, version, true))?;
+
+                Ok(Outcome::new_success_with_output(
+                    toolchain.to_owned(),
+                    outcome.output().to_string(),
+                ))
+            }
+            Verdict::Fail if outcome.is_success() => {
+                self.reporter
+                    .report_event(Warning::predicate_override(predicate, version, false))?;
+
+                Ok(Outcome::new_failure(
+                    toolchain.to_owned(),
+                    outcome.output().to_string(),
+                ))
+            }
+            Verdict::Skip => {
+                self.reporter.report_event(SearchControl::Skipped {
+                    version: version.clone(),
+                })?;
+                self.reporter
+                    .report_event(Warning::version_skipped(version, "by the predicate"))?;
+
+                Err(CargoMSRVError::CheckSkipped {
+                    version: version.clone(),
+                })
+            }
+            _ => Ok(outcome),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Verdict {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl Verdict {
+    /// The verdict of the predicate which exited with the given status, if it is one.
+    fn from_status(status: ExitStatus) -> Option<Self> {
+        match status.code()? {
+            0 => Some(Self::Pass),
+            SKIP_EXIT_CODE => Some(Self::Skip),
+            1..=127 => Some(Self::Fail),
+            _ => None,
+        }
+    }
+}
+
+/// Run the predicate for the outcome of the check of the toolchain. The output of the check is
+/// written to a temporary file, since it may be too large for the environment.
+fn run_predicate(
+    predicate: &Utf8Path,
+    toolchain: &Toolchain,
+    outcome: &Outcome,
+) -> TResult<Verdict> {
+    let mut output_file = tempfile::NamedTempFile::new().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::CreateTempFile,
+    })?;
+    std::io::Write::write_all(&mut output_file, outcome.output().as_bytes()).map_err(|error| {
+        IoError {
+            error,
+            source: IoErrorSource::CreateTempFile,
+        }
+    })?;
+
+    let exit_code = outcome
+        .exit_code()
+        .map(|code| code.to_string())
+        .unwrap_or_default();
+
+    let mut command = Command::new(predicate.as_std_path());
+    command
+        .arg(toolchain.version().to_string())
+        .env("CARGO_MSRV_VERSION", toolchain.version().to_string())
+        .env("CARGO_MSRV_TARGET", toolchain.target())
+        .env("CARGO_MSRV_CHECK_EXIT_CODE", exit_code)
+        .env("CARGO_MSRV_CHECK_OUTPUT", output_file.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let output = process_tree::output(&mut command).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::SpawnProcess(predicate.as_os_str().to_owned()),
+    })?;
+
+    info!(
+        %predicate,
+        version = %toolchain.version(),
+        status = %output.status,
+        stdout = %String::from_utf8_lossy(&output.stdout),
+        stderr = %String::from_utf8_lossy(&output.stderr),
+        "ran the predicate"
+    );
+
+    Verdict::from_status(output.status).ok_or_else(|| CargoMSRVError::InvalidPredicateVerdict {
+        predicate: predicate.to_path_buf(),
+        version: toolchain.version().clone(),
+        status: output.status.to_string(),
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::reporter::TestReporterWrapper;
+    use crate::semver;
+    use camino::Utf8PathBuf;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// A predicate script with the given body, in the given directory.
+    fn predicate(dir: &assert_fs::TempDir, body: &str) -> Utf8PathBuf {
+        let path = Utf8Path::from_path(dir.path())
+            .unwrap()
+            .join("predicate.sh");
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    #[yare::parameterized(
+        pass = { "exit 0", 60, true },
+        fail = { "exit 1", 70, false },
+        keeps_success = { r#"[ "$CARGO_MSRV_CHECK_EXIT_CODE" = 0 ] || exit 1"#, 70, true },
+        keeps_failure = { r#"[ "$CARGO_MSRV_CHECK_EXIT_CODE" = 0 ] || exit 1"#, 60, false },
+        by_version = { r#"[ "$1" = "1.60.0" ] && exit 0 || exit 1"#, 60, true },
+    )]
+    fn verdict(body: &str, minor: u64, expected: bool) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let predicate = predicate(&dir, body);
+        let reporter = TestReporterWrapper::default();
+
+        let runner = PredicateCheck::new(
+            reporter.get(),
+            TestRunner::with_ok("x", [&semver::Version::new(1, 70, 0)]),
+            Some(&predicate),
+        );
+
+        let outcome = runner.check(&toolchain(minor)).unwrap();
+
+        assert_eq!(outcome.is_success(), expected);
+    }
+
+    #[test]
+    fn skip() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let predicate = predicate(&dir, "exit 125");
+        let reporter = TestReporterWrapper::default();
+
+        let runner = PredicateCheck::new(
+            reporter.get(),
+            TestRunner::with_ok("x", [&semver::Version::new(1, 70, 0)]),
+            Some(&predicate),
+        );
+
+        assert!(matches!(
+            runner.check(&toolchain(70)),
+            Err(CargoMSRVError::CheckSkipped { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_verdict() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let predicate = predicate(&dir, "exit 200");
+        let reporter = TestReporterWrapper::default();
+
+        let runner = PredicateCheck::new(
+            reporter.get(),
+            TestRunner::with_ok("x", [&semver::Version::new(1, 70, 0)]),
+            Some(&predicate),
+        );
+
+        assert!(matches!(
+            runner.check(&toolchain(70)),
+            Err(CargoMSRVError::InvalidPredicateVerdict { .. })
+        ));
+    }
+}
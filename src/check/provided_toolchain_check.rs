@@ -0,0 +1,209 @@
+use crate::check::rustup_toolchain_check::{
+    create_lockfile_handle, memory_limit_exceeded, remove_lockfile, report_feature_set_results,
+    report_outcome,
+};
+use crate::check::{Check, RunCommand};
+use crate::context::EnvironmentContext;
+use crate::error::{IoError, IoErrorSource};
+use crate::external_command::process_tree;
+use crate::jobserver;
+use crate::reporter::event::{CheckMethod, CheckToolchain, Method};
+use crate::rust::Toolchain;
+use crate::stabilization::StabilizationIndex;
+use crate::{CargoMSRVError, Outcome, Reporter, TResult};
+use std::fmt;
+use std::fmt::Formatter;
+use std::process::{Command, Stdio};
+
+/// Checks a crate with a toolchain which is provided by something other than rustup, for example
+/// a container image, or a nix shell.
+///
+/// The lockfile, the feedback and the outcome of a check are handled alike for each provider:
+/// the provider only builds the command which runs the check, see [`ProviderCommand`].
+pub struct ProvidedToolchainCheck<'reporter, 'env, R: Reporter, P> {
+    reporter: &'reporter R,
+    ignore_lockfile: bool,
+    no_check_feedback: bool,
+    environment: &'env EnvironmentContext,
+    check_cmd: RunCommand,
+    provider: P,
+    stabilizations: StabilizationIndex,
+}
+
+/// Builds the command which checks a crate, with a toolchain of the provider.
+pub trait ProviderCommand: fmt::Debug {
+    /// The command which checks the crate of the `environment` with the given toolchain. It's
+    /// built after the lockfile was moved, if the lockfile is ignored.
+    fn command(
+        &self,
+        reporter: &impl Reporter,
+        environment: &EnvironmentContext,
+        check_cmd: &RunCommand,
+        toolchain: &Toolchain,
+    ) -> TResult<ProviderRun>;
+}
+
+/// A check command of a toolchain provider, with the method by which it's reported.
+pub struct ProviderRun {
+    method: Method,
+    command: Command,
+    local: bool,
+}
+
+impl ProviderRun {
+    /// A check command which runs on this machine: it takes a job of the jobserver, and its
+    /// processes are limited by the resource limits of the check.
+    pub fn local(method: Method, command: Command) -> Self {
+        Self {
+            method,
+            command,
+            local: true,
+        }
+    }
+
+    /// A check command which delegates the check elsewhere, for example to a container engine,
+    /// where the jobserver and the resource limits don't apply.
+    pub fn delegated(method: Method, command: Command) -> Self {
+        Self {
+            method,
+            command,
+            local: false,
+        }
+    }
+}
+
+impl<'reporter, 'env, R: Reporter, P> ProvidedToolchainCheck<'reporter, 'env, R, P> {
+    pub fn new(
+        reporter: &'reporter R,
+        ignore_lockfile: bool,
+        no_check_feedback: bool,
+        environment: &'env EnvironmentContext,
+        run_command: RunCommand,
+        provider: P,
+    ) -> Self {
+        Self {
+            reporter,
+            ignore_lockfile,
+            no_check_feedback,
+            environment,
+            check_cmd: run_command,
+            provider,
+            stabilizations: StabilizationIndex::load(),
+        }
+    }
+}
+
+impl<R: Reporter, P: ProviderCommand> ProvidedToolchainCheck<'_, '_, R, P> {
+    #[instrument(skip_all)]
+    fn run_check_command(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let ProviderRun {
+            method,
+            mut command,
+            local,
+        } = self
+            .provider
+            .command(self.reporter, self.environment, &self.check_cmd, toolchain)?;
+
+        self.reporter
+            .report_event(CheckMethod::new(toolchain.to_owned(), method))?;
+
+        let unable_to_run = || CargoMSRVError::UnableToRunCheck {
+            command: self.check_cmd.components().join(" "),
+            cwd: self.environment.root().to_path_buf(),
+        };
+
+        command.stdout(Stdio::null()).stderr(Stdio::piped());
+
+        let output = if local {
+            let _token = jobserver::share(&mut command).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::AcquireJobToken,
+            })?;
+            let limits = self.check_cmd.limits();
+            let limited =
+                process_tree::limited_output(&mut command, limits).map_err(|_| unable_to_run())?;
+
+            if limited.exceeded_memory_limit {
+                return Err(memory_limit_exceeded(self.reporter, toolchain, limits)?);
+            }
+
+            limited.output
+        } else {
+            process_tree::output(&mut command).map_err(|_| unable_to_run())?
+        };
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        if self.check_cmd.is_cargo_hack() {
+            report_feature_set_results(self.reporter, toolchain, &stderr)?;
+        }
+
+        if output.status.success() {
+            Ok(Outcome::new_success_with_output(
+                toolchain.to_owned(),
+                stderr.into_owned(),
+            ))
+        } else {
+            info!(
+                ?toolchain,
+                provider = ?self.provider,
+                stderr = stderr.as_ref(),
+                "try_building run failed"
+            );
+
+            Ok(
+                Outcome::new_failure(toolchain.to_owned(), stderr.into_owned())
+                    .with_exit_code(output.status.code()),
+            )
+        }
+    }
+}
+
+impl<R: Reporter, P: ProviderCommand> Check for ProvidedToolchainCheck<'_, '_, R, P> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        self.reporter
+            .run_scoped_event(CheckToolchain::new(toolchain.to_owned()), || {
+                info!(ignore_lockfile_enabled = self.ignore_lockfile);
+
+                // temporarily move the lockfile if the user opted to ignore it, and it exists
+                let handle_wrap = create_lockfile_handle(self.ignore_lockfile, self.environment)
+                    .map(|handle| handle.move_lockfile())
+                    .transpose()?;
+
+                if handle_wrap.is_some() {
+                    remove_lockfile(&self.environment.lock())?;
+                }
+
+                let outcome = self.run_check_command(toolchain);
+
+                // move the lockfile back, also when the check failed to run
+                if let Some(handle) = handle_wrap {
+                    handle.move_lockfile_back()?;
+                }
+
+                let outcome = outcome?;
+
+                // report outcome to UI
+                report_outcome(
+                    self.reporter,
+                    &outcome,
+                    self.no_check_feedback,
+                    &self.stabilizations,
+                )?;
+
+                Ok(outcome)
+            })
+    }
+}
+
+impl<R: Reporter, P: fmt::Debug> fmt::Debug for ProvidedToolchainCheck<'_, '_, R, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProvidedToolchainCheck")
+            .field("ignore_lockfile", &self.ignore_lockfile)
+            .field("no_check_feedback", &self.no_check_feedback)
+            .field("environment", &self.environment)
+            .field("check_cmd", &self.check_cmd)
+            .field("provider", &self.provider)
+            .finish()
+    }
+}
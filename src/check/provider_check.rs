@@ -0,0 +1,108 @@
+use crate::check::{
+    Check, ContainerToolchainCheck, DistributionToolchainCheck, NixToolchainCheck,
+    RemoteToolchainCheck, RunCommand, RustupToolchainCheck, TarballToolchainCheck,
+};
+use crate::context::{EnvironmentContext, ToolchainProvider};
+use crate::rust::Toolchain;
+use crate::{Outcome, Reporter, TResult};
+
+/// The check for the configured [`ToolchainProvider`].
+#[derive(Debug)]
+pub enum ProviderCheck<'reporter, 'env, R: Reporter> {
+    Rustup(RustupToolchainCheck<'reporter, 'env, R>),
+    Distribution(DistributionToolchainCheck<'reporter, 'env, R>),
+    Container(ContainerToolchainCheck<'reporter, 'env, R>),
+    Nix(NixToolchainCheck<'reporter, 'env, R>),
+    Remote(RemoteToolchainCheck<'reporter, 'env, R>),
+    Tarballs(TarballToolchainCheck<'reporter, 'env, R>),
+}
+
+impl<'reporter, 'env, R: Reporter> ProviderCheck<'reporter, 'env, R> {
+    pub fn new(
+        reporter: &'reporter R,
+        ignore_lockfile: bool,
+        no_check_feedback: bool,
+        environment: &'env EnvironmentContext,
+        run_command: RunCommand,
+        provider: ToolchainProvider,
+    ) -> Self {
+        match provider {
+            ToolchainProvider::Rustup => Self::Rustup(RustupToolchainCheck::new(
+                reporter,
+                ignore_lockfile,
+                no_check_feedback,
+                environment,
+                run_command,
+            )),
+            ToolchainProvider::Distribution(distribution) => {
+                Self::Distribution(DistributionToolchainCheck::new(
+                    reporter,
+                    ignore_lockfile,
+                    no_check_feedback,
+                    environment,
+                    run_command,
+                    distribution,
+                ))
+            }
+            ToolchainProvider::Container(container) => {
+                Self::Container(ContainerToolchainCheck::new(
+                    reporter,
+                    ignore_lockfile,
+                    no_check_feedback,
+                    environment,
+                    run_command,
+                    container,
+                ))
+            }
+            ToolchainProvider::Nix(nix) => Self::Nix(NixToolchainCheck::new(
+                reporter,
+                ignore_lockfile,
+                no_check_feedback,
+                environment,
+                run_command,
+                nix,
+            )),
+            ToolchainProvider::Remote(remote) => Self::Remote(RemoteToolchainCheck::new(
+                reporter,
+                ignore_lockfile,
+                no_check_feedback,
+                environment,
+                run_command,
+                remote,
+            )),
+            ToolchainProvider::Tarballs(tarballs) => Self::Tarballs(TarballToolchainCheck::new(
+                reporter,
+                ignore_lockfile,
+                no_check_feedback,
+                environment,
+                run_command,
+                tarballs,
+            )),
+        }
+    }
+}
+
+impl<R: Reporter> ProviderCheck<'_, '_, R> {
+    /// Install the toolchain for the given host instead, for a Rust version which was never
+    /// published for the native host. Only toolchains which are installed by rustup fall back.
+    pub fn with_fallback_host(self, host: Option<&'static str>) -> Self {
+        match self {
+            Self::Rustup(check) => Self::Rustup(check.with_fallback_host(host)),
+            other => other,
+        }
+    }
+}
+
+impl<'reporter, 'env, R: Reporter> Check for ProviderCheck<'reporter, 'env, R> {
+    #[instrument(skip(self, toolchain), fields(version = %toolchain.version(), toolchain = toolchain.spec()))]
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        match self {
+            Self::Rustup(check) => check.check(toolchain),
+            Self::Distribution(check) => check.check(toolchain),
+            Self::Container(check) => check.check(toolchain),
+            Self::Nix(check) => check.check(toolchain),
+            Self::Remote(check) => check.check(toolchain),
+            Self::Tarballs(check) => check.check(toolchain),
+        }
+    }
+}
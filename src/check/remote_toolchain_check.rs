@@ -0,0 +1,148 @@
+use crate::check::provided_toolchain_check::{
+    ProvidedToolchainCheck, ProviderCommand, ProviderRun,
+};
+use crate::check::RunCommand;
+use crate::context::{EnvironmentContext, RemoteContext};
+use crate::external_command::ssh_command::SshCommand;
+use crate::reporter::event::Method;
+use crate::rust::setup_toolchain::{SetupRemoteToolchain, SetupToolchain};
+use crate::rust::Toolchain;
+use crate::{CargoMSRVError, Reporter, TResult};
+use camino::Utf8Path;
+use std::process::Command;
+
+/// Checks a crate on a remote builder, over ssh. The crate is synced to the remote builder with
+/// rsync, after which the toolchain is installed and the check command is run there, with rustup.
+pub type RemoteToolchainCheck<'reporter, 'env, R> =
+    ProvidedToolchainCheck<'reporter, 'env, R, RemoteContext>;
+
+impl ProviderCommand for RemoteContext {
+    fn command(
+        &self,
+        reporter: &impl Reporter,
+        environment: &EnvironmentContext,
+        check_cmd: &RunCommand,
+        toolchain: &Toolchain,
+    ) -> TResult<ProviderRun> {
+        let ssh = self.ssh();
+        let dir = self.dir(environment.root());
+
+        // the lockfile is synced as well, so it's (re)moved remotely too
+        sync(self, &ssh, environment.root(), &dir)?;
+
+        SetupRemoteToolchain::new(reporter, &ssh).download(toolchain)?;
+
+        let cmd = rustup_run(check_cmd, toolchain);
+
+        let mut command = Command::new("ssh");
+        command.args(ssh.args(Some(&dir), &remote_command(check_cmd, toolchain, &cmd)));
+
+        Ok(ProviderRun::delegated(
+            Method::remote_run(&self.destination, &cmd, dir),
+            command,
+        ))
+    }
+}
+
+/// Sync the crate to the remote directory, so the remote builder checks the current sources.
+fn sync(remote: &RemoteContext, ssh: &SshCommand, crate_root: &Utf8Path, dir: &str) -> TResult<()> {
+    let failed =
+        |command: &str, output: std::process::Output| CargoMSRVError::RemoteCommandFailed {
+            destination: remote.destination.clone(),
+            command: command.to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        };
+
+    let output = ssh.run(None, &["mkdir", "-p", dir])?;
+    if !output.status.success() {
+        return Err(failed("mkdir", output));
+    }
+
+    let output = ssh.sync(crate_root, dir)?;
+    if !output.status.success() {
+        return Err(failed("rsync", output));
+    }
+
+    Ok(())
+}
+
+/// The arguments to `rustup run`, to be run in the remote directory.
+fn rustup_run(check_cmd: &RunCommand, toolchain: &Toolchain) -> Vec<String> {
+    let mut cmd = vec![toolchain.rustup_toolchain()];
+    cmd.extend(check_cmd.components().iter().cloned());
+    cmd
+}
+
+/// The remote command line: the `rustup run` command, prefixed by `env` if the check environment
+/// of the toolchain isn't empty.
+fn remote_command(check_cmd: &RunCommand, toolchain: &Toolchain, cmd: &[String]) -> Vec<String> {
+    let envs = check_cmd.envs_for(toolchain.version());
+    let mut remote = Vec::new();
+
+    if !envs.is_empty() {
+        remote.push("env".to_string());
+        remote.extend(
+            envs.iter()
+                .map(|(name, value)| format!("{}={}", name, value)),
+        );
+    }
+
+    remote.extend(["rustup".to_string(), "run".to_string()]);
+    remote.extend(cmd.iter().cloned());
+    remote
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    #[test]
+    fn rustup_run_command() {
+        let check_cmd = RunCommand::custom(vec!["cargo".to_string(), "check".to_string()]);
+
+        let toolchain = Toolchain::new(
+            semver::Version::new(1, 56, 0),
+            "x86_64-unknown-linux-gnu",
+            &[],
+        );
+
+        assert_eq!(
+            rustup_run(&check_cmd, &toolchain),
+            vec!["1.56.0", "cargo", "check"]
+        );
+    }
+
+    #[yare::parameterized(
+        git_protocol = { 67, &["env", "CARGO_REGISTRIES_CRATES_IO_PROTOCOL=git", "rustup", "run", "1.67.0", "cargo", "check"] },
+        configured_protocol = { 68, &["rustup", "run", "1.68.0", "cargo", "check"] },
+    )]
+    fn remote_command(minor: u64, expected: &[&str]) {
+        let check_cmd = RunCommand::custom(vec!["cargo".to_string(), "check".to_string()]);
+
+        let toolchain = Toolchain::new(
+            semver::Version::new(1, minor, 0),
+            "x86_64-unknown-linux-gnu",
+            &[],
+        );
+        let cmd = rustup_run(&check_cmd, &toolchain);
+
+        assert_eq!(remote_command(&check_cmd, &toolchain, &cmd), expected);
+    }
+
+    #[yare::parameterized(
+        given = { Some("builds/my-crate"), "builds/my-crate" },
+        default = { None, ".cargo-msrv/remote/my-crate" },
+    )]
+    fn remote_dir(dir: Option<&str>, expected: &str) {
+        let remote = RemoteContext {
+            destination: "user@host".to_string(),
+            dir: dir.map(String::from),
+        };
+
+        assert_eq!(
+            remote.dir(Utf8Path::new("/does/not/exist/my-crate")),
+            expected
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use crate::check::Check;
+use crate::rust::Toolchain;
+use crate::search_state::SearchState;
+use crate::{Outcome, TResult};
+
+/// Takes the outcomes of the Rust versions which were checked by the search which is resumed,
+/// see `--resume`, instead of checking these versions again.
+pub struct ResumedCheck<'a, C: Check> {
+    inner: C,
+    state: Option<&'a SearchState>,
+}
+
+impl<'a, C: Check> ResumedCheck<'a, C> {
+    pub fn new(inner: C, state: Option<&'a SearchState>) -> Self {
+        Self { inner, state }
+    }
+}
+
+impl<C: Check> Check for ResumedCheck<'_, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let saved = self
+            .state
+            .and_then(|state| state.outcome(toolchain.version(), toolchain.target()));
+
+        match saved {
+            Some(true) => {
+                info!(version = %toolchain.version(), "compatible, according to the saved state");
+                Ok(Outcome::new_success(toolchain.clone()))
+            }
+            Some(false) => {
+                info!(version = %toolchain.version(), "incompatible, according to the saved state");
+                Ok(Outcome::new_failure(
+                    toolchain.clone(),
+                    "Incompatible, according to the saved state of the search".to_string(),
+                ))
+            }
+            None => self.inner.check(toolchain),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::reporter::event::CheckedVersion;
+    use crate::semver;
+
+    #[test]
+    fn saved_outcomes() {
+        let state = SearchState {
+            target: "x".to_string(),
+            checked: vec![
+                CheckedVersion {
+                    version: semver::Version::new(1, 68, 0),
+                    compatible: true,
+                },
+                CheckedVersion {
+                    version: semver::Version::new(1, 62, 0),
+                    compatible: false,
+                },
+            ],
+        };
+
+        // The test runner considers every version incompatible
+        let runner = ResumedCheck::new(TestRunner::with_ok("x", []), Some(&state));
+
+        let check = |minor| {
+            runner
+                .check(&Toolchain::new(semver::Version::new(1, minor, 0), "x", &[]))
+                .unwrap()
+                .is_success()
+        };
+
+        assert!(check(68));
+        assert!(!check(62));
+        assert!(!check(65));
+
+        assert_eq!(runner.inner.checked(), vec![semver::Version::new(1, 65, 0)]);
+    }
+}
@@ -1,20 +1,39 @@
-use crate::check::Check;
+use crate::check::{diagnostics, Check};
 use crate::context::EnvironmentContext;
 use crate::error::{IoError, IoErrorSource};
-use crate::external_command::cargo_command::CargoCommand;
+use crate::external_command::cargo_command::{
+    parse_compiled_unit, CargoCommand, JSON_MESSAGE_FORMAT,
+};
+use crate::external_command::cargo_hack_command::{self, CargoHackCommand};
 use crate::external_command::rustup_command::RustupCommand;
 use crate::lockfile::LockfileHandler;
-use crate::reporter::event::{CheckMethod, CheckResult, CheckToolchain, Method};
+use crate::reporter::event::{
+    CheckMethod, CheckResult, CheckToolchain, CompilationProgress, FeatureSetResult, Method,
+    SearchControl, Warning, WarningCode,
+};
+use crate::reporter::formatting::format_size;
+use crate::resource_limits::ResourceLimits;
+use crate::rust::default_target::default_target;
 use crate::rust::setup_toolchain::{SetupRustupToolchain, SetupToolchain};
 use crate::rust::Toolchain;
-use crate::{lockfile, CargoMSRVError, Outcome, Reporter, TResult};
+use crate::stabilization::StabilizationIndex;
+use crate::{lockfile, semver, CargoMSRVError, Outcome, Reporter, TResult};
 use camino::{Utf8Path, Utf8PathBuf};
 use std::fmt;
 use std::fmt::Formatter;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub struct RustupToolchainCheck<'reporter, 'env, R: Reporter> {
     reporter: &'reporter R,
     settings: Settings<'env>,
+    stabilizations: StabilizationIndex,
+    /// The estimated number of units to compile, shared by all checks.
+    units: Option<u64>,
+    /// Whether the user was warned that the lockfile is resolved anew by each check.
+    warned_ignored_lockfile: AtomicBool,
+    /// The host of the toolchains which are installed instead of those of the native host, for
+    /// Rust versions which were never published for the native host.
+    fallback_host: Option<&'static str>,
 }
 
 impl<'reporter, 'env, R: Reporter> RustupToolchainCheck<'reporter, 'env, R> {
@@ -33,8 +52,19 @@ impl<'reporter, 'env, R: Reporter> RustupToolchainCheck<'reporter, 'env, R> {
                 environment,
                 check_cmd: run_command,
             },
+            stabilizations: StabilizationIndex::load(),
+            units: estimate_units(environment),
+            warned_ignored_lockfile: AtomicBool::new(false),
+            fallback_host: None,
         }
     }
+
+    /// Install the toolchain for the given host instead, for a Rust version which was never
+    /// published for the native host, see `--fallback-host`.
+    pub fn with_fallback_host(mut self, host: Option<&'static str>) -> Self {
+        self.fallback_host = host;
+        self
+    }
 }
 
 impl<'reporter, 'env, R: Reporter> Check for RustupToolchainCheck<'reporter, 'env, R> {
@@ -45,18 +75,20 @@ impl<'reporter, 'env, R: Reporter> Check for RustupToolchainCheck<'reporter, 'en
             .run_scoped_event(CheckToolchain::new(toolchain.to_owned()), || {
                 info!(ignore_lockfile_enabled = settings.ignore_lockfile());
 
+                // before the lockfile is moved, since a toolchain which is unavailable is skipped
+                let toolchain = &setup_toolchain(self.reporter, toolchain, self.fallback_host)?;
+
                 // temporarily move the lockfile if the user opted to ignore it, and it exists
                 let ignore_lockfile = settings.ignore_lockfile();
                 let handle_wrap = create_lockfile_handle(ignore_lockfile, settings.environment)
                     .map(|handle| handle.move_lockfile())
                     .transpose()?;
 
-                setup_toolchain(self.reporter, toolchain)?;
-
                 if handle_wrap.is_some() {
                     remove_lockfile(&settings.lockfile_path())?;
                 }
 
+                let lockfile_existed = settings.lockfile_path().is_file();
                 let crate_root = settings.crate_root_path();
                 let cmd = &self.settings.check_cmd;
 
@@ -64,64 +96,183 @@ impl<'reporter, 'env, R: Reporter> Check for RustupToolchainCheck<'reporter, 'en
                     self.reporter,
                     toolchain,
                     crate_root,
-                    cmd.components(),
-                )?;
-
-                // report outcome to UI
-                report_outcome(self.reporter, &outcome, settings.no_check_feedback())?;
+                    cmd,
+                    self.units,
+                );
 
-                // move the lockfile back
+                // move the lockfile back, also when the check was interrupted
+                let lockfile_ignored = handle_wrap.is_some();
                 if let Some(handle) = handle_wrap {
                     handle.move_lockfile_back()?;
                 }
 
+                let outcome = outcome?;
+
+                self.report_regenerated_lockfile(toolchain, lockfile_ignored, lockfile_existed)?;
+
+                // report outcome to UI
+                report_outcome(
+                    self.reporter,
+                    &outcome,
+                    settings.no_check_feedback(),
+                    &self.stabilizations,
+                )?;
+
                 Ok(outcome)
             })
     }
 }
 
+impl<R: Reporter> RustupToolchainCheck<'_, '_, R> {
+    /// Warn when the lockfile was not used by the check: because it is ignored, and was thus
+    /// resolved anew (once per run), or because it did not exist, and was generated.
+    fn report_regenerated_lockfile(
+        &self,
+        toolchain: &Toolchain,
+        ignored: bool,
+        existed: bool,
+    ) -> TResult<()> {
+        if ignored {
+            if !self.warned_ignored_lockfile.swap(true, Ordering::Relaxed) {
+                self.reporter.report_event(Warning::new(
+                    WarningCode::LockfileRegenerated,
+                    "The lockfile is ignored, so the dependencies are resolved anew by each check",
+                ))?;
+            }
+        } else if !existed && self.settings.lockfile_path().is_file() {
+            self.reporter.report_event(Warning::new(
+                WarningCode::LockfileRegenerated,
+                format!(
+                    "No lockfile existed, so the check with Rust {} generated '{}'",
+                    toolchain.version(),
+                    self.settings.lockfile_path()
+                ),
+            ))?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<R: Reporter> fmt::Debug for RustupToolchainCheck<'_, '_, R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.write_fmt(format_args!("{:?}", self.settings))
     }
 }
 
-fn setup_toolchain(reporter: &impl Reporter, toolchain: &Toolchain) -> TResult<()> {
+/// Install the toolchain, and return the toolchain which was installed. A Rust version which was
+/// never published for the native host says nothing about the crate, so the toolchain for the
+/// fallback host is installed instead, if any, and otherwise the version is skipped, instead of
+/// being reported as incompatible.
+fn setup_toolchain(
+    reporter: &impl Reporter,
+    toolchain: &Toolchain,
+    fallback_host: Option<&'static str>,
+) -> TResult<Toolchain> {
     let downloader = SetupRustupToolchain::new(reporter);
-    downloader.download(toolchain)?;
 
-    Ok(())
+    let unavailable = match downloader.download(toolchain) {
+        Err(CargoMSRVError::ToolchainUnavailable { reason, .. }) => reason,
+        result => return result.map(|_| toolchain.to_owned()),
+    };
+
+    let version = toolchain.version();
+
+    if let Some(host) = fallback_host {
+        // The target of the native host is unavailable as well, so the fallback host is targeted
+        let target = match default_target() {
+            Ok(native) if native == toolchain.target() => host,
+            _ => toolchain.target(),
+        };
+        let fallback = toolchain.on_host(host, target);
+
+        reporter.report_event(Warning::host_fallback(version, &unavailable, host))?;
+
+        match downloader.download(&fallback) {
+            Err(CargoMSRVError::ToolchainUnavailable { reason, .. }) => {
+                return skip_unavailable(reporter, version, &reason);
+            }
+            result => return result.map(|_| fallback),
+        }
+    }
+
+    skip_unavailable(reporter, version, &unavailable)
 }
 
+fn skip_unavailable(
+    reporter: &impl Reporter,
+    version: &semver::Version,
+    reason: &str,
+) -> TResult<Toolchain> {
+    reporter.report_event(SearchControl::Skipped {
+        version: version.clone(),
+    })?;
+    reporter.report_event(Warning::toolchain_unavailable(version, reason))?;
+
+    Err(CargoMSRVError::CheckSkipped {
+        version: version.clone(),
+    })
+}
+
+#[instrument(name = "run_check_command", skip_all, fields(command = tracing::field::Empty))]
 fn run_check_command_via_rustup(
     reporter: &impl Reporter,
     toolchain: &Toolchain,
     dir: &Utf8Path,
-    check: &[String],
+    check: &RunCommand,
+    units: Option<u64>,
 ) -> TResult<Outcome> {
-    let version = format!("{}", toolchain.version());
-    let mut cmd = vec![version.as_str()];
-    cmd.extend(check.iter().map(|s| s.as_str()));
+    let rustup_toolchain = toolchain.rustup_toolchain();
+    let mut cmd = vec![rustup_toolchain.as_str()];
+    cmd.extend(check.components_for(toolchain.version()));
+    tracing::Span::current().record("command", cmd.join(" "));
 
     reporter.report_event(CheckMethod::new(
         toolchain.to_owned(),
         Method::rustup_run(&cmd, dir),
     ))?;
 
-    let rustup_output = RustupCommand::new()
+    let rustup = RustupCommand::new()
         .with_args(cmd.iter())
+        .with_envs(check.envs_for(toolchain.version()))
         .with_dir(dir)
         .with_stderr()
-        .run()
-        .map_err(|_| CargoMSRVError::UnableToRunCheck {
-            command: cmd[1..].join(" "),
-            cwd: dir.to_path_buf(),
-        })?;
+        .with_jobserver()
+        .with_limits(*check.limits())
+        .interruptible();
+
+    let rustup_output = if check.reports_progress(toolchain.version()) {
+        rustup.run_streaming(report_compilation_progress(reporter, units))
+    } else {
+        rustup.run()
+    };
+
+    let rustup_output = rustup_output.map_err(|_| CargoMSRVError::UnableToRunCheck {
+        command: cmd[1..].join(" "),
+        cwd: dir.to_path_buf(),
+    })?;
+
+    if rustup_output.was_interrupted() {
+        return Err(CargoMSRVError::CheckInterrupted {
+            version: toolchain.version().clone(),
+        });
+    }
+
+    if rustup_output.exceeded_memory_limit() {
+        return Err(memory_limit_exceeded(reporter, toolchain, check.limits())?);
+    }
+
+    if check.is_cargo_hack() {
+        report_feature_set_results(reporter, toolchain, rustup_output.stderr())?;
+    }
 
     let status = rustup_output.exit_status();
 
     if status.success() {
-        Ok(Outcome::new_success(toolchain.to_owned()))
+        Ok(Outcome::new_success_with_output(
+            toolchain.to_owned(),
+            rustup_output.stderr().to_string(),
+        ))
     } else {
         let stderr = rustup_output.stderr();
         let command = cmd.join(" ");
@@ -133,36 +284,111 @@ fn run_check_command_via_rustup(
             "try_building run failed"
         );
 
-        Ok(Outcome::new_failure(
+        Ok(
+            Outcome::new_failure(toolchain.to_owned(), stderr.to_string())
+                .with_exit_code(status.code()),
+        )
+    }
+}
+
+/// Warn that the check of the toolchain exceeded its memory limit, and return the error which makes
+/// its outcome inconclusive, since the toolchain may well be compatible, given more memory.
+pub(crate) fn memory_limit_exceeded(
+    reporter: &impl Reporter,
+    toolchain: &Toolchain,
+    limits: &ResourceLimits,
+) -> TResult<CargoMSRVError> {
+    let limit = limits.memory.unwrap_or_default();
+
+    reporter.report_event(Warning::new(
+        WarningCode::MemoryLimitExceeded,
+        format!(
+            "The check of Rust {} was killed, since it exceeded the memory limit of {}",
+            toolchain.version(),
+            format_size(limit)
+        ),
+    ))?;
+
+    Ok(CargoMSRVError::MemoryLimitExceeded {
+        version: toolchain.version().clone(),
+        limit,
+    })
+}
+
+/// Report each unit which was compiled, as parsed from the json messages of cargo.
+fn report_compilation_progress(
+    reporter: &impl Reporter,
+    units: Option<u64>,
+) -> impl FnMut(&str) + '_ {
+    let mut compiled = 0;
+
+    move |line| {
+        if let Some(unit) = parse_compiled_unit(line) {
+            compiled += 1;
+
+            // The progress is informative only, so it should not fail the check
+            if let Err(error) =
+                reporter.report_event(CompilationProgress::new(unit, compiled, units))
+            {
+                debug!(%error, "unable to report compilation progress");
+            }
+        }
+    }
+}
+
+/// The estimated number of units to compile: one for each package in the lockfile, if any. The
+/// lockfile is read instead of resolved, since resolving may write it.
+pub(crate) fn estimate_units(environment: &EnvironmentContext) -> Option<u64> {
+    let lockfile = std::fs::read_to_string(environment.lock()).ok()?;
+
+    let packages = lockfile
+        .lines()
+        .filter(|line| line.trim() == "[[package]]")
+        .count();
+
+    Some(packages as u64)
+}
+
+/// Reports the outcome of each feature set which was checked by `cargo hack`.
+pub(super) fn report_feature_set_results(
+    reporter: &impl Reporter,
+    toolchain: &Toolchain,
+    output: &str,
+) -> TResult<()> {
+    for run in cargo_hack_command::parse_feature_set_runs(output) {
+        reporter.report_event(FeatureSetResult::new(
             toolchain.to_owned(),
-            stderr.to_string(),
-        ))
+            run.package,
+            run.feature_flags,
+            run.success,
+        ))?;
     }
+
+    Ok(())
 }
 
-fn report_outcome(
+pub(super) fn report_outcome(
     reporter: &impl Reporter,
     outcome: &Outcome,
     no_error_report: bool,
+    stabilizations: &StabilizationIndex,
 ) -> TResult<()> {
     match outcome {
         Outcome::Success(outcome) => {
             // report compatibility with this toolchain
             reporter.report_event(CheckResult::compatible(outcome.toolchain_spec.to_owned()))?
         }
-        Outcome::Failure(outcome) if no_error_report => {
-            // report incompatibility with this toolchain
-            reporter.report_event(CheckResult::incompatible(
-                outcome.toolchain_spec.to_owned(),
-                None,
-            ))?
-        }
         Outcome::Failure(outcome) => {
+            let hint = stabilizations.hint(&outcome.error_message);
+            let failed_targets = diagnostics::failed_targets(&outcome.error_message);
+            let error = (!no_error_report).then(|| outcome.error_message.clone());
+
             // report incompatibility with this toolchain
-            reporter.report_event(CheckResult::incompatible(
-                outcome.toolchain_spec.to_owned(),
-                Some(outcome.error_message.clone()),
-            ))?
+            reporter.report_event(
+                CheckResult::incompatible(outcome.toolchain_spec.to_owned(), error)
+                    .with_stabilization_hint(hint)
+                    .with_failed_targets(failed_targets),
+            )?
         }
     };
 
@@ -171,7 +397,7 @@ fn report_outcome(
 
 /// Creates a lockfile handle, iff the lockfile exists and the user opted
 /// to ignore it.
-fn create_lockfile_handle(
+pub(super) fn create_lockfile_handle(
     ignore_lockfile: bool,
     env: &EnvironmentContext,
 ) -> Option<LockfileHandler<lockfile::Start>> {
@@ -181,7 +407,7 @@ fn create_lockfile_handle(
         .map(LockfileHandler::new)
 }
 
-fn remove_lockfile(lock_file: &Utf8Path) -> TResult<()> {
+pub(super) fn remove_lockfile(lock_file: &Utf8Path) -> TResult<()> {
     if lock_file.is_file() {
         std::fs::remove_file(lock_file).map_err(|error| IoError {
             error,
@@ -219,23 +445,173 @@ impl<'env> Settings<'env> {
     }
 }
 
-#[derive(Debug)]
+/// The first Rust release with a cargo which supports [`JSON_MESSAGE_FORMAT`].
+const JSON_RENDER_DIAGNOSTICS_SINCE: semver::Version = semver::Version::new(1, 38, 0);
+
+/// The first Rust release with a cargo which supports the sparse registry protocol.
+const SPARSE_REGISTRY_SINCE: semver::Version = semver::Version::new(1, 68, 0);
+
+/// Configures cargo to fetch the crates.io index with the given protocol, also over the
+/// protocol in the cargo configuration of the user.
+const CRATES_IO_PROTOCOL_ENV_VAR: &str = "CARGO_REGISTRIES_CRATES_IO_PROTOCOL";
+
+const CARGO_HOME_ENV_VAR: &str = "CARGO_HOME";
+
+#[derive(Clone, Debug)]
 pub struct RunCommand {
     command: Vec<String>,
+    kind: RunCommandKind,
+    limits: ResourceLimits,
+    /// The cargo home of the checks, configured to use the vendored sources of `--vendor`.
+    cargo_home: Option<Utf8PathBuf>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum RunCommandKind {
+    Cargo,
+    CargoHack,
+    Custom,
 }
 
 impl RunCommand {
     pub fn default(cargo_command: CargoCommand) -> Self {
         Self {
             command: cargo_command.into_args(),
+            kind: RunCommandKind::Cargo,
+            limits: ResourceLimits::default(),
+            cargo_home: None,
+        }
+    }
+
+    pub fn cargo_hack(cargo_hack_command: CargoHackCommand) -> Self {
+        Self {
+            command: cargo_hack_command.into_args(),
+            kind: RunCommandKind::CargoHack,
+            limits: ResourceLimits::default(),
+            cargo_home: None,
         }
     }
 
     pub fn custom(command: Vec<String>) -> Self {
-        Self { command }
+        Self {
+            command,
+            kind: RunCommandKind::Custom,
+            limits: ResourceLimits::default(),
+            cargo_home: None,
+        }
+    }
+
+    /// Limit the memory and CPU time of the checks, see `--memory-limit` and `--cpu-limit`.
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Build the checks with the vendored sources of `--vendor`, offline, see [`crate::vendor`].
+    pub fn with_cargo_home(mut self, cargo_home: Option<&Utf8Path>) -> Self {
+        self.cargo_home = cargo_home.map(Utf8Path::to_path_buf);
+        self
     }
 
     pub fn components(&self) -> &[String] {
         self.command.as_ref()
     }
+
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.limits
+    }
+
+    /// The command with which the toolchain of the given Rust version is checked.
+    pub fn components_for(&self, version: &semver::Version) -> Vec<&str> {
+        let mut components = self.command.iter().map(String::as_str).collect::<Vec<_>>();
+
+        // Right after `cargo check`, so it is not passed on to a custom subcommand
+        if self.reports_progress(version) {
+            components.insert(2, JSON_MESSAGE_FORMAT);
+        }
+
+        components
+    }
+
+    /// Whether the progress of the compilation is followed, by the json messages of cargo. Only
+    /// for the default `cargo check`, since custom commands may not accept the message format.
+    pub fn reports_progress(&self, version: &semver::Version) -> bool {
+        self.kind == RunCommandKind::Cargo && version >= &JSON_RENDER_DIAGNOSTICS_SINCE
+    }
+
+    /// The environment with which the toolchain of the given Rust version is checked. A cargo
+    /// which predates the sparse registry protocol uses the git protocol, since it would
+    /// otherwise fail on a sparse protocol in the cargo configuration, before the crate is even
+    /// compiled. With `--vendor`, the cargo home is the one which uses the vendored sources.
+    pub fn envs_for(&self, version: &semver::Version) -> Vec<(&'static str, String)> {
+        let mut envs = Vec::new();
+
+        if version < &SPARSE_REGISTRY_SINCE {
+            envs.push((CRATES_IO_PROTOCOL_ENV_VAR, "git".to_string()));
+        }
+
+        if let Some(cargo_home) = &self.cargo_home {
+            envs.push((CARGO_HOME_ENV_VAR, cargo_home.to_string()));
+        }
+
+        envs
+    }
+
+    /// Whether the feature iteration of this command is delegated to `cargo hack`.
+    pub fn is_cargo_hack(&self) -> bool {
+        self.kind == RunCommandKind::CargoHack
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        before_json_messages = { 37, "cargo check --features a" },
+        json_messages = { 38, "cargo check --message-format=json-render-diagnostics --features a" },
+    )]
+    fn cargo_command_for_version(minor: u64, expected: &str) {
+        let command = RunCommand::default(CargoCommand::default().features(Some(vec!["a".into()])));
+        let version = semver::Version::new(1, minor, 0);
+
+        assert_eq!(command.components_for(&version).join(" "), expected);
+    }
+
+    #[test]
+    fn custom_command_unchanged() {
+        let command = RunCommand::custom(vec!["cargo".into(), "test".into()]);
+        let version = semver::Version::new(1, 60, 0);
+
+        assert!(!command.reports_progress(&version));
+        assert_eq!(command.components_for(&version), ["cargo", "test"]);
+    }
+
+    #[yare::parameterized(
+        git_protocol = { 67, vec![(CRATES_IO_PROTOCOL_ENV_VAR, "git")] },
+        configured_protocol = { 68, vec![] },
+    )]
+    fn registry_protocol(minor: u64, expected: Vec<(&str, &str)>) {
+        let command = RunCommand::custom(vec!["cargo".into(), "test".into()]);
+        let envs = command.envs_for(&semver::Version::new(1, minor, 0));
+
+        assert_eq!(
+            envs.iter()
+                .map(|(name, value)| (*name, value.as_str()))
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn vendored_cargo_home() {
+        let command = RunCommand::custom(vec!["cargo".into(), "test".into()])
+            .with_cargo_home(Some(Utf8Path::new("/tmp/vendor/cargo-home")));
+        let envs = command.envs_for(&semver::Version::new(1, 70, 0));
+
+        assert_eq!(
+            envs,
+            vec![(CARGO_HOME_ENV_VAR, "/tmp/vendor/cargo-home".to_string())]
+        );
+    }
 }
@@ -0,0 +1,72 @@
+use crate::check::Check;
+use crate::error::CargoMSRVError;
+use crate::rust::Toolchain;
+use crate::{Outcome, TResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Stops the search once cargo-msrv received a signal to stop, see [`signal`]: no check starts
+/// after the signal, and the check which was interrupted by it, is inconclusive.
+///
+/// [`signal`]: crate::signal
+pub struct SignalCheck<'a, C: Check> {
+    inner: C,
+    interrupted: &'a AtomicBool,
+}
+
+impl<'a, C: Check> SignalCheck<'a, C> {
+    pub fn new(inner: C, interrupted: &'a AtomicBool) -> Self {
+        Self { inner, interrupted }
+    }
+
+    fn is_interrupted(&self) -> bool {
+        self.interrupted.load(Ordering::SeqCst)
+    }
+}
+
+impl<C: Check> Check for SignalCheck<'_, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        if self.is_interrupted() {
+            return Err(CargoMSRVError::Interrupted);
+        }
+
+        let outcome = self.inner.check(toolchain);
+
+        // The interrupted check may have failed in any way, e.g. since its compiler was killed
+        if self.is_interrupted() {
+            return Err(CargoMSRVError::Interrupted);
+        }
+
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::TestRunner;
+    use crate::semver;
+
+    fn toolchain() -> Toolchain {
+        Toolchain::new(semver::Version::new(1, 60, 0), "x", &[])
+    }
+
+    #[test]
+    fn not_interrupted() {
+        let interrupted = AtomicBool::new(false);
+        let runner = SignalCheck::new(TestRunner::with_ok("x", []), &interrupted);
+
+        assert!(!runner.check(&toolchain()).unwrap().is_success());
+    }
+
+    #[test]
+    fn interrupted() {
+        let interrupted = AtomicBool::new(true);
+        let runner = SignalCheck::new(TestRunner::with_ok("x", []), &interrupted);
+
+        assert!(matches!(
+            runner.check(&toolchain()),
+            Err(CargoMSRVError::Interrupted)
+        ));
+        assert!(runner.inner.checked().is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::check::{Check, DistributionToolchainCheck, RunCommand};
+use crate::context::EnvironmentContext;
+use crate::reporter::event::SetupToolchain;
+use crate::rust::tarball_toolchain::TarballToolchains;
+use crate::rust::Toolchain;
+use crate::{Outcome, Reporter, TResult};
+use std::fmt;
+use std::fmt::Formatter;
+
+/// Checks a crate with a toolchain which is installed from a standalone tarball, instead of being
+/// downloaded by rustup, for build machines without network access.
+///
+/// Once installed, the toolchain is checked like a `rustc` and `cargo` pair which is not managed by
+/// rustup, see [`DistributionToolchainCheck`].
+pub struct TarballToolchainCheck<'reporter, 'env, R: Reporter> {
+    reporter: &'reporter R,
+    ignore_lockfile: bool,
+    no_check_feedback: bool,
+    environment: &'env EnvironmentContext,
+    check_cmd: RunCommand,
+    tarballs: TarballToolchains,
+}
+
+impl<'reporter, 'env, R: Reporter> TarballToolchainCheck<'reporter, 'env, R> {
+    pub fn new(
+        reporter: &'reporter R,
+        ignore_lockfile: bool,
+        no_check_feedback: bool,
+        environment: &'env EnvironmentContext,
+        run_command: RunCommand,
+        tarballs: TarballToolchains,
+    ) -> Self {
+        Self {
+            reporter,
+            ignore_lockfile,
+            no_check_feedback,
+            environment,
+            check_cmd: run_command,
+            tarballs,
+        }
+    }
+}
+
+impl<'reporter, 'env, R: Reporter> Check for TarballToolchainCheck<'reporter, 'env, R> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let distribution =
+            self.reporter
+                .run_scoped_event(SetupToolchain::new(toolchain.to_owned()), || {
+                    self.tarballs
+                        .install(toolchain.version(), toolchain.target())
+                })?;
+
+        DistributionToolchainCheck::new(
+            self.reporter,
+            self.ignore_lockfile,
+            self.no_check_feedback,
+            self.environment,
+            self.check_cmd.clone(),
+            distribution,
+        )
+        .check(toolchain)
+    }
+}
+
+impl<R: Reporter> fmt::Debug for TarballToolchainCheck<'_, '_, R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TarballToolchainCheck")
+            .field("ignore_lockfile", &self.ignore_lockfile)
+            .field("no_check_feedback", &self.no_check_feedback)
+            .field("environment", &self.environment)
+            .field("check_cmd", &self.check_cmd)
+            .field("tarballs", &self.tarballs)
+            .finish()
+    }
+}
@@ -1,13 +1,17 @@
 use crate::check::Check;
+use crate::error::CargoMSRVError;
 use crate::outcome::Outcome;
 use crate::rust::Toolchain;
 use crate::semver::Version;
 use crate::TResult;
 use std::collections::HashSet;
+use std::sync::Mutex;
 
 pub struct TestRunner {
     accept_versions: HashSet<Version>,
     target: &'static str,
+    skip_versions: HashSet<Version>,
+    checked: Mutex<Vec<Version>>,
 }
 
 impl TestRunner {
@@ -15,13 +19,31 @@ impl TestRunner {
         Self {
             accept_versions: iter.into_iter().cloned().collect(),
             target,
+            skip_versions: HashSet::new(),
+            checked: Mutex::new(Vec::new()),
         }
     }
+
+    /// Skip the checks of the given versions, as if requested over the control channel.
+    pub fn with_skipped<'v, T: IntoIterator<Item = &'v Version>>(mut self, iter: T) -> Self {
+        self.skip_versions = iter.into_iter().cloned().collect();
+        self
+    }
+
+    /// The versions which were checked, in order.
+    pub fn checked(&self) -> Vec<Version> {
+        self.checked.lock().unwrap().clone()
+    }
 }
 
 impl Check for TestRunner {
     fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
         let v = toolchain.version();
+        self.checked.lock().unwrap().push(v.clone());
+
+        if self.skip_versions.contains(v) {
+            return Err(CargoMSRVError::CheckSkipped { version: v.clone() });
+        }
 
         if self.accept_versions.contains(toolchain.version()) {
             Ok(Outcome::new_success(Toolchain::new(
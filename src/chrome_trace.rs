@@ -0,0 +1,305 @@
+//! Records the `tracing` spans of a run in the [trace event format] of Chrome, for `--trace-file`,
+//! so the time spent on each phase, like downloading a toolchain or compiling the crate, can be
+//! inspected in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+//!
+//! Each span is recorded as a complete event (`"ph": "X"`), with the fields of the span as its
+//! arguments. The file is written when the [`ChromeTraceGuard`] is finished, at the end of the
+//! program.
+//!
+//! [trace event format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+
+use crate::error::{IoError, IoErrorSource};
+use crate::{CargoMSRVError, TResult};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// A layer which records the spans, and the guard which writes them to the given file when
+/// finished.
+pub fn layer(path: PathBuf) -> (ChromeTraceLayer, ChromeTraceGuard) {
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let layer = ChromeTraceLayer {
+        started: Instant::now(),
+        events: Arc::clone(&events),
+    };
+
+    let guard = ChromeTraceGuard {
+        path,
+        events,
+        written: false,
+    };
+
+    (layer, guard)
+}
+
+/// Records the spans of the run, as they close.
+pub struct ChromeTraceLayer {
+    /// The start of the timeline, to which the timestamps of the events are relative.
+    started: Instant,
+    events: Arc<Mutex<Vec<TraceEvent>>>,
+}
+
+/// Writes the recorded spans to the trace file, when finished. If the guard is dropped before, the
+/// file is written when it's dropped, and a failure is only logged.
+pub struct ChromeTraceGuard {
+    path: PathBuf,
+    events: Arc<Mutex<Vec<TraceEvent>>>,
+    written: bool,
+}
+
+impl ChromeTraceGuard {
+    /// Write the recorded spans to the trace file.
+    pub fn finish(mut self) -> TResult<()> {
+        self.written = true;
+
+        self.write().map_err(|error| {
+            CargoMSRVError::from(IoError {
+                error,
+                source: IoErrorSource::WriteTraceFile(self.path.clone()),
+            })
+        })
+    }
+
+    fn write(&self) -> std::io::Result<()> {
+        let events = self
+            .events
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let trace = serde_json::json!({
+            "traceEvents": *events,
+            "displayTimeUnit": "ms",
+        });
+
+        std::fs::write(&self.path, serde_json::to_vec(&trace)?)
+    }
+}
+
+impl Drop for ChromeTraceGuard {
+    fn drop(&mut self) {
+        if self.written {
+            return;
+        }
+
+        if let Err(error) = self.write() {
+            tracing::warn!(
+                %error,
+                path = %self.path.display(),
+                "unable to write the trace file"
+            );
+        }
+    }
+}
+
+/// A complete event, i.e. a span with its begin and duration.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+struct TraceEvent {
+    name: String,
+    cat: String,
+    ph: &'static str,
+    /// The begin, in microseconds since the start of the run.
+    ts: u64,
+    /// The duration, in microseconds.
+    dur: u64,
+    pid: u32,
+    tid: u64,
+    args: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The state of an open span.
+struct OpenSpan {
+    started: Instant,
+    tid: u64,
+    args: serde_json::Map<String, serde_json::Value>,
+}
+
+impl<S> Layer<S> for ChromeTraceLayer
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut open = OpenSpan {
+            started: Instant::now(),
+            tid: thread_id(),
+            args: serde_json::Map::new(),
+        };
+        attrs.record(&mut ArgsVisitor(&mut open.args));
+
+        span.extensions_mut().insert(open);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(open) = span.extensions_mut().get_mut::<OpenSpan>() {
+                values.record(&mut ArgsVisitor(&mut open.args));
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+
+        let Some(open) = span.extensions_mut().remove::<OpenSpan>() else {
+            return;
+        };
+
+        // Both relative to the start of the timeline, so a nested span never ends after its parent
+        let begin = micros(open.started.saturating_duration_since(self.started));
+        let end = micros(self.started.elapsed());
+
+        let metadata = span.metadata();
+        let event = TraceEvent {
+            name: metadata.name().to_string(),
+            cat: category(metadata.target()).to_string(),
+            ph: "X",
+            ts: begin,
+            dur: end.saturating_sub(begin),
+            pid: std::process::id(),
+            tid: open.tid,
+            args: open.args,
+        };
+
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+}
+
+struct ArgsVisitor<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl Visit for ArgsVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+/// The category of a span, as the last segment of the module which recorded it, like
+/// `setup_toolchain` for `cargo_msrv::rust::setup_toolchain`.
+fn category(target: &str) -> &str {
+    target.rsplit("::").next().unwrap_or(target)
+}
+
+/// The id of the current thread. The standard library only exposes it by its debug
+/// representation, like `ThreadId(2)`.
+fn thread_id() -> u64 {
+    let id = format!("{:?}", std::thread::current().id());
+
+    id.chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>()
+        .parse()
+        .unwrap_or_default()
+}
+
+fn micros(duration: std::time::Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn written() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("trace.json");
+
+        let (layer, guard) = layer(path.clone());
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let check = tracing::info_span!("check", version = "1.60.0");
+            let _check = check.enter();
+
+            let command = tracing::info_span!("run_check_command", command = tracing::field::Empty);
+            command.record("command", "cargo check");
+            command.in_scope(|| {});
+        });
+        guard.finish().unwrap();
+
+        let trace: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let events = trace["traceEvents"].as_array().unwrap();
+
+        assert_eq!(events.len(), 2);
+        let (command, check) = (&events[0], &events[1]);
+
+        assert_eq!(command["name"], "run_check_command");
+        assert_eq!(command["ph"], "X");
+        assert_eq!(command["cat"], "tests");
+        assert_eq!(command["args"]["command"], "cargo check");
+        assert_eq!(check["args"]["version"], "1.60.0");
+
+        // The command ran within the check
+        let (check_ts, command_ts) = (
+            check["ts"].as_u64().unwrap(),
+            command["ts"].as_u64().unwrap(),
+        );
+        assert!(check_ts <= command_ts);
+        assert!(
+            command_ts + command["dur"].as_u64().unwrap()
+                <= check_ts + check["dur"].as_u64().unwrap()
+        );
+    }
+
+    #[test]
+    fn unwritable() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("missing").join("trace.json");
+
+        let (_layer, guard) = layer(path);
+        let error = guard.finish().unwrap_err();
+
+        assert!(matches!(
+            error,
+            CargoMSRVError::Io(IoError {
+                source: IoErrorSource::WriteTraceFile(_),
+                ..
+            })
+        ));
+    }
+
+    #[yare::parameterized(
+        module = { "cargo_msrv::rust::setup_toolchain", "setup_toolchain" },
+        crate_root = { "cargo_msrv", "cargo_msrv" },
+    )]
+    fn categories(target: &str, expected: &str) {
+        assert_eq!(category(target), expected);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::external_command::container_command::ContainerEngine;
+use clap::{ArgGroup, Args};
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Container options")]
+#[command(group(
+    ArgGroup::new("container_backend")
+        .args(["container", "container_engine", "container_image", "container_rootless"])
+        .multiple(true)
+        .conflicts_with_all(["memory_limit", "cpu_limit", "jobs"])
+))]
+pub struct ContainerOpts {
+    /// Run the compatibility checks in a container, instead of with toolchains installed by rustup
+    ///
+    /// Each toolchain is provided by a container image (see --container-image), with the crate
+    /// mounted into the container. Implied by the other container options.
+    #[arg(long, global = true)]
+    pub container: bool,
+
+    /// The container engine to run the compatibility checks with
+    ///
+    /// If not given, podman is used when installed, and docker otherwise.
+    #[arg(long, value_enum, value_name = "ENGINE", global = true)]
+    pub container_engine: Option<ContainerEngine>,
+
+    /// The image to run the compatibility check of a toolchain in
+    ///
+    /// The `{version}` placeholder is replaced by the Rust version of the toolchain, and the
+    /// optional `{target}` placeholder by its target.
+    ///
+    /// For example: --container-image "myregistry/rust:{version}-alpine"
+    #[arg(long, value_name = "TEMPLATE", global = true)]
+    pub container_image: Option<String>,
+
+    /// Run the compatibility checks as the invoking user, instead of as root in the container
+    ///
+    /// Use this with rootless podman or docker, so files written to the crate (like the target
+    /// directory) are owned by the invoking user.
+    #[arg(long, global = true)]
+    pub container_rootless: bool,
+}
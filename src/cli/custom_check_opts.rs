@@ -1,3 +1,5 @@
+use crate::context::CheckCommandPreset;
+use camino::Utf8PathBuf;
 use clap::Args;
 
 #[derive(Debug, Args)]
@@ -24,6 +26,73 @@ pub struct CustomCheckOpts {
     #[arg(long)]
     pub no_default_features: bool,
 
+    /// Use one of the built-in compatibility check commands.
+    ///
+    /// The `hack-each-feature` command delegates checking each feature separately to
+    /// `cargo hack`, which must be installed. The --features and --no-default-features flags are
+    /// forwarded to `cargo hack`.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "PRESET",
+        conflicts_with_all = ["all_features", "custom_check_opts"]
+    )]
+    pub check_cmd: Option<CheckCommandPreset>,
+
+    /// Also check the examples, and the member crates of the workspace in the `examples`
+    /// directory, with each toolchain.
+    ///
+    /// If a custom compatibility check command is used, this option is ignored.
+    #[arg(long)]
+    pub check_examples: bool,
+
+    /// Also check the benchmarks, like those in `benches/`, with each toolchain.
+    ///
+    /// Benchmarks are excluded by default, since these often depend on a benchmark harness which
+    /// requires a newer Rust version than the crate. If a custom compatibility check command is
+    /// used, this option is ignored.
+    #[arg(long)]
+    pub check_benches: bool,
+
+    /// Also check the cargo-fuzz projects, like `fuzz/`, with each toolchain.
+    ///
+    /// Each project is checked once the crate itself passes, and reported with its own result.
+    /// Fuzz projects are excluded by default.
+    #[arg(long)]
+    pub check_fuzz: bool,
+
+    /// Only check the binary target with the given name, instead of the library and binaries
+    ///
+    /// Useful for a crate with multiple binaries, of which only one is shipped, so auxiliary
+    /// binaries don't affect the MSRV. If a custom compatibility check command is used, this option
+    /// is ignored.
+    #[arg(long, value_name = "NAME")]
+    pub bin: Option<String>,
+
+    /// Only check the library target, instead of the library and binaries
+    ///
+    /// If a custom compatibility check command is used, this option is ignored.
+    #[arg(long)]
+    pub lib: bool,
+
+    /// Vendor the dependencies once, with `cargo vendor`, and build each check offline from the
+    /// vendored sources
+    ///
+    /// Every toolchain is checked with the same dependencies, regardless of the network. Not
+    /// supported with containers or remote builders.
+    #[arg(long)]
+    pub vendor: bool,
+
+    /// Run the given script after each check, to decide whether the check passes, fails or is
+    /// skipped
+    ///
+    /// The script is given the Rust version as its argument, and the `CARGO_MSRV_VERSION`,
+    /// `CARGO_MSRV_TARGET`, `CARGO_MSRV_CHECK_EXIT_CODE` and `CARGO_MSRV_CHECK_OUTPUT` (the path of
+    /// a file with the output of the check) environment variables. Like with `git bisect run`, exit
+    /// code 0 passes the check, 125 skips it, and any other code up to 127 fails it.
+    #[arg(long, value_name = "SCRIPT")]
+    pub predicate: Option<Utf8PathBuf>,
+
     /// Supply a custom `check` command to be used by cargo msrv
     #[arg(last = true)]
     pub custom_check_opts: Option<Vec<String>>,
@@ -1,15 +1,33 @@
+use crate::cli::container_opts::ContainerOpts;
 use crate::cli::custom_check_opts::CustomCheckOpts;
+use crate::cli::nix_opts::NixOpts;
+use crate::cli::remote_opts::RemoteOpts;
 use crate::cli::rust_releases_opts::RustReleasesOpts;
 use crate::cli::shared_opts::SharedOpts;
+use crate::cli::tarball_opts::TarballOpts;
 use crate::cli::toolchain_opts::ToolchainOpts;
+use crate::context::graph::GraphFormat;
 use crate::context::list::ListMsrvVariant;
+use crate::context::report::ReportFormat;
+use crate::context::whatif::NewDependency;
+use crate::context::{LockfileStrategy, SearchMethod};
+use crate::control::ControlSource;
+use crate::deadline::parse_duration;
 use crate::manifest::bare_version::BareVersion;
+use crate::manifest::msrv_policy::ReleaseType;
+use crate::search_method::Shard;
+use camino::Utf8PathBuf;
 use clap::{Args, Parser, Subcommand};
 use std::ffi::{OsStr, OsString};
+use std::time::Duration;
 
+pub(crate) mod container_opts;
 pub(crate) mod custom_check_opts;
+pub(crate) mod nix_opts;
+pub(crate) mod remote_opts;
 pub(crate) mod rust_releases_opts;
 pub(crate) mod shared_opts;
+pub(crate) mod tarball_opts;
 pub(crate) mod toolchain_opts;
 
 #[derive(Debug, Parser)]
@@ -25,6 +43,15 @@ impl CargoCli {
         CargoCli::parse_from(modified_args)
     }
 
+    /// Like [`CargoCli::parse_args`], but returns the error, rather than exiting, for arguments
+    /// which are invalid.
+    pub fn try_parse_args<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(
+        args: I,
+    ) -> Result<Self, clap::Error> {
+        let modified_args = modify_args(args);
+        CargoCli::try_parse_from(modified_args)
+    }
+
     pub fn to_cargo_msrv_cli(self) -> CargoMsrvCli {
         self.subcommand
     }
@@ -104,18 +131,94 @@ pub struct CargoMsrvOpts {
 #[derive(Debug, Subcommand)]
 #[command(propagate_version = true)]
 pub enum SubCommand {
+    /// Find the update of Cargo.lock which first made your crate fail with a given toolchain
+    ///
+    /// The committed revisions of Cargo.lock are checked with the toolchain, using a binary search, from the lockfile of the working tree to the oldest committed revision, or the revision given by `--good`. The dependencies which changed between the last compatible and the first incompatible revision are reported. The manifests of the working tree are used for each revision, and your lockfile is restored afterwards.
+    #[command(name = "bisect-lockfile")]
+    BisectLockfile(BisectLockfileOpts),
+    /// Inspect the global cache of cargo-msrv
+    ///
+    /// The global cache holds the fetched crates.io index files and channel manifests. Its size is bounded by `--max-cache-size`: when a run leaves the cache larger, the least recently used files are evicted.
+    Cache(CacheOpts),
+    /// Check whether each declaration of the MSRV in the repository agrees with the Cargo manifest
+    ///
+    /// The `rust-version` of the crate and workspace manifests, the `msrv` of the clippy configuration, a Rust version pinned in `rust-toolchain.toml`, and `msrv` or `rust-version` values in the CI configuration are compared. Each declaration which disagrees is reported, with its file and line. CI jobs which are pinned to a Rust version older than the MSRV are reported as warnings.
+    CheckConsistency,
+    /// Remove the caches, stored runs, log files and target directories of cargo-msrv
+    ///
+    /// With `--toolchains`, the toolchains which were installed by cargo-msrv are uninstalled too. Use `--dry-run` to see what would be removed, and how much space it takes.
+    Clean(CleanOpts),
+    /// Estimate the MSRV without compiling, by scanning the source code for language and library features
+    ///
+    /// The estimate is approximate: it does not resolve method calls or expand macros, and does not take the MSRV of dependencies into account.
+    Estimate,
     /// Find the MSRV
     Find(FindOpts),
+    /// Render the dependency graph, with the MSRV's of dependencies
+    ///
+    /// Each dependency is colored by the Rust version it requires, and the paths to the dependencies which require the newest Rust version are highlighted.
+    Graph(GraphOpts),
+    /// Suggest the container images and distribution packages which provide the MSRV
+    ///
+    /// Lists the tags of the official `rust` container image for the MSRV, like `rust:1.70-slim`, and whether the Rust toolchain packaged by common Linux distributions is at least the MSRV.
+    Images(ImagesOpts),
+    /// Browse the runs stored by `cargo msrv find --save-run` or `cargo msrv verify --save-run`
+    ///
+    /// Without a run id, the stored runs are listed. With a run id, the checks of the run are shown, or with `--log`, the complete output of a single check.
+    Inspect(InspectOpts),
     /// Display the MSRV's of dependencies
     List(ListOpts),
+    /// Migrate the legacy `package.metadata.msrv` of each workspace member to `package.rust-version`
+    ///
+    /// The formatting of the manifests is preserved. A member inherits the `rust-version` of the workspace if `workspace.package.rust-version` is the same version. A `package.metadata.msrv` which conflicts with the `rust-version` of its package, or which is older than Rust 1.56, is reported as a warning, and left as is.
+    Migrate(MigrateOpts),
+    /// Combine the JSON outputs of sharded `find` runs into one MSRV
+    ///
+    /// Each file must contain the JSON output of `cargo msrv find --shard i/n`, and every shard of the search space must be present.
+    MergeResults(MergeResultsOpts),
+    /// List the dependencies with newer releases which require a newer Rust version than the MSRV
+    ///
+    /// These are the upgrades you cannot take without raising the MSRV. For each dependency, the newest release which is still compatible with the MSRV is listed too.
+    Outdated(OutdatedOpts),
+    /// Render a report of a run from its event log, as recorded with `--log-events`
+    ///
+    /// The report is rendered from the recorded events, so the checks are not run again. The report holds the result of the run, each check, with its first error if the Rust version is incompatible, and the warnings.
+    Report(ReportOpts),
     /// Set the MSRV of the current crate to a given Rust version
+    ///
+    /// For the root of a virtual workspace, i.e. a manifest without `[package]`, the `workspace.package.rust-version` is set, which members inherit with `rust-version.workspace = true`.
     Set(SetOpts),
     /// Show the MSRV of your crate, as specified in the Cargo manifest
+    ///
+    /// For the root of a virtual workspace, i.e. a manifest without `[package]`, the MSRV of each member is shown, as well as the `workspace.package.rust-version`.
     Show,
+    /// Update each declaration of the MSRV in the repository to the MSRV
+    ///
+    /// The declarations which `cargo msrv check-consistency` compares are updated, by default to the MSRV of the Cargo manifest. Either each declaration is updated, or, if a file can't be written, none is. The changed lines are summarized.
+    Sync(SyncOpts),
     /// Verify whether the MSRV is satisfiable.
     ///
     ///  The MSRV must be specified via the `--rust-version` option, or via the 'package.rust-version' or 'package.metadata.msrv' keys in the Cargo.toml manifest.
     Verify(VerifyOpts),
+    /// Analyse how a change to the dependencies would affect the MSRV, without changing your crate
+    #[command(name = "whatif")]
+    WhatIf(WhatIfOpts),
+}
+
+impl SubCommand {
+    /// The toolchain options of the subcommands which check toolchains.
+    pub fn toolchain_opts(&self) -> Option<&ToolchainOpts> {
+        match self {
+            SubCommand::BisectLockfile(opts) => Some(&opts.toolchain_opts),
+            SubCommand::Find(opts) => Some(&opts.toolchain_opts),
+            SubCommand::Verify(opts) => Some(&opts.toolchain_opts),
+            SubCommand::WhatIf(opts) => match &opts.action {
+                WhatIfAction::Add(opts) => Some(&opts.toolchain_opts),
+                WhatIfAction::Update(opts) => Some(&opts.toolchain_opts),
+            },
+            _ => None,
+        }
+    }
 }
 
 // Cli Options for top-level cargo-msrv (find) command
@@ -127,15 +230,49 @@ pub struct FindOpts {
     /// When the search space is sufficiently large, which is common, this is much
     /// faster than a linear search. A binary search will approximately halve the search
     /// space for each Rust version checked for compatibility.
-    #[arg(long, conflicts_with = "linear")]
+    #[arg(long, conflicts_with_all = ["linear", "hybrid", "search_method"])]
     pub bisect: bool,
 
     /// Use a linear search to find the MSRV
     ///
     /// This method checks toolchain from the most recent release to the earliest.
-    #[arg(long, conflicts_with = "bisect")]
+    #[arg(long, conflicts_with_all = ["bisect", "hybrid", "search_method"])]
     pub linear: bool,
 
+    /// Use the static MSRV estimate as a starting point, and confirm it by compiling
+    ///
+    /// This method first estimates the MSRV without compiling (see `cargo msrv estimate`), and then
+    /// only checks the estimated release, and the release right before it. If the estimate proves
+    /// wrong, it falls back to a binary search of the remaining releases.
+    #[arg(long, conflicts_with_all = ["bisect", "linear", "search_method"])]
+    pub hybrid: bool,
+
+    /// Set the method to find the MSRV with
+    ///
+    /// Besides `linear`, `bisect` and `hybrid`, like `--linear`, `--bisect` and `--hybrid`, the
+    /// MSRV can be searched starting from another guess than the static estimate: `declared`
+    /// starts from the declared MSRV, like `package.rust-version`, and `last-run` from the MSRV
+    /// which an earlier run found. As with `--hybrid`, only the guessed release and the release
+    /// right before it are checked if the guess is right, and otherwise the remaining releases are
+    /// bisected.
+    #[arg(long, value_enum, value_name = "METHOD")]
+    pub search_method: Option<SearchMethod>,
+
+    /// Only check a deterministic slice of the search space, as shard `i` of `n`
+    ///
+    /// The search space, ordered from most to least recent, is split into `n` contiguous slices,
+    /// where shard 1 takes the most recent releases. Each shard runs a linear search over its
+    /// slice, so multiple CI jobs can share the work. The JSON outputs of all shards can be
+    /// combined into one MSRV with `cargo msrv merge-results`.
+    ///
+    /// Implies `--linear`.
+    #[arg(
+        long,
+        value_name = "i/n",
+        conflicts_with_all = ["bisect", "hybrid", "search_method", "write_toolchain_file", "write_msrv", "sync"]
+    )]
+    pub shard: Option<Shard>,
+
     /// Pin the MSRV by writing the version to a rust-toolchain file
     ///
     /// The toolchain file will pin the Rust version for this crate.
@@ -150,6 +287,18 @@ pub struct FindOpts {
     #[arg(long)]
     pub ignore_lockfile: bool,
 
+    /// How to handle the lockfile, when the cargo of a toolchain can't read its version
+    ///
+    /// Such toolchains are always reported. Without a strategy, they are checked with the
+    /// lockfile as is.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "STRATEGY",
+        conflicts_with = "ignore_lockfile"
+    )]
+    pub lockfile_strategy: Option<LockfileStrategy>,
+
     /// Don't print the result of compatibility checks
     ///
     /// The feedback of a compatibility check can be useful to determine why a certain Rust
@@ -159,6 +308,90 @@ pub struct FindOpts {
     #[arg(long)]
     pub no_check_feedback: bool,
 
+    /// Compare the compiler output of the last incompatible release with the output of the MSRV
+    ///
+    /// Once the MSRV is found, the diagnostics which were emitted by the check of the most recent
+    /// incompatible release, but no longer by the check of the MSRV, are reported, and vice versa.
+    /// The diagnostic which disappeared usually points at the feature which determines the MSRV.
+    #[arg(long, conflicts_with = "shard")]
+    pub diff_errors: bool,
+
+    /// Report which editions and language features become available at the Rust versions after
+    /// the MSRV
+    ///
+    /// Once the MSRV is found, the newer editions and flagship language features are listed per
+    /// Rust version, up to the latest release, e.g. "Bumping to 1.65 unlocks let-else statements",
+    /// to weigh the benefit of raising the MSRV against its cost.
+    #[arg(long, conflicts_with = "shard")]
+    pub report_unlocks: bool,
+
+    /// Don't exclude the Rust versions of which cargo fails to parse the project
+    ///
+    /// By default, the manifests and cargo configuration are scanned for constructs which older
+    /// versions of cargo fail on, like edition 2021, inheritance from the workspace, or `dep:`
+    /// features. Rust versions older than the most recent of these are excluded from the search,
+    /// without being checked.
+    #[arg(long)]
+    pub no_cargo_gates: bool,
+
+    /// Store the output, diagnostics and duration of each check under `.cargo-msrv/runs/<run-id>/`
+    ///
+    /// The stored run can be browsed afterwards with `cargo msrv inspect`, e.g. after the CI job
+    /// which ran it has ended. The run is identified by the (UTC) time at which it started.
+    #[arg(long)]
+    pub save_run: bool,
+
+    /// Also run for the crates of the manifests matched by the given glob, each with its own result
+    ///
+    /// For crates which are not part of the workspace, like examples or tools which are excluded
+    /// from it, e.g. `--also 'examples/*/Cargo.toml'`. The glob is relative to the root of the
+    /// crate; a matched directory stands for its `Cargo.toml`. May be given multiple times.
+    #[arg(long, value_name = "GLOB")]
+    pub also: Vec<String>,
+
+    /// Find the MSRV for each of the given targets independently, and report them in a table
+    ///
+    /// Code which is gated on the target, like `#[cfg(windows)]`, can make the MSRV differ per
+    /// target, which a single MSRV hides. The highest of the MSRV's is reported as well, since it
+    /// is the MSRV of the crate across the targets.
+    ///
+    /// For example: --targets x86_64-unknown-linux-gnu,x86_64-pc-windows-msvc,wasm32-unknown-unknown
+    #[arg(
+        long,
+        value_name = "TARGET",
+        value_delimiter = ',',
+        conflicts_with_all = ["target", "shard", "write_msrv", "write_toolchain_file", "sync", "also"]
+    )]
+    pub targets: Vec<String>,
+
+    /// Read commands to pause, skip or abort the search while it runs, from `stdin`, or from a
+    /// Unix domain socket (on Windows, a named pipe) at the given path
+    ///
+    /// One command is read per line: `pause`, `resume`, `skip-current` or `abort`. A skipped
+    /// release is inconclusive; the search continues with the remaining releases.
+    #[arg(long, value_name = "SOURCE")]
+    pub control: Option<ControlSource>,
+
+    /// Stop the search once it has run for the given duration, like `20m` or `1h30m`
+    ///
+    /// Instead of the MSRV, the bounds of the MSRV which were narrowed down so far are reported,
+    /// e.g. that the MSRV is between Rust 1.63 and Rust 1.68, and cargo-msrv exits with code 3.
+    /// The check which is running once the duration expires, is interrupted.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub max_duration: Option<Duration>,
+
+    /// Continue the search which stopped early, e.g. since it was interrupted
+    ///
+    /// The Rust versions which were checked by the earlier search, as saved to
+    /// `.cargo-msrv/state.json`, are not checked again. Without a saved state, the search starts
+    /// anew.
+    ///
+    /// With `--targets`, the MSRV of each target which was found by an earlier run, as saved to
+    /// `.cargo-msrv/matrix.json`, is reused if it still holds, and only the other targets are
+    /// searched.
+    #[arg(long, conflicts_with = "shard")]
+    pub resume: bool,
+
     /// Write the MSRV to the Cargo manifest
     ///
     /// For toolchains which include a Cargo version which supports the rust-version field,
@@ -167,22 +400,189 @@ pub struct FindOpts {
     #[arg(long)]
     pub write_msrv: bool,
 
+    /// Append an entry on the change of the MSRV to the changelog, when the MSRV is written
+    ///
+    /// The entry is appended to the `## [Unreleased]` section of `CHANGELOG.md`, unless the
+    /// `changelog` table of the `cargo-msrv` metadata configures another `file`, `section` or
+    /// `template`. Nothing is appended if the MSRV did not change.
+    #[arg(long, requires = "write_msrv")]
+    pub changelog: bool,
+
+    /// Report the entry which `--changelog` would append to the changelog, without writing it
+    #[arg(long, requires = "write_msrv", conflicts_with = "changelog")]
+    pub changelog_dry_run: bool,
+
+    /// Update each declaration of the MSRV in the repository to the MSRV which was found
+    ///
+    /// Like `cargo msrv sync`, which updates the declarations which
+    /// `cargo msrv check-consistency` compares, like the `msrv` of `clippy.toml`. Combine with
+    /// `--write-msrv` to write the Cargo manifest too, if it does not declare an MSRV yet.
+    #[arg(long)]
+    pub sync: bool,
+
     #[command(flatten)]
     pub rust_releases_opts: RustReleasesOpts,
 
     #[command(flatten)]
     pub toolchain_opts: ToolchainOpts,
 
+    #[command(flatten)]
+    pub container_opts: ContainerOpts,
+
+    #[command(flatten)]
+    pub nix_opts: NixOpts,
+
+    #[command(flatten)]
+    pub remote_opts: RemoteOpts,
+
+    #[command(flatten)]
+    pub tarball_opts: TarballOpts,
+
+    #[command(flatten)]
+    pub custom_check_opts: CustomCheckOpts,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Bisect lockfile options")]
+pub struct BisectLockfileOpts {
+    /// The Rust version of the toolchain with which your crate fails
+    #[arg(long, value_name = "rust-version")]
+    pub toolchain: BareVersion,
+
+    /// A commit of which the lockfile is known to be compatible with the toolchain
+    ///
+    /// Revisions of the lockfile which were committed before it are not checked. When not given,
+    /// the oldest committed revision of the lockfile is the lower bound of the search.
+    #[arg(long, value_name = "REV")]
+    pub good: Option<String>,
+
+    /// Don't print the result of the compatibility checks
+    #[arg(long)]
+    pub no_check_feedback: bool,
+
+    #[command(flatten)]
+    pub toolchain_opts: ToolchainOpts,
+
     #[command(flatten)]
     pub custom_check_opts: CustomCheckOpts,
 }
 
+#[derive(Debug, Args)]
+pub struct CacheOpts {
+    #[command(subcommand)]
+    pub action: CacheAction,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheAction {
+    /// Show the size of the global cache, per section, and when its files were last used
+    Stats,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Clean options")]
+pub struct CleanOpts {
+    /// Only list what would be removed, with the size per category
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Also uninstall the toolchains which were installed by cargo-msrv
+    ///
+    /// Only the toolchains which were not installed yet, when cargo-msrv installed them, are
+    /// uninstalled. Toolchains installed by cargo-msrv before this was recorded, are kept.
+    #[arg(long)]
+    pub toolchains: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Graph options")]
+pub struct GraphOpts {
+    /// The format in which the graph is rendered
+    #[arg(long, value_enum, default_value_t)]
+    pub format: GraphFormat,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Images options")]
+pub struct ImagesOpts {
+    /// The MSRV to suggest images and packages for
+    ///
+    /// If not set, the MSRV will be parsed from the Cargo manifest instead.
+    #[arg(long, value_name = "rust-version")]
+    pub rust_version: Option<BareVersion>,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Inspect options")]
+pub struct InspectOpts {
+    /// The run to inspect, as listed by `cargo msrv inspect`
+    ///
+    /// Use `latest` to inspect the most recent run.
+    #[arg(value_name = "RUN_ID")]
+    pub run_id: Option<String>,
+
+    /// Print the complete output of the check of the given Rust version
+    #[arg(long, value_name = "rust-version", requires = "run_id")]
+    pub log: Option<BareVersion>,
+}
+
 #[derive(Debug, Args)]
 #[command(next_help_heading = "List options")]
 pub struct ListOpts {
     /// Display the MSRV's of crates that your crate depends on
     #[arg(long, value_enum, default_value_t)]
     pub variant: ListMsrvVariant,
+
+    /// Only list the direct dependencies of your crate
+    ///
+    /// Direct dependencies are the dependencies you can actually change, by updating or
+    /// replacing them in your Cargo manifest. Equivalent to `--depth 1`.
+    #[arg(long, conflicts_with = "depth")]
+    pub direct_only: bool,
+
+    /// Only list dependencies up to the given depth in the dependency graph
+    ///
+    /// Direct dependencies have a depth of 1, their dependencies a depth of 2, and so on.
+    /// The depth of a dependency which is included through multiple paths, is the length
+    /// of its shortest path.
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub depth: Option<u32>,
+
+    /// Only list dependencies which require a Rust version newer than the given version
+    ///
+    /// Dependencies which do not specify a Rust version are not listed.
+    #[arg(long, value_name = "rust-version")]
+    pub min_rust_version: Option<BareVersion>,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Merge results options")]
+pub struct MergeResultsOpts {
+    /// The files with the JSON output of each shard
+    #[arg(value_name = "FILE", required = true)]
+    pub files: Vec<Utf8PathBuf>,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Report options")]
+pub struct ReportOpts {
+    /// The event log of the run, as recorded with `--log-events`
+    #[arg(long, value_name = "FILE")]
+    pub from: Utf8PathBuf,
+
+    /// The format in which the report is rendered
+    #[arg(long, value_enum, default_value_t)]
+    pub format: ReportFormat,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Outdated options")]
+pub struct OutdatedOpts {
+    /// The MSRV to compare the releases of dependencies against
+    ///
+    /// If not set, the MSRV will be parsed from the Cargo manifest instead.
+    #[arg(long, value_name = "rust-version")]
+    pub rust_version: Option<BareVersion>,
 }
 
 #[derive(Debug, Args)]
@@ -197,10 +597,53 @@ pub struct SetOpts {
     #[arg(value_name = "MSRV")]
     pub msrv: BareVersion,
 
+    /// Also update the other declarations of the MSRV, like the MSRV badge of the README
+    ///
+    /// Each badge, or mention of the MSRV, in the README which `cargo msrv verify --check-readme`
+    /// recognizes, is updated to the MSRV, as well as each value located by the `sync-rules` of
+    /// the `cargo-msrv` metadata table.
+    #[arg(long)]
+    pub sync: bool,
+
+    /// Append an entry on the change of the MSRV to the changelog
+    ///
+    /// The entry is appended to the `## [Unreleased]` section of `CHANGELOG.md`, unless the
+    /// `changelog` table of the `cargo-msrv` metadata configures another `file`, `section` or
+    /// `template`. Nothing is appended if the MSRV did not change.
+    #[arg(long)]
+    pub changelog: bool,
+
+    /// Report the entry which `--changelog` would append to the changelog, without writing it
+    #[arg(long, conflicts_with = "changelog")]
+    pub changelog_dry_run: bool,
+
     #[command(flatten)]
     pub rust_releases_opts: RustReleasesOpts,
 }
 
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Migrate options")]
+pub struct MigrateOpts {
+    /// Only list the migrations, without writing them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Sync options")]
+pub struct SyncOpts {
+    /// The version to which each declaration is updated
+    ///
+    /// Defaults to the MSRV declared by the Cargo manifest. To update the declarations to a
+    /// freshly computed MSRV, use `cargo msrv find --sync` instead.
+    #[arg(value_name = "MSRV")]
+    pub msrv: Option<BareVersion>,
+
+    /// Only summarize the changes, without writing them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 #[derive(Debug, Args)]
 #[command(next_help_heading = "Verify options")]
 pub struct VerifyOpts {
@@ -208,6 +651,18 @@ pub struct VerifyOpts {
     #[arg(long)]
     pub ignore_lockfile: bool,
 
+    /// How to handle the lockfile, when the cargo of a toolchain can't read its version
+    ///
+    /// Such toolchains are always reported. Without a strategy, they are checked with the
+    /// lockfile as is.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "STRATEGY",
+        conflicts_with = "ignore_lockfile"
+    )]
+    pub lockfile_strategy: Option<LockfileStrategy>,
+
     /// Don't print the result of compatibility checks
     ///
     /// The feedback of a compatibility check can be useful to determine why a certain Rust
@@ -217,15 +672,206 @@ pub struct VerifyOpts {
     #[arg(long)]
     pub no_check_feedback: bool,
 
+    /// Store the output, diagnostics and duration of each check under `.cargo-msrv/runs/<run-id>/`
+    ///
+    /// The stored run can be browsed afterwards with `cargo msrv inspect`, e.g. after the CI job
+    /// which ran it has ended. The run is identified by the (UTC) time at which it started.
+    #[arg(long)]
+    pub save_run: bool,
+
+    /// Also run for the crates of the manifests matched by the given glob, each with its own result
+    ///
+    /// For crates which are not part of the workspace, like examples or tools which are excluded
+    /// from it, e.g. `--also 'examples/*/Cargo.toml'`. The glob is relative to the root of the
+    /// crate; a matched directory stands for its `Cargo.toml`. May be given multiple times.
+    #[arg(long, value_name = "GLOB")]
+    pub also: Vec<String>,
+
+    /// Find the MSRV first, and fail unless the Rust version to verify equals it
+    ///
+    /// Fails when the Rust version is lower than the MSRV which was found, since the crate does
+    /// not build with it, or higher by more than `--tolerance` minor versions, since the crate
+    /// would needlessly claim to require a newer Rust version.
+    #[arg(long, conflicts_with = "rustc")]
+    pub find: bool,
+
+    /// Find the MSRV first, and report how much slack the Rust version to verify has
+    ///
+    /// The slack is the number of minor releases between the MSRV which was found and the Rust
+    /// version to verify. When there is slack, the crate also builds with older Rust versions
+    /// than it claims to require, and a lower MSRV is suggested. Unlike `--find`, slack does not
+    /// fail the verification.
+    #[arg(long, conflicts_with_all = ["rustc", "find"])]
+    pub slack: bool,
+
+    /// How many minor versions the Rust version may be higher than the MSRV found with `--find`
+    #[arg(
+        long,
+        value_name = "MINOR_VERSIONS",
+        requires = "find",
+        default_value_t = 0
+    )]
+    pub tolerance: u64,
+
+    /// Find the MSRV first, and fail if it raises the Rust version for a type of release which may not raise the MSRV
+    ///
+    /// The MSRV policy of the crate, the `allow-msrv-bump` key in the `cargo-msrv` metadata table
+    /// of the Cargo manifest, lists the types of releases which may raise the MSRV. Without a
+    /// policy, minor and major releases may raise it, but patch releases may not. The Rust
+    /// version to verify is the MSRV of the previous release.
+    #[arg(
+        long,
+        value_name = "RELEASE_TYPE",
+        conflicts_with_all = ["rustc", "find", "slack"]
+    )]
+    pub release_type: Option<ReleaseType>,
+
+    /// Don't exclude the Rust versions of which cargo fails to parse the project, when finding the MSRV first
+    ///
+    /// Like `cargo msrv find --no-cargo-gates`, for `--find`, `--slack` and `--release-type`.
+    #[arg(long)]
+    pub no_cargo_gates: bool,
+
+    /// Fail when a declaration of the MSRV disagrees with the Cargo manifest
+    ///
+    /// The declarations are found like `cargo msrv check-consistency` finds them, e.g. in
+    /// `clippy.toml`, `rust-toolchain.toml` and the CI configuration. This check runs before the
+    /// toolchain is checked.
+    #[arg(long)]
+    pub check_consistency: bool,
+
+    /// Fail when an MSRV badge, or a mention of the MSRV, in the README disagrees with the Cargo manifest
+    ///
+    /// Static shields.io badges, like `https://img.shields.io/badge/MSRV-1.60-blue`, and plain
+    /// text, like `MSRV: 1.60`, are recognized. Use `cargo msrv set --sync` to update them.
+    #[arg(long)]
+    pub check_readme: bool,
+
+    /// Fail when the fingerprint of the environment differs from the given fingerprint id
+    ///
+    /// The fingerprint, reported with the result of `cargo msrv find`, covers everything which
+    /// affects the MSRV, like the lockfile, the sources, the target and the features of the check.
+    /// A different fingerprint means an earlier result may no longer apply.
+    #[arg(long, value_name = "ID")]
+    pub expect_fingerprint: Option<String>,
+
     #[command(flatten)]
     pub rust_releases_opts: RustReleasesOpts,
 
     /// The Rust version, to check against for toolchain compatibility
     ///
-    /// If not set, the MSRV will be parsed from the Cargo manifest instead.
+    /// If not set, the Rust version is read from the `MSRV` environment variable, if it is set and
+    /// not empty. Otherwise, the MSRV will be parsed from the Cargo manifest instead.
     #[arg(long, value_name = "rust-version")]
     pub rust_version: Option<BareVersion>,
 
+    /// Verify with the given `rustc`, instead of a toolchain installed by rustup
+    ///
+    /// Checks whether the crate builds with a `rustc` and `cargo` pair which is not managed by
+    /// rustup, for example the Rust toolchain packaged by a Linux distribution. The Rust version
+    /// (and unless `--target` is given, the target) is determined from `rustc -vV`.
+    ///
+    /// Must be given together with `--cargo`.
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "cargo",
+        conflicts_with_all = [
+            "rust_version",
+            "require_component",
+            "container_backend",
+            "nix_backend",
+            "remote_backend",
+        ]
+    )]
+    pub rustc: Option<Utf8PathBuf>,
+
+    /// Verify with the given `cargo`, instead of a toolchain installed by rustup
+    ///
+    /// Must be given together with `--rustc`.
+    #[arg(long, value_name = "PATH", requires = "rustc")]
+    pub cargo: Option<Utf8PathBuf>,
+
+    #[command(flatten)]
+    pub toolchain_opts: ToolchainOpts,
+
+    #[command(flatten)]
+    pub container_opts: ContainerOpts,
+
+    #[command(flatten)]
+    pub nix_opts: NixOpts,
+
+    #[command(flatten)]
+    pub remote_opts: RemoteOpts,
+
+    #[command(flatten)]
+    pub tarball_opts: TarballOpts,
+
+    #[command(flatten)]
+    pub custom_check_opts: CustomCheckOpts,
+}
+
+#[derive(Debug, Args)]
+pub struct WhatIfOpts {
+    #[command(subcommand)]
+    pub action: WhatIfAction,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum WhatIfAction {
+    /// Analyse whether adding a dependency would raise the MSRV
+    ///
+    /// The dependency is added to a temporary copy of your crate, so your crate is left untouched.
+    Add(WhatIfAddOpts),
+    /// Analyse whether updating the lockfile would raise the MSRV
+    ///
+    /// The MSRV with the versions pinned by your Cargo.lock is compared with the MSRV with the newest versions allowed by your version requirements. The lockfile is updated in a temporary copy of your crate, so your crate is left untouched.
+    Update(WhatIfUpdateOpts),
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "What-if options")]
+pub struct WhatIfAddOpts {
+    /// The dependency to add, optionally with a version requirement, e.g. `serde@1.0`
+    ///
+    /// If no version requirement is given, the newest compatible version is used.
+    #[arg(value_name = "CRATE[@VERSION]")]
+    pub dependency: NewDependency,
+
+    /// Also check whether your crate, with the added dependency, still compiles with the
+    /// current MSRV
+    ///
+    /// The current MSRV is the newest Rust version required by your crate or its dependencies,
+    /// before the dependency is added.
+    #[arg(long)]
+    pub verify: bool,
+
+    /// Don't print the result of the compatibility check
+    #[arg(long, requires = "verify")]
+    pub no_check_feedback: bool,
+
+    #[command(flatten)]
+    pub toolchain_opts: ToolchainOpts,
+
+    #[command(flatten)]
+    pub custom_check_opts: CustomCheckOpts,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "What-if options")]
+pub struct WhatIfUpdateOpts {
+    /// Also check whether your crate, with the updated lockfile, still compiles with the
+    /// current MSRV
+    ///
+    /// The current MSRV is the newest Rust version required by your crate or its dependencies,
+    /// with the versions pinned by your Cargo.lock.
+    #[arg(long)]
+    pub verify: bool,
+
+    /// Don't print the result of the compatibility check
+    #[arg(long, requires = "verify")]
+    pub no_check_feedback: bool,
+
     #[command(flatten)]
     pub toolchain_opts: ToolchainOpts,
 
@@ -393,6 +1039,117 @@ mod tests {
                 });
             }
 
+            #[test]
+            fn has_shard() {
+                let cargo = CargoCli::parse_args(["cargo", "msrv", "find", "--shard", "2/3"]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(find_opts.shard, Some(Shard::new(2, 3).unwrap()));
+                });
+            }
+
+            #[yare::parameterized(
+                bisect = { "--bisect" },
+                hybrid = { "--hybrid" },
+                write_msrv = { "--write-msrv" },
+                sync = { "--sync" },
+                write_toolchain_file = { "--write-toolchain-file" },
+            )]
+            fn shard_conflicts(flag: &str) {
+                let result =
+                    CargoCli::try_parse_from(["cargo", "msrv", "find", "--shard", "1/2", flag]);
+
+                assert!(result.is_err());
+            }
+
+            #[yare::parameterized(
+                container = { "--container" },
+                container_image = { "--container-image=rust:{version}" },
+                remote = { "--remote=user@host" },
+            )]
+            fn jobs_conflicts(flag: &str) {
+                let result =
+                    CargoCli::try_parse_from(["cargo", "msrv", "find", "--jobs", "2", flag]);
+
+                assert!(result.is_err());
+            }
+
+            #[yare::parameterized(
+                declared = { "declared", SearchMethod::Declared },
+                last_run = { "last-run", SearchMethod::LastRun },
+                hybrid = { "hybrid", SearchMethod::Hybrid },
+            )]
+            fn has_search_method(value: &str, expected: SearchMethod) {
+                let cargo =
+                    CargoCli::parse_args(["cargo", "msrv", "find", "--search-method", value]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(find_opts.search_method, Some(expected));
+                });
+            }
+
+            #[yare::parameterized(
+                bisect = { "--bisect" },
+                linear = { "--linear" },
+                hybrid = { "--hybrid" },
+            )]
+            fn search_method_conflicts(flag: &str) {
+                let result = CargoCli::try_parse_from([
+                    "cargo",
+                    "msrv",
+                    "find",
+                    "--search-method",
+                    "declared",
+                    flag,
+                ]);
+
+                assert!(result.is_err());
+            }
+
+            #[test]
+            fn has_targets() {
+                let cargo = CargoCli::parse_args([
+                    "cargo",
+                    "msrv",
+                    "find",
+                    "--targets",
+                    "x86_64-unknown-linux-gnu,wasm32-unknown-unknown",
+                ]);
+                let cargo_msrv = cargo.to_cargo_msrv_cli();
+                let opts = cargo_msrv.to_opts();
+
+                assert_find_opts(opts, |find_opts| {
+                    assert_eq!(
+                        find_opts.targets,
+                        ["x86_64-unknown-linux-gnu", "wasm32-unknown-unknown"]
+                    );
+                });
+            }
+
+            #[yare::parameterized(
+                target = { &["--target", "x86_64-unknown-linux-gnu"] },
+                shard = { &["--shard", "1/2"] },
+                write_msrv = { &["--write-msrv"] },
+                sync = { &["--sync"] },
+                also = { &["--also", "fuzz"] },
+            )]
+            fn targets_conflicts(flags: &[&str]) {
+                let args = [
+                    "cargo",
+                    "msrv",
+                    "find",
+                    "--targets",
+                    "wasm32-unknown-unknown",
+                ];
+                let result = CargoCli::try_parse_from(args.iter().chain(flags));
+
+                assert!(result.is_err());
+            }
+
             // todo: rust-releases opts
 
             // todo: toolchain opts
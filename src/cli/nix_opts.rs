@@ -0,0 +1,39 @@
+use clap::{ArgGroup, Args};
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Nix options")]
+#[command(group(
+    ArgGroup::new("nix_backend")
+        .args(["nix", "nix_expr", "nix_installable"])
+        .multiple(true)
+        .conflicts_with("container_backend")
+))]
+pub struct NixOpts {
+    /// Provide the toolchains with nix, instead of installing them with rustup
+    ///
+    /// Each toolchain is materialized by `nix shell`, from the Nix expression given by --nix-expr,
+    /// or the flake installable given by --nix-installable. By default, the toolchains of
+    /// oxalica/rust-overlay are used. Implied by the other nix options.
+    #[arg(long, global = true)]
+    pub nix: bool,
+
+    /// The Nix expression which evaluates to the toolchain of a Rust version
+    ///
+    /// The `{version}` placeholder is replaced by the Rust version of the toolchain, and the
+    /// optional `{target}` placeholder by its target. Pin the inputs of the expression for
+    /// bit-reproducible toolchains.
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        conflicts_with = "nix_installable",
+        global = true
+    )]
+    pub nix_expr: Option<String>,
+
+    /// The flake installable which provides the toolchain of a Rust version
+    ///
+    /// The `{version}` placeholder is replaced by the Rust version of the toolchain, and the
+    /// optional `{target}` placeholder by its target.
+    #[arg(long, value_name = "TEMPLATE", global = true)]
+    pub nix_installable: Option<String>,
+}
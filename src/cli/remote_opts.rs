@@ -0,0 +1,28 @@
+use clap::{ArgGroup, Args};
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Remote options")]
+#[command(group(
+    ArgGroup::new("remote_backend")
+        .args(["remote", "remote_dir"])
+        .multiple(true)
+        .conflicts_with_all(["container_backend", "nix_backend", "memory_limit", "cpu_limit", "jobs"])
+))]
+pub struct RemoteOpts {
+    /// Run the compatibility checks on a remote builder, over ssh
+    ///
+    /// The crate is synced to the remote builder with rsync, after which the toolchains are
+    /// installed, and the checks are run there, with rustup. Requires `ssh` and `rsync`, and
+    /// key based authentication (for example with ssh-agent).
+    ///
+    /// For example: --remote user@builder.example.com
+    #[arg(long, value_name = "DESTINATION", global = true)]
+    pub remote: Option<String>,
+
+    /// The directory on the remote builder to sync the crate to
+    ///
+    /// Relative paths are relative to the home directory of the remote user. Defaults to
+    /// `.cargo-msrv/remote/<crate directory name>`.
+    #[arg(long, value_name = "PATH", requires = "remote", global = true)]
+    pub remote_dir: Option<String>,
+}
@@ -1,8 +1,11 @@
+use crate::context::ChainedReleaseSource;
+use crate::deadline::parse_duration;
 use crate::manifest::bare_version;
 use crate::manifest::bare_version::BareVersion;
-use crate::ReleaseSource;
+use camino::Utf8PathBuf;
 use clap::Args;
 use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Debug, Args)]
 #[command(next_help_heading = "Rust releases options")]
@@ -25,11 +28,57 @@ pub struct RustReleasesOpts {
     pub max: Option<BareVersion>,
 
     /// Include all patch releases, instead of only the last
-    #[arg(long)]
+    ///
+    /// The MSRV is then reported, and written, as a full `major.minor.patch` version.
+    #[arg(long, visible_alias = "include-patch-releases")]
     pub include_all_patch_releases: bool,
 
-    #[arg(long, value_enum, default_value_t, value_name = "SOURCE")]
-    pub release_source: ReleaseSource,
+    /// Include the current beta release, like `1.83.0-beta.3`, as a candidate
+    ///
+    /// A pre-release is checked like any release, but it is never reported, or written, as the
+    /// MSRV. Useful to check the compatibility with the upcoming release in the same run.
+    #[arg(long)]
+    pub include_prerelease: bool,
+
+    /// Include a release which is on the denylist, of releases known to be broken
+    ///
+    /// A two component `major.minor` version allows each of its patch releases. May be given
+    /// multiple times, or as a comma separated list.
+    #[arg(long, value_delimiter = ',', value_name = "VERSION_SPEC")]
+    pub allow_version: Vec<BareVersion>,
+
+    /// The sources of the Rust releases, in order of preference
+    ///
+    /// Each source is one of `rust-changelog`, `rust-dist` or `snapshot`, optionally with the time
+    /// it may take to serve the releases, like `rust-dist:30s`. When a source fails, or takes
+    /// longer, the next source is used instead.
+    #[arg(
+        long = "release-source",
+        value_delimiter = ',',
+        default_value = "rust-changelog",
+        value_name = "SOURCE[:TIMEOUT]"
+    )]
+    pub release_sources: Vec<ChainedReleaseSource>,
+
+    /// A file with a Rust release on each line, for the `snapshot` release source
+    ///
+    /// Empty lines, and lines starting with `#`, are ignored.
+    #[arg(long, value_name = "FILE")]
+    pub release_snapshot: Option<Utf8PathBuf>,
+
+    /// How long the cached index of Rust releases is used, before it is fetched anew [default: 1h]
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub index_ttl: Option<Duration>,
+
+    /// Fetch the index of Rust releases anew, even if the cached index is recent
+    #[arg(long, conflicts_with = "frozen_index")]
+    pub refresh_index: bool,
+
+    /// Never fetch the index of Rust releases, but only use the cached index, or a release snapshot
+    ///
+    /// Fails if none of the release sources has a cached index, regardless of its age.
+    #[arg(long)]
+    pub frozen_index: bool,
 }
 
 #[derive(Clone, Debug)]
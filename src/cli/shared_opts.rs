@@ -1,7 +1,11 @@
-use crate::context::{OutputFormat, TracingTargetOption};
+use crate::cache::parse_size;
+use crate::context::{ColorChoice, OutputFormat, TracingTargetOption};
 use crate::log_level::LogLevel;
+use crate::reporter::formatting::locale_supports_unicode;
+use crate::reporter::{Locale, MatrixReportFormat};
 use clap::{ArgGroup, Args, ValueHint};
-use std::path::PathBuf;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
 // Cli Options shared between subcommands
 #[derive(Debug, Args)]
@@ -26,6 +30,9 @@ pub struct SharedOpts {
 
     #[command(flatten)]
     pub debug_output_opts: DebugOutputOpts,
+
+    #[command(flatten)]
+    pub global_cache_opts: GlobalCacheOpts,
 }
 
 #[derive(Debug, Args)]
@@ -44,16 +51,147 @@ pub struct UserOutputOpts {
     /// Disable user output
     #[arg(long, global = true, conflicts_with = "output_format")]
     no_user_output: bool,
+
+    /// Pretty print the events of the json output format over multiple lines
+    ///
+    /// Implies `--output-format json`. By default, each event is printed on a single line.
+    #[arg(long, global = true, conflicts_with = "no_user_output")]
+    json_pretty: bool,
+
+    /// Also stream the events, as newline delimited JSON, to the Unix domain socket (or on
+    /// Windows, the named pipe) at the given path
+    ///
+    /// The events are streamed in addition to the regular user output. The socket must already be
+    /// listening when cargo-msrv starts.
+    #[arg(long, value_name = "PATH", global = true, value_hint = ValueHint::FilePath)]
+    event_socket: Option<PathBuf>,
+
+    /// Also record the events, as newline delimited JSON, to the file at the given path
+    ///
+    /// The events are recorded in addition to the regular user output. Use `cargo msrv report`
+    /// to render a report from the recorded events afterwards.
+    #[arg(long, value_name = "PATH", global = true, value_hint = ValueHint::FilePath)]
+    log_events: Option<PathBuf>,
+
+    /// Don't write the result as outputs of the GitHub Actions step
+    ///
+    /// When `GITHUB_OUTPUT` is set, as it is in GitHub Actions, the `msrv`, `verified` and
+    /// `checked-versions` outputs are written to it by default.
+    #[arg(long, global = true)]
+    no_github_output: bool,
+
+    /// Don't annotate the Buildkite build with the result
+    ///
+    /// When `BUILDKITE` is `true`, as it is in Buildkite, the build is annotated with the result
+    /// and the checked Rust versions by default.
+    #[arg(long, global = true)]
+    no_buildkite_annotation: bool,
+
+    /// Write a matrix of the outcome of each check to the given file, once cargo-msrv finishes
+    ///
+    /// The checked Rust versions are the rows, and the targets (see `find --targets`) and
+    /// feature sets (see `--check-cmd hack-each-feature`) are the columns, with a pass, fail or
+    /// skip cell for each checked combination. The format is chosen by the extension of the file,
+    /// unless `--matrix-report-format` is given.
+    #[arg(long, value_name = "PATH", global = true, value_hint = ValueHint::FilePath)]
+    matrix_report: Option<PathBuf>,
+
+    /// Set the format of the matrix report
+    ///
+    /// By default, a `.md` file is written as markdown, a `.json` file as json, and other files as
+    /// a table.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        global = true,
+        requires = "matrix_report"
+    )]
+    matrix_report_format: Option<MatrixReportFormat>,
+
+    /// Restrict the human output to ASCII characters
+    ///
+    /// Replaces the spinner, the borders of tables, and other non-ASCII symbols, for terminals
+    /// and CI log viewers which mangle Unicode. Enabled by default if the locale (`LC_ALL`,
+    /// `LC_CTYPE` or `LANG`) does not use UTF-8.
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Set the language of the human output
+    ///
+    /// Supported are `en` (the default), `de` and `nl`. The other output formats are not
+    /// localized.
+    #[arg(long, value_name = "LOCALE", global = true)]
+    locale: Option<Locale>,
+
+    /// Set when the human output is colored
+    ///
+    /// By default, the output is colored if it is written to a terminal, unless the `NO_COLOR`
+    /// environment variable is set, or `CLICOLOR` is `0`. With `CLICOLOR_FORCE`, it is colored
+    /// regardless.
+    #[arg(long, value_enum, default_value_t, value_name = "WHEN", global = true)]
+    color: ColorChoice,
 }
 
 impl UserOutputOpts {
     pub fn effective_output_format(&self) -> OutputFormat {
         if self.no_user_output {
             OutputFormat::None
+        } else if self.json_pretty {
+            OutputFormat::Json
         } else {
             self.output_format
         }
     }
+
+    pub fn json_pretty(&self) -> bool {
+        self.json_pretty
+    }
+
+    pub fn event_socket(&self) -> Option<&Path> {
+        self.event_socket.as_deref()
+    }
+
+    pub fn log_events(&self) -> Option<&Path> {
+        self.log_events.as_deref()
+    }
+
+    pub fn github_output(&self) -> bool {
+        !self.no_github_output
+    }
+
+    pub fn buildkite_annotation(&self) -> bool {
+        !self.no_buildkite_annotation
+    }
+
+    /// The file to which the matrix report is written, if any, and its format.
+    pub fn matrix_report(&self) -> Option<(&Path, MatrixReportFormat)> {
+        self.matrix_report.as_deref().map(|path| {
+            let format = self
+                .matrix_report_format
+                .unwrap_or_else(|| MatrixReportFormat::from_path(path));
+
+            (path, format)
+        })
+    }
+
+    /// Whether the human output is restricted to ASCII, by `--ascii`, or because the locale does
+    /// not support Unicode.
+    pub fn ascii(&self) -> bool {
+        self.ascii || !locale_supports_unicode(|name| std::env::var(name).ok())
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale.unwrap_or_default()
+    }
+
+    /// Whether the human output is colored, by `--color`, or the environment.
+    pub fn color(&self) -> bool {
+        self.color.enables_color(
+            |name| std::env::var(name).ok(),
+            std::io::stderr().is_terminal(),
+        )
+    }
 }
 
 #[derive(Debug, Args)]
@@ -76,4 +214,41 @@ pub struct DebugOutputOpts {
     /// Specify the severity of logs which should be
     #[arg(long, value_enum, default_value_t, value_name = "LEVEL", global = true)]
     pub log_level: LogLevel,
+
+    /// The size above which the log file is rotated, e.g. `1MiB` [default: 10MiB]
+    ///
+    /// The log file is renamed to `cargo-msrv-log.1`, and earlier rotated log files are renamed to
+    /// the next number, once writing to it would make it larger than this size.
+    #[arg(long, value_name = "SIZE", value_parser = parse_size, global = true)]
+    pub max_log_size: Option<u64>,
+
+    /// The amount of rotated log files which is kept [default: 5]
+    #[arg(long, value_name = "COUNT", global = true)]
+    pub max_log_files: Option<usize>,
+
+    /// Export the traces of the run to an OpenTelemetry collector, with OTLP over HTTP
+    ///
+    /// The traces are sent to the `/v1/traces` path of the given base URL, like
+    /// `http://localhost:4318`. Defaults to `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`, or
+    /// `OTEL_EXPORTER_OTLP_ENDPOINT`, if set.
+    #[arg(long, value_name = "URL", global = true)]
+    pub otlp_endpoint: Option<String>,
+
+    /// Record the spans of the run, like downloads and checks, to the given file, in the trace
+    /// event format of Chrome
+    ///
+    /// The file can be opened in `chrome://tracing` or Perfetto.
+    #[arg(long, value_name = "PATH", global = true, value_hint = ValueHint::FilePath)]
+    pub trace_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Cache options")]
+pub struct GlobalCacheOpts {
+    /// The maximum size of the global cache, e.g. `500MiB` or `2G` [default: 1GiB]
+    ///
+    /// When a run leaves the cache larger than this size, the least recently used files are
+    /// evicted. Can also be set with the `CARGO_MSRV_MAX_CACHE_SIZE` environment variable.
+    #[arg(long, value_name = "SIZE", value_parser = parse_size, global = true)]
+    pub max_cache_size: Option<u64>,
 }
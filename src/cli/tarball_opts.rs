@@ -0,0 +1,21 @@
+use camino::Utf8PathBuf;
+use clap::Args;
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Tarball options")]
+pub struct TarballOpts {
+    /// Install the toolchains from a directory of official standalone tarballs, instead of
+    /// downloading them with rustup
+    ///
+    /// For build machines without network access. The directory holds a tarball per Rust version,
+    /// like `rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz`, each next to its `.sha256` file, against
+    /// which it is verified before it is installed. The Rust versions of the tarballs make up the
+    /// search space, so the release index is not fetched. Requires `tar`.
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with_all = ["container_backend", "nix_backend", "remote_backend"],
+        global = true
+    )]
+    pub toolchain_tarballs: Option<Utf8PathBuf>,
+}
@@ -1,4 +1,7 @@
+use crate::cache::parse_size;
+use crate::resource_limits::parse_cpus;
 use clap::Args;
+use std::num::NonZeroUsize;
 
 // Cli Options for commands which invoke Rust toolchains, such as the top level cargo msrv command
 // (find) or cargo msrv verify
@@ -17,4 +20,73 @@ pub struct ToolchainOpts {
     /// For example: --component rustc --component cargo
     #[arg(long, value_name = "COMPONENT", global = true)]
     pub component: Vec<String>,
+
+    /// Components which must be available for a toolchain to be considered
+    ///
+    /// Toolchains for which these components were not distributed (for the given target) are
+    /// excluded from the search space. The components are installed during the toolchain setup.
+    ///
+    /// For example: --require-component clippy,rustfmt
+    #[arg(long, value_name = "COMPONENT", value_delimiter = ',', global = true)]
+    pub require_component: Vec<String>,
+
+    /// The number of compilers which may run at once, across the checks
+    ///
+    /// The checks share the limit with the cargo of each check, over the jobserver protocol
+    /// of GNU make. By default, the jobserver of a make or cargo which runs cargo-msrv is
+    /// used, if any, and otherwise, each cargo limits its own number of jobs.
+    #[arg(long, value_name = "N", global = true)]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// The memory which the processes of a check may use together
+    ///
+    /// A check which exceeds the limit is killed, and its outcome is inconclusive, rather than
+    /// incompatible. Supported on Linux, with a cgroup v2 hierarchy of which the cgroup of
+    /// cargo-msrv is delegated to the user, and on Windows.
+    ///
+    /// For example: --memory-limit 4GiB
+    #[arg(long, value_name = "SIZE", value_parser = parse_size, global = true)]
+    pub memory_limit: Option<u64>,
+
+    /// The number of CPUs of which the processes of a check may use the time together
+    ///
+    /// Supported on the same platforms as --memory-limit.
+    ///
+    /// For example: --cpu-limit 1.5
+    #[arg(long, value_name = "CPUS", value_parser = parse_cpus, global = true)]
+    pub cpu_limit: Option<f64>,
+
+    /// Don't estimate the disk space which the checks need before the first check
+    ///
+    /// By default, the disk space of the toolchains which are not yet installed, and of the build
+    /// artifacts of the checks, is estimated, and the run fails early if it exceeds the free
+    /// space.
+    #[arg(long, global = true)]
+    pub no_disk_preflight: bool,
+
+    /// The server from which rustup downloads the toolchains, e.g. a mirror of static.rust-lang.org
+    ///
+    /// Takes precedence over the RUSTUP_DIST_SERVER environment variable, and over the `mirror`
+    /// table of the `cargo-msrv` metadata in the Cargo manifest. Also used to fetch the channel
+    /// manifests, for --require-component.
+    ///
+    /// For example: --dist-server https://mirrors.ustc.edu.cn/rust-static
+    #[arg(long, value_name = "URL", global = true)]
+    pub dist_server: Option<String>,
+
+    /// The host of the toolchain to install instead, for a Rust version which was never published
+    /// for the native host
+    ///
+    /// Older releases were often not built for newer tier 2 hosts, but the toolchain of another
+    /// host may run, like x86_64 via Rosetta on Apple Silicon, or musl instead of gnu. Without
+    /// --target, the toolchain of the fallback host targets the fallback host as well. Only for
+    /// toolchains installed by rustup.
+    ///
+    /// For example: --fallback-host x86_64-apple-darwin
+    #[arg(long, value_name = "TRIPLE", global = true)]
+    pub fallback_host: Option<String>,
+
+    /// Don't check whether the dist server responds before the first toolchain is installed
+    #[arg(long, global = true)]
+    pub no_dist_server_check: bool,
 }
@@ -0,0 +1,516 @@
+//! A builder of the [`Context`] of a `find` or `verify` run, for library consumers, as an
+//! alternative to parsing command line arguments.
+//!
+//! Unlike the command line, which rejects the first invalid argument, [`ConfigBuilder::build`]
+//! validates every field before the context is resolved, and reports each problem it found in one
+//! [`ConfigReport`], so all of them can be fixed at once.
+
+use crate::cli::rust_releases_opts::EditionOrVersion;
+use crate::cli::CargoCli;
+use crate::context::{ChainedReleaseSource, ReleaseSource, SearchMethod};
+use crate::manifest::bare_version::BareVersion;
+use crate::Context;
+use std::ffi::OsString;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// The subcommand of which the builder builds the context.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Subcommand {
+    Find,
+    Verify,
+}
+
+impl Subcommand {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Find => "find",
+            Self::Verify => "verify",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ConfigBuilder {
+    subcommand: Subcommand,
+    path: Option<PathBuf>,
+    manifest_path: Option<PathBuf>,
+    min: Option<String>,
+    max: Option<String>,
+    rust_version: Option<String>,
+    search_method: Option<SearchMethod>,
+    target: Option<String>,
+    features: Vec<String>,
+    all_features: bool,
+    no_default_features: bool,
+    release_sources: Vec<String>,
+    release_snapshot: Option<PathBuf>,
+    check_command: Vec<String>,
+}
+
+impl ConfigBuilder {
+    /// Build the context of `cargo msrv find`.
+    pub fn find() -> Self {
+        Self::new(Subcommand::Find)
+    }
+
+    /// Build the context of `cargo msrv verify`.
+    pub fn verify() -> Self {
+        Self::new(Subcommand::Verify)
+    }
+
+    fn new(subcommand: Subcommand) -> Self {
+        Self {
+            subcommand,
+            path: None,
+            manifest_path: None,
+            min: None,
+            max: None,
+            rust_version: None,
+            search_method: None,
+            target: None,
+            features: Vec::new(),
+            all_features: false,
+            no_default_features: false,
+            release_sources: Vec::new(),
+            release_snapshot: None,
+            check_command: Vec::new(),
+        }
+    }
+
+    /// The root directory of the crate, like `--path`.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// The Cargo manifest of the crate, like `--manifest-path`.
+    pub fn manifest_path(mut self, manifest_path: impl Into<PathBuf>) -> Self {
+        self.manifest_path = Some(manifest_path.into());
+        self
+    }
+
+    /// The least recent version, or edition, to take into account, like `--min`.
+    pub fn min(mut self, min: impl Into<String>) -> Self {
+        self.min = Some(min.into());
+        self
+    }
+
+    /// The most recent version to take into account, like `--max`.
+    pub fn max(mut self, max: impl Into<String>) -> Self {
+        self.max = Some(max.into());
+        self
+    }
+
+    /// The Rust version to verify, like `verify --rust-version`.
+    pub fn rust_version(mut self, rust_version: impl Into<String>) -> Self {
+        self.rust_version = Some(rust_version.into());
+        self
+    }
+
+    /// The method to find the MSRV with, like `find --search-method`.
+    pub fn search_method(mut self, search_method: SearchMethod) -> Self {
+        self.search_method = Some(search_method);
+        self
+    }
+
+    /// The target of the checks, like `--target`.
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// A feature to enable for the checks, like `--features`.
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    pub fn all_features(mut self, all_features: bool) -> Self {
+        self.all_features = all_features;
+        self
+    }
+
+    pub fn no_default_features(mut self, no_default_features: bool) -> Self {
+        self.no_default_features = no_default_features;
+        self
+    }
+
+    /// The next release source of the chain, like `--release-source rust-dist:30s`.
+    pub fn release_source(mut self, release_source: impl Into<String>) -> Self {
+        self.release_sources.push(release_source.into());
+        self
+    }
+
+    /// The release file of the `snapshot` release source, like `--release-snapshot`.
+    pub fn release_snapshot(mut self, release_snapshot: impl Into<PathBuf>) -> Self {
+        self.release_snapshot = Some(release_snapshot.into());
+        self
+    }
+
+    /// The custom check command, like the arguments after `--`.
+    pub fn check_command<I, T>(mut self, command: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.check_command = command.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Validate each field, and resolve the context if all of them are valid.
+    pub fn build(self) -> Result<Context, ConfigReport> {
+        let problems = self.validate();
+        if !problems.is_empty() {
+            return Err(ConfigReport { problems });
+        }
+
+        let unresolvable = |message: String| ConfigReport {
+            problems: vec![ConfigProblem {
+                field: None,
+                kind: ProblemKind::Unresolvable,
+                message,
+            }],
+        };
+
+        let cli = CargoCli::try_parse_args(self.to_args())
+            .map_err(|error| unresolvable(error.to_string().trim_end().to_string()))?;
+        let opts = cli.to_cargo_msrv_cli().to_opts();
+
+        Context::try_from(opts).map_err(|error| unresolvable(error.to_string()))
+    }
+
+    /// Each problem of the fields, in the order of the fields.
+    fn validate(&self) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+
+        if self.path.is_some() && self.manifest_path.is_some() {
+            problems.push(ConfigProblem::conflict(
+                "manifest_path",
+                "a manifest path can't be given together with a path",
+            ));
+        }
+        if let Some(path) = self.path.as_ref().filter(|path| !path.is_dir()) {
+            problems.push(ConfigProblem::path_not_found("path", path));
+        }
+        if let Some(path) = self.manifest_path.as_ref().filter(|path| !path.is_file()) {
+            problems.push(ConfigProblem::path_not_found("manifest_path", path));
+        }
+
+        let min = parse(&mut problems, "min", self.min.as_deref())
+            .map(|min: EditionOrVersion| min.as_bare_version());
+        let max = parse::<BareVersion>(&mut problems, "max", self.max.as_deref());
+        if let (Some(min), Some(max)) = (min, max) {
+            if min.to_semver_version() > max.to_semver_version() {
+                problems.push(ConfigProblem::conflict(
+                    "max",
+                    format!(
+                        "the maximum version {} is lower than the minimum version {}",
+                        max, min
+                    ),
+                ));
+            }
+        }
+
+        parse::<BareVersion>(&mut problems, "rust_version", self.rust_version.as_deref());
+        if self.rust_version.is_some() && self.subcommand != Subcommand::Verify {
+            problems.push(ConfigProblem::conflict(
+                "rust_version",
+                "a Rust version to verify can only be given to verify",
+            ));
+        }
+        if self.search_method.is_some() && self.subcommand != Subcommand::Find {
+            problems.push(ConfigProblem::conflict(
+                "search_method",
+                "a search method can only be given to find",
+            ));
+        }
+
+        let mut uses_snapshot = false;
+        for release_source in &self.release_sources {
+            let parsed = parse::<ChainedReleaseSource>(
+                &mut problems,
+                "release_source",
+                Some(release_source),
+            );
+            uses_snapshot |= parsed.is_some_and(|it| it.source == ReleaseSource::Snapshot);
+        }
+        match &self.release_snapshot {
+            Some(path) if !path.is_file() => {
+                problems.push(ConfigProblem::path_not_found("release_snapshot", path));
+            }
+            None if uses_snapshot => problems.push(ConfigProblem {
+                field: Some("release_snapshot"),
+                kind: ProblemKind::Missing,
+                message: "the snapshot release source requires a release snapshot".to_string(),
+            }),
+            _ => {}
+        }
+
+        problems
+    }
+
+    /// The command line arguments of the configuration, which [`ConfigBuilder::validate`] found to
+    /// be valid.
+    fn to_args(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec!["cargo".into(), "msrv".into()];
+        let mut arg = |name: &str, value: Option<OsString>| {
+            args.push(name.into());
+            args.extend(value);
+        };
+
+        if let Some(path) = &self.path {
+            arg("--path", Some(path.into()));
+        }
+        if let Some(manifest_path) = &self.manifest_path {
+            arg("--manifest-path", Some(manifest_path.into()));
+        }
+
+        arg(self.subcommand.name(), None);
+
+        if let Some(min) = &self.min {
+            arg("--min", Some(min.into()));
+        }
+        if let Some(max) = &self.max {
+            arg("--max", Some(max.into()));
+        }
+        if let Some(rust_version) = &self.rust_version {
+            arg("--rust-version", Some(rust_version.into()));
+        }
+        if let Some(search_method) = self.search_method {
+            arg("--search-method", Some(<&str>::from(search_method).into()));
+        }
+        if let Some(target) = &self.target {
+            arg("--target", Some(target.into()));
+        }
+        if !self.features.is_empty() {
+            arg("--features", Some(self.features.join(",").into()));
+        }
+        if self.all_features {
+            arg("--all-features", None);
+        }
+        if self.no_default_features {
+            arg("--no-default-features", None);
+        }
+        if !self.release_sources.is_empty() {
+            arg(
+                "--release-source",
+                Some(self.release_sources.join(",").into()),
+            );
+        }
+        if let Some(release_snapshot) = &self.release_snapshot {
+            arg("--release-snapshot", Some(release_snapshot.into()));
+        }
+        if !self.check_command.is_empty() {
+            arg("--", None);
+            args.extend(self.check_command.iter().map(OsString::from));
+        }
+
+        args
+    }
+}
+
+/// Parse the value of a field, if it is given, or add the problem why it can't be parsed.
+fn parse<T>(
+    problems: &mut Vec<ConfigProblem>,
+    field: &'static str,
+    value: Option<&str>,
+) -> Option<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    match value?.parse() {
+        Ok(parsed) => Some(parsed),
+        Err(error) => {
+            problems.push(ConfigProblem {
+                field: Some(field),
+                kind: ProblemKind::InvalidValue,
+                message: format!("'{}' is invalid: {}", value?, error),
+            });
+            None
+        }
+    }
+}
+
+/// Each problem of a configuration, which [`ConfigBuilder::build`] rejected.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct ConfigReport {
+    problems: Vec<ConfigProblem>,
+}
+
+impl ConfigReport {
+    pub fn problems(&self) -> &[ConfigProblem] {
+        &self.problems
+    }
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The configuration is invalid:")?;
+
+        for problem in &self.problems {
+            write!(f, "\n  - {}", problem)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigReport {}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ConfigProblem {
+    /// The field of the builder, like `min`, or `None` if the problem is not about a single field.
+    pub field: Option<&'static str>,
+    pub kind: ProblemKind,
+    pub message: String,
+}
+
+impl ConfigProblem {
+    fn conflict(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field: Some(field),
+            kind: ProblemKind::Conflict,
+            message: message.into(),
+        }
+    }
+
+    fn path_not_found(field: &'static str, path: &std::path::Path) -> Self {
+        Self {
+            field: Some(field),
+            kind: ProblemKind::PathNotFound,
+            message: format!("'{}' does not exist", path.display()),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.field {
+            Some(field) => write!(f, "{}: {}", field, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ProblemKind {
+    /// The value of the field can't be parsed, like a version which is not a Rust version.
+    InvalidValue,
+    /// The field conflicts with another field, or with the subcommand.
+    Conflict,
+    /// The path of the field does not exist.
+    PathNotFound,
+    /// The field is required by another field, but was not given.
+    Missing,
+    /// The fields are valid, but the context can't be resolved for the crate, e.g. since its
+    /// manifest can't be read.
+    Unresolvable,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(name)
+    }
+
+    fn problems(builder: ConfigBuilder) -> Vec<(Option<&'static str>, ProblemKind)> {
+        builder
+            .build()
+            .unwrap_err()
+            .problems()
+            .iter()
+            .map(|problem| (problem.field, problem.kind))
+            .collect()
+    }
+
+    #[test]
+    fn each_problem_is_reported() {
+        let builder = ConfigBuilder::find()
+            .path(fixture("does-not-exist"))
+            .manifest_path(fixture("1.36.0").join("Cargo.toml"))
+            .min("1.a")
+            .max("1.40")
+            .rust_version("1.38")
+            .release_source("rust-dist")
+            .release_source("crates-io");
+
+        assert_eq!(
+            problems(builder),
+            vec![
+                (Some("manifest_path"), ProblemKind::Conflict),
+                (Some("path"), ProblemKind::PathNotFound),
+                (Some("min"), ProblemKind::InvalidValue),
+                (Some("rust_version"), ProblemKind::Conflict),
+                (Some("release_source"), ProblemKind::InvalidValue),
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        min_above_max = {
+            ConfigBuilder::find().min("1.40").max("1.38.0"),
+            Some("max"),
+            ProblemKind::Conflict
+        },
+        search_method_of_verify = {
+            ConfigBuilder::verify().search_method(SearchMethod::Linear),
+            Some("search_method"),
+            ProblemKind::Conflict
+        },
+        snapshot_without_file = {
+            ConfigBuilder::find().release_source("snapshot"),
+            Some("release_snapshot"),
+            ProblemKind::Missing
+        },
+    )]
+    fn problem(builder: ConfigBuilder, field: Option<&'static str>, kind: ProblemKind) {
+        assert_eq!(problems(builder), vec![(field, kind)]);
+    }
+
+    #[test]
+    fn find_context() {
+        let context = ConfigBuilder::find()
+            .path(fixture("1.36.0"))
+            .min("2018")
+            .max("1.40")
+            .search_method(SearchMethod::Linear)
+            .feature("a")
+            .build()
+            .unwrap();
+
+        let find = context.to_find_context().unwrap();
+        assert_eq!(find.search_method, SearchMethod::Linear);
+        assert_eq!(find.check_cmd.cargo_features, Some(vec!["a".to_string()]));
+    }
+
+    #[test]
+    fn verify_context() {
+        let context = ConfigBuilder::verify()
+            .path(fixture("1.36.0"))
+            .rust_version("1.36")
+            .build()
+            .unwrap();
+
+        assert!(context.to_verify_context().is_some());
+    }
+
+    #[test]
+    fn report_of_problems() {
+        let report = ConfigBuilder::find().min("a").max("b").build().unwrap_err();
+
+        let report = report.to_string();
+        assert!(report.starts_with("The configuration is invalid:\n  - min: 'a' is invalid: "));
+        assert_eq!(report.lines().count(), 3);
+    }
+}
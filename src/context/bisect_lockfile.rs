@@ -0,0 +1,88 @@
+use crate::check::RunCommand;
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::{CheckCommandContext, EnvironmentContext, ToolchainContext};
+use crate::error::CargoMSRVError;
+use crate::semver;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct BisectLockfileContext {
+    /// The Rust version of the toolchain with which the crate fails
+    pub toolchain_version: semver::Version,
+
+    /// The commit of which the lockfile is known to be compatible, if any
+    pub good: Option<String>,
+
+    /// Don't print the result of the compatibility checks
+    pub no_check_feedback: bool,
+
+    /// The context for Rust toolchains
+    pub toolchain: ToolchainContext,
+
+    /// The context for custom checks to be used with rustup
+    pub check_cmd: CheckCommandContext,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for BisectLockfileContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let opts = match subcommand {
+            SubCommand::BisectLockfile(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `bisect-lockfile`!"),
+        };
+
+        let environment: EnvironmentContext = (&shared_opts).try_into()?;
+        let check_cmd: CheckCommandContext = opts.custom_check_opts.try_into()?;
+        check_cmd.validate_targets(&environment)?;
+
+        Ok(Self {
+            toolchain_version: opts.toolchain.to_semver_version(),
+            good: opts.good,
+            no_check_feedback: opts.no_check_feedback,
+            toolchain: opts.toolchain_opts.try_into()?,
+            check_cmd,
+            environment,
+        })
+    }
+}
+
+impl BisectLockfileContext {
+    pub fn run_command(&self) -> RunCommand {
+        self.check_cmd
+            .run_command(self.toolchain.target, &self.environment)
+            .with_limits(self.toolchain.limits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::CargoCli;
+
+    #[test]
+    fn context() {
+        let opts = CargoCli::parse_args([
+            "cargo",
+            "msrv",
+            "bisect-lockfile",
+            "--toolchain",
+            "1.63",
+            "--good",
+            "v0.1.0",
+        ]);
+        let context = BisectLockfileContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+        assert_eq!(context.toolchain_version, semver::Version::new(1, 63, 0));
+        assert_eq!(context.good.as_deref(), Some("v0.1.0"));
+        assert!(!context.no_check_feedback);
+    }
+}
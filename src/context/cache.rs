@@ -0,0 +1,34 @@
+use crate::cli::{CacheAction, CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct CacheContext {
+    /// What to do with the global cache
+    pub action: CacheAction,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for CacheContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let cache_opts = match subcommand {
+            SubCommand::Cache(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `cache`!"),
+        };
+
+        Ok(Self {
+            action: cache_opts.action,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
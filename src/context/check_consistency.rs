@@ -0,0 +1,22 @@
+use crate::cli::CargoMsrvOpts;
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct CheckConsistencyContext {
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for CheckConsistencyContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts { shared_opts, .. } = opts;
+
+        Ok(Self {
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
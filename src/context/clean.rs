@@ -0,0 +1,38 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct CleanContext {
+    /// Only list what would be removed
+    pub dry_run: bool,
+
+    /// Also uninstall the toolchains installed by cargo-msrv
+    pub toolchains: bool,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for CleanContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let clean_opts = match subcommand {
+            SubCommand::Clean(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `clean`!"),
+        };
+
+        Ok(Self {
+            dry_run: clean_opts.dry_run,
+            toolchains: clean_opts.toolchains,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
@@ -1,29 +1,78 @@
 use crate::check::RunCommand;
 use crate::cli::{CargoMsrvOpts, SubCommand};
 use crate::context::{
-    CheckCommandContext, EnvironmentContext, RustReleasesContext, SearchMethod, ToolchainContext,
+    also_crate_roots, CheckCommandContext, EnvironmentContext, LockfileStrategy,
+    RustReleasesContext, SearchMethod, ToolchainContext, ToolchainProvider,
 };
-use crate::error::CargoMSRVError;
-use crate::external_command::cargo_command::CargoCommand;
+use crate::control::ControlSource;
+use crate::deadline::Deadline;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::custom_toolchains::{custom_toolchains, CustomToolchain};
+use crate::manifest::denylist::{configured_denylist, Denylist};
+use crate::search_method::Shard;
+use crate::semver;
+use crate::writer::changelog::ChangelogMode;
+use camino::Utf8PathBuf;
+use cargo_metadata::MetadataCommand;
 use std::convert::{TryFrom, TryInto};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct FindContext {
-    /// Use a binary (bisect) or linear search to find the MSRV
+    /// Use a binary (bisect), linear or hybrid search to find the MSRV
     pub search_method: SearchMethod,
 
+    /// Only check this slice of the search space, with a linear search
+    pub shard: Option<Shard>,
+
     /// Write the toolchain file if the MSRV is found
     pub write_toolchain_file: bool,
 
     /// Ignore the lockfile for the MSRV search
     pub ignore_lockfile: bool,
 
+    /// How to handle the lockfile, when the cargo of a toolchain can't read it
+    pub lockfile_strategy: Option<LockfileStrategy>,
+
     /// Don't print the result of compatibility checks
     pub no_check_feedback: bool,
 
     /// Write the MSRV to the Cargo manifest
     pub write_msrv: bool,
 
+    /// Append an entry on the change of the MSRV to the changelog, when the MSRV is written
+    pub changelog: Option<ChangelogMode>,
+
+    /// Update each declaration of the MSRV in the repository to the MSRV
+    pub sync: bool,
+
+    /// Compare the compiler output of the last incompatible release with the output of the MSRV
+    pub diff_errors: bool,
+
+    /// Report the editions and language features which become available after the MSRV
+    pub report_unlocks: bool,
+
+    /// Exclude the Rust versions of which cargo fails to parse the project from the search
+    pub cargo_gates: bool,
+
+    /// Store the artifacts of each check, to be browsed with `cargo msrv inspect`
+    pub save_run: bool,
+
+    /// The roots of the other crates to run for, included with `--also`
+    pub also: Vec<Utf8PathBuf>,
+
+    /// The targets to find the MSRV for independently, with `--targets`
+    pub targets: Vec<&'static str>,
+
+    /// Where the commands to steer the search are read from, if anywhere
+    pub control: Option<ControlSource>,
+
+    /// When the search stops, if it has a time budget
+    pub deadline: Option<Deadline>,
+
+    /// Continue from the saved state of a search which stopped early
+    pub resume: bool,
+
     /// The context for Rust releases
     pub rust_releases: RustReleasesContext,
 
@@ -33,6 +82,17 @@ pub struct FindContext {
     /// The context for checks to be used with rustup
     pub check_cmd: CheckCommandContext,
 
+    /// Provides the toolchains to check
+    pub toolchain_provider: ToolchainProvider,
+
+    /// Custom toolchains, linked with `rustup toolchain link`, which are included in the
+    /// search space
+    pub custom_toolchains: Vec<CustomToolchain>,
+
+    /// The releases which are excluded from the search space, unless allowed with
+    /// `--allow-version`
+    pub denylist: Denylist,
+
     /// Resolved environment options
     pub environment: EnvironmentContext,
 }
@@ -51,39 +111,130 @@ impl TryFrom<CargoMsrvOpts> for FindContext {
             _ => unreachable!("This should never happen. The subcommand is not `find`!"),
         };
 
-        let toolchain = find_opts.toolchain_opts.try_into()?;
-        let environment = (&shared_opts).try_into()?;
+        let toolchain_provider = ToolchainProvider::try_from_opts(
+            find_opts.container_opts,
+            find_opts.nix_opts,
+            find_opts.remote_opts,
+            find_opts.tarball_opts,
+        )?;
+
+        let mut toolchain_opts = find_opts.toolchain_opts;
+        if toolchain_opts.target.is_none() {
+            toolchain_opts.target = toolchain_provider.default_target()?;
+        }
+
+        let toolchain = toolchain_opts.try_into()?;
+        let environment: EnvironmentContext = (&shared_opts).try_into()?;
+        let custom_toolchains = configured_custom_toolchains(&environment)?;
+        let denylist = configured_denylist(&environment)?;
+        let check_cmd: CheckCommandContext = find_opts.custom_check_opts.try_into()?;
+        check_cmd.validate_targets(&environment)?;
+        check_cmd.validate_vendor(&toolchain_provider)?;
 
         Ok(Self {
-            search_method: if find_opts.linear {
+            search_method: if let Some(search_method) = find_opts.search_method {
+                search_method
+            } else if find_opts.linear || find_opts.shard.is_some() {
                 SearchMethod::Linear
+            } else if find_opts.hybrid {
+                SearchMethod::Hybrid
             } else {
                 SearchMethod::Bisect
             },
+            shard: find_opts.shard,
             write_toolchain_file: find_opts.write_toolchain_file,
             ignore_lockfile: find_opts.ignore_lockfile,
+            lockfile_strategy: find_opts.lockfile_strategy,
             no_check_feedback: find_opts.no_check_feedback,
             write_msrv: find_opts.write_msrv,
+            changelog: ChangelogMode::from_flags(find_opts.changelog, find_opts.changelog_dry_run),
+            sync: find_opts.sync,
+            diff_errors: find_opts.diff_errors,
+            report_unlocks: find_opts.report_unlocks,
+            cargo_gates: !find_opts.no_cargo_gates,
+            save_run: find_opts.save_run,
+            also: also_crate_roots(environment.root(), &find_opts.also)?,
+            targets: find_opts
+                .targets
+                .into_iter()
+                .map(|target| -> &'static str { String::leak(target) })
+                .collect(),
+            control: find_opts.control,
+            deadline: find_opts.max_duration.map(Deadline::after),
+            resume: find_opts.resume,
             rust_releases: find_opts.rust_releases_opts.into(),
             toolchain,
-            check_cmd: find_opts.custom_check_opts.into(),
+            check_cmd,
+            toolchain_provider,
+            custom_toolchains,
+            denylist,
             environment,
         })
     }
 }
 
+/// The custom toolchains configured in the Cargo manifest, if any.
+pub(super) fn configured_custom_toolchains(
+    environment: &EnvironmentContext,
+) -> TResult<Vec<CustomToolchain>> {
+    if let Some(metadata) = environment.workspace_packages.metadata() {
+        return custom_toolchains(metadata);
+    }
+
+    // The metadata of the workspace wasn't loaded, e.g. since its dependencies couldn't be
+    // resolved, while the manifest itself can still be read
+    let metadata = MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()?;
+
+    custom_toolchains(&metadata)
+}
+
 impl FindContext {
     pub fn run_command(&self) -> RunCommand {
-        if let Some(custom) = &self.check_cmd.rustup_command {
-            RunCommand::custom(custom.clone())
-        } else {
-            let cargo_command = CargoCommand::default()
-                .target(Some(self.toolchain.target))
-                .features(self.check_cmd.cargo_features.clone())
-                .all_features(self.check_cmd.cargo_all_features)
-                .no_default_features(self.check_cmd.cargo_no_default_features);
-
-            RunCommand::default(cargo_command)
+        self.check_cmd
+            .run_command(self.toolchain.target, &self.environment)
+            .with_limits(self.toolchain.limits)
+    }
+
+    /// The context for another crate, like one included with `--also`.
+    pub fn for_crate(&self, root: Utf8PathBuf) -> Self {
+        Self {
+            also: Vec::new(),
+            environment: self.environment.for_crate(root),
+            ..self.clone()
+        }
+    }
+
+    /// The context for one of the targets given with `--targets`.
+    pub fn for_target(&self, target: &'static str) -> Self {
+        Self {
+            targets: Vec::new(),
+            toolchain: ToolchainContext {
+                target,
+                ..self.toolchain.clone()
+            },
+            ..self.clone()
+        }
+    }
+
+    /// The context to check the boundary of the saved MSRV of one of the targets given with
+    /// `--targets`: the MSRV itself, and the release before it. The MSRV still holds if it is
+    /// found again, in this search space.
+    pub fn for_target_boundary(&self, target: &'static str, msrv: &semver::Version) -> Self {
+        let before = BareVersion::TwoComponents(msrv.major, msrv.minor.saturating_sub(1));
+
+        Self {
+            search_method: SearchMethod::Linear,
+            diff_errors: false,
+            resume: false,
+            rust_releases: RustReleasesContext {
+                minimum_rust_version: Some(before),
+                maximum_rust_version: Some(BareVersion::from(msrv)),
+                ..self.rust_releases.clone()
+            },
+            ..self.for_target(target)
         }
     }
 }
@@ -0,0 +1,55 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use clap::ValueEnum;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+
+#[derive(Debug)]
+pub struct GraphContext {
+    /// The format in which the graph is rendered
+    pub format: GraphFormat,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for GraphContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let graph_opts = match subcommand {
+            SubCommand::Graph(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `graph`!"),
+        };
+
+        Ok(Self {
+            format: graph_opts.format,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, ValueEnum, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphFormat {
+    /// The DOT language, as rendered by Graphviz
+    #[default]
+    Dot,
+    /// A Mermaid flowchart, as rendered by GitHub in markdown
+    Mermaid,
+}
+
+impl fmt::Display for GraphFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dot => write!(f, "dot"),
+            Self::Mermaid => write!(f, "mermaid"),
+        }
+    }
+}
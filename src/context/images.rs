@@ -0,0 +1,42 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use crate::sub_command::verify::RustVersion;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct ImagesContext {
+    /// The MSRV to suggest container images and distribution packages for
+    pub rust_version: RustVersion,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for ImagesContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let images_opts = match subcommand {
+            SubCommand::Images(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `images`!"),
+        };
+
+        let environment = (&shared_opts).try_into()?;
+
+        let rust_version = match images_opts.rust_version {
+            Some(version) => RustVersion::from_arg(version),
+            None => RustVersion::try_from_environment(&environment)?,
+        };
+
+        Ok(Self {
+            rust_version,
+            environment,
+        })
+    }
+}
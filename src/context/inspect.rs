@@ -0,0 +1,39 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use crate::semver;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct InspectContext {
+    /// The run to inspect; if not set, the stored runs are listed
+    pub run_id: Option<String>,
+
+    /// The Rust version of which the complete output of its check is shown
+    pub log: Option<semver::Version>,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for InspectContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let inspect_opts = match subcommand {
+            SubCommand::Inspect(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `inspect`!"),
+        };
+
+        Ok(Self {
+            run_id: inspect_opts.run_id,
+            log: inspect_opts.log.map(|version| version.to_semver_version()),
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
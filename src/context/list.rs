@@ -1,6 +1,8 @@
 use crate::cli::{CargoMsrvOpts, SubCommand};
 use crate::context::EnvironmentContext;
 use crate::error::CargoMSRVError;
+use crate::manifest::bare_version::BareVersion;
+use crate::semver;
 use clap::ValueEnum;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
@@ -12,6 +14,9 @@ pub struct ListContext {
     /// The type of output expected by the user
     pub variant: ListMsrvVariant,
 
+    /// The dependencies which should be listed
+    pub filter: ListFilter,
+
     /// Resolved environment options
     pub environment: EnvironmentContext,
 }
@@ -33,13 +38,54 @@ impl TryFrom<CargoMsrvOpts> for ListContext {
 
         let environment = (&shared_opts).try_into()?;
 
+        let max_depth = if list_opts.direct_only {
+            Some(1)
+        } else {
+            list_opts.depth
+        };
+
         Ok(Self {
             variant: list_opts.variant,
+            filter: ListFilter {
+                max_depth,
+                min_rust_version: list_opts.min_rust_version,
+            },
             environment,
         })
     }
 }
 
+/// Narrows down the dependencies listed by `cargo msrv list`.
+///
+/// By default, every dependency is listed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ListFilter {
+    /// Only list dependencies up to this depth, where direct dependencies have a depth of `1`.
+    pub max_depth: Option<u32>,
+    /// Only list dependencies which require a newer Rust version than this version.
+    pub min_rust_version: Option<BareVersion>,
+}
+
+impl ListFilter {
+    /// Whether a package at the given `depth` from the root crate, and with the given
+    /// `rust_version`, should be listed.
+    ///
+    /// The root crate itself has a depth of `0`.
+    pub fn includes(&self, depth: usize, rust_version: Option<&semver::Version>) -> bool {
+        let within_depth = self
+            .max_depth
+            .map_or(true, |max_depth| depth <= max_depth as usize);
+
+        let requires_newer = match (&self.min_rust_version, rust_version) {
+            (Some(threshold), Some(version)) => version > &threshold.to_semver_version(),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+
+        within_depth && requires_newer
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, ValueEnum)]
 pub enum ListMsrvVariant {
     DirectDeps,
@@ -75,3 +121,50 @@ impl fmt::Display for ListMsrvVariant {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::CargoCli;
+    use clap::Parser;
+
+    fn context(args: &[&str]) -> ListContext {
+        let args = ["cargo", "msrv", "list"].iter().chain(args);
+        let opts = CargoCli::parse_args(args);
+
+        ListContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap()
+    }
+
+    #[yare::parameterized(
+        none = { &[], None },
+        direct_only = { &["--direct-only"], Some(1) },
+        depth = { &["--depth", "2"], Some(2) },
+    )]
+    fn max_depth(args: &[&str], expected: Option<u32>) {
+        assert_eq!(context(args).filter.max_depth, expected);
+    }
+
+    #[yare::parameterized(
+        zero_depth = { &["--depth", "0"] },
+        direct_only_with_depth = { &["--direct-only", "--depth", "2"] },
+    )]
+    fn rejected(args: &[&str]) {
+        let args = ["cargo", "msrv", "list"].iter().chain(args);
+        assert!(CargoCli::try_parse_from(args).is_err());
+    }
+
+    #[yare::parameterized(
+        unfiltered = { ListFilter::default(), 3, None, true },
+        within_depth = { ListFilter { max_depth: Some(1), min_rust_version: None }, 1, None, true },
+        beyond_depth = { ListFilter { max_depth: Some(1), min_rust_version: None }, 2, None, false },
+        newer = { ListFilter { max_depth: None, min_rust_version: Some(BareVersion::TwoComponents(1, 60)) }, 1, Some("1.61.0"), true },
+        equal = { ListFilter { max_depth: None, min_rust_version: Some(BareVersion::TwoComponents(1, 60)) }, 1, Some("1.60.0"), false },
+        older = { ListFilter { max_depth: None, min_rust_version: Some(BareVersion::TwoComponents(1, 60)) }, 1, Some("1.56.0"), false },
+        unspecified = { ListFilter { max_depth: None, min_rust_version: Some(BareVersion::TwoComponents(1, 60)) }, 1, None, false },
+    )]
+    fn includes(filter: ListFilter, depth: usize, rust_version: Option<&str>, expected: bool) {
+        let rust_version = rust_version.map(|v| semver::Version::parse(v).unwrap());
+
+        assert_eq!(filter.includes(depth, rust_version.as_ref()), expected);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use camino::Utf8PathBuf;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct MergeResultsContext {
+    /// The files with the JSON output of the sharded `find` runs
+    pub files: Vec<Utf8PathBuf>,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for MergeResultsContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let merge_results_opts = match subcommand {
+            SubCommand::MergeResults(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `merge-results`!"),
+        };
+
+        Ok(Self {
+            files: merge_results_opts.files,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
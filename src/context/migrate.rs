@@ -0,0 +1,35 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct MigrateContext {
+    /// Only list the migrations, without writing them
+    pub dry_run: bool,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for MigrateContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+            ..
+        } = opts;
+
+        let migrate_opts = match subcommand {
+            SubCommand::Migrate(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `migrate`!"),
+        };
+
+        Ok(Self {
+            dry_run: migrate_opts.dry_run,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
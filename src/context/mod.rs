@@ -6,36 +6,86 @@
 //!
 //! Unlike the opts, the context is top down, not bottom up.
 
+use crate::cli::container_opts::ContainerOpts;
+use crate::cli::nix_opts::NixOpts;
+use crate::cli::remote_opts::RemoteOpts;
 use crate::cli::rust_releases_opts::RustReleasesOpts;
 use crate::cli::shared_opts::SharedOpts;
+use crate::cli::tarball_opts::TarballOpts;
 use crate::cli::toolchain_opts::ToolchainOpts;
 
-use crate::error::{CargoMSRVError, InvalidUtf8Error, IoError, IoErrorSource, PathError};
+use crate::cache::{parse_size, DEFAULT_MAX_CACHE_SIZE, MAX_CACHE_SIZE_ENV_VAR};
+use crate::deadline::{parse_duration, FormattedDuration};
+use crate::error::{CargoMSRVError, InvalidUtf8Error, IoError, IoErrorSource, PathError, TResult};
+use crate::external_command::cargo_command::{CargoCommand, Targets};
+use crate::external_command::cargo_hack_command::{self, CargoHackCommand};
+use crate::external_command::container_command::User;
+use crate::external_command::ssh_command::{self, SshCommand};
 use crate::manifest::bare_version::BareVersion;
+use crate::rust::index_cache::DEFAULT_INDEX_TTL;
 use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::{workspace_default_members_is_missing, MetadataCommand};
 use clap::ValueEnum;
 use std::convert::{TryFrom, TryInto};
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 use std::{env, fmt};
 
+pub mod bisect_lockfile;
+pub mod cache;
+pub mod check_consistency;
+pub mod clean;
+pub mod estimate;
 pub mod find;
+pub mod graph;
+pub mod images;
+pub mod inspect;
 pub mod list;
+pub mod merge_results;
+pub mod migrate;
+pub mod outdated;
+pub mod report;
 pub mod set;
 pub mod show;
+pub mod sync;
 pub mod verify;
+pub mod whatif;
 
+use crate::check::RunCommand;
 use crate::cli::custom_check_opts::CustomCheckOpts;
 use crate::cli::rust_releases_opts::Edition;
 use crate::cli::{CargoMsrvOpts, SubCommand};
+pub use crate::external_command::container_command::ContainerEngine;
+pub use crate::external_command::nix_command::NixToolchainSource;
+use crate::log_file::{DEFAULT_MAX_LOG_FILES, DEFAULT_MAX_LOG_SIZE};
 use crate::log_level::LogLevel;
 use crate::reporter::event::SelectedPackage;
-use crate::rust::default_target::default_target;
+use crate::resource_limits::ResourceLimits;
+use crate::rust::default_target::{default_target, parse_default_target};
+use crate::rust::distribution_toolchain::DistributionToolchain;
+use crate::rust::tarball_toolchain::TarballToolchains;
+use crate::rust::Toolchain;
+pub use bisect_lockfile::BisectLockfileContext;
+pub use cache::CacheContext;
+pub use check_consistency::CheckConsistencyContext;
+pub use clean::CleanContext;
+pub use estimate::EstimateContext;
 pub use find::FindContext;
+pub use graph::GraphContext;
+pub use images::ImagesContext;
+pub use inspect::InspectContext;
 pub use list::ListContext;
+pub use merge_results::MergeResultsContext;
+pub use migrate::MigrateContext;
+pub use outdated::OutdatedContext;
+pub use report::ReportContext;
 pub use set::SetContext;
 pub use show::ShowContext;
+pub use sync::SyncContext;
 pub use verify::VerifyContext;
+pub use whatif::WhatIfContext;
 
 /// A `context` in `cargo-msrv`, is a definitive and flattened set of options,
 /// required for the program (and its selected sub-command) to function.
@@ -57,31 +107,73 @@ pub use verify::VerifyContext;
 /// data.
 #[derive(Debug)]
 pub enum Context {
+    BisectLockfile(BisectLockfileContext),
+    Cache(CacheContext),
+    CheckConsistency(CheckConsistencyContext),
+    Clean(CleanContext),
+    Estimate(EstimateContext),
     Find(FindContext),
+    Graph(GraphContext),
+    Images(ImagesContext),
+    Inspect(InspectContext),
     List(ListContext),
+    MergeResults(MergeResultsContext),
+    Migrate(MigrateContext),
+    Outdated(OutdatedContext),
+    Report(ReportContext),
     Set(SetContext),
     Show(ShowContext),
+    Sync(SyncContext),
     Verify(VerifyContext),
+    WhatIf(WhatIfContext),
 }
 
 impl Context {
     pub fn reporting_name(&self) -> &'static str {
         match self {
+            Context::BisectLockfile(_) => "bisect_lockfile",
+            Context::Cache(_) => "cache",
+            Context::CheckConsistency(_) => "check_consistency",
+            Context::Clean(_) => "clean",
+            Context::Estimate(_) => "estimate",
             Context::Find(_) => "find",
+            Context::Graph(_) => "graph",
+            Context::Images(_) => "images",
+            Context::Inspect(_) => "inspect",
             Context::List(_) => "list",
+            Context::MergeResults(_) => "merge_results",
+            Context::Migrate(_) => "migrate",
+            Context::Outdated(_) => "outdated",
+            Context::Report(_) => "report",
             Context::Set(_) => "set",
             Context::Show(_) => "show",
+            Context::Sync(_) => "sync",
             Context::Verify(_) => "verify",
+            Context::WhatIf(_) => "whatif",
         }
     }
 
     pub fn environment_context(&self) -> &EnvironmentContext {
         match self {
+            Context::BisectLockfile(ctx) => &ctx.environment,
+            Context::Cache(ctx) => &ctx.environment,
+            Context::CheckConsistency(ctx) => &ctx.environment,
+            Context::Clean(ctx) => &ctx.environment,
+            Context::Estimate(ctx) => &ctx.environment,
             Context::Find(ctx) => &ctx.environment,
+            Context::Graph(ctx) => &ctx.environment,
+            Context::Images(ctx) => &ctx.environment,
+            Context::Inspect(ctx) => &ctx.environment,
             Context::List(ctx) => &ctx.environment,
+            Context::MergeResults(ctx) => &ctx.environment,
+            Context::Migrate(ctx) => &ctx.environment,
+            Context::Outdated(ctx) => &ctx.environment,
+            Context::Report(ctx) => &ctx.environment,
             Context::Set(ctx) => &ctx.environment,
             Context::Show(ctx) => &ctx.environment,
+            Context::Sync(ctx) => &ctx.environment,
             Context::Verify(ctx) => &ctx.environment,
+            Context::WhatIf(ctx) => &ctx.environment,
         }
     }
 
@@ -109,11 +201,29 @@ impl TryFrom<CargoMsrvOpts> for Context {
 
     fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
         let ctx = match opts.subcommand {
+            SubCommand::BisectLockfile(_) => {
+                Self::BisectLockfile(BisectLockfileContext::try_from(opts)?)
+            }
+            SubCommand::Cache(_) => Self::Cache(CacheContext::try_from(opts)?),
+            SubCommand::CheckConsistency => {
+                Self::CheckConsistency(CheckConsistencyContext::try_from(opts)?)
+            }
+            SubCommand::Clean(_) => Self::Clean(CleanContext::try_from(opts)?),
+            SubCommand::Estimate => Self::Estimate(EstimateContext::try_from(opts)?),
             SubCommand::Find(_) => Self::Find(FindContext::try_from(opts)?),
+            SubCommand::Graph(_) => Self::Graph(GraphContext::try_from(opts)?),
+            SubCommand::Images(_) => Self::Images(ImagesContext::try_from(opts)?),
+            SubCommand::Inspect(_) => Self::Inspect(InspectContext::try_from(opts)?),
             SubCommand::List(_) => Self::List(ListContext::try_from(opts)?),
+            SubCommand::MergeResults(_) => Self::MergeResults(MergeResultsContext::try_from(opts)?),
+            SubCommand::Migrate(_) => Self::Migrate(MigrateContext::try_from(opts)?),
+            SubCommand::Outdated(_) => Self::Outdated(OutdatedContext::try_from(opts)?),
+            SubCommand::Report(_) => Self::Report(ReportContext::try_from(opts)?),
             SubCommand::Set(_) => Self::Set(SetContext::try_from(opts)?),
             SubCommand::Show => Self::Show(ShowContext::try_from(opts)?),
+            SubCommand::Sync(_) => Self::Sync(SyncContext::try_from(opts)?),
             SubCommand::Verify(_) => Self::Verify(VerifyContext::try_from(opts)?),
+            SubCommand::WhatIf(_) => Self::WhatIf(WhatIfContext::try_from(opts)?),
         };
 
         Ok(ctx)
@@ -131,8 +241,21 @@ pub struct RustReleasesContext {
     /// Whether to consider patch releases as separate versions.
     pub consider_patch_releases: bool,
 
-    /// The release source to use.
-    pub release_source: ReleaseSource,
+    /// Whether to include the current beta release as a candidate.
+    pub include_prerelease: bool,
+
+    /// The releases which are included, even if they are on the denylist.
+    pub allowed_versions: Vec<BareVersion>,
+
+    /// The release sources to use, in order: each next source is only used if the sources before
+    /// it failed to serve the release index.
+    pub release_sources: Vec<ChainedReleaseSource>,
+
+    /// The file with the Rust releases of the `snapshot` release source.
+    pub release_snapshot: Option<Utf8PathBuf>,
+
+    /// When the cached release index is fetched anew.
+    pub index_refresh: IndexRefresh,
 }
 
 impl From<RustReleasesOpts> for RustReleasesContext {
@@ -141,7 +264,19 @@ impl From<RustReleasesOpts> for RustReleasesContext {
             minimum_rust_version: opts.min.map(|min| min.as_bare_version()),
             maximum_rust_version: opts.max,
             consider_patch_releases: opts.include_all_patch_releases,
-            release_source: opts.release_source,
+            include_prerelease: opts.include_prerelease,
+            allowed_versions: opts.allow_version,
+            release_sources: opts.release_sources,
+            release_snapshot: opts.release_snapshot,
+            index_refresh: if opts.refresh_index {
+                IndexRefresh::Always
+            } else if opts.frozen_index {
+                IndexRefresh::Never
+            } else {
+                IndexRefresh::Stale {
+                    ttl: opts.index_ttl.unwrap_or(DEFAULT_INDEX_TTL),
+                }
+            },
         }
     }
 }
@@ -186,13 +321,33 @@ impl RustReleasesContext {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ToolchainContext {
     /// The target of the toolchain
     pub target: &'static str,
 
     /// Components to be installed for the toolchain
     pub components: &'static [&'static str],
+
+    /// Components which must have been distributed for a toolchain to be considered.
+    ///
+    /// These are also part of the `components` to be installed.
+    pub required_components: &'static [&'static str],
+
+    /// The limits of the memory and CPU time of each check
+    pub limits: ResourceLimits,
+
+    /// Whether the disk space which the checks need is compared with the free space, before
+    /// the first check
+    pub disk_preflight: bool,
+
+    /// Whether the connectivity to the dist server is checked, before the first toolchain is
+    /// installed
+    pub dist_server_check: bool,
+
+    /// The host of the toolchain to install instead, for a Rust version which was never
+    /// published for the native host
+    pub fallback_host: Option<&'static str>,
 }
 
 impl TryFrom<ToolchainOpts> for ToolchainContext {
@@ -207,21 +362,50 @@ impl TryFrom<ToolchainOpts> for ToolchainContext {
 
         let target: &'static str = String::leak(target);
 
-        let components: &'static [&'static str] = Vec::leak(
-            opts.component
-                .into_iter()
-                .map(|s| {
-                    let s: &'static str = String::leak(s);
-                    s
-                })
-                .collect(),
-        );
+        let required_components = leak_components(opts.require_component.clone());
+
+        let mut components = opts.component;
+        for component in opts.require_component {
+            if !components.contains(&component) {
+                components.push(component);
+            }
+        }
 
-        Ok(Self { target, components })
+        let components = leak_components(components);
+
+        let limits = ResourceLimits {
+            memory: opts.memory_limit,
+            cpus: opts.cpu_limit,
+        };
+        limits.prepare()?;
+
+        Ok(Self {
+            target,
+            components,
+            required_components,
+            limits,
+            disk_preflight: !opts.no_disk_preflight,
+            dist_server_check: !opts.no_dist_server_check,
+            fallback_host: opts
+                .fallback_host
+                .map(|host| -> &'static str { String::leak(host) }),
+        })
     }
 }
 
-#[derive(Debug)]
+fn leak_components(components: Vec<String>) -> &'static [&'static str] {
+    Vec::leak(
+        components
+            .into_iter()
+            .map(|s| {
+                let s: &'static str = String::leak(s);
+                s
+            })
+            .collect(),
+    )
+}
+
+#[derive(Clone, Debug)]
 pub struct CheckCommandContext {
     pub cargo_features: Option<Vec<String>>,
 
@@ -229,21 +413,693 @@ pub struct CheckCommandContext {
 
     pub cargo_no_default_features: bool,
 
+    /// The built-in check command to use, if no custom `Rustup` command was given.
+    pub preset: CheckCommandPreset,
+
     /// The custom `Rustup` command to invoke for a toolchain.
     pub rustup_command: Option<Vec<String>>,
+
+    /// Whether the examples, and the example crates of the workspace, are checked as well.
+    pub check_examples: bool,
+
+    /// Whether the benchmarks are checked as well.
+    pub check_benches: bool,
+
+    /// Whether the cargo-fuzz projects of the crate are checked as well.
+    pub check_fuzz: bool,
+
+    /// The binary target which is checked, instead of the library and binaries, if any.
+    pub bin: Option<String>,
+
+    /// Whether only the library target is checked.
+    pub lib: bool,
+
+    /// Whether the dependencies are vendored, so the checks build offline.
+    pub vendor: bool,
+
+    /// The script which decides whether each check passes, fails or is skipped, if any.
+    pub predicate: Option<Utf8PathBuf>,
 }
 
-impl From<CustomCheckOpts> for CheckCommandContext {
-    fn from(opts: CustomCheckOpts) -> Self {
-        Self {
+impl TryFrom<CustomCheckOpts> for CheckCommandContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CustomCheckOpts) -> Result<Self, Self::Error> {
+        let preset = opts.check_cmd.unwrap_or_default();
+
+        if preset == CheckCommandPreset::HackEachFeature && !cargo_hack_command::is_installed() {
+            return Err(CargoMSRVError::CargoHackNotInstalled);
+        }
+
+        Ok(Self {
             cargo_features: opts.features,
             cargo_all_features: opts.all_features,
             cargo_no_default_features: opts.no_default_features,
+            preset,
             rustup_command: opts.custom_check_opts,
+            check_examples: opts.check_examples,
+            check_benches: opts.check_benches,
+            check_fuzz: opts.check_fuzz,
+            bin: opts.bin,
+            lib: opts.lib,
+            vendor: opts.vendor,
+            predicate: opts.predicate,
+        })
+    }
+}
+
+impl CheckCommandContext {
+    /// The command to run for each toolchain, when checking for compatibility.
+    pub fn run_command(&self, target: &str, environment: &EnvironmentContext) -> RunCommand {
+        if let Some(custom) = &self.rustup_command {
+            return RunCommand::custom(custom.clone());
+        }
+
+        let targets = self.targets(environment);
+
+        match self.preset {
+            CheckCommandPreset::CargoCheck => {
+                let cargo_command = CargoCommand::default()
+                    .target(Some(target))
+                    .features(self.cargo_features.clone())
+                    .all_features(self.cargo_all_features)
+                    .no_default_features(self.cargo_no_default_features)
+                    .targets(targets);
+
+                RunCommand::default(cargo_command)
+            }
+            CheckCommandPreset::HackEachFeature => {
+                let cargo_hack_command = CargoHackCommand::default()
+                    .target(Some(target))
+                    .features(self.cargo_features.clone())
+                    .no_default_features(self.cargo_no_default_features)
+                    .targets(targets);
+
+                RunCommand::cargo_hack(cargo_hack_command)
+            }
+        }
+    }
+
+    /// The environment of each cargo-fuzz project which is checked, if any.
+    pub fn fuzz_projects(&self, environment: &EnvironmentContext) -> Vec<EnvironmentContext> {
+        if !self.check_fuzz {
+            return Vec::new();
+        }
+
+        fuzz_project_roots(environment)
+            .into_iter()
+            .map(|root| environment.for_crate(root))
+            .collect()
+    }
+
+    /// Fail if the dependencies are vendored for a provider of which the checks don't run on this
+    /// machine, and so can't read the vendored sources.
+    pub fn validate_vendor(&self, provider: &ToolchainProvider) -> TResult<()> {
+        match provider {
+            ToolchainProvider::Container(_) | ToolchainProvider::Remote(_) if self.vendor => {
+                Err(CargoMSRVError::VendorUnsupported)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate the `--bin` and `--lib` targets against the targets of the crate, so a typo
+    /// isn't reported as an incompatible Rust version for each toolchain.
+    pub fn validate_targets(&self, environment: &EnvironmentContext) -> TResult<()> {
+        if self.bin.is_none() && !self.lib {
+            return Ok(());
+        }
+
+        match MetadataCommand::new()
+            .manifest_path(environment.manifest())
+            .no_deps()
+            .exec()
+        {
+            Ok(metadata) => validate_selected_targets(
+                &metadata,
+                self.bin.as_deref(),
+                self.lib,
+                &environment.workspace_packages.isolated_packages(),
+            ),
+            Err(error) => {
+                // Cargo will report the broken manifest in the check itself
+                info!(%error, "unable to validate the selected targets of the crate");
+                Ok(())
+            }
+        }
+    }
+
+    /// The checked targets which require features, which are enabled for the check. Unless
+    /// `--all-features` is given, in which case these are enabled already.
+    pub fn required_features(&self, environment: &EnvironmentContext) -> Vec<RequiredFeatures> {
+        if self.cargo_all_features
+            || (self.bin.is_none() && !self.check_examples && !self.check_benches)
+        {
+            return Vec::new();
+        }
+
+        match MetadataCommand::new()
+            .manifest_path(environment.manifest())
+            .no_deps()
+            .exec()
+        {
+            Ok(metadata) => required_features(
+                &metadata,
+                self.bin.as_deref(),
+                self.check_examples,
+                self.check_benches,
+                &environment.workspace_packages.isolated_packages(),
+            ),
+            Err(error) => {
+                // Cargo will report the broken manifest in the check itself
+                info!(%error, "unable to read the required features of the crate");
+                Vec::new()
+            }
+        }
+    }
+
+    /// The packages and targets to check, beyond the default targets of cargo.
+    fn targets(&self, environment: &EnvironmentContext) -> Targets {
+        let isolated = environment.workspace_packages.isolated_packages();
+
+        let targets = if !self.check_examples && !self.check_benches {
+            Targets {
+                packages: isolated,
+                ..Targets::default()
+            }
+        } else {
+            match MetadataCommand::new()
+                .manifest_path(environment.manifest())
+                .no_deps()
+                .exec()
+            {
+                Ok(metadata) => selected_targets(
+                    &metadata,
+                    self.check_examples,
+                    self.check_benches,
+                    &isolated,
+                ),
+                Err(error) => {
+                    // Cargo will report the broken manifest in the check itself
+                    info!(%error, "unable to read the targets of the crate");
+
+                    Targets {
+                        examples: self.check_examples,
+                        benches: self.check_benches,
+                        library: true,
+                        packages: isolated,
+                        ..Targets::default()
+                    }
+                }
+            }
+        };
+
+        let mut required_features = self
+            .required_features(environment)
+            .into_iter()
+            .flat_map(|required| required.features)
+            .collect::<Vec<_>>();
+        required_features.sort();
+        required_features.dedup();
+
+        Targets {
+            selected_bin: self.bin.clone(),
+            selected_lib: self.lib,
+            required_features,
+            ..targets
+        }
+    }
+}
+
+/// A checked target which declares `required-features`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequiredFeatures {
+    /// The name of the target.
+    pub target: String,
+    /// The features which the target requires, qualified by their package, like `b/color`, for
+    /// the packages other than the root package.
+    pub features: Vec<String>,
+}
+
+/// The roots of the cargo-fuzz projects of the crate: the `fuzz` directories of the crate, and
+/// of each member of its workspace, which have a manifest.
+fn fuzz_project_roots(environment: &EnvironmentContext) -> Vec<Utf8PathBuf> {
+    let mut crate_roots = vec![environment.root().to_path_buf()];
+
+    match MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()
+    {
+        Ok(metadata) => crate_roots.extend(
+            metadata
+                .workspace_packages()
+                .into_iter()
+                .filter_map(|package| package.manifest_path.parent().map(Utf8Path::to_path_buf)),
+        ),
+        Err(error) => info!(%error, "unable to read the members of the workspace"),
+    }
+
+    let mut roots = Vec::new();
+
+    for root in crate_roots.into_iter().map(|root| root.join("fuzz")) {
+        if root.join("Cargo.toml").is_file() && !roots.contains(&root) {
+            roots.push(root);
+        }
+    }
+
+    roots
+}
+
+/// The targets which check the examples and/or benchmarks of the crate. The examples include
+/// the member crates of the workspace in its `examples` directory.
+fn selected_targets(
+    metadata: &cargo_metadata::Metadata,
+    examples: bool,
+    benches: bool,
+    isolated: &[String],
+) -> Targets {
+    let examples_dir = metadata.workspace_root.join("examples");
+
+    let example_crates = metadata
+        .workspace_packages()
+        .into_iter()
+        .filter(|package| examples && package.manifest_path.starts_with(&examples_dir))
+        .collect::<Vec<_>>();
+
+    let default_crates = checked_crates(metadata, isolated);
+
+    let library = default_crates
+        .iter()
+        .chain(&example_crates)
+        .flat_map(|package| &package.targets)
+        .any(|target| target.is_lib());
+
+    // Example crates which aren't checked by default need to be selected, along with the
+    // crates which are
+    let packages = if isolated.is_empty()
+        && example_crates
+            .iter()
+            .all(|package| default_crates.contains(package))
+    {
+        Vec::new()
+    } else {
+        let mut packages = default_crates;
+        for package in example_crates {
+            if !packages.contains(&package) {
+                packages.push(package);
+            }
+        }
+
+        packages
+            .into_iter()
+            .map(|package| package.name.to_string())
+            .collect()
+    };
+
+    Targets {
+        examples,
+        benches,
+        library,
+        packages,
+        ..Targets::default()
+    }
+}
+
+/// The crates which are checked: the packages which were selected with `--package`, `--workspace`
+/// or `--exclude`, if any, or otherwise those which cargo checks by default.
+fn checked_crates<'m>(
+    metadata: &'m cargo_metadata::Metadata,
+    isolated: &[String],
+) -> Vec<&'m cargo_metadata::Package> {
+    if isolated.is_empty() {
+        return default_crates(metadata);
+    }
+
+    metadata
+        .workspace_packages()
+        .into_iter()
+        .filter(|package| isolated.contains(&package.name))
+        .collect()
+}
+
+/// The crates which cargo checks by default, without selecting packages.
+fn default_crates(metadata: &cargo_metadata::Metadata) -> Vec<&cargo_metadata::Package> {
+    match metadata.root_package() {
+        Some(package) => vec![package],
+        None if workspace_default_members_is_missing(&metadata.workspace_default_members) => {
+            metadata.workspace_packages()
+        }
+        None => metadata.workspace_default_packages(),
+    }
+}
+
+/// The targets which are checked, with `--bin`, `--check-examples` or `--check-benches`, and which
+/// require features. The library and the other binaries don't need these: cargo builds binaries
+/// which require features only if these are enabled, and a library can't require features.
+fn required_features(
+    metadata: &cargo_metadata::Metadata,
+    bin: Option<&str>,
+    examples: bool,
+    benches: bool,
+    isolated: &[String],
+) -> Vec<RequiredFeatures> {
+    let examples_dir = metadata.workspace_root.join("examples");
+    let root_package = metadata.root_package();
+
+    let mut packages = checked_crates(metadata, isolated);
+    if examples {
+        for package in metadata.workspace_packages() {
+            if package.manifest_path.starts_with(&examples_dir) && !packages.contains(&package) {
+                packages.push(package);
+            }
+        }
+    }
+
+    packages
+        .into_iter()
+        .flat_map(|package| package.targets.iter().map(move |target| (package, target)))
+        .filter(|(_, target)| {
+            (target.is_bin() && bin == Some(target.name.as_str()))
+                || (examples && target.is_example())
+                || (benches && target.is_bench())
+        })
+        .filter(|(_, target)| !target.required_features.is_empty())
+        .map(|(package, target)| RequiredFeatures {
+            target: target.name.clone(),
+            features: target
+                .required_features
+                .iter()
+                .map(|feature| match Some(package) == root_package {
+                    true => feature.clone(),
+                    false => format!("{}/{}", package.name, feature),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Fail if a crate which is checked by default has no binary target by the given name, or, if
+/// only the library is checked, no library target.
+fn validate_selected_targets(
+    metadata: &cargo_metadata::Metadata,
+    bin: Option<&str>,
+    lib: bool,
+    isolated: &[String],
+) -> TResult<()> {
+    let targets = checked_crates(metadata, isolated)
+        .into_iter()
+        .flat_map(|package| &package.targets)
+        .collect::<Vec<_>>();
+
+    if lib && !targets.iter().any(|target| target.is_lib()) {
+        return Err(CargoMSRVError::NoLibTarget);
+    }
+
+    if let Some(bin) = bin {
+        let bins = targets
+            .iter()
+            .filter(|target| target.is_bin())
+            .map(|target| target.name.as_str())
+            .collect::<Vec<_>>();
+
+        if !bins.contains(&bin) {
+            return Err(CargoMSRVError::UnknownBinTarget {
+                name: bin.to_string(),
+                available: bins.join(", "),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Provides the toolchains with which compatibility is checked.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum ToolchainProvider {
+    /// Toolchains are installed with rustup
+    #[default]
+    Rustup,
+
+    /// A `rustc` and `cargo` pair which is not managed by rustup, is used as is
+    Distribution(DistributionToolchain),
+
+    /// Toolchains are provided by container images
+    Container(ContainerContext),
+
+    /// Toolchains are materialized by nix
+    Nix(NixContext),
+
+    /// Toolchains are installed with rustup on a remote builder
+    Remote(RemoteContext),
+
+    /// Toolchains are installed from a directory of standalone tarballs
+    Tarballs(TarballToolchains),
+}
+
+impl ToolchainProvider {
+    pub fn try_from_opts(
+        container_opts: ContainerOpts,
+        nix_opts: NixOpts,
+        remote_opts: RemoteOpts,
+        tarball_opts: TarballOpts,
+    ) -> TResult<Self> {
+        if let Some(dir) = tarball_opts.toolchain_tarballs {
+            return TarballToolchains::new(dir).map(Self::Tarballs);
+        }
+
+        let container: Option<ContainerContext> = container_opts.try_into()?;
+        let nix: Option<NixContext> = nix_opts.try_into()?;
+        let remote: Option<RemoteContext> = remote_opts.try_into()?;
+
+        Ok(match (container, nix, remote) {
+            (Some(container), _, _) => Self::Container(container),
+            (None, Some(nix), _) => Self::Nix(nix),
+            (None, None, Some(remote)) => Self::Remote(remote),
+            (None, None, None) => Self::Rustup,
+        })
+    }
+
+    /// The target to check, if no target was given, when it differs from the rustup default
+    /// of this machine.
+    pub fn default_target(&self) -> TResult<Option<String>> {
+        match self {
+            Self::Distribution(distribution) => Ok(Some(distribution.host().to_string())),
+            Self::Remote(remote) => remote.default_target().map(Some),
+            // The machine may not have rustup, so the target of the tarballs is used, if unambiguous
+            Self::Tarballs(tarballs) => tarballs.target(),
+            Self::Rustup | Self::Container(_) | Self::Nix(_) => Ok(None),
+        }
+    }
+}
+
+/// The remote builder on which compatibility checks are run, over ssh.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoteContext {
+    /// The ssh destination, e.g. `user@host`
+    pub destination: String,
+
+    /// The directory on the remote builder to sync the crate to, if not the default
+    pub dir: Option<String>,
+}
+
+impl TryFrom<RemoteOpts> for Option<RemoteContext> {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: RemoteOpts) -> Result<Self, Self::Error> {
+        let Some(destination) = opts.remote else {
+            return Ok(None);
+        };
+
+        if !ssh_command::is_installed() {
+            return Err(CargoMSRVError::RemoteToolsNotInstalled);
+        }
+
+        Ok(Some(RemoteContext {
+            destination,
+            dir: opts.remote_dir,
+        }))
+    }
+}
+
+impl RemoteContext {
+    pub fn ssh(&self) -> SshCommand<'_> {
+        SshCommand::new(&self.destination)
+    }
+
+    /// The directory on the remote builder to sync the crate at `crate_root` to.
+    pub fn dir(&self, crate_root: &Utf8Path) -> String {
+        self.dir.clone().unwrap_or_else(|| {
+            // The crate root may be relative, like `.`
+            let crate_root = crate_root
+                .canonicalize_utf8()
+                .unwrap_or_else(|_| crate_root.to_path_buf());
+            let name = crate_root.file_name().unwrap_or("crate");
+
+            format!(".cargo-msrv/remote/{}", name)
+        })
+    }
+
+    /// The default target of rustup on the remote builder.
+    pub fn default_target(&self) -> TResult<String> {
+        let output = self.ssh().run(None, &["rustup", "show"])?;
+
+        if !output.status.success() {
+            return Err(CargoMSRVError::RemoteCommandFailed {
+                destination: self.destination.clone(),
+                command: "rustup show".to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+
+        parse_default_target(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+/// The container in which compatibility checks are run, instead of with toolchains installed by
+/// rustup.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainerContext {
+    /// The container engine to run the checks with
+    pub engine: ContainerEngine,
+
+    /// The image of a toolchain, with `{version}` and `{target}` placeholders
+    pub image_template: String,
+
+    /// The user to run the checks as, for rootless operation
+    pub user: Option<User>,
+}
+
+/// The official Rust images.
+const DEFAULT_CONTAINER_IMAGE: &str = "rust:{version}";
+
+impl TryFrom<ContainerOpts> for Option<ContainerContext> {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: ContainerOpts) -> Result<Self, Self::Error> {
+        let enabled = opts.container
+            || opts.container_engine.is_some()
+            || opts.container_image.is_some()
+            || opts.container_rootless;
+
+        if !enabled {
+            return Ok(None);
+        }
+
+        let engine = match opts.container_engine {
+            Some(engine) => engine,
+            None => ContainerEngine::detect().ok_or(CargoMSRVError::NoContainerEngine)?,
+        };
+
+        let image_template = opts
+            .container_image
+            .unwrap_or_else(|| DEFAULT_CONTAINER_IMAGE.to_string());
+
+        if !image_template.contains("{version}") {
+            return Err(CargoMSRVError::InvalidContainerImage(image_template));
+        }
+
+        let user = if opts.container_rootless {
+            Some(User::current().ok_or(CargoMSRVError::UnableToDetermineUser)?)
+        } else {
+            None
+        };
+
+        Ok(Some(ContainerContext {
+            engine,
+            image_template,
+            user,
+        }))
+    }
+}
+
+impl ContainerContext {
+    /// The image which provides the given toolchain.
+    pub fn image(&self, toolchain: &Toolchain) -> String {
+        self.image_template
+            .replace("{version}", &toolchain.version().to_string())
+            .replace("{target}", toolchain.target())
+    }
+}
+
+/// The Nix expression or flake installable with which toolchains are provided, instead of being
+/// installed by rustup.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NixContext {
+    /// The source of a toolchain, with `{version}` and `{target}` placeholders
+    pub source_template: NixToolchainSource,
+}
+
+/// The toolchains of [rust-overlay](https://github.com/oxalica/rust-overlay).
+const DEFAULT_NIX_EXPRESSION: &str = r#"let pkgs = import <nixpkgs> { overlays = [ (import (builtins.fetchTarball "https://github.com/oxalica/rust-overlay/archive/master.tar.gz")) ]; }; in pkgs.rust-bin.stable."{version}".minimal"#;
+
+impl TryFrom<NixOpts> for Option<NixContext> {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: NixOpts) -> Result<Self, Self::Error> {
+        let source_template = match (opts.nix_expr, opts.nix_installable) {
+            (Some(expression), _) => NixToolchainSource::Expression(expression),
+            (None, Some(installable)) => NixToolchainSource::Installable(installable),
+            (None, None) if opts.nix => {
+                NixToolchainSource::Expression(DEFAULT_NIX_EXPRESSION.to_string())
+            }
+            (None, None) => return Ok(None),
+        };
+
+        let (NixToolchainSource::Expression(template) | NixToolchainSource::Installable(template)) =
+            &source_template;
+
+        if !template.contains("{version}") {
+            return Err(CargoMSRVError::InvalidNixTemplate(template.clone()));
+        }
+
+        Ok(Some(NixContext { source_template }))
+    }
+}
+
+impl NixContext {
+    /// The source which provides the given toolchain.
+    pub fn source(&self, toolchain: &Toolchain) -> NixToolchainSource {
+        let fill = |template: &str| {
+            template
+                .replace("{version}", &toolchain.version().to_string())
+                .replace("{target}", toolchain.target())
+        };
+
+        match &self.source_template {
+            NixToolchainSource::Expression(template) => {
+                NixToolchainSource::Expression(fill(template))
+            }
+            NixToolchainSource::Installable(template) => {
+                NixToolchainSource::Installable(fill(template))
+            }
         }
     }
 }
 
+/// The built-in commands which can be used to check whether a toolchain is compatible.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum CheckCommandPreset {
+    /// Run `cargo check`
+    #[default]
+    CargoCheck,
+    /// Run `cargo hack check --each-feature`, which checks each feature separately
+    /// (requires cargo-hack to be installed)
+    HackEachFeature,
+}
+
+/// How a check handles a lockfile which the cargo of the checked toolchain can't read.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum LockfileStrategy {
+    /// Check without the lockfile, so it is generated anew by the checked toolchain
+    Regenerate,
+    /// Check with the lockfile rewritten to a version which the checked toolchain can read
+    Downgrade,
+    /// Stop, with an error
+    Fail,
+}
+
 #[derive(Clone, Debug)]
 pub struct EnvironmentContext {
     // TODO: Some parts assume a Cargo crate, but that's not strictly a requirement
@@ -256,6 +1112,9 @@ pub struct EnvironmentContext {
 
     /// Resolved workspace
     pub workspace_packages: WorkspacePackages,
+
+    /// The maximum size of the global cache, in bytes
+    pub max_cache_size: u64,
 }
 
 impl<'shared_opts> TryFrom<&'shared_opts SharedOpts> for EnvironmentContext {
@@ -311,7 +1170,15 @@ impl<'shared_opts> TryFrom<&'shared_opts SharedOpts> for EnvironmentContext {
                 ?excluded
             );
 
+            let workspace = &opts.workspace;
+            let explicit = workspace.workspace
+                || workspace.all
+                || !workspace.package.is_empty()
+                || !workspace.exclude.is_empty();
+
             WorkspacePackages::from_vec(selected)
+                .explicitly_selected(explicit)
+                .with_metadata(metadata)
         } else {
             info!(
                 action = "detect_cargo_workspace_packages",
@@ -322,13 +1189,28 @@ impl<'shared_opts> TryFrom<&'shared_opts SharedOpts> for EnvironmentContext {
             WorkspacePackages::default()
         };
 
+        let max_cache_size = match opts.global_cache_opts.max_cache_size {
+            Some(size) => size,
+            None => max_cache_size_from_env(env::var(MAX_CACHE_SIZE_ENV_VAR).ok().as_deref())?,
+        };
+
         Ok(Self {
             root_crate_path,
             workspace_packages,
+            max_cache_size,
         })
     }
 }
 
+/// The maximum cache size, from the value of the [`MAX_CACHE_SIZE_ENV_VAR`] environment variable,
+/// or the default maximum if it is not set, or empty.
+fn max_cache_size_from_env(value: Option<&str>) -> TResult<u64> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => parse_size(value).map_err(CargoMSRVError::InvalidMaxCacheSize),
+        None => Ok(DEFAULT_MAX_CACHE_SIZE),
+    }
+}
+
 impl EnvironmentContext {
     /// Path to the crate root
     pub fn root(&self) -> &Utf8Path {
@@ -344,6 +1226,61 @@ impl EnvironmentContext {
     pub fn lock(&self) -> Utf8PathBuf {
         self.root_crate_path.join("Cargo.lock")
     }
+
+    /// The environment of another crate, like one included with `--also`. None of its packages
+    /// are selected.
+    pub fn for_crate(&self, root: Utf8PathBuf) -> Self {
+        Self {
+            root_crate_path: root,
+            workspace_packages: WorkspacePackages::default(),
+            max_cache_size: self.max_cache_size,
+        }
+    }
+}
+
+/// The roots of the crates of which the manifest is matched by one of the given globs, like
+/// `examples/*/Cargo.toml`, relative to the given crate root. A matched directory stands for its
+/// `Cargo.toml`. The crate root itself is not included.
+pub(crate) fn also_crate_roots(root: &Utf8Path, globs: &[String]) -> TResult<Vec<Utf8PathBuf>> {
+    let mut roots = Vec::new();
+
+    for pattern in globs {
+        let matched_before = roots.len();
+        let full_pattern = format!("{}/{}", glob::Pattern::escape(root.as_str()), pattern);
+        let paths = glob::glob(&full_pattern).map_err(|error| {
+            CargoMSRVError::InvalidConfig(format!(
+                "Invalid glob '{}' given to '--also': {}",
+                pattern, error
+            ))
+        })?;
+
+        for path in paths.filter_map(Result::ok) {
+            let Ok(path) = Utf8PathBuf::try_from(path) else {
+                continue;
+            };
+
+            let crate_root = if path.is_dir() {
+                path
+            } else if path.file_name() == Some("Cargo.toml") {
+                path.parent().map(Utf8Path::to_path_buf).unwrap_or_default()
+            } else {
+                continue;
+            };
+
+            if crate_root.join("Cargo.toml").is_file()
+                && crate_root != root
+                && !roots.contains(&crate_root)
+            {
+                roots.push(crate_root);
+            }
+        }
+
+        if roots.len() == matched_before {
+            info!(pattern, "no manifest matched by '--also'");
+        }
+    }
+
+    Ok(roots)
 }
 
 // ---
@@ -351,15 +1288,56 @@ impl EnvironmentContext {
 #[derive(Clone, Debug, Default)]
 pub struct WorkspacePackages {
     selected: Option<Vec<cargo_metadata::Package>>,
+    /// Whether the packages were selected with `--package`, `--workspace` or `--exclude`, rather
+    /// than being the default members of the workspace.
+    explicit: bool,
+    /// The metadata of the workspace, from which the packages were selected.
+    metadata: Option<Arc<cargo_metadata::Metadata>>,
 }
 
 impl WorkspacePackages {
     pub fn from_vec(selected: Vec<cargo_metadata::Package>) -> Self {
         Self {
             selected: Some(selected),
+            explicit: false,
+            metadata: None,
+        }
+    }
+
+    /// Keep the metadata of the workspace, so the configuration of cargo-msrv in the manifest
+    /// can be read without running `cargo metadata` again.
+    pub fn with_metadata(self, metadata: cargo_metadata::Metadata) -> Self {
+        Self {
+            metadata: Some(Arc::new(metadata)),
+            ..self
         }
     }
 
+    /// The metadata of the workspace, if it was loaded.
+    pub fn metadata(&self) -> Option<&cargo_metadata::Metadata> {
+        self.metadata.as_deref()
+    }
+
+    /// Mark the packages as selected with `--package`, `--workspace` or `--exclude`, so the checks
+    /// are isolated to these packages.
+    pub fn explicitly_selected(self, explicit: bool) -> Self {
+        Self { explicit, ..self }
+    }
+
+    /// The names of the packages which are passed to cargo with `--package`, so the checks only
+    /// compile the selected packages, and not the unrelated members of the workspace. Empty if no
+    /// packages were selected explicitly, in which case cargo checks its default packages.
+    pub fn isolated_packages(&self) -> Vec<String> {
+        if !self.explicit {
+            return Vec::new();
+        }
+
+        self.selected_packages()
+            .iter()
+            .map(|package| package.name.to_string())
+            .collect()
+    }
+
     pub fn selected(&self) -> Option<Vec<SelectedPackage>> {
         self.selected.as_deref().map(|pks| {
             pks.iter()
@@ -398,6 +1376,10 @@ pub enum OutputFormat {
     Json,
     /// Minimal output, usually just the result, such as the MSRV or whether verify succeeded or failed
     Minimal,
+    /// A GitLab code quality report printed to stdout, with the failed verifications and MSRV mismatches
+    Gitlab,
+    /// Azure Pipelines logging commands printed to stdout, with the failures as issues of the build
+    Azure,
     /// No output -- meant to be used for debugging and testing
     #[value(skip)]
     None,
@@ -409,6 +1391,8 @@ impl fmt::Display for OutputFormat {
             Self::Human => write!(f, "human"),
             Self::Json => write!(f, "json"),
             Self::Minimal => write!(f, "minimal"),
+            Self::Gitlab => write!(f, "gitlab"),
+            Self::Azure => write!(f, "azure"),
             Self::None => write!(f, "none"),
         }
     }
@@ -422,6 +1406,8 @@ impl FromStr for OutputFormat {
             "human" => Ok(Self::Human),
             "json" => Ok(Self::Json),
             "minimal" => Ok(Self::Minimal),
+            "gitlab" => Ok(Self::Gitlab),
+            "azure" => Ok(Self::Azure),
             unknown => Err(CargoMSRVError::InvalidConfig(format!(
                 "Given output format '{}' is not valid",
                 unknown
@@ -430,6 +1416,51 @@ impl FromStr for OutputFormat {
     }
 }
 
+/// When the human output is colored.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum ColorChoice {
+    /// Color the output if stderr is a terminal, unless disabled by `NO_COLOR` or `CLICOLOR=0`,
+    /// or forced by `CLICOLOR_FORCE`
+    #[default]
+    Auto,
+    /// Always color the output
+    Always,
+    /// Never color the output
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether the output should be colored, given the environment variables, and whether the
+    /// output is written to a terminal.
+    pub fn enables_color(self, var: impl Fn(&str) -> Option<String>, is_terminal: bool) -> bool {
+        let is_set = |name: &str| var(name).is_some_and(|value| !value.is_empty());
+
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            // See https://no-color.org and https://bixense.com/clicolors
+            Self::Auto if is_set("NO_COLOR") => false,
+            Self::Auto
+                if is_set("CLICOLOR_FORCE") && var("CLICOLOR_FORCE").as_deref() != Some("0") =>
+            {
+                true
+            }
+            Self::Auto if var("CLICOLOR").as_deref() == Some("0") => false,
+            Self::Auto => is_terminal,
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auto => write!(f, "auto"),
+            Self::Always => write!(f, "always"),
+            Self::Never => write!(f, "never"),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Serialize, ValueEnum)]
 #[serde(rename_all = "snake_case")]
 pub enum ReleaseSource {
@@ -437,6 +1468,8 @@ pub enum ReleaseSource {
     RustChangelog,
     #[cfg(feature = "rust-releases-dist-source")]
     RustDist,
+    /// A local file with a Rust version on each line, see `--release-snapshot`
+    Snapshot,
 }
 
 impl FromStr for ReleaseSource {
@@ -453,6 +1486,7 @@ impl From<ReleaseSource> for &'static str {
             ReleaseSource::RustChangelog => "rust-changelog",
             #[cfg(feature = "rust-releases-dist-source")]
             ReleaseSource::RustDist => "rust-dist",
+            ReleaseSource::Snapshot => "snapshot",
         }
     }
 }
@@ -465,6 +1499,7 @@ impl TryFrom<&str> for ReleaseSource {
             "rust-changelog" => Ok(Self::RustChangelog),
             #[cfg(feature = "rust-releases-dist-source")]
             "rust-dist" => Ok(Self::RustDist),
+            "snapshot" => Ok(Self::Snapshot),
             s => Err(CargoMSRVError::RustReleasesSourceParseError(s.to_string())),
         }
     }
@@ -476,16 +1511,78 @@ impl fmt::Display for ReleaseSource {
             Self::RustChangelog => write!(f, "rust-changelog"),
             #[cfg(feature = "rust-releases-dist-source")]
             Self::RustDist => write!(f, "rust-dist"),
+            Self::Snapshot => write!(f, "snapshot"),
+        }
+    }
+}
+
+/// When the cached release index is fetched anew, see [`crate::rust::index_cache`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IndexRefresh {
+    /// Once the cached index is older than the TTL
+    Stale { ttl: Duration },
+    /// Always, see `--refresh-index`
+    Always,
+    /// Never, see `--frozen-index`
+    Never,
+}
+
+impl Default for IndexRefresh {
+    fn default() -> Self {
+        Self::Stale {
+            ttl: DEFAULT_INDEX_TTL,
+        }
+    }
+}
+
+/// A source of the release index, in the chain of `--release-source`, and how long it may take to
+/// serve the index before the next source of the chain is tried instead.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChainedReleaseSource {
+    pub source: ReleaseSource,
+    pub timeout: Option<Duration>,
+}
+
+impl FromStr for ChainedReleaseSource {
+    type Err = CargoMSRVError;
+
+    /// Parse a source with an optional timeout, like `rust-dist:30s`.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (source, timeout) = match input.split_once(':') {
+            Some((source, timeout)) => (source, Some(parse_duration(timeout)?)),
+            None => (input, None),
+        };
+
+        Ok(Self {
+            source: source.try_into()?,
+            timeout,
+        })
+    }
+}
+
+impl fmt::Display for ChainedReleaseSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.timeout {
+            Some(timeout) => write!(f, "{}:{}", self.source, FormattedDuration(timeout)),
+            None => write!(f, "{}", self.source),
         }
     }
 }
 
-#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, serde::Serialize)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, serde::Serialize, ValueEnum)]
 #[serde(rename_all = "snake_case")]
 pub enum SearchMethod {
+    /// Check each release, from the most recent to the least recent
     Linear,
+    /// Halve the search space with each check
     #[default]
     Bisect,
+    /// Start from the static MSRV estimate, see `cargo msrv estimate`
+    Hybrid,
+    /// Start from the declared MSRV, like `package.rust-version`
+    Declared,
+    /// Start from the MSRV which an earlier run found
+    LastRun,
 }
 
 impl From<SearchMethod> for &'static str {
@@ -493,6 +1590,9 @@ impl From<SearchMethod> for &'static str {
         match method {
             SearchMethod::Linear => "linear",
             SearchMethod::Bisect => "bisect",
+            SearchMethod::Hybrid => "hybrid",
+            SearchMethod::Declared => "declared",
+            SearchMethod::LastRun => "last-run",
         }
     }
 }
@@ -501,20 +1601,34 @@ impl From<SearchMethod> for &'static str {
 pub struct TracingOptions {
     target: TracingTargetOption,
     level: LogLevel,
+    /// The size above which the log file is rotated.
+    max_log_size: u64,
+    /// The amount of rotated log files which is kept.
+    max_log_files: usize,
 }
 
 impl TracingOptions {
     pub fn new(target: TracingTargetOption, level: LogLevel) -> Self {
-        Self { target, level }
+        Self {
+            target,
+            level,
+            max_log_size: DEFAULT_MAX_LOG_SIZE,
+            max_log_files: DEFAULT_MAX_LOG_FILES,
+        }
+    }
+
+    /// Rotate the log file once it would grow larger than the given size, and keep the given
+    /// amount of rotated log files.
+    pub fn with_rotation(mut self, max_log_size: u64, max_log_files: usize) -> Self {
+        self.max_log_size = max_log_size;
+        self.max_log_files = max_log_files;
+        self
     }
 }
 
 impl Default for TracingOptions {
     fn default() -> Self {
-        Self {
-            target: TracingTargetOption::File,
-            level: LogLevel::default(),
-        }
+        Self::new(TracingTargetOption::File, LogLevel::default())
     }
 }
 
@@ -526,11 +1640,23 @@ impl TracingOptions {
     pub fn level(&self) -> &LogLevel {
         &self.level
     }
+
+    pub fn max_log_size(&self) -> u64 {
+        self.max_log_size
+    }
+
+    pub fn max_log_files(&self) -> usize {
+        self.max_log_files
+    }
 }
 
 #[derive(Debug, Copy, Clone, ValueEnum)]
 pub enum TracingTargetOption {
+    /// The log file in the local data directory of cargo-msrv, which is rotated by size
     File,
+    /// Standard error, along with the user output
+    Stderr,
+    /// Standard output
     Stdout,
 }
 
@@ -542,6 +1668,7 @@ impl Default for TracingTargetOption {
 
 impl TracingTargetOption {
     pub const FILE: &'static str = "file";
+    pub const STDERR: &'static str = "stderr";
     pub const STDOUT: &'static str = "stdout";
 }
 
@@ -551,6 +1678,7 @@ impl FromStr for TracingTargetOption {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             Self::FILE => Ok(Self::File),
+            Self::STDERR => Ok(Self::Stderr),
             Self::STDOUT => Ok(Self::Stdout),
             unknown => Err(CargoMSRVError::InvalidConfig(format!(
                 "Given log target '{}' is not valid",
@@ -559,3 +1687,320 @@ impl FromStr for TracingTargetOption {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        always = { ColorChoice::Always, &[("NO_COLOR", "1")], false, true },
+        never = { ColorChoice::Never, &[], true, false },
+        auto_terminal = { ColorChoice::Auto, &[], true, true },
+        auto_redirected = { ColorChoice::Auto, &[], false, false },
+        no_color = { ColorChoice::Auto, &[("NO_COLOR", "1")], true, false },
+        empty_no_color = { ColorChoice::Auto, &[("NO_COLOR", "")], true, true },
+        clicolor_off = { ColorChoice::Auto, &[("CLICOLOR", "0")], true, false },
+        clicolor_force = { ColorChoice::Auto, &[("CLICOLOR_FORCE", "1")], false, true },
+        clicolor_force_off = { ColorChoice::Auto, &[("CLICOLOR_FORCE", "0")], false, false },
+        no_color_over_force = { ColorChoice::Auto, &[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], true, false },
+    )]
+    fn color(choice: ColorChoice, vars: &[(&str, &str)], is_terminal: bool, expected: bool) {
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        };
+
+        assert_eq!(choice.enables_color(var, is_terminal), expected);
+    }
+
+    #[test]
+    fn also_crates() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        for crate_dir in [
+            "",
+            "examples/a",
+            "examples/b",
+            "examples/not-a-crate",
+            "fuzz",
+        ] {
+            std::fs::create_dir_all(root.join(crate_dir)).unwrap();
+            if crate_dir != "examples/not-a-crate" {
+                std::fs::write(root.join(crate_dir).join("Cargo.toml"), "").unwrap();
+            }
+        }
+
+        let globs = ["examples/*", "fuzz/Cargo.toml", "examples/a", "*"].map(String::from);
+
+        assert_eq!(
+            also_crate_roots(root, &globs).unwrap(),
+            vec![
+                root.join("examples/a"),
+                root.join("examples/b"),
+                root.join("fuzz")
+            ]
+        );
+    }
+
+    #[test]
+    fn also_invalid_glob() {
+        let globs = ["examples/[".to_string()];
+
+        assert!(matches!(
+            also_crate_roots(Utf8Path::new("."), &globs),
+            Err(CargoMSRVError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn custom_toolchains_of_loaded_metadata() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        write_crate(
+            root,
+            "[package]\nname = \"a\"\nversion = \"0.1.0\"\n\n[[package.metadata.cargo-msrv.custom-toolchains]]\nname = \"stage1\"\nversion = \"1.66.0\"\n",
+            "lib.rs",
+        );
+
+        let metadata = MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+        let environment = EnvironmentContext {
+            root_crate_path: Utf8PathBuf::from("/does/not/exist"),
+            workspace_packages: WorkspacePackages::default().with_metadata(metadata),
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
+        };
+
+        let toolchains = find::configured_custom_toolchains(&environment).unwrap();
+
+        assert_eq!(toolchains[0].name, "stage1");
+    }
+
+    #[test]
+    fn custom_toolchains_of_broken_manifest() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        write_crate(root, "[package\n", "lib.rs");
+
+        let environment = EnvironmentContext {
+            root_crate_path: root.to_path_buf(),
+            workspace_packages: WorkspacePackages::default(),
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
+        };
+
+        assert!(matches!(
+            find::configured_custom_toolchains(&environment),
+            Err(CargoMSRVError::CargoMetadata(_))
+        ));
+    }
+
+    fn write_crate(dir: &Utf8Path, manifest: &str, src: &str) {
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+        std::fs::write(dir.join("src").join(src), "").unwrap();
+    }
+
+    #[yare::parameterized(
+        single_crate = { "", true, false, Targets { examples: true, benches: false, library: true, packages: vec![], ..Targets::default() } },
+        example_crate = {
+            "[workspace]\nmembers = [\"examples/demo\"]\n",
+            true,
+            false,
+            Targets { examples: true, benches: false, library: true, packages: vec!["a".into(), "demo".into()], ..Targets::default() }
+        },
+        benches = {
+            "[workspace]\nmembers = [\"examples/demo\"]\n",
+            false,
+            true,
+            Targets { examples: false, benches: true, library: true, packages: vec![], ..Targets::default() }
+        },
+    )]
+    fn targets(workspace: &str, examples: bool, benches: bool, expected: Targets) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        let manifest = format!(
+            "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n{workspace}"
+        );
+        write_crate(root, &manifest, "lib.rs");
+        write_crate(
+            &root.join("examples/demo"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+            "main.rs",
+        );
+
+        let metadata = MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+
+        assert_eq!(
+            selected_targets(&metadata, examples, benches, &[]),
+            expected
+        );
+    }
+
+    #[yare::parameterized(
+        bin = { Some("cli"), false, true },
+        unknown_bin = { Some("tool"), false, false },
+        lib = { None, true, true },
+        bin_and_lib = { Some("cli"), true, true },
+    )]
+    fn validated_targets(bin: Option<&str>, lib: bool, expected: bool) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        let manifest = "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n[[bin]]\nname = \"cli\"\npath = \"src/lib.rs\"\n";
+        write_crate(root, manifest, "lib.rs");
+
+        let metadata = MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+
+        assert_eq!(
+            validate_selected_targets(&metadata, bin, lib, &[]).is_ok(),
+            expected
+        );
+    }
+
+    #[yare::parameterized(
+        bin = { Some("cli"), false, &["cli: color"] },
+        other_bin = { Some("plain"), false, &[] },
+        examples = { None, true, &["demo: demo-feature", "fancy: wasm-example/fancy"] },
+        neither = { None, false, &[] },
+    )]
+    fn required_features_of_targets(bin: Option<&str>, examples: bool, expected: &[&str]) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        let manifest = "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\
+            [features]\ncolor = []\ndemo-feature = []\n\
+            [[bin]]\nname = \"cli\"\npath = \"src/lib.rs\"\nrequired-features = [\"color\"]\n\
+            [[bin]]\nname = \"plain\"\npath = \"src/lib.rs\"\n\
+            [[example]]\nname = \"demo\"\npath = \"src/lib.rs\"\nrequired-features = [\"demo-feature\"]\n\
+            [workspace]\nmembers = [\"examples/wasm\"]\n";
+        write_crate(root, manifest, "lib.rs");
+        write_crate(
+            &root.join("examples/wasm"),
+            "[package]\nname = \"wasm-example\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\
+            [features]\nfancy = []\n\
+            [[bin]]\nname = \"fancy\"\npath = \"src/main.rs\"\nrequired-features = [\"fancy\"]\n\
+            [[example]]\nname = \"fancy\"\npath = \"src/main.rs\"\nrequired-features = [\"fancy\"]\n",
+            "main.rs",
+        );
+
+        let metadata = MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+
+        let required = required_features(&metadata, bin, examples, false, &[])
+            .into_iter()
+            .map(|required| format!("{}: {}", required.target, required.features.join(",")))
+            .collect::<Vec<_>>();
+
+        assert_eq!(required, expected);
+    }
+
+    #[test]
+    fn no_lib_target() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        let manifest = "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n";
+        write_crate(root, manifest, "main.rs");
+
+        let metadata = MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+
+        assert!(matches!(
+            validate_selected_targets(&metadata, None, true, &[]),
+            Err(CargoMSRVError::NoLibTarget)
+        ));
+    }
+
+    #[yare::parameterized(
+        isolated = { false, &["b"], Targets { benches: true, library: true, packages: vec!["b".into()], ..Targets::default() } },
+        isolated_with_example_crate = {
+            true,
+            &["b"],
+            Targets { examples: true, library: true, packages: vec!["b".into(), "demo".into()], ..Targets::default() }
+        },
+    )]
+    fn isolated_targets(examples: bool, isolated: &[&str], expected: Targets) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        let manifest = "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n[workspace]\nmembers = [\"b\", \"examples/demo\"]\n";
+        write_crate(root, manifest, "main.rs");
+        write_crate(
+            &root.join("b"),
+            "[package]\nname = \"b\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+            "lib.rs",
+        );
+        write_crate(
+            &root.join("examples/demo"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+            "main.rs",
+        );
+
+        let metadata = MetadataCommand::new()
+            .manifest_path(root.join("Cargo.toml"))
+            .no_deps()
+            .exec()
+            .unwrap();
+
+        let isolated = isolated
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            selected_targets(&metadata, examples, !examples, &isolated),
+            expected
+        );
+    }
+
+    #[test]
+    fn fuzz_projects() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        let manifest = "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n[workspace]\nmembers = [\"b\"]\n";
+        write_crate(root, manifest, "lib.rs");
+        write_crate(
+            &root.join("b"),
+            "[package]\nname = \"b\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+            "lib.rs",
+        );
+        for fuzz in ["fuzz", "b/fuzz"] {
+            write_crate(
+                &root.join(fuzz),
+                "[package]\nname = \"fuzz\"\n[workspace]\n",
+                "main.rs",
+            );
+        }
+
+        let environment = EnvironmentContext {
+            root_crate_path: root.to_path_buf(),
+            workspace_packages: WorkspacePackages::default(),
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
+        };
+
+        assert_eq!(
+            fuzz_project_roots(&environment),
+            vec![root.join("fuzz"), root.join("b/fuzz")]
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use crate::sub_command::verify::RustVersion;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct OutdatedContext {
+    /// The MSRV to compare the releases of dependencies against
+    pub rust_version: RustVersion,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for OutdatedContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let outdated_opts = match subcommand {
+            SubCommand::Outdated(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `outdated`!"),
+        };
+
+        let environment = (&shared_opts).try_into()?;
+
+        let rust_version = match outdated_opts.rust_version {
+            Some(version) => RustVersion::from_arg(version),
+            None => RustVersion::try_from_environment(&environment)?,
+        };
+
+        Ok(Self {
+            rust_version,
+            environment,
+        })
+    }
+}
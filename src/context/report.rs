@@ -0,0 +1,63 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use camino::Utf8PathBuf;
+use clap::ValueEnum;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+
+#[derive(Debug)]
+pub struct ReportContext {
+    /// The event log of the run, as recorded with `--log-events`
+    pub from: Utf8PathBuf,
+
+    /// The format in which the report is rendered
+    pub format: ReportFormat,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for ReportContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let report_opts = match subcommand {
+            SubCommand::Report(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `report`!"),
+        };
+
+        Ok(Self {
+            from: report_opts.from,
+            format: report_opts.format,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, ValueEnum, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    /// A markdown document, e.g. for a pull request comment or a job summary
+    #[default]
+    Markdown,
+    /// A standalone HTML page
+    Html,
+    /// A JUnit XML report, as read by most CI systems, with a test case for each check
+    Junit,
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Markdown => write!(f, "markdown"),
+            Self::Html => write!(f, "html"),
+            Self::Junit => write!(f, "junit"),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use crate::cli::{CargoMsrvOpts, SubCommand};
 use crate::context::{EnvironmentContext, RustReleasesContext};
 use crate::error::CargoMSRVError;
 use crate::manifest::bare_version::BareVersion;
+use crate::writer::changelog::ChangelogMode;
 use std::convert::{TryFrom, TryInto};
 
 #[derive(Debug)]
@@ -9,6 +10,12 @@ pub struct SetContext {
     /// MSRV to set.
     pub msrv: BareVersion,
 
+    /// Also update the other declarations of the MSRV
+    pub sync: bool,
+
+    /// Append an entry on the change of the MSRV to the changelog
+    pub changelog: Option<ChangelogMode>,
+
     /// The context for Rust releases
     pub rust_releases: RustReleasesContext,
 
@@ -35,6 +42,8 @@ impl TryFrom<CargoMsrvOpts> for SetContext {
 
         Ok(Self {
             msrv: set_opts.msrv,
+            sync: set_opts.sync,
+            changelog: ChangelogMode::from_flags(set_opts.changelog, set_opts.changelog_dry_run),
             rust_releases: set_opts.rust_releases_opts.into(),
             environment,
         })
@@ -0,0 +1,41 @@
+use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::EnvironmentContext;
+use crate::error::CargoMSRVError;
+use crate::manifest::bare_version::BareVersion;
+use std::convert::{TryFrom, TryInto};
+
+#[derive(Debug)]
+pub struct SyncContext {
+    /// The MSRV to which the declarations are updated, or `None` for the MSRV of the Cargo
+    /// manifest.
+    pub msrv: Option<BareVersion>,
+
+    /// Only summarize the changes, without writing them
+    pub dry_run: bool,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for SyncContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+            ..
+        } = opts;
+
+        let sync_opts = match subcommand {
+            SubCommand::Sync(opts) => opts,
+            _ => unreachable!("This should never happen. The subcommand is not `sync`!"),
+        };
+
+        Ok(Self {
+            msrv: sync_opts.msrv,
+            dry_run: sync_opts.dry_run,
+            environment: (&shared_opts).try_into()?,
+        })
+    }
+}
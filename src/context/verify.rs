@@ -1,15 +1,21 @@
 use crate::cli::{CargoMsrvOpts, SubCommand};
+use crate::context::find::configured_custom_toolchains;
 use crate::context::{
-    CheckCommandContext, EnvironmentContext, RustReleasesContext, ToolchainContext,
+    also_crate_roots, CheckCommandContext, EnvironmentContext, FindContext, LockfileStrategy,
+    RustReleasesContext, SearchMethod, ToolchainContext, ToolchainProvider,
 };
+use crate::manifest::denylist::configured_denylist;
 
 use crate::check::RunCommand;
-use crate::error::CargoMSRVError;
-use crate::external_command::cargo_command::CargoCommand;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::msrv_policy::ReleaseType;
+use crate::rust::distribution_toolchain::DistributionToolchain;
+use crate::sub_command::check_consistency::RequiredConsistency;
 use crate::sub_command::verify::RustVersion;
+use camino::Utf8PathBuf;
 use std::convert::{TryFrom, TryInto};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct VerifyContext {
     /// The resolved Rust version, to check against for toolchain compatibility.
     pub rust_version: RustVersion,
@@ -17,9 +23,35 @@ pub struct VerifyContext {
     /// Ignore the lockfile for the MSRV verification
     pub ignore_lockfile: bool,
 
+    /// How to handle the lockfile, when the cargo of a toolchain can't read it
+    pub lockfile_strategy: Option<LockfileStrategy>,
+
     /// Don't print the result of compatibility check
     pub no_check_feedback: bool,
 
+    /// Store the artifacts of the check, to be browsed with `cargo msrv inspect`
+    pub save_run: bool,
+
+    /// The roots of the other crates to run for, included with `--also`
+    pub also: Vec<Utf8PathBuf>,
+
+    /// Find the MSRV first, and compare the Rust version to it (`--find` or `--slack`)
+    pub find_msrv: Option<FindMsrv>,
+
+    /// Find the MSRV first, and fail if it raises the Rust version for a type of release which
+    /// the MSRV policy doesn't allow to (`--release-type`)
+    pub release_type: Option<ReleaseType>,
+
+    /// Exclude the Rust versions of which cargo fails to parse the project, when finding the MSRV
+    /// first
+    pub cargo_gates: bool,
+
+    /// Fail when these declarations of the MSRV disagree with the Cargo manifest
+    pub required_consistency: Option<RequiredConsistency>,
+
+    /// Fail when the fingerprint of the environment has another id
+    pub expect_fingerprint: Option<String>,
+
     /// The context for Rust releases
     pub rust_releases: RustReleasesContext,
 
@@ -29,10 +61,24 @@ pub struct VerifyContext {
     /// The context for custom checks to be used with rustup
     pub check_cmd: CheckCommandContext,
 
+    /// Provides the toolchain to check
+    pub toolchain_provider: ToolchainProvider,
+
     /// Resolved environment options
     pub environment: EnvironmentContext,
 }
 
+/// How the Rust version is compared to the MSRV which is found before verifying.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FindMsrv {
+    /// Fail unless the Rust version is at most `tolerance` minor versions higher than the MSRV
+    /// (`--find`)
+    Strict { tolerance: u64 },
+    /// Report how many minor releases the Rust version is higher than the MSRV, without failing
+    /// on it (`--slack`)
+    Slack,
+}
+
 impl TryFrom<CargoMsrvOpts> for VerifyContext {
     type Error = CargoMSRVError;
 
@@ -47,21 +93,67 @@ impl TryFrom<CargoMsrvOpts> for VerifyContext {
             _ => unreachable!("This should never happen. The subcommand is not `verify`!"),
         };
 
-        let toolchain = verify_opts.toolchain_opts.try_into()?;
+        let toolchain_provider = match (verify_opts.rustc, verify_opts.cargo) {
+            (Some(rustc), Some(cargo)) => {
+                ToolchainProvider::Distribution(DistributionToolchain::probe(rustc, cargo)?)
+            }
+            _ => ToolchainProvider::try_from_opts(
+                verify_opts.container_opts,
+                verify_opts.nix_opts,
+                verify_opts.remote_opts,
+                verify_opts.tarball_opts,
+            )?,
+        };
+
+        let mut toolchain_opts = verify_opts.toolchain_opts;
+        if toolchain_opts.target.is_none() {
+            toolchain_opts.target = toolchain_provider.default_target()?;
+        }
+
+        let toolchain = toolchain_opts.try_into()?;
         let environment = (&shared_opts).try_into()?;
 
-        let rust_version = match verify_opts.rust_version {
-            Some(v) => RustVersion::from_arg(v),
-            None => RustVersion::try_from_environment(&environment)?,
+        let rust_version = match (verify_opts.rust_version, &toolchain_provider) {
+            (Some(v), _) => RustVersion::from_arg(v),
+            (None, ToolchainProvider::Distribution(distribution)) => {
+                RustVersion::from_distribution(distribution)
+            }
+            (None, _) => match RustVersion::try_from_env_var()? {
+                Some(rust_version) => rust_version,
+                None => RustVersion::try_from_environment(&environment)?,
+            },
         };
 
+        let check_cmd: CheckCommandContext = verify_opts.custom_check_opts.try_into()?;
+        check_cmd.validate_targets(&environment)?;
+        check_cmd.validate_vendor(&toolchain_provider)?;
+
         Ok(Self {
             rust_version,
             ignore_lockfile: verify_opts.ignore_lockfile,
+            lockfile_strategy: verify_opts.lockfile_strategy,
             no_check_feedback: verify_opts.no_check_feedback,
+            save_run: verify_opts.save_run,
+            also: also_crate_roots(environment.root(), &verify_opts.also)?,
+            find_msrv: match (verify_opts.find, verify_opts.slack) {
+                (true, _) => Some(FindMsrv::Strict {
+                    tolerance: verify_opts.tolerance,
+                }),
+                (false, true) => Some(FindMsrv::Slack),
+                (false, false) => None,
+            },
+            release_type: verify_opts.release_type,
+            cargo_gates: !verify_opts.no_cargo_gates,
+            required_consistency: match (verify_opts.check_consistency, verify_opts.check_readme) {
+                (true, _) => Some(RequiredConsistency::All),
+                (false, true) => Some(RequiredConsistency::Readme),
+                (false, false) => None,
+            },
+            expect_fingerprint: verify_opts.expect_fingerprint,
             rust_releases: verify_opts.rust_releases_opts.into(),
             toolchain,
-            check_cmd: verify_opts.custom_check_opts.into(),
+            check_cmd,
+            toolchain_provider,
             environment,
         })
     }
@@ -69,17 +161,59 @@ impl TryFrom<CargoMsrvOpts> for VerifyContext {
 
 impl VerifyContext {
     pub fn run_command(&self) -> RunCommand {
-        if let Some(custom) = &self.check_cmd.rustup_command {
-            RunCommand::custom(custom.clone())
+        self.check_cmd
+            .run_command(self.toolchain.target, &self.environment)
+            .with_limits(self.toolchain.limits)
+    }
+
+    /// The context with which the MSRV is found, for `--find`.
+    pub fn find_context(&self) -> TResult<FindContext> {
+        Ok(FindContext {
+            search_method: SearchMethod::default(),
+            shard: None,
+            write_toolchain_file: false,
+            ignore_lockfile: self.ignore_lockfile,
+            lockfile_strategy: self.lockfile_strategy,
+            no_check_feedback: self.no_check_feedback,
+            write_msrv: false,
+            changelog: None,
+            sync: false,
+            diff_errors: false,
+            report_unlocks: false,
+            cargo_gates: self.cargo_gates,
+            save_run: self.save_run,
+            also: Vec::new(),
+            targets: Vec::new(),
+            control: None,
+            deadline: None,
+            resume: false,
+            rust_releases: self.rust_releases.clone(),
+            toolchain: self.toolchain.clone(),
+            check_cmd: self.check_cmd.clone(),
+            toolchain_provider: self.toolchain_provider.clone(),
+            custom_toolchains: configured_custom_toolchains(&self.environment)?,
+            denylist: configured_denylist(&self.environment)?,
+            environment: self.environment.clone(),
+        })
+    }
+
+    /// The context for another crate, like one included with `--also`. Unless the Rust version
+    /// was given, the MSRV of the other crate is verified.
+    pub fn for_crate(&self, root: Utf8PathBuf) -> TResult<Self> {
+        let environment = self.environment.for_crate(root);
+
+        let rust_version = if self.rust_version.is_declared() {
+            RustVersion::try_from_environment(&environment)?
         } else {
-            let cargo_command = CargoCommand::default()
-                .target(Some(self.toolchain.target))
-                .features(self.check_cmd.cargo_features.clone())
-                .all_features(self.check_cmd.cargo_all_features)
-                .no_default_features(self.check_cmd.cargo_no_default_features);
+            self.rust_version.clone()
+        };
 
-            RunCommand::default(cargo_command)
-        }
+        Ok(Self {
+            rust_version,
+            also: Vec::new(),
+            environment,
+            ..self.clone()
+        })
     }
 }
 
@@ -98,4 +232,249 @@ mod tests {
             assert_eq!(context.toolchain.target, "x");
         }
     }
+
+    mod container {
+        use crate::cli::CargoCli;
+        use crate::context::{ContainerEngine, ToolchainProvider, VerifyContext};
+        use std::convert::TryFrom;
+
+        fn context(args: &[&str]) -> crate::TResult<VerifyContext> {
+            let args = ["cargo", "msrv", "verify", "--target", "x"]
+                .iter()
+                .chain(args);
+            let opts = CargoCli::parse_args(args);
+            VerifyContext::try_from(opts.to_cargo_msrv_cli().to_opts())
+        }
+
+        #[test]
+        fn disabled() {
+            assert_eq!(
+                context(&[]).unwrap().toolchain_provider,
+                ToolchainProvider::Rustup
+            );
+        }
+
+        #[test]
+        fn image_implies_container() {
+            let context = context(&[
+                "--container-engine",
+                "podman",
+                "--container-image",
+                "myregistry/rust:{version}-alpine",
+            ])
+            .unwrap();
+
+            let ToolchainProvider::Container(container) = context.toolchain_provider else {
+                panic!("expected the container toolchain provider");
+            };
+            assert_eq!(container.engine, ContainerEngine::Podman);
+            assert_eq!(container.image_template, "myregistry/rust:{version}-alpine");
+            assert!(container.user.is_none());
+        }
+
+        #[test]
+        fn image_without_version() {
+            let context = context(&[
+                "--container-engine",
+                "docker",
+                "--container-image",
+                "rust:latest",
+            ]);
+
+            assert!(context.is_err());
+        }
+    }
+
+    mod nix {
+        use crate::cli::CargoCli;
+        use crate::context::{NixToolchainSource, ToolchainProvider, VerifyContext};
+        use clap::Parser;
+        use std::convert::TryFrom;
+
+        fn context(args: &[&str]) -> crate::TResult<VerifyContext> {
+            let args = ["cargo", "msrv", "verify", "--target", "x"]
+                .iter()
+                .chain(args);
+            let opts = CargoCli::parse_args(args);
+            VerifyContext::try_from(opts.to_cargo_msrv_cli().to_opts())
+        }
+
+        #[test]
+        fn default_expression() {
+            let context = context(&["--nix"]).unwrap();
+
+            let ToolchainProvider::Nix(nix) = context.toolchain_provider else {
+                panic!("expected the nix toolchain provider");
+            };
+            assert!(matches!(
+                nix.source_template,
+                NixToolchainSource::Expression(expression) if expression.contains("rust-overlay")
+            ));
+        }
+
+        #[test]
+        fn installable_implies_nix() {
+            let context =
+                context(&["--nix-installable", "github:owner/repo#rust-{version}"]).unwrap();
+
+            let ToolchainProvider::Nix(nix) = context.toolchain_provider else {
+                panic!("expected the nix toolchain provider");
+            };
+            assert_eq!(
+                nix.source_template,
+                NixToolchainSource::Installable("github:owner/repo#rust-{version}".to_string())
+            );
+        }
+
+        #[yare::parameterized(
+            with_container = { &["--nix", "--container"] },
+            with_remote = { &["--nix", "--remote", "user@host"] },
+            remote_dir_without_remote = { &["--remote-dir", "builds"] },
+            expression_and_installable = { &["--nix-expr", "{version}", "--nix-installable", "{version}"] },
+        )]
+        fn rejected(args: &[&str]) {
+            let args = ["cargo", "msrv", "verify"].iter().chain(args);
+            assert!(CargoCli::try_parse_from(args).is_err());
+        }
+    }
+
+    mod distribution_toolchain {
+        use crate::cli::CargoCli;
+        use crate::context::{ToolchainProvider, VerifyContext};
+        use clap::Parser;
+        use std::convert::TryFrom;
+
+        /// A `rustc` which only reports its verbose version, for the given host.
+        #[cfg(unix)]
+        fn fake_rustc(dir: &assert_fs::TempDir, host: &str) -> std::path::PathBuf {
+            use std::os::unix::fs::PermissionsExt;
+
+            let path = dir.path().join("rustc");
+            let verbose_version = format!("rustc 1.63.0\nrelease: 1.63.0\nhost: {}", host);
+            std::fs::write(
+                &path,
+                format!("#!/bin/sh\nprintf '{}\\n'\n", verbose_version),
+            )
+            .unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn target_from_rustc_host() {
+            use crate::semver;
+
+            let dir = assert_fs::TempDir::new().unwrap();
+            let rustc = fake_rustc(&dir, "riscv64gc-unknown-linux-gnu");
+
+            let opts = CargoCli::parse_args([
+                "cargo".as_ref(),
+                "msrv".as_ref(),
+                "verify".as_ref(),
+                "--rustc".as_ref(),
+                rustc.as_os_str(),
+                "--cargo".as_ref(),
+                "cargo".as_ref(),
+            ]);
+            let context = VerifyContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+            let ToolchainProvider::Distribution(distribution) = context.toolchain_provider else {
+                panic!("expected the distribution toolchain provider");
+            };
+            assert_eq!(context.toolchain.target, "riscv64gc-unknown-linux-gnu");
+            assert_eq!(distribution.host(), "riscv64gc-unknown-linux-gnu");
+            assert_eq!(*distribution.version(), semver::Version::new(1, 63, 0));
+            assert_eq!(
+                context.rust_version.version().to_semver_version(),
+                *distribution.version()
+            );
+        }
+
+        #[yare::parameterized(
+            rustc_without_cargo = { &["--rustc", "rustc"] },
+            cargo_without_rustc = { &["--cargo", "cargo"] },
+            with_rust_version = { &["--rustc", "rustc", "--cargo", "cargo", "--rust-version", "1.56"] },
+        )]
+        fn rejected(args: &[&str]) {
+            let args = ["cargo", "msrv", "verify"].iter().chain(args);
+            assert!(CargoCli::try_parse_from(args).is_err());
+        }
+    }
+    mod find {
+        use crate::cli::CargoCli;
+        use crate::context::verify::FindMsrv;
+        use crate::context::VerifyContext;
+        use clap::Parser;
+        use std::convert::TryFrom;
+
+        fn context(args: &[&str]) -> crate::TResult<VerifyContext> {
+            let args = ["cargo", "msrv", "verify"].iter().chain(args);
+            let opts = CargoCli::parse_args(args);
+            VerifyContext::try_from(opts.to_cargo_msrv_cli().to_opts())
+        }
+
+        #[yare::parameterized(
+            disabled = { &[], None },
+            strict = { &["--find"], Some(FindMsrv::Strict { tolerance: 0 }) },
+            tolerance = { &["--find", "--tolerance", "2"], Some(FindMsrv::Strict { tolerance: 2 }) },
+            slack = { &["--slack"], Some(FindMsrv::Slack) },
+        )]
+        fn find_msrv(args: &[&str], expected: Option<FindMsrv>) {
+            assert_eq!(context(args).unwrap().find_msrv, expected);
+        }
+
+        #[yare::parameterized(
+            tolerance_without_find = { &["--tolerance", "2"] },
+            slack_with_find = { &["--find", "--slack"] },
+            slack_with_distribution_toolchain = { &["--slack", "--rustc", "rustc", "--cargo", "cargo"] },
+            with_distribution_toolchain = { &["--find", "--rustc", "rustc", "--cargo", "cargo"] },
+        )]
+        fn rejected(args: &[&str]) {
+            let args = ["cargo", "msrv", "verify"].iter().chain(args);
+            assert!(CargoCli::try_parse_from(args).is_err());
+        }
+
+        #[yare::parameterized(
+            default = { &["--find"], true },
+            disabled = { &["--find", "--no-cargo-gates"], false },
+        )]
+        fn cargo_gates(args: &[&str], expected: bool) {
+            let context = context(args).unwrap();
+
+            assert_eq!(context.cargo_gates, expected);
+            assert_eq!(context.find_context().unwrap().cargo_gates, expected);
+        }
+    }
+
+    mod release_type {
+        use crate::cli::CargoCli;
+        use crate::context::VerifyContext;
+        use crate::manifest::msrv_policy::ReleaseType;
+        use clap::Parser;
+        use std::convert::TryFrom;
+
+        #[yare::parameterized(
+            disabled = { &[], None },
+            patch = { &["--release-type", "patch"], Some(ReleaseType::Patch) },
+            major = { &["--release-type", "major"], Some(ReleaseType::Major) },
+        )]
+        fn release_type(args: &[&str], expected: Option<ReleaseType>) {
+            let args = ["cargo", "msrv", "verify"].iter().chain(args);
+            let opts = CargoCli::parse_args(args);
+            let context = VerifyContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+            assert_eq!(context.release_type, expected);
+        }
+
+        #[yare::parameterized(
+            unknown = { &["--release-type", "breaking"] },
+            with_find = { &["--release-type", "minor", "--find"] },
+            with_slack = { &["--release-type", "minor", "--slack"] },
+        )]
+        fn rejected(args: &[&str]) {
+            let args = ["cargo", "msrv", "verify"].iter().chain(args);
+            assert!(CargoCli::try_parse_from(args).is_err());
+        }
+    }
 }
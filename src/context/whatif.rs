@@ -0,0 +1,210 @@
+use crate::check::RunCommand;
+use crate::cli::{CargoMsrvOpts, SubCommand, WhatIfAction};
+use crate::context::{CheckCommandContext, EnvironmentContext, ToolchainContext};
+use crate::error::CargoMSRVError;
+use crate::semver;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct WhatIfContext {
+    /// The change to the dependencies which would be made
+    pub change: WhatIfChange,
+
+    /// Whether to check if the crate still compiles with the current MSRV, once the change is
+    /// made
+    pub verify: bool,
+
+    /// Don't print the result of the compatibility check
+    pub no_check_feedback: bool,
+
+    /// The context for Rust toolchains
+    pub toolchain: ToolchainContext,
+
+    /// The context for custom checks to be used with rustup
+    pub check_cmd: CheckCommandContext,
+
+    /// Resolved environment options
+    pub environment: EnvironmentContext,
+}
+
+impl TryFrom<CargoMsrvOpts> for WhatIfContext {
+    type Error = CargoMSRVError;
+
+    fn try_from(opts: CargoMsrvOpts) -> Result<Self, Self::Error> {
+        let CargoMsrvOpts {
+            shared_opts,
+            subcommand,
+        } = opts;
+
+        let action = match subcommand {
+            SubCommand::WhatIf(opts) => opts.action,
+            _ => unreachable!("This should never happen. The subcommand is not `whatif`!"),
+        };
+
+        let (change, verify, no_check_feedback, toolchain_opts, custom_check_opts) = match action {
+            WhatIfAction::Add(opts) => (
+                WhatIfChange::Add(opts.dependency),
+                opts.verify,
+                opts.no_check_feedback,
+                opts.toolchain_opts,
+                opts.custom_check_opts,
+            ),
+            WhatIfAction::Update(opts) => (
+                WhatIfChange::Update,
+                opts.verify,
+                opts.no_check_feedback,
+                opts.toolchain_opts,
+                opts.custom_check_opts,
+            ),
+        };
+
+        let environment: EnvironmentContext = (&shared_opts).try_into()?;
+        let check_cmd: CheckCommandContext = custom_check_opts.try_into()?;
+        check_cmd.validate_targets(&environment)?;
+
+        Ok(Self {
+            change,
+            verify,
+            no_check_feedback,
+            toolchain: toolchain_opts.try_into()?,
+            check_cmd,
+            environment,
+        })
+    }
+}
+
+impl WhatIfContext {
+    pub fn run_command(&self) -> RunCommand {
+        self.check_cmd
+            .run_command(self.toolchain.target, &self.environment)
+            .with_limits(self.toolchain.limits)
+    }
+}
+
+/// A change to the dependencies of a crate, of which the effect on the MSRV is analysed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WhatIfChange {
+    /// Add a dependency, or replace an existing dependency with the same name
+    Add(NewDependency),
+    /// Replace the versions pinned by the lockfile with the newest versions allowed by the version
+    /// requirements
+    Update,
+}
+
+/// A dependency, as given by `name` or `name@version`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewDependency {
+    /// The name of the crate
+    pub name: String,
+    /// The version requirement, if any
+    pub version: Option<semver::VersionReq>,
+}
+
+impl FromStr for NewDependency {
+    type Err = CargoMSRVError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (name, version) = match input.split_once('@') {
+            Some((name, version)) => (name, Some(version)),
+            None => (input, None),
+        };
+
+        if name.is_empty() {
+            return Err(CargoMSRVError::InvalidConfig(format!(
+                "No crate name given in '{}'",
+                input
+            )));
+        }
+
+        let version = version
+            .map(|version| {
+                semver::VersionReq::parse(version).map_err(|error| {
+                    CargoMSRVError::InvalidConfig(format!(
+                        "Invalid version requirement '{}': {}",
+                        version, error
+                    ))
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            name: name.to_string(),
+            version,
+        })
+    }
+}
+
+impl fmt::Display for NewDependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{}@{}", self.name, version),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::CargoCli;
+    use clap::Parser;
+
+    #[yare::parameterized(
+        name = { "serde", "serde", None },
+        name_and_version = { "serde@1.0", "serde", Some("^1.0") },
+        name_and_exact_version = { "serde@=1.0.100", "serde", Some("=1.0.100") },
+    )]
+    fn parse(input: &str, name: &str, version: Option<&str>) {
+        let dependency = input.parse::<NewDependency>().unwrap();
+
+        assert_eq!(dependency.name, name);
+        assert_eq!(
+            dependency.version.map(|v| v.to_string()).as_deref(),
+            version
+        );
+    }
+
+    #[yare::parameterized(
+        no_name = { "@1.0" },
+        invalid_version = { "serde@one" },
+    )]
+    fn parse_invalid(input: &str) {
+        assert!(input.parse::<NewDependency>().is_err());
+    }
+
+    #[test]
+    fn context() {
+        let opts = CargoCli::parse_args(["cargo", "msrv", "whatif", "add", "serde@1", "--verify"]);
+        let context = WhatIfContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+        assert!(
+            matches!(context.change, WhatIfChange::Add(dependency) if dependency.name == "serde")
+        );
+        assert!(context.verify);
+    }
+
+    #[test]
+    fn update_context() {
+        let opts = CargoCli::parse_args(["cargo", "msrv", "whatif", "update"]);
+        let context = WhatIfContext::try_from(opts.to_cargo_msrv_cli().to_opts()).unwrap();
+
+        assert_eq!(context.change, WhatIfChange::Update);
+        assert!(!context.verify);
+    }
+
+    #[test]
+    fn no_check_feedback_requires_verify() {
+        let args = [
+            "cargo",
+            "msrv",
+            "whatif",
+            "add",
+            "serde",
+            "--no-check-feedback",
+        ];
+
+        assert!(CargoCli::try_parse_from(args).is_err());
+    }
+}
@@ -0,0 +1,390 @@
+//! A control channel, over which a running search can be steered, by the user or by a wrapping
+//! program.
+//!
+//! Commands are read line by line, from stdin, or from a Unix domain socket (on Windows, a named
+//! pipe) which is listened on by the controlling program:
+//!
+//! * `pause`: pause the search before the next check;
+//! * `resume`: continue a paused search;
+//! * `skip-current`: skip the check which is running; its outcome is inconclusive, and the
+//!   search continues with the remaining Rust versions;
+//! * `abort`: stop the search, and the check which is running.
+
+use crate::error::{CargoMSRVError, TResult};
+use crate::external_command::interrupt::interrupt_running_commands;
+use crate::reporter::event::{SearchControl, Warning};
+use crate::reporter::Reporter;
+use crate::semver;
+use std::collections::BTreeSet;
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+
+/// Where the control commands are read from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ControlSource {
+    Stdin,
+    /// A Unix domain socket, or on Windows, a named pipe.
+    Socket(PathBuf),
+}
+
+impl FromStr for ControlSource {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "stdin" | "-" => Self::Stdin,
+            path => Self::Socket(PathBuf::from(path)),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ControlCommand {
+    Pause,
+    Resume,
+    SkipCurrent,
+    Abort,
+}
+
+impl FromStr for ControlCommand {
+    type Err = ParseControlCommandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "pause" => Ok(Self::Pause),
+            "resume" | "continue" => Ok(Self::Resume),
+            "skip-current" | "skip" => Ok(Self::SkipCurrent),
+            "abort" => Ok(Self::Abort),
+            _ => Err(ParseControlCommandError(s.trim().to_string())),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Unknown control command '{0}', expected one of 'pause', 'resume', 'skip-current' or 'abort'"
+)]
+pub struct ParseControlCommandError(String);
+
+#[derive(Default)]
+pub struct Control {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct State {
+    paused: bool,
+    aborted: bool,
+    /// The Rust version which is being checked, if any.
+    running: Option<semver::Version>,
+    /// Whether the running check should be skipped.
+    skip_running: bool,
+    /// The Rust versions of which the check was skipped. A search may ask for these versions
+    /// again, but they stay inconclusive.
+    skipped: BTreeSet<semver::Version>,
+}
+
+impl Control {
+    /// Apply the commands read from the given source, on a separate thread, for as long as the
+    /// program runs, or until the source is closed.
+    pub fn listen(source: &ControlSource) -> TResult<Arc<Self>> {
+        let control = Arc::new(Self::default());
+
+        let reader: Box<dyn BufRead + Send> = match source {
+            ControlSource::Stdin => Box::new(io::BufReader::new(io::stdin())),
+            ControlSource::Socket(path) => {
+                let socket =
+                    connect(path).map_err(|error| CargoMSRVError::UnableToConnectControl {
+                        path: path.clone(),
+                        error,
+                    })?;
+                Box::new(io::BufReader::new(socket))
+            }
+        };
+
+        let listener = Arc::clone(&control);
+        thread::spawn(move || listener.apply_all(reader));
+
+        Ok(control)
+    }
+
+    fn apply_all(&self, reader: impl BufRead) {
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match line.parse::<ControlCommand>() {
+                Ok(command) => self.apply(command),
+                Err(error) => info!(%error, "ignored control command"),
+            }
+        }
+    }
+
+    pub fn apply(&self, command: ControlCommand) {
+        info!(?command, "applying control command");
+
+        let mut state = self.lock();
+
+        match command {
+            ControlCommand::Pause => state.paused = true,
+            ControlCommand::Resume => state.paused = false,
+            ControlCommand::SkipCurrent if state.running.is_some() => {
+                state.skip_running = true;
+                interrupt_running_commands();
+            }
+            ControlCommand::SkipCurrent => {
+                info!("no check is running, nothing to skip");
+            }
+            ControlCommand::Abort => {
+                state.aborted = true;
+                interrupt_running_commands();
+            }
+        }
+
+        self.changed.notify_all();
+    }
+
+    /// Wait while the search is paused, then mark the check of the given version as running.
+    fn start_check(&self, version: &semver::Version, reporter: &impl Reporter) -> TResult<()> {
+        let mut state = self.lock();
+
+        if state.skipped.contains(version) {
+            return Err(CargoMSRVError::CheckSkipped {
+                version: version.clone(),
+            });
+        }
+
+        if state.paused && !state.aborted {
+            drop(state);
+            reporter.report_event(SearchControl::Paused)?;
+
+            state = self.lock();
+            while state.paused && !state.aborted {
+                state = self
+                    .changed
+                    .wait(state)
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+            }
+
+            if !state.aborted {
+                drop(state);
+                reporter.report_event(SearchControl::Resumed)?;
+                state = self.lock();
+            }
+        }
+
+        if state.aborted {
+            drop(state);
+            reporter.report_event(SearchControl::Aborted)?;
+            return Err(CargoMSRVError::SearchAborted);
+        }
+
+        state.running = Some(version.clone());
+        state.skip_running = false;
+
+        Ok(())
+    }
+
+    /// Mark the check of the given version as finished. If it was skipped, or the search was
+    /// aborted meanwhile, its outcome is discarded.
+    fn finish_check<T>(
+        &self,
+        version: &semver::Version,
+        outcome: TResult<T>,
+        reporter: &impl Reporter,
+    ) -> TResult<T> {
+        let mut state = self.lock();
+        state.running = None;
+
+        if state.aborted {
+            drop(state);
+            reporter.report_event(SearchControl::Aborted)?;
+            return Err(CargoMSRVError::SearchAborted);
+        }
+
+        if std::mem::take(&mut state.skip_running) {
+            state.skipped.insert(version.clone());
+            drop(state);
+
+            reporter.report_event(SearchControl::Skipped {
+                version: version.clone(),
+            })?;
+            reporter.report_event(Warning::version_skipped(
+                version,
+                "skipped over the control channel",
+            ))?;
+
+            return Err(CargoMSRVError::CheckSkipped {
+                version: version.clone(),
+            });
+        }
+
+        outcome
+    }
+
+    /// Run the check of the given version, under the control of this channel.
+    pub fn controlled<T>(
+        &self,
+        version: &semver::Version,
+        reporter: &impl Reporter,
+        check: impl FnOnce() -> TResult<T>,
+    ) -> TResult<T> {
+        self.start_check(version, reporter)?;
+        let outcome = check();
+        self.finish_check(version, outcome, reporter)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(unix)]
+fn connect(path: &std::path::Path) -> io::Result<std::os::unix::net::UnixStream> {
+    std::os::unix::net::UnixStream::connect(path)
+}
+
+#[cfg(not(unix))]
+fn connect(path: &std::path::Path) -> io::Result<std::fs::File> {
+    // A client opens a named pipe like a file
+    std::fs::File::open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use crate::Event;
+    use std::time::Duration;
+
+    fn version(minor: u64) -> semver::Version {
+        semver::Version::new(1, minor, 0)
+    }
+
+    #[yare::parameterized(
+        pause = { "pause", ControlCommand::Pause },
+        resume = { "resume", ControlCommand::Resume },
+        continue_ = { "continue", ControlCommand::Resume },
+        skip_current = { "skip-current", ControlCommand::SkipCurrent },
+        skip = { " skip\n", ControlCommand::SkipCurrent },
+        abort = { "ABORT", ControlCommand::Abort },
+    )]
+    fn parse_command(input: &str, expected: ControlCommand) {
+        assert_eq!(input.parse::<ControlCommand>().unwrap(), expected);
+    }
+
+    #[test]
+    fn unknown_command() {
+        assert!("stop".parse::<ControlCommand>().is_err());
+    }
+
+    #[yare::parameterized(
+        stdin = { "stdin", ControlSource::Stdin },
+        dash = { "-", ControlSource::Stdin },
+        socket = { "/tmp/control.sock", ControlSource::Socket(PathBuf::from("/tmp/control.sock")) },
+    )]
+    fn parse_source(input: &str, expected: ControlSource) {
+        assert_eq!(input.parse::<ControlSource>().unwrap(), expected);
+    }
+
+    #[test]
+    fn skip_running_check() {
+        let reporter = TestReporterWrapper::default();
+        let control = Control::default();
+
+        let outcome = control.controlled(&version(60), reporter.get(), || {
+            control.apply(ControlCommand::SkipCurrent);
+            Ok(())
+        });
+        assert!(matches!(outcome, Err(CargoMSRVError::CheckSkipped { .. })));
+
+        // A skipped version stays inconclusive, without being checked again
+        let outcome = control.controlled(&version(60), reporter.get(), || -> TResult<()> {
+            unreachable!("a skipped version is not checked again")
+        });
+        assert!(matches!(outcome, Err(CargoMSRVError::CheckSkipped { .. })));
+
+        // Other versions are checked as usual
+        assert!(control
+            .controlled(&version(59), reporter.get(), || Ok(()))
+            .is_ok());
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![
+                Event::unscoped(Message::SearchControl(SearchControl::Skipped {
+                    version: version(60)
+                })),
+                Event::unscoped(Message::Warning(Warning::version_skipped(
+                    &version(60),
+                    "skipped over the control channel"
+                ))),
+            ]
+        );
+    }
+
+    #[test]
+    fn skip_without_running_check() {
+        let reporter = TestReporterWrapper::default();
+        let control = Control::default();
+
+        control.apply(ControlCommand::SkipCurrent);
+
+        assert!(control
+            .controlled(&version(60), reporter.get(), || Ok(()))
+            .is_ok());
+    }
+
+    #[test]
+    fn abort() {
+        let reporter = TestReporterWrapper::default();
+        let control = Control::default();
+
+        control.apply(ControlCommand::Abort);
+
+        let outcome = control.controlled(&version(60), reporter.get(), || -> TResult<()> {
+            unreachable!("no check runs after the search was aborted")
+        });
+
+        assert!(matches!(outcome, Err(CargoMSRVError::SearchAborted)));
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let reporter = TestReporterWrapper::default();
+        let control = Arc::new(Control::default());
+
+        control.apply(ControlCommand::Pause);
+
+        let resumer = Arc::clone(&control);
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            resumer.apply_all(io::Cursor::new("\nbogus\nresume\n"));
+        });
+
+        assert!(control
+            .controlled(&version(60), reporter.get(), || Ok(()))
+            .is_ok());
+        handle.join().unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![
+                Event::unscoped(Message::SearchControl(SearchControl::Paused)),
+                Event::unscoped(Message::SearchControl(SearchControl::Resumed)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! The time budget of a search, see `--max-duration`.
+//!
+//! Once the budget expires, the search stops gracefully: the check which is running is
+//! interrupted, and the bounds of the MSRV which were narrowed so far are reported, instead of
+//! the MSRV.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The moment at which the time budget of a run expires.
+///
+/// The budget starts when the deadline is created, and is shared by the crates included with
+/// `--also`.
+#[derive(Clone, Copy, Debug)]
+pub struct Deadline {
+    budget: Duration,
+    at: Instant,
+}
+
+impl Deadline {
+    /// The deadline which expires once the given budget has passed, from now.
+    pub fn after(budget: Duration) -> Self {
+        Self {
+            budget,
+            at: Instant::now() + budget,
+        }
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.at
+    }
+
+    /// The time which remains until the deadline, or zero if it expired.
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_duration_since(Instant::now())
+    }
+}
+
+/// Parse a duration, like `20m`, `90s` or `1h30m`. A number without a unit is a number of
+/// seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, ParseDurationError> {
+    let error = || ParseDurationError {
+        input: input.to_string(),
+    };
+
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(error());
+    }
+
+    if let Ok(seconds) = trimmed.parse::<u64>() {
+        return Ok(Duration::from_secs(seconds));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = trimmed;
+
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or_else(error)?;
+        let (number, tail) = rest.split_at(split);
+        let number = number.parse::<f64>().map_err(|_| error())?;
+
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+
+        let seconds_per_unit = match unit.trim() {
+            "s" | "sec" => 1.0,
+            "m" | "min" => 60.0,
+            "h" => 3600.0,
+            _ => return Err(error()),
+        };
+
+        total += Duration::try_from_secs_f64(number * seconds_per_unit).map_err(|_| error())?;
+        rest = tail;
+    }
+
+    Ok(total)
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("'{input}' is not a valid duration, expected a number followed by a unit like 's', 'm' or 'h', e.g. '20m' or '1h30m'")]
+pub struct ParseDurationError {
+    input: String,
+}
+
+/// Formats a duration like `--max-duration` accepts it, e.g. `1h30m`.
+pub struct FormattedDuration(pub Duration);
+
+impl fmt::Display for FormattedDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.0.as_secs();
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+        if hours > 0 {
+            write!(f, "{}h", hours)?;
+        }
+        if minutes > 0 {
+            write!(f, "{}m", minutes)?;
+        }
+        if seconds > 0 || (hours == 0 && minutes == 0) {
+            write!(f, "{}s", seconds)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        seconds = { "90s", Duration::from_secs(90) },
+        minutes = { "20m", Duration::from_secs(20 * 60) },
+        hours_and_minutes = { "1h30m", Duration::from_secs(90 * 60) },
+        fractional = { "1.5h", Duration::from_secs(90 * 60) },
+        long_units = { "2min 30sec", Duration::from_secs(150) },
+        without_unit = { "45", Duration::from_secs(45) },
+    )]
+    fn parsed(input: &str, expected: Duration) {
+        assert_eq!(parse_duration(input).unwrap(), expected);
+    }
+
+    #[yare::parameterized(
+        empty = { "" },
+        unknown_unit = { "3d" },
+        without_number = { "m" },
+        trailing_number = { "1h30" },
+    )]
+    fn invalid(input: &str) {
+        assert!(parse_duration(input).is_err());
+    }
+
+    #[yare::parameterized(
+        seconds = { Duration::from_secs(45), "45s" },
+        minutes = { Duration::from_secs(20 * 60), "20m" },
+        hours_and_minutes = { Duration::from_secs(90 * 60), "1h30m" },
+        zero = { Duration::ZERO, "0s" },
+    )]
+    fn formatted(duration: Duration, expected: &str) {
+        assert_eq!(FormattedDuration(duration).to_string(), expected);
+    }
+
+    #[test]
+    fn expired() {
+        assert!(Deadline::after(Duration::ZERO).is_expired());
+        assert!(!Deadline::after(Duration::from_secs(3600)).is_expired());
+    }
+}
@@ -0,0 +1,213 @@
+//! The MSRV, as declared in the CI configuration of a workspace, and the Rust versions to which
+//! CI jobs are pinned.
+//!
+//! The configuration is not parsed as YAML. A value is a declaration if it is a Rust version,
+//! and its key names the MSRV, like `msrv: 1.60` or `RUST_VERSION: "1.60"`. A Rust version is
+//! pinned by:
+//!
+//! * the `dtolnay/rust-toolchain` action, e.g. `uses: dtolnay/rust-toolchain@1.60`;
+//! * a `toolchain` input, e.g. of `dtolnay/rust-toolchain@master` or `actions-rs/toolchain`;
+//! * the tag of a `rust` container image, e.g. `image: rust:1.60-slim`;
+//! * a rustup command, e.g. `rustup toolchain install 1.60`.
+
+use crate::declaration::PinKind;
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use camino::{Utf8Path, Utf8PathBuf};
+use std::io;
+
+/// The keys which declare the MSRV, compared case-insensitively.
+const KEYS: [&str; 4] = ["msrv", "rust-version", "rust_version", "min_rust_version"];
+
+/// The CI configuration files of other CI services than GitHub, relative to the workspace root.
+const CONFIG_FILES: [&str; 5] = [
+    ".gitlab-ci.yml",
+    ".circleci/config.yml",
+    "azure-pipelines.yml",
+    ".travis.yml",
+    ".buildkite/pipeline.yml",
+];
+
+/// The GitHub workflows of the workspace, in order of their name, followed by the configuration
+/// of other CI services, if present.
+pub(super) fn config_files(workspace_root: &Utf8Path) -> TResult<Vec<Utf8PathBuf>> {
+    let dir = workspace_root.join(".github").join("workflows");
+
+    let mut files = match dir.read_dir_utf8() {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.into_path())
+            .filter(|path| matches!(path.extension(), Some("yml" | "yaml")))
+            .collect::<Vec<_>>(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(error) => {
+            return Err(IoError {
+                error,
+                source: IoErrorSource::ReadDir(dir),
+            }
+            .into())
+        }
+    };
+    files.sort();
+
+    files.extend(
+        CONFIG_FILES
+            .iter()
+            .map(|file| workspace_root.join(file))
+            .filter(|path| path.is_file()),
+    );
+
+    Ok(files)
+}
+
+/// The line (starting at 1) and Rust version of each declaration in the configuration.
+pub(super) fn scan_declarations(contents: &str) -> impl Iterator<Item = (usize, BareVersion)> + '_ {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(n, line)| declared_version(line).map(|version| (n + 1, version)))
+}
+
+/// The line (starting at 1), kind and Rust version of each pin in the configuration.
+pub(super) fn scan_pins(
+    contents: &str,
+) -> impl Iterator<Item = (usize, PinKind, BareVersion)> + '_ {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(n, line)| pinned_version(line).map(|(kind, version)| (n + 1, kind, version)))
+}
+
+fn declared_version(line: &str) -> Option<BareVersion> {
+    let (key, value) = key_value(line)?;
+
+    if !KEYS.iter().any(|known| key.eq_ignore_ascii_case(known)) {
+        return None;
+    }
+
+    value.parse().ok()
+}
+
+fn pinned_version(line: &str) -> Option<(PinKind, BareVersion)> {
+    let line = line.split(" #").next().unwrap_or_default();
+
+    if let Some((_, reference)) = line.split_once("dtolnay/rust-toolchain@") {
+        let version = unquote(first_word(reference)).parse().ok()?;
+        return Some((PinKind::RustToolchainAction, version));
+    }
+
+    if let Some((key, value)) = key_value(line) {
+        if key.eq_ignore_ascii_case("toolchain") {
+            let version = value.parse().ok()?;
+            return Some((PinKind::ToolchainInput, version));
+        }
+    }
+
+    if let Some(tag) = rust_image_tag(line) {
+        // E.g. `1.60-slim-bullseye`
+        let version = tag.split('-').next().unwrap_or_default().parse().ok()?;
+        return Some((PinKind::ContainerImage, version));
+    }
+
+    let (_, command) = line.split_once("rustup ")?;
+    let version = command
+        .split_whitespace()
+        .map(unquote)
+        .take_while(|word| !word.starts_with("&&") && !word.starts_with(';'))
+        .find_map(|word| word.parse().ok())?;
+
+    Some((PinKind::Rustup, version))
+}
+
+/// The tag of a `rust` image, like `1.60-slim` of `docker.io/library/rust:1.60-slim`.
+fn rust_image_tag(line: &str) -> Option<&str> {
+    line.match_indices("rust:").find_map(|(start, _)| {
+        let preceded_by = line[..start].chars().next_back();
+
+        let is_image_name = match preceded_by {
+            None => true,
+            Some(c) => c.is_whitespace() || matches!(c, '/' | '"' | '\''),
+        };
+
+        is_image_name.then(|| unquote(first_word(&line[start + "rust:".len()..])))
+    })
+}
+
+/// The key and (unquoted) value of a `key: value` line, which may be a list item.
+fn key_value(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start().trim_start_matches("- ");
+    let (key, value) = line.split_once(':')?;
+
+    let value = value.split(" #").next().unwrap_or_default().trim();
+
+    Some((unquote(key.trim()), unquote(value)))
+}
+
+fn first_word(text: &str) -> &str {
+    text.split_whitespace().next().unwrap_or_default()
+}
+
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| c == '"' || c == '\'')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        env = { "      MSRV: 1.60", Some("1.60") },
+        quoted = { "  rust-version: \"1.60.0\"", Some("1.60.0") },
+        single_quoted = { "  msrv: '1.56'", Some("1.56") },
+        list_item = { "  - rust_version: 1.70 # the MSRV", Some("1.70") },
+        expression = { "  msrv: ${{ matrix.msrv }}", None },
+        other_key = { "  toolchain: 1.60", None },
+        job_name = { "  msrv:", None },
+    )]
+    fn declaration(line: &str, expected: Option<&str>) {
+        assert_eq!(
+            declared_version(line),
+            expected.map(|version| version.parse().unwrap())
+        );
+    }
+
+    #[yare::parameterized(
+        action = { "      - uses: dtolnay/rust-toolchain@1.60", Some((PinKind::RustToolchainAction, "1.60")) },
+        action_quoted = { "      - uses: \"dtolnay/rust-toolchain@1.60.0\"", Some((PinKind::RustToolchainAction, "1.60.0")) },
+        action_stable = { "      - uses: dtolnay/rust-toolchain@stable", None },
+        input = { "          toolchain: 1.56.1", Some((PinKind::ToolchainInput, "1.56.1")) },
+        input_stable = { "          toolchain: stable", None },
+        image = { "image: rust:1.60", Some((PinKind::ContainerImage, "1.60")) },
+        image_variant = { "  container: docker.io/library/rust:1.65-slim-bullseye", Some((PinKind::ContainerImage, "1.65")) },
+        image_latest = { "image: rust:latest", None },
+        other_image = { "image: rustlang/rust:nightly", None },
+        rustup = { "  - rustup toolchain install 1.60 --profile minimal", Some((PinKind::Rustup, "1.60")) },
+        rustup_default = { "    script: rustup default 1.62.0 && cargo test", Some((PinKind::Rustup, "1.62.0")) },
+        rustup_after_command = { "    script: rustup default stable && cargo +1.60 test", None },
+        comment = { "  toolchain: stable # not 1.60", None },
+    )]
+    fn pin(line: &str, expected: Option<(PinKind, &str)>) {
+        assert_eq!(
+            pinned_version(line),
+            expected.map(|(kind, version)| (kind, version.parse().unwrap()))
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let workflow = "jobs:\n  msrv:\n    steps:\n      - uses: dtolnay/rust-toolchain@1.58\n    env:\n      MSRV: 1.60\n";
+
+        assert_eq!(
+            scan_declarations(workflow).collect::<Vec<_>>(),
+            vec![(6, BareVersion::TwoComponents(1, 60))]
+        );
+        assert_eq!(
+            scan_pins(workflow).collect::<Vec<_>>(),
+            vec![(
+                4,
+                PinKind::RustToolchainAction,
+                BareVersion::TwoComponents(1, 58)
+            )]
+        );
+    }
+}
@@ -0,0 +1,953 @@
+//! The places in which the MSRV of a crate can be declared, besides the Cargo manifest, and
+//! whether these declarations agree with each other.
+//!
+//! The declarations are searched for in the root of the crate, and in the root of its workspace:
+//!
+//! * `package.rust-version` and `package.metadata.msrv`, in the `Cargo.toml` of the crate;
+//! * `workspace.package.rust-version`, in the `Cargo.toml` of the workspace;
+//! * `msrv`, in `clippy.toml` or `.clippy.toml`;
+//! * `toolchain.channel`, in `rust-toolchain.toml` or `rust-toolchain`, if it is a Rust version;
+//! * a badge, or a plain text mention, in the README;
+//! * `msrv` and `rust-version` values, in the CI configuration of the workspace.
+//! * the values located by the sync rules of the `cargo-msrv` metadata table, see
+//!   [`crate::manifest::sync_rules`].
+//!
+//! The Rust versions to which CI jobs are pinned are not declarations of the MSRV, since a job
+//! may as well test a newer Rust version, but a pin which is older than the MSRV is outdated: it
+//! was likely left behind when the MSRV was raised, and no longer tests the MSRV.
+
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::sync_rules::{SyncRule, SyncRuleKind};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::{fmt, fs, io};
+use toml_edit::{ImDocument, Item};
+
+mod ci;
+mod readme;
+mod rules;
+
+/// The suffix of the sibling file to which an updated file is written, before it replaces it.
+const STAGED_SUFFIX: &str = ".cargo-msrv.tmp";
+
+/// A Rust version, declared as MSRV in a file of the workspace.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Declaration {
+    pub kind: DeclarationKind,
+    /// The file of the declaration, relative to the workspace root.
+    pub file: Utf8PathBuf,
+    /// The line of the declaration, starting at 1.
+    pub line: usize,
+    pub version: BareVersion,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeclarationKind {
+    /// `package.rust-version` in the Cargo manifest of the crate.
+    RustVersion,
+    /// `package.metadata.msrv` in the Cargo manifest of the crate.
+    MetadataMsrv,
+    /// `workspace.package.rust-version` in the Cargo manifest of the workspace.
+    WorkspaceRustVersion,
+    /// `msrv` in the clippy configuration.
+    ClippyMsrv,
+    /// `toolchain.channel` in the rust-toolchain file.
+    ToolchainChannel,
+    /// A shields.io badge in the README.
+    ReadmeBadge,
+    /// A plain text mention in the README, like `MSRV: 1.60`.
+    ReadmeText,
+    /// An `msrv` or `rust-version` value in a CI configuration file.
+    CiWorkflow,
+    /// A value in a file which is configured by a sync rule.
+    SyncRule,
+}
+
+impl DeclarationKind {
+    pub fn is_readme(self) -> bool {
+        matches!(self, Self::ReadmeBadge | Self::ReadmeText)
+    }
+}
+
+/// A Rust version to which a CI job is pinned.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Pin {
+    pub kind: PinKind,
+    /// The CI configuration file of the pin, relative to the workspace root.
+    pub file: Utf8PathBuf,
+    /// The line of the pin, starting at 1.
+    pub line: usize,
+    pub version: BareVersion,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PinKind {
+    /// The version of the `dtolnay/rust-toolchain` action, e.g. `dtolnay/rust-toolchain@1.60`.
+    RustToolchainAction,
+    /// A `toolchain` input of an action.
+    ToolchainInput,
+    /// The tag of a `rust` container image, e.g. `rust:1.60-slim`.
+    ContainerImage,
+    /// A toolchain installed or selected with `rustup`.
+    Rustup,
+}
+
+impl fmt::Display for PinKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::RustToolchainAction => "dtolnay/rust-toolchain",
+            Self::ToolchainInput => "toolchain input",
+            Self::ContainerImage => "container image",
+            Self::Rustup => "rustup",
+        };
+
+        f.write_str(kind)
+    }
+}
+
+impl fmt::Display for DeclarationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::RustVersion => "package.rust-version",
+            Self::MetadataMsrv => "package.metadata.msrv",
+            Self::WorkspaceRustVersion => "workspace.package.rust-version",
+            Self::ClippyMsrv => "clippy msrv",
+            Self::ToolchainChannel => "toolchain channel",
+            Self::ReadmeBadge => "README badge",
+            Self::ReadmeText => "README",
+            Self::CiWorkflow => "CI workflow",
+            Self::SyncRule => "sync rule",
+        };
+
+        f.write_str(kind)
+    }
+}
+
+/// A declaration which disagrees with the MSRV.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Conflict {
+    #[serde(flatten)]
+    pub declaration: Declaration,
+    /// The MSRV, as declared by the first declaration.
+    pub expected: BareVersion,
+}
+
+/// Find the declarations of the MSRV, in order of precedence: the declarations of the Cargo
+/// manifests come first.
+pub fn scan(
+    crate_root: &Utf8Path,
+    workspace_root: &Utf8Path,
+    rules: &[SyncRule],
+) -> TResult<Vec<Declaration>> {
+    let mut roots = vec![crate_root];
+    if workspace_root != crate_root {
+        roots.push(workspace_root);
+    }
+
+    let mut scanner = Scanner {
+        workspace_root,
+        declarations: Vec::new(),
+    };
+
+    scanner.scan_toml(
+        &crate_root.join("Cargo.toml"),
+        &[
+            (&["package", "rust-version"], DeclarationKind::RustVersion),
+            (
+                &["package", "metadata", "msrv"],
+                DeclarationKind::MetadataMsrv,
+            ),
+        ],
+    )?;
+    scanner.scan_toml(
+        &workspace_root.join("Cargo.toml"),
+        &[(
+            &["workspace", "package", "rust-version"],
+            DeclarationKind::WorkspaceRustVersion,
+        )],
+    )?;
+
+    for root in &roots {
+        for file in ["clippy.toml", ".clippy.toml"] {
+            scanner.scan_toml(
+                &root.join(file),
+                &[(&["msrv"], DeclarationKind::ClippyMsrv)],
+            )?;
+        }
+
+        scanner.scan_toml(
+            &root.join("rust-toolchain.toml"),
+            &[(&["toolchain", "channel"], DeclarationKind::ToolchainChannel)],
+        )?;
+        scanner.scan_legacy_toolchain_file(&root.join("rust-toolchain"))?;
+        scanner.scan_readme(root)?;
+    }
+
+    for config in ci::config_files(workspace_root)? {
+        if let Some(contents) = read(&config)? {
+            let path = scanner.relative(&config);
+            let found = ci::scan_declarations(&contents).map(|(line, version)| Declaration {
+                kind: DeclarationKind::CiWorkflow,
+                file: path.clone(),
+                line,
+                version,
+            });
+            scanner.declarations.extend(found);
+        }
+    }
+
+    for rule in rules {
+        scanner.scan_rule(rule)?;
+    }
+
+    Ok(scanner.declarations)
+}
+
+/// Find the Rust versions to which the CI jobs of the workspace are pinned.
+pub fn scan_pins(workspace_root: &Utf8Path) -> TResult<Vec<Pin>> {
+    let mut pins = Vec::new();
+
+    for config in ci::config_files(workspace_root)? {
+        if let Some(contents) = read(&config)? {
+            let file = config
+                .strip_prefix(workspace_root)
+                .unwrap_or(&config)
+                .to_path_buf();
+
+            pins.extend(ci::scan_pins(&contents).map(|(line, kind, version)| Pin {
+                kind,
+                file: file.clone(),
+                line,
+                version,
+            }));
+        }
+    }
+
+    Ok(pins)
+}
+
+/// The pins which are older than the MSRV.
+pub fn outdated_pins(pins: &[Pin], msrv: &BareVersion) -> Vec<Pin> {
+    let msrv = msrv.to_semver_version();
+
+    pins.iter()
+        .filter(|pin| pin.version.to_semver_version() < msrv)
+        .cloned()
+        .collect()
+}
+
+/// The declarations which disagree with the first declaration, which takes precedence.
+pub fn conflicts(declarations: &[Declaration]) -> Vec<Conflict> {
+    let Some((msrv, rest)) = declarations.split_first() else {
+        return Vec::new();
+    };
+
+    rest.iter()
+        .filter(|declaration| !same_version(&declaration.version, &msrv.version))
+        .map(|declaration| Conflict {
+            declaration: declaration.clone(),
+            expected: msrv.version.clone(),
+        })
+        .collect()
+}
+
+/// A declaration which is updated to the MSRV, with its line before and after the update.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Change {
+    /// The declaration, with the version it declared before the update.
+    #[serde(flatten)]
+    pub declaration: Declaration,
+    /// The MSRV, to which the declaration is updated.
+    pub msrv: BareVersion,
+    pub before: String,
+    pub after: String,
+}
+
+/// The update of declarations to the MSRV, of which no file has been written yet.
+#[derive(Debug)]
+pub struct Update {
+    files: Vec<UpdatedFile>,
+    changes: Vec<Change>,
+}
+
+#[derive(Debug)]
+struct UpdatedFile {
+    path: Utf8PathBuf,
+    original: String,
+    contents: String,
+}
+
+impl Update {
+    /// Update each of the given declarations which does not already declare the MSRV. The files
+    /// are written in order of their first declaration.
+    pub fn new(
+        workspace_root: &Utf8Path,
+        declarations: &[Declaration],
+        msrv: &BareVersion,
+    ) -> TResult<Self> {
+        let mut files = Vec::<UpdatedFile>::new();
+        let mut changes = Vec::new();
+
+        for declaration in declarations {
+            if same_version(&declaration.version, msrv) {
+                continue;
+            }
+
+            let path = workspace_root.join(&declaration.file);
+            let file = match files.iter().position(|file| file.path == path) {
+                Some(index) => &mut files[index],
+                None => {
+                    let Some(original) = read(&path)? else {
+                        continue;
+                    };
+
+                    files.push(UpdatedFile {
+                        path,
+                        contents: original.clone(),
+                        original,
+                    });
+                    files.last_mut().expect("a file was just added")
+                }
+            };
+
+            file.contents =
+                replace_version(&file.contents, declaration.line, &declaration.version, msrv);
+
+            changes.push(Change {
+                declaration: declaration.clone(),
+                msrv: msrv.clone(),
+                before: line_text(&file.original, declaration.line),
+                after: line_text(&file.contents, declaration.line),
+            });
+        }
+
+        Ok(Self { files, changes })
+    }
+
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Write the updated files. If a file can't be written, the files which were already written
+    /// are restored, so either each declaration is updated, or none is. Each file is replaced
+    /// at once, so the file which can't be written is left as it was.
+    pub fn write(self) -> TResult<Vec<Change>> {
+        for (n, file) in self.files.iter().enumerate() {
+            let Err(error) = replace_file(&file.path, &file.contents) else {
+                continue;
+            };
+
+            let unrestored = self.files[..n]
+                .iter()
+                .filter(|written| replace_file(&written.path, &written.original).is_err())
+                .map(|written| written.path.clone())
+                .collect();
+
+            return Err(CargoMSRVError::UpdateRolledBack {
+                error: IoError {
+                    error,
+                    source: IoErrorSource::WriteFile(file.path.clone()),
+                },
+                unrestored,
+            });
+        }
+
+        Ok(self.changes)
+    }
+}
+
+/// Replace the contents of the file by writing them to a sibling file first, which is then
+/// renamed over it, so the file is never left partially written. The permissions of the file
+/// are kept, and a symlink is followed to the file it points to.
+fn replace_file(path: &Utf8Path, contents: &str) -> io::Result<()> {
+    let path = fs::canonicalize(path)?;
+    let mut staged = path.clone().into_os_string();
+    staged.push(STAGED_SUFFIX);
+
+    let staged_written = fs::write(&staged, contents)
+        .and_then(|()| fs::set_permissions(&staged, fs::metadata(&path)?.permissions()))
+        .and_then(|()| fs::rename(&staged, &path));
+
+    if staged_written.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+
+    staged_written
+}
+
+/// Rewrite the version of each of the given declarations to the MSRV. Returns the declarations
+/// which were rewritten, i.e. those which did not already declare the MSRV.
+pub fn rewrite(
+    workspace_root: &Utf8Path,
+    declarations: &[Declaration],
+    msrv: &BareVersion,
+) -> TResult<Vec<Declaration>> {
+    let changes = Update::new(workspace_root, declarations, msrv)?.write()?;
+
+    Ok(changes
+        .into_iter()
+        .map(|change| change.declaration)
+        .collect())
+}
+
+/// The text of the given line (starting at 1), without surrounding whitespace.
+fn line_text(contents: &str, line: usize) -> String {
+    contents
+        .lines()
+        .nth(line - 1)
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// Replace each occurrence of the old version on the given line (starting at 1) by the new
+/// version. An occurrence which is part of a longer version, like `1.600`, is left as is.
+fn replace_version(contents: &str, line: usize, old: &BareVersion, new: &BareVersion) -> String {
+    let old = old.to_string();
+    let new = new.to_string();
+
+    let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_ascii_digit());
+
+    contents
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(n, text)| {
+            if n + 1 != line {
+                return text.to_string();
+            }
+
+            let mut replaced = String::with_capacity(text.len());
+            let mut rest = text;
+
+            while let Some(start) = rest.find(&old) {
+                let end = start + old.len();
+                let mut after = rest[end..].chars();
+
+                // E.g. the `.1` of `1.60.1`, or the `0` of `1.600`
+                let longer = is_digit(rest[..start].chars().next_back())
+                    || match after.next() {
+                        Some('.') => is_digit(after.next()),
+                        next => is_digit(next),
+                    };
+
+                replaced.push_str(&rest[..start]);
+                if longer {
+                    replaced.push_str(&old);
+                } else {
+                    replaced.push_str(&new);
+                }
+                rest = &rest[end..];
+            }
+
+            replaced.push_str(rest);
+            replaced
+        })
+        .collect()
+}
+
+/// Whether both versions refer to the same Rust release, where a two component version refers to
+/// the first release of its minor version, like `rust-version` does.
+fn same_version(lhs: &BareVersion, rhs: &BareVersion) -> bool {
+    lhs.to_semver_version() == rhs.to_semver_version()
+}
+
+struct Scanner<'root> {
+    workspace_root: &'root Utf8Path,
+    declarations: Vec<Declaration>,
+}
+
+impl Scanner<'_> {
+    fn scan_toml(&mut self, path: &Utf8Path, keys: &[(&[&str], DeclarationKind)]) -> TResult<()> {
+        let Some(contents) = read(path)? else {
+            return Ok(());
+        };
+
+        let document = ImDocument::parse(contents.as_str())?;
+
+        for (key, kind) in keys {
+            let item = key
+                .iter()
+                .try_fold(document.as_item(), |item, key| item.get(key));
+
+            // E.g. `rust-version.workspace = true` is not a declared version
+            let Some((version, span)) = item.and_then(version_of) else {
+                continue;
+            };
+
+            self.declarations.push(Declaration {
+                kind: *kind,
+                file: self.relative(path),
+                line: line_of(&contents, span),
+                version,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// A `rust-toolchain` file is either a TOML file, or holds just the name of the toolchain.
+    fn scan_legacy_toolchain_file(&mut self, path: &Utf8Path) -> TResult<()> {
+        let Some(contents) = read(path)? else {
+            return Ok(());
+        };
+
+        if contents.contains('[') {
+            return self.scan_toml(
+                path,
+                &[(&["toolchain", "channel"], DeclarationKind::ToolchainChannel)],
+            );
+        }
+
+        if let Ok(version) = contents.trim().parse::<BareVersion>() {
+            self.declarations.push(Declaration {
+                kind: DeclarationKind::ToolchainChannel,
+                file: self.relative(path),
+                line: 1,
+                version,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn scan_readme(&mut self, root: &Utf8Path) -> TResult<()> {
+        for file in readme::FILES {
+            let path = root.join(file);
+
+            if let Some(contents) = read(&path)? {
+                let file = self.relative(&path);
+                let found =
+                    readme::scan_readme(&contents).map(|(line, kind, version)| Declaration {
+                        kind,
+                        file: file.clone(),
+                        line,
+                        version,
+                    });
+                self.declarations.extend(found);
+
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn scan_rule(&mut self, rule: &SyncRule) -> TResult<()> {
+        let path = self.workspace_root.join(&rule.file);
+
+        let found = match rule.kind {
+            SyncRuleKind::TomlPath => {
+                return self.scan_toml(&path, &[(&rule.path(), DeclarationKind::SyncRule)]);
+            }
+            SyncRuleKind::Regex => {
+                let Some(contents) = read(&path)? else {
+                    return Ok(());
+                };
+                rules::scan_regex(&contents, &rule.regex()?).collect::<Vec<_>>()
+            }
+            SyncRuleKind::YamlPath => {
+                let Some(contents) = read(&path)? else {
+                    return Ok(());
+                };
+                rules::scan_yaml_path(&contents, &rule.path())
+                    .into_iter()
+                    .collect()
+            }
+        };
+
+        let file = self.relative(&path);
+        self.declarations
+            .extend(found.into_iter().map(|(line, version)| Declaration {
+                kind: DeclarationKind::SyncRule,
+                file: file.clone(),
+                line,
+                version,
+            }));
+
+        Ok(())
+    }
+
+    fn relative(&self, path: &Utf8Path) -> Utf8PathBuf {
+        path.strip_prefix(self.workspace_root)
+            .unwrap_or(path)
+            .to_path_buf()
+    }
+}
+
+/// The Rust version of a string value, like `"1.56"`, with its location. Values which are not a
+/// Rust version, like a `stable` toolchain channel, are skipped.
+fn version_of(item: &Item) -> Option<(BareVersion, std::ops::Range<usize>)> {
+    let value = item.as_value()?;
+    let version = value.as_str()?.trim().parse().ok()?;
+
+    Some((version, value.span()?))
+}
+
+fn line_of(contents: &str, span: std::ops::Range<usize>) -> usize {
+    contents[..span.start].matches('\n').count() + 1
+}
+
+/// The contents of the file, or `None` if it does not exist.
+fn read(path: &Utf8Path) -> TResult<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(IoError {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        }
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(files: &[(&str, &str)]) -> (assert_fs::TempDir, Utf8PathBuf) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+
+        for (path, contents) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        (dir, root)
+    }
+
+    fn declaration(kind: DeclarationKind, file: &str, line: usize, version: &str) -> Declaration {
+        Declaration {
+            kind,
+            file: Utf8PathBuf::from(file),
+            line,
+            version: version.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn scan_all_locations() {
+        let (_dir, root) = workspace(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"a\"\nrust-version = \"1.60\"\n\n[package.metadata]\nmsrv = \"1.60.0\"\n",
+            ),
+            ("clippy.toml", "msrv = \"1.58\"\n"),
+            ("rust-toolchain.toml", "[toolchain]\nchannel = \"1.60.0\"\n"),
+            (
+                ".github/workflows/ci.yml",
+                "jobs:\n  msrv:\n    env:\n      MSRV: \"1.60\"\n",
+            ),
+        ]);
+
+        let declarations = scan(&root, &root, &[]).unwrap();
+
+        assert_eq!(
+            declarations,
+            vec![
+                declaration(DeclarationKind::RustVersion, "Cargo.toml", 3, "1.60"),
+                declaration(DeclarationKind::MetadataMsrv, "Cargo.toml", 6, "1.60.0"),
+                declaration(DeclarationKind::ClippyMsrv, "clippy.toml", 1, "1.58"),
+                declaration(
+                    DeclarationKind::ToolchainChannel,
+                    "rust-toolchain.toml",
+                    2,
+                    "1.60.0"
+                ),
+                declaration(
+                    DeclarationKind::CiWorkflow,
+                    ".github/workflows/ci.yml",
+                    4,
+                    "1.60"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_workspace_member() {
+        let (_dir, root) = workspace(&[
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"a\"]\n\n[workspace.package]\nrust-version = \"1.70\"\n",
+            ),
+            (
+                "a/Cargo.toml",
+                "[package]\nname = \"a\"\nrust-version.workspace = true\n",
+            ),
+            ("a/.clippy.toml", "msrv = \"1.70\"\n"),
+        ]);
+
+        let declarations = scan(&root.join("a"), &root, &[]).unwrap();
+
+        assert_eq!(
+            declarations,
+            vec![
+                declaration(
+                    DeclarationKind::WorkspaceRustVersion,
+                    "Cargo.toml",
+                    5,
+                    "1.70"
+                ),
+                declaration(DeclarationKind::ClippyMsrv, "a/.clippy.toml", 1, "1.70"),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_ci_pins() {
+        let (_dir, root) = workspace(&[
+            (
+                ".github/workflows/ci.yml",
+                "jobs:\n  msrv:\n    steps:\n      - uses: dtolnay/rust-toolchain@1.58\n  stable:\n    steps:\n      - uses: dtolnay/rust-toolchain@stable\n",
+            ),
+            (".gitlab-ci.yml", "msrv:\n  image: rust:1.60-slim\n"),
+        ]);
+
+        let pins = scan_pins(&root).unwrap();
+        let outdated = outdated_pins(&pins, &BareVersion::TwoComponents(1, 60));
+
+        assert_eq!(
+            pins.iter()
+                .map(|pin| (pin.file.as_str(), pin.line, pin.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                (".github/workflows/ci.yml", 4, PinKind::RustToolchainAction),
+                (".gitlab-ci.yml", 2, PinKind::ContainerImage),
+            ]
+        );
+        assert_eq!(outdated, vec![pins[0].clone()]);
+    }
+
+    #[test]
+    fn scan_readme() {
+        let (_dir, root) = workspace(&[(
+            "README.md",
+            "# a\n\n![MSRV](https://img.shields.io/badge/MSRV-1.58-blue)\n\nMSRV: 1.60\n",
+        )]);
+
+        assert_eq!(
+            scan(&root, &root, &[]).unwrap(),
+            vec![
+                declaration(DeclarationKind::ReadmeBadge, "README.md", 3, "1.58"),
+                declaration(DeclarationKind::ReadmeText, "README.md", 5, "1.60"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rewrite_declarations() {
+        let (_dir, root) = workspace(&[(
+            "README.md",
+            "[![MSRV 1.58](https://img.shields.io/badge/MSRV-1.58-blue)](#msrv)\n\nSince 1.58.1: MSRV is 1.58.\nMSRV: 1.60\n",
+        )]);
+
+        let declarations = scan(&root, &root, &[]).unwrap();
+        let rewritten = rewrite(&root, &declarations, &"1.60".parse().unwrap()).unwrap();
+
+        assert_eq!(rewritten.len(), 2);
+        assert_eq!(
+            fs::read_to_string(root.join("README.md")).unwrap(),
+            "[![MSRV 1.60](https://img.shields.io/badge/MSRV-1.60-blue)](#msrv)\n\nSince 1.58.1: MSRV is 1.60.\nMSRV: 1.60\n",
+        );
+    }
+
+    #[test]
+    fn update_declarations() {
+        let (_dir, root) = workspace(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"a\"\nrust-version = \"1.58\"\n",
+            ),
+            ("clippy.toml", "msrv = \"1.60\"\n"),
+            (".github/workflows/ci.yml", "env:\n  MSRV: 1.58\n"),
+        ]);
+
+        let declarations = scan(&root, &root, &[]).unwrap();
+        let update = Update::new(&root, &declarations, &"1.60".parse().unwrap()).unwrap();
+
+        let changes = update
+            .changes()
+            .iter()
+            .map(|change| (change.before.as_str(), change.after.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                ("rust-version = \"1.58\"", "rust-version = \"1.60\""),
+                ("MSRV: 1.58", "MSRV: 1.60"),
+            ]
+        );
+
+        // Nothing is written before the update is
+        assert!(fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("1.58"));
+
+        update.write().unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join(".github/workflows/ci.yml")).unwrap(),
+            "env:\n  MSRV: 1.60\n"
+        );
+    }
+
+    #[test]
+    fn roll_back_update() {
+        let (_dir, root) = workspace(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"a\"\nrust-version = \"1.58\"\n",
+            ),
+            (".github/workflows/ci.yml", "env:\n  MSRV: 1.58\n"),
+        ]);
+
+        let declarations = scan(&root, &root, &[]).unwrap();
+        let update = Update::new(&root, &declarations, &"1.60".parse().unwrap()).unwrap();
+
+        // The workflow can no longer be written, after the manifest was
+        fs::remove_dir_all(root.join(".github")).unwrap();
+
+        assert!(matches!(
+            update.write(),
+            Err(CargoMSRVError::UpdateRolledBack { unrestored, .. }) if unrestored.is_empty()
+        ));
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            "[package]\nname = \"a\"\nrust-version = \"1.58\"\n"
+        );
+    }
+
+    #[test]
+    fn failed_file_is_left_as_is() {
+        let (_dir, root) = workspace(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"a\"\nrust-version = \"1.58\"\n",
+            ),
+            (".github/workflows/ci.yml", "env:\n  MSRV: 1.58\n"),
+        ]);
+
+        let declarations = scan(&root, &root, &[]).unwrap();
+        let update = Update::new(&root, &declarations, &"1.60".parse().unwrap()).unwrap();
+
+        // The updated workflow can't be staged, so it can't replace the workflow
+        let staged = root.join(format!(".github/workflows/ci.yml{STAGED_SUFFIX}"));
+        fs::create_dir(&staged).unwrap();
+
+        assert!(matches!(
+            update.write(),
+            Err(CargoMSRVError::UpdateRolledBack { unrestored, .. }) if unrestored.is_empty()
+        ));
+        assert_eq!(
+            fs::read_to_string(root.join(".github/workflows/ci.yml")).unwrap(),
+            "env:\n  MSRV: 1.58\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("Cargo.toml")).unwrap(),
+            "[package]\nname = \"a\"\nrust-version = \"1.58\"\n"
+        );
+        assert!(!root.join(format!("Cargo.toml{STAGED_SUFFIX}")).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn update_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_dir, root) = workspace(&[("clippy.toml", "msrv = \"1.58\"\n")]);
+        let path = root.join("clippy.toml");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        let declarations = scan(&root, &root, &[]).unwrap();
+        rewrite(&root, &declarations, &"1.60".parse().unwrap()).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "msrv = \"1.60\"\n");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn scan_and_rewrite_sync_rules() {
+        let (_dir, root) = workspace(&[
+            ("Dockerfile", "FROM rust:1.58-slim\nRUN cargo build\n"),
+            (
+                "docs/book.toml",
+                "[preprocessor.variables]\nmsrv = \"1.58\"\n",
+            ),
+            ("deploy.yml", "build:\n  rust: 1.58\n"),
+        ]);
+        let rules = [
+            (SyncRuleKind::Regex, "Dockerfile", r"FROM rust:([\d.]+)"),
+            (
+                SyncRuleKind::TomlPath,
+                "docs/book.toml",
+                "preprocessor.variables.msrv",
+            ),
+            (SyncRuleKind::YamlPath, "deploy.yml", "build.rust"),
+            (SyncRuleKind::Regex, "missing.txt", r"MSRV ([\d.]+)"),
+        ]
+        .map(|(kind, file, pattern)| SyncRule {
+            file: Utf8PathBuf::from(file),
+            kind,
+            pattern: pattern.to_string(),
+        });
+
+        let declarations = scan(&root, &root, &rules).unwrap();
+        assert_eq!(
+            declarations,
+            vec![
+                declaration(DeclarationKind::SyncRule, "Dockerfile", 1, "1.58"),
+                declaration(DeclarationKind::SyncRule, "docs/book.toml", 2, "1.58"),
+                declaration(DeclarationKind::SyncRule, "deploy.yml", 2, "1.58"),
+            ]
+        );
+
+        rewrite(&root, &declarations, &"1.60".parse().unwrap()).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("Dockerfile")).unwrap(),
+            "FROM rust:1.60-slim\nRUN cargo build\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("deploy.yml")).unwrap(),
+            "build:\n  rust: 1.60\n"
+        );
+    }
+
+    #[yare::parameterized(
+        version = { "1.65.0\n", true },
+        stable = { "stable\n", false },
+        toml_stable = { "[toolchain]\nchannel = \"stable\"\n", false },
+    )]
+    fn legacy_toolchain_file(contents: &str, declared: bool) {
+        let (_dir, root) = workspace(&[("rust-toolchain", contents)]);
+
+        assert_eq!(!scan(&root, &root, &[]).unwrap().is_empty(), declared);
+    }
+
+    #[yare::parameterized(
+        agree = { &["1.60", "1.60.0", "1.60"], &[] },
+        patch = { &["1.60", "1.60.1"], &["1.60.1"] },
+        differ = { &["1.60", "1.58", "1.60", "1.61"], &["1.58", "1.61"] },
+        none = { &[], &[] },
+    )]
+    fn find_conflicts(versions: &[&str], expected: &[&str]) {
+        let declarations = versions
+            .iter()
+            .map(|version| declaration(DeclarationKind::ClippyMsrv, "clippy.toml", 1, version))
+            .collect::<Vec<_>>();
+
+        let conflicts = conflicts(&declarations)
+            .into_iter()
+            .map(|conflict| conflict.declaration.version.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(conflicts, expected);
+    }
+}
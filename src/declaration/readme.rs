@@ -0,0 +1,144 @@
+//! The MSRV, as declared in the README of a crate: by a [shields.io](https://shields.io) badge,
+//! like `https://img.shields.io/badge/MSRV-1.60-blue`, or in plain text, like `MSRV: 1.60`.
+
+use crate::declaration::DeclarationKind;
+use crate::manifest::bare_version::BareVersion;
+
+/// The file names of a README, in order of preference.
+pub(super) const FILES: [&str; 3] = ["README.md", "README", "readme.md"];
+
+/// The words which introduce the MSRV in plain text, compared case-insensitively.
+const KEYWORDS: [&str; 2] = ["msrv", "minimum supported rust version"];
+
+/// The labels of a badge which shows the MSRV, compared case-insensitively.
+const BADGE_LABELS: [&str; 3] = ["msrv", "rustc", "rust"];
+
+/// How many words may separate a keyword from the version, like in "MSRV of this crate is 1.60".
+const MAX_WORDS_BEFORE_VERSION: usize = 5;
+
+/// The line (starting at 1), kind and Rust version of each declaration in the README.
+pub(super) fn scan_readme(
+    contents: &str,
+) -> impl Iterator<Item = (usize, DeclarationKind, BareVersion)> + '_ {
+    contents.lines().enumerate().filter_map(|(n, line)| {
+        let (kind, version) = badge_version(line)
+            .map(|version| (DeclarationKind::ReadmeBadge, version))
+            .or_else(|| text_version(line).map(|version| (DeclarationKind::ReadmeText, version)))?;
+
+        Some((n + 1, kind, version))
+    })
+}
+
+/// The version of a static shields.io badge, like `/badge/MSRV-1.60-blue` or
+/// `/badge/rustc-1.60%2B-orange.svg`.
+fn badge_version(line: &str) -> Option<BareVersion> {
+    line.match_indices("img.shields.io/badge/")
+        .find_map(|(start, prefix)| {
+            let rest = &line[start + prefix.len()..];
+            let path = rest.split([')', '"', '\'', ' ', ']', '>', '?']).next()?;
+
+            // A literal dash is escaped as `--`
+            let unescaped = path.replace("--", "\u{0}");
+            let mut parts = unescaped.split('-');
+            let (label, message) = (parts.next()?, parts.next()?);
+
+            let label = label.replace('\u{0}', "-").replace(['_', '%'], " ");
+            let label = label.trim();
+            if !BADGE_LABELS
+                .iter()
+                .any(|known| label.eq_ignore_ascii_case(known))
+            {
+                return None;
+            }
+
+            let message = message.replace("%2B", "+").replace("%2b", "+");
+            parse_version(
+                message
+                    .trim_start_matches(['v', '≥'])
+                    .trim_start_matches(">="),
+            )
+        })
+}
+
+/// The version following a keyword in plain text, like `**MSRV**: 1.60`.
+fn text_version(line: &str) -> Option<BareVersion> {
+    let lowercase = line.to_ascii_lowercase();
+
+    KEYWORDS.iter().find_map(|keyword| {
+        let (start, _) = lowercase.match_indices(keyword).next()?;
+        let rest = &line[start + keyword.len()..];
+
+        let word = rest
+            .split(|c: char| c.is_whitespace() || matches!(c, ':' | '*' | '_' | '`' | '='))
+            .filter(|word| !word.is_empty())
+            .take(MAX_WORDS_BEFORE_VERSION + 1)
+            .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))?;
+
+        parse_version(word)
+    })
+}
+
+/// Parse a version like `1.60`, which may be followed by punctuation, like `1.60+` or `1.60.`.
+fn parse_version(text: &str) -> Option<BareVersion> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+
+    text[..end].trim_end_matches('.').parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        msrv = { "[![MSRV](https://img.shields.io/badge/MSRV-1.60-blue)](Cargo.toml)", Some("1.60") },
+        rustc_plus = { "![rustc](https://img.shields.io/badge/rustc-1.56+-lightgray.svg)", Some("1.56") },
+        escaped_plus = { "<img src=\"https://img.shields.io/badge/rust-1.70.0%2B-orange\">", Some("1.70.0") },
+        html_label = { "![](https://img.shields.io/badge/MSRV-v1.65-green?style=flat)", Some("1.65") },
+        other_label = { "![license](https://img.shields.io/badge/license-MIT-blue)", None },
+        dynamic = { "![MSRV](https://img.shields.io/crates/msrv/cargo-msrv)", None },
+    )]
+    fn badge(line: &str, expected: Option<&str>) {
+        assert_eq!(
+            badge_version(line),
+            expected.map(|version| version.parse().unwrap())
+        );
+    }
+
+    #[yare::parameterized(
+        colon = { "MSRV: 1.60", Some("1.60") },
+        bold = { "**MSRV**: 1.60.0.", Some("1.60.0") },
+        sentence = { "The MSRV of this crate is 1.65, see below.", Some("1.65") },
+        spelled_out = { "Minimum Supported Rust Version: Rust 1.70", Some("1.70") },
+        policy = { "The MSRV policy is to support the last 3 releases", None },
+        without_version = { "## MSRV", None },
+    )]
+    fn text(line: &str, expected: Option<&str>) {
+        assert_eq!(
+            text_version(line),
+            expected.map(|version| version.parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let readme = "# crate\n\n[![MSRV 1.60](https://img.shields.io/badge/MSRV-1.60-blue)](#msrv)\n\n## MSRV\n\nThe MSRV is 1.60.\n";
+
+        assert_eq!(
+            scan_readme(readme).collect::<Vec<_>>(),
+            vec![
+                (
+                    3,
+                    DeclarationKind::ReadmeBadge,
+                    BareVersion::TwoComponents(1, 60)
+                ),
+                (
+                    7,
+                    DeclarationKind::ReadmeText,
+                    BareVersion::TwoComponents(1, 60)
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! The MSRV, as declared in a file which is configured by a sync rule, see
+//! [`crate::manifest::sync_rules`].
+//!
+//! A `yaml-path` rule is not parsed as YAML: the path is followed by the indentation of its keys,
+//! like `env:` followed by a more indented `MSRV: 1.60`. Keys within a list are not followed.
+
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::sync_rules::VERSION_GROUP;
+use regex::Regex;
+
+/// The line (starting at 1) and Rust version of each match of the regular expression.
+pub(super) fn scan_regex<'c>(
+    contents: &'c str,
+    regex: &'c Regex,
+) -> impl Iterator<Item = (usize, BareVersion)> + 'c {
+    regex.captures_iter(contents).filter_map(|captures| {
+        let version = captures.name(VERSION_GROUP).or_else(|| captures.get(1))?;
+
+        let line = contents[..version.start()].matches('\n').count() + 1;
+        Some((line, version.as_str().trim().parse().ok()?))
+    })
+}
+
+/// The line (starting at 1) and Rust version of the value of the key at the given path.
+pub(super) fn scan_yaml_path(contents: &str, path: &[&str]) -> Option<(usize, BareVersion)> {
+    // The indentation and name of the keys which enclose the current line
+    let mut enclosing = Vec::<(usize, &str)>::new();
+
+    for (n, line) in contents.lines().enumerate() {
+        let text = line.trim_start();
+        if text.is_empty() || text.starts_with('#') || text.starts_with('-') {
+            continue;
+        }
+
+        let Some((key, value)) = text.split_once(':') else {
+            continue;
+        };
+
+        let indent = line.len() - text.len();
+        while enclosing.last().is_some_and(|&(outer, _)| outer >= indent) {
+            enclosing.pop();
+        }
+        enclosing.push((indent, unquote(key.trim())));
+
+        let is_path = enclosing.len() == path.len()
+            && enclosing
+                .iter()
+                .zip(path)
+                .all(|((_, key), expected)| key == expected);
+
+        if is_path {
+            let value = value.split(" #").next().unwrap_or_default().trim();
+            return Some((n + 1, unquote(value).parse().ok()?));
+        }
+    }
+
+    None
+}
+
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| c == '"' || c == '\'')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        first_group = { r"FROM rust:(\d+\.\d+)", vec![(2, "1.60")] },
+        named_group = { r"(rust):(?<msrv>[\d.]+)", vec![(2, "1.60")] },
+        each_match = { r"(?:rust:|toolchain )([\d.]+)", vec![(2, "1.60"), (3, "1.58")] },
+        not_a_version = { r"AS (\w+)", vec![] },
+    )]
+    fn regex(pattern: &str, expected: Vec<(usize, &str)>) {
+        let dockerfile =
+            "# syntax=docker/dockerfile:1\nFROM rust:1.60 AS builder\nRUN rustup toolchain 1.58\n";
+        let regex = Regex::new(pattern).unwrap();
+
+        assert_eq!(
+            scan_regex(dockerfile, &regex).collect::<Vec<_>>(),
+            expected
+                .into_iter()
+                .map(|(line, version)| (line, version.parse().unwrap()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[yare::parameterized(
+        nested = { &["jobs", "msrv", "env", "MSRV"], Some((7, "1.60")) },
+        top_level = { &["msrv"], Some((1, "1.56")) },
+        other_job = { &["jobs", "test", "env", "MSRV"], None },
+        not_a_version = { &["jobs", "msrv", "runs-on"], None },
+        partial_path = { &["jobs", "msrv"], None },
+    )]
+    fn yaml_path(path: &[&str], expected: Option<(usize, &str)>) {
+        let workflow = "msrv: '1.56'\njobs:\n  msrv:\n    runs-on: ubuntu-latest\n    # the MSRV\n    env:\n      MSRV: \"1.60\" # see Cargo.toml\n  latest:\n    env:\n      MSRV: 1.70\n";
+
+        assert_eq!(
+            scan_yaml_path(workflow, path),
+            expected.map(|(line, version)| (line, version.parse().unwrap()))
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! The releases of crates published to crates.io, as listed by the
+//! [sparse index](https://doc.rust-lang.org/cargo/reference/registry-index.html).
+
+use crate::cache::GlobalCache;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::semver;
+use rust_releases_io::{CachedClient, ResourceFile, RustReleasesClient};
+use std::time::Duration;
+
+const SPARSE_INDEX: &str = "https://index.crates.io";
+
+// New releases of a crate may be published at any moment, but the index is fetched for each
+// dependency, so we cache it for a short while.
+const INDEX_CACHE_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// The section of the global cache which holds the index files.
+const CACHE_SECTION: &str = "crates-index";
+
+/// A release of a crate, as listed by the index.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexRelease {
+    pub version: semver::Version,
+    /// The declared `rust-version`, if any.
+    pub rust_version: Option<semver::Version>,
+    pub yanked: bool,
+}
+
+pub struct CratesIndex {
+    cache: GlobalCache,
+    client: CachedClient,
+}
+
+impl CratesIndex {
+    pub fn new() -> TResult<Self> {
+        let cache = GlobalCache::new().ok_or(CargoMSRVError::UnableToCacheCratesIndex)?;
+        let client = CachedClient::new(cache.section(CACHE_SECTION).into(), INDEX_CACHE_TIMEOUT);
+
+        Ok(Self { cache, client })
+    }
+
+    /// The releases of the crate with the given `name`, in the order they were published.
+    pub fn releases(&self, name: &str) -> TResult<Vec<IndexRelease>> {
+        let name = name.to_lowercase();
+        let url = format!("{}/{}", SPARSE_INDEX, index_path(&name));
+
+        let document = self
+            .client
+            .fetch(ResourceFile::new(&url, &name))
+            .map_err(|error| CargoMSRVError::FetchCratesIndex {
+                name: name.clone(),
+                error,
+            })?;
+
+        self.cache.touch(CACHE_SECTION, &name);
+
+        let contents = String::from_utf8_lossy(document.into_document().buffer()).into_owned();
+
+        Ok(parse_releases(&contents))
+    }
+}
+
+/// The path of the index file of a crate, relative to the root of the index.
+fn index_path(name: &str) -> String {
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct IndexEntry {
+    vers: semver::Version,
+    #[serde(default)]
+    rust_version: Option<String>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+/// Each line of an index file describes one release. Lines which can not be parsed are skipped.
+fn parse_releases(contents: &str) -> Vec<IndexRelease> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<IndexEntry>(line).ok())
+        .map(|entry| IndexRelease {
+            version: entry.vers,
+            rust_version: entry
+                .rust_version
+                .and_then(|version| version.parse::<BareVersion>().ok())
+                .map(|version| version.to_semver_version()),
+            yanked: entry.yanked,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        one = { "a", "1/a" },
+        two = { "ab", "2/ab" },
+        three = { "abc", "3/a/abc" },
+        four = { "serde", "se/rd/serde" },
+    )]
+    fn path(name: &str, expected: &str) {
+        assert_eq!(index_path(name), expected);
+    }
+
+    #[test]
+    fn parse() {
+        let contents = r#"{"name":"a","vers":"0.1.0","deps":[],"cksum":"","features":{},"yanked":false}
+{"name":"a","vers":"0.2.0","deps":[],"cksum":"","features":{},"yanked":true,"rust_version":"1.60"}
+not json
+{"name":"a","vers":"0.3.0","deps":[],"cksum":"","features":{},"yanked":false,"rust_version":"1.70.1"}
+"#;
+
+        assert_eq!(
+            parse_releases(contents),
+            vec![
+                IndexRelease {
+                    version: semver::Version::new(0, 1, 0),
+                    rust_version: None,
+                    yanked: false,
+                },
+                IndexRelease {
+                    version: semver::Version::new(0, 2, 0),
+                    rust_version: Some(semver::Version::new(1, 60, 0)),
+                    yanked: true,
+                },
+                IndexRelease {
+                    version: semver::Version::new(0, 3, 0),
+                    rust_version: Some(semver::Version::new(1, 70, 1)),
+                    yanked: false,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! A small dependency graph, to test the output of the subcommands which render the dependency
+//! graph.
+
+use super::resolver::graph_from_metadata;
+use super::DependencyGraph;
+
+fn package(name: &str, rust_version: &str, dependencies: &str) -> String {
+    format!(
+        r#"{{
+      "name": "{name}",
+      "version": "0.1.0",
+      "id": "{name} 0.1.0 (path+file:///{name})",
+      "rust_version": {rust_version},
+      "dependencies": [{dependencies}],
+      "targets": [],
+      "features": {{}},
+      "manifest_path": "/{name}/Cargo.toml",
+      "metadata": null,
+      "edition": "2021"
+    }}"#
+    )
+}
+
+fn node(name: &str, deps: &[(&str, &str)]) -> String {
+    let deps = deps
+        .iter()
+        .map(|(dep, kind)| {
+            format!(
+                r#"{{ "name": "{dep}", "pkg": "{dep} 0.1.0 (path+file:///{dep})", "dep_kinds": [{{ "kind": {kind}, "target": null }}] }}"#
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        r#"{{ "id": "{name} 0.1.0 (path+file:///{name})", "dependencies": [], "deps": [{deps}], "features": [] }}"#
+    )
+}
+
+fn metadata_of(packages: &[String], nodes: &[String]) -> cargo_metadata::Metadata {
+    let json = format!(
+        r#"{{
+  "packages": [{}],
+  "workspace_members": ["a 0.1.0 (path+file:///a)"],
+  "resolve": {{ "nodes": [{}], "root": "a 0.1.0 (path+file:///a)" }},
+  "target_directory": "/a/target",
+  "version": 1,
+  "workspace_root": "/a",
+  "metadata": null
+}}"#,
+        packages.join(", "),
+        nodes.join(", "),
+    );
+
+    serde_json::from_str(&json).unwrap()
+}
+
+/// The root crate `a` depends on `b`, which depends on `c`. The dev-dependency `d` is not
+/// relevant for the MSRV.
+pub(crate) fn metadata() -> cargo_metadata::Metadata {
+    metadata_of(
+        &[
+            package("a", "\"1.70\"", ""),
+            package("b", "\"1.60\"", ""),
+            package("c", "null", ""),
+            package("d", "\"1.80\"", ""),
+        ],
+        &[
+            node("a", &[("b", "null"), ("d", "\"dev\"")]),
+            node("b", &[("c", "null")]),
+            node("c", &[]),
+            node("d", &[]),
+        ],
+    )
+}
+
+/// The dependency graph of [`metadata`].
+pub(crate) fn graph() -> DependencyGraph {
+    graph_from_metadata(metadata()).unwrap()
+}
+
+/// The dependency graph of [`metadata`], with an additional dependency `e` of the root crate,
+/// which requires the given `rust_version`, and depends on `c` and `f`, which requires Rust 1.65.
+pub(crate) fn graph_with_dependency(rust_version: &str) -> DependencyGraph {
+    let rust_version = format!("\"{}\"", rust_version);
+
+    let metadata = metadata_of(
+        &[
+            package("a", "\"1.70\"", ""),
+            package("b", "\"1.60\"", ""),
+            package("c", "null", ""),
+            package("d", "\"1.80\"", ""),
+            package("e", &rust_version, ""),
+            package("f", "\"1.65\"", ""),
+        ],
+        &[
+            node("a", &[("b", "null"), ("d", "\"dev\""), ("e", "null")]),
+            node("b", &[("c", "null")]),
+            node("c", &[]),
+            node("d", &[]),
+            node("e", &[("c", "null"), ("f", "null")]),
+            node("f", &[]),
+        ],
+    );
+
+    graph_from_metadata(metadata).unwrap()
+}
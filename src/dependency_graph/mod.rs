@@ -1,7 +1,11 @@
 use cargo_metadata::{Package, PackageId};
-use petgraph::visit::Dfs;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::{Bfs, Dfs};
 use std::collections::HashMap;
 
+pub(crate) mod crates_index;
+#[cfg(test)]
+pub(crate) mod fixture;
 pub(crate) mod resolver;
 
 type PackageGraphIndex = usize;
@@ -53,6 +57,39 @@ impl DependencyGraph {
     pub fn root_crate(&self) -> &PackageId {
         &self.root_crate
     }
+
+    /// For each package reachable from the root crate, its parent on one of the shortest paths
+    /// from the root crate. The root crate itself has no parent.
+    ///
+    /// Returns no parents if the root crate is not part of the graph.
+    pub fn shortest_path_parents(
+        &self,
+    ) -> HashMap<NodeIndex<PackageGraphIndex>, NodeIndex<PackageGraphIndex>> {
+        let mut parents = HashMap::new();
+
+        let Some(&root_index) = self.index.get(&self.root_crate) else {
+            return parents;
+        };
+
+        let root_index = NodeIndex::new(root_index);
+
+        // Since the graph is traversed breadth first, the first found parent of a package is on
+        // one of the shortest paths from the root crate
+        let mut bfs = Bfs::new(&self.packages, root_index);
+
+        while let Some(nx) = bfs.next(&self.packages) {
+            for dependency in self
+                .packages
+                .neighbors_directed(nx, petgraph::Direction::Outgoing)
+            {
+                if dependency != root_index {
+                    parents.entry(dependency).or_insert(nx);
+                }
+            }
+        }
+
+        parents
+    }
 }
 
 impl PartialEq for DependencyGraph {
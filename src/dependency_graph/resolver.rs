@@ -1,7 +1,7 @@
 use crate::dependency_graph::DependencyGraph;
 use crate::error::{CargoMSRVError, TResult};
 use camino::Utf8Path;
-use cargo_metadata::MetadataCommand;
+use cargo_metadata::{Metadata, MetadataCommand};
 
 pub(crate) trait DependencyResolver {
     fn resolve(&self) -> TResult<DependencyGraph>;
@@ -24,21 +24,26 @@ impl DependencyResolver for CargoMetadataResolver {
     fn resolve(&self) -> TResult<DependencyGraph> {
         let result = self.metadata_command.exec()?;
 
-        let our_crate = result
-            .root_package()
-            .ok_or(CargoMSRVError::NoCrateRootFound)
-            .map(|pkg| pkg.id.clone())?;
+        graph_from_metadata(result)
+    }
+}
 
-        if let Some(dependencies) = result.resolve {
-            let node_alloc = dependencies.nodes.len();
-            let mut graph = DependencyGraph::with_capacity(our_crate, node_alloc);
+/// Builds the dependency graph of the root package of the given metadata.
+pub(crate) fn graph_from_metadata(result: Metadata) -> TResult<DependencyGraph> {
+    let our_crate = result
+        .root_package()
+        .ok_or(CargoMSRVError::NoCrateRootFound)
+        .map(|pkg| pkg.id.clone())?;
 
-            build_package_graph(&mut graph, result.packages, dependencies.nodes);
+    if let Some(dependencies) = result.resolve {
+        let node_alloc = dependencies.nodes.len();
+        let mut graph = DependencyGraph::with_capacity(our_crate, node_alloc);
 
-            Ok(graph)
-        } else {
-            Ok(DependencyGraph::empty(our_crate))
-        }
+        build_package_graph(&mut graph, result.packages, dependencies.nodes);
+
+        Ok(graph)
+    } else {
+        Ok(DependencyGraph::empty(our_crate))
     }
 }
 
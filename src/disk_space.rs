@@ -0,0 +1,356 @@
+//! The disk space preflight, which runs before the first check of `find` and `verify`.
+//!
+//! The disk space which the planned checks need is estimated, per volume: the toolchains which
+//! are not yet installed, in the toolchains directory of rustup, and the build artifacts of each
+//! check, in the target directory of the crate. A volume of which the free space doesn't suffice
+//! fails the run before it starts, instead of halfway through the search, with a `No space left on
+//! device` from rustup or cargo. The estimates are rough, but err on the side of too much, since
+//! the number of checks of a search is not known up front. See `--no-disk-preflight`.
+
+use crate::check::estimate_units;
+use crate::context::{EnvironmentContext, SearchMethod, ToolchainProvider};
+use crate::error::{CargoMSRVError, TResult};
+use crate::reporter::event::DiskSpace;
+use crate::reporter::Reporter;
+use crate::rust::{installed_toolchains, is_installed, rustup_home};
+use crate::semver;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::MetadataCommand;
+
+const MIB: u64 = 1024 * 1024;
+
+/// The size of the build artifacts of a check, when the number of units it compiles is unknown.
+const DEFAULT_ARTIFACTS_SIZE: u64 = 256 * MIB;
+
+/// Estimate the disk space which the checks of the given Rust versions need, report it for each
+/// volume, and fail if a volume doesn't have enough free space. The number of `checks` may be
+/// smaller than the number of candidates, e.g. for a binary search, in which case the largest
+/// toolchains which are not yet installed are assumed to be checked.
+pub fn preflight<'v>(
+    reporter: &impl Reporter,
+    environment: &EnvironmentContext,
+    provider: &ToolchainProvider,
+    candidates: impl IntoIterator<Item = &'v semver::Version>,
+    checks: u64,
+) -> TResult<()> {
+    let installs_toolchains = match provider {
+        ToolchainProvider::Rustup => true,
+        // Tarballs are installed outside of the toolchains directory of rustup
+        ToolchainProvider::Distribution(_)
+        | ToolchainProvider::Nix(_)
+        | ToolchainProvider::Tarballs(_) => false,
+        // The checks don't use the disk of this machine, or not the target directory of the crate
+        ToolchainProvider::Container(_) | ToolchainProvider::Remote(_) => return Ok(()),
+    };
+
+    if checks == 0 {
+        return Ok(());
+    }
+
+    let mut volumes = Volumes::default();
+
+    if let Some(toolchains_dir) = installs_toolchains
+        .then(rustup_home)
+        .flatten()
+        .map(|home| home.join("toolchains"))
+    {
+        let installed = installed_toolchains(&toolchains_dir);
+        let sizes = toolchains_to_install(candidates, &installed, checks);
+
+        volumes.add(&toolchains_dir, sizes.iter().sum(), sizes.len() as u64, 0);
+    }
+
+    let artifacts = artifacts_size(estimate_units(environment));
+    volumes.add(
+        &target_dir(environment),
+        artifacts.saturating_mul(checks),
+        0,
+        checks,
+    );
+
+    for volume in volumes.0 {
+        let event = DiskSpace::new(
+            volume.path.clone(),
+            volume.required,
+            volume.available,
+            volume.toolchains,
+            volume.checks,
+        );
+        let sufficient = event.is_sufficient();
+
+        reporter.report_event(event)?;
+
+        if !sufficient {
+            return Err(CargoMSRVError::InsufficientDiskSpace {
+                path: volume.path,
+                required: volume.required,
+                available: volume.available,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// The number of checks which a search over the given number of Rust versions runs at most, or
+/// for a binary search, usually.
+pub fn planned_checks(search_method: SearchMethod, candidates: u64) -> u64 {
+    // One check per halving of the search space
+    let bisect = u64::from(u64::BITS - candidates.leading_zeros());
+
+    match search_method {
+        SearchMethod::Linear => candidates,
+        SearchMethod::Bisect => bisect,
+        // The guess and its predecessor are checked first
+        SearchMethod::Hybrid | SearchMethod::Declared | SearchMethod::LastRun => {
+            (bisect + 2).min(candidates)
+        }
+    }
+}
+
+/// The approximate installed size of a toolchain with the minimal profile of rustup, i.e. `rustc`,
+/// `cargo` and the standard library, which grew with the releases of Rust.
+fn toolchain_size(version: &semver::Version) -> u64 {
+    let mib = match version.minor {
+        0..=29 => 250,
+        30..=49 => 350,
+        50..=64 => 500,
+        _ => 650,
+    };
+
+    mib * MIB
+}
+
+/// The approximate size of the build artifacts of `cargo check`, given the number of units it
+/// compiles.
+fn artifacts_size(units: Option<u64>) -> u64 {
+    units.map_or(DEFAULT_ARTIFACTS_SIZE, |units| (units * 2).max(32) * MIB)
+}
+
+/// The sizes of the toolchains which are not yet installed, of which the `checks` largest are
+/// assumed to be installed.
+fn toolchains_to_install<'v>(
+    candidates: impl IntoIterator<Item = &'v semver::Version>,
+    installed: &[String],
+    checks: u64,
+) -> Vec<u64> {
+    let mut sizes = candidates
+        .into_iter()
+        .filter(|version| !is_installed(installed, version))
+        .map(toolchain_size)
+        .collect::<Vec<_>>();
+
+    sizes.sort_unstable_by(|a, b| b.cmp(a));
+    sizes.truncate(usize::try_from(checks).unwrap_or(usize::MAX));
+    sizes
+}
+
+/// The target directory of the crate, which may be configured outside of the crate.
+fn target_dir(environment: &EnvironmentContext) -> Utf8PathBuf {
+    MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()
+        .map(|metadata| metadata.target_directory)
+        .unwrap_or_else(|_| environment.root().join("target"))
+}
+
+/// The required disk space, per volume.
+#[derive(Default)]
+struct Volumes(Vec<Volume>);
+
+struct Volume {
+    id: sys::VolumeId,
+    path: Utf8PathBuf,
+    required: u64,
+    available: u64,
+    toolchains: u64,
+    checks: u64,
+}
+
+impl Volumes {
+    /// Add the space which is required at the path, which may not exist yet. A volume of which the
+    /// free space can't be determined is left out, so the preflight never fails a run by itself.
+    fn add(&mut self, path: &Utf8Path, required: u64, toolchains: u64, checks: u64) {
+        let Some(existing) = path.ancestors().find(|ancestor| ancestor.exists()) else {
+            return;
+        };
+
+        let volume = sys::volume_id(existing.as_std_path()).and_then(|id| {
+            let available = sys::available_space(existing.as_std_path())?;
+            Ok((id, available))
+        });
+
+        let (id, available) = match volume {
+            Ok(volume) => volume,
+            Err(error) => {
+                info!(%error, %path, "unable to determine the free disk space");
+                return;
+            }
+        };
+
+        match self.0.iter_mut().find(|volume| volume.id == id) {
+            Some(volume) => {
+                volume.required = volume.required.saturating_add(required);
+                volume.toolchains += toolchains;
+                volume.checks += checks;
+            }
+            None => self.0.push(Volume {
+                id,
+                path: path.to_path_buf(),
+                required,
+                available,
+                toolchains,
+                checks,
+            }),
+        }
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+    use std::{fs, io, mem};
+
+    /// The device of the file system.
+    pub type VolumeId = u64;
+
+    pub fn volume_id(path: &Path) -> io::Result<VolumeId> {
+        fs::metadata(path).map(|metadata| metadata.dev())
+    }
+
+    /// The space which is available to unprivileged users, in bytes.
+    pub fn available_space(path: &Path) -> io::Result<u64> {
+        let path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+        // SAFETY: the path is a valid C string, and the statistics are written to a zeroed struct
+        // of the right type
+        unsafe {
+            let mut stats: libc::statvfs = mem::zeroed();
+            if libc::statvfs(path.as_ptr(), &mut stats) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            #[allow(clippy::useless_conversion)]
+            Ok(u64::from(stats.f_bavail).saturating_mul(u64::from(stats.f_frsize)))
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::{Component, Path};
+    use std::{io, ptr};
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    /// The prefix of the path, like `C:`.
+    pub type VolumeId = Option<OsString>;
+
+    pub fn volume_id(path: &Path) -> io::Result<VolumeId> {
+        let path = path.canonicalize()?;
+
+        Ok(path
+            .components()
+            .next()
+            .and_then(|component| match component {
+                Component::Prefix(prefix) => Some(prefix.as_os_str().to_os_string()),
+                _ => None,
+            }))
+    }
+
+    /// The space which is available to the user, in bytes.
+    pub fn available_space(path: &Path) -> io::Result<u64> {
+        let path = path
+            .as_os_str()
+            .encode_wide()
+            .chain(Some(0))
+            .collect::<Vec<u16>>();
+        let mut available = 0;
+
+        // SAFETY: the path is a null terminated wide string, and the totals which are not needed
+        // are not written
+        let succeeded = unsafe {
+            GetDiskFreeSpaceExW(
+                path.as_ptr(),
+                &mut available,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        } != 0;
+
+        if succeeded {
+            Ok(available)
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod sys {
+    use std::io;
+    use std::path::Path;
+
+    pub type VolumeId = ();
+
+    pub fn volume_id(_path: &Path) -> io::Result<VolumeId> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the free disk space can't be determined on this platform",
+        ))
+    }
+
+    pub fn available_space(_path: &Path) -> io::Result<u64> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the free disk space can't be determined on this platform",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        none = { SearchMethod::Bisect, 0, 0 },
+        one = { SearchMethod::Bisect, 1, 1 },
+        bisect = { SearchMethod::Bisect, 80, 7 },
+        linear = { SearchMethod::Linear, 80, 80 },
+        hybrid = { SearchMethod::Hybrid, 80, 9 },
+        hybrid_small = { SearchMethod::Hybrid, 2, 2 },
+    )]
+    fn checks_of_search(search_method: SearchMethod, candidates: u64, expected: u64) {
+        assert_eq!(planned_checks(search_method, candidates), expected);
+    }
+
+    #[test]
+    fn largest_toolchains_which_are_not_installed() {
+        let candidates = [
+            semver::Version::new(1, 70, 0),
+            semver::Version::new(1, 60, 0),
+            semver::Version::new(1, 40, 0),
+            semver::Version::new(1, 20, 0),
+        ];
+        let installed = ["1.70.0-x86_64-unknown-linux-gnu".to_string()];
+
+        assert_eq!(
+            toolchains_to_install(&candidates, &installed, 2),
+            vec![500 * MIB, 350 * MIB]
+        );
+        assert_eq!(toolchains_to_install(&candidates, &installed, 10).len(), 3);
+    }
+
+    #[test]
+    fn artifacts_of_units() {
+        assert_eq!(artifacts_size(None), DEFAULT_ARTIFACTS_SIZE);
+        assert_eq!(artifacts_size(Some(3)), 32 * MIB);
+        assert_eq!(artifacts_size(Some(100)), 200 * MIB);
+    }
+}
@@ -6,21 +6,34 @@ use std::io;
 use std::path::PathBuf;
 use std::string::FromUtf8Error;
 
+use crate::cache::{ParseSizeError, MAX_CACHE_SIZE_ENV_VAR};
 use crate::cli::rust_releases_opts::{ParseEditionError, ParseEditionOrVersionError};
+use crate::context::ReleaseSource;
+use crate::deadline::{FormattedDuration, ParseDurationError};
+use crate::estimate::Finding;
 use crate::log_level::ParseLogLevelError;
 use crate::manifest::bare_version::{BareVersion, NoVersionMatchesManifestMsrvError};
+use crate::manifest::msrv_sources::MsrvSource;
 use crate::manifest::ManifestParseError;
+use crate::reporter::formatting::format_size;
 use rust_releases::Release;
 
-use crate::sub_command::{show, verify};
+use crate::sub_command::{
+    bisect_lockfile, check_consistency, clean, inspect, merge_results, report, show, sync, verify,
+    whatif,
+};
 
 pub(crate) type TResult<T> = Result<T, CargoMSRVError>;
 
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum CargoMSRVError {
     #[error("Unable to parse minimum rust version: {0}")]
     BareVersionParse(#[from] crate::manifest::bare_version::Error),
 
+    #[error("The `hack-each-feature` check command requires cargo-hack, which could not be found. Run `cargo install cargo-hack` to install it.")]
+    CargoHackNotInstalled,
+
     #[error(transparent)]
     CargoMetadata(#[from] cargo_metadata::Error),
 
@@ -30,15 +43,50 @@ pub enum CargoMSRVError {
     #[error(transparent)]
     Env(#[from] env::VarError),
 
+    #[error("Unable to export the traces to '{endpoint}': {error}")]
+    ExportTraces {
+        endpoint: String,
+        error: Box<ureq::Error>,
+    },
+
+    #[error("Unable to fetch the channel manifest for Rust {version}: {error}")]
+    FetchChannelManifest {
+        version: rust_releases::semver::Version,
+        error: rust_releases_io::CachedClientError,
+    },
+
+    #[error("Unable to fetch the channel manifest of the beta channel: {error}")]
+    FetchBetaChannelManifest {
+        error: rust_releases_io::CachedClientError,
+    },
+
+    #[error("Unable to fetch the releases of crate '{name}' from the crates.io index: {error}")]
+    FetchCratesIndex {
+        name: String,
+        error: rust_releases_io::CachedClientError,
+    },
+
     #[error("{0}")]
     GenericMessage(String),
 
+    #[error(
+        "Command '{command}' failed:\n    {}",
+        stderr.trim_end().lines().collect::<Vec<_>>().join("\n    ")
+    )]
+    GitCommandFailed { command: String, stderr: String },
+
     #[error(transparent)]
     Io(#[from] IoError),
 
     #[error("{0}")]
     InvalidConfig(String),
 
+    #[error(
+        "Invalid value of the {} environment variable: {0}",
+        MAX_CACHE_SIZE_ENV_VAR
+    )]
+    InvalidMaxCacheSize(ParseSizeError),
+
     #[error(transparent)]
     InvalidRustVersionNumber(#[from] std::num::ParseIntError),
 
@@ -57,8 +105,54 @@ pub enum CargoMSRVError {
     #[error(transparent)]
     NoToolchainsToTry(#[from] NoToolchainsToTryError),
 
-    #[error("Unable to set MSRV for workspace, try setting it for individual packages instead.")]
-    WorkspaceFound,
+    #[error("Custom toolchain '{name}' is not linked, see 'rustup toolchain link --help'")]
+    CustomToolchainNotLinked { name: String },
+
+    #[error(
+        "Unable to determine the version of rustc at '{rustc}', from the output of 'rustc -vV'"
+    )]
+    UnableToProbeRustc { rustc: Utf8PathBuf },
+
+    #[error(
+        "No container engine found, install podman or docker, or select one with '--container-engine'"
+    )]
+    NoContainerEngine,
+
+    #[error("The container image '{0}' must contain the '{{version}}' placeholder")]
+    InvalidContainerImage(String),
+
+    #[error("The Nix toolchain template '{0}' must contain the '{{version}}' placeholder")]
+    InvalidNixTemplate(String),
+
+    #[error("Running checks on a remote builder requires 'ssh' and 'rsync' to be installed")]
+    RemoteToolsNotInstalled,
+
+    #[error(
+        "Command '{command}' failed on remote builder '{destination}':\n    {}",
+        stderr.trim_end().lines().collect::<Vec<_>>().join("\n    ")
+    )]
+    RemoteCommandFailed {
+        destination: String,
+        command: String,
+        stderr: String,
+    },
+
+    #[error(
+        "Unable to determine the user and group id of the invoking user, for '--container-rootless'"
+    )]
+    UnableToDetermineUser,
+
+    #[error(
+        "{error}, so the declarations of the MSRV which were already updated were restored{}",
+        unrestored_files(unrestored)
+    )]
+    UpdateRolledBack {
+        error: IoError,
+        unrestored: Vec<Utf8PathBuf>,
+    },
+
+    #[error("Unable to find the section '{section}' in the changelog at '{path}', to add the change of the MSRV to; configure it with the 'changelog.section' key of the 'cargo-msrv' metadata table")]
+    ChangelogSectionNotFound { section: String, path: Utf8PathBuf },
 
     #[error(transparent)]
     NoVersionMatchesManifestMSRV(#[from] NoVersionMatchesManifestMsrvError),
@@ -66,12 +160,21 @@ pub enum CargoMSRVError {
     #[error("Unable to find key 'package.rust-version' (or 'package.metadata.msrv') in '{0}'")]
     NoMSRVKeyInCargoToml(Utf8PathBuf),
 
+    #[error("Unable to find the MSRV in any of the configured MSRV sources ({}) of the crate at '{}'", msrv_sources(.sources), .manifest)]
+    NoMsrvInSources {
+        sources: Vec<MsrvSource>,
+        manifest: Utf8PathBuf,
+    },
+
     #[error(transparent)]
     ParseEdition(#[from] ParseEditionError),
 
     #[error(transparent)]
     ParseEditionOrVersion(#[from] ParseEditionOrVersionError),
 
+    #[error(transparent)]
+    ParseDuration(#[from] ParseDurationError),
+
     #[error(transparent)]
     ParseLogLevel(#[from] ParseLogLevelError),
 
@@ -91,6 +194,34 @@ pub enum CargoMSRVError {
     #[error("There are no Rust releases in the rust-releases index")]
     RustReleasesEmptyReleaseSet,
 
+    #[error("The release source '{release_source}' did not serve the release index within {}", FormattedDuration(*timeout))]
+    ReleaseSourceTimeout {
+        release_source: ReleaseSource,
+        timeout: std::time::Duration,
+    },
+
+    #[error("The index of Rust releases may not be fetched with --frozen-index, but none of the release sources has a cached index")]
+    FrozenIndexNotCached,
+
+    #[error(
+        "The 'snapshot' release source requires a snapshot file, given with --release-snapshot"
+    )]
+    ReleaseSnapshotMissing,
+
+    #[error("Unable to parse line {line} of the release snapshot '{path}', expected a Rust version like '1.80.1': {error}")]
+    InvalidReleaseSnapshot {
+        path: Utf8PathBuf,
+        line: usize,
+        error: rust_releases::semver::Error,
+    },
+
+    #[error("The required components '{}' are not available for Rust {version} on target '{target}'", components.join(", "))]
+    RequiredComponentsUnavailable {
+        version: rust_releases::semver::Version,
+        target: String,
+        components: Vec<String>,
+    },
+
     #[error(transparent)]
     RustupError(#[from] RustupError),
 
@@ -106,12 +237,36 @@ pub enum CargoMSRVError {
     #[error("Unable to print event output")]
     Storyteller,
 
+    #[error(transparent)]
+    SubCommandBisectLockfile(#[from] bisect_lockfile::Error),
+
+    #[error(transparent)]
+    SubCommandCheckConsistency(#[from] check_consistency::Error),
+
+    #[error(transparent)]
+    SubCommandClean(#[from] clean::Error),
+
+    #[error(transparent)]
+    SubCommandInspect(#[from] inspect::Error),
+
+    #[error(transparent)]
+    SubCommandMergeResults(#[from] merge_results::Error),
+
+    #[error(transparent)]
+    SubCommandReport(#[from] report::Error),
+
     #[error(transparent)]
     SubCommandVerify(#[from] verify::Error),
 
     #[error(transparent)]
     SubCommandShow(#[from] show::Error),
 
+    #[error(transparent)]
+    SubCommandSync(#[from] sync::Error),
+
+    #[error(transparent)]
+    SubCommandWhatIf(#[from] whatif::Error),
+
     #[error(transparent)]
     SystemTime(#[from] std::time::SystemTimeError),
 
@@ -121,9 +276,20 @@ pub enum CargoMSRVError {
     #[error("The given target could not be found. Run `rustup target list` for an overview of available toolchains.")]
     UnknownTarget,
 
+    #[error(
+        "The channel manifest of the beta channel does not contain the version of the beta release"
+    )]
+    InvalidBetaChannelManifest,
+
     #[error("Unable to get or store the channel manifest on disk.")]
     UnableToCacheChannelManifest,
 
+    #[error("Unable to get or store the crates.io index on disk.")]
+    UnableToCacheCratesIndex,
+
+    #[error("Unable to locate the cache directory of cargo-msrv.")]
+    UnableToLocateCache,
+
     #[error(
         r#"Unable to find a Minimum Supported Rust Version (MSRV).
 
@@ -145,6 +311,118 @@ Thank you in advance!"#
     #[error("Unable to run the check command: '{}' at '{}'", &command, &cwd)]
     UnableToRunCheck { command: String, cwd: Utf8PathBuf },
 
+    #[error("The check of Rust {version} was interrupted")]
+    CheckInterrupted {
+        version: rust_releases::semver::Version,
+    },
+
+    #[error("The check of Rust {version} was skipped, so its outcome is inconclusive")]
+    CheckSkipped {
+        version: rust_releases::semver::Version,
+    },
+
+    #[error("Rust {version} is not available for this host: {reason}")]
+    ToolchainUnavailable {
+        version: rust_releases::semver::Version,
+        reason: String,
+    },
+
+    #[error("The check of Rust {version} exited with code {exit_code}, which is configured as inconclusive, so the MSRV can't be determined")]
+    CheckInconclusive {
+        version: rust_releases::semver::Version,
+        exit_code: i32,
+    },
+
+    #[error("The check of Rust {version} exceeded the memory limit of {}, so its outcome is inconclusive", format_size(*limit))]
+    MemoryLimitExceeded {
+        version: rust_releases::semver::Version,
+        limit: u64,
+    },
+
+    #[error("The predicate '{predicate}' exited with {status} for Rust {version}, instead of with code 0 (pass), 125 (skip) or another code up to 127 (fail)")]
+    InvalidPredicateVerdict {
+        predicate: Utf8PathBuf,
+        version: rust_releases::semver::Version,
+        status: String,
+    },
+
+    #[error("The search was aborted")]
+    SearchAborted,
+
+    #[error("The search was stopped, since it exceeded its maximum duration of {}", FormattedDuration(*budget))]
+    MaxDurationExceeded { budget: std::time::Duration },
+
+    #[error("cargo-msrv was interrupted")]
+    Interrupted,
+
+    #[error("The cargo of Rust {version} can't read version {lockfile_version} of the lockfile, which requires at least Rust {readable_since}")]
+    LockfileIncompatible {
+        version: rust_releases::semver::Version,
+        lockfile_version: u64,
+        readable_since: rust_releases::semver::Version,
+    },
+
+    #[error("The planned checks need about {} of disk space at '{path}', but only {} is free. Free up space, e.g. with `cargo msrv clean`, narrow the search space, e.g. with --min, or skip this check with --no-disk-preflight", format_size(*required), format_size(*available))]
+    InsufficientDiskSpace {
+        path: Utf8PathBuf,
+        required: u64,
+        available: u64,
+    },
+
+    #[error("Unable to reach the dist server '{url}', from which rustup installs the toolchains: {error}. Configure a mirror with --dist-server, RUSTUP_DIST_SERVER or the 'mirror' table of the cargo-msrv metadata, or skip this check with --no-dist-server-check")]
+    DistServerUnreachable { url: String, error: String },
+
+    #[error("The directory of toolchain tarballs '{0}' does not exist")]
+    TarballDirNotFound(Utf8PathBuf),
+
+    #[error(
+        "Unable to locate the local data directory, to which the toolchain tarballs are installed"
+    )]
+    UnableToLocateTarballInstallDir,
+
+    #[error("No tarball of toolchain '{toolchain}' found in '{dir}', expected 'rust-{toolchain}.tar.xz' or 'rust-{toolchain}.tar.gz'")]
+    TarballNotFound { dir: Utf8PathBuf, toolchain: String },
+
+    #[error("Unable to verify the toolchain tarball, since its checksum file '{path}' could not be read")]
+    TarballChecksumNotFound { path: Utf8PathBuf },
+
+    #[error("The SHA-256 checksum of the toolchain tarball '{path}' is {actual}, but {expected} was expected")]
+    TarballChecksumMismatch {
+        path: Utf8PathBuf,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Unable to extract the toolchain tarball '{path}': {stderr}")]
+    UnableToExtractTarball { path: Utf8PathBuf, stderr: String },
+
+    #[error("Unable to limit the resources of the checks: {error}")]
+    UnableToLimitResources { error: std::io::Error },
+
+    #[error("Unable to connect to the control socket at '{}': {error}", path.display())]
+    UnableToConnectControl {
+        path: std::path::PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("The crate has no binary target '{name}', the binary targets are: {available}")]
+    UnknownBinTarget { name: String, available: String },
+
+    #[error("The crate has no library target, so it can't be checked with '--lib'")]
+    NoLibTarget,
+
+    #[error("Unable to vendor the dependencies with 'cargo vendor': {stderr}")]
+    UnableToVendor { stderr: String },
+
+    #[error("'--vendor' is not supported with containers or remote builders, of which the checks can't read the vendored sources")]
+    VendorUnsupported,
+
+    #[error("The fingerprint of the environment is '{actual}', rather than the expected '{expected}', so an earlier result may no longer apply")]
+    FingerprintMismatch { expected: String, actual: String },
+
+    #[error(transparent)]
+    InvalidConfiguration(#[from] crate::config_builder::ConfigReport),
+
     #[error(transparent)]
     Path(#[from] PathError),
 }
@@ -155,6 +433,245 @@ impl From<String> for CargoMSRVError {
     }
 }
 
+impl CargoMSRVError {
+    /// The machine-readable code of the error, which is stable across releases, unlike the
+    /// variants and the messages of the errors.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::RustupError(_)
+            | Self::CustomToolchainNotLinked { .. }
+            | Self::ToolchainNotInstalled
+            | Self::UnknownTarget
+            | Self::RequiredComponentsUnavailable { .. }
+            | Self::ToolchainUnavailable { .. }
+            | Self::DistServerUnreachable { .. }
+            | Self::TarballDirNotFound(_)
+            | Self::UnableToLocateTarballInstallDir
+            | Self::TarballNotFound { .. }
+            | Self::TarballChecksumNotFound { .. }
+            | Self::TarballChecksumMismatch { .. }
+            | Self::UnableToExtractTarball { .. } => ErrorCode::ToolchainInstall,
+
+            Self::FetchChannelManifest { .. }
+            | Self::FetchBetaChannelManifest { .. }
+            | Self::FetchCratesIndex { .. }
+            | Self::InvalidBetaChannelManifest
+            | Self::UnableToCacheChannelManifest
+            | Self::UnableToCacheCratesIndex
+            | Self::UnableToParseRustVersion
+            | Self::RustReleasesSource(_)
+            | Self::RustReleasesEmptyReleaseSet
+            | Self::ReleaseSourceTimeout { .. }
+            | Self::FrozenIndexNotCached => ErrorCode::IndexFetch,
+            #[cfg(feature = "rust-releases-dist-source")]
+            Self::RustReleasesRustDistSource(_) => ErrorCode::IndexFetch,
+
+            Self::RustupRunWithCommandFailed
+            | Self::UnableToRunCheck { .. }
+            | Self::RemoteCommandFailed { .. }
+            | Self::UnableToVendor { .. } => ErrorCode::CheckFailed,
+
+            Self::CheckInterrupted { .. }
+            | Self::CheckSkipped { .. }
+            | Self::CheckInconclusive { .. }
+            | Self::MemoryLimitExceeded { .. }
+            | Self::InvalidPredicateVerdict { .. }
+            | Self::LockfileIncompatible { .. } => ErrorCode::CheckInconclusive,
+
+            Self::UnableToFindAnyGoodVersion { .. } => ErrorCode::NoMsrvFound,
+            Self::SubCommandMergeResults(merge_results::Error::NoCompatibleToolchain {
+                ..
+            }) => ErrorCode::NoMsrvFound,
+
+            Self::SubCommandVerify(error) => match error {
+                verify::Error::VerifyFailed(_) => ErrorCode::Incompatible,
+                verify::Error::BelowMsrv { .. } => ErrorCode::BelowMsrv,
+                verify::Error::AboveMsrv { .. } => ErrorCode::AboveMsrv,
+                verify::Error::MsrvBumpNotAllowed { .. } => ErrorCode::MsrvBumpNotAllowed,
+            },
+            Self::SubCommandCheckConsistency(check_consistency::Error::Inconsistent { .. }) => {
+                ErrorCode::Inconsistent
+            }
+            Self::FingerprintMismatch { .. } => ErrorCode::FingerprintMismatch,
+
+            Self::NoToolchainsToTry(_)
+            | Self::InvalidMsrvSet(_)
+            | Self::NoVersionMatchesManifestMSRV(_) => ErrorCode::EmptySearchSpace,
+
+            Self::InvalidConfig(_)
+            | Self::InvalidConfiguration(_)
+            | Self::InvalidMaxCacheSize(_)
+            | Self::InvalidContainerImage(_)
+            | Self::InvalidNixTemplate(_)
+            | Self::ChangelogSectionNotFound { .. }
+            | Self::ParseEdition(_)
+            | Self::ParseEditionOrVersion(_)
+            | Self::ParseDuration(_)
+            | Self::ParseLogLevel(_)
+            | Self::BareVersionParse(_)
+            | Self::SemverError(_)
+            | Self::InvalidRustVersionNumber(_)
+            | Self::UnableToParseCliArgs
+            | Self::RustReleasesSourceParseError(_)
+            | Self::ReleaseSnapshotMissing
+            | Self::InvalidReleaseSnapshot { .. }
+            | Self::UnknownBinTarget { .. }
+            | Self::NoLibTarget
+            | Self::VendorUnsupported => ErrorCode::Configuration,
+
+            Self::CargoMetadata(_)
+            | Self::ManifestParseError(_)
+            | Self::ParseToml(_)
+            | Self::NoCrateRootFound
+            | Self::SetMsrv(_)
+            | Self::SubCommandShow(_)
+            | Self::SubCommandSync(_) => ErrorCode::Manifest,
+            Self::NoMSRVKeyInCargoToml(_) | Self::NoMsrvInSources { .. } => {
+                ErrorCode::NoMsrvDeclared
+            }
+            Self::SubCommandWhatIf(whatif::Error::NoMsrvToVerify) => ErrorCode::NoMsrvDeclared,
+
+            Self::CargoHackNotInstalled
+            | Self::DefaultHostTripleNotFound
+            | Self::Env(_)
+            | Self::UnableToProbeRustc { .. }
+            | Self::NoContainerEngine
+            | Self::RemoteToolsNotInstalled
+            | Self::UnableToDetermineUser
+            | Self::GitCommandFailed { .. }
+            | Self::UnableToLocateCache
+            | Self::InsufficientDiskSpace { .. }
+            | Self::UnableToLimitResources { .. }
+            | Self::UnableToConnectControl { .. }
+            | Self::ExportTraces { .. } => ErrorCode::Environment,
+
+            Self::SubCommandBisectLockfile(_)
+            | Self::SubCommandInspect(_)
+            | Self::SubCommandReport(_)
+            | Self::SubCommandMergeResults(_)
+            | Self::SubCommandWhatIf(_) => ErrorCode::InvalidInput,
+
+            Self::Interrupted | Self::SearchAborted => ErrorCode::Interrupted,
+            Self::MaxDurationExceeded { .. } => ErrorCode::MaxDurationExceeded,
+
+            Self::Io(_)
+            | Self::Path(_)
+            | Self::InvalidUTF8(_)
+            | Self::UpdateRolledBack { .. }
+            | Self::SubCommandClean(_)
+            | Self::SystemTime(_)
+            | Self::Storyteller => ErrorCode::Io,
+
+            Self::GenericMessage(_) => ErrorCode::Other,
+        }
+    }
+}
+
+/// What an error is about, as a machine-readable code. Library consumers, and consumers of the
+/// `terminate_with_failure` event of the json output, can branch on the code, which is stable,
+/// rather than on the message of the error. New codes may be added in new releases.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// A toolchain could not be installed, or lacks a required component or target.
+    ToolchainInstall,
+    /// The index of Rust releases, a channel manifest, or the crates.io index could not be
+    /// fetched.
+    IndexFetch,
+    /// The check command of a Rust version could not be run to completion.
+    CheckFailed,
+    /// A check neither passed nor failed, e.g. since it was skipped, or exceeded its memory limit.
+    CheckInconclusive,
+    /// None of the checked Rust versions is compatible, so there is no MSRV.
+    NoMsrvFound,
+    /// The crate is not compatible with the verified Rust version.
+    Incompatible,
+    /// The verified Rust version is lower than the MSRV which was found, see `verify --find-msrv`.
+    BelowMsrv,
+    /// The verified Rust version is higher than the MSRV which was found, by more than the
+    /// tolerance of `verify --find-msrv`.
+    AboveMsrv,
+    /// The MSRV which was found is higher than the verified Rust version, which the MSRV policy
+    /// doesn't allow for the release type of `verify --release-type`.
+    MsrvBumpNotAllowed,
+    /// The declarations of the MSRV are not consistent with each other.
+    Inconsistent,
+    /// The fingerprint of the environment differs from the expected fingerprint.
+    FingerprintMismatch,
+    /// After filtering, there are no Rust releases left to check.
+    EmptySearchSpace,
+    /// An option, the configuration, or an environment variable is invalid.
+    Configuration,
+    /// The Cargo manifest of the crate could not be read, parsed or updated.
+    Manifest,
+    /// The crate does not declare an MSRV, which the subcommand requires.
+    NoMsrvDeclared,
+    /// A tool which cargo-msrv requires is missing, or the host does not meet a requirement.
+    Environment,
+    /// The input of a subcommand, like a recorded run or a shard result, is missing or invalid.
+    InvalidInput,
+    /// cargo-msrv, or the search, was interrupted.
+    Interrupted,
+    /// The search exceeded `--max-duration`.
+    MaxDurationExceeded,
+    /// A file could not be read or written, or a process could not be spawned.
+    Io,
+    /// Any other error.
+    Other,
+}
+
+/// By what the search space was limited, as part of an error message.
+fn limited_search_space(
+    min: Option<&BareVersion>,
+    max: Option<&BareVersion>,
+    gate: Option<&Finding>,
+) -> String {
+    let version = |version: Option<&BareVersion>| {
+        version
+            .map(|s| format!("{}", s))
+            .unwrap_or_else(|| "<not overridden>".to_string())
+    };
+
+    match gate {
+        Some(gate) => format!(
+            " Search space limited by the {} ({}) to min Rust '{}', and by user to max Rust '{}'",
+            gate.reason,
+            gate.location,
+            gate.version,
+            version(max)
+        ),
+        None => format!(
+            " Search space limited by user to min Rust '{}', and max Rust '{}'",
+            version(min),
+            version(max)
+        ),
+    }
+}
+
+/// The files which could not be restored after a failed update, as part of an error message.
+fn msrv_sources(sources: &[MsrvSource]) -> String {
+    sources
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn unrestored_files(files: &[Utf8PathBuf]) -> String {
+    if files.is_empty() {
+        return String::new();
+    }
+
+    let files = files
+        .iter()
+        .map(|file| format!("'{file}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(", except {files}, which could not be restored")
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("IO error: '{error}'. caused by: '{source}'.")]
 pub struct IoError {
@@ -173,6 +690,24 @@ pub enum IoErrorSource {
     #[error("Unable to read file '{0}'")]
     ReadFile(Utf8PathBuf),
 
+    #[error("Unable to read directory '{0}'")]
+    ReadDir(Utf8PathBuf),
+
+    #[error("Unable to canonicalize path '{0}'")]
+    Canonicalize(Utf8PathBuf),
+
+    #[error("Unable to create directory '{0}'")]
+    CreateDir(Utf8PathBuf),
+
+    #[error("Unable to create a temporary directory")]
+    CreateTempDir,
+
+    #[error("Unable to create a temporary file")]
+    CreateTempFile,
+
+    #[error("Unable to copy file '{0}'")]
+    CopyFile(Utf8PathBuf),
+
     #[error("Unable to write file '{0}'")]
     WriteFile(Utf8PathBuf),
 
@@ -187,6 +722,15 @@ pub enum IoErrorSource {
 
     #[error("Unable to collect output from '{0:?}', or process did not terminate properly")]
     WaitForProcessAndCollectOutput(OsString),
+
+    #[error("Unable to take a token of the jobserver")]
+    AcquireJobToken,
+
+    #[error("Unable to spawn thread '{0}'")]
+    SpawnThread(String),
+
+    #[error("Unable to write the trace file '{}'", .0.display())]
+    WriteTraceFile(PathBuf),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -202,7 +746,7 @@ pub enum SetMsrvError {
     inner.as_ref().map(|clues| format!("{}", clues)).unwrap_or_default(),
 )]
 pub struct NoToolchainsToTryError {
-    inner: Option<NoToolchainToTryClues>,
+    inner: Option<Box<NoToolchainToTryClues>>,
 }
 
 impl NoToolchainsToTryError {
@@ -210,12 +754,19 @@ impl NoToolchainsToTryError {
         Self { inner: None }
     }
 
-    pub fn with_clues(user_min: Option<BareVersion>, user_max: Option<BareVersion>) -> Self {
+    /// The constraints of the user, and the cargo gate which raised the minimum of the search
+    /// space above the minimum of the user, if any.
+    pub fn with_clues(
+        user_min: Option<BareVersion>,
+        user_max: Option<BareVersion>,
+        gate: Option<Finding>,
+    ) -> Self {
         Self {
-            inner: Some(NoToolchainToTryClues {
+            inner: Some(Box::new(NoToolchainToTryClues {
                 min: user_min,
                 max: user_max,
-            }),
+                gate,
+            })),
         }
     }
 
@@ -225,13 +776,12 @@ impl NoToolchainsToTryError {
 }
 
 #[derive(Debug, thiserror::Error)]
-#[error(" Search space limited by user to min Rust '{}', and max Rust '{}'",
-    min.as_ref().map(|s| format!("{}", s)).unwrap_or_else(|| "<not overridden>".to_string()),
-    max.as_ref().map(|s| format!("{}", s)).unwrap_or_else(|| "<not overridden>".to_string()),
-)]
+#[error("{}", limited_search_space(min.as_ref(), max.as_ref(), gate.as_ref()))]
 pub struct NoToolchainToTryClues {
     min: Option<BareVersion>,
     max: Option<BareVersion>,
+    /// The cargo gate which raised the minimum of the search space, if any.
+    gate: Option<Finding>,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -0,0 +1,593 @@
+//! The minimum version of cargo which can parse a project, which is shipped with the toolchain of
+//! the same Rust version.
+//!
+//! Before a crate is compiled, cargo reads its manifests, its lockfile and the cargo
+//! configuration. Constructs which are newer than the cargo of a toolchain, like edition 2021 or
+//! lockfile version 4, make the toolchain fail before it even gets to compile the crate. These
+//! constructs are found statically:
+//!
+//! * in the manifest: the edition, `rust-version`, the resolver, inheritance from the workspace,
+//!   namespaced and weak dependency features, and the `[lints]` table;
+//! * in the lockfile: its version, which is not a gate, since cargo rewrites a lockfile of a newer
+//!   version when it has no dependencies, and the lockfile is handled with `--lockfile-strategy`;
+//! * in the cargo configuration: the sparse registry protocol, and sparse registry indexes.
+
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::estimate::{relative_path, Finding, FindingKind};
+use crate::{lockfile, semver};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::ops::Range;
+use toml_edit::{ImDocument, Item, Table, TableLike};
+
+/// The tables in which dependencies are declared, also under `[target.'cfg(..)']`.
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// The cargo configuration files, relative to a directory, in order of precedence. The file
+/// without extension is the legacy name.
+const CONFIG_FILES: [&str; 2] = [".cargo/config.toml", ".cargo/config"];
+
+/// The constructs in the given manifests, lockfile and cargo configuration files which require a
+/// minimum cargo version. Files which do not exist are skipped.
+pub fn cargo_findings(
+    manifests: &[Utf8PathBuf],
+    lockfile: Option<&Utf8Path>,
+    config_dirs: &[&Utf8Path],
+    root: &Utf8Path,
+) -> TResult<Vec<CargoFinding>> {
+    let mut findings = Vec::new();
+
+    for manifest in manifests {
+        if let Some(contents) = read(manifest)? {
+            findings.extend(manifest_findings(&contents, relative_path(manifest, root)));
+        }
+    }
+
+    if let Some(lockfile) = lockfile {
+        if let Some(contents) = read(lockfile)? {
+            findings.extend(lockfile_findings(&contents, relative_path(lockfile, root)));
+        }
+    }
+
+    let config_files = config_dirs
+        .iter()
+        .flat_map(|dir| CONFIG_FILES.iter().map(|file| dir.join(file)))
+        .collect::<Vec<_>>();
+
+    for config in &config_files {
+        if let Some(contents) = read(config)? {
+            findings.extend(config_findings(
+                &contents,
+                relative_path(config, root),
+                config.extension() == Some("toml"),
+            ));
+        }
+    }
+
+    Ok(findings)
+}
+
+/// The constructs in a Cargo manifest which require a minimum cargo version.
+pub fn manifest_findings(contents: &str, path: &Utf8Path) -> Vec<CargoFinding> {
+    let Some(document) = parse(contents, path) else {
+        return Vec::new();
+    };
+
+    let mut findings = Findings::new(contents, path);
+    let root = document.as_table();
+
+    if let Some(package) = root.get("package").and_then(Item::as_table_like) {
+        if let Some(edition) = package.get("edition") {
+            let version = match edition.as_str() {
+                Some("2018") => Some((1, 31)),
+                Some("2021") => Some((1, 56)),
+                Some("2024") => Some((1, 85)),
+                _ => None,
+            };
+
+            if let Some(version) = version {
+                let reason = format!("edition {}", edition.as_str().unwrap_or_default());
+                findings.push(version, reason, key_span(package, "edition"));
+            }
+        }
+
+        if package.contains_key("rust-version") {
+            // Older versions of cargo ignore the field, with a warning
+            findings.push_ignored(
+                (1, 56),
+                "`package.rust-version`",
+                key_span(package, "rust-version"),
+            );
+        }
+
+        for (key, value) in package.iter() {
+            if is_inherited(value) {
+                let reason = format!("`package.{}` inherited from the workspace", key);
+                findings.push((1, 64), reason, key_span(package, key));
+            }
+        }
+    }
+
+    for table in ["package", "workspace"] {
+        let Some(table) = root.get(table).and_then(Item::as_table_like) else {
+            continue;
+        };
+
+        let resolver = table.get("resolver").and_then(Item::as_str);
+        let version = match resolver {
+            Some("2") => Some((1, 51)),
+            Some("3") => Some((1, 84)),
+            _ => None,
+        };
+
+        if let (Some(version), Some(resolver)) = (version, resolver) {
+            let reason = format!("dependency resolver version {}", resolver);
+            findings.push(version, reason, key_span(table, "resolver"));
+        }
+    }
+
+    // Older versions of cargo ignore these tables, with an "unused manifest key" warning; only
+    // inheriting from them fails
+    if let Some(workspace) = root.get("workspace").and_then(Item::as_table_like) {
+        for key in ["package", "dependencies"] {
+            if workspace.contains_key(key) {
+                let reason = format!("`[workspace.{}]` table", key);
+                findings.push_ignored((1, 64), reason, key_span(workspace, key));
+            }
+        }
+
+        if workspace.contains_key("lints") {
+            findings.push_ignored(
+                (1, 74),
+                "`[workspace.lints]` table",
+                key_span(workspace, "lints"),
+            );
+        }
+    }
+
+    if root.contains_key("lints") {
+        findings.push_ignored((1, 74), "`[lints]` table", key_span(root, "lints"));
+    }
+
+    for table in dependency_tables(root) {
+        for (name, dependency) in table.iter() {
+            if is_inherited(dependency) {
+                findings.push(
+                    (1, 64),
+                    "dependency inherited from the workspace",
+                    key_span(table, name),
+                );
+            }
+        }
+    }
+
+    if let Some(features) = root.get("features").and_then(Item::as_table_like) {
+        let enabled = features
+            .iter()
+            .filter_map(|(_, value)| value.as_array())
+            .flat_map(|array| array.iter())
+            .filter_map(|value| Some((value.as_str()?, value.span())));
+
+        for (feature, span) in enabled {
+            if feature.starts_with("dep:") {
+                findings.push((1, 60), "namespaced dependency features (`dep:`)", span);
+            } else if feature.contains("?/") {
+                findings.push((1, 60), "weak dependency features (`?/`)", span);
+            }
+        }
+    }
+
+    findings.into_inner()
+}
+
+/// The version of the lockfile, which cargo can only read from a minimum version. The version is
+/// not a gate: a lockfile may be generated by a newer cargo, like the one of cargo-msrv itself,
+/// and cargo still builds a crate of which the lockfile lists no dependencies.
+pub fn lockfile_findings(contents: &str, path: &Utf8Path) -> Vec<CargoFinding> {
+    let Some(document) = parse(contents, path) else {
+        return Vec::new();
+    };
+
+    let mut findings = Findings::new(contents, path);
+
+    // Lockfiles without a version (1 and 2) are read by every cargo which supports editions
+    let version = document
+        .get("version")
+        .and_then(Item::as_integer)
+        .and_then(|version| u64::try_from(version).ok());
+
+    if let Some((version, since)) = version.and_then(|v| Some((v, lockfile::readable_since(v)?))) {
+        let reason = format!("lockfile version {}", version);
+        let span = key_span(document.as_table(), "version");
+        findings.push_ignored((since.major, since.minor), reason, span);
+    }
+
+    findings.into_inner()
+}
+
+/// The settings of the cargo configuration which cargo only understands from a minimum version.
+pub fn config_findings(contents: &str, path: &Utf8Path, is_toml_file: bool) -> Vec<CargoFinding> {
+    let Some(document) = parse(contents, path) else {
+        return Vec::new();
+    };
+
+    let mut findings = Findings::new(contents, path);
+
+    if is_toml_file {
+        // Older versions of cargo only read `.cargo/config`
+        findings.push_ignored((1, 39), "`.cargo/config.toml` file name", None);
+    }
+
+    let registries = document
+        .get("registries")
+        .and_then(Item::as_table_like)
+        .into_iter()
+        .flat_map(|registries| registries.iter())
+        .filter_map(|(name, registry)| Some((name, registry.as_table_like()?)));
+
+    for (name, registry) in registries {
+        let setting = |key| registry.get(key).and_then(Item::as_str);
+
+        // Older versions of cargo are checked with the git protocol instead, which only
+        // exists for crates.io
+        if name == "crates-io" && setting("protocol") == Some("sparse") {
+            let span = key_span(registry, "protocol");
+            findings.push_ignored((1, 68), "sparse registry protocol", span);
+        }
+
+        if setting("index").is_some_and(|url| url.starts_with("sparse+")) {
+            findings.push(
+                (1, 68),
+                "sparse registry index",
+                key_span(registry, "index"),
+            );
+        }
+    }
+
+    findings.into_inner()
+}
+
+/// The dependency tables of a manifest, including those which are specific to a target.
+fn dependency_tables(root: &Table) -> impl Iterator<Item = &dyn TableLike> {
+    let targets = root
+        .get("target")
+        .and_then(Item::as_table_like)
+        .into_iter()
+        .flat_map(|targets| targets.iter())
+        .filter_map(|(_, target)| target.as_table_like());
+
+    std::iter::once(root as &dyn TableLike)
+        .chain(targets)
+        .flat_map(|table| {
+            DEPENDENCY_TABLES
+                .iter()
+                .filter_map(|key| table.get(key)?.as_table_like())
+        })
+}
+
+/// Whether a value is inherited from the workspace, like `version.workspace = true`.
+fn is_inherited(item: &Item) -> bool {
+    item.as_table_like()
+        .and_then(|table| table.get("workspace"))
+        .and_then(Item::as_bool)
+        .unwrap_or_default()
+}
+
+/// The span of a key of the table, which is also known for dotted keys and implicit tables,
+/// like the `lints` of `[lints.rust]`.
+fn key_span(table: &dyn TableLike, key: &str) -> Option<Range<usize>> {
+    let (key, item) = table.get_key_value(key)?;
+    key.span().or_else(|| item.span())
+}
+
+fn parse<'c>(contents: &'c str, path: &Utf8Path) -> Option<ImDocument<&'c str>> {
+    match ImDocument::parse(contents) {
+        Ok(document) => Some(document),
+        Err(error) => {
+            info!(%error, %path, "unable to parse file, skipping it for the cargo version");
+            None
+        }
+    }
+}
+
+fn read(path: &Utf8Path) -> TResult<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(IoError {
+            error,
+            source: IoErrorSource::ReadFile(path.to_path_buf()),
+        }
+        .into()),
+    }
+}
+
+/// A construct which requires a minimum cargo version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CargoFinding {
+    pub finding: Finding,
+    /// Whether older versions of cargo fail on the construct, rather than ignoring it. Rust
+    /// versions older than a gate can't be compatible.
+    pub is_gate: bool,
+}
+
+/// The findings of a single file, located by the line of their span.
+struct Findings<'c> {
+    contents: &'c str,
+    path: &'c Utf8Path,
+    findings: Vec<CargoFinding>,
+}
+
+impl<'c> Findings<'c> {
+    fn new(contents: &'c str, path: &'c Utf8Path) -> Self {
+        Self {
+            contents,
+            path,
+            findings: Vec::new(),
+        }
+    }
+
+    /// A construct on which older versions of cargo fail.
+    fn push(&mut self, version: (u64, u64), reason: impl Into<String>, span: Option<Range<usize>>) {
+        self.push_finding(version, reason.into(), span, true);
+    }
+
+    /// A construct which older versions of cargo ignore.
+    fn push_ignored(
+        &mut self,
+        version: (u64, u64),
+        reason: impl Into<String>,
+        span: Option<Range<usize>>,
+    ) {
+        self.push_finding(version, reason.into(), span, false);
+    }
+
+    fn push_finding(
+        &mut self,
+        version: (u64, u64),
+        reason: String,
+        span: Option<Range<usize>>,
+        is_gate: bool,
+    ) {
+        let (major, minor) = version;
+
+        let location = match span {
+            Some(span) => {
+                let line = self.contents[..span.start].matches('\n').count() + 1;
+                format!("{}:{}", self.path, line)
+            }
+            None => self.path.to_string(),
+        };
+
+        self.findings.push(CargoFinding {
+            finding: Finding {
+                version: semver::Version::new(major, minor, 0),
+                reason,
+                location,
+                kind: FindingKind::Cargo,
+            },
+            is_gate,
+        });
+    }
+
+    fn into_inner(self) -> Vec<CargoFinding> {
+        self.findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(findings: Vec<CargoFinding>) -> Vec<(String, String, String)> {
+        findings
+            .into_iter()
+            .map(|CargoFinding { finding, .. }| {
+                (
+                    finding.version.to_string(),
+                    finding.reason,
+                    finding.location,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn manifest() {
+        let manifest = r#"[package]
+name = "a"
+version.workspace = true
+edition = "2021"
+rust-version = "1.70"
+
+[dependencies]
+serde = { workspace = true }
+log = "0.4"
+
+[target.'cfg(unix)'.dependencies]
+libc = { workspace = true }
+
+[features]
+default = ["dep:log"]
+derive = ["serde?/derive", "std"]
+std = []
+
+[lints.rust]
+unsafe_code = "forbid"
+"#;
+
+        let findings = manifest_findings(manifest, Utf8Path::new("Cargo.toml"));
+
+        assert_eq!(
+            summary(findings),
+            vec![
+                (
+                    "1.56.0".into(),
+                    "edition 2021".into(),
+                    "Cargo.toml:4".into()
+                ),
+                (
+                    "1.56.0".into(),
+                    "`package.rust-version`".into(),
+                    "Cargo.toml:5".into()
+                ),
+                (
+                    "1.64.0".into(),
+                    "`package.version` inherited from the workspace".into(),
+                    "Cargo.toml:3".into()
+                ),
+                (
+                    "1.74.0".into(),
+                    "`[lints]` table".into(),
+                    "Cargo.toml:19".into()
+                ),
+                (
+                    "1.64.0".into(),
+                    "dependency inherited from the workspace".into(),
+                    "Cargo.toml:8".into()
+                ),
+                (
+                    "1.64.0".into(),
+                    "dependency inherited from the workspace".into(),
+                    "Cargo.toml:12".into()
+                ),
+                (
+                    "1.60.0".into(),
+                    "namespaced dependency features (`dep:`)".into(),
+                    "Cargo.toml:15".into()
+                ),
+                (
+                    "1.60.0".into(),
+                    "weak dependency features (`?/`)".into(),
+                    "Cargo.toml:16".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn gates() {
+        let manifest = "[package]\nname = \"a\"\nedition = \"2021\"\nrust-version = \"1.56\"\n\n[lints.rust]\nunsafe_code = \"forbid\"\n";
+
+        let gates = manifest_findings(manifest, Utf8Path::new("Cargo.toml"))
+            .into_iter()
+            .map(|found| (found.finding.reason, found.is_gate))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            gates,
+            vec![
+                ("edition 2021".to_string(), true),
+                ("`package.rust-version`".to_string(), false),
+                ("`[lints]` table".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn sparse_protocol_is_not_a_gate() {
+        let config = "[registries.crates-io]\nprotocol = \"sparse\"\n\n[registries.mirror]\nindex = \"sparse+https://mirror.example.com/index/\"\n";
+
+        let gates = config_findings(config, Utf8Path::new(".cargo/config"), false)
+            .into_iter()
+            .map(|found| (found.finding.reason, found.is_gate))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            gates,
+            vec![
+                ("sparse registry protocol".to_string(), false),
+                ("sparse registry index".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn workspace_manifest() {
+        let manifest = r#"[workspace]
+members = ["a"]
+resolver = "2"
+
+[workspace.package]
+version = "0.1.0"
+
+[workspace.dependencies]
+serde = "1"
+"#;
+
+        let findings = manifest_findings(manifest, Utf8Path::new("Cargo.toml"));
+
+        assert_eq!(
+            summary(findings),
+            vec![
+                (
+                    "1.51.0".into(),
+                    "dependency resolver version 2".into(),
+                    "Cargo.toml:3".into()
+                ),
+                (
+                    "1.64.0".into(),
+                    "`[workspace.package]` table".into(),
+                    "Cargo.toml:5".into()
+                ),
+                (
+                    "1.64.0".into(),
+                    "`[workspace.dependencies]` table".into(),
+                    "Cargo.toml:8".into()
+                ),
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        unversioned = { "[[package]]\nname = \"a\"\n", None },
+        v3 = { "version = 3\n", Some("1.47.0") },
+        v4 = { "# generated by cargo\nversion = 4\n", Some("1.78.0") },
+        unparsable = { "version = ", None },
+    )]
+    fn lockfile(contents: &str, expected: Option<&str>) {
+        let findings = lockfile_findings(contents, Utf8Path::new("Cargo.lock"));
+
+        assert!(findings.iter().all(|found| !found.is_gate));
+
+        assert_eq!(
+            findings
+                .first()
+                .map(|found| found.finding.version.to_string())
+                .as_deref(),
+            expected
+        );
+    }
+
+    #[test]
+    fn config() {
+        let config = r#"[registries.crates-io]
+protocol = "sparse"
+
+[registries.mirror]
+index = "sparse+https://mirror.example.com/index/"
+"#;
+
+        let findings = config_findings(config, Utf8Path::new(".cargo/config.toml"), true);
+
+        assert_eq!(
+            summary(findings),
+            vec![
+                (
+                    "1.39.0".into(),
+                    "`.cargo/config.toml` file name".into(),
+                    ".cargo/config.toml".into()
+                ),
+                (
+                    "1.68.0".into(),
+                    "sparse registry protocol".into(),
+                    ".cargo/config.toml:2".into()
+                ),
+                (
+                    "1.68.0".into(),
+                    "sparse registry index".into(),
+                    ".cargo/config.toml:5".into()
+                ),
+            ]
+        );
+    }
+}
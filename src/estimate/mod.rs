@@ -0,0 +1,355 @@
+//! A static estimate of the MSRV, which does not compile the crate.
+//!
+//! The syntax tree of each source file is scanned for language features and library items which
+//! are part of the [`StabilizationIndex`]. The estimate is the most recent Rust version in which
+//! one of the used features was stabilized.
+//!
+//! The estimate is approximate: method calls can't be resolved without type information, macros
+//! are not expanded, and the MSRV of dependencies is not taken into account. It may thus be lower
+//! than the actual MSRV, but should be a good starting point.
+
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use crate::estimate::cargo::CargoFinding;
+use crate::estimate::visitor::{Usage, UsageKind, UsageVisitor};
+use crate::semver;
+use crate::stabilization::{StabilizationHint, StabilizationIndex};
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::{Metadata, MetadataCommand, Package};
+use std::collections::BTreeSet;
+
+pub mod cargo;
+mod visitor;
+
+/// Target kinds which are compiled by a regular `cargo check`.
+const CHECKED_TARGET_KINDS: [&str; 7] = [
+    "lib",
+    "rlib",
+    "dylib",
+    "cdylib",
+    "staticlib",
+    "proc-macro",
+    "bin",
+];
+
+/// A statically estimated MSRV, together with the findings it is based on.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Estimate {
+    findings: Vec<Finding>,
+}
+
+impl Estimate {
+    fn from_findings(mut findings: Vec<Finding>) -> Self {
+        // The most restrictive findings first; for each feature, only its first usage is kept.
+        findings.sort_by(|lhs, rhs| rhs.version.cmp(&lhs.version));
+
+        let mut seen = BTreeSet::new();
+        findings.retain(|finding| seen.insert((finding.version.clone(), finding.reason.clone())));
+
+        Self { findings }
+    }
+
+    /// The estimated MSRV, or `None` if none of the used features is part of the stabilization
+    /// index.
+    pub fn version(&self) -> Option<&semver::Version> {
+        self.findings.first().map(|finding| &finding.version)
+    }
+
+    /// The features which were found, most restrictive first.
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    /// The minimum cargo version which can parse the project, or `None` if no construct with a
+    /// known cargo version was found.
+    pub fn cargo_version(&self) -> Option<&semver::Version> {
+        self.findings
+            .iter()
+            .find(|finding| finding.kind == FindingKind::Cargo)
+            .map(|finding| &finding.version)
+    }
+}
+
+/// A feature which requires at least a certain Rust version.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Finding {
+    /// The Rust version in which the feature was stabilized.
+    pub version: semver::Version,
+    /// What was stabilized, e.g. "let-else statements stabilized".
+    pub reason: String,
+    /// Where the feature is used, e.g. `src/lib.rs:12:5`, relative to the crate root.
+    pub location: String,
+    /// Whether the feature is required by the compiler, or by cargo.
+    pub kind: FindingKind,
+}
+
+/// The part of the toolchain which requires a feature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// A language feature or library item, used in the source code.
+    Compiler,
+    /// A construct of a manifest, the lockfile or the cargo configuration, which cargo must be
+    /// able to parse.
+    Cargo,
+}
+
+impl Finding {
+    fn new(hint: StabilizationHint, location: String) -> Self {
+        Self {
+            version: hint.version,
+            reason: hint.reason,
+            location,
+            kind: FindingKind::Compiler,
+        }
+    }
+}
+
+/// Estimate the MSRV of the selected packages, or the root package if none were selected.
+pub fn estimate(env: &EnvironmentContext, index: &StabilizationIndex) -> TResult<Estimate> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(env.manifest())
+        .no_deps()
+        .exec()?;
+    let packages = packages(env, &metadata);
+    let root = env.root();
+
+    let mut findings = project_cargo_findings(env, &metadata, &packages, true)?
+        .into_iter()
+        .map(|found| found.finding)
+        .collect::<Vec<_>>();
+
+    for package in &packages {
+        let manifest = relative_path(&package.manifest_path, root);
+        let edition = format!("edition{}", package.edition.as_str());
+
+        if let Some(hint) = index.language_hint(&edition) {
+            findings.push(Finding::new(hint, manifest.to_string()));
+        }
+
+        for file in source_files(package)? {
+            let contents = std::fs::read_to_string(&file).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::ReadFile(file.clone()),
+            })?;
+
+            let path = relative_path(&file, root);
+            findings.extend(source_findings(&contents, path, index));
+        }
+    }
+
+    Ok(Estimate::from_findings(findings))
+}
+
+/// The constructs of the project on which older versions of cargo fail, like edition 2021 or
+/// namespaced dependency features, most restrictive first. Rust versions older than these can't
+/// be compatible.
+pub fn cargo_gates(env: &EnvironmentContext) -> TResult<Vec<Finding>> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(env.manifest())
+        .no_deps()
+        .exec()?;
+    let packages = packages(env, &metadata);
+
+    let gates = project_cargo_findings(env, &metadata, &packages, false)?
+        .into_iter()
+        .filter(|found| found.is_gate)
+        .map(|found| found.finding)
+        .collect();
+
+    Ok(Estimate::from_findings(gates).findings)
+}
+
+/// The constructs of the manifests of the workspace and the given packages, the lockfile (if
+/// included) and the cargo configuration, which require a minimum cargo version.
+fn project_cargo_findings(
+    env: &EnvironmentContext,
+    metadata: &Metadata,
+    packages: &[Package],
+    include_lockfile: bool,
+) -> TResult<Vec<CargoFinding>> {
+    let root = env.root();
+    let workspace_root = metadata.workspace_root.as_path();
+
+    let manifests = std::iter::once(workspace_root.join("Cargo.toml"))
+        .chain(packages.iter().map(|package| package.manifest_path.clone()))
+        .collect::<BTreeSet<_>>();
+    let config_dirs = BTreeSet::from([root, workspace_root]);
+    let lockfile = workspace_root.join("Cargo.lock");
+
+    cargo::cargo_findings(
+        &manifests.into_iter().collect::<Vec<_>>(),
+        include_lockfile.then_some(lockfile.as_path()),
+        &config_dirs.into_iter().collect::<Vec<_>>(),
+        root,
+    )
+}
+
+/// Estimate the MSRV of a single source file, located at `path`.
+pub fn estimate_source(source: &str, path: &Utf8Path, index: &StabilizationIndex) -> Estimate {
+    Estimate::from_findings(source_findings(source, path, index))
+}
+
+fn source_findings(source: &str, path: &Utf8Path, index: &StabilizationIndex) -> Vec<Finding> {
+    let file = match syn::parse_file(source) {
+        Ok(file) => file,
+        Err(error) => {
+            info!(%error, %path, "unable to parse source file, skipping it for the estimate");
+            return Vec::new();
+        }
+    };
+
+    UsageVisitor::collect(&file)
+        .into_iter()
+        .filter_map(|usage| {
+            let Usage { kind, line, column } = usage;
+
+            let hint = match kind {
+                UsageKind::Language(name) => index.language_hint(name),
+                UsageKind::Item { name, scope } => index.item_hint(&name, scope.as_deref()),
+            }?;
+
+            Some(Finding::new(hint, format!("{}:{}:{}", path, line, column)))
+        })
+        .collect()
+}
+
+fn packages(env: &EnvironmentContext, metadata: &Metadata) -> Vec<Package> {
+    let selected = env.workspace_packages.selected_packages();
+
+    if !selected.is_empty() {
+        return selected.to_vec();
+    }
+
+    match metadata.root_package() {
+        Some(package) => vec![package.clone()],
+        // A virtual manifest: estimate the default members of the workspace.
+        None => metadata
+            .workspace_default_packages()
+            .into_iter()
+            .cloned()
+            .collect(),
+    }
+}
+
+/// The source files of the targets of a package which are compiled by `cargo check`: all Rust
+/// files in the directories of the library and binary targets, and the build script.
+fn source_files(package: &Package) -> TResult<BTreeSet<Utf8PathBuf>> {
+    let mut files = BTreeSet::new();
+
+    for target in &package.targets {
+        if target.kind.iter().any(|kind| kind == "custom-build") {
+            files.insert(target.src_path.clone());
+        } else if target
+            .kind
+            .iter()
+            .any(|kind| CHECKED_TARGET_KINDS.contains(&kind.as_str()))
+        {
+            if let Some(dir) = target.src_path.parent() {
+                collect_rust_files(dir, &mut files)?;
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+fn collect_rust_files(dir: &Utf8Path, files: &mut BTreeSet<Utf8PathBuf>) -> TResult<()> {
+    let read_dir_error = |error| {
+        CargoMSRVError::Io(IoError {
+            error,
+            source: IoErrorSource::ReadDir(dir.to_path_buf()),
+        })
+    };
+
+    for entry in dir.read_dir_utf8().map_err(read_dir_error)? {
+        let path = entry.map_err(read_dir_error)?.into_path();
+
+        if path.is_dir() {
+            collect_rust_files(&path, files)?;
+        } else if path.extension() == Some("rs") {
+            files.insert(path);
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn relative_path<'path>(path: &'path Utf8Path, root: &Utf8Path) -> &'path Utf8Path {
+    // The crate root may not be canonicalized, while the paths reported by cargo metadata are.
+    let canonical_root = root.canonicalize_utf8().ok();
+
+    path.strip_prefix(root)
+        .ok()
+        .or_else(|| canonical_root.and_then(|root| path.strip_prefix(root).ok()))
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_restrictive_finding() {
+        let source = r#"
+use std::sync::OnceLock;
+
+fn f(x: Option<u32>) -> u32 {
+    let Some(x) = x else { return 0 };
+    x
+}
+"#;
+        let estimate = estimate_source(
+            source,
+            Utf8Path::new("src/lib.rs"),
+            &StabilizationIndex::shipped(),
+        );
+
+        assert_eq!(estimate.version(), Some(&semver::Version::new(1, 70, 0)));
+        assert_eq!(
+            estimate.findings(),
+            &[
+                Finding {
+                    version: semver::Version::new(1, 70, 0),
+                    reason: "`OnceLock` stabilized".to_string(),
+                    location: "src/lib.rs:2:16".to_string(),
+                    kind: FindingKind::Compiler,
+                },
+                Finding {
+                    version: semver::Version::new(1, 65, 0),
+                    reason: "let-else statements stabilized".to_string(),
+                    location: "src/lib.rs:5:21".to_string(),
+                    kind: FindingKind::Compiler,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn deduplicates_findings() {
+        let source = "async fn a() {} async fn b() {}";
+        let estimate = estimate_source(
+            source,
+            Utf8Path::new("src/lib.rs"),
+            &StabilizationIndex::shipped(),
+        );
+
+        assert_eq!(estimate.findings().len(), 1);
+        assert_eq!(estimate.findings()[0].location, "src/lib.rs:1:1");
+    }
+
+    #[yare::parameterized(
+        no_features = { "fn main() {}" },
+        unparsable = { "fn main( {" },
+    )]
+    fn no_estimate(source: &str) {
+        let estimate = estimate_source(
+            source,
+            Utf8Path::new("src/lib.rs"),
+            &StabilizationIndex::shipped(),
+        );
+
+        assert!(estimate.version().is_none());
+    }
+}
@@ -0,0 +1,431 @@
+//! A syntax tree visitor which collects the language features and library items used by a
+//! source file, which determine the MSRV of a crate.
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::visit::{self, Visit};
+use syn::{Attribute, Expr, GenericParam, Lit, Pat, ReturnType, Type, UseTree};
+
+/// A language feature or library item, used somewhere in a source file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Usage {
+    pub(crate) kind: UsageKind,
+    /// The line and column (1-based) at which the usage was found.
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum UsageKind {
+    /// A language feature, by the name of its entry in the stabilization index.
+    Language(&'static str),
+    /// A library item, e.g. `OnceLock` in scope `sync`.
+    Item { name: String, scope: Option<String> },
+}
+
+/// Collects the usages of a single source file.
+#[derive(Debug, Default)]
+pub(crate) struct UsageVisitor {
+    usages: Vec<Usage>,
+}
+
+impl UsageVisitor {
+    pub(crate) fn collect(file: &syn::File) -> Vec<Usage> {
+        let mut visitor = Self::default();
+        visitor.visit_file(file);
+        visitor.usages
+    }
+
+    fn language(&mut self, feature: &'static str, span: Span) {
+        self.push(UsageKind::Language(feature), span);
+    }
+
+    fn item(&mut self, name: String, scope: Option<String>, span: Span) {
+        self.push(UsageKind::Item { name, scope }, span);
+    }
+
+    fn push(&mut self, kind: UsageKind, span: Span) {
+        let start = span.start();
+
+        self.usages.push(Usage {
+            kind,
+            line: start.line,
+            column: start.column + 1,
+        });
+    }
+
+    fn use_tree(&mut self, tree: &UseTree, parent: Option<String>) {
+        match tree {
+            UseTree::Path(path) => self.use_tree(&path.tree, Some(path.ident.to_string())),
+            UseTree::Name(name) => self.item(name.ident.to_string(), parent, name.ident.span()),
+            UseTree::Rename(rename) => {
+                self.item(rename.ident.to_string(), parent, rename.ident.span())
+            }
+            UseTree::Group(group) => {
+                for tree in &group.items {
+                    self.use_tree(tree, parent.clone());
+                }
+            }
+            UseTree::Glob(_) => {}
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for UsageVisitor {
+    fn visit_attribute(&mut self, attr: &'ast Attribute) {
+        let path = attr.path();
+
+        if path.is_ident("non_exhaustive") {
+            self.language("non_exhaustive", path.segments[0].ident.span());
+        } else if path.is_ident("expect") {
+            self.language("lint_reasons", path.segments[0].ident.span());
+        }
+
+        visit::visit_attribute(self, attr);
+    }
+
+    fn visit_variant(&mut self, variant: &'ast syn::Variant) {
+        if let Some(attr) = variant.attrs.iter().find(|a| a.path().is_ident("default")) {
+            self.language("default_enum_variant", attr.path().segments[0].ident.span());
+        }
+
+        visit::visit_variant(self, variant);
+    }
+
+    fn visit_signature(&mut self, sig: &'ast syn::Signature) {
+        if let Some(asyncness) = &sig.asyncness {
+            self.language("async_await", asyncness.span);
+        }
+
+        visit::visit_signature(self, sig);
+    }
+
+    fn visit_trait_item_fn(&mut self, item: &'ast syn::TraitItemFn) {
+        if let Some(asyncness) = &item.sig.asyncness {
+            self.language("async_fn_in_trait", asyncness.span);
+        }
+
+        if let ReturnType::Type(_, ty) = &item.sig.output {
+            if let Type::ImplTrait(impl_trait) = ty.as_ref() {
+                self.language(
+                    "return_position_impl_trait_in_trait",
+                    impl_trait.impl_token.span,
+                );
+            }
+        }
+
+        visit::visit_trait_item_fn(self, item);
+    }
+
+    fn visit_trait_item_type(&mut self, item: &'ast syn::TraitItemType) {
+        if !item.generics.params.is_empty() {
+            self.language("generic_associated_types", item.ident.span());
+        }
+
+        visit::visit_trait_item_type(self, item);
+    }
+
+    fn visit_generic_param(&mut self, param: &'ast GenericParam) {
+        if let GenericParam::Const(param) = param {
+            self.language("const_generics", param.const_token.span);
+        }
+
+        visit::visit_generic_param(self, param);
+    }
+
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        if let Some((else_token, _)) = local.init.as_ref().and_then(|init| init.diverge.as_ref()) {
+            self.language("let_else", else_token.span);
+        }
+
+        visit::visit_local(self, local);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Async(it) => self.language("async_await", it.async_token.span),
+            Expr::Await(it) => self.language("async_await", it.await_token.span),
+            Expr::Block(it) if it.label.is_some() => {
+                if let Some(label) = &it.label {
+                    self.language("label_break_value", label.name.apostrophe);
+                }
+            }
+            Expr::Const(it) => self.language("inline_const", it.const_token.span),
+            Expr::Closure(it) => {
+                if let Some(asyncness) = &it.asyncness {
+                    self.language("async_closure", asyncness.span);
+                }
+            }
+            Expr::Assign(it) => {
+                if matches!(
+                    it.left.as_ref(),
+                    Expr::Tuple(_) | Expr::Array(_) | Expr::Struct(_)
+                ) {
+                    self.language("destructuring_assignment", it.eq_token.span);
+                }
+            }
+            _ => {}
+        }
+
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_lit(&mut self, lit: &'ast Lit) {
+        if let Lit::CStr(it) = lit {
+            self.language("c_str_literals", it.span());
+        }
+
+        visit::visit_lit(self, lit);
+    }
+
+    fn visit_pat(&mut self, pat: &'ast Pat) {
+        let nested = match pat {
+            Pat::Tuple(it) => it.elems.iter().collect(),
+            Pat::TupleStruct(it) => it.elems.iter().collect(),
+            Pat::Slice(it) => it.elems.iter().collect(),
+            Pat::Struct(it) => it.fields.iter().map(|f| f.pat.as_ref()).collect(),
+            Pat::Reference(it) => vec![it.pat.as_ref()],
+            Pat::Ident(it) => it.subpat.iter().map(|(_, p)| p.as_ref()).collect(),
+            _ => Vec::new(),
+        };
+
+        if let Some(Pat::Or(or)) = nested
+            .into_iter()
+            .map(unparenthesized)
+            .find(|p| matches!(p, Pat::Or(_)))
+        {
+            let span = or
+                .cases
+                .pairs()
+                .next()
+                .and_then(|pair| pair.punct().map(|punct| punct.spans[0]));
+
+            if let Some(span) = span {
+                self.language("nested_or_patterns", span);
+            }
+        }
+
+        visit::visit_pat(self, pat);
+    }
+
+    fn visit_item_use(&mut self, item: &'ast syn::ItemUse) {
+        self.use_tree(&item.tree, None);
+
+        visit::visit_item_use(self, item);
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        let segments = &path.segments;
+
+        if segments.len() >= 2 {
+            let name = &segments[segments.len() - 1].ident;
+            let scope = &segments[segments.len() - 2].ident;
+
+            self.item(name.to_string(), Some(scope.to_string()), name.span());
+        }
+
+        visit::visit_path(self, path);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        if let Some(ident) = mac.path.get_ident() {
+            // Macros of the standard library are exported from the root of the crate,
+            // e.g. `std::matches`.
+            self.item(ident.to_string(), Some("std".to_string()), ident.span());
+
+            if is_formatting_macro(&ident.to_string()) && captures_identifiers(&mac.tokens) {
+                self.language("format_args_capture", ident.span());
+            }
+        }
+
+        visit::visit_macro(self, mac);
+    }
+}
+
+fn unparenthesized(pat: &Pat) -> &Pat {
+    match pat {
+        Pat::Paren(it) => unparenthesized(&it.pat),
+        _ => pat,
+    }
+}
+
+fn is_formatting_macro(name: &str) -> bool {
+    matches!(
+        name,
+        "format"
+            | "format_args"
+            | "print"
+            | "println"
+            | "eprint"
+            | "eprintln"
+            | "write"
+            | "writeln"
+            | "panic"
+            | "unreachable"
+            | "todo"
+            | "unimplemented"
+    )
+}
+
+/// Whether the format string of a formatting macro refers to an identifier which is not passed
+/// as a named argument, e.g. `println!("{name}")`, as opposed to `println!("{name}", name = x)`.
+fn captures_identifiers(tokens: &TokenStream) -> bool {
+    let tokens = tokens.clone().into_iter().collect::<Vec<_>>();
+
+    let Some(format_string) = tokens.iter().find_map(|token| match token {
+        TokenTree::Literal(lit) => match Lit::new(lit.clone()) {
+            Lit::Str(s) => Some(s.value()),
+            _ => None,
+        },
+        _ => None,
+    }) else {
+        return false;
+    };
+
+    let named_arguments = tokens
+        .windows(2)
+        .filter_map(|window| match window {
+            [TokenTree::Ident(ident), TokenTree::Punct(punct)] if punct.as_char() == '=' => {
+                Some(ident.to_string())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    format_arguments(&format_string)
+        .into_iter()
+        .any(|argument| !named_arguments.contains(&argument.to_string()))
+}
+
+/// The identifiers in the `{...}` placeholders of a format string.
+fn format_arguments(format_string: &str) -> Vec<&str> {
+    let mut arguments = Vec::new();
+    let mut rest = format_string;
+
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+
+        // escaped brace: `{{`
+        if let Some(escaped) = after.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+
+        let end = after.find('}').unwrap_or(after.len());
+        let placeholder = &after[..end];
+        let argument = placeholder.split(':').next().unwrap_or_default().trim();
+
+        let is_identifier = argument
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && argument.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+        if is_identifier {
+            arguments.push(argument);
+        }
+
+        rest = &after[end..];
+    }
+
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn language_features(source: &str) -> Vec<&'static str> {
+        let file = syn::parse_file(source).unwrap();
+
+        UsageVisitor::collect(&file)
+            .into_iter()
+            .filter_map(|usage| match usage.kind {
+                UsageKind::Language(name) => Some(name),
+                UsageKind::Item { .. } => None,
+            })
+            .collect()
+    }
+
+    fn items(source: &str) -> Vec<(String, Option<String>)> {
+        let file = syn::parse_file(source).unwrap();
+
+        UsageVisitor::collect(&file)
+            .into_iter()
+            .filter_map(|usage| match usage.kind {
+                UsageKind::Item { name, scope } => Some((name, scope)),
+                UsageKind::Language(_) => None,
+            })
+            .collect()
+    }
+
+    #[yare::parameterized(
+        async_fn = { "async fn f() {}", "async_await" },
+        await_expr = { "fn f() { x.await; }", "async_await" },
+        non_exhaustive = { "#[non_exhaustive] struct S;", "non_exhaustive" },
+        const_generics = { "struct S<const N: usize>;", "const_generics" },
+        nested_or_patterns = { "fn f() { let (A | B, c) = x; }", "nested_or_patterns" },
+        format_args_capture = { r#"fn f() { println!("{name}"); }"#, "format_args_capture" },
+        destructuring_assignment = { "fn f() { (a, b) = (b, a); }", "destructuring_assignment" },
+        default_enum_variant = { "#[derive(Default)] enum E { #[default] A }", "default_enum_variant" },
+        let_else = { "fn f() { let Some(x) = y else { return }; }", "let_else" },
+        generic_associated_types = { "trait T { type A<'a>; }", "generic_associated_types" },
+        label_break_value = { "fn f() { 'a: { break 'a; } }", "label_break_value" },
+        async_fn_in_trait = { "trait T { async fn f(); }", "async_fn_in_trait" },
+        rpitit = { "trait T { fn f() -> impl Sized; }", "return_position_impl_trait_in_trait" },
+        c_str_literals = { r#"fn f() { let s = c"hi"; }"#, "c_str_literals" },
+        inline_const = { "fn f() { let x = const { 1 }; }", "inline_const" },
+        lint_reasons = { "#[expect(dead_code)] fn f() {}", "lint_reasons" },
+        async_closure = { "fn f() { let c = async || {}; }", "async_closure" },
+    )]
+    fn detects_language_feature(source: &str, expected: &str) {
+        assert!(language_features(source).contains(&expected));
+    }
+
+    #[yare::parameterized(
+        top_level_or_pattern = { "fn f() { match x { A | B => {} } }" },
+        named_format_argument = { r#"fn f() { println!("{name}", name = 1); }"# },
+        positional_format_argument = { r#"fn f() { println!("{} {0} {:?}", 1); }"# },
+        escaped_braces = { r#"fn f() { println!("{{name}}"); }"# },
+        plain_assignment = { "fn f() { a = b; }" },
+        plain_let = { "fn f() { let x = 1; }" },
+    )]
+    fn no_language_feature(source: &str) {
+        assert!(language_features(source).is_empty());
+    }
+
+    #[test]
+    fn collects_use_trees() {
+        let items = items("use std::sync::{OnceLock, Mutex as M}; use std::io::*;");
+
+        assert_eq!(
+            items,
+            vec![
+                ("OnceLock".to_string(), Some("sync".to_string())),
+                ("Mutex".to_string(), Some("sync".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn collects_paths_and_macros() {
+        let items = items("fn f() { std::hint::black_box(1); matches!(x, y); }");
+
+        assert!(items.contains(&("black_box".to_string(), Some("hint".to_string()))));
+        assert!(items.contains(&("matches".to_string(), Some("std".to_string()))));
+    }
+
+    #[test]
+    fn location() {
+        let file = syn::parse_file("\nfn f() {\n    let Some(x) = y else { return };\n}").unwrap();
+        let usages = UsageVisitor::collect(&file);
+
+        assert_eq!(
+            usages,
+            vec![Usage {
+                kind: UsageKind::Language("let_else"),
+                line: 3,
+                column: 21,
+            }]
+        );
+    }
+}
@@ -1,7 +1,28 @@
+use crate::CargoMSRVError;
+
 /// Exit codes returned by cargo-msrv
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ExitCode {
     Success,
     Failure,
+    /// The search stopped before it found the MSRV, and reported the bounds of the MSRV instead,
+    /// e.g. since it exceeded `--max-duration`.
+    PartialResult,
+    /// cargo-msrv was stopped by Ctrl-C or SIGTERM; like a shell, 128 plus the number of SIGINT.
+    Interrupted,
+    /// The program panicked; this is the exit code of a Rust program which panics.
+    Panic,
+}
+
+impl ExitCode {
+    /// The exit code of a run which failed with the given error.
+    pub fn of_error(error: &CargoMSRVError) -> Self {
+        match error {
+            CargoMSRVError::MaxDurationExceeded { .. } => Self::PartialResult,
+            CargoMSRVError::Interrupted => Self::Interrupted,
+            _ => Self::Failure,
+        }
+    }
 }
 
 impl From<ExitCode> for i32 {
@@ -9,6 +30,9 @@ impl From<ExitCode> for i32 {
         match code {
             ExitCode::Success => 0,
             ExitCode::Failure => 1,
+            ExitCode::PartialResult => 3,
+            ExitCode::Interrupted => 130,
+            ExitCode::Panic => 101,
         }
     }
 }
@@ -1,9 +1,79 @@
+/// Makes cargo write its messages, such as the compiled artifacts, as json to stdout, while the
+/// diagnostics are still rendered to stderr.
+pub const JSON_MESSAGE_FORMAT: &str = "--message-format=json-render-diagnostics";
+
 #[derive(Debug, Default)]
 pub struct CargoCommand {
     features: Option<Vec<String>>,
     all_features: bool,
     no_default_features: bool,
     target: Option<String>,
+    targets: Targets,
+}
+
+/// The packages and targets to check, beyond the default targets of cargo.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Targets {
+    /// Whether the examples are checked, in addition to the library and binaries.
+    pub examples: bool,
+    /// Whether the benchmarks are checked, in addition to the library and binaries.
+    pub benches: bool,
+    /// Whether a checked package has a library target, since cargo refuses `--lib` otherwise.
+    pub library: bool,
+    /// The packages to check, by name. If empty, cargo checks the default members of the
+    /// workspace.
+    pub packages: Vec<String>,
+    /// The binary target to check, by name, instead of the library and binaries.
+    pub selected_bin: Option<String>,
+    /// Whether only the library is checked, instead of the library and binaries.
+    pub selected_lib: bool,
+    /// The features which the checked targets require, which are enabled for the check, since
+    /// cargo otherwise skips, or refuses, these targets.
+    pub required_features: Vec<String>,
+}
+
+impl Targets {
+    /// The arguments which select these targets, like `-p crate --lib --bins --examples`.
+    pub fn into_args(self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        for package in self.packages {
+            args.extend_from_slice(&["--package".to_string(), package]);
+        }
+
+        if !self.required_features.is_empty() {
+            args.extend_from_slice(&["--features".to_string(), self.required_features.join(",")]);
+        }
+
+        if self.selected_lib {
+            args.push("--lib".to_string());
+        }
+
+        if let Some(bin) = self.selected_bin.as_ref() {
+            args.extend_from_slice(&["--bin".to_string(), bin.to_string()]);
+        }
+
+        let selected = self.selected_lib || self.selected_bin.is_some();
+
+        // Selecting a target deselects the default targets, so these are selected as well
+        if (self.examples || self.benches) && !selected {
+            if self.library {
+                args.push("--lib".to_string());
+            }
+
+            args.push("--bins".to_string());
+        }
+
+        if self.examples {
+            args.push("--examples".to_string());
+        }
+
+        if self.benches {
+            args.push("--benches".to_string());
+        }
+
+        args
+    }
 }
 
 impl CargoCommand {
@@ -31,6 +101,12 @@ impl CargoCommand {
         self
     }
 
+    /// Set the packages and targets to check, like `cargo <cmd> --lib --bins --examples`
+    pub fn targets(mut self, targets: Targets) -> Self {
+        self.targets = targets;
+        self
+    }
+
     /// Intended to be used in conjunction with [`RunCommand`] and/or [`RustupCommand`].
     ///
     /// [`RunCommand`]: crate::check::RunCommand
@@ -69,13 +145,42 @@ impl CargoCommand {
             args.push(target);
         }
 
+        args.extend(self.targets.into_args());
+
         args
     }
 }
 
+/// The name of the target which was compiled, if the line is a `compiler-artifact` message of
+/// cargo. Build scripts are left out, since these are compiled in addition to their package.
+pub fn parse_compiled_unit(line: &str) -> Option<String> {
+    #[derive(serde::Deserialize)]
+    struct Message {
+        reason: String,
+        target: Option<Target>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Target {
+        name: String,
+        kind: Vec<String>,
+    }
+
+    if !line.starts_with('{') {
+        return None;
+    }
+
+    let message = serde_json::from_str::<Message>(line).ok()?;
+    let target = message.target?;
+
+    let is_build_script = target.kind.iter().any(|kind| kind == "custom-build");
+
+    (message.reason == "compiler-artifact" && !is_build_script).then_some(target.name)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::external_command::cargo_command::CargoCommand;
+    use crate::external_command::cargo_command::{parse_compiled_unit, CargoCommand, Targets};
 
     #[test]
     fn set_features_none() {
@@ -163,4 +268,33 @@ mod tests {
         assert!(cmd.contains("--no-default-features"));
         assert!(cmd.contains("--target pickme"));
     }
+
+    #[yare::parameterized(
+        default = { Targets::default(), "cargo check" },
+        examples = { Targets { examples: true, benches: false, library: true, packages: vec![], ..Targets::default() }, "cargo check --lib --bins --examples" },
+        examples_without_library = { Targets { examples: true, benches: false, library: false, packages: vec![], ..Targets::default() }, "cargo check --bins --examples" },
+        benches = { Targets { examples: false, benches: true, library: true, packages: vec![], ..Targets::default() }, "cargo check --lib --bins --benches" },
+        example_crates = { Targets { examples: true, benches: false, library: true, packages: vec!["pika".into(), "chu".into()], ..Targets::default() }, "cargo check --package pika --package chu --lib --bins --examples" },
+        bin = { Targets { selected_bin: Some("cli".into()), ..Targets::default() }, "cargo check --bin cli" },
+        lib = { Targets { library: true, selected_lib: true, ..Targets::default() }, "cargo check --lib" },
+        required_features = { Targets { selected_bin: Some("cli".into()), required_features: vec!["cli".into(), "b/color".into()], ..Targets::default() }, "cargo check --features cli,b/color --bin cli" },
+        bin_with_examples = { Targets { examples: true, library: true, selected_bin: Some("cli".into()), ..Targets::default() }, "cargo check --bin cli --examples" },
+    )]
+    fn set_targets(targets: Targets, expected: &str) {
+        let cargo_command = CargoCommand::default().targets(targets);
+
+        assert_eq!(cargo_command.into_args().join(" "), expected);
+    }
+
+    #[yare::parameterized(
+        library = { r#"{"reason":"compiler-artifact","package_id":"serde 1.0.0","target":{"kind":["lib"],"crate_types":["lib"],"name":"serde"},"fresh":false}"#, Some("serde") },
+        binary = { r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"cargo-msrv"}}"#, Some("cargo-msrv") },
+        build_script = { r#"{"reason":"compiler-artifact","target":{"kind":["custom-build"],"name":"build-script-build"}}"#, None },
+        build_script_executed = { r#"{"reason":"build-script-executed","package_id":"serde 1.0.0"}"#, None },
+        build_finished = { r#"{"reason":"build-finished","success":true}"#, None },
+        not_json = { "    Checking serde v1.0.0", None },
+    )]
+    fn compiled_unit(line: &str, expected: Option<&str>) {
+        assert_eq!(parse_compiled_unit(line).as_deref(), expected);
+    }
 }
@@ -0,0 +1,247 @@
+use crate::external_command::cargo_command::Targets;
+use std::process::{Command, Stdio};
+
+/// A `cargo hack` invocation, which delegates the iteration over the feature sets of a crate
+/// to [cargo-hack](https://github.com/taiki-e/cargo-hack).
+///
+/// `cargo-hack` must be installed separately.
+#[derive(Debug, Default)]
+pub struct CargoHackCommand {
+    features: Option<Vec<String>>,
+    no_default_features: bool,
+    target: Option<String>,
+    targets: Targets,
+}
+
+impl CargoHackCommand {
+    /// Set the features to be forwarded as `cargo hack <cmd> --features`, which cargo-hack will
+    /// enable for every feature set it checks.
+    pub fn features(mut self, features: Option<Vec<String>>) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Set the `no default features` flag to be forwarded as `cargo hack <cmd> --no-default-features`
+    pub fn no_default_features(mut self, value: bool) -> Self {
+        self.no_default_features = value;
+        self
+    }
+
+    /// Set the target flag to be forwarded as `cargo hack <cmd> --target`
+    pub fn target(mut self, target: Option<impl ToString>) -> Self {
+        self.target = target.map(|t| t.to_string());
+        self
+    }
+
+    /// Set the packages and targets to be forwarded to `cargo hack <cmd>`, like `--examples`
+    pub fn targets(mut self, targets: Targets) -> Self {
+        self.targets = targets;
+        self
+    }
+
+    /// Intended to be used in conjunction with [`RunCommand`].
+    ///
+    /// [`RunCommand`]: crate::check::RunCommand
+    pub fn into_args(self) -> Vec<String> {
+        let mut args = Vec::<String>::with_capacity(10);
+
+        // `--keep-going` ensures we get a result for each feature set, instead of only up to
+        // the first incompatible feature set.
+        args.extend_from_slice(&[
+            "cargo".to_string(),
+            "hack".to_string(),
+            "check".to_string(),
+            "--each-feature".to_string(),
+            "--keep-going".to_string(),
+        ]);
+
+        if let Some(features) = self.features {
+            let features = features.join(",");
+
+            args.extend_from_slice(&["--features".to_string(), features]);
+        }
+
+        if self.no_default_features {
+            args.push("--no-default-features".to_string());
+        }
+
+        if let Some(target) = self.target {
+            args.push("--target".to_string());
+            args.push(target);
+        }
+
+        args.extend(self.targets.into_args());
+
+        args
+    }
+}
+
+/// Whether `cargo hack` can be invoked.
+pub fn is_installed() -> bool {
+    Command::new("cargo")
+        .args(["hack", "--version"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// The outcome of a single cargo invocation ran by cargo-hack, for one feature set.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeatureSetRun {
+    /// The package for which the feature set was checked, if cargo-hack reported it.
+    pub package: Option<String>,
+    /// The feature related cargo flags, e.g. `--no-default-features --features a`.
+    pub feature_flags: String,
+    /// Whether the check succeeded for this feature set.
+    pub success: bool,
+}
+
+const RUNNING_PREFIX: &str = "info: running ";
+const FAILED_RUN_MARKER: &str = "error: process didn't exit successfully";
+
+/// Splits the (stderr) output of `cargo hack --each-feature` into its individual cargo
+/// invocations.
+///
+/// Each invocation is announced by cargo-hack with a line like:
+/// ``info: running `cargo check --no-default-features --features a` on my_crate (3/4)``,
+/// and each failed invocation is followed by a line reporting that the process didn't exit
+/// successfully.
+pub fn parse_feature_set_runs(output: &str) -> Vec<FeatureSetRun> {
+    let mut runs: Vec<FeatureSetRun> = Vec::new();
+
+    for line in output.lines() {
+        if let Some(announcement) = line.strip_prefix(RUNNING_PREFIX) {
+            if let Some(run) = parse_announcement(announcement) {
+                runs.push(run);
+            }
+        } else if line.starts_with(FAILED_RUN_MARKER) {
+            if let Some(run) = runs.last_mut() {
+                run.success = false;
+            }
+        }
+    }
+
+    runs
+}
+
+fn parse_announcement(announcement: &str) -> Option<FeatureSetRun> {
+    let announcement = announcement.strip_prefix('`')?;
+    let (command, remainder) = announcement.split_once('`')?;
+
+    // NB: in verbose mode, cargo-hack does not print the package name
+    let package = remainder
+        .trim_start()
+        .strip_prefix("on ")
+        .and_then(|rest| rest.split_whitespace().next())
+        .map(String::from);
+
+    let feature_flags = command
+        .split_whitespace()
+        .skip_while(|arg| !arg.starts_with("--"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(FeatureSetRun {
+        package,
+        feature_flags,
+        success: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = r#"info: running `cargo check --all-features` on hackt (1/3)
+    Checking hackt v0.1.0 (/tmp/hackt)
+error: b is broken
+error: could not compile `hackt` (lib) due to 1 previous error
+error: process didn't exit successfully: `/bin/cargo check --manifest-path Cargo.toml --all-features` (exit status: 101)
+
+info: running `cargo check --no-default-features` on hackt (2/3)
+    Checking hackt v0.1.0 (/tmp/hackt)
+    Finished `dev` profile [unoptimized + debuginfo] target(s) in 0.03s
+
+info: running `cargo check --no-default-features --features b` on hackt (3/3)
+    Checking hackt v0.1.0 (/tmp/hackt)
+error: process didn't exit successfully: `/bin/cargo check --manifest-path Cargo.toml --no-default-features --features b` (exit status: 101)
+
+error: failed to run 2 commands
+
+failed commands:
+    hackt:
+        `/bin/cargo check --manifest-path Cargo.toml --all-features`
+        `/bin/cargo check --manifest-path Cargo.toml --no-default-features --features b`
+"#;
+
+    #[test]
+    fn default_args() {
+        let cmd = CargoHackCommand::default();
+        assert_eq!(
+            cmd.into_args().join(" "),
+            "cargo hack check --each-feature --keep-going"
+        );
+    }
+
+    #[test]
+    fn all_args() {
+        let cmd = CargoHackCommand::default()
+            .features(Some(vec!["pika".to_string(), "chu".to_string()]))
+            .no_default_features(true)
+            .target(Some("pickme"));
+
+        assert_eq!(
+            cmd.into_args().join(" "),
+            "cargo hack check --each-feature --keep-going --features pika,chu --no-default-features --target pickme"
+        );
+    }
+
+    #[test]
+    fn parse_runs() {
+        let runs = parse_feature_set_runs(OUTPUT);
+
+        assert_eq!(
+            runs,
+            vec![
+                FeatureSetRun {
+                    package: Some("hackt".to_string()),
+                    feature_flags: "--all-features".to_string(),
+                    success: false,
+                },
+                FeatureSetRun {
+                    package: Some("hackt".to_string()),
+                    feature_flags: "--no-default-features".to_string(),
+                    success: true,
+                },
+                FeatureSetRun {
+                    package: Some("hackt".to_string()),
+                    feature_flags: "--no-default-features --features b".to_string(),
+                    success: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_verbose_run_without_package() {
+        let runs = parse_feature_set_runs("info: running `cargo check --features a` (1/1)");
+
+        assert_eq!(
+            runs,
+            vec![FeatureSetRun {
+                package: None,
+                feature_flags: "--features a".to_string(),
+                success: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_unrelated_output() {
+        let runs = parse_feature_set_runs("error: could not compile `x`");
+
+        assert!(runs.is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+//! The cgroups (v2) of the checks with resource limits, on Linux.
+//!
+//! A cgroup of which the controllers are enabled for its children can't hold processes itself, so
+//! cargo-msrv first moves itself to a cgroup of its own, within the cgroup in which it was
+//! started, and then enables the memory and cpu controllers of the latter. Each check with limits
+//! runs in a cgroup next to the one of cargo-msrv. This requires that the cgroup in which
+//! cargo-msrv was started is delegated to the user, and holds no other processes, e.g. when
+//! started with `systemd-run --user --scope -p Delegate=yes`.
+
+use crate::resource_limits::ResourceLimits;
+use once_cell::sync::OnceCell;
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// The period of the CPU bandwidth limit, in microseconds.
+const CPU_PERIOD: u64 = 100_000;
+
+/// The cgroup in which cargo-msrv was started, which holds the cgroups of the checks.
+static PARENT: OnceCell<PathBuf> = OnceCell::new();
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Move cargo-msrv to a cgroup of its own, and enable the controllers of the cgroup in which it
+/// was started, once. Returns the latter.
+pub fn prepare() -> io::Result<&'static Path> {
+    PARENT
+        .get_or_try_init(|| {
+            let mountinfo = fs::read_to_string("/proc/self/mountinfo")?;
+            let mount = cgroup2_mount(&mountinfo)
+                .ok_or_else(|| error("no cgroup v2 hierarchy is mounted"))?;
+
+            let membership = fs::read_to_string("/proc/self/cgroup")?;
+            let cgroup = own_cgroup(&membership)
+                .ok_or_else(|| error("cargo-msrv is not in a cgroup of the v2 hierarchy"))?;
+
+            let parent = Path::new(mount).join(cgroup.trim_start_matches('/'));
+            let own = parent.join("cargo-msrv");
+
+            create_dir(&own)?;
+            write(&own.join("cgroup.procs"), "0")?;
+            write(&parent.join("cgroup.subtree_control"), "+memory +cpu")?;
+
+            Ok(parent)
+        })
+        .map(PathBuf::as_path)
+}
+
+/// The cgroup of a check, which is removed when dropped.
+pub struct Cgroup {
+    dir: PathBuf,
+}
+
+impl Cgroup {
+    /// Create a cgroup with the given limits, which the command joins when it is spawned, before
+    /// it runs, so the processes it spawns are in the cgroup as well.
+    pub fn create(command: &mut Command, limits: &ResourceLimits) -> io::Result<Self> {
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        let dir = prepare()?.join(format!("check-{}", id));
+
+        create_dir(&dir)?;
+        let cgroup = Self { dir };
+
+        if let Some(memory) = limits.memory {
+            write(&cgroup.dir.join("memory.max"), &memory.to_string())?;
+            // Swapping would only postpone exceeding the limit, at the cost of a crawling check
+            let _ = fs::write(cgroup.dir.join("memory.swap.max"), "0");
+        }
+
+        if let Some(cpus) = limits.cpus {
+            write(&cgroup.dir.join("cpu.max"), &cpu_max(cpus))?;
+        }
+
+        let procs = CString::new(cgroup.dir.join("cgroup.procs").as_os_str().as_bytes())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+        // SAFETY: only async-signal-safe functions are called between fork and exec
+        unsafe {
+            command.pre_exec(move || join(&procs));
+        }
+
+        Ok(cgroup)
+    }
+
+    /// Kill every process of the cgroup, including those which left the process group.
+    pub fn kill(&self) {
+        // Not supported before Linux 5.14, where the process group is still killed
+        let _ = fs::write(self.dir.join("cgroup.kill"), "1");
+    }
+
+    /// Whether a process of the cgroup was killed, since the cgroup exceeded its memory limit.
+    pub fn exceeded_memory_limit(&self) -> bool {
+        fs::read_to_string(self.dir.join("memory.events"))
+            .ok()
+            .and_then(|events| oom_kills(&events))
+            .is_some_and(|kills| kills > 0)
+    }
+}
+
+impl Drop for Cgroup {
+    fn drop(&mut self) {
+        // The killed processes may take a moment to leave the cgroup
+        for _ in 0..20 {
+            match fs::remove_dir(&self.dir) {
+                Err(error) if error.raw_os_error() == Some(libc::EBUSY) => {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(error) => {
+                    info!(%error, dir = %self.dir.display(), "unable to remove the cgroup");
+                    return;
+                }
+                Ok(()) => return,
+            }
+        }
+    }
+}
+
+/// Move the calling process to the cgroup, of which the `cgroup.procs` file is given.
+fn join(procs: &CStr) -> io::Result<()> {
+    // SAFETY: the path is a valid C string, and the written buffer outlives the call
+    unsafe {
+        let fd = libc::open(procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let written = libc::write(fd, b"0".as_ptr().cast(), 1);
+        let error = io::Error::last_os_error();
+        libc::close(fd);
+
+        if written != 1 {
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+fn create_dir(dir: &Path) -> io::Result<()> {
+    match fs::create_dir(dir) {
+        Err(error) if error.kind() != io::ErrorKind::AlreadyExists => Err(with_path(error, dir)),
+        _ => Ok(()),
+    }
+}
+
+fn write(file: &Path, contents: &str) -> io::Result<()> {
+    fs::write(file, contents).map_err(|error| with_path(error, file))
+}
+
+fn with_path(error: io::Error, path: &Path) -> io::Error {
+    io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+}
+
+fn error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, message)
+}
+
+/// The mount point of the cgroup v2 hierarchy, from `/proc/self/mountinfo`.
+fn cgroup2_mount(mountinfo: &str) -> Option<&str> {
+    mountinfo.lines().find_map(|line| {
+        let (mount, filesystem) = line.split_once(" - ")?;
+        let fs_type = filesystem.split_whitespace().next()?;
+
+        (fs_type == "cgroup2")
+            .then(|| mount.split_whitespace().nth(4))
+            .flatten()
+    })
+}
+
+/// The cgroup of the v2 hierarchy, from `/proc/self/cgroup`.
+fn own_cgroup(membership: &str) -> Option<&str> {
+    membership.lines().find_map(|line| line.strip_prefix("0::"))
+}
+
+/// The number of processes which were killed since the cgroup exceeded its memory limit, from
+/// its `memory.events`.
+fn oom_kills(events: &str) -> Option<u64> {
+    events.lines().find_map(|line| {
+        line.strip_prefix("oom_kill ")
+            .and_then(|kills| kills.trim().parse().ok())
+    })
+}
+
+/// The `cpu.max` of the number of CPUs: the quota of CPU time per period.
+fn cpu_max(cpus: f64) -> String {
+    let quota = ((cpus * CPU_PERIOD as f64) as u64).max(1_000);
+    format!("{} {}", quota, CPU_PERIOD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        unified = { "35 24 0:30 / /sys/fs/cgroup rw,nosuid,nodev,noexec,relatime shared:9 - cgroup2 cgroup2 rw,nsdelegate", Some("/sys/fs/cgroup") },
+        hybrid = { "30 24 0:26 / /sys/fs/cgroup/memory rw,relatime shared:11 - cgroup cgroup rw,memory\n31 24 0:27 / /sys/fs/cgroup/unified rw,relatime shared:12 - cgroup2 cgroup2 rw", Some("/sys/fs/cgroup/unified") },
+        legacy = { "30 24 0:26 / /sys/fs/cgroup/memory rw,relatime shared:11 - cgroup cgroup rw,memory", None },
+    )]
+    fn mount_of_hierarchy(mountinfo: &str, expected: Option<&str>) {
+        assert_eq!(cgroup2_mount(mountinfo), expected);
+    }
+
+    #[yare::parameterized(
+        unified = { "0::/user.slice/user-1000.slice/session-2.scope\n", Some("/user.slice/user-1000.slice/session-2.scope") },
+        hybrid = { "4:memory:/ci\n0::/ci\n", Some("/ci") },
+        legacy = { "4:memory:/ci\n", None },
+    )]
+    fn cgroup_of_process(membership: &str, expected: Option<&str>) {
+        assert_eq!(own_cgroup(membership), expected);
+    }
+
+    #[yare::parameterized(
+        killed = { "low 0\nhigh 0\nmax 12\noom 1\noom_kill 1\n", Some(1) },
+        not_killed = { "low 0\nhigh 0\nmax 0\noom 0\noom_kill 0\n", Some(0) },
+        missing = { "low 0\n", None },
+    )]
+    fn oom_kills_of_events(events: &str, expected: Option<u64>) {
+        assert_eq!(oom_kills(events), expected);
+    }
+
+    #[yare::parameterized(
+        one = { 1.0, "100000 100000" },
+        several = { 2.5, "250000 100000" },
+        tiny = { 0.001, "1000 100000" },
+    )]
+    fn cpu_max_of_cpus(cpus: f64, expected: &str) {
+        assert_eq!(cpu_max(cpus), expected);
+    }
+}
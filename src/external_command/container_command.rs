@@ -0,0 +1,219 @@
+use camino::Utf8Path;
+use clap::ValueEnum;
+use std::fmt;
+use std::process::{Command, Stdio};
+
+/// The directory at which the crate is mounted in the container.
+const WORKSPACE_DIR: &str = "/workspace";
+
+/// When running as the invoking user, `CARGO_HOME` of the image is usually not writable, so a
+/// directory within the target directory of the crate is used instead.
+const ROOTLESS_CARGO_HOME: &str = "/workspace/target/cargo-msrv-container/cargo-home";
+
+/// The container engines which can be used to run compatibility checks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerEngine {
+    Docker,
+    Podman,
+}
+
+impl ContainerEngine {
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+        }
+    }
+
+    /// Find an installed container engine, preferring podman, which doesn't require a daemon.
+    pub fn detect() -> Option<Self> {
+        [Self::Podman, Self::Docker]
+            .into_iter()
+            .find(|engine| engine.is_installed())
+    }
+
+    /// Whether this engine can be invoked.
+    pub fn is_installed(&self) -> bool {
+        Command::new(self.program())
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+}
+
+impl fmt::Display for ContainerEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.program())
+    }
+}
+
+/// The user and group id of the invoking user, used to run rootless containers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct User {
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl User {
+    /// Determine the invoking user with `id`.
+    pub fn current() -> Option<Self> {
+        let id = |flag: &str| {
+            let output = Command::new("id").arg(flag).output().ok()?;
+            String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+        };
+
+        Some(Self {
+            uid: id("-u")?,
+            gid: id("-g")?,
+        })
+    }
+}
+
+/// Builds the `<engine> run` invocation of a check command.
+#[derive(Debug)]
+pub struct ContainerRunCommand<'a> {
+    engine: ContainerEngine,
+    image: &'a str,
+    crate_root: &'a Utf8Path,
+    user: Option<User>,
+    envs: Vec<(&'static str, String)>,
+}
+
+impl<'a> ContainerRunCommand<'a> {
+    pub fn new(engine: ContainerEngine, image: &'a str, crate_root: &'a Utf8Path) -> Self {
+        Self {
+            engine,
+            image,
+            crate_root,
+            user: None,
+            envs: Vec::new(),
+        }
+    }
+
+    /// Run the check command as the given user, so files written to the mounted crate aren't
+    /// owned by root.
+    pub fn user(mut self, user: Option<User>) -> Self {
+        self.user = user;
+        self
+    }
+
+    /// Set the given environment variables in the container.
+    pub fn envs(mut self, envs: Vec<(&'static str, String)>) -> Self {
+        self.envs = envs;
+        self
+    }
+
+    /// The arguments to the container engine, including the check command.
+    pub fn into_args(self, check_command: &[String]) -> Vec<String> {
+        let mut args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "--volume".to_string(),
+            format!("{}:{}", self.crate_root, WORKSPACE_DIR),
+            "--workdir".to_string(),
+            WORKSPACE_DIR.to_string(),
+        ];
+
+        if let Some(user) = self.user {
+            // Rootless podman maps the invoking user to root in the container by default
+            if self.engine == ContainerEngine::Podman {
+                args.push("--userns=keep-id".to_string());
+            }
+
+            args.extend([
+                "--user".to_string(),
+                format!("{}:{}", user.uid, user.gid),
+                "--env".to_string(),
+                format!("CARGO_HOME={}", ROOTLESS_CARGO_HOME),
+            ]);
+        }
+
+        // After the rootless `CARGO_HOME`, so the cargo home of `--vendor` takes precedence
+        for (name, value) in self.envs {
+            args.extend(["--env".to_string(), format!("{}={}", name, value)]);
+        }
+
+        args.push(self.image.to_string());
+        args.extend(check_command.iter().cloned());
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_args() {
+        let args = ContainerRunCommand::new(
+            ContainerEngine::Docker,
+            "rust:1.56.0",
+            Utf8Path::new("/home/user/crate"),
+        )
+        .into_args(&["cargo".to_string(), "check".to_string()]);
+
+        assert_eq!(
+            args,
+            vec![
+                "run",
+                "--rm",
+                "--volume",
+                "/home/user/crate:/workspace",
+                "--workdir",
+                "/workspace",
+                "rust:1.56.0",
+                "cargo",
+                "check"
+            ]
+        );
+    }
+
+    #[test]
+    fn env_args() {
+        let args = ContainerRunCommand::new(
+            ContainerEngine::Docker,
+            "rust:1.56.0",
+            Utf8Path::new("/crate"),
+        )
+        .envs(vec![(
+            "CARGO_REGISTRIES_CRATES_IO_PROTOCOL",
+            "git".to_string(),
+        )])
+        .into_args(&["cargo".to_string(), "check".to_string()]);
+
+        assert!(args.ends_with(&[
+            "--env".to_string(),
+            "CARGO_REGISTRIES_CRATES_IO_PROTOCOL=git".to_string(),
+            "rust:1.56.0".to_string(),
+            "cargo".to_string(),
+            "check".to_string()
+        ]));
+    }
+
+    #[yare::parameterized(
+        docker = { ContainerEngine::Docker, false },
+        podman = { ContainerEngine::Podman, true },
+    )]
+    fn rootless_run_args(engine: ContainerEngine, keep_id: bool) {
+        let args = ContainerRunCommand::new(engine, "rust:1.56.0", Utf8Path::new("/crate"))
+            .user(Some(User {
+                uid: 1000,
+                gid: 100,
+            }))
+            .into_args(&["cargo".to_string(), "check".to_string()]);
+
+        let user = args.iter().position(|arg| arg == "--user").unwrap();
+        assert_eq!(args[user + 1], "1000:100");
+        assert!(args.contains(&format!("CARGO_HOME={}", ROOTLESS_CARGO_HOME)));
+        assert_eq!(args.contains(&"--userns=keep-id".to_string()), keep_id);
+        assert!(args.ends_with(&[
+            "rust:1.56.0".to_string(),
+            "cargo".to_string(),
+            "check".to_string()
+        ]));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::ffi::OsStr;
+use std::process::Command;
+
+/// The separator of the fields of a commit in the output of `git log`, the unit separator, which a
+/// commit summary can't contain.
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+const LOG_FORMAT: &str = "--format=%H%x1f%h%x1f%s";
+
+/// A commit, as listed by `git log`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Commit {
+    /// The full hash of the commit
+    pub id: String,
+    /// The abbreviated hash of the commit
+    pub short_id: String,
+    /// The first line of the commit message
+    pub summary: String,
+}
+
+/// Reads the history of the files of a git repository, with `git`.
+#[derive(Clone, Debug)]
+pub struct GitCommand<'a> {
+    dir: &'a Utf8Path,
+}
+
+impl<'a> GitCommand<'a> {
+    /// The commands are run in `dir`, which may be any directory of the repository.
+    pub fn new(dir: &'a Utf8Path) -> Self {
+        Self { dir }
+    }
+
+    /// The root directory of the working tree of the repository.
+    pub fn toplevel(&self) -> TResult<Utf8PathBuf> {
+        let output = self.run(["rev-parse", "--show-toplevel"])?;
+
+        Ok(Utf8PathBuf::from(output.trim_end()))
+    }
+
+    /// The commit of the given revision, like a branch, tag or abbreviated hash.
+    pub fn commit(&self, revision: &str) -> TResult<Commit> {
+        let revision = format!("{}^{{commit}}", revision);
+        let output = self.run([
+            "log",
+            "--no-walk",
+            LOG_FORMAT,
+            "--end-of-options",
+            &revision,
+        ])?;
+
+        parse_log(&output)
+            .into_iter()
+            .next()
+            .ok_or_else(|| CargoMSRVError::GitCommandFailed {
+                command: format!("git log --no-walk {}", revision),
+                stderr: "no such commit".to_string(),
+            })
+    }
+
+    /// The commits of the given revision range, like `HEAD` or `v1.0..HEAD`, which changed the
+    /// file at `path`, newest first, except for those which deleted it. Only the first parent of
+    /// a merge commit is followed, so the changes of a merged branch are attributed to the merge.
+    pub fn log(&self, revisions: &str, path: &Utf8Path) -> TResult<Vec<Commit>> {
+        let output = self.run([
+            "log",
+            "--first-parent",
+            "--diff-filter=AM",
+            LOG_FORMAT,
+            "--end-of-options",
+            revisions,
+            "--",
+            path.as_str(),
+        ])?;
+
+        Ok(parse_log(&output))
+    }
+
+    /// The contents of the file at `path` in the given commit. The path is relative to the root of
+    /// the working tree.
+    pub fn show(&self, commit: &str, path: &Utf8Path) -> TResult<String> {
+        // Git paths use forward slashes on every platform
+        let path = path.components().map(|c| c.as_str()).collect::<Vec<_>>();
+        let object = format!("{}:{}", commit, path.join("/"));
+
+        self.run(["show", &object])
+    }
+
+    fn run<I, S>(&self, args: I) -> TResult<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut command = Command::new("git");
+        command.current_dir(self.dir).args(args);
+
+        let output = command.output().map_err(|error| IoError {
+            error,
+            source: IoErrorSource::SpawnProcess(command.get_program().to_os_string()),
+        })?;
+
+        if !output.status.success() {
+            let args = command
+                .get_args()
+                .map(|arg| arg.to_string_lossy())
+                .collect::<Vec<_>>();
+
+            return Err(CargoMSRVError::GitCommandFailed {
+                command: format!("git {}", args.join(" ")),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+fn parse_log(output: &str) -> Vec<Commit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, FIELD_SEPARATOR);
+
+            Some(Commit {
+                id: fields.next()?.to_string(),
+                short_id: fields.next()?.to_string(),
+                summary: fields.next().unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_commits() {
+        let output = "4f2a\u{1f}4f2\u{1f}Bump serde to 1.0.200\n9c1b\u{1f}9c1\u{1f}\n";
+
+        assert_eq!(
+            parse_log(output),
+            vec![
+                Commit {
+                    id: "4f2a".to_string(),
+                    short_id: "4f2".to_string(),
+                    summary: "Bump serde to 1.0.200".to_string(),
+                },
+                Commit {
+                    id: "9c1b".to_string(),
+                    short_id: "9c1".to_string(),
+                    summary: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_empty_log() {
+        assert!(parse_log("").is_empty());
+    }
+}
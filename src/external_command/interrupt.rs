@@ -0,0 +1,26 @@
+//! Interrupt the external commands which are running, for example to skip the check which is
+//! running when the search is controlled by the user.
+//!
+//! Only commands which opted in, with [`RustupCommand::interruptible`], are interrupted. Each
+//! interruption starts a new epoch; a command which was started in an earlier epoch is killed.
+//!
+//! [`RustupCommand::interruptible`]: crate::external_command::rustup_command::RustupCommand::interruptible
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static EPOCH: AtomicU64 = AtomicU64::new(0);
+
+/// Interrupt the interruptible commands which are currently running.
+pub fn interrupt_running_commands() {
+    EPOCH.fetch_add(1, Ordering::SeqCst);
+}
+
+/// The current epoch, to be compared with [`is_interrupted`] later.
+pub fn epoch() -> u64 {
+    EPOCH.load(Ordering::SeqCst)
+}
+
+/// Whether the commands started in the given epoch were interrupted since.
+pub fn is_interrupted(started_in: u64) -> bool {
+    epoch() != started_in
+}
@@ -1,2 +1,11 @@
 pub mod cargo_command;
+pub mod cargo_hack_command;
+#[cfg(target_os = "linux")]
+pub mod cgroup;
+pub mod container_command;
+pub mod git_command;
+pub mod interrupt;
+pub mod nix_command;
+pub mod process_tree;
 pub mod rustup_command;
+pub mod ssh_command;
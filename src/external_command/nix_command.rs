@@ -0,0 +1,90 @@
+/// The Nix expression or flake installable which provides a Rust toolchain.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NixToolchainSource {
+    /// A Nix expression, evaluated with `nix shell --impure --expr`.
+    Expression(String),
+    /// A flake installable, like `github:owner/repo#output`.
+    Installable(String),
+}
+
+/// Builds the `nix shell` invocation of a check command.
+#[derive(Debug)]
+pub struct NixShellCommand<'a> {
+    source: &'a NixToolchainSource,
+}
+
+impl<'a> NixShellCommand<'a> {
+    pub fn new(source: &'a NixToolchainSource) -> Self {
+        Self { source }
+    }
+
+    /// The arguments to `nix`, including the check command.
+    pub fn into_args(self, check_command: &[String]) -> Vec<String> {
+        let mut args = vec![
+            "--extra-experimental-features".to_string(),
+            "nix-command flakes".to_string(),
+            "shell".to_string(),
+        ];
+
+        match self.source {
+            NixToolchainSource::Expression(expression) => {
+                args.extend([
+                    "--impure".to_string(),
+                    "--expr".to_string(),
+                    expression.clone(),
+                ]);
+            }
+            NixToolchainSource::Installable(installable) => {
+                args.push(installable.clone());
+            }
+        }
+
+        args.push("--command".to_string());
+        args.extend(check_command.iter().cloned());
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_args_expression() {
+        let source = NixToolchainSource::Expression("(import <nixpkgs> {}).rustc".to_string());
+        let args =
+            NixShellCommand::new(&source).into_args(&["cargo".to_string(), "check".to_string()]);
+
+        assert_eq!(
+            args,
+            vec![
+                "--extra-experimental-features",
+                "nix-command flakes",
+                "shell",
+                "--impure",
+                "--expr",
+                "(import <nixpkgs> {}).rustc",
+                "--command",
+                "cargo",
+                "check"
+            ]
+        );
+    }
+
+    #[test]
+    fn shell_args_installable() {
+        let source = NixToolchainSource::Installable("github:owner/repo#rust-1_56_0".to_string());
+        let args =
+            NixShellCommand::new(&source).into_args(&["cargo".to_string(), "check".to_string()]);
+
+        assert_eq!(
+            args[3..],
+            [
+                "github:owner/repo#rust-1_56_0",
+                "--command",
+                "cargo",
+                "check"
+            ]
+        );
+    }
+}
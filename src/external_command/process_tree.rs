@@ -0,0 +1,435 @@
+//! Kill the processes spawned by cargo-msrv together with the processes they spawn in turn, so a
+//! check which is stopped doesn't leave its compilers running, e.g. the `rustc` processes of a
+//! killed `cargo`.
+//!
+//! On Unix, each process is spawned in a process group of its own, and on Windows, each process
+//! is assigned to a Job Object of its own. A [`ProcessTree`] is killed when it is dropped, for
+//! example when a check returns early, or panics, and every process tree which is running is
+//! registered, so they can all be killed at once with [`kill_all`], for example at the deadline
+//! of `--max-duration`, or on Ctrl-C.
+//!
+//! A process tree may be spawned with [`ResourceLimits`], see [`resource_limits`].
+//!
+//! [`resource_limits`]: crate::resource_limits
+
+#[cfg(target_os = "linux")]
+use crate::external_command::cgroup::Cgroup;
+use crate::resource_limits::ResourceLimits;
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+use std::process::{Child, ChildStderr, ChildStdout, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+/// The process trees which are running, by their id.
+static REGISTRY: Mutex<BTreeMap<u64, sys::Group>> = Mutex::new(BTreeMap::new());
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+fn registry() -> MutexGuard<'static, BTreeMap<u64, sys::Group>> {
+    // A panic while the registry was locked doesn't leave it in an inconsistent state
+    REGISTRY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Kill every process tree which is running.
+pub fn kill_all() {
+    for group in registry().values() {
+        if let Err(error) = group.kill() {
+            info!(%error, "unable to kill a process tree");
+        }
+    }
+}
+
+/// Run the command to completion as a [`ProcessTree`], collecting its output, like
+/// [`Command::output`]. Stdin is closed, and the output streams are collected only if they
+/// were configured to be piped.
+pub fn output(command: &mut Command) -> io::Result<Output> {
+    ProcessTree::spawn(command.stdin(Stdio::null()))?.wait_with_output()
+}
+
+/// Like [`output`], for a process tree with the given limits.
+pub fn limited_output(command: &mut Command, limits: &ResourceLimits) -> io::Result<LimitedOutput> {
+    let mut tree = ProcessTree::spawn_limited(command.stdin(Stdio::null()), limits)?;
+    let output = tree.wait_with_output()?;
+
+    Ok(LimitedOutput {
+        output,
+        exceeded_memory_limit: tree.exceeded_memory_limit(),
+    })
+}
+
+pub struct LimitedOutput {
+    pub output: Output,
+    /// Whether the process tree exceeded its memory limit, see
+    /// [`ProcessTree::exceeded_memory_limit`].
+    pub exceeded_memory_limit: bool,
+}
+
+/// A spawned child process, and the processes it spawns in turn.
+pub struct ProcessTree {
+    id: u64,
+    child: Child,
+    /// The group of the processes of the tree; if the child can't be assigned to a group of its
+    /// own, only the child itself is killed.
+    group: Option<sys::Group>,
+    #[cfg(windows)]
+    limits: ResourceLimits,
+    #[cfg(target_os = "linux")]
+    cgroup: Option<Cgroup>,
+}
+
+impl ProcessTree {
+    pub fn spawn(command: &mut Command) -> io::Result<Self> {
+        Self::spawn_limited(command, &ResourceLimits::default())
+    }
+
+    pub fn spawn_limited(command: &mut Command, limits: &ResourceLimits) -> io::Result<Self> {
+        sys::configure(command);
+
+        #[cfg(target_os = "linux")]
+        let cgroup = (!limits.is_unlimited())
+            .then(|| Cgroup::create(command, limits))
+            .transpose()?;
+
+        #[cfg(not(any(target_os = "linux", windows)))]
+        if !limits.is_unlimited() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "resource limits are only supported on Linux and Windows",
+            ));
+        }
+
+        let mut child = command.spawn()?;
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+
+        let group = match sys::Group::new(&child, limits) {
+            Ok(group) => {
+                registry().insert(id, group);
+                Some(group)
+            }
+            // The limits of a Windows process tree are those of its Job Object
+            Err(error) if cfg!(windows) && !limits.is_unlimited() => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(error);
+            }
+            Err(error) => {
+                info!(%error, "unable to track the processes spawned by the child");
+                None
+            }
+        };
+
+        Ok(Self {
+            id,
+            child,
+            group,
+            #[cfg(windows)]
+            limits: *limits,
+            #[cfg(target_os = "linux")]
+            cgroup,
+        })
+    }
+
+    pub fn take_stdout(&mut self) -> Option<ChildStdout> {
+        self.child.stdout.take()
+    }
+
+    pub fn take_stderr(&mut self) -> Option<ChildStderr> {
+        self.child.stderr.take()
+    }
+
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+
+    pub fn wait(&mut self) -> io::Result<ExitStatus> {
+        self.child.wait()
+    }
+
+    /// Kill every process of the tree which is still running.
+    pub fn kill(&mut self) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        if let Some(cgroup) = &self.cgroup {
+            cgroup.kill();
+        }
+
+        match self.group {
+            Some(group) => group.kill(),
+            None => self.child.kill(),
+        }
+    }
+
+    /// Whether the process tree exceeded its memory limit, once it exited. On Linux, this is
+    /// whether a process was killed for it. On Windows, where an allocation which would exceed
+    /// the limit fails instead, this is whether the processes used at least 90% of the limit.
+    pub fn exceeded_memory_limit(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.cgroup
+                .as_ref()
+                .is_some_and(Cgroup::exceeded_memory_limit)
+        }
+
+        #[cfg(windows)]
+        {
+            match (self.limits.memory, self.group) {
+                (Some(limit), Some(group)) => group
+                    .peak_memory()
+                    .is_some_and(|peak| peak >= limit / 10 * 9),
+                _ => false,
+            }
+        }
+
+        #[cfg(not(any(target_os = "linux", windows)))]
+        {
+            false
+        }
+    }
+
+    /// Wait for the child to exit, collecting the output streams which were piped.
+    pub fn wait_with_output(&mut self) -> io::Result<Output> {
+        let stdout = self.take_stdout().map(read_to_end);
+        let stderr = self.take_stderr().map(read_to_end);
+
+        let status = self.wait()?;
+
+        // The processes which the child left behind would keep the pipes open
+        let _ = self.kill();
+
+        let collect = |pipe: Option<thread::JoinHandle<io::Result<Vec<u8>>>>| match pipe {
+            Some(handle) => handle.join().unwrap_or_else(|_| Ok(Vec::new())),
+            None => Ok(Vec::new()),
+        };
+
+        Ok(Output {
+            status,
+            stdout: collect(stdout)?,
+            stderr: collect(stderr)?,
+        })
+    }
+}
+
+impl Drop for ProcessTree {
+    fn drop(&mut self) {
+        let group = registry().remove(&self.id);
+
+        let _ = self.kill();
+        let _ = self.child.wait();
+
+        if let Some(group) = group {
+            group.close();
+        }
+    }
+}
+
+fn read_to_end(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        pipe.read_to_end(&mut buffer).map(|_| buffer)
+    })
+}
+
+#[cfg(unix)]
+mod sys {
+    use crate::resource_limits::ResourceLimits;
+    use std::io;
+    use std::os::unix::process::CommandExt;
+    use std::process::{Child, Command};
+
+    pub fn configure(command: &mut Command) {
+        command.process_group(0);
+    }
+
+    /// The process group of the child, of which the id is the id of the child.
+    #[derive(Clone, Copy)]
+    pub struct Group(libc::pid_t);
+
+    impl Group {
+        pub fn new(child: &Child, _limits: &ResourceLimits) -> io::Result<Self> {
+            libc::pid_t::try_from(child.id())
+                .map(Self)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+        }
+
+        pub fn kill(self) -> io::Result<()> {
+            // SAFETY: `kill` only sends a signal, and has no memory safety requirements
+            if unsafe { libc::kill(-self.0, libc::SIGKILL) } == 0 {
+                return Ok(());
+            }
+
+            match io::Error::last_os_error() {
+                // No process of the group is left
+                error if error.raw_os_error() == Some(libc::ESRCH) => Ok(()),
+                error => Err(error),
+            }
+        }
+
+        pub fn close(self) {}
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use crate::resource_limits::ResourceLimits;
+    use std::os::windows::io::AsRawHandle;
+    use std::process::{Child, Command};
+    use std::{io, mem, ptr, thread};
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectCpuRateControlInformation,
+        JobObjectExtendedLimitInformation, QueryInformationJobObject, SetInformationJobObject,
+        TerminateJobObject, JOBOBJECT_CPU_RATE_CONTROL_INFORMATION,
+        JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOB_OBJECT_CPU_RATE_CONTROL_ENABLE,
+        JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP, JOB_OBJECT_LIMIT_JOB_MEMORY,
+        JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+    };
+
+    pub fn configure(_command: &mut Command) {}
+
+    /// The Job Object of the child, which the processes it spawns are assigned to as well.
+    #[derive(Clone, Copy)]
+    pub struct Group(HANDLE);
+
+    impl Group {
+        pub fn new(child: &Child, resource_limits: &ResourceLimits) -> io::Result<Self> {
+            // SAFETY: the job is created without security attributes or a name, the information
+            // is passed with its size, and the process handle is owned by the child, which
+            // outlives this call
+            unsafe {
+                let job = CreateJobObjectW(ptr::null(), ptr::null());
+                if job == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+
+                let group = Self(job);
+
+                // The processes of the job are killed when the job is closed, even if cargo-msrv
+                // itself exits without closing it
+                let mut limits: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = mem::zeroed();
+                limits.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+
+                if let Some(memory) = resource_limits.memory {
+                    limits.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_JOB_MEMORY;
+                    limits.JobMemoryLimit = usize::try_from(memory).unwrap_or(usize::MAX);
+                }
+
+                let mut assigned = SetInformationJobObject(
+                    job,
+                    JobObjectExtendedLimitInformation,
+                    ptr::addr_of!(limits).cast(),
+                    mem::size_of_val(&limits) as u32,
+                ) != 0;
+
+                if let (true, Some(cpus)) = (assigned, resource_limits.cpus) {
+                    let mut rate: JOBOBJECT_CPU_RATE_CONTROL_INFORMATION = mem::zeroed();
+                    rate.ControlFlags =
+                        JOB_OBJECT_CPU_RATE_CONTROL_ENABLE | JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP;
+                    rate.Anonymous.CpuRate = cpu_rate(cpus);
+
+                    assigned = SetInformationJobObject(
+                        job,
+                        JobObjectCpuRateControlInformation,
+                        ptr::addr_of!(rate).cast(),
+                        mem::size_of_val(&rate) as u32,
+                    ) != 0;
+                }
+
+                assigned =
+                    assigned && AssignProcessToJobObject(job, child.as_raw_handle() as HANDLE) != 0;
+
+                if !assigned {
+                    let error = io::Error::last_os_error();
+                    group.close();
+                    return Err(error);
+                }
+
+                Ok(group)
+            }
+        }
+
+        pub fn kill(self) -> io::Result<()> {
+            // SAFETY: the job is open until the group is closed
+            if unsafe { TerminateJobObject(self.0, 1) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(())
+        }
+
+        /// The most memory which the processes of the job used together, in bytes.
+        pub fn peak_memory(self) -> Option<u64> {
+            // SAFETY: the job is open until the group is closed, and the information is queried
+            // with its size
+            unsafe {
+                let mut limits: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = mem::zeroed();
+
+                let queried = QueryInformationJobObject(
+                    self.0,
+                    JobObjectExtendedLimitInformation,
+                    ptr::addr_of_mut!(limits).cast(),
+                    mem::size_of_val(&limits) as u32,
+                    ptr::null_mut(),
+                ) != 0;
+
+                queried.then_some(limits.PeakJobMemoryUsed as u64)
+            }
+        }
+
+        pub fn close(self) {
+            // SAFETY: the group is closed once, after which it's not used anymore
+            unsafe {
+                CloseHandle(self.0);
+            }
+        }
+    }
+
+    /// The CPU rate of the number of CPUs: the share of the time of all CPUs, in 1/100th of a
+    /// percent.
+    fn cpu_rate(cpus: f64) -> u32 {
+        let available = thread::available_parallelism().map_or(1, |n| n.get()) as f64;
+        ((cpus / available) * 10_000.0).clamp(1.0, 10_000.0) as u32
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    // If a process of the tree outlives the child, it keeps the pipe open for 30 seconds
+    fn command(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command
+            .args(["-c", script])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        command
+    }
+
+    #[test]
+    fn dropped_tree_is_killed() {
+        let mut tree = ProcessTree::spawn(&mut command("sleep 30 & sleep 30")).unwrap();
+        let mut stdout = tree.take_stdout().unwrap();
+        let id = tree.id;
+
+        let started = Instant::now();
+        drop(tree);
+
+        let mut buffer = Vec::new();
+        stdout.read_to_end(&mut buffer).unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(!registry().contains_key(&id));
+    }
+
+    #[test]
+    fn output_of_tree_with_processes_left_behind() {
+        let started = Instant::now();
+        let output = output(&mut command("echo checked; sleep 30 &")).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"checked\n");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,23 +1,45 @@
 use std::ffi::{OsStr, OsString};
+use std::io::Read;
 use std::path::Path;
-use std::process::{Command, Stdio};
+use std::process::{Command, ExitStatus, Output, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
 use crate::error::{IoError, IoErrorSource, TResult};
+use crate::external_command::interrupt;
+use crate::external_command::process_tree::ProcessTree;
+use crate::jobserver;
+use crate::resource_limits::ResourceLimits;
+use crate::rust::dist_server;
+use crate::rust::download_progress::PROGRESS_ENV_VAR;
+
+/// How often an interruptible command checks whether it was interrupted.
+const INTERRUPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 pub struct RustupCommand {
     command: Command,
     args: Vec<OsString>,
     stdout: Stdio,
     stderr: Stdio,
+    interruptible: bool,
+    jobserver: bool,
+    limits: ResourceLimits,
 }
 
 impl RustupCommand {
     pub fn new() -> Self {
+        let mut command = Command::new("rustup");
+        dist_server::get().configure(&mut command);
+
         Self {
-            command: Command::new("rustup"),
+            command,
             args: Vec::new(),
             stdout: Stdio::null(),
             stderr: Stdio::null(),
+            interruptible: false,
+            jobserver: false,
+            limits: ResourceLimits::default(),
         }
     }
 
@@ -31,6 +53,14 @@ impl RustupCommand {
         self
     }
 
+    pub fn with_envs<K: AsRef<OsStr>, V: AsRef<OsStr>>(
+        mut self,
+        envs: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        let _ = self.command.envs(envs);
+        self
+    }
+
     pub fn with_stdout(mut self) -> Self {
         self.stdout = Stdio::piped();
         self
@@ -41,14 +71,35 @@ impl RustupCommand {
         self
     }
 
+    /// Kill the command when the running commands are interrupted, see
+    /// [`interrupt::interrupt_running_commands`].
+    pub fn interruptible(mut self) -> Self {
+        self.interruptible = true;
+        self
+    }
+
+    /// Hold a token of the jobserver of cargo-msrv while the command runs, and pass the
+    /// jobserver to the command, see [`jobserver::share`].
+    pub fn with_jobserver(mut self) -> Self {
+        self.jobserver = true;
+        self
+    }
+
+    /// Limit the memory and CPU time which the processes of the command may use together, see
+    /// [`RustupOutput::exceeded_memory_limit`].
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Execute `rustup run [...]`
     pub fn run(self) -> TResult<RustupOutput> {
         self.execute(OsStr::new("run"))
     }
 
-    /// Execute `rustup install [...]`
-    pub fn install(self) -> TResult<RustupOutput> {
-        self.execute(OsStr::new("install"))
+    /// Execute `rustup install [...]`, see [RustupCommand::execute_streaming]
+    pub fn install(self, on_stderr: impl FnMut(&str)) -> TResult<RustupOutput> {
+        self.execute_streaming(OsStr::new("install"), on_stderr)
     }
 
     /// Execute `rustup show [...]`
@@ -56,12 +107,19 @@ impl RustupCommand {
         self.execute(OsStr::new("show"))
     }
 
-    pub fn target(self) -> TResult<RustupOutput> {
-        self.execute(OsStr::new("target"))
+    /// Execute `rustup toolchain [...]`
+    pub fn toolchain(self) -> TResult<RustupOutput> {
+        self.execute(OsStr::new("toolchain"))
+    }
+
+    /// Execute `rustup target [...]`, see [RustupCommand::execute_streaming]
+    pub fn target(self, on_stderr: impl FnMut(&str)) -> TResult<RustupOutput> {
+        self.execute_streaming(OsStr::new("target"), on_stderr)
     }
 
-    pub fn component(self) -> TResult<RustupOutput> {
-        self.execute(OsStr::new("component"))
+    /// Execute `rustup component [...]`, see [RustupCommand::execute_streaming]
+    pub fn component(self, on_stderr: impl FnMut(&str)) -> TResult<RustupOutput> {
+        self.execute_streaming(OsStr::new("component"), on_stderr)
     }
 
     /// Execute a given `rustup` command.
@@ -70,7 +128,43 @@ impl RustupCommand {
     /// * [RustupCommand::run](RustupCommand::run)
     /// * [RustupCommand::install](RustupCommand::install)
     /// * [RustupCommand::show](RustupCommand::show)
-    pub fn execute(mut self, cmd: &OsStr) -> TResult<RustupOutput> {
+    pub fn execute(self, cmd: &OsStr) -> TResult<RustupOutput> {
+        self.spawn_and_wait(cmd, Streams::default())
+    }
+
+    /// Execute a given `rustup` command, passing each line it writes to stderr to `on_stderr`
+    /// while it runs, e.g. to follow the progress of its downloads. Progress lines, which rustup
+    /// redraws in place, are passed as separate lines as well.
+    pub fn execute_streaming(
+        mut self,
+        cmd: &OsStr,
+        mut on_stderr: impl FnMut(&str),
+    ) -> TResult<RustupOutput> {
+        self.stderr = Stdio::piped();
+        self.command.env(PROGRESS_ENV_VAR, "always");
+        self.spawn_and_wait(
+            cmd,
+            Streams {
+                stdout: None,
+                stderr: Some(&mut on_stderr),
+            },
+        )
+    }
+
+    /// Execute `rustup run [...]`, passing each line written to stdout to `on_stdout` while it
+    /// runs, e.g. to follow the progress of a compilation.
+    pub fn run_streaming(mut self, mut on_stdout: impl FnMut(&str)) -> TResult<RustupOutput> {
+        self.stdout = Stdio::piped();
+        self.spawn_and_wait(
+            OsStr::new("run"),
+            Streams {
+                stdout: Some(&mut on_stdout),
+                stderr: None,
+            },
+        )
+    }
+
+    fn spawn_and_wait(mut self, cmd: &OsStr, streams: Streams<'_>) -> TResult<RustupOutput> {
         debug!(
             cmd = ?cmd,
             args = ?self.args.as_slice()
@@ -82,25 +176,178 @@ impl RustupCommand {
         self.command.stdout(self.stdout);
         self.command.stderr(self.stderr);
 
-        let child = self.command.spawn().map_err(|error| IoError {
-            error,
-            source: IoErrorSource::SpawnProcess(cmd.to_owned()),
-        })?;
-        let output = child.wait_with_output().map_err(|error| IoError {
+        let _token = if self.jobserver {
+            jobserver::share(&mut self.command).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::AcquireJobToken,
+            })?
+        } else {
+            None
+        };
+
+        let epoch = self.interruptible.then(interrupt::epoch);
+
+        let mut tree =
+            ProcessTree::spawn_limited(&mut self.command, &self.limits).map_err(|error| {
+                IoError {
+                    error,
+                    source: IoErrorSource::SpawnProcess(cmd.to_owned()),
+                }
+            })?;
+
+        let (output, interrupted) = if epoch.is_some() || streams.is_streaming() {
+            wait(&mut tree, epoch, streams)
+        } else {
+            tree.wait_with_output().map(|output| (output, false))
+        }
+        .map_err(|error| IoError {
             error,
             source: IoErrorSource::WaitForProcessAndCollectOutput(cmd.to_owned()),
         })?;
 
         Ok(RustupOutput {
             output,
+            interrupted,
+            exceeded_memory_limit: tree.exceeded_memory_limit(),
             stdout: once_cell::sync::OnceCell::new(),
             stderr: once_cell::sync::OnceCell::new(),
         })
     }
 }
 
+/// The handlers of the lines written by the child, while it runs.
+#[derive(Default)]
+struct Streams<'a> {
+    stdout: Option<&'a mut dyn FnMut(&str)>,
+    stderr: Option<&'a mut dyn FnMut(&str)>,
+}
+
+impl Streams<'_> {
+    fn is_streaming(&self) -> bool {
+        self.stdout.is_some() || self.stderr.is_some()
+    }
+}
+
+enum Line {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// Wait for the child to exit, while passing the lines it writes to the streaming handlers. If
+/// started in an interrupt epoch, the child and the processes it spawned are killed when it is
+/// interrupted. Returns the collected output, and whether the child was interrupted.
+fn wait(
+    tree: &mut ProcessTree,
+    epoch: Option<u64>,
+    mut streams: Streams<'_>,
+) -> std::io::Result<(Output, bool)> {
+    let (sender, receiver) = mpsc::channel();
+
+    // The pipes are drained while waiting, so the child doesn't block on a full pipe
+    let stdout = tree.take_stdout().map(|pipe| {
+        let sender = streams.stdout.is_some().then(|| sender.clone());
+        drain(pipe, sender, Line::Stdout)
+    });
+    let stderr = tree.take_stderr().map(|pipe| {
+        let sender = streams.stderr.is_some().then(|| sender.clone());
+        drain(pipe, sender, Line::Stderr)
+    });
+    drop(sender);
+
+    let mut handle = |line: Line| match line {
+        Line::Stdout(line) => streams.stdout.as_mut().map(|on_line| on_line(&line)),
+        Line::Stderr(line) => streams.stderr.as_mut().map(|on_line| on_line(&line)),
+    };
+
+    let (status, interrupted): (ExitStatus, bool) = loop {
+        if let Some(status) = tree.try_wait()? {
+            // The processes which the child left behind would keep the pipes open
+            let _ = tree.kill();
+            break (status, false);
+        }
+
+        if epoch.is_some_and(interrupt::is_interrupted) {
+            tree.kill()?;
+            break (tree.wait()?, true);
+        }
+
+        match receiver.recv_timeout(INTERRUPT_POLL_INTERVAL) {
+            Ok(line) => {
+                handle(line);
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            // Nothing is streamed (anymore), so there's nothing to wait for but the child
+            Err(mpsc::RecvTimeoutError::Disconnected) => thread::sleep(INTERRUPT_POLL_INTERVAL),
+        }
+    };
+
+    // The lines which were written right before the child exited
+    if !interrupted {
+        for line in receiver.iter() {
+            handle(line);
+        }
+    }
+
+    let collect = |pipe: Option<thread::JoinHandle<Vec<u8>>>| {
+        pipe.and_then(|handle| handle.join().ok())
+            .unwrap_or_default()
+    };
+
+    let output = Output {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    };
+
+    Ok((output, interrupted))
+}
+
+/// Read the pipe to the end. If given a sender, each line is sent while it is read, where lines
+/// may be terminated by `\r` as well, since progress is often redrawn in place.
+fn drain(
+    mut pipe: impl Read + Send + 'static,
+    sender: Option<mpsc::Sender<Line>>,
+    to_line: fn(String) -> Line,
+) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+
+        let Some(sender) = sender else {
+            let _ = pipe.read_to_end(&mut buffer);
+            return buffer;
+        };
+
+        let mut chunk = [0; 4096];
+        // The start of the line which is not yet terminated
+        let mut line_start = 0;
+
+        while let Ok(read @ 1..) = pipe.read(&mut chunk) {
+            buffer.extend_from_slice(&chunk[..read]);
+
+            while let Some(end) = buffer[line_start..]
+                .iter()
+                .position(|&b| b == b'\n' || b == b'\r')
+            {
+                let line = String::from_utf8_lossy(&buffer[line_start..line_start + end]);
+                // The receiver is gone when the child was interrupted
+                let _ = sender.send(to_line(line.into_owned()));
+                line_start += end + 1;
+            }
+        }
+
+        if line_start < buffer.len() {
+            let line = String::from_utf8_lossy(&buffer[line_start..]);
+            let _ = sender.send(to_line(line.into_owned()));
+        }
+
+        buffer
+    })
+}
+
 pub struct RustupOutput {
     output: std::process::Output,
+    interrupted: bool,
+    exceeded_memory_limit: bool,
     stdout: once_cell::sync::OnceCell<String>,
     stderr: once_cell::sync::OnceCell<String>,
 }
@@ -121,4 +368,14 @@ impl RustupOutput {
     pub fn exit_status(&self) -> std::process::ExitStatus {
         self.output.status
     }
+
+    /// Whether the command was killed, because it was interrupted.
+    pub fn was_interrupted(&self) -> bool {
+        self.interrupted
+    }
+
+    /// Whether a process of the command was killed, since the command exceeded its memory limit.
+    pub fn exceeded_memory_limit(&self) -> bool {
+        self.exceeded_memory_limit
+    }
 }
@@ -0,0 +1,147 @@
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::external_command::process_tree;
+use camino::Utf8Path;
+use std::ffi::OsStr;
+use std::process::{Command, Output, Stdio};
+
+/// Runs commands on a remote builder, over `ssh`.
+#[derive(Clone, Debug)]
+pub struct SshCommand<'a> {
+    destination: &'a str,
+}
+
+impl<'a> SshCommand<'a> {
+    /// The `destination` is given to `ssh` as is, e.g. `user@host`, or a host from the ssh config.
+    pub fn new(destination: &'a str) -> Self {
+        Self { destination }
+    }
+
+    /// The arguments to `ssh`, to run the given command in the given remote directory.
+    pub fn args(&self, dir: Option<&str>, command: &[impl AsRef<str>]) -> Vec<String> {
+        let command = command
+            .iter()
+            .map(|arg| shell_quote(arg.as_ref()))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let remote_command = match dir {
+            Some(dir) => format!("cd {} && {}", shell_quote(dir), command),
+            None => command,
+        };
+
+        vec![
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+            self.destination.to_string(),
+            remote_command,
+        ]
+    }
+
+    /// Run the given command in the given remote directory, collecting its output.
+    pub fn run(&self, dir: Option<&str>, command: &[impl AsRef<str>]) -> TResult<Output> {
+        output(Command::new("ssh").args(self.args(dir, command)))
+    }
+
+    /// The arguments to `rsync`, to sync the crate at `crate_root` to the remote directory.
+    ///
+    /// The target directory is not synced, and files which no longer exist locally are deleted.
+    pub fn sync_args(&self, crate_root: &Utf8Path, dir: &str) -> Vec<String> {
+        vec![
+            "--archive".to_string(),
+            "--compress".to_string(),
+            "--delete".to_string(),
+            "--exclude".to_string(),
+            "/target".to_string(),
+            "--rsh".to_string(),
+            "ssh -o BatchMode=yes".to_string(),
+            format!("{}/", crate_root),
+            format!("{}:{}/", self.destination, dir),
+        ]
+    }
+
+    /// Sync the crate at `crate_root` to the remote directory, with `rsync`.
+    pub fn sync(&self, crate_root: &Utf8Path, dir: &str) -> TResult<Output> {
+        output(Command::new("rsync").args(self.sync_args(crate_root, dir)))
+    }
+}
+
+fn output(command: &mut Command) -> TResult<Output> {
+    let program = command.get_program().to_owned();
+
+    process_tree::output(command.stdout(Stdio::piped()).stderr(Stdio::piped())).map_err(|error| {
+        IoError {
+            error,
+            source: IoErrorSource::SpawnProcess(program),
+        }
+        .into()
+    })
+}
+
+/// Quote an argument for the remote shell, which `ssh` passes its command to.
+fn shell_quote(arg: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c);
+
+    if !arg.is_empty() && arg.chars().all(is_safe) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Whether `ssh` and `rsync` can be invoked.
+pub fn is_installed() -> bool {
+    [("ssh", "-V"), ("rsync", "--version")]
+        .iter()
+        .all(|(program, flag)| {
+            Command::new(OsStr::new(program))
+                .arg(flag)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        plain = { "cargo", "cargo" },
+        flag = { "--target=x86_64-unknown-linux-gnu", "--target=x86_64-unknown-linux-gnu" },
+        space = { "a b", "'a b'" },
+        quote = { "it's", r"'it'\''s'" },
+        empty = { "", "''" },
+        substitution = { "$(id)", "'$(id)'" },
+    )]
+    fn quote(arg: &str, expected: &str) {
+        assert_eq!(shell_quote(arg), expected);
+    }
+
+    #[test]
+    fn run_args() {
+        let args = SshCommand::new("user@host").args(
+            Some(".cargo-msrv/remote/my crate"),
+            &["rustup", "run", "1.56.0", "cargo", "check"],
+        );
+
+        assert_eq!(
+            args,
+            vec![
+                "-o",
+                "BatchMode=yes",
+                "user@host",
+                "cd '.cargo-msrv/remote/my crate' && rustup run 1.56.0 cargo check"
+            ]
+        );
+    }
+
+    #[test]
+    fn sync_args() {
+        let args = SshCommand::new("user@host").sync_args(Utf8Path::new("/crate"), "remote");
+
+        assert!(args.ends_with(&["/crate/".to_string(), "user@host:remote/".to_string()]));
+        assert!(args.contains(&"/target".to_string()));
+    }
+}
@@ -0,0 +1,234 @@
+//! A fingerprint of everything which affects the MSRV which cargo-msrv finds: the lockfile, the
+//! sources of the crate, the target and features of the checks, the host and version of
+//! cargo-msrv, and the release index. The fingerprint is included in the result of `find`, and
+//! `verify --expect-fingerprint` fails if it no longer matches, since the result of an earlier run
+//! may then no longer apply.
+//!
+//! Each component is a digest of its inputs, or the input itself, and the id of the fingerprint is
+//! a digest of each component. Digests are the first 16 hexadecimal digits of a SHA-256 hash.
+
+use crate::context::{CheckCommandContext, EnvironmentContext};
+use camino::{Utf8Path, Utf8PathBuf};
+use rust_releases::ReleaseIndex;
+use sha2::{Digest, Sha256};
+
+/// The directories of which the sources are not part of the fingerprint: build artifacts, and
+/// hidden directories, like `.git`.
+const SKIPPED_DIRS: &[&str] = &["target"];
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Fingerprint {
+    /// The digest of the other components, which identifies the fingerprint as a whole.
+    pub id: String,
+    /// The digest of the lockfile, if the crate has one.
+    pub lockfile: Option<String>,
+    /// The digest of the Rust sources and Cargo manifests of the crate.
+    pub sources: String,
+    pub target: String,
+    /// The feature flags of the checks, like `--features a,b`.
+    pub features: Vec<String>,
+    /// The target triple of the host, for which cargo-msrv was built.
+    pub host: Option<String>,
+    pub cargo_msrv_version: String,
+    /// The digest of the versions of the release index.
+    pub release_index: String,
+}
+
+impl Fingerprint {
+    pub fn new(
+        environment: &EnvironmentContext,
+        target: &str,
+        check_cmd: &CheckCommandContext,
+        index: &ReleaseIndex,
+    ) -> Self {
+        let lockfile = std::fs::read(environment.lock())
+            .ok()
+            .map(|contents| digest([contents.as_slice()]));
+
+        let mut features = Vec::new();
+        if let Some(selected) = &check_cmd.cargo_features {
+            features.push(format!("--features {}", selected.join(",")));
+        }
+        if check_cmd.cargo_all_features {
+            features.push("--all-features".to_string());
+        }
+        if check_cmd.cargo_no_default_features {
+            features.push("--no-default-features".to_string());
+        }
+
+        let versions = index
+            .releases()
+            .iter()
+            .map(|release| release.version().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Self::from_components(
+            lockfile,
+            sources_digest(environment.root()),
+            target.to_string(),
+            features,
+            option_env!("VERGEN_CARGO_TARGET_TRIPLE").map(String::from),
+            env!("CARGO_PKG_VERSION").to_string(),
+            digest([versions.as_bytes()]),
+        )
+    }
+
+    fn from_components(
+        lockfile: Option<String>,
+        sources: String,
+        target: String,
+        features: Vec<String>,
+        host: Option<String>,
+        cargo_msrv_version: String,
+        release_index: String,
+    ) -> Self {
+        let features_joined = features.join(" ");
+        let id = digest([
+            lockfile.as_deref().unwrap_or_default().as_bytes(),
+            sources.as_bytes(),
+            target.as_bytes(),
+            features_joined.as_bytes(),
+            host.as_deref().unwrap_or_default().as_bytes(),
+            cargo_msrv_version.as_bytes(),
+            release_index.as_bytes(),
+        ]);
+
+        Self {
+            id,
+            lockfile,
+            sources,
+            target,
+            features,
+            host,
+            cargo_msrv_version,
+            release_index,
+        }
+    }
+}
+
+/// The digest of the Rust sources and Cargo manifests below the given root, by their path relative
+/// to the root, and their contents. Files which can't be read are left out.
+fn sources_digest(root: &Utf8Path) -> String {
+    let mut files = Vec::new();
+    collect_sources(root, &mut files);
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for path in files {
+        let Ok(contents) = std::fs::read(&path) else {
+            continue;
+        };
+
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        // Paths use forward slashes on every platform, so the digest is the same on each
+        let relative = relative
+            .components()
+            .map(|component| component.as_str())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(&contents);
+        hasher.update([0]);
+    }
+
+    hex(&hasher.finalize())
+}
+
+fn collect_sources(dir: &Utf8Path, files: &mut Vec<Utf8PathBuf>) {
+    let Ok(entries) = dir.read_dir_utf8() else {
+        return;
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let name = entry.file_name();
+
+        match entry.file_type() {
+            Ok(file_type)
+                if file_type.is_dir()
+                    && !name.starts_with('.')
+                    && !SKIPPED_DIRS.contains(&name) =>
+            {
+                collect_sources(path, files);
+            }
+            Ok(file_type)
+                if file_type.is_file()
+                    && (path.extension() == Some("rs") || name == "Cargo.toml") =>
+            {
+                files.push(path.to_path_buf());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The digest of the given parts, each of which is separated from the next.
+fn digest<'a>(parts: impl IntoIterator<Item = &'a [u8]>) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+        hasher.update([0]);
+    }
+
+    hex(&hasher.finalize())
+}
+
+fn hex(hash: &[u8]) -> String {
+    hash.iter()
+        .take(8)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Utf8Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn sources_of_crate() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        write(root, "Cargo.toml", "[package]\n");
+        write(root, "src/lib.rs", "pub fn a() {}\n");
+        let before = sources_digest(root);
+
+        // Neither build artifacts, nor other files, are sources
+        write(root, "target/debug/build.rs", "");
+        write(root, ".git/hooks/hook.rs", "");
+        write(root, "README.md", "# a\n");
+        assert_eq!(sources_digest(root), before);
+
+        write(root, "src/lib.rs", "pub fn b() {}\n");
+        assert_ne!(sources_digest(root), before);
+    }
+
+    #[test]
+    fn id_of_components() {
+        let fingerprint = |target: &str| {
+            Fingerprint::from_components(
+                None,
+                "sources".to_string(),
+                target.to_string(),
+                Vec::new(),
+                None,
+                "0.0.0".to_string(),
+                "index".to_string(),
+            )
+        };
+
+        assert_eq!(fingerprint("a").id, fingerprint("a").id);
+        assert_ne!(fingerprint("a").id, fingerprint("b").id);
+        assert_eq!(fingerprint("a").id.len(), 16);
+    }
+}
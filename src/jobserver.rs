@@ -0,0 +1,388 @@
+//! Limit the number of compilers which run at once, across the checks of cargo-msrv, with the
+//! jobserver protocol of GNU make, which cargo implements as well.
+//!
+//! cargo-msrv either takes part in the jobserver of the make (or cargo) which runs it, or with
+//! `--jobs`, creates a jobserver of its own. Each check command holds a token of the jobserver
+//! while it runs, and the cargo of the check takes the tokens for its compilers from the same
+//! jobserver, so concurrent checks don't oversubscribe the machine.
+//!
+//! As in make, the process which runs a jobserver holds one implicit token, which isn't in the
+//! jobserver itself: the first check command which runs takes the implicit token.
+
+use once_cell::sync::OnceCell;
+use std::io;
+use std::num::NonZeroUsize;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static JOBSERVER: OnceCell<Jobserver> = OnceCell::new();
+
+/// Install the jobserver of cargo-msrv: a jobserver with the given number of jobs, if given,
+/// or otherwise, the jobserver which cargo-msrv inherited from its parent, if any.
+///
+/// # Safety
+///
+/// Must be called before any file is opened, since the file descriptors of an inherited
+/// jobserver may otherwise have been closed, and since been reused for another file.
+pub unsafe fn install(jobs: Option<NonZeroUsize>) -> io::Result<()> {
+    let jobserver = match jobs {
+        Some(jobs) => Some(Jobserver::new(jobs)?),
+        None => Jobserver::inherited(),
+    };
+
+    if let Some(jobserver) = jobserver {
+        let _ = JOBSERVER.set(jobserver);
+    }
+
+    Ok(())
+}
+
+/// The installed jobserver, if any, see [`install`].
+pub fn get() -> Option<&'static Jobserver> {
+    JOBSERVER.get()
+}
+
+/// Let the command take part in the installed jobserver, if any. The command may only be
+/// spawned while the returned token is held.
+pub fn share(command: &mut Command) -> io::Result<Option<Token<'static>>> {
+    get()
+        .map(|jobserver| {
+            let token = jobserver.acquire()?;
+            jobserver.configure(command);
+            Ok(token)
+        })
+        .transpose()
+}
+
+pub struct Jobserver {
+    client: sys::Client,
+    /// Whether the implicit token is free to be taken.
+    implicit: AtomicBool,
+}
+
+impl Jobserver {
+    /// A jobserver of cargo-msrv, with the given number of jobs, including the implicit one.
+    pub fn new(jobs: NonZeroUsize) -> io::Result<Self> {
+        Ok(Self {
+            client: sys::Client::new(jobs.get() - 1)?,
+            implicit: AtomicBool::new(true),
+        })
+    }
+
+    /// The jobserver which cargo-msrv inherited from its parent, if any, as passed to cargo.
+    ///
+    /// # Safety
+    ///
+    /// See [`install`].
+    unsafe fn inherited() -> Option<Self> {
+        let flags = ["CARGO_MAKEFLAGS", "MAKEFLAGS", "MFLAGS"]
+            .iter()
+            .find_map(|var| std::env::var(var).ok())?;
+        let auth = auth(&flags)?;
+
+        match sys::Client::open(auth) {
+            Ok(client) => Some(Self {
+                client,
+                implicit: AtomicBool::new(true),
+            }),
+            Err(error) => {
+                info!(%error, auth, "unable to take part in the inherited jobserver");
+                None
+            }
+        }
+    }
+
+    /// Take a token, waiting until one is free.
+    pub fn acquire(&self) -> io::Result<Token<'_>> {
+        if self.implicit.swap(false, Ordering::SeqCst) {
+            return Ok(Token {
+                jobserver: self,
+                acquired: None,
+            });
+        }
+
+        Ok(Token {
+            jobserver: self,
+            acquired: Some(self.client.acquire()?),
+        })
+    }
+
+    /// Pass the jobserver to the command, like make does.
+    pub fn configure(&self, command: &mut Command) {
+        let auth = self.client.auth();
+        let flags = if auth.starts_with("fifo:") {
+            format!("-j --jobserver-auth={}", auth)
+        } else {
+            // Old versions of cargo only know of `--jobserver-fds`
+            format!("-j --jobserver-fds={0} --jobserver-auth={0}", auth)
+        };
+
+        command
+            .env("CARGO_MAKEFLAGS", &flags)
+            .env("MAKEFLAGS", &flags)
+            .env("MFLAGS", &flags);
+    }
+}
+
+/// A token of a jobserver, which is returned to the jobserver when dropped.
+pub struct Token<'a> {
+    jobserver: &'a Jobserver,
+    /// The token taken from the jobserver, or none, for the implicit token.
+    acquired: Option<sys::Acquired>,
+}
+
+impl Drop for Token<'_> {
+    fn drop(&mut self) {
+        match self.acquired.take() {
+            Some(acquired) => {
+                if let Err(error) = self.jobserver.client.release(acquired) {
+                    info!(%error, "unable to return a token to the jobserver");
+                }
+            }
+            None => self.jobserver.implicit.store(true, Ordering::SeqCst),
+        }
+    }
+}
+
+/// The value of the last `--jobserver-auth` (or the older `--jobserver-fds`) flag of the make
+/// flags, which describes how to connect to the jobserver.
+fn auth(flags: &str) -> Option<&str> {
+    flags.split_whitespace().rev().find_map(|flag| {
+        flag.strip_prefix("--jobserver-auth=")
+            .or_else(|| flag.strip_prefix("--jobserver-fds="))
+    })
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::fs::{File, OpenOptions};
+    use std::io::{self, Read, Write};
+    use std::os::unix::io::FromRawFd;
+
+    /// A pipe, or with `fifo:` auth, a named pipe, of which each byte is a token.
+    pub struct Client {
+        read: File,
+        write: File,
+        auth: String,
+    }
+
+    /// The byte which was read from the pipe, which is written back as it is.
+    pub struct Acquired(u8);
+
+    impl Client {
+        pub fn new(tokens: usize) -> io::Result<Self> {
+            let mut fds = [0; 2];
+
+            // SAFETY: `pipe` writes two file descriptors to the array. These are inherited by
+            // the processes spawned by cargo-msrv, as make does.
+            if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            // SAFETY: the file descriptors were just opened, and are owned by the client
+            let (read, mut write) =
+                unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+            write.write_all(&vec![b'|'; tokens])?;
+
+            Ok(Self {
+                read,
+                write,
+                auth: format!("{},{}", fds[0], fds[1]),
+            })
+        }
+
+        /// # Safety
+        ///
+        /// The file descriptors of the auth, if any, must be those of the jobserver.
+        pub unsafe fn open(auth: &str) -> io::Result<Self> {
+            if let Some(path) = auth.strip_prefix("fifo:") {
+                let fifo = OpenOptions::new().read(true).write(true).open(path)?;
+
+                return Ok(Self {
+                    read: fifo.try_clone()?,
+                    write: fifo,
+                    auth: auth.to_string(),
+                });
+            }
+
+            let invalid = || io::Error::new(io::ErrorKind::InvalidInput, "invalid jobserver auth");
+
+            let (read, write) = auth.split_once(',').ok_or_else(invalid)?;
+            let read: libc::c_int = read.parse().map_err(|_| invalid())?;
+            let write: libc::c_int = write.parse().map_err(|_| invalid())?;
+
+            // The file descriptors aren't open if make didn't pass the jobserver to cargo-msrv,
+            // e.g. since its recipe isn't marked with a `+`
+            for fd in [read, write] {
+                if libc::fcntl(fd, libc::F_GETFD) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+
+            Ok(Self {
+                read: File::from_raw_fd(read),
+                write: File::from_raw_fd(write),
+                auth: auth.to_string(),
+            })
+        }
+
+        pub fn auth(&self) -> &str {
+            &self.auth
+        }
+
+        pub fn acquire(&self) -> io::Result<Acquired> {
+            let mut token = [0];
+
+            loop {
+                match (&self.read).read(&mut token) {
+                    Ok(1) => return Ok(Acquired(token[0])),
+                    Ok(_) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "the jobserver was closed",
+                        ))
+                    }
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        pub fn release(&self, acquired: Acquired) -> io::Result<()> {
+            (&self.write).write_all(&[acquired.0])
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::ffi::CString;
+    use std::{io, ptr};
+    use windows_sys::Win32::Foundation::{FALSE, HANDLE, WAIT_OBJECT_0};
+    use windows_sys::Win32::System::Threading::{
+        CreateSemaphoreA, OpenSemaphoreA, ReleaseSemaphore, WaitForSingleObject, INFINITE,
+        SEMAPHORE_MODIFY_STATE, SYNCHRONIZATION_SYNCHRONIZE,
+    };
+
+    /// A named semaphore, of which the count is the number of tokens.
+    pub struct Client {
+        semaphore: HANDLE,
+        auth: String,
+    }
+
+    pub struct Acquired;
+
+    impl Client {
+        pub fn new(tokens: usize) -> io::Result<Self> {
+            let auth = format!("__cargo_msrv_jobserver_{}", std::process::id());
+            let name = CString::new(auth.as_str()).expect("the name has no nul bytes");
+            let tokens = i32::try_from(tokens).unwrap_or(i32::MAX);
+
+            // SAFETY: the semaphore is created without security attributes, with a valid name
+            let semaphore = unsafe {
+                CreateSemaphoreA(ptr::null(), tokens, tokens.max(1), name.as_ptr().cast())
+            };
+            if semaphore == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self { semaphore, auth })
+        }
+
+        /// # Safety
+        ///
+        /// Always safe on Windows, since the jobserver is named.
+        pub unsafe fn open(auth: &str) -> io::Result<Self> {
+            let name = CString::new(auth)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidInput, error))?;
+
+            let semaphore = OpenSemaphoreA(
+                SYNCHRONIZATION_SYNCHRONIZE | SEMAPHORE_MODIFY_STATE,
+                FALSE,
+                name.as_ptr().cast(),
+            );
+            if semaphore == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self {
+                semaphore,
+                auth: auth.to_string(),
+            })
+        }
+
+        pub fn auth(&self) -> &str {
+            &self.auth
+        }
+
+        pub fn acquire(&self) -> io::Result<Acquired> {
+            // SAFETY: the semaphore is open for as long as the client lives
+            if unsafe { WaitForSingleObject(self.semaphore, INFINITE) } != WAIT_OBJECT_0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Acquired)
+        }
+
+        pub fn release(&self, _acquired: Acquired) -> io::Result<()> {
+            // SAFETY: the semaphore is open for as long as the client lives
+            if unsafe { ReleaseSemaphore(self.semaphore, 1, ptr::null_mut()) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        fds = { "-j --jobserver-fds=3,4 --jobserver-auth=3,4", Some("3,4") },
+        fds_only = { " -j8 --jobserver-fds=5,6", Some("5,6") },
+        fifo = { "-j4 --jobserver-auth=fifo:/tmp/GMfifo1234", Some("fifo:/tmp/GMfifo1234") },
+        last_wins = { "--jobserver-auth=3,4 --jobserver-auth=7,8", Some("7,8") },
+        none = { "-j4 -k", None },
+    )]
+    fn auth_of_flags(flags: &str, expected: Option<&str>) {
+        assert_eq!(auth(flags), expected);
+    }
+
+    #[test]
+    fn tokens_are_returned() {
+        let jobserver = Jobserver::new(NonZeroUsize::new(2).unwrap()).unwrap();
+
+        let implicit = jobserver.acquire().unwrap();
+        assert!(implicit.acquired.is_none());
+
+        let acquired = jobserver.acquire().unwrap();
+        assert!(acquired.acquired.is_some());
+
+        drop(implicit);
+        drop(acquired);
+
+        // Both tokens are free again
+        let implicit = jobserver.acquire().unwrap();
+        let acquired = jobserver.acquire().unwrap();
+        assert!(implicit.acquired.is_none());
+        assert!(acquired.acquired.is_some());
+    }
+
+    #[test]
+    fn configured_command() {
+        let jobserver = Jobserver::new(NonZeroUsize::new(4).unwrap()).unwrap();
+        let mut command = Command::new("cargo");
+        jobserver.configure(&mut command);
+
+        let flags = command
+            .get_envs()
+            .find(|(var, _)| *var == "CARGO_MAKEFLAGS")
+            .and_then(|(_, value)| value)
+            .and_then(|value| value.to_str())
+            .unwrap();
+
+        assert_eq!(auth(flags), Some(jobserver.client.auth()));
+    }
+}
@@ -20,37 +20,85 @@ extern crate core;
 #[macro_use]
 extern crate tracing;
 
+pub use crate::config_builder::ConfigBuilder;
 pub use crate::context::{Context, OutputFormat, TracingOptions, TracingTargetOption};
 pub use crate::outcome::Outcome;
-pub use crate::sub_command::{Find, List, Set, Show, SubCommand, Verify};
+pub use crate::sub_command::{
+    BisectLockfile, Cache, CheckConsistency, Clean, Estimate, Find, Graph, Images, Inspect, List,
+    MergeResults, Migrate, Outdated, Report, Set, Show, SubCommand, SyncDeclarations, Verify,
+    WhatIf,
+};
 
-use crate::check::RustupToolchainCheck;
-use crate::context::ReleaseSource;
+use crate::cache::GlobalCache;
+use crate::check::{
+    ArtifactCheck, Check, ControlledCheck, DeadlineCheck, ExitCodeCheck, FuzzCheck, LockfileCheck,
+    PredicateCheck, ProviderCheck, ResumedCheck, SignalCheck,
+};
+use crate::context::{
+    CheckCommandContext, EnvironmentContext, FindContext, LockfileStrategy, ReleaseSource,
+    ToolchainContext, ToolchainProvider, VerifyContext,
+};
+use crate::control::{Control, ControlSource};
+use crate::deadline::Deadline;
+use crate::dependency_graph::crates_index::CratesIndex;
 use crate::error::{CargoMSRVError, TResult};
-use crate::reporter::event::{Meta, SelectedPackages, SubcommandInit};
+use crate::fingerprint::Fingerprint;
+use crate::manifest::exit_codes::configured_exit_codes;
+use crate::matrix_state::MatrixState;
+use crate::reporter::event::{
+    AuxiliaryOutput, AuxiliaryOutputItem, Destination, IncludedCrate, Meta, SearchTarget,
+    SelectedPackages, SubcommandInit, TargetMsrv, TargetsResult, Warning,
+};
 use crate::reporter::{Event, Reporter};
+use crate::run_artifacts::RunArtifacts;
+use crate::sub_command::check_consistency::require_consistency;
+use crate::sub_command::find::shard::FindShard;
+use crate::sub_command::verify::{verify_freshness, verify_release};
+use crate::vendor::VendoredSources;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::MetadataCommand;
 use rust::release_index;
-use rust_releases::semver;
+use rust_releases::{semver, Release, ReleaseIndex};
 
+pub mod cache;
 pub mod check;
+pub mod chrome_trace;
 pub mod cli;
+pub mod config_builder;
 
 pub mod context;
+pub mod control;
+pub mod deadline;
+pub mod declaration;
 pub mod dependency_graph;
+pub mod disk_space;
 pub mod error;
+pub mod estimate;
 pub mod exit_code;
 mod external_command;
+pub mod fingerprint;
 pub mod io;
+pub mod jobserver;
 pub mod lockfile;
+pub mod log_file;
 pub mod log_level;
 pub mod manifest;
+pub mod matrix_state;
 pub mod msrv;
+pub mod otlp;
 pub mod outcome;
+pub mod recorded_run;
 pub mod reporter;
+pub mod resource_limits;
+pub mod run_artifacts;
 pub mod rust;
 pub mod search_method;
+pub mod search_state;
+pub mod signal;
+pub mod stabilization;
 pub mod sub_command;
 pub mod typed_bool;
+pub mod vendor;
 pub mod writer;
 
 pub fn run_app(ctx: &Context, reporter: &impl Reporter) -> TResult<()> {
@@ -60,43 +108,472 @@ pub fn run_app(ctx: &Context, reporter: &impl Reporter) -> TResult<()> {
     ))?;
     reporter.report_event(SubcommandInit::new(ctx.reporting_name()))?;
 
+    let result = run_subcommand(ctx, reporter);
+
+    // The cache is left as is, while it is being inspected
+    if !matches!(ctx, Context::Cache(_)) {
+        evict_cache(ctx.environment_context().max_cache_size);
+    }
+
+    result
+}
+
+fn run_subcommand(ctx: &Context, reporter: &impl Reporter) -> TResult<()> {
     match ctx {
+        Context::BisectLockfile(ctx) => {
+            BisectLockfile.run(ctx, reporter)?;
+        }
+        Context::Cache(ctx) => {
+            Cache.run(ctx, reporter)?;
+        }
+        Context::CheckConsistency(ctx) => {
+            CheckConsistency.run(ctx, reporter)?;
+        }
+        Context::Clean(ctx) => {
+            Clean.run(ctx, reporter)?;
+        }
+        Context::Estimate(ctx) => {
+            Estimate.run(ctx, reporter)?;
+        }
+        Context::Find(ctx) if !ctx.targets.is_empty() => {
+            find_per_target(ctx, reporter)?;
+        }
         Context::Find(ctx) => {
-            let index = release_index::fetch_index(reporter, ctx.rust_releases.release_source)?;
-
-            let runner = RustupToolchainCheck::new(
-                reporter,
-                ctx.ignore_lockfile,
-                ctx.no_check_feedback,
-                &ctx.environment,
-                ctx.run_command(),
-            );
-            Find::new(&index, runner).run(ctx, reporter)?;
+            let for_crate = |root: &Utf8Path| Ok(ctx.for_crate(root.to_path_buf()));
+            run_for_each_crate(ctx, &ctx.also, reporter, for_crate, |ctx, reporter| {
+                find(ctx, reporter).map(drop)
+            })?;
+        }
+        Context::Graph(ctx) => {
+            Graph.run(ctx, reporter)?;
+        }
+        Context::Images(ctx) => {
+            Images.run(ctx, reporter)?;
+        }
+        Context::Inspect(ctx) => {
+            Inspect.run(ctx, reporter)?;
         }
         Context::List(ctx) => {
             List.run(ctx, reporter)?;
         }
+        Context::MergeResults(ctx) => {
+            MergeResults.run(ctx, reporter)?;
+        }
+        Context::Migrate(ctx) => {
+            Migrate.run(ctx, reporter)?;
+        }
+        Context::Outdated(ctx) => {
+            let index = CratesIndex::new()?;
+            Outdated::new(index).run(ctx, reporter)?;
+        }
+        Context::Report(ctx) => {
+            Report.run(ctx, reporter)?;
+        }
         Context::Set(ctx) => {
-            let index = release_index::fetch_index(reporter, ctx.rust_releases.release_source).ok();
+            let index = release_index::fetch_index(reporter, &ctx.rust_releases).ok();
             Set::new(index.as_ref()).run(ctx, reporter)?;
         }
         Context::Show(ctx) => {
             Show.run(ctx, reporter)?;
         }
+        Context::Sync(ctx) => {
+            SyncDeclarations.run(ctx, reporter)?;
+        }
         Context::Verify(ctx) => {
-            let index = release_index::fetch_index(reporter, ctx.rust_releases.release_source)?;
+            let for_crate = |root: &Utf8Path| ctx.for_crate(root.to_path_buf());
+            run_for_each_crate(ctx, &ctx.also, reporter, for_crate, verify)?;
+        }
+        Context::WhatIf(ctx) => {
+            WhatIf.run(ctx, reporter)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the MSRV, unless only a shard of the search space is searched.
+fn find(ctx: &FindContext, reporter: &impl Reporter) -> TResult<Option<semver::Version>> {
+    let index = match &ctx.toolchain_provider {
+        // No other toolchains can be installed, and the index may not be reachable
+        ToolchainProvider::Tarballs(tarballs) => tarballs.release_index(ctx.toolchain.target)?,
+        _ => release_index::fetch_index(reporter, &ctx.rust_releases)?,
+    };
+
+    report_required_features(&ctx.check_cmd, &ctx.environment, reporter)?;
+
+    let check = SearchCheck {
+        ignore_lockfile: ctx.ignore_lockfile,
+        lockfile_strategy: ctx.lockfile_strategy,
+        no_check_feedback: ctx.no_check_feedback,
+        check_cmd: &ctx.check_cmd,
+        toolchain: &ctx.toolchain,
+        toolchain_provider: &ctx.toolchain_provider,
+        environment: &ctx.environment,
+        control: ctx.control.as_ref(),
+        deadline: ctx.deadline.as_ref(),
+        resume: ctx.resume,
+        save_run: ctx.save_run.then_some("find"),
+    };
+
+    check.run(reporter, |runner, artifacts| {
+        let msrv = if ctx.shard.is_some() {
+            FindShard::new(&index, runner)
+                .run(ctx, reporter)
+                .map(|_| None)
+        } else {
+            Find::new(&index, runner).run(ctx, reporter).map(Some)
+        };
+
+        if let Some(artifacts) = artifacts {
+            artifacts.finish(reporter, msrv.as_ref().ok().and_then(Option::as_ref))?;
+        }
+
+        msrv
+    })
+}
+
+/// Find the MSRV for each of the targets given with `--targets`, and report the MSRV across the
+/// targets. Each target is searched, even if no Rust version is compatible with an earlier one.
+///
+/// The MSRV of each target is saved, see [`matrix_state`]. With `--resume`, a saved MSRV is reused
+/// if the boundary of the target still holds, and only the other targets are searched.
+fn find_per_target(ctx: &FindContext, reporter: &impl Reporter) -> TResult<()> {
+    let root = ctx.environment.root();
+    let mut state = match ctx.resume {
+        true => matrix_state::load(root)?.unwrap_or_default(),
+        false => MatrixState::default(),
+    };
+
+    let mut targets = Vec::with_capacity(ctx.targets.len());
+    let mut unable_to_find = None;
+    let mut saved = None;
+
+    for &target in &ctx.targets {
+        reporter.report_event(SearchTarget::new(target))?;
+
+        let saved_msrv = state
+            .msrv(target)
+            .filter(|msrv| in_search_space(ctx, msrv))
+            .cloned();
+        let resumed = match saved_msrv {
+            Some(msrv) => resume_target(ctx, target, &msrv, reporter)?,
+            None => None,
+        };
+
+        let found = match &resumed {
+            Some(msrv) => Ok(Some(msrv.clone())),
+            None => find(&ctx.for_target(target), reporter),
+        };
+        let msrv = match found {
+            Ok(msrv) => msrv,
+            Err(error @ CargoMSRVError::UnableToFindAnyGoodVersion { .. }) => {
+                unable_to_find.get_or_insert(error);
+                None
+            }
+            Err(error) => return Err(error),
+        };
+
+        // Saved after each target, so a run which stops early keeps the targets before
+        state.set(target, msrv.clone());
+        match matrix_state::save(root, &state) {
+            Ok(path) => saved = Some(path),
+            Err(error) => info!(%error, "unable to save the MSRV of each target"),
+        }
+
+        targets.push(TargetMsrv {
+            target: target.to_string(),
+            msrv,
+            resumed: resumed.is_some(),
+        });
+    }
+
+    if let Some(path) = saved {
+        reporter.report_event(AuxiliaryOutput::new(
+            Destination::file(path),
+            AuxiliaryOutputItem::matrix_state(),
+        ))?;
+    }
+
+    reporter.report_event(TargetsResult::new(targets))?;
+
+    unable_to_find.map_or(Ok(()), Err)
+}
+
+/// Check the boundary of the saved MSRV of the target, and return the MSRV if it still holds. If
+/// it doesn't, the MSRV of the target must be searched again.
+fn resume_target(
+    ctx: &FindContext,
+    target: &'static str,
+    msrv: &semver::Version,
+    reporter: &impl Reporter,
+) -> TResult<Option<semver::Version>> {
+    match find(&ctx.for_target_boundary(target, msrv), reporter) {
+        Ok(Some(found)) if &found == msrv => return Ok(Some(found)),
+        Ok(_) | Err(CargoMSRVError::UnableToFindAnyGoodVersion { .. }) => {}
+        Err(error) => return Err(error),
+    }
+
+    reporter.report_event(Warning::target_msrv_invalidated(target, msrv))?;
+
+    Ok(None)
+}
 
-            let runner = RustupToolchainCheck::new(
-                reporter,
-                ctx.ignore_lockfile,
-                ctx.no_check_feedback,
-                &ctx.environment,
-                ctx.run_command(),
-            );
+/// Whether the version is within the search space given with `--min` and `--max`, so a saved
+/// MSRV from another search space is not reused.
+fn in_search_space(ctx: &FindContext, version: &semver::Version) -> bool {
+    let releases = &ctx.rust_releases;
 
-            Verify::new(&index, runner).run(ctx, reporter)?;
+    releases
+        .minimum_rust_version
+        .as_ref()
+        .map_or(true, |min| min.is_at_least(version))
+        && releases
+            .maximum_rust_version
+            .as_ref()
+            .map_or(true, |max| max.is_at_most(version))
+}
+
+fn verify(ctx: &VerifyContext, reporter: &impl Reporter) -> TResult<()> {
+    // Checked first, since it is much cheaper than checking the toolchain
+    if let Some(required) = ctx.required_consistency {
+        require_consistency(&ctx.environment, required, reporter)?;
+    }
+
+    if let Some(expected) = &ctx.expect_fingerprint {
+        expect_fingerprint(ctx, expected, reporter)?;
+    }
+
+    if let Some(mode) = ctx.find_msrv {
+        let msrv =
+            find(&ctx.find_context()?, reporter)?.expect("the whole search space is searched");
+
+        return verify_freshness(reporter, ctx, msrv, mode);
+    }
+
+    if let Some(release_type) = ctx.release_type {
+        let msrv =
+            find(&ctx.find_context()?, reporter)?.expect("the whole search space is searched");
+
+        return verify_release(reporter, ctx, msrv, release_type);
+    }
+
+    let index = match &ctx.toolchain_provider {
+        // The toolchain is given, so the release index doesn't need to be fetched
+        ToolchainProvider::Distribution(distribution) => {
+            let release = Release::new_stable(distribution.version().clone());
+            ReleaseIndex::from_iter([release])
         }
+        ToolchainProvider::Tarballs(tarballs) => tarballs.release_index(ctx.toolchain.target)?,
+        _ => release_index::fetch_index(reporter, &ctx.rust_releases)?,
+    };
+
+    report_required_features(&ctx.check_cmd, &ctx.environment, reporter)?;
+
+    let check = SearchCheck {
+        ignore_lockfile: ctx.ignore_lockfile,
+        lockfile_strategy: ctx.lockfile_strategy,
+        no_check_feedback: ctx.no_check_feedback,
+        check_cmd: &ctx.check_cmd,
+        toolchain: &ctx.toolchain,
+        toolchain_provider: &ctx.toolchain_provider,
+        environment: &ctx.environment,
+        control: None,
+        deadline: None,
+        resume: false,
+        save_run: ctx.save_run.then_some("verify"),
+    };
+
+    check.run(reporter, |runner, artifacts| {
+        let verified = Verify::new(&index, runner).run(ctx, reporter);
+
+        if let Some(artifacts) = artifacts {
+            artifacts.finish(reporter, None)?;
+        }
+
+        verified.map(drop)
+    })
+}
+
+/// The check with which `find` and `verify` check each Rust version of their search: the check of
+/// the toolchain provider, within the checks which decorate it.
+struct SearchCheck<'ctx> {
+    ignore_lockfile: bool,
+    lockfile_strategy: Option<LockfileStrategy>,
+    no_check_feedback: bool,
+    check_cmd: &'ctx CheckCommandContext,
+    toolchain: &'ctx ToolchainContext,
+    toolchain_provider: &'ctx ToolchainProvider,
+    environment: &'ctx EnvironmentContext,
+    control: Option<&'ctx ControlSource>,
+    deadline: Option<&'ctx Deadline>,
+    resume: bool,
+    /// The name of the saved run, if the run is saved.
+    save_run: Option<&'static str>,
+}
+
+impl SearchCheck<'_> {
+    /// Run the search with the check, and with the artifacts of the run, if the run is saved.
+    fn run<T>(
+        self,
+        reporter: &impl Reporter,
+        search: impl FnOnce(&dyn Check, Option<&RunArtifacts>) -> TResult<T>,
+    ) -> TResult<T> {
+        let fuzz_projects = self.check_cmd.fuzz_projects(self.environment);
+
+        let vendored = self
+            .check_cmd
+            .vendor
+            .then(|| VendoredSources::create(self.environment, &fuzz_projects))
+            .transpose()?;
+        let cargo_home = vendored.as_ref().map(VendoredSources::cargo_home);
+        let runner =
+            FuzzCheck::for_projects(reporter, self.environment, &fuzz_projects, |environment| {
+                ProviderCheck::new(
+                    reporter,
+                    self.ignore_lockfile,
+                    self.no_check_feedback,
+                    environment,
+                    self.check_cmd
+                        .run_command(self.toolchain.target, environment)
+                        .with_limits(self.toolchain.limits)
+                        .with_cargo_home(cargo_home),
+                    self.toolchain_provider.clone(),
+                )
+                .with_fallback_host(self.toolchain.fallback_host)
+            });
+
+        let lockfile = (!self.ignore_lockfile).then(|| self.environment.lock());
+        let runner = LockfileCheck::new(reporter, runner, lockfile, self.lockfile_strategy);
+        let exit_codes = configured_exit_codes(self.environment)?;
+        let runner = ExitCodeCheck::new(reporter, runner, &exit_codes);
+        let runner = PredicateCheck::new(reporter, runner, self.check_cmd.predicate.as_deref());
+
+        let control = self.control.map(Control::listen).transpose()?;
+        let runner = ControlledCheck::new(reporter, runner, control.as_deref());
+        let runner = DeadlineCheck::new(runner, self.deadline);
+        let runner = SignalCheck::new(runner, signal::interrupted());
+
+        let artifacts = self
+            .save_run
+            .map(|name| RunArtifacts::create(self.environment.root(), name))
+            .transpose()?;
+        let runner = ArtifactCheck::new(runner, artifacts.as_ref());
+
+        let state = self
+            .resume
+            .then(|| search_state::load(self.environment.root()))
+            .transpose()?
+            .flatten();
+        let runner = ResumedCheck::new(runner, state.as_ref());
+
+        search(&runner, artifacts.as_ref())
+    }
+}
+
+/// Fail if the fingerprint of the environment, as `find` would report it, has another id.
+fn expect_fingerprint(
+    ctx: &VerifyContext,
+    expected: &str,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    let index = match &ctx.toolchain_provider {
+        ToolchainProvider::Tarballs(tarballs) => tarballs.release_index(ctx.toolchain.target)?,
+        _ => release_index::fetch_index(reporter, &ctx.rust_releases)?,
+    };
+
+    let fingerprint = Fingerprint::new(
+        &ctx.environment,
+        ctx.toolchain.target,
+        &ctx.check_cmd,
+        &index,
+    );
+
+    if fingerprint.id != expected {
+        return Err(CargoMSRVError::FingerprintMismatch {
+            expected: expected.to_string(),
+            actual: fingerprint.id,
+        });
     }
 
     Ok(())
 }
+
+/// Report each checked target of which the required features are enabled for the checks.
+fn report_required_features(
+    check_cmd: &CheckCommandContext,
+    environment: &EnvironmentContext,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    for required in check_cmd.required_features(environment) {
+        reporter.report_event(Warning::required_features_enabled(
+            &required.target,
+            &required.features,
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Run for the crate, and then for each of the crates included with `--also`, each with its own
+/// result. Each crate is run, even if an earlier one failed: the first failure is returned, and
+/// the later failures are reported as warnings.
+fn run_for_each_crate<C, R: Reporter>(
+    ctx: &C,
+    also: &[Utf8PathBuf],
+    reporter: &R,
+    for_crate: impl Fn(&Utf8Path) -> TResult<C>,
+    run: impl Fn(&C, &R) -> TResult<()>,
+) -> TResult<()> {
+    let mut outcome = run(ctx, reporter);
+
+    for root in also {
+        let manifest_path = root.join("Cargo.toml");
+        reporter.report_event(IncludedCrate::new(
+            package_name(&manifest_path),
+            manifest_path.clone(),
+        ))?;
+
+        let included = for_crate(root).and_then(|ctx| run(&ctx, reporter));
+
+        match included {
+            Ok(()) => {}
+            Err(error) if outcome.is_ok() => outcome = Err(error),
+            Err(error) => {
+                reporter.report_event(Warning::included_crate_failed(&manifest_path, &error))?;
+            }
+        }
+    }
+
+    outcome
+}
+
+/// The name of the package of the given manifest, if it can be read.
+fn package_name(manifest_path: &Utf8Path) -> Option<String> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(manifest_path)
+        .no_deps()
+        .exec()
+        .ok()?;
+
+    metadata
+        .root_package()
+        .map(|package| package.name.to_string())
+}
+
+/// Evict the least recently used files from the global cache, until it no longer exceeds
+/// `max_size` bytes. Eviction is best effort: a cache which can't be trimmed is left as is.
+fn evict_cache(max_size: u64) {
+    let Some(cache) = GlobalCache::new() else {
+        return;
+    };
+
+    match cache.evict(max_size) {
+        Ok(evicted) if !evicted.is_empty() => info!(
+            files = evicted.len(),
+            bytes = evicted.iter().map(|entry| entry.bytes).sum::<u64>(),
+            "evicted least recently used files from the cache"
+        ),
+        Ok(_) => {}
+        Err(error) => info!(%error, "unable to evict files from the cache"),
+    }
+}
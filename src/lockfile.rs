@@ -1,7 +1,9 @@
 use camino::{Utf8Path, Utf8PathBuf};
+use std::collections::{BTreeMap, BTreeSet};
 use std::marker::PhantomData;
 
 use crate::error::{IoError, IoErrorSource, TResult};
+use crate::semver;
 
 pub struct LockfileHandler<S: LockfileState> {
     state: Utf8PathBuf,
@@ -59,3 +61,136 @@ impl LockfileHandler<Moved> {
         })
     }
 }
+
+/// The version of the lockfile format, as given by its `version` field. Lockfiles of version 1
+/// and 2 have no such field, and can be read by every cargo which supports editions.
+pub fn lockfile_version(contents: &str) -> Option<u64> {
+    let document = contents.parse::<toml_edit::DocumentMut>().ok()?;
+    let version = document.get("version")?.as_integer()?;
+
+    u64::try_from(version).ok()
+}
+
+/// The Rust version of the first cargo which can read lockfiles of the given version, if it is
+/// known.
+pub fn readable_since(lockfile_version: u64) -> Option<semver::Version> {
+    match lockfile_version {
+        3 => Some(semver::Version::new(1, 47, 0)),
+        4 => Some(semver::Version::new(1, 78, 0)),
+        _ => None,
+    }
+}
+
+/// The most recent lockfile version which the cargo of the given Rust version can read, or
+/// `None` if it only reads lockfiles without a version.
+fn newest_readable(rust_version: &semver::Version) -> Option<u64> {
+    [4, 3]
+        .into_iter()
+        .find(|version| readable_since(*version).is_some_and(|since| rust_version >= &since))
+}
+
+/// Rewrite the lockfile to the most recent version which the cargo of the given Rust version can
+/// read. Only the `version` field is changed: the formats differ mostly in how the sources of
+/// dependencies are encoded, which older versions of cargo still parse.
+pub fn downgrade(contents: &str, rust_version: &semver::Version) -> String {
+    let target = newest_readable(rust_version);
+    let mut before_tables = true;
+
+    contents
+        .split_inclusive('\n')
+        .filter_map(|line| {
+            // The `version` of the lockfile precedes the `[[package]]` tables
+            before_tables &= !line.starts_with('[');
+
+            if !before_tables || !line.starts_with("version") {
+                return Some(line.to_string());
+            }
+
+            target.map(|version| format!("version = {}\n", version))
+        })
+        .collect()
+}
+
+/// The locked versions of each package, by name. A package may be locked at several versions, like
+/// when the dependencies require incompatible versions of it. Versions which are not valid semver
+/// are left out.
+pub fn packages(contents: &str) -> BTreeMap<String, BTreeSet<semver::Version>> {
+    let mut packages = BTreeMap::<String, BTreeSet<semver::Version>>::new();
+
+    let Ok(document) = contents.parse::<toml_edit::DocumentMut>() else {
+        return packages;
+    };
+
+    let tables = document
+        .get("package")
+        .and_then(toml_edit::Item::as_array_of_tables);
+
+    for package in tables.into_iter().flatten() {
+        let name = package.get("name").and_then(toml_edit::Item::as_str);
+        let version = package
+            .get("version")
+            .and_then(toml_edit::Item::as_str)
+            .and_then(|version| semver::Version::parse(version).ok());
+
+        if let (Some(name), Some(version)) = (name, version) {
+            packages
+                .entry(name.to_string())
+                .or_default()
+                .insert(version);
+        }
+    }
+
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCKFILE: &str = "# This file is automatically @generated by Cargo.\nversion = 4\n\n[[package]]\nname = \"a\"\nversion = \"0.1.0\"\n";
+
+    #[yare::parameterized(
+        unversioned = { "[[package]]\nname = \"a\"\nversion = \"0.1.0\"\n", None },
+        v3 = { "version = 3\n", Some(3) },
+        v4 = { LOCKFILE, Some(4) },
+        unparsable = { "version = ", None },
+    )]
+    fn version(contents: &str, expected: Option<u64>) {
+        assert_eq!(lockfile_version(contents), expected);
+    }
+
+    #[yare::parameterized(
+        readable = { 78, LOCKFILE.to_string() },
+        to_v3 = { 77, LOCKFILE.replace("version = 4", "version = 3") },
+        unversioned = { 46, LOCKFILE.replace("version = 4\n", "") },
+    )]
+    fn downgraded(minor: u64, expected: String) {
+        let downgraded = downgrade(LOCKFILE, &semver::Version::new(1, minor, 0));
+
+        assert_eq!(downgraded, expected);
+    }
+
+    #[test]
+    fn locked_packages() {
+        let contents = format!(
+            "{}\n[[package]]\nname = \"b\"\nversion = \"1.0.0\"\n\n[[package]]\nname = \"b\"\nversion = \"2.1.0\"\n",
+            LOCKFILE
+        );
+
+        let packages = packages(&contents);
+
+        assert_eq!(
+            packages.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    "a".to_string(),
+                    BTreeSet::from([semver::Version::new(0, 1, 0)])
+                ),
+                (
+                    "b".to_string(),
+                    BTreeSet::from([semver::Version::new(1, 0, 0), semver::Version::new(2, 1, 0)])
+                ),
+            ]
+        );
+    }
+}
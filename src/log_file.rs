@@ -0,0 +1,176 @@
+//! The log file of cargo-msrv, which is rotated by size: once writing to the log file would make
+//! it larger than the maximum size, it is renamed to `cargo-msrv-log.1`, the earlier rotated files
+//! are renamed to the next number, and a new log file is started. Only the given amount of rotated
+//! files is kept, so the logs of a few earlier runs are available to attach to a bug report,
+//! without the logs growing without bound.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The name of the log file, which is also the prefix of the rotated log files.
+pub const LOG_FILE_NAME: &str = "cargo-msrv-log";
+
+/// The size above which the log file is rotated, by default.
+pub const DEFAULT_MAX_LOG_SIZE: u64 = 10 * 1024 * 1024;
+
+/// The amount of rotated log files which is kept, by default.
+pub const DEFAULT_MAX_LOG_FILES: usize = 5;
+
+pub struct RotatingLogFile {
+    dir: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    file: File,
+    /// The size of the current log file.
+    size: u64,
+}
+
+impl RotatingLogFile {
+    /// Open the log file in the given directory, to append to it, and create the directory if it
+    /// doesn't exist yet.
+    pub fn open(dir: &Path, max_size: u64, max_files: usize) -> io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+
+        let file = open_append(&dir.join(LOG_FILE_NAME))?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            max_size,
+            max_files,
+            file,
+            size,
+        })
+    }
+
+    /// The path of the log file, or of the rotated log file with the given number.
+    fn path(&self, number: usize) -> PathBuf {
+        match number {
+            0 => self.dir.join(LOG_FILE_NAME),
+            n => self.dir.join(format!("{}.{}", LOG_FILE_NAME, n)),
+        }
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        if self.max_files == 0 {
+            self.file = File::create(self.path(0))?;
+        } else {
+            let oldest = self.path(self.max_files);
+            if oldest.exists() {
+                std::fs::remove_file(oldest)?;
+            }
+
+            for number in (1..self.max_files).rev() {
+                let from = self.path(number);
+                if from.exists() {
+                    std::fs::rename(from, self.path(number + 1))?;
+                }
+            }
+
+            std::fs::rename(self.path(0), self.path(1))?;
+            self.file = open_append(&self.path(0))?;
+        }
+
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // A single write larger than the maximum size still ends up in a log file of its own
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_files(dir: &Path) -> Vec<(String, String)> {
+        let mut files = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                (name, std::fs::read_to_string(&path).unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn rotated_by_size() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mut log = RotatingLogFile::open(dir.path(), 8, 2).unwrap();
+
+        for line in ["a\n", "bbbb\n", "cc\n", "ddddd\n", "e\n", "ff\n"] {
+            log.write_all(line.as_bytes()).unwrap();
+        }
+        log.flush().unwrap();
+
+        // The oldest lines, "a" and "bbbb", were rotated out, since only two rotated files are kept
+        assert_eq!(
+            log_files(dir.path()),
+            vec![
+                ("cargo-msrv-log".to_string(), "ff\n".to_string()),
+                ("cargo-msrv-log.1".to_string(), "ddddd\ne\n".to_string()),
+                ("cargo-msrv-log.2".to_string(), "cc\n".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn appends_to_existing_log() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        std::fs::write(dir.path().join(LOG_FILE_NAME), "earlier run\n").unwrap();
+
+        let mut log = RotatingLogFile::open(dir.path(), 1024, 2).unwrap();
+        log.write_all(b"this run\n").unwrap();
+        log.flush().unwrap();
+
+        assert_eq!(
+            log_files(dir.path()),
+            vec![(
+                "cargo-msrv-log".to_string(),
+                "earlier run\nthis run\n".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn without_rotated_files() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let mut log = RotatingLogFile::open(dir.path(), 4, 0).unwrap();
+
+        log.write_all(b"aaa\n").unwrap();
+        log.write_all(b"bb\n").unwrap();
+        log.flush().unwrap();
+
+        assert_eq!(
+            log_files(dir.path()),
+            vec![("cargo-msrv-log".to_string(), "bb\n".to_string())]
+        );
+    }
+}
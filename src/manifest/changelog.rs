@@ -0,0 +1,102 @@
+//! Where, and how, `cargo msrv set --changelog` and `cargo msrv find --write-msrv --changelog`
+//! record a change of the MSRV in the changelog. This can be configured in the Cargo manifest, in
+//! either the `package.metadata.cargo-msrv` or `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [package.metadata.cargo-msrv.changelog]
+//! file = "CHANGELOG.md"
+//! section = "### Changed"
+//! template = "* Raised the MSRV from Rust {old} to Rust {new}"
+//! ```
+//!
+//! The `{old}`, `{new}` and `{date}` placeholders of the template are replaced by the previous
+//! MSRV (or `none`), the new MSRV and the current date (as `YYYY-MM-DD`).
+
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::{config_value, CONFIG_TABLE};
+use camino::Utf8PathBuf;
+use cargo_metadata::Metadata;
+
+const CHANGELOG_KEY: &str = "changelog";
+
+pub const DEFAULT_FILE: &str = "CHANGELOG.md";
+pub const DEFAULT_SECTION: &str = "## [Unreleased]";
+pub const DEFAULT_TEMPLATE: &str = "* Changed the MSRV from {old} to {new} ({date})";
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChangelogConfig {
+    /// The changelog, relative to the workspace root.
+    pub file: Utf8PathBuf,
+    /// The heading of the section to which the entry is appended.
+    pub section: String,
+    /// The entry, with the `{old}`, `{new}` and `{date}` placeholders.
+    pub template: String,
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            file: Utf8PathBuf::from(DEFAULT_FILE),
+            section: DEFAULT_SECTION.to_string(),
+            template: DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+/// Read the changelog configuration from the `cargo-msrv` metadata table of the root package, or
+/// otherwise of the workspace.
+pub fn changelog_config(metadata: &Metadata) -> TResult<ChangelogConfig> {
+    let Some(config) = config_value(metadata, CHANGELOG_KEY) else {
+        return Ok(ChangelogConfig::default());
+    };
+
+    serde_json::from_value(config.clone()).map_err(|error| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a table with a 'file', 'section' and/or 'template': {}",
+            CONFIG_TABLE, CHANGELOG_KEY, error
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+
+    #[test]
+    fn not_configured() {
+        let metadata = test_metadata("null", "null");
+
+        assert_eq!(
+            changelog_config(&metadata).unwrap(),
+            ChangelogConfig::default()
+        );
+    }
+
+    #[yare::parameterized(
+        package = { r####"{ "cargo-msrv": { "changelog": { "file": "CHANGES.md", "section": "### Changed" } } }"####, "null" },
+        workspace = { "null", r####"{ "cargo-msrv": { "changelog": { "file": "CHANGES.md", "section": "### Changed" } } }"#### },
+    )]
+    fn configured(package_metadata: &str, workspace_metadata: &str) {
+        let metadata = test_metadata(package_metadata, workspace_metadata);
+        let config = changelog_config(&metadata).unwrap();
+
+        assert_eq!(config.file, "CHANGES.md");
+        assert_eq!(config.section, "### Changed");
+        assert_eq!(config.template, DEFAULT_TEMPLATE);
+    }
+
+    #[test]
+    fn invalid() {
+        let metadata = test_metadata(
+            r####"{ "cargo-msrv": { "changelog": { "heading": "### Changed" } } }"####,
+            "null",
+        );
+
+        assert!(matches!(
+            changelog_config(&metadata),
+            Err(CargoMSRVError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+//! Custom toolchains, like a locally built `rustc`, which have been linked with
+//! `rustup toolchain link <name> <path>`, and are configured to be included in the search space.
+//!
+//! These are configured in the Cargo manifest, in either the `package.metadata.cargo-msrv` or
+//! `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [[package.metadata.cargo-msrv.custom-toolchains]]
+//! name = "stage1"
+//! version = "1.66.0"
+//! ```
+
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::{config_value, CONFIG_TABLE};
+use cargo_metadata::{semver, Metadata};
+
+const CUSTOM_TOOLCHAINS_KEY: &str = "custom-toolchains";
+
+/// A toolchain linked with `rustup toolchain link`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+pub struct CustomToolchain {
+    /// The name with which the toolchain was linked.
+    pub name: String,
+    /// The Rust version on which the toolchain is based.
+    pub version: semver::Version,
+}
+
+/// Read the custom toolchains from the `cargo-msrv` metadata table of the root package, or
+/// otherwise of the workspace.
+pub fn custom_toolchains(metadata: &Metadata) -> TResult<Vec<CustomToolchain>> {
+    let Some(custom_toolchains) = config_value(metadata, CUSTOM_TOOLCHAINS_KEY) else {
+        return Ok(Vec::new());
+    };
+
+    serde_json::from_value(custom_toolchains.clone()).map_err(|error| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a list of tables with a 'name' and 'version': {}",
+            CONFIG_TABLE, CUSTOM_TOOLCHAINS_KEY, error
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+
+    #[yare::parameterized(
+        package = { r#"{ "cargo-msrv": { "custom-toolchains": [{ "name": "stage1", "version": "1.66.0" }] } }"#, "null" },
+        workspace = { "null", r#"{ "cargo-msrv": { "custom-toolchains": [{ "name": "stage1", "version": "1.66.0" }] } }"# },
+    )]
+    fn configured(package_metadata: &str, workspace_metadata: &str) {
+        let metadata = test_metadata(package_metadata, workspace_metadata);
+
+        assert_eq!(
+            custom_toolchains(&metadata).unwrap(),
+            vec![CustomToolchain {
+                name: "stage1".to_string(),
+                version: semver::Version::new(1, 66, 0),
+            }]
+        );
+    }
+
+    #[yare::parameterized(
+        no_metadata = { "null" },
+        other_metadata = { r#"{ "msrv": "1.56" }"# },
+    )]
+    fn not_configured(package_metadata: &str) {
+        let metadata = test_metadata(package_metadata, "null");
+
+        assert!(custom_toolchains(&metadata).unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "custom-toolchains": [{ "name": "stage1" }] } }"#,
+            "null",
+        );
+
+        assert!(custom_toolchains(&metadata).is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! The Rust releases which are excluded from the search space, since they were pulled after they
+//! were published, or are known to be broken, possibly only for certain hosts. A small list of such
+//! releases is shipped with cargo-msrv, which can be extended in the Cargo manifest, in either the
+//! `package.metadata.cargo-msrv` or `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [[package.metadata.cargo-msrv.denylist]]
+//! version = "1.71.0"
+//! reason = "our linker wrapper crashes on this release"
+//! hosts = ["x86_64-pc-windows-msvc"]
+//! ```
+//!
+//! A release without `hosts` is excluded for every host. A denied release can still be included
+//! with `--allow-version`.
+
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::{config_value, CONFIG_TABLE};
+use crate::semver;
+use cargo_metadata::{Metadata, MetadataCommand};
+
+const DENYLIST_KEY: &str = "denylist";
+
+/// The releases which are denied regardless of the configuration, as `(version, reason)`.
+const SHIPPED: &[(&str, &str)] = &[(
+    "1.52.0",
+    "its incremental compilation fails with 'unstable fingerprints', which 1.52.1 works around",
+)];
+
+/// A release which is excluded from the search space.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+pub struct DeniedRelease {
+    pub version: semver::Version,
+    pub reason: String,
+    /// The hosts for which the release is denied, or every host if empty.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+}
+
+impl DeniedRelease {
+    /// Whether the release is denied for the given host.
+    pub fn applies_to(&self, host: &str) -> bool {
+        self.hosts.is_empty() || self.hosts.iter().any(|denied| denied == host)
+    }
+}
+
+/// The denied releases.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Denylist {
+    releases: Vec<DeniedRelease>,
+}
+
+impl Denylist {
+    /// The releases which are shipped with cargo-msrv.
+    pub fn shipped() -> Self {
+        let releases = SHIPPED
+            .iter()
+            .map(|(version, reason)| DeniedRelease {
+                version: semver::Version::parse(version).expect("a shipped release is valid"),
+                reason: reason.to_string(),
+                hosts: Vec::new(),
+            })
+            .collect();
+
+        Self { releases }
+    }
+
+    /// Extend the list with the given releases.
+    pub fn with(mut self, releases: impl IntoIterator<Item = DeniedRelease>) -> Self {
+        self.releases.extend(releases);
+        self
+    }
+
+    /// The entry which denies the given release for the given host, unless the release is allowed
+    /// with `--allow-version`.
+    pub fn denied(
+        &self,
+        version: &semver::Version,
+        host: &str,
+        allowed: &[BareVersion],
+    ) -> Option<&DeniedRelease> {
+        if allowed
+            .iter()
+            .any(|allowed| allowed.to_comparator().matches(version))
+        {
+            return None;
+        }
+
+        self.releases
+            .iter()
+            .find(|denied| &denied.version == version && denied.applies_to(host))
+    }
+}
+
+/// Read the denied releases from the `cargo-msrv` metadata table of the root package, or otherwise
+/// of the workspace.
+pub fn denylist(metadata: &Metadata) -> TResult<Vec<DeniedRelease>> {
+    let Some(denylist) = config_value(metadata, DENYLIST_KEY) else {
+        return Ok(Vec::new());
+    };
+
+    serde_json::from_value(denylist.clone()).map_err(|error| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected an array of tables with a 'version' and a 'reason': {}",
+            CONFIG_TABLE, DENYLIST_KEY, error
+        ))
+    })
+}
+
+/// The shipped denied releases, extended with those of the Cargo manifest of the given
+/// environment.
+pub fn configured_denylist(environment: &EnvironmentContext) -> TResult<Denylist> {
+    match MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()
+    {
+        Ok(metadata) => Ok(Denylist::shipped().with(denylist(&metadata)?)),
+        // Not a Cargo project, so there is no configuration either
+        Err(_) => Ok(Denylist::shipped()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+
+    const HOST: &str = "x86_64-unknown-linux-gnu";
+
+    #[yare::parameterized(
+        package = { r#"{ "cargo-msrv": { "denylist": [{ "version": "1.71.0", "reason": "broken" }] } }"#, "null" },
+        workspace = { "null", r#"{ "cargo-msrv": { "denylist": [{ "version": "1.71.0", "reason": "broken" }] } }"# },
+    )]
+    fn configured(package_metadata: &str, workspace_metadata: &str) {
+        let metadata = test_metadata(package_metadata, workspace_metadata);
+
+        assert_eq!(
+            denylist(&metadata).unwrap(),
+            vec![DeniedRelease {
+                version: semver::Version::new(1, 71, 0),
+                reason: "broken".to_string(),
+                hosts: Vec::new(),
+            }]
+        );
+    }
+
+    #[yare::parameterized(
+        no_reason = { r#"{ "cargo-msrv": { "denylist": [{ "version": "1.71.0" }] } }"# },
+        bare_version = { r#"{ "cargo-msrv": { "denylist": [{ "version": "1.71", "reason": "broken" }] } }"# },
+        not_an_array = { r#"{ "cargo-msrv": { "denylist": "1.71.0" } }"# },
+    )]
+    fn invalid(package_metadata: &str) {
+        let metadata = test_metadata(package_metadata, "null");
+
+        assert!(matches!(
+            denylist(&metadata),
+            Err(CargoMSRVError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn shipped_releases_are_valid() {
+        assert!(!Denylist::shipped().releases.is_empty());
+    }
+
+    #[yare::parameterized(
+        denied = { "1.71.0", HOST, &[], true },
+        other_release = { "1.71.1", HOST, &[], false },
+        other_host = { "1.71.0", "aarch64-apple-darwin", &[], false },
+        allowed = { "1.71.0", HOST, &[BareVersion::ThreeComponents(1, 71, 0)], false },
+        allowed_minor = { "1.71.0", HOST, &[BareVersion::TwoComponents(1, 71)], false },
+        allowed_other = { "1.71.0", HOST, &[BareVersion::TwoComponents(1, 70)], true },
+    )]
+    fn denied(version: &str, host: &str, allowed: &[BareVersion], expected: bool) {
+        let denylist = Denylist::default().with([DeniedRelease {
+            version: semver::Version::new(1, 71, 0),
+            reason: "broken".to_string(),
+            hosts: vec![HOST.to_string()],
+        }]);
+
+        let version = semver::Version::parse(version).unwrap();
+
+        assert_eq!(denylist.denied(&version, host, allowed).is_some(), expected);
+    }
+}
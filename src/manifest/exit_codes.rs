@@ -0,0 +1,151 @@
+//! The outcome of a check which exits with a given code, for check commands which exit with
+//! distinct codes for problems of their environment, like wrapper scripts or `cross`. These are
+//! configured in the Cargo manifest, in either the `package.metadata.cargo-msrv` or
+//! `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [package.metadata.cargo-msrv.exit-codes]
+//! 3 = "skip"
+//! 4 = "inconclusive"
+//! ```
+//!
+//! A check which exits with a code which is not configured passes on `0`, and fails otherwise.
+
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::{config_value, CONFIG_TABLE};
+use cargo_metadata::{Metadata, MetadataCommand};
+use std::collections::BTreeMap;
+
+const EXIT_CODES_KEY: &str = "exit-codes";
+
+/// The outcome to which an exit code of the check is mapped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MappedOutcome {
+    /// The Rust version is compatible.
+    Pass,
+    /// The Rust version is incompatible.
+    Fail,
+    /// The check could not decide, e.g. since its environment is broken, so cargo-msrv stops,
+    /// since the checks of the other Rust versions would not decide either.
+    Inconclusive,
+    /// The Rust version is skipped, so its outcome is inconclusive, while the search continues.
+    Skip,
+}
+
+impl std::fmt::Display for MappedOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail => write!(f, "fail"),
+            Self::Inconclusive => write!(f, "inconclusive"),
+            Self::Skip => write!(f, "skip"),
+        }
+    }
+}
+
+/// The configured outcome of each exit code of the check.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExitCodeMapping {
+    outcomes: BTreeMap<i32, MappedOutcome>,
+}
+
+impl FromIterator<(i32, MappedOutcome)> for ExitCodeMapping {
+    fn from_iter<T: IntoIterator<Item = (i32, MappedOutcome)>>(outcomes: T) -> Self {
+        Self {
+            outcomes: outcomes.into_iter().collect(),
+        }
+    }
+}
+
+impl ExitCodeMapping {
+    /// The configured outcome of the given exit code, if any.
+    pub fn outcome(&self, code: i32) -> Option<MappedOutcome> {
+        self.outcomes.get(&code).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+}
+
+/// Read the outcome of each exit code from the `cargo-msrv` metadata table of the root package, or
+/// otherwise of the workspace.
+pub fn exit_codes(metadata: &Metadata) -> TResult<ExitCodeMapping> {
+    let Some(exit_codes) = config_value(metadata, EXIT_CODES_KEY) else {
+        return Ok(ExitCodeMapping::default());
+    };
+
+    let invalid = |reason: &dyn std::fmt::Display| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a table of exit codes to 'pass', 'fail', 'inconclusive' or 'skip': {}",
+            CONFIG_TABLE, EXIT_CODES_KEY, reason
+        ))
+    };
+
+    let outcomes: BTreeMap<String, MappedOutcome> =
+        serde_json::from_value(exit_codes.clone()).map_err(|error| invalid(&error))?;
+
+    outcomes
+        .into_iter()
+        .map(|(code, outcome)| {
+            code.parse::<i32>()
+                .map(|code| (code, outcome))
+                .map_err(|_| invalid(&format_args!("'{}' is not an exit code", code)))
+        })
+        .collect()
+}
+
+/// Read the outcome of each exit code, from the Cargo manifest of the given environment.
+pub fn configured_exit_codes(environment: &EnvironmentContext) -> TResult<ExitCodeMapping> {
+    match MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()
+    {
+        Ok(metadata) => exit_codes(&metadata),
+        // Not a Cargo project, so there is no configuration either
+        Err(_) => Ok(ExitCodeMapping::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+
+    #[yare::parameterized(
+        package = { r#"{ "cargo-msrv": { "exit-codes": { "3": "skip", "4": "inconclusive" } } }"#, "null" },
+        workspace = { "null", r#"{ "cargo-msrv": { "exit-codes": { "3": "skip", "4": "inconclusive" } } }"# },
+    )]
+    fn configured(package_metadata: &str, workspace_metadata: &str) {
+        let metadata = test_metadata(package_metadata, workspace_metadata);
+        let mapping = exit_codes(&metadata).unwrap();
+
+        assert_eq!(mapping.outcome(3), Some(MappedOutcome::Skip));
+        assert_eq!(mapping.outcome(4), Some(MappedOutcome::Inconclusive));
+        assert_eq!(mapping.outcome(101), None);
+    }
+
+    #[test]
+    fn not_configured() {
+        let metadata = test_metadata("null", "null");
+
+        assert!(exit_codes(&metadata).unwrap().is_empty());
+    }
+
+    #[yare::parameterized(
+        unknown_outcome = { r#"{ "cargo-msrv": { "exit-codes": { "3": "retry" } } }"# },
+        not_an_exit_code = { r#"{ "cargo-msrv": { "exit-codes": { "three": "skip" } } }"# },
+        not_a_table = { r#"{ "cargo-msrv": { "exit-codes": ["skip"] } }"# },
+    )]
+    fn invalid(package_metadata: &str) {
+        let metadata = test_metadata(package_metadata, "null");
+
+        assert!(matches!(
+            exit_codes(&metadata),
+            Err(CargoMSRVError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+//! The layout and colors of the `human` output format, which can be configured in the Cargo
+//! manifest, in either the `package.metadata.cargo-msrv` or `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [package.metadata.cargo-msrv.human-output]
+//! columns = ["status", "version", "duration"]
+//! widths = { version = 10 }
+//!
+//! [package.metadata.cargo-msrv.human-theme]
+//! success = "blue"
+//! failure = "#c0392b"
+//! ```
+
+use crate::cli::CargoMsrvOpts;
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::{config_value, CONFIG_TABLE};
+use crate::reporter::{HumanLayout, Theme};
+use cargo_metadata::{Metadata, MetadataCommand};
+
+const HUMAN_OUTPUT_KEY: &str = "human-output";
+const HUMAN_THEME_KEY: &str = "human-theme";
+
+/// The configuration of the `human` output format.
+#[derive(Debug, Default)]
+pub struct HumanOutputConfig {
+    pub layout: Option<HumanLayout>,
+    pub theme: Option<Theme>,
+}
+
+/// Read the layout of the `human` output format from the `cargo-msrv` metadata table of the
+/// root package, or otherwise of the workspace.
+pub fn human_layout(metadata: &Metadata) -> TResult<Option<HumanLayout>> {
+    let Some(layout) = config_value(metadata, HUMAN_OUTPUT_KEY) else {
+        return Ok(None);
+    };
+
+    serde_json::from_value(layout.clone()).map(Some).map_err(|error| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a table with a list of 'columns' ('status', 'version', 'duration', 'target' or 'features'), and optionally their 'widths': {}",
+            CONFIG_TABLE, HUMAN_OUTPUT_KEY, error
+        ))
+    })
+}
+
+/// Read the colors of the `human` output format from the `cargo-msrv` metadata table of the
+/// root package, or otherwise of the workspace.
+pub fn human_theme(metadata: &Metadata) -> TResult<Option<Theme>> {
+    let Some(theme) = config_value(metadata, HUMAN_THEME_KEY) else {
+        return Ok(None);
+    };
+
+    serde_json::from_value(theme.clone()).map(Some).map_err(|error| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a table with the 'success', 'failure' and 'info' colors: {}",
+            CONFIG_TABLE, HUMAN_THEME_KEY, error
+        ))
+    })
+}
+
+/// Read the configuration of the `human` output format, from the Cargo manifest selected by the
+/// given options, if any.
+pub fn configured_human_output(opts: &CargoMsrvOpts) -> TResult<HumanOutputConfig> {
+    // Without a crate, there is no configuration either; the subcommand reports why
+    let Ok(environment) = EnvironmentContext::try_from(&opts.shared_opts) else {
+        return Ok(HumanOutputConfig::default());
+    };
+
+    match MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()
+    {
+        Ok(metadata) => Ok(HumanOutputConfig {
+            layout: human_layout(&metadata)?,
+            theme: human_theme(&metadata)?,
+        }),
+        Err(_) => Ok(HumanOutputConfig::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+    use crate::reporter::Column;
+
+    #[test]
+    fn configured() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "human-output": { "columns": ["version", "status"] } } }"#,
+            "null",
+        );
+
+        assert_eq!(
+            human_layout(&metadata).unwrap(),
+            Some(HumanLayout::new(
+                vec![Column::Version, Column::Status],
+                Default::default()
+            ))
+        );
+    }
+
+    #[test]
+    fn not_configured() {
+        let metadata = test_metadata(r#"{ "msrv": "1.56" }"#, "null");
+
+        assert!(human_layout(&metadata).unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "human-output": { "columns": "version" } } }"#,
+            "null",
+        );
+
+        assert!(human_layout(&metadata).is_err());
+    }
+
+    #[test]
+    fn theme() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "human-theme": { "success": "blue" } } }"#,
+            "null",
+        );
+
+        assert!(human_theme(&metadata).unwrap().is_some());
+        assert!(human_layout(&metadata).unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_theme() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "human-theme": { "success": "sky" } } }"#,
+            "null",
+        );
+
+        assert!(human_theme(&metadata).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Where the metrics of a run are sent, which can be configured in the Cargo manifest, in either
+//! the `package.metadata.cargo-msrv` or `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [package.metadata.cargo-msrv.metrics]
+//! pushgateway = "http://pushgateway.example:9091"
+//! statsd = "127.0.0.1:8125"
+//! ```
+
+use crate::cli::CargoMsrvOpts;
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::{config_value, CONFIG_TABLE};
+use cargo_metadata::{Metadata, MetadataCommand};
+
+const METRICS_KEY: &str = "metrics";
+
+/// The endpoints to which the metrics of a run are sent, once it finishes.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// The URL of a Prometheus Pushgateway, like `http://pushgateway:9091`.
+    pub pushgateway: Option<String>,
+    /// The job under which the metrics are pushed to the Pushgateway.
+    #[serde(default = "default_job")]
+    pub job: String,
+    /// The address of a statsd server, like `127.0.0.1:8125`, to which the metrics are sent over
+    /// UDP.
+    pub statsd: Option<String>,
+    /// The prefix of the statsd metric names.
+    #[serde(default = "default_prefix")]
+    pub prefix: String,
+    /// The name of the root package, which groups the metrics of the crate.
+    #[serde(skip)]
+    pub crate_name: Option<String>,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            pushgateway: None,
+            job: default_job(),
+            statsd: None,
+            prefix: default_prefix(),
+            crate_name: None,
+        }
+    }
+}
+
+fn default_job() -> String {
+    "cargo-msrv".to_string()
+}
+
+fn default_prefix() -> String {
+    "cargo_msrv".to_string()
+}
+
+/// Read where the metrics are sent from the `cargo-msrv` metadata table of the root package, or
+/// otherwise of the workspace.
+pub fn metrics(metadata: &Metadata) -> TResult<Option<MetricsConfig>> {
+    let Some(metrics) = config_value(metadata, METRICS_KEY) else {
+        return Ok(None);
+    };
+
+    let mut config: MetricsConfig =
+        serde_json::from_value(metrics.clone()).map_err(|error| {
+            CargoMSRVError::InvalidConfig(format!(
+                "Unable to parse '{}.{}', expected a table with a 'pushgateway' URL and/or a 'statsd' address: {}",
+                CONFIG_TABLE, METRICS_KEY, error
+            ))
+        })?;
+
+    if let Some(pushgateway) = &config.pushgateway {
+        if !pushgateway.starts_with("http://") {
+            return Err(CargoMSRVError::InvalidConfig(format!(
+                "Unable to use '{}' as '{}.{}.pushgateway', only 'http://' URLs are supported",
+                pushgateway, CONFIG_TABLE, METRICS_KEY
+            )));
+        }
+    }
+
+    config.crate_name = metadata.root_package().map(|package| package.name.clone());
+
+    Ok(Some(config))
+}
+
+/// Read where the metrics are sent, from the Cargo manifest selected by the given options, if
+/// any.
+pub fn configured_metrics(opts: &CargoMsrvOpts) -> TResult<Option<MetricsConfig>> {
+    // Without a crate, there is no configuration either; the subcommand reports why
+    let Ok(environment) = EnvironmentContext::try_from(&opts.shared_opts) else {
+        return Ok(None);
+    };
+
+    match MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()
+    {
+        Ok(metadata) => metrics(&metadata),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+
+    #[test]
+    fn configured() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "metrics": { "pushgateway": "http://localhost:9091", "statsd": "127.0.0.1:8125" } } }"#,
+            "null",
+        );
+
+        assert_eq!(
+            metrics(&metadata).unwrap(),
+            Some(MetricsConfig {
+                pushgateway: Some("http://localhost:9091".to_string()),
+                job: "cargo-msrv".to_string(),
+                statsd: Some("127.0.0.1:8125".to_string()),
+                prefix: "cargo_msrv".to_string(),
+                crate_name: Some("a".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn not_configured() {
+        let metadata = test_metadata("null", "null");
+
+        assert_eq!(metrics(&metadata).unwrap(), None);
+    }
+
+    #[yare::parameterized(
+        https = { r#"{ "pushgateway": "https://localhost:9091" }"# },
+        unknown_key = { r#"{ "graphite": "localhost:2003" }"# },
+    )]
+    fn invalid(config: &str) {
+        let metadata = test_metadata(
+            &format!(r#"{{ "cargo-msrv": {{ "metrics": {} }} }}"#, config),
+            "null",
+        );
+
+        assert!(metrics(&metadata).is_err());
+    }
+}
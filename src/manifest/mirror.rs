@@ -0,0 +1,134 @@
+//! The mirror from which the toolchains are downloaded, which can be configured in the Cargo
+//! manifest, in either the `package.metadata.cargo-msrv` or `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [package.metadata.cargo-msrv.mirror]
+//! dist-server = "https://mirrors.ustc.edu.cn/rust-static"
+//! update-root = "https://mirrors.ustc.edu.cn/rust-static/rustup"
+//! ```
+//!
+//! See [`crate::rust::dist_server`] for how it's combined with `--dist-server` and the environment.
+
+use crate::cli::CargoMsrvOpts;
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::{config_value, CONFIG_TABLE};
+use cargo_metadata::{Metadata, MetadataCommand};
+
+const MIRROR_KEY: &str = "mirror";
+
+/// The servers of a mirror of `static.rust-lang.org`, like those of `RUSTUP_DIST_SERVER` and
+/// `RUSTUP_UPDATE_ROOT`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MirrorConfig {
+    /// The server from which rustup downloads the toolchains, like `https://static.rust-lang.org`.
+    pub dist_server: Option<String>,
+    /// The server from which rustup downloads updates of itself.
+    pub update_root: Option<String>,
+}
+
+/// Read the mirror from the `cargo-msrv` metadata table of the root package, or otherwise of the
+/// workspace.
+pub fn mirror(metadata: &Metadata) -> TResult<Option<MirrorConfig>> {
+    let Some(mirror) = config_value(metadata, MIRROR_KEY) else {
+        return Ok(None);
+    };
+
+    let config: MirrorConfig = serde_json::from_value(mirror.clone()).map_err(|error| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a table with a 'dist-server' and/or an 'update-root' URL: {}",
+            CONFIG_TABLE, MIRROR_KEY, error
+        ))
+    })?;
+
+    for (key, url) in [
+        ("dist-server", &config.dist_server),
+        ("update-root", &config.update_root),
+    ] {
+        if let Some(url) = url.as_deref() {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return Err(CargoMSRVError::InvalidConfig(format!(
+                    "Unable to use '{}' as '{}.{}.{}', expected an 'https://' or 'http://' URL",
+                    url, CONFIG_TABLE, MIRROR_KEY, key
+                )));
+            }
+        }
+    }
+
+    Ok(Some(config))
+}
+
+/// Read the mirror, from the Cargo manifest selected by the given options, if any.
+pub fn configured_mirror(opts: &CargoMsrvOpts) -> TResult<Option<MirrorConfig>> {
+    // Without a crate, there is no configuration either; the subcommand reports why
+    let Ok(environment) = EnvironmentContext::try_from(&opts.shared_opts) else {
+        return Ok(None);
+    };
+
+    match MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()
+    {
+        Ok(metadata) => mirror(&metadata),
+        Err(_) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+
+    #[test]
+    fn configured() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "mirror": { "dist-server": "https://mirror.example/rust-static" } } }"#,
+            "null",
+        );
+
+        assert_eq!(
+            mirror(&metadata).unwrap(),
+            Some(MirrorConfig {
+                dist_server: Some("https://mirror.example/rust-static".to_string()),
+                update_root: None,
+            })
+        );
+    }
+
+    #[test]
+    fn configured_for_workspace() {
+        let metadata = test_metadata(
+            "null",
+            r#"{ "cargo-msrv": { "mirror": { "update-root": "https://mirror.example/rustup" } } }"#,
+        );
+
+        assert_eq!(
+            mirror(&metadata)
+                .unwrap()
+                .and_then(|config| config.update_root),
+            Some("https://mirror.example/rustup".to_string())
+        );
+    }
+
+    #[test]
+    fn not_configured() {
+        let metadata = test_metadata("null", "null");
+
+        assert_eq!(mirror(&metadata).unwrap(), None);
+    }
+
+    #[yare::parameterized(
+        unknown_key = { r#"{ "cargo-msrv": { "mirror": { "server": "https://mirror.example" } } }"# },
+        not_a_url = { r#"{ "cargo-msrv": { "mirror": { "dist-server": "mirror.example" } } }"# },
+    )]
+    fn invalid(package_metadata: &str) {
+        let metadata = test_metadata(package_metadata, "null");
+
+        assert!(matches!(
+            mirror(&metadata),
+            Err(CargoMSRVError::InvalidConfig(_))
+        ));
+    }
+}
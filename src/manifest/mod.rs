@@ -4,6 +4,61 @@ use std::convert::TryFrom;
 use toml_edit::{DocumentMut, TomlError};
 
 pub(crate) mod bare_version;
+pub mod changelog;
+pub mod custom_toolchains;
+pub mod denylist;
+pub mod exit_codes;
+pub mod human_output;
+pub mod metrics;
+pub mod mirror;
+pub mod msrv_policy;
+pub mod msrv_sources;
+pub mod sync_rules;
+
+/// The table, in `package.metadata` or `workspace.metadata`, in which cargo-msrv is configured.
+pub(crate) const CONFIG_TABLE: &str = "cargo-msrv";
+
+/// The value of the given key in the `cargo-msrv` metadata table of the root package, or
+/// otherwise of the workspace.
+pub(crate) fn config_value<'m>(metadata: &'m Metadata, key: &str) -> Option<&'m serde_json::Value> {
+    let package_metadata = metadata.root_package().map(|package| &package.metadata);
+
+    package_metadata
+        .into_iter()
+        .chain(std::iter::once(&metadata.workspace_metadata))
+        .find_map(|metadata| metadata.get(CONFIG_TABLE)?.get(key))
+}
+
+/// Metadata of a single package, with the given package and workspace metadata.
+#[cfg(test)]
+pub(crate) fn test_metadata(package_metadata: &str, workspace_metadata: &str) -> Metadata {
+    let json = format!(
+        r#"{{
+  "packages": [
+    {{
+      "name": "a",
+      "version": "0.1.0",
+      "id": "a 0.1.0 (path+file:///a)",
+      "dependencies": [],
+      "targets": [],
+      "features": {{}},
+      "manifest_path": "/a/Cargo.toml",
+      "metadata": {},
+      "edition": "2021"
+    }}
+  ],
+  "workspace_members": ["a 0.1.0 (path+file:///a)"],
+  "resolve": {{ "nodes": [], "root": "a 0.1.0 (path+file:///a)" }},
+  "target_directory": "/a/target",
+  "version": 1,
+  "workspace_root": "/a",
+  "metadata": {}
+}}"#,
+        package_metadata, workspace_metadata
+    );
+
+    serde_json::from_str(&json).unwrap()
+}
 
 pub trait TomlParser {
     type Error;
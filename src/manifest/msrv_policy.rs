@@ -0,0 +1,134 @@
+//! The MSRV policy of a crate: the types of releases which may raise the MSRV, as checked by
+//! `cargo msrv verify --release-type`. The policy can be configured in the Cargo manifest, in
+//! either the `package.metadata.cargo-msrv` or `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [package.metadata.cargo-msrv]
+//! allow-msrv-bump = ["major"]
+//! ```
+//!
+//! Without a policy, minor and major releases may raise the MSRV, but patch releases may not.
+
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::{config_value, CONFIG_TABLE};
+use cargo_metadata::{Metadata, MetadataCommand};
+use std::fmt;
+
+const ALLOW_MSRV_BUMP_KEY: &str = "allow-msrv-bump";
+
+/// The type of a release, by the component of its version which is incremented.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseType {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl fmt::Display for ReleaseType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Patch => write!(f, "patch"),
+            Self::Minor => write!(f, "minor"),
+            Self::Major => write!(f, "major"),
+        }
+    }
+}
+
+/// The release types which may raise the MSRV.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MsrvPolicy {
+    allow_msrv_bump: Vec<ReleaseType>,
+}
+
+impl Default for MsrvPolicy {
+    fn default() -> Self {
+        Self {
+            allow_msrv_bump: vec![ReleaseType::Minor, ReleaseType::Major],
+        }
+    }
+}
+
+impl MsrvPolicy {
+    /// Whether a release of the given type may raise the MSRV.
+    pub fn allows_bump(&self, release_type: ReleaseType) -> bool {
+        self.allow_msrv_bump.contains(&release_type)
+    }
+}
+
+/// Read the MSRV policy from the `cargo-msrv` metadata table of the root package, or otherwise of
+/// the workspace.
+pub fn msrv_policy(metadata: &Metadata) -> TResult<MsrvPolicy> {
+    let Some(allowed) = config_value(metadata, ALLOW_MSRV_BUMP_KEY) else {
+        return Ok(MsrvPolicy::default());
+    };
+
+    let allow_msrv_bump = serde_json::from_value(allowed.clone()).map_err(|error| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a list of release types ('patch', 'minor' or 'major'): {}",
+            CONFIG_TABLE, ALLOW_MSRV_BUMP_KEY, error
+        ))
+    })?;
+
+    Ok(MsrvPolicy { allow_msrv_bump })
+}
+
+/// The MSRV policy of the crate, or the default policy if it is not a Cargo project.
+pub fn configured_msrv_policy(environment: &EnvironmentContext) -> TResult<MsrvPolicy> {
+    match MetadataCommand::new()
+        .manifest_path(environment.manifest())
+        .no_deps()
+        .exec()
+    {
+        Ok(metadata) => msrv_policy(&metadata),
+        Err(_) => Ok(MsrvPolicy::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+
+    #[yare::parameterized(
+        patch = { ReleaseType::Patch, false },
+        minor = { ReleaseType::Minor, true },
+        major = { ReleaseType::Major, true },
+    )]
+    fn default_policy(release_type: ReleaseType, allowed: bool) {
+        let metadata = test_metadata("null", "null");
+
+        assert_eq!(
+            msrv_policy(&metadata).unwrap().allows_bump(release_type),
+            allowed
+        );
+    }
+
+    #[yare::parameterized(
+        package = { r#"{ "cargo-msrv": { "allow-msrv-bump": ["major"] } }"#, "null" },
+        workspace = { "null", r#"{ "cargo-msrv": { "allow-msrv-bump": ["major"] } }"# },
+    )]
+    fn configured(package_metadata: &str, workspace_metadata: &str) {
+        let metadata = test_metadata(package_metadata, workspace_metadata);
+        let policy = msrv_policy(&metadata).unwrap();
+
+        assert!(!policy.allows_bump(ReleaseType::Minor));
+        assert!(policy.allows_bump(ReleaseType::Major));
+    }
+
+    #[test]
+    fn invalid() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "allow-msrv-bump": ["breaking"] } }"#,
+            "null",
+        );
+
+        assert!(matches!(
+            msrv_policy(&metadata),
+            Err(CargoMSRVError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,270 @@
+//! The order in which `cargo msrv verify` and `cargo msrv show` look for the MSRV of a crate, for
+//! projects which treat another file than the Cargo manifest as canonical. The order can be
+//! configured in the Cargo manifest, in either the `package.metadata.cargo-msrv` or
+//! `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [package.metadata.cargo-msrv]
+//! msrv-sources = ["clippy", "rust-version", "metadata-msrv", "toolchain-file"]
+//! ```
+//!
+//! The MSRV is taken from the first source which declares one. Without an order, the MSRV is
+//! taken from `package.rust-version`, and otherwise from `package.metadata.msrv`.
+
+use crate::declaration::{self, DeclarationKind};
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::{config_value, ManifestParseError, CONFIG_TABLE};
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::{Metadata, Package};
+use std::fmt;
+
+const MSRV_SOURCES_KEY: &str = "msrv-sources";
+
+/// A place in which the MSRV of a crate can be declared.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all(deserialize = "kebab-case", serialize = "snake_case"))]
+pub enum MsrvSource {
+    /// `package.rust-version` in the Cargo manifest, which may be inherited from the workspace.
+    RustVersion,
+    /// `package.metadata.msrv` in the Cargo manifest.
+    MetadataMsrv,
+    /// `msrv` in `clippy.toml` or `.clippy.toml`.
+    Clippy,
+    /// `toolchain.channel` in `rust-toolchain.toml` or `rust-toolchain`, if it is a Rust version.
+    ToolchainFile,
+}
+
+impl fmt::Display for MsrvSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RustVersion => write!(f, "package.rust-version"),
+            Self::MetadataMsrv => write!(f, "package.metadata.msrv"),
+            Self::Clippy => write!(f, "clippy msrv"),
+            Self::ToolchainFile => write!(f, "toolchain channel"),
+        }
+    }
+}
+
+/// The order which is used when none is configured.
+pub const DEFAULT_MSRV_SOURCES: [MsrvSource; 2] =
+    [MsrvSource::RustVersion, MsrvSource::MetadataMsrv];
+
+/// The MSRV of a crate, and the source which declares it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeclaredMsrv {
+    pub version: BareVersion,
+    pub source: MsrvSource,
+    /// The file which declares the MSRV.
+    pub file: Utf8PathBuf,
+}
+
+/// Read the order of the MSRV sources from the `cargo-msrv` metadata table of the root package, or
+/// otherwise of the workspace.
+pub fn msrv_sources(metadata: &Metadata) -> TResult<Vec<MsrvSource>> {
+    let Some(sources) = config_value(metadata, MSRV_SOURCES_KEY) else {
+        return Ok(DEFAULT_MSRV_SOURCES.to_vec());
+    };
+
+    let invalid = |reason: &dyn fmt::Display| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a list of 'rust-version', 'metadata-msrv', 'clippy' and/or 'toolchain-file': {}",
+            CONFIG_TABLE, MSRV_SOURCES_KEY, reason
+        ))
+    };
+
+    let sources: Vec<MsrvSource> =
+        serde_json::from_value(sources.clone()).map_err(|error| invalid(&error))?;
+
+    if sources.is_empty() {
+        return Err(invalid(&"the list is empty"));
+    }
+
+    Ok(sources)
+}
+
+/// The MSRV of the root package of the metadata, taken from the first of the sources which declares
+/// one.
+pub fn declared_msrv(
+    metadata: &Metadata,
+    crate_root: &Utf8Path,
+    sources: &[MsrvSource],
+) -> TResult<Option<DeclaredMsrv>> {
+    let Some(package) = metadata.root_package() else {
+        return Ok(None);
+    };
+
+    package_declared_msrv(metadata, package, crate_root, sources)
+}
+
+/// The MSRV of the given package of the metadata, like a member of the workspace, located in
+/// `crate_root`, taken from the first of the sources which declares one.
+pub fn package_declared_msrv(
+    metadata: &Metadata,
+    package: &Package,
+    crate_root: &Utf8Path,
+    sources: &[MsrvSource],
+) -> TResult<Option<DeclaredMsrv>> {
+    // The other files are only scanned if a source needs them
+    let mut declarations = None;
+
+    for &source in sources {
+        let declared = match source {
+            MsrvSource::RustVersion => package.rust_version.as_ref().map(|version| DeclaredMsrv {
+                version: BareVersion::from(version),
+                source,
+                file: package.manifest_path.clone(),
+            }),
+            MsrvSource::MetadataMsrv => package
+                .metadata
+                .get("msrv")
+                .and_then(|msrv| msrv.as_str())
+                .map(BareVersion::try_from)
+                .transpose()
+                .map_err(ManifestParseError::from)?
+                .map(|version| DeclaredMsrv {
+                    version,
+                    source,
+                    file: package.manifest_path.clone(),
+                }),
+            MsrvSource::Clippy | MsrvSource::ToolchainFile => {
+                let kind = match source {
+                    MsrvSource::Clippy => DeclarationKind::ClippyMsrv,
+                    _ => DeclarationKind::ToolchainChannel,
+                };
+
+                let declarations = match &declarations {
+                    Some(declarations) => declarations,
+                    None => declarations.insert(declaration::scan(
+                        crate_root,
+                        &metadata.workspace_root,
+                        &[],
+                    )?),
+                };
+
+                declarations
+                    .iter()
+                    .find(|declaration| declaration.kind == kind)
+                    .map(|declaration| DeclaredMsrv {
+                        version: declaration.version.clone(),
+                        source,
+                        file: metadata.workspace_root.join(&declaration.file),
+                    })
+            }
+        };
+
+        if declared.is_some() {
+            return Ok(declared);
+        }
+    }
+
+    Ok(None)
+}
+
+/// The error when none of the sources declares the MSRV. With the default sources, that is the
+/// given error, which is about the Cargo manifest.
+pub fn not_declared(
+    sources: Vec<MsrvSource>,
+    manifest: &Utf8Path,
+    in_manifest: impl FnOnce() -> CargoMSRVError,
+) -> CargoMSRVError {
+    if sources == DEFAULT_MSRV_SOURCES {
+        return in_manifest();
+    }
+
+    CargoMSRVError::NoMsrvInSources {
+        sources,
+        manifest: manifest.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+    use crate::semver;
+
+    #[test]
+    fn not_configured() {
+        let metadata = test_metadata("null", "null");
+
+        assert_eq!(msrv_sources(&metadata).unwrap(), DEFAULT_MSRV_SOURCES);
+    }
+
+    #[yare::parameterized(
+        package = { r#"{ "cargo-msrv": { "msrv-sources": ["clippy", "rust-version"] } }"#, "null" },
+        workspace = { "null", r#"{ "cargo-msrv": { "msrv-sources": ["clippy", "rust-version"] } }"# },
+    )]
+    fn configured(package_metadata: &str, workspace_metadata: &str) {
+        let metadata = test_metadata(package_metadata, workspace_metadata);
+
+        assert_eq!(
+            msrv_sources(&metadata).unwrap(),
+            [MsrvSource::Clippy, MsrvSource::RustVersion]
+        );
+    }
+
+    /// The metadata of a crate in the given directory, with the given MSRV sources and
+    /// `package.rust-version`.
+    fn crate_metadata(root: &Utf8Path, sources: &str, rust_version: Option<&str>) -> Metadata {
+        let package_metadata = format!(
+            r#"{{ "msrv": "1.40", "cargo-msrv": {{ "msrv-sources": {} }} }}"#,
+            sources
+        );
+        let mut metadata = test_metadata(&package_metadata, "null");
+        metadata.workspace_root = root.to_path_buf();
+        metadata.packages[0].manifest_path = root.join("Cargo.toml");
+        metadata.packages[0].rust_version =
+            rust_version.map(|version| semver::Version::parse(version).unwrap());
+        metadata
+    }
+
+    #[yare::parameterized(
+        rust_version = { r#"["rust-version", "clippy"]"#, Some("1.60.0"), MsrvSource::RustVersion, BareVersion::ThreeComponents(1, 60, 0) },
+        clippy = { r#"["clippy", "rust-version"]"#, Some("1.60.0"), MsrvSource::Clippy, BareVersion::TwoComponents(1, 55) },
+        metadata_msrv = { r#"["rust-version", "metadata-msrv"]"#, None, MsrvSource::MetadataMsrv, BareVersion::TwoComponents(1, 40) },
+        missing_toolchain_file = { r#"["toolchain-file", "rust-version"]"#, Some("1.60.0"), MsrvSource::RustVersion, BareVersion::ThreeComponents(1, 60, 0) },
+    )]
+    fn declared(
+        sources: &str,
+        rust_version: Option<&str>,
+        expected_source: MsrvSource,
+        expected: BareVersion,
+    ) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        std::fs::write(root.join("clippy.toml"), "msrv = \"1.55\"\n").unwrap();
+
+        let metadata = crate_metadata(root, sources, rust_version);
+        let sources = msrv_sources(&metadata).unwrap();
+        let declared = declared_msrv(&metadata, root, &sources).unwrap().unwrap();
+
+        assert_eq!(declared.source, expected_source);
+        assert_eq!(declared.version, expected);
+    }
+
+    #[test]
+    fn none_declared() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        let metadata = crate_metadata(root, r#"["clippy", "toolchain-file"]"#, Some("1.60.0"));
+
+        let sources = msrv_sources(&metadata).unwrap();
+
+        assert!(declared_msrv(&metadata, root, &sources).unwrap().is_none());
+    }
+
+    #[yare::parameterized(
+        unknown = { r#"{ "cargo-msrv": { "msrv-sources": ["readme"] } }"# },
+        empty = { r#"{ "cargo-msrv": { "msrv-sources": [] } }"# },
+    )]
+    fn invalid(package_metadata: &str) {
+        let metadata = test_metadata(package_metadata, "null");
+
+        assert!(matches!(
+            msrv_sources(&metadata),
+            Err(CargoMSRVError::InvalidConfig(_))
+        ));
+    }
+}
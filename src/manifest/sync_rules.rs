@@ -0,0 +1,161 @@
+//! User-defined declarations of the MSRV, in files which cargo-msrv does not recognize by itself,
+//! like a Dockerfile or the documentation. These are compared by `cargo msrv check-consistency`,
+//! and updated by `cargo msrv sync` and `cargo msrv set --sync`.
+//!
+//! The rules are configured in the Cargo manifest, in either the `package.metadata.cargo-msrv`
+//! or `workspace.metadata.cargo-msrv` table:
+//!
+//! ```toml
+//! [[package.metadata.cargo-msrv.sync-rules]]
+//! file = "Dockerfile"
+//! kind = "regex"
+//! pattern = 'FROM rust:(\d+\.\d+)'
+//!
+//! [[package.metadata.cargo-msrv.sync-rules]]
+//! file = "docs/book.toml"
+//! kind = "toml-path"
+//! pattern = "preprocessor.variables.msrv"
+//! ```
+
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::{config_value, CONFIG_TABLE};
+use camino::Utf8PathBuf;
+use cargo_metadata::Metadata;
+use regex::Regex;
+
+const SYNC_RULES_KEY: &str = "sync-rules";
+
+/// The name of the capture group of a `regex` rule, which captures the Rust version. Without
+/// this group, the first capture group captures the Rust version.
+pub const VERSION_GROUP: &str = "msrv";
+
+/// A file, and where in that file the MSRV is declared.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+pub struct SyncRule {
+    /// The file, relative to the workspace root.
+    pub file: Utf8PathBuf,
+    pub kind: SyncRuleKind,
+    /// The regular expression, or the dotted path of the key, which locates the Rust version.
+    pub pattern: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncRuleKind {
+    /// A regular expression, of which a capture group captures the Rust version.
+    Regex,
+    /// The dotted path of a key in a TOML file, like `tool.msrv`.
+    TomlPath,
+    /// The dotted path of a key in a YAML file, like `env.MSRV`.
+    YamlPath,
+}
+
+impl SyncRule {
+    /// The regular expression of a `regex` rule.
+    pub fn regex(&self) -> TResult<Regex> {
+        let regex = Regex::new(&self.pattern).map_err(|error| self.invalid(error))?;
+
+        if regex.captures_len() < 2 {
+            return Err(self.invalid(format_args!(
+                "expected a capture group which captures the Rust version, like '(\\d+\\.\\d+)' or '(?<{}>...)'",
+                VERSION_GROUP
+            )));
+        }
+
+        Ok(regex)
+    }
+
+    /// The keys of the dotted path of a `toml-path` or `yaml-path` rule.
+    pub fn path(&self) -> Vec<&str> {
+        self.pattern.split('.').map(str::trim).collect()
+    }
+
+    fn validate(&self) -> TResult<()> {
+        match self.kind {
+            SyncRuleKind::Regex => self.regex().map(drop),
+            SyncRuleKind::TomlPath | SyncRuleKind::YamlPath => {
+                if self.path().iter().any(|key| key.is_empty()) {
+                    return Err(self.invalid("expected a dotted path of keys, like 'env.MSRV'"));
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn invalid(&self, reason: impl std::fmt::Display) -> CargoMSRVError {
+        CargoMSRVError::InvalidConfig(format!(
+            "Invalid pattern '{}' of the '{}.{}' rule for '{}': {}",
+            self.pattern, CONFIG_TABLE, SYNC_RULES_KEY, self.file, reason
+        ))
+    }
+}
+
+/// Read the sync rules from the `cargo-msrv` metadata table of the root package, or otherwise of
+/// the workspace.
+pub fn sync_rules(metadata: &Metadata) -> TResult<Vec<SyncRule>> {
+    let Some(rules) = config_value(metadata, SYNC_RULES_KEY) else {
+        return Ok(Vec::new());
+    };
+
+    let rules: Vec<SyncRule> = serde_json::from_value(rules.clone()).map_err(|error| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Unable to parse '{}.{}', expected a list of tables with a 'file', 'kind' ('regex', 'toml-path' or 'yaml-path') and 'pattern': {}",
+            CONFIG_TABLE, SYNC_RULES_KEY, error
+        ))
+    })?;
+
+    for rule in &rules {
+        rule.validate()?;
+    }
+
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::test_metadata;
+
+    #[test]
+    fn configured() {
+        let metadata = test_metadata(
+            r#"{ "cargo-msrv": { "sync-rules": [{ "file": "Dockerfile", "kind": "regex", "pattern": "FROM rust:(\\d+\\.\\d+)" }] } }"#,
+            "null",
+        );
+
+        assert_eq!(
+            sync_rules(&metadata).unwrap(),
+            vec![SyncRule {
+                file: Utf8PathBuf::from("Dockerfile"),
+                kind: SyncRuleKind::Regex,
+                pattern: r"FROM rust:(\d+\.\d+)".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn not_configured() {
+        let metadata = test_metadata("null", "null");
+
+        assert!(sync_rules(&metadata).unwrap().is_empty());
+    }
+
+    #[yare::parameterized(
+        unknown_kind = { r#"{ "file": "Dockerfile", "kind": "glob", "pattern": "*" }"# },
+        invalid_regex = { r#"{ "file": "Dockerfile", "kind": "regex", "pattern": "rust:(" }"# },
+        without_group = { r#"{ "file": "Dockerfile", "kind": "regex", "pattern": "rust:1\\.60" }"# },
+        empty_key = { r#"{ "file": "book.toml", "kind": "toml-path", "pattern": "tool..msrv" }"# },
+    )]
+    fn invalid(rule: &str) {
+        let metadata = test_metadata(
+            &format!(r#"{{ "cargo-msrv": {{ "sync-rules": [{rule}] }} }}"#),
+            "null",
+        );
+
+        assert!(matches!(
+            sync_rules(&metadata),
+            Err(CargoMSRVError::InvalidConfig(_))
+        ));
+    }
+}
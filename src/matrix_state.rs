@@ -0,0 +1,146 @@
+//! The MSRV of each target of `cargo msrv find --targets`, so a later run with `--resume` only
+//! searches the targets which are missing, instead of each target again.
+//!
+//! The state is saved to `.cargo-msrv/matrix.json`, in the root of the crate, after each target of
+//! which the MSRV was found. The saved MSRV of a target may no longer hold, e.g. after a change of
+//! the source code, so a resumed run only checks the boundary of the target: the MSRV itself, which
+//! must still be compatible, and the release before it, which must still be incompatible. If
+//! either check disagrees, the MSRV of the target is searched again.
+
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::run_artifacts::ignore_artifacts;
+use crate::semver;
+use camino::{Utf8Path, Utf8PathBuf};
+
+/// The file, relative to the root of the crate, to which the MSRV of each target is saved.
+pub const MATRIX_STATE_FILE: &str = ".cargo-msrv/matrix.json";
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MatrixState {
+    /// The targets of which the MSRV was found, in the order in which they were searched.
+    pub targets: Vec<TargetCell>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TargetCell {
+    pub target: String,
+    pub msrv: semver::Version,
+}
+
+impl MatrixState {
+    /// The saved MSRV of the given target, if any.
+    pub fn msrv(&self, target: &str) -> Option<&semver::Version> {
+        self.targets
+            .iter()
+            .find(|cell| cell.target == target)
+            .map(|cell| &cell.msrv)
+    }
+
+    /// Set the MSRV of the given target, or forget it, if the target has no MSRV.
+    pub fn set(&mut self, target: &str, msrv: Option<semver::Version>) {
+        self.targets.retain(|cell| cell.target != target);
+
+        if let Some(msrv) = msrv {
+            self.targets.push(TargetCell {
+                target: target.to_string(),
+                msrv,
+            });
+        }
+    }
+}
+
+/// Save the MSRV of each target of the crate at `root`, and return the path of the state file.
+pub fn save(root: &Utf8Path, state: &MatrixState) -> TResult<Utf8PathBuf> {
+    let path = root.join(MATRIX_STATE_FILE);
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::CreateDir(dir.to_path_buf()),
+        })?;
+    }
+    ignore_artifacts(&path)?;
+
+    let contents = serde_json::to_string_pretty(state).expect("a matrix state can be serialized");
+    std::fs::write(&path, contents).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::WriteFile(path.clone()),
+    })?;
+
+    Ok(path)
+}
+
+/// Load the saved MSRV of each target of the crate at `root`, if any.
+pub fn load(root: &Utf8Path) -> TResult<Option<MatrixState>> {
+    let path = root.join(MATRIX_STATE_FILE);
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(IoError {
+                error,
+                source: IoErrorSource::ReadFile(path),
+            }
+            .into())
+        }
+    };
+
+    // A state which can't be read, e.g. of another version of cargo-msrv, is searched anew
+    match serde_json::from_str(&contents) {
+        Ok(state) => Ok(Some(state)),
+        Err(error) => {
+            info!(%error, %path, "ignored the unreadable matrix state");
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_and_loaded() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        assert_eq!(load(root).unwrap(), None);
+
+        let mut state = MatrixState::default();
+        state.set(
+            "wasm32-unknown-unknown",
+            Some(semver::Version::new(1, 65, 0)),
+        );
+
+        let path = save(root, &state).unwrap();
+        assert_eq!(path, root.join(MATRIX_STATE_FILE));
+        assert_eq!(load(root).unwrap(), Some(state));
+    }
+
+    #[test]
+    fn set() {
+        let mut state = MatrixState::default();
+        state.set(
+            "x86_64-unknown-linux-gnu",
+            Some(semver::Version::new(1, 60, 0)),
+        );
+        state.set(
+            "wasm32-unknown-unknown",
+            Some(semver::Version::new(1, 65, 0)),
+        );
+
+        state.set(
+            "x86_64-unknown-linux-gnu",
+            Some(semver::Version::new(1, 62, 0)),
+        );
+        assert_eq!(
+            state.msrv("x86_64-unknown-linux-gnu"),
+            Some(&semver::Version::new(1, 62, 0))
+        );
+
+        state.set("wasm32-unknown-unknown", None);
+        assert_eq!(state.msrv("wasm32-unknown-unknown"), None);
+        assert_eq!(state.targets.len(), 1);
+    }
+}
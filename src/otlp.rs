@@ -0,0 +1,508 @@
+//! Exports the `tracing` spans of a run as [OpenTelemetry] traces, with OTLP over HTTP (in the
+//! JSON encoding), so runs of cargo-msrv show up in the observability stack of a CI system, with
+//! the time spent on fetching the release index, setting up each toolchain, and each check.
+//!
+//! The spans are collected by the [`OtlpLayer`] as they close, and sent at once when the
+//! [`OtlpGuard`] is finished, at the end of the program.
+//!
+//! The endpoint, and headers like an API key, are configured with the environment variables of
+//! the [OTLP exporter]: `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` or `OTEL_EXPORTER_OTLP_ENDPOINT`, and
+//! `OTEL_EXPORTER_OTLP_HEADERS`. When the `TRACEPARENT` environment variable is set, e.g. by the
+//! CI system, the spans are part of its trace.
+//!
+//! [OpenTelemetry]: https://opentelemetry.io
+//! [OTLP exporter]: https://opentelemetry.io/docs/specs/otel/protocol/exporter/
+
+use crate::{CargoMSRVError, TResult};
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+pub const OTLP_ENDPOINT_ENV_VAR: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+pub const OTLP_TRACES_ENDPOINT_ENV_VAR: &str = "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT";
+pub const OTLP_HEADERS_ENV_VAR: &str = "OTEL_EXPORTER_OTLP_HEADERS";
+pub const TRACEPARENT_ENV_VAR: &str = "TRACEPARENT";
+
+/// How long to wait for the collector, so an unreachable collector doesn't hold up cargo-msrv.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The URL to which the traces are sent: the given base URL, or otherwise the URL configured with
+/// the environment variables of the OTLP exporter, if any.
+pub fn traces_endpoint(base: Option<&str>) -> Option<String> {
+    let from_env = |key| std::env::var(key).ok().filter(|value| !value.is_empty());
+
+    match base {
+        Some(base) => Some(traces_url(base)),
+        None => from_env(OTLP_TRACES_ENDPOINT_ENV_VAR)
+            .or_else(|| from_env(OTLP_ENDPOINT_ENV_VAR).map(|base| traces_url(&base))),
+    }
+}
+
+fn traces_url(base: &str) -> String {
+    format!("{}/v1/traces", base.trim_end_matches('/'))
+}
+
+/// A layer which collects the spans, and the guard which exports them when finished.
+pub fn layer(endpoint: String) -> (OtlpLayer, OtlpGuard) {
+    let parent = std::env::var(TRACEPARENT_ENV_VAR)
+        .ok()
+        .and_then(|traceparent| parse_traceparent(&traceparent));
+
+    let ids = IdGenerator::default();
+    let (trace_id, parent_span_id) = match parent {
+        Some((trace_id, span_id)) => (trace_id, Some(span_id)),
+        None => (ids.trace_id(), None),
+    };
+
+    let spans = Arc::new(Mutex::new(Vec::new()));
+
+    let layer = OtlpLayer {
+        trace_id,
+        parent_span_id,
+        ids,
+        spans: Arc::clone(&spans),
+    };
+
+    let guard = OtlpGuard {
+        endpoint,
+        headers: std::env::var(OTLP_HEADERS_ENV_VAR)
+            .map(|headers| parse_headers(&headers))
+            .unwrap_or_default(),
+        spans,
+    };
+
+    (layer, guard)
+}
+
+/// Collects the spans of the run, as they close.
+pub struct OtlpLayer {
+    trace_id: String,
+    /// The span of the CI system, of which the spans of the run are children.
+    parent_span_id: Option<String>,
+    ids: IdGenerator,
+    spans: Arc<Mutex<Vec<SpanData>>>,
+}
+
+/// Sends the collected spans to the collector, when finished. If the guard is dropped before, the
+/// spans are sent when it's dropped, and a failure is only logged.
+pub struct OtlpGuard {
+    endpoint: String,
+    headers: Vec<(String, String)>,
+    spans: Arc<Mutex<Vec<SpanData>>>,
+}
+
+impl OtlpGuard {
+    /// Send the collected spans to the collector.
+    pub fn finish(self) -> TResult<()> {
+        self.export()
+    }
+
+    fn export(&self) -> TResult<()> {
+        let spans = std::mem::take(
+            &mut *self
+                .spans
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+
+        if spans.is_empty() {
+            return Ok(());
+        }
+
+        let mut request = ureq::post(&self.endpoint)
+            .timeout(TIMEOUT)
+            .set("Content-Type", "application/json");
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+
+        request
+            .send_string(&export_request(&spans).to_string())
+            .map_err(|error| CargoMSRVError::ExportTraces {
+                endpoint: self.endpoint.clone(),
+                error: Box::new(error),
+            })?;
+
+        Ok(())
+    }
+}
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        // The spans are already taken if the guard was finished
+        if let Err(error) = self.export() {
+            tracing::warn!(%error, "unable to export the traces");
+        }
+    }
+}
+
+/// A closed span, as exported.
+#[derive(Clone, Debug, PartialEq)]
+struct SpanData {
+    trace_id: String,
+    span_id: String,
+    parent_span_id: Option<String>,
+    name: String,
+    start: SystemTime,
+    end: SystemTime,
+    attributes: Vec<(String, AttributeValue)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum AttributeValue {
+    String(String),
+    Int(i64),
+    Double(f64),
+    Bool(bool),
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'lookup> LookupSpan<'lookup>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let parent_span_id = span
+            .parent()
+            .and_then(|parent| {
+                parent
+                    .extensions()
+                    .get::<SpanData>()
+                    .map(|data| data.span_id.clone())
+            })
+            .or_else(|| self.parent_span_id.clone());
+
+        let mut data = SpanData {
+            trace_id: self.trace_id.clone(),
+            span_id: self.ids.span_id(),
+            parent_span_id,
+            name: span.name().to_string(),
+            start: SystemTime::now(),
+            end: SystemTime::now(),
+            attributes: Vec::new(),
+        };
+        attrs.record(&mut AttributeVisitor(&mut data.attributes));
+
+        span.extensions_mut().insert(data);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+                values.record(&mut AttributeVisitor(&mut data.attributes));
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+
+        let Some(mut data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+        data.end = SystemTime::now();
+
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.push(data);
+        }
+    }
+}
+
+struct AttributeVisitor<'a>(&'a mut Vec<(String, AttributeValue)>);
+
+impl AttributeVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: AttributeValue) {
+        let name = field.name().to_string();
+        self.0.retain(|(key, _)| *key != name);
+        self.0.push((name, value));
+    }
+}
+
+impl Visit for AttributeVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, AttributeValue::Double(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, AttributeValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match i64::try_from(value) {
+            Ok(value) => self.insert(field, AttributeValue::Int(value)),
+            Err(_) => self.insert(field, AttributeValue::String(value.to_string())),
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, AttributeValue::Bool(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, AttributeValue::String(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, AttributeValue::String(format!("{:?}", value)));
+    }
+}
+
+/// The body of an OTLP/HTTP request with the given spans, in the JSON encoding.
+fn export_request(spans: &[SpanData]) -> serde_json::Value {
+    let spans = spans
+        .iter()
+        .map(|span| {
+            let mut json = serde_json::json!({
+                "traceId": span.trace_id,
+                "spanId": span.span_id,
+                "name": span.name,
+                // SPAN_KIND_INTERNAL
+                "kind": 1,
+                "startTimeUnixNano": unix_nanos(span.start).to_string(),
+                "endTimeUnixNano": unix_nanos(span.end).to_string(),
+                "attributes": attributes(&span.attributes),
+            });
+
+            if let Some(parent_span_id) = &span.parent_span_id {
+                json["parentSpanId"] = parent_span_id.clone().into();
+            }
+
+            json
+        })
+        .collect::<Vec<_>>();
+
+    let resource = [
+        (
+            "service.name".to_string(),
+            AttributeValue::String("cargo-msrv".to_string()),
+        ),
+        (
+            "service.version".to_string(),
+            AttributeValue::String(env!("CARGO_PKG_VERSION").to_string()),
+        ),
+    ];
+
+    serde_json::json!({
+        "resourceSpans": [{
+            "resource": { "attributes": attributes(&resource) },
+            "scopeSpans": [{
+                "scope": { "name": "cargo-msrv", "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+fn attributes(attributes: &[(String, AttributeValue)]) -> serde_json::Value {
+    attributes
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                AttributeValue::String(value) => serde_json::json!({ "stringValue": value }),
+                // 64-bit integers are encoded as strings
+                AttributeValue::Int(value) => serde_json::json!({ "intValue": value.to_string() }),
+                AttributeValue::Double(value) => serde_json::json!({ "doubleValue": value }),
+                AttributeValue::Bool(value) => serde_json::json!({ "boolValue": value }),
+            };
+
+            serde_json::json!({ "key": key, "value": value })
+        })
+        .collect()
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default()
+}
+
+/// The trace and span id of a W3C `traceparent`, like
+/// `00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01`.
+fn parse_traceparent(traceparent: &str) -> Option<(String, String)> {
+    let mut parts = traceparent.trim().split('-');
+    let (version, trace_id, span_id) = (parts.next()?, parts.next()?, parts.next()?);
+
+    let is_hex = |id: &str, len: usize| {
+        id.len() == len
+            && id.bytes().all(|b| b.is_ascii_hexdigit())
+            && id.bytes().any(|b| b != b'0')
+    };
+
+    (version == "00" && is_hex(trace_id, 32) && is_hex(span_id, 16))
+        .then(|| (trace_id.to_ascii_lowercase(), span_id.to_ascii_lowercase()))
+}
+
+/// The headers of `OTEL_EXPORTER_OTLP_HEADERS`, like `api-key=secret,team=rust`.
+fn parse_headers(headers: &str) -> Vec<(String, String)> {
+    headers
+        .split(',')
+        .filter_map(|header| {
+            let (name, value) = header.split_once('=')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .filter(|(name, _)| !name.is_empty())
+        .collect()
+}
+
+/// Generates random ids, as hex. The ids only need to be unique, not unpredictable, so the
+/// randomly seeded hasher of the standard library suffices.
+#[derive(Default)]
+struct IdGenerator {
+    counter: AtomicU64,
+}
+
+impl IdGenerator {
+    fn random(&self) -> u64 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(self.counter.fetch_add(1, Ordering::Relaxed));
+        hasher.write_u128(unix_nanos(SystemTime::now()));
+        hasher.finish()
+    }
+
+    fn trace_id(&self) -> String {
+        format!("{:016x}{:016x}", self.random(), self.random())
+    }
+
+    fn span_id(&self) -> String {
+        format!("{:016x}", self.random())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn collected(parent_span_id: Option<&str>) -> Vec<SpanData> {
+        let spans = Arc::new(Mutex::new(Vec::new()));
+        let layer = OtlpLayer {
+            trace_id: "0af7651916cd43dd8448eb211c80319c".to_string(),
+            parent_span_id: parent_span_id.map(String::from),
+            ids: IdGenerator::default(),
+            spans: Arc::clone(&spans),
+        };
+
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let run = tracing::info_span!("run", subcommand = "find");
+            let _run = run.enter();
+
+            let check = tracing::info_span!("check", toolchain = "1.60.0", units = 3u64);
+            check.in_scope(|| {});
+        });
+
+        let spans = spans.lock().unwrap().clone();
+        spans
+    }
+
+    #[test]
+    fn nested_spans() {
+        let spans = collected(None);
+
+        assert_eq!(spans.len(), 2);
+        let (check, run) = (&spans[0], &spans[1]);
+
+        assert_eq!(check.name, "check");
+        assert_eq!(check.parent_span_id.as_deref(), Some(run.span_id.as_str()));
+        assert_eq!(
+            check.attributes,
+            vec![
+                (
+                    "toolchain".to_string(),
+                    AttributeValue::String("1.60.0".to_string())
+                ),
+                ("units".to_string(), AttributeValue::Int(3)),
+            ]
+        );
+        assert_eq!(run.parent_span_id, None);
+        assert!(run.start <= check.start && check.end <= run.end);
+    }
+
+    #[test]
+    fn child_of_ci_span() {
+        let spans = collected(Some("b7ad6b7169203331"));
+
+        assert_eq!(spans[1].parent_span_id.as_deref(), Some("b7ad6b7169203331"));
+    }
+
+    #[test]
+    fn request() {
+        let spans = collected(None);
+        let request = export_request(&spans);
+
+        let exported = &request["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(exported[0]["name"], "check");
+        assert_eq!(exported[0]["traceId"], "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(exported[0]["parentSpanId"], spans[1].span_id.as_str());
+        assert_eq!(
+            exported[0]["attributes"][1],
+            serde_json::json!({ "key": "units", "value": { "intValue": "3" } })
+        );
+        assert!(exported[1].get("parentSpanId").is_none());
+    }
+
+    #[test]
+    fn unreachable_collector() {
+        // A port on which nothing listens, once the listener is dropped
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let guard = OtlpGuard {
+            endpoint: format!("http://127.0.0.1:{}/v1/traces", port),
+            headers: Vec::new(),
+            spans: Arc::new(Mutex::new(collected(None))),
+        };
+        let error = guard.finish().unwrap_err();
+
+        assert!(matches!(error, CargoMSRVError::ExportTraces { .. }));
+    }
+
+    #[yare::parameterized(
+        valid = { "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01", Some(("0af7651916cd43dd8448eb211c80319c", "b7ad6b7169203331")) },
+        zero_trace = { "00-00000000000000000000000000000000-b7ad6b7169203331-01", None },
+        other_version = { "01-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01", None },
+        truncated = { "00-0af7651916cd43dd", None },
+    )]
+    fn traceparent(traceparent: &str, expected: Option<(&str, &str)>) {
+        assert_eq!(
+            parse_traceparent(traceparent),
+            expected.map(|(trace, span)| (trace.to_string(), span.to_string()))
+        );
+    }
+
+    #[test]
+    fn headers() {
+        assert_eq!(
+            parse_headers("api-key=secret, x-team = rust,invalid"),
+            vec![
+                ("api-key".to_string(), "secret".to_string()),
+                ("x-team".to_string(), "rust".to_string()),
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        base = { "http://localhost:4318", "http://localhost:4318/v1/traces" },
+        trailing_slash = { "http://localhost:4318/", "http://localhost:4318/v1/traces" },
+    )]
+    fn endpoint(base: &str, expected: &str) {
+        assert_eq!(traces_endpoint(Some(base)).as_deref(), Some(expected));
+    }
+}
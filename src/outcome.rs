@@ -13,16 +13,36 @@ pub enum Outcome {
 
 impl Outcome {
     pub fn new_success(toolchain_spec: Toolchain) -> Self {
-        Self::Success(SuccessOutcome { toolchain_spec })
+        Self::new_success_with_output(toolchain_spec, String::new())
+    }
+
+    /// A successful outcome, which retains the (diagnostic) output of the check, e.g. warnings.
+    pub fn new_success_with_output(toolchain_spec: Toolchain, output: String) -> Self {
+        Self::Success(SuccessOutcome {
+            toolchain_spec,
+            output,
+        })
     }
 
     pub fn new_failure(toolchain_spec: Toolchain, error_message: String) -> Self {
         Self::Failure(FailureOutcome {
             toolchain_spec,
             error_message,
+            exit_code: None,
         })
     }
 
+    /// Record the exit code of the failed check command, if it exited with one.
+    pub fn with_exit_code(self, code: Option<i32>) -> Self {
+        match self {
+            Self::Failure(outcome) => Self::Failure(FailureOutcome {
+                exit_code: code,
+                ..outcome
+            }),
+            success => success,
+        }
+    }
+
     pub fn is_success(&self) -> bool {
         match self {
             Self::Success { .. } => true,
@@ -37,6 +57,24 @@ impl Outcome {
         }
     }
 
+    /// The diagnostic output of the check: the error message of a failure, or the retained
+    /// output of a success.
+    pub fn output(&self) -> &str {
+        match self {
+            Self::Success(outcome) => &outcome.output,
+            Self::Failure(outcome) => &outcome.error_message,
+        }
+    }
+
+    /// The exit code of the check command: zero for a success, and for a failure, the recorded
+    /// code, if any.
+    pub fn exit_code(&self) -> Option<i32> {
+        match self {
+            Self::Success(_) => Some(0),
+            Self::Failure(outcome) => outcome.exit_code,
+        }
+    }
+
     pub fn toolchain_spec(&self) -> &Toolchain {
         match self {
             Self::Success(outcome) => &outcome.toolchain_spec,
@@ -48,12 +86,14 @@ impl Outcome {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SuccessOutcome {
     pub(crate) toolchain_spec: Toolchain,
+    pub(crate) output: String,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FailureOutcome {
     pub(crate) toolchain_spec: Toolchain,
     pub(crate) error_message: String,
+    pub(crate) exit_code: Option<i32>,
 }
 
 #[cfg(test)]
@@ -84,5 +124,7 @@ mod tests {
         assert!(!outcome.is_success());
         assert_eq!(outcome.version(), &semver::Version::new(1, 2, 3));
         assert_eq!(outcome.toolchain_spec(), &toolchain);
+        assert_eq!(outcome.exit_code(), None);
+        assert_eq!(outcome.with_exit_code(Some(101)).exit_code(), Some(101));
     }
 }
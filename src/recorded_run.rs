@@ -0,0 +1,302 @@
+//! A run of cargo-msrv, as recorded with `--log-events`, of which `cargo msrv report` renders a
+//! report. The event log holds the events of the run as newline delimited JSON, like the `json`
+//! output format.
+//!
+//! Only the events which are part of a report are read: the subcommand and its result, the
+//! checks, the warnings, and how the run terminated. Other events, and lines which are not events,
+//! are ignored.
+
+use crate::semver;
+use serde::Deserialize;
+
+/// The parts of a recorded run which are reported.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RecordedRun {
+    /// The version of cargo-msrv which recorded the run.
+    pub cargo_msrv_version: Option<String>,
+    /// The subcommand of the run, like `find` or `verify`.
+    pub subcommand: Option<String>,
+    /// The final result of the subcommand, if it is a `find` or `verify` result.
+    pub result: Option<RecordedResult>,
+    /// The checks, in the order in which they finished.
+    pub checks: Vec<RecordedCheck>,
+    pub warnings: Vec<RecordedWarning>,
+    /// Why the run failed, if it failed.
+    pub failure: Option<String>,
+    /// How the run ended, unless the log ends before the run.
+    pub termination: Option<RecordedTermination>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecordedResult {
+    /// The MSRV which was found, or `None` if no Rust version is compatible.
+    Msrv(Option<semver::Version>),
+    /// The Rust version which was verified, and whether it is compatible.
+    Verified {
+        version: semver::Version,
+        compatible: bool,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+pub struct RecordedCheck {
+    pub toolchain: RecordedToolchain,
+    pub is_compatible: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl RecordedCheck {
+    /// The first error in the error output of the check, as a single line: the first line which
+    /// starts with `error`, or the first non-empty line if no such line exists.
+    pub fn first_error(&self) -> Option<&str> {
+        let error = self.error.as_deref()?;
+        let mut lines = error.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        lines
+            .clone()
+            .find(|line| line.starts_with("error"))
+            .or_else(|| lines.next())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+pub struct RecordedToolchain {
+    pub version: semver::Version,
+    pub target: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+pub struct RecordedWarning {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+pub struct RecordedTermination {
+    /// Like `success`, `failure` or `interrupted`.
+    pub outcome: String,
+    pub duration_ms: u64,
+}
+
+impl RecordedTermination {
+    pub fn is_success(&self) -> bool {
+        self.outcome == "success"
+    }
+}
+
+/// An event of the log which is part of a report, but which can't be read.
+#[derive(Debug, thiserror::Error)]
+#[error("the event on line {line} is invalid: {error}")]
+pub struct InvalidEvent {
+    pub line: usize,
+    pub error: serde_json::Error,
+}
+
+#[derive(Deserialize)]
+struct MetaEvent {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct SubcommandEvent {
+    subcommand_id: String,
+}
+
+#[derive(Deserialize)]
+struct FindResultEvent {
+    result: FindResultDetails,
+}
+
+#[derive(Deserialize)]
+struct FindResultDetails {
+    version: Option<semver::Version>,
+}
+
+#[derive(Deserialize)]
+struct TargetsResultEvent {
+    result: TargetsResultDetails,
+}
+
+#[derive(Deserialize)]
+struct TargetsResultDetails {
+    msrv: Option<semver::Version>,
+}
+
+#[derive(Deserialize)]
+struct VerifyResultEvent {
+    result: RecordedCheck,
+}
+
+#[derive(Deserialize)]
+struct FailureEvent {
+    reason: FailureReason,
+}
+
+#[derive(Deserialize)]
+struct FailureReason {
+    description: String,
+}
+
+impl RecordedRun {
+    /// Read the run from the contents of its event log.
+    pub fn from_event_log(contents: &str) -> Result<Self, InvalidEvent> {
+        let mut run = Self::default();
+
+        for (index, line) in contents.lines().enumerate() {
+            // Not every line is necessarily an event, e.g. when stdout and stderr were combined
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(line) else {
+                continue;
+            };
+
+            run.record(event).map_err(|error| InvalidEvent {
+                line: index + 1,
+                error,
+            })?;
+        }
+
+        Ok(run)
+    }
+
+    /// Whether any event of a run was recorded.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the run searched for the MSRV, so incompatible checks were expected.
+    pub fn is_search(&self) -> bool {
+        matches!(self.result, Some(RecordedResult::Msrv(_)))
+            || self.subcommand.as_deref() == Some("find")
+    }
+
+    /// Whether the run succeeded, or if the log ends before the run, whether it has not failed yet.
+    pub fn is_success(&self) -> bool {
+        self.failure.is_none()
+            && self
+                .termination
+                .as_ref()
+                .map_or(true, RecordedTermination::is_success)
+    }
+
+    fn record(&mut self, event: serde_json::Value) -> Result<(), serde_json::Error> {
+        match event["type"].as_str() {
+            Some("meta") => {
+                let meta = MetaEvent::deserialize(event)?;
+                self.cargo_msrv_version = Some(meta.version);
+            }
+            Some("subcommand_init") => {
+                let init = SubcommandEvent::deserialize(event)?;
+                self.subcommand.get_or_insert(init.subcommand_id);
+            }
+            // The result of the subcommand, which is the last one, if the subcommand reported
+            // results of its own searches, like `find --targets`
+            Some("subcommand_result") => {
+                let subcommand = SubcommandEvent::deserialize(&event)?.subcommand_id;
+
+                self.result = match subcommand.as_str() {
+                    "find" => Some(RecordedResult::Msrv(
+                        FindResultEvent::deserialize(event)?.result.version,
+                    )),
+                    "find_targets" => Some(RecordedResult::Msrv(
+                        TargetsResultEvent::deserialize(event)?.result.msrv,
+                    )),
+                    "verify" => {
+                        let verified = VerifyResultEvent::deserialize(event)?.result;
+                        Some(RecordedResult::Verified {
+                            version: verified.toolchain.version,
+                            compatible: verified.is_compatible,
+                        })
+                    }
+                    _ => None,
+                };
+            }
+            Some("check_result") => {
+                self.checks.push(RecordedCheck::deserialize(event)?);
+            }
+            Some("warning") => {
+                self.warnings.push(RecordedWarning::deserialize(event)?);
+            }
+            Some("terminate_with_failure") => {
+                self.failure = Some(FailureEvent::deserialize(event)?.reason.description);
+            }
+            Some("terminated") => {
+                self.termination = Some(RecordedTermination::deserialize(event)?);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIND_LOG: &str = r#"{"type":"meta","instance":"cargo-msrv","version":"0.16.2"}
+{"type":"subcommand_init","subcommand_id":"find"}
+{"type":"check_result","toolchain":{"version":"1.60.0","target":"x86_64-unknown-linux-gnu"},"is_compatible":true}
+{"type":"check_result","toolchain":{"version":"1.55.0","target":"x86_64-unknown-linux-gnu"},"is_compatible":false,"error":"   Compiling a\nerror[E0658]: use of unstable library feature\n"}
+{"type":"warning","code":"lockfile_regenerated","message":"The lockfile was regenerated"}
+{"type":"subcommand_result","subcommand_id":"find","result":{"version":"1.56.0","success":true}}
+{"type":"terminated","exit_code":0,"outcome":"success","duration_ms":1500}
+"#;
+
+    #[test]
+    fn find_run() {
+        let run = RecordedRun::from_event_log(FIND_LOG).unwrap();
+
+        assert_eq!(run.cargo_msrv_version.as_deref(), Some("0.16.2"));
+        assert_eq!(run.subcommand.as_deref(), Some("find"));
+        assert_eq!(
+            run.result,
+            Some(RecordedResult::Msrv(Some(semver::Version::new(1, 56, 0))))
+        );
+        assert_eq!(run.checks.len(), 2);
+        assert_eq!(
+            run.checks[1].first_error(),
+            Some("error[E0658]: use of unstable library feature")
+        );
+        assert_eq!(run.warnings[0].code, "lockfile_regenerated");
+        assert!(run.is_search());
+        assert!(run.is_success());
+    }
+
+    #[test]
+    fn failed_verify_run() {
+        let log = r#"{"type":"subcommand_init","subcommand_id":"verify"}
+{"type":"subcommand_result","subcommand_id":"verify","result":{"toolchain":{"version":"1.56.0","target":"x86_64-unknown-linux-gnu"},"is_compatible":false}}
+{"type":"terminate_with_failure","reason":{"description":"Crate source was found to be incompatible with its MSRV '1.56.0'"}}
+{"type":"terminated","exit_code":1,"outcome":"failure","duration_ms":800}
+"#;
+
+        let run = RecordedRun::from_event_log(log).unwrap();
+
+        assert_eq!(
+            run.result,
+            Some(RecordedResult::Verified {
+                version: semver::Version::new(1, 56, 0),
+                compatible: false,
+            })
+        );
+        assert!(!run.is_search());
+        assert!(!run.is_success());
+    }
+
+    #[yare::parameterized(
+        empty = { "" },
+        other_output = { "Compiling a v0.1.0\n" },
+        other_events = { r#"{"type":"fetch_index","source":"rust_changelog"}"# },
+    )]
+    fn nothing_recorded(log: &str) {
+        assert!(RecordedRun::from_event_log(log).unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_event() {
+        let log = "{\"type\":\"meta\",\"version\":\"0.16.2\"}\n{\"type\":\"check_result\"}\n";
+
+        let error = RecordedRun::from_event_log(log).unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+}
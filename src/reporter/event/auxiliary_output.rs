@@ -1,3 +1,4 @@
+use crate::declaration::DeclarationKind;
 use crate::reporter::event::Message;
 use crate::Event;
 use camino::Utf8PathBuf;
@@ -13,6 +14,14 @@ impl AuxiliaryOutput {
     pub fn new(destination: Destination, item: Item) -> Self {
         Self { destination, item }
     }
+
+    pub fn destination(&self) -> &Destination {
+        &self.destination
+    }
+
+    pub fn item(&self) -> &Item {
+        &self.item
+    }
 }
 
 impl From<AuxiliaryOutput> for Event {
@@ -26,20 +35,49 @@ impl From<AuxiliaryOutput> for Event {
 #[serde(tag = "type")]
 pub enum Destination {
     File { path: Utf8PathBuf },
+    Directory { path: Utf8PathBuf },
 }
 
 impl Destination {
     pub fn file(path: Utf8PathBuf) -> Self {
         Self::File { path }
     }
+
+    pub fn directory(path: Utf8PathBuf) -> Self {
+        Self::Directory { path }
+    }
+
+    pub fn path(&self) -> &Utf8PathBuf {
+        match self {
+            Self::File { path } | Self::Directory { path } => path,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "type")]
 pub enum Item {
-    Msrv { kind: MsrvKind },
-    ToolchainFile { kind: ToolchainFileKind },
+    Msrv {
+        kind: MsrvKind,
+    },
+    ToolchainFile {
+        kind: ToolchainFileKind,
+    },
+    /// The artifacts of a `find` or `verify` run, which can be browsed with `cargo msrv inspect`.
+    RunArtifacts {
+        run_id: String,
+    },
+    /// The state of a search which stopped early, which can be continued with `--resume`.
+    SearchState,
+    /// The MSRV of each target of `find --targets`, which a run with `--resume` reuses.
+    MatrixState,
+    /// A declaration of the MSRV outside the Cargo manifest, like a README badge, which was
+    /// updated to the MSRV.
+    Declaration {
+        kind: DeclarationKind,
+        line: usize,
+    },
 }
 
 impl Item {
@@ -50,6 +88,22 @@ impl Item {
     pub fn toolchain_file(kind: ToolchainFileKind) -> Self {
         Self::ToolchainFile { kind }
     }
+
+    pub fn run_artifacts(run_id: String) -> Self {
+        Self::RunArtifacts { run_id }
+    }
+
+    pub fn search_state() -> Self {
+        Self::SearchState
+    }
+
+    pub fn matrix_state() -> Self {
+        Self::MatrixState
+    }
+
+    pub fn declaration(kind: DeclarationKind, line: usize) -> Self {
+        Self::Declaration { kind, line }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
@@ -82,6 +136,8 @@ mod tests {
         rust_version_msrv = { Item::msrv(MsrvKind::RustVersion) },
         metadata_fallback_msrv = { Item::msrv(MsrvKind::MetadataFallback) },
         toolchain_file_toml = { Item::toolchain_file(ToolchainFileKind::Toml) },
+        run_artifacts = { Item::run_artifacts("20240615T133045Z".to_string()) },
+        declaration = { Item::declaration(DeclarationKind::ReadmeBadge, 3) },
     )]
     fn reported_action(item: Item) {
         let reporter = TestReporterWrapper::default();
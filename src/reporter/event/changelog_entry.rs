@@ -0,0 +1,73 @@
+use crate::reporter::{Event, Message};
+use camino::{Utf8Path, Utf8PathBuf};
+
+/// The entry on the change of the MSRV which was added to the changelog, with `--changelog`, or
+/// which would have been added, with `--changelog-dry-run`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangelogEntry {
+    path: Utf8PathBuf,
+    section: String,
+    entry: String,
+    dry_run: bool,
+}
+
+impl ChangelogEntry {
+    pub fn new(path: Utf8PathBuf, section: String, entry: String, dry_run: bool) -> Self {
+        Self {
+            path,
+            section,
+            entry,
+            dry_run,
+        }
+    }
+
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    /// The heading of the section to which the entry is appended.
+    pub fn section(&self) -> &str {
+        &self.section
+    }
+
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+
+    /// Whether the entry was only reported, without being written.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+impl From<ChangelogEntry> for Event {
+    fn from(it: ChangelogEntry) -> Self {
+        Message::ChangelogEntry(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = ChangelogEntry::new(
+            Utf8PathBuf::from("CHANGELOG.md"),
+            "## [Unreleased]".to_string(),
+            "* Changed the MSRV from 1.60 to 1.65 (2024-05-01)".to_string(),
+            true,
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::ChangelogEntry(event))]
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::reporter::event::types::bisect_lockfile_result::LockfileRevision;
+use crate::reporter::{Event, Message};
+
+/// The revision of the lockfile which `cargo msrv bisect-lockfile` checks next.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CheckLockfileRevision {
+    revision: LockfileRevision,
+}
+
+impl CheckLockfileRevision {
+    pub fn new(revision: LockfileRevision) -> Self {
+        Self { revision }
+    }
+
+    pub fn revision(&self) -> &LockfileRevision {
+        &self.revision
+    }
+}
+
+impl From<CheckLockfileRevision> for Event {
+    fn from(it: CheckLockfileRevision) -> Self {
+        Message::CheckLockfileRevision(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = CheckLockfileRevision::new(LockfileRevision::WorkingTree);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::CheckLockfileRevision(event))]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::context::ContainerEngine;
 use crate::reporter::event::Message;
 use crate::rust::Toolchain;
 use crate::Event;
@@ -17,6 +18,10 @@ impl CheckMethod {
             method,
         }
     }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
 }
 
 impl From<CheckMethod> for Event {
@@ -33,6 +38,30 @@ pub enum Method {
         args: Vec<String>,
         path: Utf8PathBuf,
     },
+    /// The check command is run directly, with the given `rustc` (see `RUSTC`).
+    DistributionRun {
+        args: Vec<String>,
+        path: Utf8PathBuf,
+        rustc: Utf8PathBuf,
+    },
+    /// The check command is run in a container, which provides the toolchain.
+    ContainerRun {
+        engine: ContainerEngine,
+        image: String,
+        args: Vec<String>,
+        path: Utf8PathBuf,
+    },
+    /// The check command is run in a `nix shell`, which provides the toolchain.
+    NixShell {
+        args: Vec<String>,
+        path: Utf8PathBuf,
+    },
+    /// The check command is run with `rustup run` on a remote builder, over ssh.
+    RemoteRun {
+        destination: String,
+        args: Vec<String>,
+        path: String,
+    },
     #[cfg(test)]
     TestRunner,
 }
@@ -47,6 +76,67 @@ impl Method {
             path: path.as_ref().to_path_buf(),
         }
     }
+
+    pub fn distribution_run(
+        args: impl IntoIterator<Item = impl AsRef<str>>,
+        path: impl AsRef<Utf8Path>,
+        rustc: impl AsRef<Utf8Path>,
+    ) -> Self {
+        Self::DistributionRun {
+            args: args.into_iter().map(|s| s.as_ref().to_string()).collect(),
+            path: path.as_ref().to_path_buf(),
+            rustc: rustc.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn container_run(
+        engine: ContainerEngine,
+        image: impl Into<String>,
+        args: impl IntoIterator<Item = impl AsRef<str>>,
+        path: impl AsRef<Utf8Path>,
+    ) -> Self {
+        Self::ContainerRun {
+            engine,
+            image: image.into(),
+            args: args.into_iter().map(|s| s.as_ref().to_string()).collect(),
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn remote_run(
+        destination: impl Into<String>,
+        args: impl IntoIterator<Item = impl AsRef<str>>,
+        path: impl Into<String>,
+    ) -> Self {
+        Self::RemoteRun {
+            destination: destination.into(),
+            args: args.into_iter().map(|s| s.as_ref().to_string()).collect(),
+            path: path.into(),
+        }
+    }
+
+    pub fn nix_shell(
+        args: impl IntoIterator<Item = impl AsRef<str>>,
+        path: impl AsRef<Utf8Path>,
+    ) -> Self {
+        Self::NixShell {
+            args: args.into_iter().map(|s| s.as_ref().to_string()).collect(),
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// The arguments of the check command.
+    pub fn args(&self) -> &[String] {
+        match self {
+            Self::RustupRun { args, .. }
+            | Self::DistributionRun { args, .. }
+            | Self::ContainerRun { args, .. }
+            | Self::NixShell { args, .. }
+            | Self::RemoteRun { args, .. } => args,
+            #[cfg(test)]
+            Self::TestRunner => &[],
+        }
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +150,10 @@ mod tests {
 
     #[yare::parameterized(
         rustup_run_with_path = { Method::rustup_run(["hello"], Utf8Path::new("haha")) },
+        distribution_run = { Method::distribution_run(["cargo", "check"], Utf8Path::new("haha"), Utf8Path::new("/usr/bin/rustc")) },
+        container_run = { Method::container_run(ContainerEngine::Podman, "rust:1.56.0", ["run", "rust:1.56.0", "cargo", "check"], Utf8Path::new("haha")) },
+        nix_shell = { Method::nix_shell(["shell", "nixpkgs#rustc", "--command", "cargo", "check"], Utf8Path::new("haha")) },
+        remote_run = { Method::remote_run("user@host", ["1.56.0", "cargo", "check"], ".cargo-msrv/remote/haha") },
         test_runner = { Method::TestRunner },
     )]
     fn reported_event(method: Method) {
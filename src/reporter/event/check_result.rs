@@ -1,6 +1,8 @@
+use crate::check::diagnostics::FailedTarget;
 use crate::reporter::event::shared::compatibility::Compatibility;
 use crate::reporter::event::Message;
 use crate::rust::Toolchain;
+use crate::stabilization::StabilizationHint;
 use crate::Event;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
@@ -8,21 +10,43 @@ use crate::Event;
 pub struct CheckResult {
     #[serde(flatten)]
     pub compatibility: Compatibility,
+    /// The Rust version which is at least required, if it could be derived from the error
+    /// output of an incompatible toolchain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stabilization_hint: Option<StabilizationHint>,
+    /// The targets which failed to compile, like the library or a benchmark, if cargo named
+    /// them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failed_targets: Vec<FailedTarget>,
 }
 
 impl CheckResult {
     pub fn compatible(toolchain: impl Into<Toolchain>) -> Self {
         Self {
             compatibility: Compatibility::compatible(toolchain),
+            stabilization_hint: None,
+            failed_targets: Vec::new(),
         }
     }
 
     pub fn incompatible(toolchain: impl Into<Toolchain>, error: Option<String>) -> Self {
         Self {
             compatibility: Compatibility::incompatible(toolchain, error),
+            stabilization_hint: None,
+            failed_targets: Vec::new(),
         }
     }
 
+    pub fn with_stabilization_hint(mut self, hint: Option<StabilizationHint>) -> Self {
+        self.stabilization_hint = hint;
+        self
+    }
+
+    pub fn with_failed_targets(mut self, targets: Vec<FailedTarget>) -> Self {
+        self.failed_targets = targets;
+        self
+    }
+
     pub fn toolchain(&self) -> &Toolchain {
         self.compatibility.toolchain()
     }
@@ -41,6 +65,7 @@ impl From<CheckResult> for Event {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::check::diagnostics::TargetKind;
     use crate::reporter::event::Message;
     use crate::reporter::TestReporterWrapper;
     use crate::{semver, Event};
@@ -81,4 +106,42 @@ mod tests {
             vec![Event::unscoped(Message::CheckResult(event)),]
         );
     }
+
+    #[test]
+    fn reported_incompatible_toolchain_with_stabilization_hint() {
+        let reporter = TestReporterWrapper::default();
+        let event = CheckResult::incompatible(
+            Toolchain::new(semver::Version::new(1, 2, 3), "test_target", &[]),
+            None,
+        )
+        .with_stabilization_hint(Some(StabilizationHint {
+            version: semver::Version::new(1, 70, 0),
+            reason: "`OnceLock` stabilized".to_string(),
+        }));
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::CheckResult(event)),]
+        );
+    }
+
+    #[test]
+    fn serialized_failed_targets() {
+        let event = CheckResult::incompatible(
+            Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]),
+            None,
+        )
+        .with_failed_targets(vec![FailedTarget {
+            package: "a".to_string(),
+            kind: TargetKind::Bench,
+            name: Some("speed".to_string()),
+        }]);
+
+        assert_eq!(
+            serde_json::to_value(Event::from(event)).unwrap()["failed_targets"],
+            serde_json::json!([{ "package": "a", "kind": "bench", "name": "speed" }])
+        );
+    }
 }
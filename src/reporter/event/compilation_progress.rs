@@ -0,0 +1,72 @@
+use crate::reporter::event::Message;
+use crate::Event;
+
+/// Reports that a unit of the crate, or of its dependencies, has been compiled by a check.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CompilationProgress {
+    // the name of the target which has been compiled
+    unit: String,
+    // how many units have been compiled, including this one
+    compiled: u64,
+    // the estimated number of units to compile, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<u64>,
+}
+
+impl CompilationProgress {
+    pub fn new(unit: impl Into<String>, compiled: u64, total: Option<u64>) -> Self {
+        Self {
+            unit: unit.into(),
+            compiled,
+            // The total is an estimate, which should not fall behind
+            total: total.map(|total| total.max(compiled)),
+        }
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn compiled(&self) -> u64 {
+        self.compiled
+    }
+
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+}
+
+impl From<CompilationProgress> for Event {
+    fn from(it: CompilationProgress) -> Self {
+        Message::CompilationProgress(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = CompilationProgress::new("serde", 3, Some(12));
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::CompilationProgress(event)),]
+        );
+    }
+
+    #[test]
+    fn total_is_at_least_compiled() {
+        let event = CompilationProgress::new("serde", 13, Some(12));
+
+        assert_eq!(event.total(), Some(13));
+    }
+}
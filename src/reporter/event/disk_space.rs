@@ -0,0 +1,100 @@
+use crate::reporter::{Event, Message};
+use camino::Utf8PathBuf;
+
+/// The disk space which the planned checks are estimated to need on a volume, and the space which
+/// is free on it, as determined before the first check. When the required space exceeds the free
+/// space, the run fails right after this event.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DiskSpace {
+    /// A directory on the volume, like the toolchains directory of rustup, or the target
+    /// directory of the crate.
+    path: Utf8PathBuf,
+    required_bytes: u64,
+    available_bytes: u64,
+    /// The number of toolchains which may be installed on the volume.
+    toolchains: u64,
+    /// The number of checks of which the build artifacts are written to the volume.
+    checks: u64,
+}
+
+impl DiskSpace {
+    pub fn new(
+        path: Utf8PathBuf,
+        required_bytes: u64,
+        available_bytes: u64,
+        toolchains: u64,
+        checks: u64,
+    ) -> Self {
+        Self {
+            path,
+            required_bytes,
+            available_bytes,
+            toolchains,
+            checks,
+        }
+    }
+
+    pub fn path(&self) -> &Utf8PathBuf {
+        &self.path
+    }
+
+    pub fn required_bytes(&self) -> u64 {
+        self.required_bytes
+    }
+
+    pub fn available_bytes(&self) -> u64 {
+        self.available_bytes
+    }
+
+    pub fn toolchains(&self) -> u64 {
+        self.toolchains
+    }
+
+    pub fn checks(&self) -> u64 {
+        self.checks
+    }
+
+    pub fn is_sufficient(&self) -> bool {
+        self.required_bytes <= self.available_bytes
+    }
+}
+
+impl From<DiskSpace> for Event {
+    fn from(it: DiskSpace) -> Self {
+        Message::DiskSpace(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = DiskSpace::new(Utf8PathBuf::from("/rustup/toolchains"), 100, 50, 2, 3);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::DiskSpace(event))]
+        );
+    }
+
+    #[test]
+    fn serialized() {
+        let event = DiskSpace::new(Utf8PathBuf::from("/crate/target"), 100, 50, 0, 3);
+        let value = serde_json::to_value(Event::from(event)).unwrap();
+
+        assert_eq!(value["type"], "disk_space");
+        assert_eq!(value["path"], "/crate/target");
+        assert_eq!(value["required_bytes"], 100);
+        assert_eq!(value["available_bytes"], 50);
+        assert_eq!(value["toolchains"], 0);
+        assert_eq!(value["checks"], 3);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::reporter::event::Message;
+use crate::Event;
+
+/// Reports how much of a component has been downloaded, while a toolchain is being set up.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DownloadProgress {
+    // the name of the component which is being downloaded, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    component: Option<String>,
+    // how many bytes have been downloaded
+    downloaded_bytes: u64,
+    // the size of the download, in bytes
+    total_bytes: u64,
+}
+
+impl DownloadProgress {
+    pub fn new(component: Option<String>, downloaded_bytes: u64, total_bytes: u64) -> Self {
+        Self {
+            component,
+            downloaded_bytes,
+            total_bytes,
+        }
+    }
+
+    pub fn component(&self) -> Option<&str> {
+        self.component.as_deref()
+    }
+
+    pub fn downloaded_bytes(&self) -> u64 {
+        self.downloaded_bytes
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+}
+
+impl From<DownloadProgress> for Event {
+    fn from(it: DownloadProgress) -> Self {
+        Message::DownloadProgress(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = DownloadProgress::new(Some("rustc".to_string()), 1024, 4096);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::DownloadProgress(event)),]
+        );
+    }
+
+    #[test]
+    fn serialized() {
+        let event = Event::unscoped(Message::DownloadProgress(DownloadProgress::new(
+            Some("cargo".to_string()),
+            10,
+            20,
+        )));
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"download_progress","component":"cargo","downloaded_bytes":10,"total_bytes":20}"#
+        );
+    }
+}
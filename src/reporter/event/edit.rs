@@ -0,0 +1,110 @@
+use crate::reporter::{Event, Message};
+use camino::{Utf8Path, Utf8PathBuf};
+
+/// A value which `cargo msrv set` changed, so tools can act on what exactly changed, without
+/// parsing the edited files again.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Edit {
+    file: Utf8PathBuf,
+    location: EditLocation,
+    /// The value before the edit, or `None` if it was added.
+    old: Option<String>,
+    /// The value after the edit, or `None` if it was removed.
+    new: Option<String>,
+}
+
+impl Edit {
+    pub fn new(
+        file: Utf8PathBuf,
+        location: EditLocation,
+        old: Option<String>,
+        new: Option<String>,
+    ) -> Self {
+        Self {
+            file,
+            location,
+            old,
+            new,
+        }
+    }
+
+    pub fn file(&self) -> &Utf8Path {
+        &self.file
+    }
+
+    pub fn location(&self) -> &EditLocation {
+        &self.location
+    }
+
+    pub fn old(&self) -> Option<&str> {
+        self.old.as_deref()
+    }
+
+    pub fn new_value(&self) -> Option<&str> {
+        self.new.as_deref()
+    }
+}
+
+impl From<Edit> for Event {
+    fn from(it: Edit) -> Self {
+        Message::Edit(it).into()
+    }
+}
+
+/// Where in the file the value was changed.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum EditLocation {
+    /// The dotted path of a key in a TOML file, like `package.rust-version`.
+    TomlPath { path: String },
+    /// A line of a file which is not edited by key, like the README, starting at 1.
+    Line { line: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = Edit::new(
+            Utf8PathBuf::from("Cargo.toml"),
+            EditLocation::TomlPath {
+                path: "package.rust-version".to_string(),
+            },
+            Some("1.60".to_string()),
+            Some("1.65".to_string()),
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::Edit(event))]
+        );
+    }
+
+    #[test]
+    fn serialized() {
+        let event = Edit::new(
+            Utf8PathBuf::from("README.md"),
+            EditLocation::Line { line: 3 },
+            Some("1.60".to_string()),
+            Some("1.65".to_string()),
+        );
+
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "file": "README.md",
+                "location": { "kind": "line", "line": 3 },
+                "old": "1.60",
+                "new": "1.65",
+            })
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::reporter::event::Message;
+use crate::rust::Toolchain;
+use crate::Event;
+
+/// Reports the result of a compatibility check for a single feature set, when the feature
+/// iteration was delegated to `cargo hack`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FeatureSetResult {
+    pub toolchain: Toolchain,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    pub features: String,
+    pub is_compatible: bool,
+}
+
+impl FeatureSetResult {
+    pub fn new(
+        toolchain: impl Into<Toolchain>,
+        package: Option<String>,
+        features: impl Into<String>,
+        is_compatible: bool,
+    ) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            package,
+            features: features.into(),
+            is_compatible,
+        }
+    }
+}
+
+impl From<FeatureSetResult> for Event {
+    fn from(it: FeatureSetResult) -> Self {
+        Message::FeatureSetResult(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use crate::{semver, Event};
+    use storyteller::EventReporter;
+
+    #[yare::parameterized(
+        compatible = { true },
+        incompatible = { false },
+    )]
+    fn reported_feature_set_result(is_compatible: bool) {
+        let reporter = TestReporterWrapper::default();
+        let event = FeatureSetResult::new(
+            Toolchain::new(semver::Version::new(1, 2, 3), "test_target", &[]),
+            Some("pkg".to_string()),
+            "--no-default-features --features a",
+            is_compatible,
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::FeatureSetResult(event)),]
+        );
+    }
+}
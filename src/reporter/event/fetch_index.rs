@@ -1,19 +1,61 @@
 use crate::reporter::event::Message;
 use crate::{Event, ReleaseSource};
+use std::time::Duration;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct FetchIndex {
     #[serde(rename = "source")]
     from_source: ReleaseSource,
+    /// The sources which are used, in order, if the source before it failed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fallbacks: Vec<ReleaseSource>,
+    /// The source which served the index, once it is fetched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    served_by: Option<ReleaseSource>,
+    /// How long ago the index was fetched, in seconds, if it was served from the cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_age_secs: Option<u64>,
+    /// Whether the cached index was updated with only the releases which are newer.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    delta_update: bool,
+    /// How many releases were not in the cached index, if any was cached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_releases: Option<usize>,
 }
 
 impl FetchIndex {
     pub fn new(source: ReleaseSource) -> Self {
         Self {
             from_source: source,
+            fallbacks: Vec::new(),
+            served_by: None,
+            cache_age_secs: None,
+            delta_update: false,
+            new_releases: None,
         }
     }
+
+    pub fn with_fallbacks(mut self, fallbacks: impl IntoIterator<Item = ReleaseSource>) -> Self {
+        self.fallbacks = fallbacks.into_iter().collect();
+        self
+    }
+
+    pub fn served_by(mut self, source: Option<ReleaseSource>) -> Self {
+        self.served_by = source;
+        self
+    }
+
+    pub fn with_cache_age(mut self, age: Option<Duration>) -> Self {
+        self.cache_age_secs = age.map(|age| age.as_secs());
+        self
+    }
+
+    pub fn with_new_releases(mut self, delta_update: bool, new_releases: Option<usize>) -> Self {
+        self.delta_update = delta_update;
+        self.new_releases = new_releases;
+        self
+    }
 }
 
 impl From<FetchIndex> for Event {
@@ -42,6 +84,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reported_served_by_fallback() {
+        let reporter = TestReporterWrapper::default();
+        let event = FetchIndex::new(ReleaseSource::RustChangelog)
+            .with_fallbacks([ReleaseSource::Snapshot])
+            .served_by(Some(ReleaseSource::Snapshot));
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::FetchIndex(event)),]
+        );
+    }
+
+    #[test]
+    fn reported_cache_age() {
+        let reporter = TestReporterWrapper::default();
+        let event = FetchIndex::new(ReleaseSource::RustChangelog)
+            .served_by(Some(ReleaseSource::RustChangelog))
+            .with_cache_age(Some(Duration::from_secs(90)));
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::FetchIndex(event)),]
+        );
+    }
+
     #[cfg(feature = "rust-releases-dist-source")]
     #[test]
     fn reported_rust_dist_source() {
@@ -0,0 +1,63 @@
+use crate::reporter::event::Message;
+use crate::rust::Toolchain;
+use crate::Event;
+use camino::Utf8PathBuf;
+
+/// Reports the result of a compatibility check for a single cargo-fuzz project, like `fuzz/`,
+/// when these are checked with `--check-fuzz`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct FuzzProjectResult {
+    pub toolchain: Toolchain,
+    pub manifest_path: Utf8PathBuf,
+    pub is_compatible: bool,
+}
+
+impl FuzzProjectResult {
+    pub fn new(
+        toolchain: impl Into<Toolchain>,
+        manifest_path: Utf8PathBuf,
+        is_compatible: bool,
+    ) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            manifest_path,
+            is_compatible,
+        }
+    }
+}
+
+impl From<FuzzProjectResult> for Event {
+    fn from(it: FuzzProjectResult) -> Self {
+        Message::FuzzProjectResult(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use crate::{semver, Event};
+    use storyteller::EventReporter;
+
+    #[yare::parameterized(
+        compatible = { true },
+        incompatible = { false },
+    )]
+    fn reported_fuzz_project_result(is_compatible: bool) {
+        let reporter = TestReporterWrapper::default();
+        let event = FuzzProjectResult::new(
+            Toolchain::new(semver::Version::new(1, 2, 3), "test_target", &[]),
+            Utf8PathBuf::from("/crate/fuzz/Cargo.toml"),
+            is_compatible,
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::FuzzProjectResult(event)),]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use crate::reporter::{Event, Message};
+use camino::Utf8PathBuf;
+
+/// A crate which was included with `--also`, and for which the subcommand runs next, with its
+/// own result.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct IncludedCrate {
+    name: Option<String>,
+    manifest_path: Utf8PathBuf,
+}
+
+impl IncludedCrate {
+    pub fn new(name: Option<String>, manifest_path: Utf8PathBuf) -> Self {
+        Self {
+            name,
+            manifest_path,
+        }
+    }
+
+    /// The name of the package, if the manifest could be read.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn manifest_path(&self) -> &Utf8PathBuf {
+        &self.manifest_path
+    }
+}
+
+impl From<IncludedCrate> for Event {
+    fn from(it: IncludedCrate) -> Self {
+        Message::IncludedCrate(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = IncludedCrate::new(
+            Some("example".to_string()),
+            Utf8PathBuf::from("examples/example/Cargo.toml"),
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::IncludedCrate(event))]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use crate::context::LockfileStrategy;
+use crate::reporter::{Event, Message};
+use crate::rust::Toolchain;
+use crate::semver;
+
+/// The cargo of a toolchain which is about to be checked can't read the version of the lockfile.
+/// Without a strategy, the check fails; otherwise the lockfile is handled by the strategy.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct LockfileIncompatible {
+    toolchain: Toolchain,
+    lockfile_version: u64,
+    /// The first Rust version of which the cargo can read the lockfile.
+    readable_since: semver::Version,
+    strategy: Option<LockfileStrategy>,
+}
+
+impl LockfileIncompatible {
+    pub fn new(
+        toolchain: impl Into<Toolchain>,
+        lockfile_version: u64,
+        readable_since: semver::Version,
+        strategy: Option<LockfileStrategy>,
+    ) -> Self {
+        Self {
+            toolchain: toolchain.into(),
+            lockfile_version,
+            readable_since,
+            strategy,
+        }
+    }
+
+    pub fn toolchain(&self) -> &Toolchain {
+        &self.toolchain
+    }
+
+    pub fn lockfile_version(&self) -> u64 {
+        self.lockfile_version
+    }
+
+    pub fn readable_since(&self) -> &semver::Version {
+        &self.readable_since
+    }
+
+    pub fn strategy(&self) -> Option<LockfileStrategy> {
+        self.strategy
+    }
+}
+
+impl From<LockfileIncompatible> for Event {
+    fn from(it: LockfileIncompatible) -> Self {
+        Message::LockfileIncompatible(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = LockfileIncompatible::new(
+            Toolchain::new(semver::Version::new(1, 70, 0), "x", &[]),
+            4,
+            semver::Version::new(1, 78, 0),
+            Some(LockfileStrategy::Downgrade),
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::LockfileIncompatible(event))]
+        );
+    }
+
+    #[test]
+    fn serialized() {
+        let event = LockfileIncompatible::new(
+            Toolchain::new(semver::Version::new(1, 70, 0), "x", &[]),
+            4,
+            semver::Version::new(1, 78, 0),
+            Some(LockfileStrategy::Regenerate),
+        );
+        let value = serde_json::to_value(Event::from(event)).unwrap();
+
+        assert_eq!(value["type"], "lockfile_incompatible");
+        assert_eq!(value["lockfile_version"], 4);
+        assert_eq!(value["readable_since"], "1.78.0");
+        assert_eq!(value["strategy"], "regenerate");
+    }
+}
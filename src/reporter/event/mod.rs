@@ -11,24 +11,57 @@ pub use scope::TestScopeGenerator;
 pub use auxiliary_output::{
     AuxiliaryOutput, Destination, Item as AuxiliaryOutputItem, MsrvKind, ToolchainFileKind,
 };
+pub use changelog_entry::ChangelogEntry;
+pub use check_lockfile_revision::CheckLockfileRevision;
 pub use check_method::{CheckMethod, Method};
 pub use check_result::CheckResult;
 pub use check_toolchain::CheckToolchain;
+pub use compilation_progress::CompilationProgress;
+pub use disk_space::DiskSpace;
+pub use download_progress::DownloadProgress;
+pub use edit::{Edit, EditLocation};
+pub use feature_set_result::FeatureSetResult;
 pub use fetch_index::FetchIndex;
+pub use fuzz_project_result::FuzzProjectResult;
+pub use included_crate::IncludedCrate;
+pub use lockfile_incompatible::LockfileIncompatible;
 pub use meta::Meta;
+pub use partial_result::{CheckedVersion, PartialResult, PartialResultReason};
 pub use progress::Progress;
+pub use reduced_search_space::ReducedSearchSpace;
+pub use search_control::SearchControl;
 pub use search_method::FindMsrv;
+pub use search_target::SearchTarget;
 pub use selected_packages::{SelectedPackage, SelectedPackages};
 pub use setup_toolchain::SetupToolchain;
 pub use subcommand_init::SubcommandInit;
 pub use subcommand_result::SubcommandResult;
+pub use terminated::{Terminated, TerminationOutcome};
 pub use termination::TerminateWithFailure;
 pub use unable_to_confirm_valid_release_version::UnableToConfirmValidReleaseVersion;
+pub use unlocks::Unlocks;
+pub use warning::{Warning, WarningCode};
+
+pub(crate) use types::list_result::metadata::package_msrv;
 
 // types
 pub use types::{
-    find_result::FindResult, list_result::ListResult, set_result::SetResult,
-    show_result::ShowResult, verify_result::VerifyResult,
+    bisect_lockfile_result::BisectLockfileResult, bisect_lockfile_result::DependencyChange,
+    bisect_lockfile_result::LockfileRevision, cache_stats_result::CacheStatsResult,
+    clean_result::CleanCategory, clean_result::CleanResult, clean_result::CleanedItem,
+    consistency_result::ConsistencyResult, estimate_result::EstimateResult, find_result::ErrorDiff,
+    find_result::FindResult, find_result::ShardResult, graph_result::GraphResult,
+    images_result::ContainerImage, images_result::DistributionPackage, images_result::ImagesResult,
+    inspect_result::InspectResult, list_result::ListResult, merge_result::MergeResult,
+    migrate_result::MigrateResult, migrate_result::Migration, migrate_result::MigrationKind,
+    migrate_result::SkipReason, migrate_result::SkippedMigration,
+    outdated_result::OutdatedDependency, outdated_result::OutdatedResult,
+    report_result::ReportResult, set_result::SetResult, show_result::MemberMsrv,
+    show_result::ShowResult, show_result::ShowWorkspaceResult, show_result::Source as ShowSource,
+    sync_result::SyncResult, targets_result::TargetMsrv, targets_result::TargetsResult,
+    verify_result::Freshness, verify_result::FreshnessDetails, verify_result::ReleaseDetails,
+    verify_result::VerifyResult, whatif_result::WhatIfResult, whatif_result::WhatIfUpdateResult,
+    whatif_result::WhatIfVerification,
 };
 
 // internals defining an event
@@ -42,19 +75,36 @@ mod types;
 
 // specific events
 mod auxiliary_output;
+mod changelog_entry;
+mod check_lockfile_revision;
 mod check_method;
 mod check_result;
 mod check_toolchain;
+mod compilation_progress;
+mod disk_space;
+mod download_progress;
+mod edit;
+mod feature_set_result;
 mod fetch_index;
+mod fuzz_project_result;
+mod included_crate;
+mod lockfile_incompatible;
 mod meta;
+mod partial_result;
 mod progress;
+mod reduced_search_space;
+mod search_control;
 mod search_method;
+mod search_target;
 mod selected_packages;
 mod setup_toolchain;
 mod subcommand_init;
 mod subcommand_result;
+mod terminated;
 mod termination;
 mod unable_to_confirm_valid_release_version;
+mod unlocks;
+mod warning;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -105,6 +155,13 @@ impl Event {
         (start_event, end_event)
     }
 
+    /// Replace the message of the event, while keeping its scope, e.g. to report at the end of a
+    /// scope what was learned during it.
+    pub(crate) fn with_message(mut self, event: impl Into<Event>) -> Self {
+        self.message = event.into().message;
+        self
+    }
+
     /// Returns `true` if this is the start of the scope, _or_, if this event has no inner scope.
     pub fn is_scope_start(&self) -> bool {
         matches!(
@@ -127,33 +184,53 @@ pub enum Message {
 
     // package selection
     SelectedPackages(SelectedPackages),
+    IncludedCrate(IncludedCrate),
+    SearchTarget(SearchTarget),
 
     // get rust-releases index
     FetchIndex(FetchIndex), // todo!
     UnableToConfirmValidReleaseVersion(UnableToConfirmValidReleaseVersion),
-
-    // todo: ReducedSearchSpace
-    //       with: reason, reduction
+    ReducedSearchSpace(ReducedSearchSpace),
+    DiskSpace(DiskSpace),
 
     // runner, setup of toolchain, method, result
     CheckToolchain(CheckToolchain),
     SetupToolchain(SetupToolchain),
+    DownloadProgress(DownloadProgress),
     CheckMethod(CheckMethod),
+    LockfileIncompatible(LockfileIncompatible),
+    CompilationProgress(CompilationProgress),
     CheckResult(CheckResult),
+    FeatureSetResult(FeatureSetResult),
+    FuzzProjectResult(FuzzProjectResult),
 
     // output written by the program
     AuxiliaryOutput(AuxiliaryOutput),
+    ChangelogEntry(ChangelogEntry),
+    Edit(Edit),
 
     // progression events for command: find
     FindMsrv(FindMsrv),
     Progress(Progress),
+    SearchControl(SearchControl),
+    Unlocks(Unlocks),
+
+    // progression events for command: bisect-lockfile
+    CheckLockfileRevision(CheckLockfileRevision),
+
+    // situations which do not stop the program, but which the user should know about
+    Warning(Warning),
 
     // command init and final result
     SubcommandInit(SubcommandInit),
     SubcommandResult(SubcommandResult),
+    // the search stopped before it found the MSRV
+    PartialResult(PartialResult),
 
     // Termination, for example when caused by an unrecoverable error
     TerminateWithFailure(TerminateWithFailure),
+    // The last event of every run
+    Terminated(Terminated),
 }
 
 impl From<Message> for Event {
@@ -0,0 +1,126 @@
+use crate::reporter::event::Message;
+use crate::{semver, Event};
+
+/// The search stopped before it found the MSRV, with the outcomes of the checks and the bounds of
+/// the MSRV which were narrowed down so far, so the search can be picked up where it stopped.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PartialResult {
+    reason: PartialResultReason,
+    /// The least recent Rust version which may be the MSRV: the release after the most recent
+    /// incompatible one, or the least recent release of the search space.
+    lower_bound: semver::Version,
+    /// The least recent Rust version which is known to be compatible, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upper_bound: Option<semver::Version>,
+    /// The Rust versions which were checked conclusively, in the order in which they were checked.
+    checked: Vec<CheckedVersion>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartialResultReason {
+    /// The time budget of `--max-duration` expired.
+    MaxDurationExceeded,
+    /// The search was aborted over the control channel.
+    Aborted,
+    /// cargo-msrv was stopped by Ctrl-C or SIGTERM.
+    Interrupted,
+    /// A check failed with an error, like a toolchain which could not be installed.
+    Failed,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CheckedVersion {
+    pub version: semver::Version,
+    pub compatible: bool,
+}
+
+impl PartialResult {
+    pub fn new(
+        reason: PartialResultReason,
+        lower_bound: semver::Version,
+        upper_bound: Option<semver::Version>,
+        checked: Vec<CheckedVersion>,
+    ) -> Self {
+        Self {
+            reason,
+            lower_bound,
+            upper_bound,
+            checked,
+        }
+    }
+
+    pub fn reason(&self) -> PartialResultReason {
+        self.reason
+    }
+
+    pub fn lower_bound(&self) -> &semver::Version {
+        &self.lower_bound
+    }
+
+    pub fn upper_bound(&self) -> Option<&semver::Version> {
+        self.upper_bound.as_ref()
+    }
+
+    pub fn checked(&self) -> &[CheckedVersion] {
+        &self.checked
+    }
+}
+
+impl From<PartialResult> for Event {
+    fn from(it: PartialResult) -> Self {
+        Message::PartialResult(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = PartialResult::new(
+            PartialResultReason::MaxDurationExceeded,
+            semver::Version::new(1, 63, 0),
+            Some(semver::Version::new(1, 68, 0)),
+            Vec::new(),
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::PartialResult(event))]
+        );
+    }
+
+    #[yare::parameterized(
+        bounded = { Some(semver::Version::new(1, 68, 0)), r#"{"type":"partial_result","reason":"aborted","lower_bound":"1.63.0","upper_bound":"1.68.0","checked":[{"version":"1.68.0","compatible":true},{"version":"1.62.0","compatible":false}]}"# },
+        unbounded = { None, r#"{"type":"partial_result","reason":"aborted","lower_bound":"1.63.0","checked":[{"version":"1.68.0","compatible":true},{"version":"1.62.0","compatible":false}]}"# },
+    )]
+    fn serialized(upper_bound: Option<semver::Version>, expected: &str) {
+        let checked = vec![
+            CheckedVersion {
+                version: semver::Version::new(1, 68, 0),
+                compatible: true,
+            },
+            CheckedVersion {
+                version: semver::Version::new(1, 62, 0),
+                compatible: false,
+            },
+        ];
+        let event = Event::from(PartialResult::new(
+            PartialResultReason::Aborted,
+            semver::Version::new(1, 63, 0),
+            upper_bound,
+            checked,
+        ));
+
+        assert_eq!(serde_json::to_string(&event).unwrap(), expected);
+    }
+}
@@ -27,6 +27,18 @@ impl Progress {
             iteration,
         }
     }
+
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    pub fn search_space_size(&self) -> u64 {
+        self.search_space_size
+    }
+
+    pub fn iteration(&self) -> u64 {
+        self.iteration
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,71 @@
+use crate::estimate::Finding;
+use crate::reporter::{Event, Message};
+use crate::semver;
+
+/// The Rust versions older than `version` are excluded from the search space, since the cargo
+/// of these versions fails on a construct of the project, like the `[lints]` table of the
+/// manifest.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReducedSearchSpace {
+    version: semver::Version,
+    reason: String,
+    location: String,
+}
+
+impl ReducedSearchSpace {
+    pub fn new(gate: Finding) -> Self {
+        Self {
+            version: gate.version,
+            reason: gate.reason,
+            location: gate.location,
+        }
+    }
+
+    /// The oldest Rust version which remains in the search space.
+    pub fn version(&self) -> &semver::Version {
+        &self.version
+    }
+
+    /// The construct which requires the Rust version, e.g. "edition 2021".
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// Where the construct is found, e.g. `Cargo.toml:4`, relative to the crate root.
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+}
+
+impl From<ReducedSearchSpace> for Event {
+    fn from(it: ReducedSearchSpace) -> Self {
+        Message::ReducedSearchSpace(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate::FindingKind;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = ReducedSearchSpace::new(Finding {
+            version: semver::Version::new(1, 74, 0),
+            reason: "`[lints]` table".to_string(),
+            location: "Cargo.toml:12".to_string(),
+            kind: FindingKind::Cargo,
+        });
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::ReducedSearchSpace(event))]
+        );
+    }
+}
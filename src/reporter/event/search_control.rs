@@ -0,0 +1,58 @@
+use crate::reporter::event::Message;
+use crate::{semver, Event};
+
+/// A command from the control channel took effect, see `--control`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "action")]
+pub enum SearchControl {
+    /// The search is paused, before the next check.
+    Paused,
+    /// The search continues.
+    Resumed,
+    /// The check of a Rust version was skipped, so its outcome is inconclusive.
+    Skipped { version: semver::Version },
+    /// The search was aborted.
+    Aborted,
+}
+
+impl From<SearchControl> for Event {
+    fn from(it: SearchControl) -> Self {
+        Message::SearchControl(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = SearchControl::Skipped {
+            version: semver::Version::new(1, 60, 0),
+        };
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::SearchControl(event))]
+        );
+    }
+
+    #[test]
+    fn serialized() {
+        let event = Event::from(SearchControl::Skipped {
+            version: semver::Version::new(1, 60, 0),
+        });
+
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"type":"search_control","action":"skipped","version":"1.60.0"}"#
+        );
+    }
+}
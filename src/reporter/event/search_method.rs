@@ -1,19 +1,33 @@
 use crate::context::SearchMethod as Method;
 use crate::reporter::event::Message;
-use crate::Event;
+use crate::{semver, Event};
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct FindMsrv {
     search_method: Method,
+    /// The statically estimated MSRV which the hybrid search method tries to confirm, or the guess
+    /// of the heuristic of the `declared` and `last_run` search methods.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimate: Option<semver::Version>,
 }
 
 impl FindMsrv {
     pub(crate) fn new(method: Method) -> Self {
         Self {
             search_method: method,
+            estimate: None,
         }
     }
+
+    pub(crate) fn with_estimate(mut self, estimate: Option<semver::Version>) -> Self {
+        self.estimate = estimate;
+        self
+    }
+
+    pub fn search_method(&self) -> Method {
+        self.search_method
+    }
 }
 
 impl From<FindMsrv> for Event {
@@ -32,6 +46,9 @@ mod tests {
     #[yare::parameterized(
         linear = { Method::Linear },
         bisect = { Method::Bisect },
+        hybrid = { Method::Hybrid },
+        declared = { Method::Declared },
+        last_run = { Method::LastRun },
     )]
     fn reported_event(method: Method) {
         let reporter = TestReporterWrapper::default();
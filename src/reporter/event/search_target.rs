@@ -0,0 +1,47 @@
+use crate::reporter::{Event, Message};
+
+/// The target for which the MSRV is searched next, with `--targets`. The events which follow, up
+/// to the next `SearchTarget`, are of the search for this target.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SearchTarget {
+    target: String,
+}
+
+impl SearchTarget {
+    pub fn new(target: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+        }
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+}
+
+impl From<SearchTarget> for Event {
+    fn from(it: SearchTarget) -> Self {
+        Message::SearchTarget(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = SearchTarget::new("wasm32-unknown-unknown");
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::SearchTarget(event))]
+        );
+    }
+}
@@ -40,4 +40,46 @@ impl Compatibility {
     pub fn error(&self) -> Option<&str> {
         self.error.as_deref()
     }
+
+    /// The first error in the error output of the check, as a single line.
+    ///
+    /// This is the first line which starts with `error`, as emitted by rustc and cargo, or the
+    /// first non-empty line if no such line exists.
+    pub fn first_error(&self) -> Option<&str> {
+        let error = self.error.as_deref()?;
+        let mut lines = error.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        lines
+            .clone()
+            .find(|line| line.starts_with("error"))
+            .or_else(|| lines.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    fn incompatible(error: Option<&str>) -> Compatibility {
+        let toolchain = Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]);
+        Compatibility::incompatible(toolchain, error.map(ToString::to_string))
+    }
+
+    #[yare::parameterized(
+        rustc_error = {
+            Some("    Checking a v0.1.0\nerror[E0658]: use of unstable library feature 'once_cell'\n --> src/lib.rs:1:5\nerror: could not compile `a`"),
+            Some("error[E0658]: use of unstable library feature 'once_cell'")
+        },
+        cargo_error = {
+            Some("error: package `b v1.0.0` cannot be built because it requires rustc 1.70 or newer"),
+            Some("error: package `b v1.0.0` cannot be built because it requires rustc 1.70 or newer")
+        },
+        without_error_prefix = { Some("\n  something went wrong\n  details\n"), Some("something went wrong") },
+        empty = { Some(""), None },
+        none = { None, None },
+    )]
+    fn first_error(error: Option<&str>, expected: Option<&str>) {
+        assert_eq!(incompatible(error).first_error(), expected);
+    }
 }
@@ -1,4 +1,9 @@
-use crate::reporter::event::{FindResult, ListResult, SetResult, ShowResult, VerifyResult};
+use crate::reporter::event::{
+    BisectLockfileResult, CacheStatsResult, CleanResult, ConsistencyResult, EstimateResult,
+    FindResult, GraphResult, ImagesResult, InspectResult, ListResult, MergeResult, MigrateResult,
+    OutdatedResult, ReportResult, SetResult, ShowResult, ShowWorkspaceResult, SyncResult,
+    TargetsResult, VerifyResult, WhatIfResult, WhatIfUpdateResult,
+};
 use crate::reporter::Message;
 use crate::Event;
 
@@ -6,11 +11,30 @@ use crate::Event;
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "subcommand_id")]
 pub enum SubcommandResult {
+    BisectLockfile(BisectLockfileResult),
+    CacheStats(CacheStatsResult),
+    CheckConsistency(ConsistencyResult),
+    Clean(CleanResult),
+    Estimate(EstimateResult),
     Find(FindResult),
+    FindTargets(TargetsResult),
+    Graph(GraphResult),
+    Images(ImagesResult),
+    Inspect(InspectResult),
     List(ListResult),
+    MergeResults(MergeResult),
+    Migrate(MigrateResult),
+    Outdated(OutdatedResult),
+    Report(ReportResult),
     Set(SetResult),
     Show(ShowResult),
+    ShowWorkspace(ShowWorkspaceResult),
+    Sync(SyncResult),
     Verify(VerifyResult),
+    #[serde(rename = "whatif")]
+    WhatIf(WhatIfResult),
+    #[serde(rename = "whatif_update")]
+    WhatIfUpdate(WhatIfUpdateResult),
 }
 
 impl From<SubcommandResult> for Event {
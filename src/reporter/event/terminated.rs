@@ -0,0 +1,106 @@
+use crate::exit_code::ExitCode;
+use crate::reporter::event::Message;
+use crate::Event;
+use std::time::Duration;
+
+/// The last event of every run, which reports how the program terminated, so consumers of the
+/// event stream can reliably detect its end.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Terminated {
+    exit_code: i32,
+    outcome: TerminationOutcome,
+    /// How long the program ran, in milliseconds.
+    duration_ms: u64,
+    /// The message and location of the panic, if the program panicked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    panic: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerminationOutcome {
+    Success,
+    Failure,
+    /// The search stopped early, with the bounds of the MSRV narrowed down so far.
+    Partial,
+    /// The program was stopped by Ctrl-C or SIGTERM.
+    Interrupted,
+    Panic,
+}
+
+impl Terminated {
+    pub fn new(exit_code: ExitCode, duration: Duration) -> Self {
+        let outcome = match exit_code {
+            ExitCode::Success => TerminationOutcome::Success,
+            ExitCode::Failure => TerminationOutcome::Failure,
+            ExitCode::PartialResult => TerminationOutcome::Partial,
+            ExitCode::Interrupted => TerminationOutcome::Interrupted,
+            ExitCode::Panic => TerminationOutcome::Panic,
+        };
+
+        Self {
+            exit_code: exit_code.into(),
+            outcome,
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            panic: None,
+        }
+    }
+
+    /// A program which panicked, with the given message.
+    pub fn panicked(message: Option<String>, duration: Duration) -> Self {
+        Self {
+            panic: message,
+            ..Self::new(ExitCode::Panic, duration)
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code
+    }
+
+    pub fn outcome(&self) -> TerminationOutcome {
+        self.outcome
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms)
+    }
+}
+
+impl From<Terminated> for Event {
+    fn from(it: Terminated) -> Self {
+        Message::Terminated(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = Terminated::new(ExitCode::Failure, Duration::from_millis(1500));
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::Terminated(event))]
+        );
+    }
+
+    #[yare::parameterized(
+        success = { Terminated::new(ExitCode::Success, Duration::from_millis(12)), serde_json::json!({ "exit_code": 0, "outcome": "success", "duration_ms": 12 }) },
+        failure = { Terminated::new(ExitCode::Failure, Duration::from_secs(2)), serde_json::json!({ "exit_code": 1, "outcome": "failure", "duration_ms": 2000 }) },
+        partial = { Terminated::new(ExitCode::PartialResult, Duration::from_secs(3)), serde_json::json!({ "exit_code": 3, "outcome": "partial", "duration_ms": 3000 }) },
+        interrupted = { Terminated::new(ExitCode::Interrupted, Duration::from_secs(4)), serde_json::json!({ "exit_code": 130, "outcome": "interrupted", "duration_ms": 4000 }) },
+        panic = { Terminated::panicked(Some("oops at src/main.rs:1:1".to_string()), Duration::ZERO), serde_json::json!({ "exit_code": 101, "outcome": "panic", "duration_ms": 0, "panic": "oops at src/main.rs:1:1" }) },
+    )]
+    fn serialized(event: Terminated, expected: serde_json::Value) {
+        assert_eq!(serde_json::to_value(event).unwrap(), expected);
+    }
+}
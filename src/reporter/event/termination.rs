@@ -1,3 +1,4 @@
+use crate::error::ErrorCode;
 use crate::reporter::event::Message;
 use crate::{CargoMSRVError, Event};
 
@@ -14,6 +15,24 @@ pub struct TerminateWithFailure {
 
 impl TerminateWithFailure {
     pub fn new(error: CargoMSRVError) -> Self {
+        Self::from(&error)
+    }
+
+    pub fn should_highlight(&self) -> bool {
+        self.highlight
+    }
+
+    pub fn as_message(&self) -> &str {
+        &self.reason.description
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.reason.code
+    }
+}
+
+impl From<&CargoMSRVError> for TerminateWithFailure {
+    fn from(error: &CargoMSRVError) -> Self {
         let highlight = matches!(
             error,
             CargoMSRVError::UnableToFindAnyGoodVersion { .. } | CargoMSRVError::InvalidMsrvSet(_)
@@ -22,18 +41,11 @@ impl TerminateWithFailure {
         Self {
             highlight,
             reason: SerializableReason {
-                description: format!("{}", &error),
+                code: error.code(),
+                description: format!("{}", error),
             },
         }
     }
-
-    pub fn should_highlight(&self) -> bool {
-        self.highlight
-    }
-
-    pub fn as_message(&self) -> &str {
-        &self.reason.description
-    }
 }
 
 impl From<TerminateWithFailure> for Event {
@@ -45,16 +57,25 @@ impl From<TerminateWithFailure> for Event {
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 struct SerializableReason {
+    code: ErrorCode,
     description: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::manifest::bare_version::BareVersion;
+    use crate::manifest::msrv_policy::ReleaseType;
     use crate::reporter::event::Message;
     use crate::reporter::TestReporterWrapper;
+    use crate::semver;
+    use crate::sub_command::verify::{self, RustVersion};
     use storyteller::EventReporter;
 
+    fn rust_version() -> RustVersion {
+        RustVersion::from_arg(BareVersion::TwoComponents(1, 56))
+    }
+
     #[test]
     fn reported_non_is_not_error_event() {
         let reporter = TestReporterWrapper::default();
@@ -93,9 +114,44 @@ mod tests {
 
         if let Message::TerminateWithFailure(msg) = &events[0].message {
             assert!(msg.should_highlight());
+            assert_eq!(msg.code(), ErrorCode::NoMsrvFound);
             assert!(msg
                 .as_message()
                 .starts_with("Unable to find a Minimum Supported Rust Version (MSRV)"));
         }
     }
+
+    #[yare::parameterized(
+        interrupted = { CargoMSRVError::Interrupted, "interrupted" },
+        no_msrv_found = {
+            CargoMSRVError::UnableToFindAnyGoodVersion { command: "cargo check".to_string() },
+            "no_msrv_found"
+        },
+        configuration = { CargoMSRVError::InvalidConfig("a".to_string()), "configuration" },
+        fingerprint_mismatch = {
+            CargoMSRVError::FingerprintMismatch { expected: "a".to_string(), actual: "b".to_string() },
+            "fingerprint_mismatch"
+        },
+        verify_failed = {
+            verify::Error::VerifyFailed(rust_version().into()).into(),
+            "incompatible"
+        },
+        below_msrv = {
+            verify::Error::BelowMsrv { failed: rust_version().into(), msrv: semver::Version::new(1, 60, 0) }.into(),
+            "below_msrv"
+        },
+        above_msrv = {
+            verify::Error::AboveMsrv { failed: rust_version().into(), msrv: semver::Version::new(1, 50, 0), tolerance: 2 }.into(),
+            "above_msrv"
+        },
+        msrv_bump_not_allowed = {
+            verify::Error::MsrvBumpNotAllowed { failed: rust_version().into(), msrv: semver::Version::new(1, 60, 0), release_type: ReleaseType::Patch }.into(),
+            "msrv_bump_not_allowed"
+        },
+    )]
+    fn serialized_code(error: CargoMSRVError, expected: &str) {
+        let event = serde_json::to_value(TerminateWithFailure::new(error)).unwrap();
+
+        assert_eq!(event["reason"]["code"], expected);
+    }
 }
@@ -0,0 +1,178 @@
+use crate::external_command::git_command::Commit;
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::{semver, Event};
+use std::fmt;
+
+/// The update of the lockfile which first made the crate fail with a toolchain, as found by
+/// `cargo msrv bisect-lockfile`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BisectLockfileResult {
+    result: ResultDetails,
+}
+
+impl BisectLockfileResult {
+    pub fn new(
+        toolchain: semver::Version,
+        first_incompatible: LockfileRevision,
+        last_compatible: LockfileRevision,
+        changes: Vec<DependencyChange>,
+        checks: u64,
+    ) -> Self {
+        Self {
+            result: ResultDetails {
+                toolchain,
+                first_incompatible,
+                last_compatible,
+                changes,
+                checks,
+            },
+        }
+    }
+
+    /// The Rust version of the toolchain which was checked.
+    pub fn toolchain(&self) -> &semver::Version {
+        &self.result.toolchain
+    }
+
+    /// The oldest revision of the lockfile with which the crate fails.
+    pub fn first_incompatible(&self) -> &LockfileRevision {
+        &self.result.first_incompatible
+    }
+
+    /// The revision of the lockfile which precedes the first incompatible revision, with which the
+    /// crate still compiles.
+    pub fn last_compatible(&self) -> &LockfileRevision {
+        &self.result.last_compatible
+    }
+
+    /// The dependencies which changed between the last compatible and the first incompatible
+    /// revision, by name.
+    pub fn changes(&self) -> &[DependencyChange] {
+        &self.result.changes
+    }
+
+    /// The number of revisions which were checked.
+    pub fn checks(&self) -> u64 {
+        self.result.checks
+    }
+}
+
+impl From<BisectLockfileResult> for SubcommandResult {
+    fn from(it: BisectLockfileResult) -> Self {
+        Self::BisectLockfile(it)
+    }
+}
+
+impl From<BisectLockfileResult> for Event {
+    fn from(it: BisectLockfileResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    toolchain: semver::Version,
+    first_incompatible: LockfileRevision,
+    last_compatible: LockfileRevision,
+    changes: Vec<DependencyChange>,
+    checks: u64,
+}
+
+/// A revision of the lockfile.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum LockfileRevision {
+    /// The lockfile of the working tree, which may have changes which are not committed
+    WorkingTree,
+    /// The lockfile as committed
+    Commit(Commit),
+}
+
+impl LockfileRevision {
+    /// The commit, unless the revision is the lockfile of the working tree.
+    pub fn commit(&self) -> Option<&Commit> {
+        match self {
+            Self::WorkingTree => None,
+            Self::Commit(commit) => Some(commit),
+        }
+    }
+}
+
+impl fmt::Display for LockfileRevision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WorkingTree => write!(f, "the working tree"),
+            Self::Commit(commit) if commit.summary.is_empty() => write!(f, "{}", commit.short_id),
+            Self::Commit(commit) => write!(f, "{} ({})", commit.short_id, commit.summary),
+        }
+    }
+}
+
+/// A package of which the locked versions differ between two revisions of the lockfile.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct DependencyChange {
+    pub name: String,
+    /// The version before the change, or `None` if the package was added.
+    pub from: Option<semver::Version>,
+    /// The version after the change, or `None` if the package was removed.
+    pub to: Option<semver::Version>,
+}
+
+impl fmt::Display for DependencyChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.from, &self.to) {
+            (Some(from), Some(to)) => write!(f, "{} {} -> {}", self.name, from, to),
+            (None, Some(to)) => write!(f, "{} {} (added)", self.name, to),
+            (Some(from), None) => write!(f, "{} {} (removed)", self.name, from),
+            (None, None) => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let commit = Commit {
+            id: "4f2a".to_string(),
+            short_id: "4f2".to_string(),
+            summary: "Bump serde".to_string(),
+        };
+
+        let event = BisectLockfileResult::new(
+            semver::Version::new(1, 63, 0),
+            LockfileRevision::Commit(commit.clone()),
+            LockfileRevision::WorkingTree,
+            vec![DependencyChange {
+                name: "serde".to_string(),
+                from: Some(semver::Version::new(1, 0, 100)),
+                to: Some(semver::Version::new(1, 0, 200)),
+            }],
+            3,
+        );
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::BisectLockfile(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::BisectLockfile(msg)) = &events[0].message
+        {
+            assert_eq!(msg.first_incompatible().commit(), Some(&commit));
+            assert_eq!(msg.changes()[0].to_string(), "serde 1.0.100 -> 1.0.200");
+            assert_eq!(msg.checks(), 3);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use crate::cache::CacheEntry;
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::reporter::formatting::{format_size, table};
+use crate::run_artifacts::Timestamp;
+use crate::Event;
+use camino::Utf8PathBuf;
+use std::fmt;
+use std::time::{Duration, UNIX_EPOCH};
+use tabled::Tabled;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CacheStatsResult {
+    result: ResultDetails,
+}
+
+impl CacheStatsResult {
+    /// The statistics of the cache at `path`, from its files, ordered from the least to the most
+    /// recently used.
+    pub fn new(path: Utf8PathBuf, max_size: u64, entries: &[CacheEntry]) -> Self {
+        let mut sections = Vec::<CacheSection>::new();
+
+        for entry in entries {
+            let last_used = timestamp(entry.last_used);
+
+            match sections
+                .iter_mut()
+                .find(|section| section.name == entry.section())
+            {
+                Some(section) => {
+                    section.files += 1;
+                    section.bytes += entry.bytes;
+                    section.most_recently_used = last_used;
+                }
+                None => sections.push(CacheSection {
+                    name: entry.section().to_string(),
+                    files: 1,
+                    bytes: entry.bytes,
+                    least_recently_used: last_used.clone(),
+                    most_recently_used: last_used,
+                }),
+            }
+        }
+
+        sections.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+
+        Self {
+            result: ResultDetails {
+                path,
+                max_size,
+                bytes: entries.iter().map(|entry| entry.bytes).sum(),
+                files: entries.len(),
+                sections,
+            },
+        }
+    }
+
+    /// The directory of the cache.
+    pub fn path(&self) -> &Utf8PathBuf {
+        &self.result.path
+    }
+
+    /// The size above which files are evicted, in bytes.
+    pub fn max_size(&self) -> u64 {
+        self.result.max_size
+    }
+
+    /// The combined size of the cached files, in bytes.
+    pub fn bytes(&self) -> u64 {
+        self.result.bytes
+    }
+
+    /// Whether the cache holds no files.
+    pub fn is_empty(&self) -> bool {
+        self.result.files == 0
+    }
+}
+
+impl fmt::Display for CacheStatsResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.result.sections.iter().map(|section| Row {
+            section: &section.name,
+            files: section.files,
+            size: format_size(section.bytes),
+            least_recently_used: &section.least_recently_used,
+            most_recently_used: &section.most_recently_used,
+        });
+
+        f.write_fmt(format_args!("{}", table(rows)))
+    }
+}
+
+impl From<CacheStatsResult> for SubcommandResult {
+    fn from(it: CacheStatsResult) -> Self {
+        Self::CacheStats(it)
+    }
+}
+
+impl From<CacheStatsResult> for Event {
+    fn from(it: CacheStatsResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    path: Utf8PathBuf,
+    max_size: u64,
+    bytes: u64,
+    files: usize,
+    sections: Vec<CacheSection>,
+}
+
+/// A section of the cache, e.g. the crates.io index files.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct CacheSection {
+    pub name: String,
+    pub files: usize,
+    pub bytes: u64,
+    /// The moment the least recently used file of the section was last used.
+    pub least_recently_used: String,
+    /// The moment the most recently used file of the section was last used.
+    pub most_recently_used: String,
+}
+
+fn timestamp(seconds: u64) -> String {
+    Timestamp::from(UNIX_EPOCH + Duration::from_secs(seconds)).to_string()
+}
+
+#[derive(Tabled)]
+struct Row<'a> {
+    #[tabled(rename = "Section")]
+    section: &'a str,
+    #[tabled(rename = "Files")]
+    files: usize,
+    #[tabled(rename = "Size")]
+    size: String,
+    #[tabled(rename = "Least recently used")]
+    least_recently_used: &'a str,
+    #[tabled(rename = "Most recently used")]
+    most_recently_used: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    fn entry(key: &str, bytes: u64, last_used: u64) -> CacheEntry {
+        CacheEntry {
+            key: key.to_string(),
+            bytes,
+            last_used,
+        }
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let event = CacheStatsResult::new(
+            Utf8PathBuf::from("/cache/cargo-msrv"),
+            1024 * 1024,
+            &[entry("crates-index/serde", 2048, 0)],
+        );
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::CacheStats(event)
+            ))]
+        );
+    }
+
+    #[test]
+    fn sections() {
+        let stats = CacheStatsResult::new(
+            Utf8PathBuf::from("/cache/cargo-msrv"),
+            1024 * 1024,
+            &[
+                entry("crates-index/syn", 100, 0),
+                entry("channel-manifests/channel-rust-1.60.0.toml", 1000, 86_400),
+                entry("crates-index/serde", 200, 2 * 86_400),
+            ],
+        );
+
+        assert_eq!(stats.bytes(), 1300);
+        assert!(!stats.is_empty());
+
+        assert_eq!(
+            stats.result.sections,
+            vec![
+                CacheSection {
+                    name: "channel-manifests".to_string(),
+                    files: 1,
+                    bytes: 1000,
+                    least_recently_used: "1970-01-02T00:00:00Z".to_string(),
+                    most_recently_used: "1970-01-02T00:00:00Z".to_string(),
+                },
+                CacheSection {
+                    name: "crates-index".to_string(),
+                    files: 2,
+                    bytes: 300,
+                    least_recently_used: "1970-01-01T00:00:00Z".to_string(),
+                    most_recently_used: "1970-01-03T00:00:00Z".to_string(),
+                },
+            ]
+        );
+    }
+}
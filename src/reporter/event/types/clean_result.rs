@@ -0,0 +1,197 @@
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::reporter::formatting::{format_size, table};
+use crate::Event;
+use camino::Utf8PathBuf;
+use std::fmt;
+use tabled::Tabled;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CleanResult {
+    result: ResultDetails,
+}
+
+impl CleanResult {
+    pub fn new(dry_run: bool, removed: Vec<CleanedItem>) -> Self {
+        Self {
+            result: ResultDetails {
+                dry_run,
+                bytes: removed.iter().map(|item| item.bytes).sum(),
+                removed,
+            },
+        }
+    }
+
+    /// Whether the items were only listed, instead of removed.
+    pub fn is_dry_run(&self) -> bool {
+        self.result.dry_run
+    }
+
+    /// The items which were (or with a dry run, would have been) removed.
+    pub fn removed(&self) -> &[CleanedItem] {
+        &self.result.removed
+    }
+
+    /// The combined size of the removed items, in bytes.
+    pub fn bytes(&self) -> u64 {
+        self.result.bytes
+    }
+}
+
+impl fmt::Display for CleanResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = CleanCategory::ALL.iter().filter_map(|&category| {
+            let items = self
+                .removed()
+                .iter()
+                .filter(|item| item.category == category)
+                .collect::<Vec<_>>();
+
+            if items.is_empty() {
+                return None;
+            }
+
+            Some(Row {
+                category,
+                size: format_size(items.iter().map(|item| item.bytes).sum()),
+                paths: items
+                    .iter()
+                    .map(|item| item.path.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            })
+        });
+
+        f.write_fmt(format_args!("{}", table(rows)))
+    }
+}
+
+impl From<CleanResult> for SubcommandResult {
+    fn from(it: CleanResult) -> Self {
+        Self::Clean(it)
+    }
+}
+
+impl From<CleanResult> for Event {
+    fn from(it: CleanResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    dry_run: bool,
+    bytes: u64,
+    removed: Vec<CleanedItem>,
+}
+
+/// A file or directory written by cargo-msrv, or a toolchain it installed.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct CleanedItem {
+    pub category: CleanCategory,
+    pub path: Utf8PathBuf,
+    /// The size of the file, or of all files in the directory.
+    pub bytes: u64,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanCategory {
+    /// The cached release index, channel manifests and crates.io index.
+    Cache,
+    /// The runs stored in the crate, with `--save-run`.
+    State,
+    /// The log files of cargo-msrv.
+    Logs,
+    /// The target directories which are exclusively used by cargo-msrv.
+    Target,
+    /// The toolchains installed by cargo-msrv.
+    Toolchains,
+}
+
+impl CleanCategory {
+    const ALL: [Self; 5] = [
+        Self::Cache,
+        Self::State,
+        Self::Logs,
+        Self::Target,
+        Self::Toolchains,
+    ];
+}
+
+impl fmt::Display for CleanCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Cache => "cache",
+            Self::State => "state",
+            Self::Logs => "logs",
+            Self::Target => "target",
+            Self::Toolchains => "toolchains",
+        })
+    }
+}
+
+#[derive(Tabled)]
+struct Row {
+    #[tabled(rename = "Category")]
+    category: CleanCategory,
+    #[tabled(rename = "Size")]
+    size: String,
+    #[tabled(rename = "Paths")]
+    paths: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    fn item(category: CleanCategory, path: &str, bytes: u64) -> CleanedItem {
+        CleanedItem {
+            category,
+            path: Utf8PathBuf::from(path),
+            bytes,
+        }
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let event = CleanResult::new(
+            true,
+            vec![
+                item(CleanCategory::Cache, "/cache/cargo-msrv", 2048),
+                item(CleanCategory::Logs, "/data/cargo-msrv/cargo-msrv-log", 512),
+            ],
+        );
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Clean(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::Clean(msg)) = &events[0].message {
+            assert!(msg.is_dry_run());
+            assert_eq!(msg.bytes(), 2560);
+        }
+    }
+
+    #[yare::parameterized(
+        bytes = { 512, "512 B" },
+        kibibytes = { 1536, "1.5 KiB" },
+        mebibytes = { 5 * 1024 * 1024, "5.0 MiB" },
+        gibibytes = { 3 * 1024 * 1024 * 1024 + 512 * 1024 * 1024, "3.5 GiB" },
+    )]
+    fn formatted_size(bytes: u64, expected: &str) {
+        assert_eq!(format_size(bytes), expected);
+    }
+}
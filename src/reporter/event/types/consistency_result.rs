@@ -0,0 +1,176 @@
+use crate::declaration::{outdated_pins, Conflict, Declaration, Pin};
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::reporter::formatting::table;
+use crate::Event;
+use std::fmt;
+use tabled::Tabled;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ConsistencyResult {
+    result: ResultDetails,
+}
+
+impl ConsistencyResult {
+    pub fn new(declarations: Vec<Declaration>, conflicts: Vec<Conflict>) -> Self {
+        Self {
+            result: ResultDetails {
+                msrv: declarations
+                    .first()
+                    .map(|declaration| declaration.version.clone()),
+                declarations,
+                conflicts,
+                pins: Vec::new(),
+                outdated_pins: Vec::new(),
+            },
+        }
+    }
+
+    /// Include the Rust versions to which CI jobs are pinned, and which of these are older than
+    /// the MSRV.
+    pub fn with_pins(mut self, pins: Vec<Pin>) -> Self {
+        self.result.outdated_pins = self
+            .msrv()
+            .map(|msrv| outdated_pins(&pins, msrv))
+            .unwrap_or_default();
+        self.result.pins = pins;
+        self
+    }
+
+    /// The MSRV, as declared by the declaration which takes precedence, if any.
+    pub fn msrv(&self) -> Option<&BareVersion> {
+        self.result.msrv.as_ref()
+    }
+
+    pub fn declarations(&self) -> &[Declaration] {
+        &self.result.declarations
+    }
+
+    /// The declarations which disagree with the MSRV.
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.result.conflicts
+    }
+
+    pub fn pins(&self) -> &[Pin] {
+        &self.result.pins
+    }
+
+    /// The pins which are older than the MSRV, and so no longer test it.
+    pub fn outdated_pins(&self) -> &[Pin] {
+        &self.result.outdated_pins
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.result.conflicts.is_empty()
+    }
+}
+
+impl fmt::Display for ConsistencyResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.conflicts().iter().map(|conflict| Row {
+            location: format!(
+                "{}:{}",
+                conflict.declaration.file, conflict.declaration.line
+            ),
+            kind: conflict.declaration.kind.to_string(),
+            version: &conflict.declaration.version,
+            expected: &conflict.expected,
+        });
+
+        f.write_fmt(format_args!("{}", table(rows)))
+    }
+}
+
+impl From<ConsistencyResult> for SubcommandResult {
+    fn from(it: ConsistencyResult) -> Self {
+        Self::CheckConsistency(it)
+    }
+}
+
+impl From<ConsistencyResult> for Event {
+    fn from(it: ConsistencyResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    msrv: Option<BareVersion>,
+    declarations: Vec<Declaration>,
+    conflicts: Vec<Conflict>,
+    pins: Vec<Pin>,
+    outdated_pins: Vec<Pin>,
+}
+
+#[derive(Tabled)]
+struct Row<'a> {
+    #[tabled(rename = "Location")]
+    location: String,
+    #[tabled(rename = "Declared by")]
+    kind: String,
+    #[tabled(rename = "Version")]
+    version: &'a BareVersion,
+    #[tabled(rename = "MSRV")]
+    expected: &'a BareVersion,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::declaration::{conflicts, DeclarationKind, PinKind};
+    use crate::reporter::TestReporterWrapper;
+    use camino::Utf8PathBuf;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let declarations = [
+            (DeclarationKind::RustVersion, "Cargo.toml", "1.60"),
+            (DeclarationKind::ClippyMsrv, "clippy.toml", "1.58"),
+        ]
+        .into_iter()
+        .map(|(kind, file, version)| Declaration {
+            kind,
+            file: Utf8PathBuf::from(file),
+            line: 1,
+            version: version.parse().unwrap(),
+        })
+        .collect::<Vec<_>>();
+        let conflicts = conflicts(&declarations);
+        let pins = ["1.58", "1.60", "1.75"]
+            .into_iter()
+            .map(|version| Pin {
+                kind: PinKind::RustToolchainAction,
+                file: Utf8PathBuf::from(".github/workflows/ci.yml"),
+                line: 1,
+                version: version.parse().unwrap(),
+            })
+            .collect::<Vec<_>>();
+        let event = ConsistencyResult::new(declarations, conflicts).with_pins(pins.clone());
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::CheckConsistency(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::CheckConsistency(msg)) =
+            &events[0].message
+        {
+            assert_eq!(msg.msrv(), Some(&BareVersion::TwoComponents(1, 60)));
+            assert!(!msg.is_consistent());
+            assert_eq!(msg.conflicts()[0].declaration.file, "clippy.toml");
+            assert_eq!(msg.pins(), pins.as_slice());
+            assert_eq!(msg.outdated_pins(), &pins[..1]);
+        }
+    }
+}
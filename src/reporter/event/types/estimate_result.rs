@@ -0,0 +1,98 @@
+use crate::estimate::{Estimate, Finding};
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::{semver, Event};
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct EstimateResult {
+    result: ResultDetails,
+}
+
+impl EstimateResult {
+    pub fn new(estimate: Estimate) -> Self {
+        Self {
+            result: ResultDetails {
+                version: estimate.version().cloned(),
+                cargo_version: estimate.cargo_version().cloned(),
+                is_estimate: true,
+                findings: estimate.findings().to_vec(),
+            },
+        }
+    }
+
+    /// The estimated MSRV, or `None` if no feature with a known stabilization version was found.
+    pub fn version(&self) -> Option<&semver::Version> {
+        self.result.version.as_ref()
+    }
+
+    /// The minimum cargo version which can parse the project, or `None` if no construct with a
+    /// known cargo version was found.
+    pub fn cargo_version(&self) -> Option<&semver::Version> {
+        self.result.cargo_version.as_ref()
+    }
+
+    pub fn findings(&self) -> &[Finding] {
+        &self.result.findings
+    }
+}
+
+impl From<EstimateResult> for SubcommandResult {
+    fn from(it: EstimateResult) -> Self {
+        Self::Estimate(it)
+    }
+}
+
+impl From<EstimateResult> for Event {
+    fn from(it: EstimateResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    version: Option<semver::Version>,
+    cargo_version: Option<semver::Version>,
+    /// Always `true`: the version was derived by static analysis, not by compiling the crate.
+    is_estimate: bool,
+    findings: Vec<Finding>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate::estimate_source;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use crate::stabilization::StabilizationIndex;
+    use camino::Utf8Path;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let estimate = estimate_source(
+            "async fn f() {}",
+            Utf8Path::new("src/lib.rs"),
+            &StabilizationIndex::shipped(),
+        );
+        let event = EstimateResult::new(estimate);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Estimate(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::Estimate(msg)) = &events[0].message {
+            assert_eq!(msg.version(), Some(&semver::Version::new(1, 39, 0)));
+            assert_eq!(msg.findings().len(), 1);
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use crate::context::SearchMethod;
+use crate::fingerprint::Fingerprint;
 use crate::manifest::bare_version::BareVersion;
 use crate::reporter::event::subcommand_result::SubcommandResult;
 use crate::reporter::event::Message;
+use crate::search_method::Shard;
 use crate::typed_bool::{False, True};
 use crate::{semver, Event};
 
@@ -18,6 +20,15 @@ pub struct FindResult {
     pub search_method: SearchMethod,
 
     result: ResultDetails,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shard: Option<ShardResult>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_diff: Option<ErrorDiff>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<Fingerprint>,
 }
 
 impl FindResult {
@@ -39,6 +50,9 @@ impl FindResult {
                 version,
                 success: True,
             },
+            shard: None,
+            error_diff: None,
+            fingerprint: None,
         }
     }
 
@@ -56,9 +70,30 @@ impl FindResult {
             search_method,
 
             result: ResultDetails::Undetermined { success: False },
+            shard: None,
+            error_diff: None,
+            fingerprint: None,
         }
     }
 
+    /// Mark this result as the result of a search over a single shard of the search space.
+    pub fn with_shard(mut self, shard: ShardResult) -> Self {
+        self.shard = Some(shard);
+        self
+    }
+
+    /// Include the comparison of the compiler output at the MSRV boundary.
+    pub fn with_error_diff(mut self, error_diff: ErrorDiff) -> Self {
+        self.error_diff = Some(error_diff);
+        self
+    }
+
+    /// Include the fingerprint of everything which affects the result.
+    pub fn with_fingerprint(mut self, fingerprint: Fingerprint) -> Self {
+        self.fingerprint = Some(fingerprint);
+        self
+    }
+
     pub fn msrv(&self) -> Option<&semver::Version> {
         if let Self {
             result: ResultDetails::Determined { version, .. },
@@ -70,6 +105,46 @@ impl FindResult {
             None
         }
     }
+
+    pub fn shard(&self) -> Option<&ShardResult> {
+        self.shard.as_ref()
+    }
+
+    pub fn error_diff(&self) -> Option<&ErrorDiff> {
+        self.error_diff.as_ref()
+    }
+
+    pub fn fingerprint(&self) -> Option<&Fingerprint> {
+        self.fingerprint.as_ref()
+    }
+}
+
+/// The difference between the diagnostics emitted by the check of the most recent incompatible
+/// release, and the diagnostics emitted by the check of the MSRV.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct ErrorDiff {
+    /// The most recent release which was found to be incompatible.
+    pub failing_version: semver::Version,
+    /// The MSRV.
+    pub passing_version: semver::Version,
+    /// The diagnostics of `failing_version` which no longer occur with `passing_version`.
+    pub removed: Vec<String>,
+    /// The diagnostics of `passing_version` which did not occur with `failing_version`.
+    pub added: Vec<String>,
+}
+
+/// The outcome of a linear search over a single shard of the search space.
+///
+/// The MSRV reported with it, is the least recent release of the shard for which it, and every
+/// more recent release of the shard, were found to be compatible.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShardResult {
+    #[serde(flatten)]
+    pub shard: Shard,
+
+    /// Whether each release of the shard was found to be compatible, in which case less recent
+    /// shards need to be considered to determine the MSRV.
+    pub all_compatible: bool,
 }
 
 impl From<FindResult> for SubcommandResult {
@@ -128,6 +203,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialized_shard() {
+        let min = BareVersion::TwoComponents(1, 0);
+        let max = BareVersion::ThreeComponents(1, 4, 0);
+
+        let event = FindResult::none("x", min, max, SearchMethod::Linear).with_shard(ShardResult {
+            shard: Shard::new(2, 3).unwrap(),
+            all_compatible: false,
+        });
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(
+            json["shard"],
+            serde_json::json!({ "index": 2, "count": 3, "all_compatible": false })
+        );
+    }
+
     #[test]
     fn reported_msrv_undetermined_event() {
         let reporter = TestReporterWrapper::default();
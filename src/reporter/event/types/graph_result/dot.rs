@@ -0,0 +1,81 @@
+//! Renders the graph in the [DOT language](https://graphviz.org/doc/info/lang.html).
+
+use super::MsrvGraph;
+use std::fmt::Write;
+
+/// Nodes are filled from green, for the oldest required Rust version, to red, for the newest.
+/// Nodes of packages which do not specify a Rust version are left white.
+pub(super) fn render(graph: &MsrvGraph) -> String {
+    let mut out = String::new();
+
+    out.push_str("digraph dependencies {\n");
+    out.push_str("    node [shape=box, style=filled, fillcolor=white];\n");
+
+    for node in &graph.nodes {
+        let rust_version = node
+            .rust_version
+            .as_ref()
+            .map_or_else(|| "unspecified".to_string(), ToString::to_string);
+
+        let _ = write!(
+            out,
+            "    n{} [label=\"{} {}\\nrust-version: {}\"",
+            node.id,
+            escape(node.name),
+            node.version,
+            rust_version
+        );
+
+        if let Some(heat) = node.heat {
+            // An HSV color, where the hue goes from green (1/3) to red (0)
+            let _ = write!(out, ", fillcolor=\"{:.3} 0.450 1.000\"", (1.0 - heat) / 3.0);
+        }
+
+        if node.is_root {
+            out.push_str(", penwidth=2");
+        }
+
+        out.push_str("];\n");
+    }
+
+    for edge in &graph.edges {
+        let _ = write!(out, "    n{} -> n{}", edge.from, edge.to);
+
+        if edge.critical {
+            out.push_str(" [color=red, penwidth=2]");
+        }
+
+        out.push_str(";\n");
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn escape(id: &str) -> String {
+    id.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context::graph::GraphFormat;
+    use crate::dependency_graph::fixture;
+    use crate::reporter::event::GraphResult;
+
+    #[test]
+    fn render() {
+        let result = GraphResult::new(GraphFormat::Dot, &fixture::graph());
+
+        let expected = r#"digraph dependencies {
+    node [shape=box, style=filled, fillcolor=white];
+    n0 [label="a 0.1.0\nrust-version: 1.70.0", fillcolor="0.000 0.450 1.000", penwidth=2];
+    n1 [label="b 0.1.0\nrust-version: 1.60.0", fillcolor="0.333 0.450 1.000"];
+    n2 [label="c 0.1.0\nrust-version: unspecified"];
+    n0 -> n1 [color=red, penwidth=2];
+    n1 -> n2;
+}
+"#;
+
+        assert_eq!(result.graph(), expected);
+    }
+}
@@ -0,0 +1,118 @@
+//! Renders the graph as a [Mermaid](https://mermaid.js.org/syntax/flowchart.html) flowchart,
+//! which GitHub renders within markdown files, issues and pull requests.
+
+use super::MsrvGraph;
+use std::fmt::Write;
+
+/// Nodes are filled from green, for the oldest required Rust version, to red, for the newest.
+/// Nodes of packages which do not specify a Rust version are left unfilled.
+pub(super) fn render(graph: &MsrvGraph) -> String {
+    let mut out = String::new();
+
+    out.push_str("flowchart TD\n");
+
+    for node in &graph.nodes {
+        let rust_version = node
+            .rust_version
+            .as_ref()
+            .map_or_else(|| "unspecified".to_string(), ToString::to_string);
+
+        let _ = writeln!(
+            out,
+            "    n{}[\"{} {}<br/>rust-version: {}\"]",
+            node.id,
+            escape(node.name),
+            node.version,
+            rust_version
+        );
+    }
+
+    for edge in &graph.edges {
+        let _ = writeln!(out, "    n{} --> n{}", edge.from, edge.to);
+    }
+
+    for node in &graph.nodes {
+        let mut style = Vec::new();
+
+        if let Some(heat) = node.heat {
+            style.push(format!("fill:{}", rgb((1.0 - heat) / 3.0, 0.45, 1.0)));
+        }
+
+        if node.is_root {
+            style.push("stroke-width:3px".to_string());
+        }
+
+        if !style.is_empty() {
+            let _ = writeln!(out, "    style n{} {}", node.id, style.join(","));
+        }
+    }
+
+    // Edges are styled by the order in which they are defined
+    for (nth, _) in graph.edges.iter().enumerate().filter(|(_, e)| e.critical) {
+        let _ = writeln!(out, "    linkStyle {} stroke:red,stroke-width:2px", nth);
+    }
+
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+/// Converts an HSV color, where each component is within `0.0..=1.0`, to a hex RGB color.
+fn rgb(hue: f64, saturation: f64, value: f64) -> String {
+    let sector = (hue * 6.0).floor();
+    let fraction = hue * 6.0 - sector;
+
+    let p = value * (1.0 - saturation);
+    let q = value * (1.0 - fraction * saturation);
+    let t = value * (1.0 - (1.0 - fraction) * saturation);
+
+    let (r, g, b) = match sector as u32 % 6 {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    };
+
+    let byte = |component: f64| (component * 255.0).round() as u8;
+
+    format!("#{:02x}{:02x}{:02x}", byte(r), byte(g), byte(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rgb;
+    use crate::context::graph::GraphFormat;
+    use crate::dependency_graph::fixture;
+    use crate::reporter::event::GraphResult;
+
+    #[test]
+    fn render() {
+        let result = GraphResult::new(GraphFormat::Mermaid, &fixture::graph());
+
+        let expected = r#"flowchart TD
+    n0["a 0.1.0<br/>rust-version: 1.70.0"]
+    n1["b 0.1.0<br/>rust-version: 1.60.0"]
+    n2["c 0.1.0<br/>rust-version: unspecified"]
+    n0 --> n1
+    n1 --> n2
+    style n0 fill:#ff8c8c,stroke-width:3px
+    style n1 fill:#8cff8c
+    linkStyle 0 stroke:red,stroke-width:2px
+"#;
+
+        assert_eq!(result.graph(), expected);
+    }
+
+    #[yare::parameterized(
+        red = { 0.0, "#ff8c8c" },
+        yellow = { 1.0 / 6.0, "#ffff8c" },
+        green = { 1.0 / 3.0, "#8cff8c" },
+    )]
+    fn hsv_to_rgb(hue: f64, expected: &str) {
+        assert_eq!(rgb(hue, 0.45, 1.0), expected);
+    }
+}
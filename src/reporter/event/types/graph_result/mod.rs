@@ -0,0 +1,201 @@
+use crate::context::graph::GraphFormat;
+use crate::dependency_graph::DependencyGraph;
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::types::list_result::metadata::package_msrv;
+use crate::reporter::event::Message;
+use crate::{semver, Event};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::Bfs;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+mod dot;
+mod mermaid;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct GraphResult {
+    result: ResultDetails,
+}
+
+impl GraphResult {
+    pub fn new(format: GraphFormat, graph: &DependencyGraph) -> Self {
+        let msrv_graph = MsrvGraph::new(graph);
+
+        let rendered = match format {
+            GraphFormat::Dot => dot::render(&msrv_graph),
+            GraphFormat::Mermaid => mermaid::render(&msrv_graph),
+        };
+
+        Self {
+            result: ResultDetails {
+                format,
+                graph: rendered,
+            },
+        }
+    }
+
+    /// The dependency graph, rendered in the requested format.
+    pub fn graph(&self) -> &str {
+        &self.result.graph
+    }
+}
+
+impl fmt::Display for GraphResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.graph())
+    }
+}
+
+impl From<GraphResult> for SubcommandResult {
+    fn from(it: GraphResult) -> Self {
+        Self::Graph(it)
+    }
+}
+
+impl From<GraphResult> for Event {
+    fn from(it: GraphResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    format: GraphFormat,
+    graph: String,
+}
+
+/// The packages reachable from the root crate, and the dependency edges between them, annotated
+/// with the Rust versions the packages require.
+///
+/// This is the input of each of the graph formats.
+struct MsrvGraph<'g> {
+    /// The nodes, in breadth first order from the root crate.
+    nodes: Vec<Node<'g>>,
+    /// The edges, ordered by the nodes they originate from, and then by the nodes they point to.
+    edges: Vec<Edge>,
+}
+
+struct Node<'g> {
+    /// An identifier, unique within the graph.
+    id: usize,
+    name: &'g str,
+    version: &'g semver::Version,
+    rust_version: Option<semver::Version>,
+    is_root: bool,
+    /// Where the `rust_version` ranks among the Rust versions required within the graph, from
+    /// `0.0` for the oldest, to `1.0` for the newest. `None` if no Rust version is required.
+    heat: Option<f64>,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    /// Whether this edge is on the shortest path from the root crate to one of the dependencies
+    /// which require the newest Rust version.
+    critical: bool,
+}
+
+impl<'g> MsrvGraph<'g> {
+    fn new(graph: &'g DependencyGraph) -> Self {
+        let Some(&root_index) = graph.index().get(graph.root_crate()) else {
+            return Self {
+                nodes: Vec::new(),
+                edges: Vec::new(),
+            };
+        };
+
+        let packages = graph.packages();
+        let root_index = NodeIndex::new(root_index);
+
+        let mut reachable = Vec::new();
+        let mut bfs = Bfs::new(packages, root_index);
+
+        while let Some(nx) = bfs.next(packages) {
+            reachable.push((nx, package_msrv(&packages[nx])));
+        }
+
+        let versions = reachable
+            .iter()
+            .filter_map(|(_, rust_version)| rust_version.as_ref())
+            .collect::<BTreeSet<_>>();
+
+        let heat = |rust_version: Option<&semver::Version>| {
+            let rank = versions.iter().position(|v| Some(*v) == rust_version)?;
+
+            if versions.len() == 1 {
+                Some(1.0)
+            } else {
+                Some(rank as f64 / (versions.len() - 1) as f64)
+            }
+        };
+
+        let critical_edges = critical_edges(graph, root_index, &reachable);
+
+        let nodes = reachable
+            .iter()
+            .map(|(nx, rust_version)| {
+                let package = &packages[*nx];
+
+                Node {
+                    id: nx.index(),
+                    name: &package.name,
+                    version: &package.version,
+                    heat: heat(rust_version.as_ref()),
+                    rust_version: rust_version.clone(),
+                    is_root: *nx == root_index,
+                }
+            })
+            .collect();
+
+        let edges = reachable
+            .iter()
+            .flat_map(|(from, _)| {
+                let mut to = packages
+                    .neighbors_directed(*from, petgraph::Direction::Outgoing)
+                    .collect::<Vec<_>>();
+                to.sort();
+
+                to.into_iter().map(|to| Edge {
+                    from: from.index(),
+                    to: to.index(),
+                    critical: critical_edges.contains(&(*from, to)),
+                })
+            })
+            .collect();
+
+        Self { nodes, edges }
+    }
+}
+
+/// The edges on the shortest paths from the root crate to each of the dependencies which require
+/// the newest Rust version.
+fn critical_edges(
+    graph: &DependencyGraph,
+    root_index: NodeIndex<usize>,
+    reachable: &[(NodeIndex<usize>, Option<semver::Version>)],
+) -> HashSet<(NodeIndex<usize>, NodeIndex<usize>)> {
+    let dependencies = reachable.iter().filter(|(nx, _)| *nx != root_index);
+
+    let Some(newest) = dependencies
+        .clone()
+        .filter_map(|(_, rust_version)| rust_version.as_ref())
+        .max()
+    else {
+        return HashSet::new();
+    };
+
+    let parents: HashMap<_, _> = graph.shortest_path_parents();
+    let mut edges = HashSet::new();
+
+    for (nx, _) in dependencies.filter(|(_, rust_version)| rust_version.as_ref() == Some(newest)) {
+        let mut current = *nx;
+
+        while let Some(&parent) = parents.get(&current) {
+            edges.insert((parent, current));
+            current = parent;
+        }
+    }
+
+    edges
+}
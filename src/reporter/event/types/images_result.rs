@@ -0,0 +1,122 @@
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::reporter::formatting::table;
+use crate::{semver, Event};
+use std::fmt;
+use tabled::Tabled;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ImagesResult {
+    result: ResultDetails,
+}
+
+impl ImagesResult {
+    pub fn new(
+        msrv: BareVersion,
+        images: Vec<ContainerImage>,
+        packages: Vec<DistributionPackage>,
+    ) -> Self {
+        Self {
+            result: ResultDetails {
+                msrv,
+                images,
+                packages,
+            },
+        }
+    }
+
+    /// The MSRV the images and packages were suggested for.
+    pub fn msrv(&self) -> &BareVersion {
+        &self.result.msrv
+    }
+
+    /// The tags of the official `rust` container image which provide the MSRV.
+    pub fn images(&self) -> &[ContainerImage] {
+        &self.result.images
+    }
+
+    /// The Rust version packaged by each distribution release.
+    pub fn packages(&self) -> &[DistributionPackage] {
+        &self.result.packages
+    }
+}
+
+impl fmt::Display for ImagesResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let images = self.images().iter().map(|image| ImageRow {
+            image: &image.image,
+            base: &image.base,
+        });
+
+        let packages = self.packages().iter().map(|package| PackageRow {
+            distribution: &package.distribution,
+            release: &package.release,
+            rustc: &package.rustc,
+            compatible: if package.compatible { "yes" } else { "no" },
+        });
+
+        writeln!(f, "{}", table(images))?;
+        f.write_fmt(format_args!("{}", table(packages)))
+    }
+}
+
+impl From<ImagesResult> for SubcommandResult {
+    fn from(it: ImagesResult) -> Self {
+        Self::Images(it)
+    }
+}
+
+impl From<ImagesResult> for Event {
+    fn from(it: ImagesResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    msrv: BareVersion,
+    images: Vec<ContainerImage>,
+    packages: Vec<DistributionPackage>,
+}
+
+/// A tag of the official `rust` container image.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct ContainerImage {
+    /// The image, with its tag, like `rust:1.70-slim`.
+    pub image: String,
+    /// The system on which the image is based.
+    pub base: String,
+}
+
+/// The Rust toolchain packaged by a release of a Linux distribution.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct DistributionPackage {
+    pub distribution: String,
+    pub release: String,
+    /// The packaged Rust version.
+    pub rustc: semver::Version,
+    /// Whether the packaged Rust version is at least the MSRV.
+    pub compatible: bool,
+}
+
+#[derive(Tabled)]
+struct ImageRow<'a> {
+    #[tabled(rename = "Image")]
+    image: &'a str,
+    #[tabled(rename = "Based on")]
+    base: &'a str,
+}
+
+#[derive(Tabled)]
+struct PackageRow<'a> {
+    #[tabled(rename = "Distribution")]
+    distribution: &'a str,
+    #[tabled(rename = "Release")]
+    release: &'a str,
+    #[tabled(rename = "rustc")]
+    rustc: &'a semver::Version,
+    #[tabled(rename = "Provides MSRV")]
+    compatible: &'static str,
+}
@@ -0,0 +1,225 @@
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::reporter::formatting::table;
+use crate::run_artifacts::Run;
+use crate::{semver, Event};
+use camino::Utf8PathBuf;
+use std::fmt;
+use tabled::Tabled;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct InspectResult {
+    result: InspectView,
+}
+
+impl InspectResult {
+    /// The stored runs, ordered from the least to the most recent.
+    pub fn runs(runs: &[Run]) -> Self {
+        Self {
+            result: InspectView::Runs {
+                runs: runs.iter().map(RunOverview::of).collect(),
+            },
+        }
+    }
+
+    /// A single run, with the artifacts of each of its checks.
+    pub fn run(path: Utf8PathBuf, run: Run) -> Self {
+        Self {
+            result: InspectView::Run { path, run },
+        }
+    }
+
+    /// The complete output of a single check of a run.
+    pub fn log(run_id: String, version: semver::Version, path: Utf8PathBuf, log: String) -> Self {
+        Self {
+            result: InspectView::Log {
+                run_id,
+                version,
+                path,
+                log,
+            },
+        }
+    }
+
+    pub fn view(&self) -> &InspectView {
+        &self.result
+    }
+
+    /// Whether the stored runs were listed, but no run was stored.
+    pub fn has_no_runs(&self) -> bool {
+        matches!(&self.result, InspectView::Runs { runs } if runs.is_empty())
+    }
+}
+
+impl fmt::Display for InspectResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result {
+            InspectView::Runs { runs } => {
+                let rows = runs.iter().map(|run| RunRow {
+                    run_id: &run.run_id,
+                    subcommand: &run.subcommand,
+                    started_at: &run.started_at,
+                    checks: run.checks,
+                    msrv: match (&run.msrv, run.finished) {
+                        (Some(msrv), _) => msrv.to_string(),
+                        (None, true) => String::new(),
+                        (None, false) => "unfinished".to_string(),
+                    },
+                });
+
+                f.write_fmt(format_args!("{}", table(rows)))
+            }
+            InspectView::Run { run, .. } => {
+                let rows = run.checks.iter().enumerate().map(|(i, check)| CheckRow {
+                    number: i + 1,
+                    version: &check.version,
+                    compatible: check.compatible,
+                    duration: format!("{:.1}s", check.duration_ms as f64 / 1000.0),
+                    first_diagnostic: check
+                        .diagnostics
+                        .iter()
+                        .find(|diagnostic| diagnostic.starts_with("error"))
+                        .and_then(|diagnostic| diagnostic.lines().next())
+                        .unwrap_or_default(),
+                });
+
+                f.write_fmt(format_args!("{}", table(rows)))
+            }
+            InspectView::Log { log, .. } => f.write_str(log),
+        }
+    }
+}
+
+impl From<InspectResult> for SubcommandResult {
+    fn from(it: InspectResult) -> Self {
+        Self::Inspect(it)
+    }
+}
+
+impl From<InspectResult> for Event {
+    fn from(it: InspectResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "view")]
+pub enum InspectView {
+    Runs {
+        runs: Vec<RunOverview>,
+    },
+    Run {
+        /// The directory in which the artifacts of the run are stored.
+        path: Utf8PathBuf,
+        run: Run,
+    },
+    Log {
+        run_id: String,
+        version: semver::Version,
+        /// The file in which the output of the check is stored.
+        path: Utf8PathBuf,
+        log: String,
+    },
+}
+
+/// A run, without the artifacts of its checks.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct RunOverview {
+    pub run_id: String,
+    pub subcommand: String,
+    pub started_at: String,
+    pub finished: bool,
+    /// The amount of checks which ran.
+    pub checks: usize,
+    pub msrv: Option<semver::Version>,
+}
+
+impl RunOverview {
+    fn of(run: &Run) -> Self {
+        Self {
+            run_id: run.run_id.clone(),
+            subcommand: run.subcommand.clone(),
+            started_at: run.started_at.clone(),
+            finished: run.finished_at.is_some(),
+            checks: run.checks.len(),
+            msrv: run.msrv.clone(),
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct RunRow<'a> {
+    #[tabled(rename = "Run")]
+    run_id: &'a str,
+    #[tabled(rename = "Subcommand")]
+    subcommand: &'a str,
+    #[tabled(rename = "Started")]
+    started_at: &'a str,
+    #[tabled(rename = "Checks")]
+    checks: usize,
+    #[tabled(rename = "MSRV")]
+    msrv: String,
+}
+
+#[derive(Tabled)]
+struct CheckRow<'a> {
+    #[tabled(rename = "#")]
+    number: usize,
+    #[tabled(rename = "Rust version")]
+    version: &'a semver::Version,
+    #[tabled(rename = "Compatible")]
+    compatible: bool,
+    #[tabled(rename = "Duration")]
+    duration: String,
+    #[tabled(rename = "First error")]
+    first_diagnostic: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    fn run(finished: bool, msrv: Option<semver::Version>) -> Run {
+        Run {
+            run_id: "20240615T133045Z".to_string(),
+            subcommand: "find".to_string(),
+            started_at: "2024-06-15T13:30:45Z".to_string(),
+            finished_at: finished.then(|| "2024-06-15T13:40:00Z".to_string()),
+            msrv,
+            checks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let event = InspectResult::runs(&[run(true, Some(semver::Version::new(1, 60, 0)))]);
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Inspect(event)
+            ))]
+        );
+    }
+
+    #[yare::parameterized(
+        found = { run(true, Some(semver::Version::new(1, 60, 0))), "1.60.0" },
+        unfinished = { run(false, None), "unfinished" },
+    )]
+    fn runs_table(run: Run, expected_msrv: &str) {
+        let table = InspectResult::runs(&[run]).to_string();
+
+        assert!(table.contains("20240615T133045Z"));
+        assert!(table.contains(expected_msrv));
+    }
+}
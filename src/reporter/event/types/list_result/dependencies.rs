@@ -0,0 +1,94 @@
+use super::metadata::{package_msrv, PackageSource};
+use crate::context::list::ListFilter;
+use crate::dependency_graph::DependencyGraph;
+use crate::semver;
+use camino::Utf8Path;
+use petgraph::graph::NodeIndex;
+
+/// The version of the schema of the [`Dependency`] list.
+///
+/// Fields may be added to the schema without changing its version, but fields are never removed
+/// or changed, unless the schema version is incremented.
+pub(super) const SCHEMA_VERSION: u32 = 1;
+
+/// A dependency of the root crate, as listed by `cargo msrv list` regardless of the variant.
+#[derive(Debug, Eq, PartialEq, serde::Serialize)]
+pub(super) struct Dependency<'g> {
+    pub name: &'g str,
+    pub version: &'g semver::Version,
+    /// The `rust-version` (or `package.metadata.msrv`) declared by the dependency, if any.
+    pub rust_version: Option<semver::Version>,
+    pub kind: DependencyKind,
+    pub source: PackageSource,
+    /// The manifest of the dependency, if it is a path dependency.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_path: Option<&'g Utf8Path>,
+    /// The names of the packages through which the dependency is included, starting with the
+    /// root crate, and ending with the dependency itself.
+    ///
+    /// If the dependency is included through multiple paths, the shortest is given.
+    pub path: Vec<&'g str>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) enum DependencyKind {
+    /// A dependency of the root crate itself.
+    Direct,
+    /// A dependency of another dependency.
+    Transitive,
+}
+
+/// The dependencies of the root crate included by the `filter`, ordered by name and version.
+pub(super) fn dependencies<'g>(
+    graph: &'g DependencyGraph,
+    filter: &ListFilter,
+) -> Vec<Dependency<'g>> {
+    let packages = graph.packages();
+    let parents = graph.shortest_path_parents();
+
+    let path_to = |nx: NodeIndex<usize>| {
+        let mut path = vec![packages[nx].name.as_str()];
+        let mut current = nx;
+
+        while let Some(&parent) = parents.get(&current) {
+            path.push(packages[parent].name.as_str());
+            current = parent;
+        }
+
+        path.reverse();
+        path
+    };
+
+    let mut dependencies = parents
+        .keys()
+        .filter_map(|&nx| {
+            let package = &packages[nx];
+            let path = path_to(nx);
+            let rust_version = package_msrv(package);
+            let source = PackageSource::of(package);
+
+            if !filter.includes(path.len() - 1, rust_version.as_ref()) {
+                return None;
+            }
+
+            Some(Dependency {
+                name: &package.name,
+                version: &package.version,
+                rust_version,
+                kind: if path.len() == 2 {
+                    DependencyKind::Direct
+                } else {
+                    DependencyKind::Transitive
+                },
+                source,
+                manifest_path: (source == PackageSource::Path)
+                    .then_some(package.manifest_path.as_path()),
+                path,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    dependencies.sort_by(|lhs, rhs| (lhs.name, lhs.version).cmp(&(rhs.name, rhs.version)));
+    dependencies
+}
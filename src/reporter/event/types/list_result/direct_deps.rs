@@ -1,7 +1,7 @@
 use super::display_option;
 use super::display_vec;
 use super::metadata::*;
-use crate::context::list::DIRECT_DEPS;
+use crate::context::list::{ListFilter, DIRECT_DEPS};
 use crate::dependency_graph::DependencyGraph;
 use crate::reporter::formatting::table;
 use std::fmt;
@@ -10,17 +10,18 @@ use tabled::Tabled;
 
 pub struct DirectDepsFormatter<'g> {
     graph: &'g DependencyGraph,
+    filter: &'g ListFilter,
 }
 
 impl<'g> DirectDepsFormatter<'g> {
-    pub fn new(graph: &'g DependencyGraph) -> Self {
-        Self { graph }
+    pub fn new(graph: &'g DependencyGraph, filter: &'g ListFilter) -> Self {
+        Self { graph, filter }
     }
 }
 
 impl fmt::Display for DirectDepsFormatter<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let values = dependencies(self.graph);
+        let values = dependencies(self.graph, self.filter);
 
         f.write_fmt(format_args!("{}", table(values)))
     }
@@ -33,34 +34,43 @@ impl serde::Serialize for DirectDepsFormatter<'_> {
     {
         let serializable = SerializableValues {
             variant: DIRECT_DEPS,
-            list: dependencies(self.graph).collect(),
+            list: dependencies(self.graph, self.filter).collect(),
         };
 
         serializable.serialize(serializer)
     }
 }
 
-fn dependencies(graph: &DependencyGraph) -> impl Iterator<Item = Values> {
+fn dependencies<'g>(
+    graph: &'g DependencyGraph,
+    filter: &'g ListFilter,
+) -> impl Iterator<Item = Values<'g>> {
     let package_id = graph.root_crate();
     let root_index = graph.index()[package_id].into();
     let neighbors = graph
         .packages()
         .neighbors_directed(root_index, petgraph::Direction::Outgoing);
 
-    neighbors.map(move |dependency| {
+    neighbors.filter_map(move |dependency| {
         let package = &graph.packages()[dependency];
         let msrv = package_msrv(package);
 
-        Values {
+        // Direct dependencies are always within the depth of the filter
+        if !filter.includes(1, msrv.as_ref()) {
+            return None;
+        }
+
+        Some(Values {
             name: &package.name,
             version: &package.version,
             msrv: format_version(msrv.as_ref()),
+            source: PackageSource::of(package),
             dependencies: package
                 .dependencies
                 .iter()
                 .map(|d| d.name.clone())
                 .collect(),
-        }
+        })
     })
 }
 
@@ -72,6 +82,8 @@ struct Values<'a> {
     version: &'a crate::semver::Version,
     #[tabled(rename = "MSRV", display_with = "display_option")]
     msrv: Option<String>,
+    #[tabled(rename = "Source")]
+    source: PackageSource,
     #[tabled(rename = "Depends on", display_with = "display_vec")]
     dependencies: Vec<String>,
 }
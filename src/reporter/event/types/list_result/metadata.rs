@@ -3,6 +3,7 @@ use crate::manifest::CargoManifest;
 use crate::semver;
 use cargo_metadata::{MetadataCommand, Package};
 use std::convert::TryFrom;
+use std::fmt;
 use std::path::Path;
 
 pub fn package_msrv(package: &Package) -> Option<semver::Version> {
@@ -13,6 +14,38 @@ pub fn package_msrv(package: &Package) -> Option<semver::Version> {
         .or_else(|| parse_manifest_workaround(package.manifest_path.as_path())) // todo: add last one as option to config
 }
 
+/// Where a package is obtained from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackageSource {
+    /// A package registry, such as crates.io.
+    Registry,
+    /// A git repository.
+    Git,
+    /// A local path, e.g. a member of the workspace, or a path dependency.
+    Path,
+}
+
+impl PackageSource {
+    pub fn of(package: &Package) -> Self {
+        match &package.source {
+            None => Self::Path,
+            Some(source) if source.repr.starts_with("git+") => Self::Git,
+            Some(_) => Self::Registry,
+        }
+    }
+}
+
+impl fmt::Display for PackageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Registry => f.write_str("registry"),
+            Self::Git => f.write_str("git"),
+            Self::Path => f.write_str("path"),
+        }
+    }
+}
+
 pub fn format_version(version: Option<&semver::Version>) -> Option<String> {
     version.map(ToString::to_string)
 }
@@ -5,13 +5,14 @@ use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Formatter;
 
-use crate::context::list::ListMsrvVariant;
+use crate::context::list::{ListFilter, ListMsrvVariant};
 use crate::reporter::event::subcommand_result::SubcommandResult;
 use crate::reporter::event::types::list_result::ordered_by_msrv::OrderedByMsrvFormatter;
 use direct_deps::DirectDepsFormatter;
 
+mod dependencies;
 mod direct_deps;
-mod metadata;
+pub(crate) mod metadata;
 mod ordered_by_msrv;
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
@@ -23,9 +24,19 @@ pub struct ListResult {
 impl ListResult {
     pub fn new(variant: ListMsrvVariant, graph: DependencyGraph) -> Self {
         Self {
-            result: ResultDetails { variant, graph },
+            result: ResultDetails {
+                variant,
+                graph,
+                filter: ListFilter::default(),
+            },
         }
     }
+
+    /// Only list the dependencies included by the given `filter`.
+    pub fn with_filter(mut self, filter: ListFilter) -> Self {
+        self.result.filter = filter;
+        self
+    }
 }
 
 impl fmt::Display for ListResult {
@@ -50,17 +61,20 @@ impl From<ListResult> for Event {
 struct ResultDetails {
     variant: ListMsrvVariant,
     graph: DependencyGraph,
+    filter: ListFilter,
 }
 
 impl fmt::Display for ResultDetails {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self.variant {
-            ListMsrvVariant::DirectDeps => {
-                f.write_fmt(format_args!("{}", DirectDepsFormatter::new(&self.graph)))
-            }
-            ListMsrvVariant::OrderedByMSRV => {
-                f.write_fmt(format_args!("{}", OrderedByMsrvFormatter::new(&self.graph)))
-            }
+            ListMsrvVariant::DirectDeps => f.write_fmt(format_args!(
+                "{}",
+                DirectDepsFormatter::new(&self.graph, &self.filter)
+            )),
+            ListMsrvVariant::OrderedByMSRV => f.write_fmt(format_args!(
+                "{}",
+                OrderedByMsrvFormatter::new(&self.graph, &self.filter)
+            )),
         }
     }
 }
@@ -72,15 +86,37 @@ impl serde::Serialize for ResultDetails {
     {
         match self.variant {
             ListMsrvVariant::DirectDeps => {
-                DirectDepsFormatter::new(&self.graph).serialize(serializer)
+                let variant = DirectDepsFormatter::new(&self.graph, &self.filter);
+                SerializableResult::new(variant, &self.graph, &self.filter).serialize(serializer)
             }
             ListMsrvVariant::OrderedByMSRV => {
-                OrderedByMsrvFormatter::new(&self.graph).serialize(serializer)
+                let variant = OrderedByMsrvFormatter::new(&self.graph, &self.filter);
+                SerializableResult::new(variant, &self.graph, &self.filter).serialize(serializer)
             }
         }
     }
 }
 
+/// The variant specific `list`, together with the variant independent `dependencies`, which have
+/// a stable schema.
+#[derive(serde::Serialize)]
+struct SerializableResult<'g, V: serde::Serialize> {
+    #[serde(flatten)]
+    variant: V,
+    schema_version: u32,
+    dependencies: Vec<dependencies::Dependency<'g>>,
+}
+
+impl<'g, V: serde::Serialize> SerializableResult<'g, V> {
+    fn new(variant: V, graph: &'g DependencyGraph, filter: &ListFilter) -> Self {
+        Self {
+            variant,
+            schema_version: dependencies::SCHEMA_VERSION,
+            dependencies: dependencies::dependencies(graph, filter),
+        }
+    }
+}
+
 fn display_option(option: &Option<String>) -> Cow<'static, str> {
     match option {
         Some(s) => Cow::from(s.to_string()),
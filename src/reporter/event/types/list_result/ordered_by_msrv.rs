@@ -1,11 +1,12 @@
 use super::display_option;
 use super::display_vec;
-use crate::context::list::ORDERED_BY_MSRV;
+use crate::context::list::{ListFilter, ORDERED_BY_MSRV};
 use crate::dependency_graph::DependencyGraph;
 use crate::reporter::event::types::list_result::metadata::{format_version, package_msrv};
 use crate::reporter::formatting::table;
 use crate::semver;
 use cargo_metadata::Package;
+use petgraph::algo::dijkstra;
 use petgraph::visit::Bfs;
 use std::collections::BTreeMap;
 use std::fmt;
@@ -14,17 +15,18 @@ use tabled::Tabled;
 
 pub struct OrderedByMsrvFormatter<'g> {
     graph: &'g DependencyGraph,
+    filter: &'g ListFilter,
 }
 
 impl<'g> OrderedByMsrvFormatter<'g> {
-    pub fn new(graph: &'g DependencyGraph) -> Self {
-        Self { graph }
+    pub fn new(graph: &'g DependencyGraph, filter: &'g ListFilter) -> Self {
+        Self { graph, filter }
     }
 }
 
 impl fmt::Display for OrderedByMsrvFormatter<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let values = dependencies(self.graph);
+        let values = dependencies(self.graph, self.filter);
 
         f.write_fmt(format_args!("{}", table(values)))
     }
@@ -37,18 +39,24 @@ impl serde::Serialize for OrderedByMsrvFormatter<'_> {
     {
         let serializable = SerializableValues {
             variant: ORDERED_BY_MSRV,
-            list: dependencies(self.graph).collect(),
+            list: dependencies(self.graph, self.filter).collect(),
         };
 
         serializable.serialize(serializer)
     }
 }
 
-fn dependencies(graph: &DependencyGraph) -> impl Iterator<Item = Values> + '_ {
+fn dependencies<'g>(
+    graph: &'g DependencyGraph,
+    filter: &ListFilter,
+) -> impl Iterator<Item = Values> + 'g {
     let package_id = &graph.root_crate();
     let root_index = graph.index()[package_id].into();
     let mut bfs = Bfs::new(graph.packages(), root_index);
 
+    // The length of the shortest path from the root crate to each package
+    let depths = dijkstra(graph.packages(), root_index, None, |_| 1usize);
+
     let mut version_map: BTreeMap<Option<semver::Version>, Vec<&Package>> = BTreeMap::new();
 
     while let Some(nx) = bfs.next(graph.packages()) {
@@ -56,6 +64,10 @@ fn dependencies(graph: &DependencyGraph) -> impl Iterator<Item = Values> + '_ {
 
         let msrv = package_msrv(package);
 
+        if !filter.includes(depths[&nx], msrv.as_ref()) {
+            continue;
+        }
+
         version_map.entry(msrv).or_default().push(package);
     }
 
@@ -0,0 +1,81 @@
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::{semver, Event};
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MergeResult {
+    result: ResultDetails,
+}
+
+impl MergeResult {
+    pub fn new(version: Option<semver::Version>, shards: u32) -> Self {
+        Self {
+            result: ResultDetails {
+                success: version.is_some(),
+                version,
+                shards,
+            },
+        }
+    }
+
+    /// The MSRV, as concluded from the results of all shards, if any release was compatible.
+    pub fn msrv(&self) -> Option<&semver::Version> {
+        self.result.version.as_ref()
+    }
+
+    /// The amount of shards from which the result was concluded.
+    pub fn shards(&self) -> u32 {
+        self.result.shards
+    }
+}
+
+impl From<MergeResult> for SubcommandResult {
+    fn from(it: MergeResult) -> Self {
+        Self::MergeResults(it)
+    }
+}
+
+impl From<MergeResult> for Event {
+    fn from(it: MergeResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<semver::Version>,
+    success: bool,
+    shards: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let event = MergeResult::new(Some(semver::Version::new(1, 60, 0)), 3);
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::MergeResults(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::MergeResults(msg)) = &events[0].message {
+            assert_eq!(msg.msrv(), Some(&semver::Version::new(1, 60, 0)));
+            assert_eq!(msg.shards(), 3);
+        }
+    }
+}
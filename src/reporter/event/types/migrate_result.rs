@@ -0,0 +1,219 @@
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::reporter::formatting::table;
+use crate::Event;
+use camino::Utf8PathBuf;
+use std::fmt;
+use tabled::Tabled;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrateResult {
+    result: ResultDetails,
+}
+
+impl MigrateResult {
+    pub fn new(dry_run: bool, migrated: Vec<Migration>, skipped: Vec<SkippedMigration>) -> Self {
+        Self {
+            result: ResultDetails {
+                dry_run,
+                migrated,
+                skipped,
+            },
+        }
+    }
+
+    /// Whether the migrations were only listed, instead of written.
+    pub fn is_dry_run(&self) -> bool {
+        self.result.dry_run
+    }
+
+    /// The packages of which the `package.metadata.msrv` was (or with a dry run, would have been)
+    /// migrated.
+    pub fn migrated(&self) -> &[Migration] {
+        &self.result.migrated
+    }
+
+    /// The packages of which the `package.metadata.msrv` was left as is.
+    pub fn skipped(&self) -> &[SkippedMigration] {
+        &self.result.skipped
+    }
+}
+
+/// The migrated packages, with their legacy MSRV and the `rust-version` which replaces it.
+impl fmt::Display for MigrateResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.migrated().iter().map(|migration| Row {
+            package: &migration.package,
+            manifest_path: &migration.manifest_path,
+            msrv: &migration.msrv,
+            rust_version: &migration.rust_version,
+        });
+
+        f.write_fmt(format_args!("{}", table(rows)))
+    }
+}
+
+impl From<MigrateResult> for SubcommandResult {
+    fn from(it: MigrateResult) -> Self {
+        Self::Migrate(it)
+    }
+}
+
+impl From<MigrateResult> for Event {
+    fn from(it: MigrateResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    dry_run: bool,
+    migrated: Vec<Migration>,
+    skipped: Vec<SkippedMigration>,
+}
+
+/// The `package.metadata.msrv` of a package, which is replaced by `package.rust-version`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Migration {
+    pub package: String,
+    pub manifest_path: Utf8PathBuf,
+    /// The value of `package.metadata.msrv`.
+    pub msrv: BareVersion,
+    /// The value of `package.rust-version` after the migration, as written inline, like `1.60` or
+    /// `{ workspace = true }`.
+    pub rust_version: String,
+    pub kind: MigrationKind,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationKind {
+    /// `package.rust-version` is added.
+    Added,
+    /// `package.rust-version` is added, inherited from `workspace.package.rust-version`, which is
+    /// the same version.
+    Inherited,
+    /// `package.rust-version` already declared the same version, so only `package.metadata.msrv`
+    /// is removed.
+    AlreadyDeclared,
+}
+
+/// The `package.metadata.msrv` of a package, which is not migrated.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct SkippedMigration {
+    pub package: String,
+    pub manifest_path: Utf8PathBuf,
+    /// The value of `package.metadata.msrv`.
+    pub msrv: BareVersion,
+    #[serde(flatten)]
+    pub reason: SkipReason,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case", tag = "reason")]
+pub enum SkipReason {
+    /// `package.rust-version`, possibly inherited from the workspace, declares another version.
+    ConflictingRustVersion { rust_version: BareVersion },
+    /// The version is older than Rust 1.56, which introduced `package.rust-version`.
+    PredatesRustVersion,
+}
+
+impl fmt::Display for SkippedMigration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            SkipReason::ConflictingRustVersion { rust_version } => write!(
+                f,
+                "package.metadata.msrv of {} ({}) is Rust {}, which conflicts with package.rust-version (Rust {}), so it is not migrated",
+                self.package, self.manifest_path, self.msrv, rust_version
+            ),
+            SkipReason::PredatesRustVersion => write!(
+                f,
+                "package.metadata.msrv of {} ({}) is Rust {}, which is older than Rust 1.56, the first version to support package.rust-version, so it is not migrated",
+                self.package, self.manifest_path, self.msrv
+            ),
+        }
+    }
+}
+
+#[derive(Tabled)]
+struct Row<'a> {
+    #[tabled(rename = "Package")]
+    package: &'a str,
+    #[tabled(rename = "Manifest")]
+    manifest_path: &'a Utf8PathBuf,
+    #[tabled(rename = "package.metadata.msrv")]
+    msrv: &'a BareVersion,
+    #[tabled(rename = "package.rust-version")]
+    rust_version: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let migration = Migration {
+            package: "a".to_string(),
+            manifest_path: Utf8PathBuf::from("a/Cargo.toml"),
+            msrv: BareVersion::TwoComponents(1, 60),
+            rust_version: "1.60".to_string(),
+            kind: MigrationKind::Added,
+        };
+        let skipped = SkippedMigration {
+            package: "b".to_string(),
+            manifest_path: Utf8PathBuf::from("b/Cargo.toml"),
+            msrv: BareVersion::TwoComponents(1, 60),
+            reason: SkipReason::ConflictingRustVersion {
+                rust_version: BareVersion::ThreeComponents(1, 65, 0),
+            },
+        };
+        let event = MigrateResult::new(true, vec![migration.clone()], vec![skipped.clone()]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Migrate(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::Migrate(msg)) = &events[0].message {
+            assert!(msg.is_dry_run());
+            assert_eq!(msg.migrated(), &[migration]);
+            assert_eq!(msg.skipped(), &[skipped]);
+        }
+    }
+
+    #[test]
+    fn serialized_skip_reason() {
+        let skipped = SkippedMigration {
+            package: "b".to_string(),
+            manifest_path: Utf8PathBuf::from("b/Cargo.toml"),
+            msrv: BareVersion::TwoComponents(1, 60),
+            reason: SkipReason::ConflictingRustVersion {
+                rust_version: BareVersion::ThreeComponents(1, 65, 0),
+            },
+        };
+
+        assert_eq!(
+            serde_json::to_value(&skipped).unwrap(),
+            serde_json::json!({
+                "package": "b",
+                "manifest_path": "b/Cargo.toml",
+                "msrv": "1.60",
+                "reason": "conflicting_rust_version",
+                "rust_version": "1.65.0",
+            })
+        );
+    }
+}
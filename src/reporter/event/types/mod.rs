@@ -1,5 +1,20 @@
+pub mod bisect_lockfile_result;
+pub mod cache_stats_result;
+pub mod clean_result;
+pub mod consistency_result;
+pub mod estimate_result;
 pub mod find_result;
+pub mod graph_result;
+pub mod images_result;
+pub mod inspect_result;
 pub mod list_result;
+pub mod merge_result;
+pub mod migrate_result;
+pub mod outdated_result;
+pub mod report_result;
 pub mod set_result;
 pub mod show_result;
+pub mod sync_result;
+pub mod targets_result;
 pub mod verify_result;
+pub mod whatif_result;
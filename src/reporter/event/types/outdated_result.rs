@@ -0,0 +1,93 @@
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::reporter::formatting::table;
+use crate::{semver, Event};
+use std::fmt;
+use tabled::Tabled;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct OutdatedResult {
+    result: ResultDetails,
+}
+
+impl OutdatedResult {
+    pub fn new(msrv: semver::Version, dependencies: Vec<OutdatedDependency>) -> Self {
+        Self {
+            result: ResultDetails { msrv, dependencies },
+        }
+    }
+
+    /// The MSRV the releases of the dependencies were compared against.
+    pub fn msrv(&self) -> &semver::Version {
+        &self.result.msrv
+    }
+
+    /// The dependencies of which the newest release requires a newer Rust version than the MSRV.
+    pub fn dependencies(&self) -> &[OutdatedDependency] {
+        &self.result.dependencies
+    }
+}
+
+impl fmt::Display for OutdatedResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.dependencies().iter().map(|dependency| Row {
+            name: &dependency.name,
+            version: &dependency.version,
+            latest: &dependency.latest,
+            latest_rust_version: &dependency.latest_rust_version,
+            newest_compatible: dependency
+                .newest_compatible
+                .as_ref()
+                .map_or_else(String::new, ToString::to_string),
+        });
+
+        f.write_fmt(format_args!("{}", table(rows)))
+    }
+}
+
+impl From<OutdatedResult> for SubcommandResult {
+    fn from(it: OutdatedResult) -> Self {
+        Self::Outdated(it)
+    }
+}
+
+impl From<OutdatedResult> for Event {
+    fn from(it: OutdatedResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    msrv: semver::Version,
+    dependencies: Vec<OutdatedDependency>,
+}
+
+/// A dependency which can not be upgraded to its newest release, without raising the MSRV.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct OutdatedDependency {
+    pub name: String,
+    /// The version currently depended upon.
+    pub version: semver::Version,
+    /// The newest release.
+    pub latest: semver::Version,
+    /// The Rust version required by the newest release.
+    pub latest_rust_version: semver::Version,
+    /// The newest release which is compatible with the MSRV, if any.
+    pub newest_compatible: Option<semver::Version>,
+}
+
+#[derive(Tabled)]
+struct Row<'a> {
+    #[tabled(rename = "Name")]
+    name: &'a str,
+    #[tabled(rename = "Version")]
+    version: &'a semver::Version,
+    #[tabled(rename = "Latest")]
+    latest: &'a semver::Version,
+    #[tabled(rename = "Latest requires")]
+    latest_rust_version: &'a semver::Version,
+    #[tabled(rename = "Newest compatible")]
+    newest_compatible: String,
+}
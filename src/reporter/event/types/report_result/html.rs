@@ -0,0 +1,111 @@
+//! Renders the report as a standalone HTML page, without external resources, so it can be
+//! published as an artifact of a CI job.
+
+use super::{compatibility, footer, summary, title};
+use crate::recorded_run::RecordedRun;
+use std::fmt::Write;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+.compatible { color: #1a7f37; }
+.incompatible { color: #cf222e; }";
+
+pub(super) fn render(run: &RecordedRun) -> String {
+    let mut out = String::new();
+    let title = escape(&title(run));
+
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>",
+        title, STYLE
+    );
+    let _ = writeln!(out, "<h1>{}</h1>\n<p>{}</p>", title, escape(&summary(run)));
+
+    if let Some(failure) = &run.failure {
+        let _ = writeln!(out, "<pre>{}</pre>", escape(failure.trim_end()));
+    }
+
+    if !run.checks.is_empty() {
+        out.push_str(
+            "<table>\n<tr><th>Rust version</th><th>Target</th><th>Result</th><th>Error</th></tr>\n",
+        );
+
+        for check in &run.checks {
+            let compatibility = compatibility(check);
+            let error = check
+                .first_error()
+                .map(|error| format!("<code>{}</code>", escape(error)))
+                .unwrap_or_default();
+
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td class=\"{}\">{}</td><td>{}</td></tr>",
+                check.toolchain.version,
+                escape(&check.toolchain.target),
+                compatibility,
+                compatibility,
+                error
+            );
+        }
+
+        out.push_str("</table>\n");
+    }
+
+    if !run.warnings.is_empty() {
+        out.push_str("<h2>Warnings</h2>\n<ul>\n");
+
+        for warning in &run.warnings {
+            let _ = writeln!(
+                out,
+                "<li><code>{}</code>: {}</li>",
+                escape(&warning.code),
+                escape(&warning.message)
+            );
+        }
+
+        out.push_str("</ul>\n");
+    }
+
+    let _ = writeln!(
+        out,
+        "<footer>{}</footer>\n</body>\n</html>",
+        escape(&footer(run))
+    );
+
+    out
+}
+
+pub(super) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::recorded_run;
+    use super::*;
+
+    #[test]
+    fn escaped_error() {
+        let rendered = render(&recorded_run());
+
+        assert!(rendered.starts_with("<!DOCTYPE html>"));
+        assert!(rendered.contains(
+            "<td class=\"incompatible\">incompatible</td><td><code>error[E0658]: `let...else` is &lt;unstable&gt; &amp; | experimental</code></td>"
+        ));
+        assert!(rendered.trim_end().ends_with("</html>"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Renders the report as a JUnit XML report, which most CI systems show as test results.
+//!
+//! Each check is a test case, and the result of the run is a test case of its own, which fails if
+//! the run failed. When the run searched for the MSRV, incompatible Rust versions are expected, so
+//! their checks don't fail: the error is included as the output of the test case instead.
+
+use super::{duration_secs, summary, title};
+use crate::recorded_run::RecordedRun;
+use std::fmt::Write;
+
+pub(super) fn render(run: &RecordedRun) -> String {
+    let classname = match &run.subcommand {
+        Some(subcommand) => format!("cargo-msrv.{}", subcommand),
+        None => "cargo-msrv".to_string(),
+    };
+
+    let mut cases = String::new();
+    let mut failures = 0;
+
+    for check in &run.checks {
+        let name = format!(
+            "Rust {} ({})",
+            check.toolchain.version, check.toolchain.target
+        );
+        let error = check.error.as_deref().unwrap_or_default().trim_end();
+
+        let body = if check.is_compatible {
+            None
+        } else if run.is_search() {
+            Some(format!("<system-out>{}</system-out>", escape(error)))
+        } else {
+            failures += 1;
+            let message = check.first_error().unwrap_or("incompatible");
+            Some(format!(
+                "<failure message=\"{}\">{}</failure>",
+                escape(message),
+                escape(error)
+            ))
+        };
+
+        write_case(&mut cases, &name, &classname, body);
+    }
+
+    let body = (!run.is_success()).then(|| {
+        failures += 1;
+        let failure = run.failure.as_deref().unwrap_or_default().trim_end();
+        format!(
+            "<failure message=\"{}\">{}</failure>",
+            escape(&summary(run)),
+            escape(failure)
+        )
+    });
+    write_case(&mut cases, "result", &classname, body);
+
+    let tests = run.checks.len() + 1;
+    let time = run
+        .termination
+        .as_ref()
+        .map(|termination| format!(" time=\"{:.3}\"", duration_secs(termination.duration_ms)))
+        .unwrap_or_default();
+    let name = escape(&title(run));
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\"{}>",
+        name, tests, failures, time
+    );
+    let _ = writeln!(
+        out,
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\"{}>",
+        name, tests, failures, time
+    );
+    out.push_str(&cases);
+    out.push_str("  </testsuite>\n</testsuites>\n");
+
+    out
+}
+
+fn write_case(out: &mut String, name: &str, classname: &str, body: Option<String>) {
+    let _ = write!(
+        out,
+        "    <testcase name=\"{}\" classname=\"{}\"",
+        escape(name),
+        escape(classname)
+    );
+
+    match body {
+        Some(body) => {
+            let _ = writeln!(out, ">\n      {}\n    </testcase>", body);
+        }
+        None => out.push_str("/>\n"),
+    }
+}
+
+/// Like HTML, but without the control characters which XML doesn't allow, like the escape codes
+/// of colored compiler output.
+fn escape(text: &str) -> String {
+    let text = text
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>();
+
+    super::html::escape(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::recorded_run;
+    use super::*;
+    use crate::recorded_run::RecordedResult;
+    use crate::semver;
+
+    #[test]
+    fn search_expects_incompatible_versions() {
+        let rendered = render(&recorded_run());
+
+        assert!(rendered.contains(
+            "<testsuite name=\"cargo msrv find\" tests=\"3\" failures=\"0\" time=\"1.500\">"
+        ));
+        assert!(rendered.contains(
+            "<testcase name=\"Rust 1.55.0 (x86_64-unknown-linux-gnu)\" classname=\"cargo-msrv.find\">\n      <system-out>error[E0658]: `let...else` is &lt;unstable&gt; &amp; | experimental</system-out>"
+        ));
+        assert!(rendered.contains("<testcase name=\"result\" classname=\"cargo-msrv.find\"/>"));
+    }
+
+    #[test]
+    fn failed_verification() {
+        let mut run = recorded_run();
+        run.subcommand = Some("verify".to_string());
+        run.result = Some(RecordedResult::Verified {
+            version: semver::Version::new(1, 55, 0),
+            compatible: false,
+        });
+        run.checks.remove(0);
+        run.failure = Some("Crate source was found to be incompatible".to_string());
+
+        let rendered = render(&run);
+
+        assert!(rendered.contains("tests=\"2\" failures=\"2\""));
+        assert!(rendered.contains("<failure message=\"error[E0658]: `let...else` is &lt;unstable&gt; &amp; | experimental\">"));
+        assert!(rendered.contains(
+            "<failure message=\"Rust 1.55.0 is not compatible\">Crate source was found to be incompatible</failure>"
+        ));
+    }
+
+    #[test]
+    fn control_characters_removed() {
+        assert_eq!(escape("\u{1b}[1merror\u{1b}[0m\n"), "[1merror[0m\n");
+    }
+}
@@ -0,0 +1,81 @@
+//! Renders the report as markdown, e.g. for a pull request comment or the summary of a CI job.
+
+use super::{compatibility, footer, summary, title};
+use crate::recorded_run::RecordedRun;
+use std::fmt::Write;
+
+pub(super) fn render(run: &RecordedRun) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# {}\n\n{}", title(run), summary(run));
+
+    if let Some(failure) = &run.failure {
+        let _ = writeln!(out, "\n```\n{}\n```", failure.trim_end());
+    }
+
+    if !run.checks.is_empty() {
+        out.push_str("\n| Rust version | Target | Result | Error |\n| --- | --- | --- | --- |\n");
+
+        for check in &run.checks {
+            let icon = if check.is_compatible { "✅" } else { "❌" };
+            let error = check
+                .first_error()
+                .map(|error| format!("`{}`", escape(error)))
+                .unwrap_or_default();
+
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} {} | {} |",
+                check.toolchain.version,
+                check.toolchain.target,
+                icon,
+                compatibility(check),
+                error
+            );
+        }
+    }
+
+    if !run.warnings.is_empty() {
+        out.push_str("\n## Warnings\n\n");
+
+        for warning in &run.warnings {
+            let _ = writeln!(out, "* `{}`: {}", warning.code, warning.message);
+        }
+    }
+
+    let _ = writeln!(out, "\n{}", footer(run));
+
+    out
+}
+
+/// Errors are rendered as code within a table cell, so neither may end early.
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|").replace('`', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::recorded_run;
+    use super::*;
+
+    #[test]
+    fn rendered() {
+        let expected = "# cargo msrv find
+
+The MSRV is Rust 1.56.0
+
+| Rust version | Target | Result | Error |
+| --- | --- | --- | --- |
+| 1.60.0 | x86_64-unknown-linux-gnu | ✅ compatible |  |
+| 1.55.0 | x86_64-unknown-linux-gnu | ❌ incompatible | `error[E0658]: 'let...else' is <unstable> & \\| experimental` |
+
+## Warnings
+
+* `lockfile_regenerated`: The lockfile was regenerated
+
+Recorded by cargo-msrv 0.16.2, which ran for 1.5s, with outcome 'success'
+";
+
+        assert_eq!(render(&recorded_run()), expected);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::context::report::ReportFormat;
+use crate::recorded_run::{RecordedCheck, RecordedResult, RecordedRun};
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::Event;
+use std::fmt;
+
+mod html;
+mod junit;
+mod markdown;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ReportResult {
+    result: ResultDetails,
+}
+
+impl ReportResult {
+    pub fn new(format: ReportFormat, run: &RecordedRun) -> Self {
+        let rendered = match format {
+            ReportFormat::Markdown => markdown::render(run),
+            ReportFormat::Html => html::render(run),
+            ReportFormat::Junit => junit::render(run),
+        };
+
+        Self {
+            result: ResultDetails {
+                format,
+                report: rendered,
+            },
+        }
+    }
+
+    /// The report, rendered in the requested format.
+    pub fn report(&self) -> &str {
+        &self.result.report
+    }
+}
+
+impl fmt::Display for ReportResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.report())
+    }
+}
+
+impl From<ReportResult> for SubcommandResult {
+    fn from(it: ReportResult) -> Self {
+        Self::Report(it)
+    }
+}
+
+impl From<ReportResult> for Event {
+    fn from(it: ReportResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    format: ReportFormat,
+    report: String,
+}
+
+/// The title of the report, like `cargo msrv find`.
+fn title(run: &RecordedRun) -> String {
+    match &run.subcommand {
+        Some(subcommand) => format!("cargo msrv {}", subcommand),
+        None => "cargo msrv".to_string(),
+    }
+}
+
+/// The result of the run, as a sentence.
+fn summary(run: &RecordedRun) -> String {
+    match &run.result {
+        Some(RecordedResult::Msrv(Some(msrv))) => format!("The MSRV is Rust {}", msrv),
+        Some(RecordedResult::Msrv(None)) => {
+            "No Rust version in the search space is compatible".to_string()
+        }
+        Some(RecordedResult::Verified {
+            version,
+            compatible: true,
+        }) => format!("Rust {} is compatible", version),
+        Some(RecordedResult::Verified {
+            version,
+            compatible: false,
+        }) => format!("Rust {} is not compatible", version),
+        None if run.is_success() => "The run has no result".to_string(),
+        None => "The run failed".to_string(),
+    }
+}
+
+fn compatibility(check: &RecordedCheck) -> &'static str {
+    if check.is_compatible {
+        "compatible"
+    } else {
+        "incompatible"
+    }
+}
+
+/// The version of cargo-msrv, how long the run took, and how it ended, as far as recorded.
+fn footer(run: &RecordedRun) -> String {
+    let mut footer = match &run.cargo_msrv_version {
+        Some(version) => format!("Recorded by cargo-msrv {}", version),
+        None => "Recorded by cargo-msrv".to_string(),
+    };
+
+    if let Some(termination) = &run.termination {
+        footer.push_str(&format!(
+            ", which ran for {:.1}s, with outcome '{}'",
+            duration_secs(termination.duration_ms),
+            termination.outcome
+        ));
+    }
+
+    footer
+}
+
+fn duration_secs(duration_ms: u64) -> f64 {
+    duration_ms as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recorded_run::{RecordedTermination, RecordedToolchain, RecordedWarning};
+    use crate::semver;
+
+    pub(super) fn recorded_run() -> RecordedRun {
+        let check = |minor: u64, error: Option<&str>| RecordedCheck {
+            toolchain: RecordedToolchain {
+                version: semver::Version::new(1, minor, 0),
+                target: "x86_64-unknown-linux-gnu".to_string(),
+            },
+            is_compatible: error.is_none(),
+            error: error.map(String::from),
+        };
+
+        RecordedRun {
+            cargo_msrv_version: Some("0.16.2".to_string()),
+            subcommand: Some("find".to_string()),
+            result: Some(RecordedResult::Msrv(Some(semver::Version::new(1, 56, 0)))),
+            checks: vec![
+                check(60, None),
+                check(
+                    55,
+                    Some("error[E0658]: `let...else` is <unstable> & | experimental"),
+                ),
+            ],
+            warnings: vec![RecordedWarning {
+                code: "lockfile_regenerated".to_string(),
+                message: "The lockfile was regenerated".to_string(),
+            }],
+            failure: None,
+            termination: Some(RecordedTermination {
+                outcome: "success".to_string(),
+                duration_ms: 1500,
+            }),
+        }
+    }
+
+    #[yare::parameterized(
+        msrv = { Some(RecordedResult::Msrv(Some(semver::Version::new(1, 56, 0)))), None, "The MSRV is Rust 1.56.0" },
+        no_msrv = { Some(RecordedResult::Msrv(None)), None, "No Rust version in the search space is compatible" },
+        verified = { Some(RecordedResult::Verified { version: semver::Version::new(1, 56, 0), compatible: true }), None, "Rust 1.56.0 is compatible" },
+        not_verified = { Some(RecordedResult::Verified { version: semver::Version::new(1, 56, 0), compatible: false }), Some("incompatible"), "Rust 1.56.0 is not compatible" },
+        failed = { None, Some("no manifest"), "The run failed" },
+    )]
+    fn summary_of_result(result: Option<RecordedResult>, failure: Option<&str>, expected: &str) {
+        let run = RecordedRun {
+            result,
+            failure: failure.map(String::from),
+            ..RecordedRun::default()
+        };
+
+        assert_eq!(summary(&run), expected);
+    }
+
+    #[test]
+    fn footer_of_run() {
+        assert_eq!(
+            footer(&recorded_run()),
+            "Recorded by cargo-msrv 0.16.2, which ran for 1.5s, with outcome 'success'"
+        );
+    }
+}
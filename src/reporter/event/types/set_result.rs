@@ -1,3 +1,4 @@
+use crate::declaration::Declaration;
 use crate::manifest::bare_version::BareVersion;
 use crate::reporter::event::subcommand_result::SubcommandResult;
 use crate::reporter::event::Message;
@@ -16,10 +17,17 @@ impl SetResult {
             result: ResultDetails {
                 version: version.into(),
                 manifest_path,
+                synced: Vec::new(),
             },
         }
     }
 
+    /// Include the other declarations of the MSRV, which were updated to the MSRV.
+    pub fn with_synced(mut self, synced: Vec<Declaration>) -> Self {
+        self.result.synced = synced;
+        self
+    }
+
     pub fn version(&self) -> &BareVersion {
         &self.result.version
     }
@@ -27,6 +35,11 @@ impl SetResult {
     pub fn manifest_path(&self) -> &Utf8Path {
         &self.result.manifest_path
     }
+
+    /// The declarations which were updated to the MSRV, with the version they declared before.
+    pub fn synced(&self) -> &[Declaration] {
+        &self.result.synced
+    }
 }
 
 impl From<SetResult> for SubcommandResult {
@@ -45,6 +58,8 @@ impl From<SetResult> for Event {
 struct ResultDetails {
     version: BareVersion,
     manifest_path: Utf8PathBuf,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    synced: Vec<Declaration>,
 }
 
 #[cfg(test)]
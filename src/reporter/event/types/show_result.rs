@@ -1,8 +1,14 @@
+use crate::dependency_graph::DependencyGraph;
 use crate::manifest::bare_version::BareVersion;
+use crate::manifest::msrv_sources::MsrvSource;
 use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::types::list_result::metadata::{package_msrv, PackageSource};
 use crate::reporter::event::Message;
-use crate::Event;
+use crate::reporter::formatting::table;
+use crate::{semver, Event};
 use camino::{Utf8Path, Utf8PathBuf};
+use std::fmt;
+use tabled::Tabled;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -16,10 +22,41 @@ impl ShowResult {
             result: ResultDetails {
                 version: version.into(),
                 manifest_path,
+                source: None,
+                path_dependencies: Vec::new(),
             },
         }
     }
 
+    /// Include the source which declares the MSRV, and the file of that source.
+    pub fn with_source(mut self, kind: MsrvSource, path: Utf8PathBuf) -> Self {
+        self.result.source = Some(Source { kind, path });
+        self
+    }
+
+    /// Include the path dependencies of the root crate of the `graph`, with their declared Rust
+    /// version. Both direct and transitive path dependencies are included.
+    pub fn with_path_dependencies(mut self, graph: &DependencyGraph) -> Self {
+        let packages = graph.packages();
+
+        let mut path_dependencies = graph
+            .shortest_path_parents()
+            .into_keys()
+            .map(|nx| &packages[nx])
+            .filter(|package| PackageSource::of(package) == PackageSource::Path)
+            .map(|package| PathDependency {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                rust_version: package_msrv(package),
+                manifest_path: package.manifest_path.clone(),
+            })
+            .collect::<Vec<_>>();
+        path_dependencies.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+
+        self.result.path_dependencies = path_dependencies;
+        self
+    }
+
     pub fn version(&self) -> &BareVersion {
         &self.result.version
     }
@@ -27,6 +64,14 @@ impl ShowResult {
     pub fn manifest_path(&self) -> &Utf8Path {
         &self.result.manifest_path
     }
+
+    pub fn source(&self) -> Option<&Source> {
+        self.result.source.as_ref()
+    }
+
+    pub fn path_dependencies(&self) -> &[PathDependency] {
+        &self.result.path_dependencies
+    }
 }
 
 impl From<ShowResult> for SubcommandResult {
@@ -45,6 +90,126 @@ impl From<ShowResult> for Event {
 struct ResultDetails {
     version: BareVersion,
     manifest_path: Utf8PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<Source>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    path_dependencies: Vec<PathDependency>,
+}
+
+/// The MSRV of each member of a virtual workspace, i.e. a workspace of which the root manifest has
+/// no `[package]`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ShowWorkspaceResult {
+    result: WorkspaceDetails,
+}
+
+impl ShowWorkspaceResult {
+    pub fn new(
+        manifest_path: Utf8PathBuf,
+        rust_version: Option<BareVersion>,
+        members: Vec<MemberMsrv>,
+    ) -> Self {
+        Self {
+            result: WorkspaceDetails {
+                manifest_path,
+                rust_version,
+                members,
+            },
+        }
+    }
+
+    pub fn manifest_path(&self) -> &Utf8Path {
+        &self.result.manifest_path
+    }
+
+    /// The `workspace.package.rust-version`, which members can inherit, if any.
+    pub fn rust_version(&self) -> Option<&BareVersion> {
+        self.result.rust_version.as_ref()
+    }
+
+    pub fn members(&self) -> &[MemberMsrv] {
+        &self.result.members
+    }
+}
+
+impl fmt::Display for ShowWorkspaceResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.members().iter().map(|member| MemberRow {
+            name: &member.name,
+            version: member
+                .version
+                .as_ref()
+                .map_or_else(|| "none".to_string(), ToString::to_string),
+            source: member
+                .source
+                .as_ref()
+                .map_or_else(String::new, |source| source.kind.to_string()),
+            manifest_path: &member.manifest_path,
+        });
+
+        f.write_fmt(format_args!("{}", table(rows)))
+    }
+}
+
+impl From<ShowWorkspaceResult> for SubcommandResult {
+    fn from(it: ShowWorkspaceResult) -> Self {
+        Self::ShowWorkspace(it)
+    }
+}
+
+impl From<ShowWorkspaceResult> for Event {
+    fn from(it: ShowWorkspaceResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct WorkspaceDetails {
+    manifest_path: Utf8PathBuf,
+    rust_version: Option<BareVersion>,
+    members: Vec<MemberMsrv>,
+}
+
+/// The effective MSRV of a member of the workspace, which may be inherited from the workspace.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct MemberMsrv {
+    pub name: String,
+    pub manifest_path: Utf8PathBuf,
+    /// The MSRV, or `None` if the member does not declare one.
+    pub version: Option<BareVersion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+}
+
+#[derive(Tabled)]
+struct MemberRow<'a> {
+    #[tabled(rename = "Package")]
+    name: &'a str,
+    #[tabled(rename = "MSRV")]
+    version: String,
+    #[tabled(rename = "Declared as")]
+    source: String,
+    #[tabled(rename = "Manifest")]
+    manifest_path: &'a Utf8PathBuf,
+}
+
+/// The MSRV source which declares the MSRV, see [`crate::manifest::msrv_sources`].
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Source {
+    pub kind: MsrvSource,
+    pub path: Utf8PathBuf,
+}
+
+/// A dependency of the crate, which is located by a local path, such as another member of the
+/// workspace.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct PathDependency {
+    pub name: String,
+    pub version: semver::Version,
+    /// The `rust-version` (or `package.metadata.msrv`) declared by the dependency, if any.
+    pub rust_version: Option<semver::Version>,
+    pub manifest_path: Utf8PathBuf,
 }
 
 #[cfg(test)]
@@ -54,6 +219,61 @@ mod tests {
     use crate::reporter::TestReporterWrapper;
     use storyteller::EventReporter;
 
+    #[test]
+    fn path_dependencies() {
+        let graph = crate::dependency_graph::fixture::graph();
+        let event = ShowResult::new(BareVersion::TwoComponents(1, 70), "/a/Cargo.toml".into())
+            .with_path_dependencies(&graph);
+
+        let names = event
+            .path_dependencies()
+            .iter()
+            .map(|dependency| (dependency.name.as_str(), dependency.rust_version.clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            vec![("b", Some(semver::Version::new(1, 60, 0))), ("c", None)]
+        );
+    }
+
+    #[test]
+    fn reported_workspace_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let member = MemberMsrv {
+            name: "a".to_string(),
+            manifest_path: "a/Cargo.toml".into(),
+            version: Some(BareVersion::TwoComponents(1, 60)),
+            source: Some(Source {
+                kind: MsrvSource::RustVersion,
+                path: "a/Cargo.toml".into(),
+            }),
+        };
+        let event = ShowWorkspaceResult::new(
+            "Cargo.toml".into(),
+            Some(BareVersion::TwoComponents(1, 60)),
+            vec![member.clone()],
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::ShowWorkspace(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::ShowWorkspace(msg)) = &events[0].message
+        {
+            assert_eq!(msg.rust_version(), Some(&BareVersion::TwoComponents(1, 60)));
+            assert_eq!(msg.members(), &[member]);
+        }
+    }
+
     #[test]
     fn reported_event() {
         let reporter = TestReporterWrapper::default();
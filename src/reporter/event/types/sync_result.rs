@@ -0,0 +1,122 @@
+use crate::declaration::Change;
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::Event;
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SyncResult {
+    result: ResultDetails,
+}
+
+impl SyncResult {
+    pub fn new(msrv: BareVersion, dry_run: bool, changes: Vec<Change>) -> Self {
+        Self {
+            result: ResultDetails {
+                msrv,
+                dry_run,
+                changes,
+            },
+        }
+    }
+
+    /// The MSRV, to which each declaration was updated.
+    pub fn msrv(&self) -> &BareVersion {
+        &self.result.msrv
+    }
+
+    /// Whether the changes were only listed, instead of written.
+    pub fn is_dry_run(&self) -> bool {
+        self.result.dry_run
+    }
+
+    /// The declarations which were (or with a dry run, would have been) updated.
+    pub fn changes(&self) -> &[Change] {
+        &self.result.changes
+    }
+}
+
+/// A summary of the changes, like a diff: the line of each declaration before and after it was
+/// updated.
+impl fmt::Display for SyncResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in self.changes() {
+            let declaration = &change.declaration;
+
+            writeln!(
+                f,
+                "{}:{} ({})",
+                declaration.file, declaration.line, declaration.kind
+            )?;
+            writeln!(f, "- {}", change.before)?;
+            writeln!(f, "+ {}", change.after)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<SyncResult> for SubcommandResult {
+    fn from(it: SyncResult) -> Self {
+        Self::Sync(it)
+    }
+}
+
+impl From<SyncResult> for Event {
+    fn from(it: SyncResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    msrv: BareVersion,
+    dry_run: bool,
+    changes: Vec<Change>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::declaration::{Declaration, DeclarationKind};
+    use crate::reporter::TestReporterWrapper;
+    use camino::Utf8PathBuf;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+
+        let change = Change {
+            declaration: Declaration {
+                kind: DeclarationKind::ClippyMsrv,
+                file: Utf8PathBuf::from("clippy.toml"),
+                line: 1,
+                version: "1.58".parse().unwrap(),
+            },
+            msrv: "1.60".parse().unwrap(),
+            before: "msrv = \"1.58\"".to_string(),
+            after: "msrv = \"1.60\"".to_string(),
+        };
+        let event = SyncResult::new("1.60".parse().unwrap(), false, vec![change.clone()]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::Sync(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::Sync(msg)) = &events[0].message {
+            assert_eq!(msg.msrv(), &BareVersion::TwoComponents(1, 60));
+            assert!(!msg.is_dry_run());
+            assert_eq!(msg.changes(), &[change]);
+        }
+    }
+}
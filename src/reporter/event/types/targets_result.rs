@@ -0,0 +1,150 @@
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::Message;
+use crate::reporter::formatting::table;
+use crate::{semver, Event};
+use std::fmt;
+use tabled::Tabled;
+
+/// The MSRV of each of the targets given with `--targets`, and the MSRV across the targets.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TargetsResult {
+    result: ResultDetails,
+}
+
+impl TargetsResult {
+    pub fn new(targets: Vec<TargetMsrv>) -> Self {
+        // Without an MSRV for each target, the crate has no MSRV across the targets
+        let msrv = targets
+            .iter()
+            .map(|target| target.msrv.clone())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|versions| versions.into_iter().max());
+
+        Self {
+            result: ResultDetails { msrv, targets },
+        }
+    }
+
+    /// The highest MSRV of the targets, or `None` if a target has no MSRV.
+    pub fn msrv(&self) -> Option<&semver::Version> {
+        self.result.msrv.as_ref()
+    }
+
+    pub fn targets(&self) -> &[TargetMsrv] {
+        &self.result.targets
+    }
+
+    /// The targets of which the MSRV is the MSRV across the targets.
+    pub fn limiting_targets(&self) -> impl Iterator<Item = &str> {
+        self.targets()
+            .iter()
+            .filter(|target| target.msrv.is_some() && target.msrv.as_ref() == self.msrv())
+            .map(|target| target.target.as_str())
+    }
+}
+
+impl fmt::Display for TargetsResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.targets().iter().map(|target| Row {
+            target: &target.target,
+            msrv: match &target.msrv {
+                Some(msrv) if target.resumed => format!("{} (resumed)", msrv),
+                Some(msrv) => msrv.to_string(),
+                None => "none".to_string(),
+            },
+        });
+
+        f.write_fmt(format_args!("{}", table(rows)))
+    }
+}
+
+impl From<TargetsResult> for SubcommandResult {
+    fn from(it: TargetsResult) -> Self {
+        Self::FindTargets(it)
+    }
+}
+
+impl From<TargetsResult> for Event {
+    fn from(it: TargetsResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    msrv: Option<semver::Version>,
+    targets: Vec<TargetMsrv>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct TargetMsrv {
+    pub target: String,
+    /// The MSRV for the target, or `None` if no Rust version in the search space is compatible.
+    pub msrv: Option<semver::Version>,
+    /// Whether the saved MSRV of the target was reused, with `--resume`, after its boundary was
+    /// checked again.
+    pub resumed: bool,
+}
+
+#[derive(Tabled)]
+struct Row<'a> {
+    #[tabled(rename = "Target")]
+    target: &'a str,
+    #[tabled(rename = "MSRV")]
+    msrv: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    fn target(target: &str, msrv: Option<(u64, u64)>) -> TargetMsrv {
+        TargetMsrv {
+            target: target.to_string(),
+            msrv: msrv.map(|(major, minor)| semver::Version::new(major, minor, 0)),
+            resumed: false,
+        }
+    }
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = TargetsResult::new(vec![
+            target("x86_64-unknown-linux-gnu", Some((1, 60))),
+            target("wasm32-unknown-unknown", Some((1, 65))),
+        ]);
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        let events = reporter.wait_for_events();
+
+        assert_eq!(
+            &events,
+            &[Event::unscoped(Message::SubcommandResult(
+                SubcommandResult::FindTargets(event)
+            ))]
+        );
+
+        if let Message::SubcommandResult(SubcommandResult::FindTargets(msg)) = &events[0].message {
+            assert_eq!(msg.msrv(), Some(&semver::Version::new(1, 65, 0)));
+            assert_eq!(
+                msg.limiting_targets().collect::<Vec<_>>(),
+                ["wasm32-unknown-unknown"]
+            );
+        }
+    }
+
+    #[test]
+    fn without_msrv_for_a_target() {
+        let event = TargetsResult::new(vec![
+            target("x86_64-unknown-linux-gnu", Some((1, 60))),
+            target("wasm32-unknown-unknown", None),
+        ]);
+
+        assert_eq!(event.msrv(), None);
+        assert_eq!(event.limiting_targets().count(), 0);
+    }
+}
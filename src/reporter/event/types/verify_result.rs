@@ -1,25 +1,92 @@
+use crate::manifest::msrv_policy::ReleaseType;
 use crate::reporter::event::shared::compatibility::Compatibility;
 use crate::reporter::event::subcommand_result::SubcommandResult;
 use crate::reporter::event::Message;
 use crate::rust::Toolchain;
+use crate::semver;
 use crate::Event;
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct VerifyResult {
     pub result: Compatibility,
+    /// How the Rust version compares to the MSRV, when it was found with `verify --find` or
+    /// `verify --slack`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<FreshnessDetails>,
+    /// Whether the MSRV may be raised by the release, for `verify --release-type`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release: Option<ReleaseDetails>,
 }
 
 impl VerifyResult {
     pub fn compatible(toolchain: impl Into<Toolchain>) -> Self {
         Self {
             result: Compatibility::compatible(toolchain),
+            freshness: None,
+            release: None,
         }
     }
 
     pub fn incompatible(toolchain: impl Into<Toolchain>, error: Option<String>) -> Self {
         Self {
             result: Compatibility::incompatible(toolchain, error),
+            freshness: None,
+            release: None,
+        }
+    }
+
+    /// The result of comparing the Rust version of the given toolchain to the MSRV which was
+    /// found. The toolchain is compatible, unless its Rust version is lower than the MSRV.
+    ///
+    /// Without a tolerance, the slack is only reported: a Rust version which is higher than the
+    /// MSRV is still verified.
+    pub fn freshness(toolchain: Toolchain, msrv: semver::Version, tolerance: Option<u64>) -> Self {
+        let freshness = Freshness::of(toolchain.version(), &msrv, tolerance.unwrap_or(0));
+        let slack = slack(toolchain.version(), &msrv);
+        let result = if freshness == Freshness::TooLow {
+            Compatibility::incompatible(toolchain, None)
+        } else {
+            Compatibility::compatible(toolchain)
+        };
+
+        Self {
+            result,
+            freshness: Some(FreshnessDetails {
+                msrv,
+                tolerance,
+                freshness,
+                slack,
+            }),
+            release: None,
+        }
+    }
+
+    /// The result of comparing the MSRV which was found to the Rust version of the given toolchain,
+    /// which is the MSRV of the previous release. The toolchain is incompatible if the MSRV was
+    /// raised, but the release is still verified if the MSRV policy allows the bump.
+    pub fn release(
+        toolchain: Toolchain,
+        msrv: semver::Version,
+        release_type: ReleaseType,
+        bump_allowed: bool,
+    ) -> Self {
+        let raises_msrv = &msrv > toolchain.version();
+        let result = if raises_msrv {
+            Compatibility::incompatible(toolchain, None)
+        } else {
+            Compatibility::compatible(toolchain)
+        };
+
+        Self {
+            result,
+            freshness: None,
+            release: Some(ReleaseDetails {
+                release_type,
+                msrv,
+                raises_msrv,
+                bump_allowed,
+            }),
         }
     }
 
@@ -30,6 +97,97 @@ impl VerifyResult {
     pub fn is_compatible(&self) -> bool {
         self.result.is_compatible()
     }
+
+    pub fn freshness_details(&self) -> Option<&FreshnessDetails> {
+        self.freshness.as_ref()
+    }
+
+    pub fn release_details(&self) -> Option<&ReleaseDetails> {
+        self.release.as_ref()
+    }
+
+    /// Whether the toolchain is compatible and, if the MSRV was found with a tolerance, the Rust
+    /// version is fresh. For a release, whether the MSRV is not raised, or may be raised.
+    pub fn is_verified(&self) -> bool {
+        if let Some(details) = &self.release {
+            return !details.raises_msrv || details.bump_allowed;
+        }
+
+        self.is_compatible()
+            && self.freshness.as_ref().map_or(true, |details| {
+                details.tolerance.is_none() || details.freshness == Freshness::Fresh
+            })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct FreshnessDetails {
+    /// The MSRV which was found.
+    pub msrv: semver::Version,
+    /// How many minor versions the Rust version may be higher than the MSRV, if the slack is
+    /// not only reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<u64>,
+    #[serde(rename = "verdict")]
+    pub freshness: Freshness,
+    /// How many minor releases the Rust version is higher than the MSRV.
+    pub slack: u64,
+}
+
+impl FreshnessDetails {
+    /// The lower MSRV which can be declared, if there is slack.
+    pub fn suggested_msrv(&self) -> Option<&semver::Version> {
+        (self.slack > 0).then_some(&self.msrv)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct ReleaseDetails {
+    pub release_type: ReleaseType,
+    /// The MSRV which was found.
+    pub msrv: semver::Version,
+    /// Whether the MSRV is higher than the Rust version of the previous release.
+    pub raises_msrv: bool,
+    /// Whether the MSRV policy allows releases of this type to raise the MSRV.
+    pub bump_allowed: bool,
+}
+
+/// How many minor releases the Rust version is higher than the MSRV; none if it is lower.
+fn slack(version: &semver::Version, msrv: &semver::Version) -> u64 {
+    if version.major != msrv.major {
+        return 0;
+    }
+
+    version.minor.saturating_sub(msrv.minor)
+}
+
+/// How a Rust version compares to the MSRV which was found.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Freshness {
+    /// The Rust version is the MSRV, or higher within the tolerance.
+    Fresh,
+    /// The Rust version is lower than the MSRV, so the crate does not build with it.
+    TooLow,
+    /// The Rust version is higher than the MSRV by more than the tolerance.
+    TooHigh,
+}
+
+impl Freshness {
+    pub fn of(version: &semver::Version, msrv: &semver::Version, tolerance: u64) -> Self {
+        if version < msrv {
+            return Self::TooLow;
+        }
+
+        let within_tolerance =
+            version.major == msrv.major && version.minor - msrv.minor <= tolerance;
+
+        if within_tolerance {
+            Self::Fresh
+        } else {
+            Self::TooHigh
+        }
+    }
 }
 
 impl From<VerifyResult> for SubcommandResult {
@@ -49,7 +207,7 @@ mod tests {
     use super::*;
     use crate::reporter::event::Message;
     use crate::reporter::TestReporterWrapper;
-    use crate::{semver, Event};
+    use crate::Event;
     use storyteller::EventReporter;
 
     #[test]
@@ -91,4 +249,94 @@ mod tests {
             ))]
         );
     }
+
+    #[yare::parameterized(
+        msrv = { (1, 60, 0), 0, Freshness::Fresh },
+        patch_of_msrv = { (1, 60, 1), 0, Freshness::Fresh },
+        within_tolerance = { (1, 62, 0), 2, Freshness::Fresh },
+        too_low = { (1, 59, 0), 2, Freshness::TooLow },
+        too_high = { (1, 61, 0), 0, Freshness::TooHigh },
+        beyond_tolerance = { (1, 63, 0), 2, Freshness::TooHigh },
+    )]
+    fn freshness(version: (u64, u64, u64), tolerance: u64, expected: Freshness) {
+        let (major, minor, patch) = version;
+        let version = semver::Version::new(major, minor, patch);
+
+        assert_eq!(
+            Freshness::of(&version, &semver::Version::new(1, 60, 0), tolerance),
+            expected
+        );
+    }
+
+    #[test]
+    fn serialized_freshness() {
+        let event = VerifyResult::freshness(
+            Toolchain::new(semver::Version::new(1, 65, 0), "x", &[]),
+            semver::Version::new(1, 60, 0),
+            Some(2),
+        );
+
+        assert!(event.is_compatible());
+        assert!(!event.is_verified());
+        assert_eq!(
+            serde_json::to_value(&event).unwrap()["freshness"],
+            serde_json::json!({ "msrv": "1.60.0", "tolerance": 2, "verdict": "too_high", "slack": 5 })
+        );
+    }
+
+    #[yare::parameterized(
+        msrv = { (1, 60, 0), 0, true, false },
+        patch_of_msrv = { (1, 60, 3), 0, true, false },
+        slack = { (1, 64, 0), 4, true, true },
+        too_low = { (1, 58, 0), 0, false, false },
+    )]
+    fn reported_slack(
+        version: (u64, u64, u64),
+        expected_slack: u64,
+        is_verified: bool,
+        has_suggestion: bool,
+    ) {
+        let (major, minor, patch) = version;
+        let event = VerifyResult::freshness(
+            Toolchain::new(semver::Version::new(major, minor, patch), "x", &[]),
+            semver::Version::new(1, 60, 0),
+            None,
+        );
+        let details = event.freshness_details().unwrap();
+
+        assert_eq!(details.slack, expected_slack);
+        assert_eq!(event.is_verified(), is_verified);
+        assert_eq!(details.suggested_msrv().is_some(), has_suggestion);
+        assert!(serde_json::to_value(&event).unwrap()["freshness"]
+            .get("tolerance")
+            .is_none());
+    }
+
+    #[yare::parameterized(
+        unchanged = { (1, 60, 0), false, true, true },
+        lowered = { (1, 58, 0), false, true, true },
+        raised_and_allowed = { (1, 62, 0), true, true, false },
+        raised_and_not_allowed = { (1, 62, 0), false, false, false },
+    )]
+    fn reported_release(
+        msrv: (u64, u64, u64),
+        bump_allowed: bool,
+        is_verified: bool,
+        is_compatible: bool,
+    ) {
+        let (major, minor, patch) = msrv;
+        let event = VerifyResult::release(
+            Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]),
+            semver::Version::new(major, minor, patch),
+            ReleaseType::Patch,
+            bump_allowed,
+        );
+
+        assert_eq!(event.is_verified(), is_verified);
+        assert_eq!(event.is_compatible(), is_compatible);
+        assert_eq!(
+            serde_json::to_value(&event).unwrap()["release"]["release_type"],
+            "patch"
+        );
+    }
 }
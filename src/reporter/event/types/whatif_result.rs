@@ -0,0 +1,376 @@
+use crate::dependency_graph::DependencyGraph;
+use crate::reporter::event::subcommand_result::SubcommandResult;
+use crate::reporter::event::types::list_result::metadata::package_msrv;
+use crate::reporter::event::Message;
+use crate::{semver, Event};
+use cargo_metadata::Package;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::Bfs;
+use std::collections::HashSet;
+
+/// Whether adding a dependency would raise the MSRV.
+///
+/// The MSRV is assumed to be the newest Rust version required by the crate or any of its
+/// dependencies, as declared in their manifests.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WhatIfResult {
+    result: ResultDetails,
+}
+
+impl WhatIfResult {
+    /// Compare the dependency graphs `before` and `after` adding the dependency with the given
+    /// `name`.
+    ///
+    /// Returns `None` if the dependency is not a direct dependency in the graph `after`.
+    pub fn new(name: &str, before: &DependencyGraph, after: &DependencyGraph) -> Option<Self> {
+        let dependency = direct_dependency(after, name)?;
+
+        let before_packages = reachable_packages(before);
+        let after_packages = reachable_packages(after);
+
+        let msrv_before = effective_msrv(&before_packages);
+        let msrv_after = effective_msrv(&after_packages);
+
+        let existing = before_packages
+            .iter()
+            .map(|(package, _)| &package.id)
+            .collect::<HashSet<_>>();
+
+        let mut raised_by = after_packages
+            .iter()
+            .filter(|(package, _)| !existing.contains(&package.id))
+            .filter(|(_, rust_version)| rust_version.is_some() && *rust_version > msrv_before)
+            .map(|(package, rust_version)| Dependency::new(package, rust_version.clone()))
+            .collect::<Vec<_>>();
+        raised_by.sort_by(|lhs, rhs| (&lhs.name, &lhs.version).cmp(&(&rhs.name, &rhs.version)));
+
+        Some(Self {
+            result: ResultDetails {
+                dependency: Dependency::new(dependency, package_msrv(dependency)),
+                raises_msrv: msrv_after > msrv_before,
+                msrv_before,
+                msrv_after,
+                raised_by,
+                verification: None,
+            },
+        })
+    }
+
+    /// Include the result of compiling the crate, with the added dependency, with the toolchain
+    /// of `msrv_before`.
+    pub fn with_verification(mut self, verification: WhatIfVerification) -> Self {
+        self.result.verification = Some(verification);
+        self
+    }
+
+    /// The dependency which would be added, as resolved.
+    pub fn dependency(&self) -> &Dependency {
+        &self.result.dependency
+    }
+
+    /// The MSRV before the dependency is added, if any package declares a Rust version.
+    pub fn msrv_before(&self) -> Option<&semver::Version> {
+        self.result.msrv_before.as_ref()
+    }
+
+    /// The MSRV once the dependency is added, if any package declares a Rust version.
+    pub fn msrv_after(&self) -> Option<&semver::Version> {
+        self.result.msrv_after.as_ref()
+    }
+
+    /// Whether adding the dependency raises the MSRV.
+    pub fn raises_msrv(&self) -> bool {
+        self.result.raises_msrv
+    }
+
+    /// The packages, introduced by adding the dependency, which require a newer Rust version than
+    /// `msrv_before`.
+    pub fn raised_by(&self) -> &[Dependency] {
+        &self.result.raised_by
+    }
+
+    pub fn verification(&self) -> Option<&WhatIfVerification> {
+        self.result.verification.as_ref()
+    }
+}
+
+impl From<WhatIfResult> for SubcommandResult {
+    fn from(it: WhatIfResult) -> Self {
+        Self::WhatIf(it)
+    }
+}
+
+impl From<WhatIfResult> for Event {
+    fn from(it: WhatIfResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct ResultDetails {
+    dependency: Dependency,
+    msrv_before: Option<semver::Version>,
+    msrv_after: Option<semver::Version>,
+    raises_msrv: bool,
+    raised_by: Vec<Dependency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification: Option<WhatIfVerification>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct Dependency {
+    pub name: String,
+    pub version: semver::Version,
+    pub rust_version: Option<semver::Version>,
+}
+
+impl Dependency {
+    fn new(package: &Package, rust_version: Option<semver::Version>) -> Self {
+        Self {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            rust_version,
+        }
+    }
+}
+
+/// The result of compiling the crate, with the changed dependencies.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct WhatIfVerification {
+    /// The Rust version of the toolchain which was used.
+    pub toolchain: semver::Version,
+    /// Whether the crate compiled.
+    pub compatible: bool,
+}
+
+/// Whether updating the lockfile would raise the MSRV.
+///
+/// Compares the MSRV with the versions pinned by the lockfile, with the MSRV with the newest
+/// versions allowed by the version requirements of the dependencies.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct WhatIfUpdateResult {
+    result: UpdateResultDetails,
+}
+
+impl WhatIfUpdateResult {
+    /// Compare the dependency graph as pinned by the lockfile, with the dependency graph of a
+    /// freshly resolved lockfile.
+    pub fn new(locked: &DependencyGraph, latest: &DependencyGraph) -> Self {
+        let locked_packages = reachable_packages(locked);
+        let latest_packages = reachable_packages(latest);
+
+        let msrv_locked = effective_msrv(&locked_packages);
+        let msrv_latest = effective_msrv(&latest_packages);
+
+        let existing = locked_packages
+            .iter()
+            .map(|(package, _)| &package.id)
+            .collect::<HashSet<_>>();
+
+        let mut raised_by = latest_packages
+            .iter()
+            .filter(|(package, _)| !existing.contains(&package.id))
+            .filter(|(_, rust_version)| rust_version.is_some() && *rust_version > msrv_locked)
+            .map(|(package, rust_version)| UpdatedDependency {
+                name: package.name.clone(),
+                locked_version: locked_packages
+                    .iter()
+                    .filter(|(locked, _)| locked.name == package.name)
+                    .map(|(locked, _)| locked.version.clone())
+                    .max(),
+                version: package.version.clone(),
+                rust_version: rust_version.clone(),
+            })
+            .collect::<Vec<_>>();
+        raised_by.sort_by(|lhs, rhs| (&lhs.name, &lhs.version).cmp(&(&rhs.name, &rhs.version)));
+
+        Self {
+            result: UpdateResultDetails {
+                raises_msrv: msrv_latest > msrv_locked,
+                msrv_locked,
+                msrv_latest,
+                raised_by,
+                verification: None,
+            },
+        }
+    }
+
+    /// Include the result of compiling the crate, with the updated lockfile, with the toolchain
+    /// of `msrv_locked`.
+    pub fn with_verification(mut self, verification: WhatIfVerification) -> Self {
+        self.result.verification = Some(verification);
+        self
+    }
+
+    /// The MSRV with the versions pinned by the lockfile, if any package declares a Rust version.
+    pub fn msrv_locked(&self) -> Option<&semver::Version> {
+        self.result.msrv_locked.as_ref()
+    }
+
+    /// The MSRV once the lockfile is updated, if any package declares a Rust version.
+    pub fn msrv_latest(&self) -> Option<&semver::Version> {
+        self.result.msrv_latest.as_ref()
+    }
+
+    /// Whether updating the lockfile raises the MSRV.
+    pub fn raises_msrv(&self) -> bool {
+        self.result.raises_msrv
+    }
+
+    /// The packages, updated or introduced by updating the lockfile, which require a newer Rust
+    /// version than `msrv_locked`.
+    pub fn raised_by(&self) -> &[UpdatedDependency] {
+        &self.result.raised_by
+    }
+
+    pub fn verification(&self) -> Option<&WhatIfVerification> {
+        self.result.verification.as_ref()
+    }
+}
+
+impl From<WhatIfUpdateResult> for SubcommandResult {
+    fn from(it: WhatIfUpdateResult) -> Self {
+        Self::WhatIfUpdate(it)
+    }
+}
+
+impl From<WhatIfUpdateResult> for Event {
+    fn from(it: WhatIfUpdateResult) -> Self {
+        Message::SubcommandResult(it.into()).into()
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct UpdateResultDetails {
+    msrv_locked: Option<semver::Version>,
+    msrv_latest: Option<semver::Version>,
+    raises_msrv: bool,
+    raised_by: Vec<UpdatedDependency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    verification: Option<WhatIfVerification>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+pub struct UpdatedDependency {
+    pub name: String,
+    /// The version pinned by the lockfile, or `None` if the package is newly introduced.
+    pub locked_version: Option<semver::Version>,
+    pub version: semver::Version,
+    pub rust_version: Option<semver::Version>,
+}
+
+fn direct_dependency<'g>(graph: &'g DependencyGraph, name: &str) -> Option<&'g Package> {
+    let root_index = graph.index().get(graph.root_crate())?;
+
+    graph
+        .packages()
+        .neighbors_directed(NodeIndex::new(*root_index), petgraph::Direction::Outgoing)
+        .map(|nx| &graph.packages()[nx])
+        .find(|package| package.name == name)
+}
+
+/// The root crate and the packages it depends on, with their declared Rust version.
+fn reachable_packages(graph: &DependencyGraph) -> Vec<(&Package, Option<semver::Version>)> {
+    let Some(&root_index) = graph.index().get(graph.root_crate()) else {
+        return Vec::new();
+    };
+
+    let packages = graph.packages();
+    let mut bfs = Bfs::new(packages, NodeIndex::new(root_index));
+    let mut reachable = Vec::new();
+
+    while let Some(nx) = bfs.next(packages) {
+        let package = &packages[nx];
+        reachable.push((package, package_msrv(package)));
+    }
+
+    reachable
+}
+
+fn effective_msrv(packages: &[(&Package, Option<semver::Version>)]) -> Option<semver::Version> {
+    packages
+        .iter()
+        .filter_map(|(_, rust_version)| rust_version.clone())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dependency_graph::fixture;
+
+    #[yare::parameterized(
+        raises = { "1.80", true, Some((1, 80)), &["e"] },
+        equal = { "1.70", false, Some((1, 70)), &[] },
+        older = { "1.50", false, Some((1, 70)), &[] },
+    )]
+    fn compare(rust_version: &str, raises: bool, after: Option<(u64, u64)>, raised_by: &[&str]) {
+        let before = fixture::graph();
+        let after_graph = fixture::graph_with_dependency(rust_version);
+
+        let result = WhatIfResult::new("e", &before, &after_graph).unwrap();
+
+        assert_eq!(result.dependency().name, "e");
+        assert_eq!(result.msrv_before(), Some(&semver::Version::new(1, 70, 0)));
+        assert_eq!(
+            result.msrv_after(),
+            after
+                .map(|(major, minor)| semver::Version::new(major, minor, 0))
+                .as_ref()
+        );
+        assert_eq!(result.raises_msrv(), raises);
+
+        let names = result
+            .raised_by()
+            .iter()
+            .map(|dependency| dependency.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, raised_by);
+    }
+
+    #[yare::parameterized(
+        raises = { "1.80", true, Some((1, 80)), &["e"] },
+        equal = { "1.70", false, Some((1, 70)), &[] },
+    )]
+    fn compare_update(
+        rust_version: &str,
+        raises: bool,
+        latest: Option<(u64, u64)>,
+        raised_by: &[&str],
+    ) {
+        let locked = fixture::graph();
+        let latest_graph = fixture::graph_with_dependency(rust_version);
+
+        let result = WhatIfUpdateResult::new(&locked, &latest_graph);
+
+        assert_eq!(result.msrv_locked(), Some(&semver::Version::new(1, 70, 0)));
+        assert_eq!(
+            result.msrv_latest(),
+            latest
+                .map(|(major, minor)| semver::Version::new(major, minor, 0))
+                .as_ref()
+        );
+        assert_eq!(result.raises_msrv(), raises);
+
+        let names = result
+            .raised_by()
+            .iter()
+            .map(|dependency| dependency.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, raised_by);
+        assert!(result
+            .raised_by()
+            .iter()
+            .all(|dependency| dependency.locked_version.is_none()));
+    }
+
+    #[test]
+    fn not_a_direct_dependency() {
+        let before = fixture::graph();
+        let after = fixture::graph_with_dependency("1.80");
+
+        assert!(WhatIfResult::new("f", &before, &after).is_none());
+    }
+}
@@ -0,0 +1,60 @@
+use crate::reporter::{Event, Message};
+use crate::semver;
+use crate::stabilization::Unlock;
+
+/// The editions and language features which become available at the Rust versions after the MSRV,
+/// so the benefit of raising the MSRV can be weighed against its cost.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Unlocks {
+    msrv: semver::Version,
+    unlocks: Vec<Unlock>,
+}
+
+impl Unlocks {
+    pub fn new(msrv: semver::Version, unlocks: Vec<Unlock>) -> Self {
+        Self { msrv, unlocks }
+    }
+
+    pub fn msrv(&self) -> &semver::Version {
+        &self.msrv
+    }
+
+    /// What becomes available, per Rust version, from the oldest to the newest.
+    pub fn unlocks(&self) -> &[Unlock] {
+        &self.unlocks
+    }
+}
+
+impl From<Unlocks> for Event {
+    fn from(it: Unlocks) -> Self {
+        Message::Unlocks(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = Unlocks::new(
+            semver::Version::new(1, 64, 0),
+            vec![Unlock {
+                version: semver::Version::new(1, 65, 0),
+                edition: None,
+                features: vec!["let-else statements".to_string()],
+            }],
+        );
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::Unlocks(event))]
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use crate::context::ReleaseSource;
+use crate::declaration::Pin;
+use crate::error::CargoMSRVError;
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::exit_codes::MappedOutcome;
+use crate::reporter::event::{Message, SkippedMigration};
+use crate::{semver, Event};
+use camino::Utf8Path;
+use std::fmt;
+
+/// A situation which does not stop cargo-msrv, but which the user should know about, with a
+/// machine-readable code, so consumers can surface warnings distinctly from progress.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Warning {
+    code: WarningCode,
+    message: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCode {
+    /// The declared MSRV is lower than the Rust version which a dependency requires.
+    RustVersionBelowDependency,
+    /// The lockfile was generated, or resolved anew, by a check.
+    LockfileRegenerated,
+    /// The check of a Rust version was skipped, so its outcome is inconclusive.
+    VersionSkipped,
+    /// A CI job is pinned to a Rust version which is older than the MSRV.
+    OutdatedCiPin,
+    /// A crate included with `--also` failed, after an earlier crate of the run had failed too.
+    IncludedCrateFailed,
+    /// The check of a Rust version exceeded its memory limit, so its outcome is inconclusive.
+    MemoryLimitExceeded,
+    /// The `package.metadata.msrv` of a package was not migrated to `package.rust-version`.
+    MsrvNotMigrated,
+    /// The saved MSRV of a target no longer holds, so the MSRV of the target is searched again.
+    TargetMsrvInvalidated,
+    /// The predicate passed a check which failed, or failed a check which passed.
+    PredicateOverride,
+    /// The exit code of a check was mapped to another outcome, by the `exit-codes` configuration.
+    ExitCodeMapped,
+    /// A Rust version was never published for the host, so its check is skipped.
+    ToolchainUnavailable,
+    /// A Rust version was never published for the host, so the toolchain of `--fallback-host` is
+    /// checked instead.
+    HostFallback,
+    /// A release source failed to serve the Rust releases, so the next source of the chain is used.
+    ReleaseSourceFailed,
+    /// Of the candidates, only a pre-release is compatible, which is never reported as the MSRV.
+    PrereleaseOnly,
+    /// A release was excluded from the search space, since it is on the denylist.
+    ReleaseDenied,
+    /// A checked target requires features, which are enabled for the check.
+    RequiredFeaturesEnabled,
+    /// The channel manifest of a release couldn't be fetched, so whether the components of
+    /// `--require-component` are available is unknown, and the release is kept in the search.
+    ComponentAvailabilityUnknown,
+}
+
+impl Warning {
+    pub fn new(code: WarningCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn rust_version_below_dependency(
+        msrv: &semver::Version,
+        dependency: &str,
+        dependency_version: &semver::Version,
+        required: &semver::Version,
+    ) -> Self {
+        Self::new(
+            WarningCode::RustVersionBelowDependency,
+            format!(
+                "The MSRV (Rust {}) is lower than Rust {}, which dependency {} {} requires",
+                msrv, required, dependency, dependency_version
+            ),
+        )
+    }
+
+    pub fn version_skipped(version: &semver::Version, reason: &str) -> Self {
+        Self::new(
+            WarningCode::VersionSkipped,
+            format!(
+                "The check of Rust {} was skipped ({}), its outcome is inconclusive",
+                version, reason
+            ),
+        )
+    }
+
+    pub fn outdated_ci_pin(pin: &Pin, msrv: &BareVersion) -> Self {
+        Self::new(
+            WarningCode::OutdatedCiPin,
+            format!(
+                "{}:{} pins Rust {} ({}), which is older than the MSRV (Rust {}), so the MSRV is not tested there",
+                pin.file, pin.line, pin.version, pin.kind, msrv
+            ),
+        )
+    }
+
+    pub fn included_crate_failed(manifest_path: &Utf8Path, error: &impl fmt::Display) -> Self {
+        Self::new(
+            WarningCode::IncludedCrateFailed,
+            format!("The run of {} failed: {}", manifest_path, error),
+        )
+    }
+
+    pub fn msrv_not_migrated(skipped: &SkippedMigration) -> Self {
+        Self::new(WarningCode::MsrvNotMigrated, skipped.to_string())
+    }
+
+    pub fn target_msrv_invalidated(target: &str, msrv: &semver::Version) -> Self {
+        Self::new(
+            WarningCode::TargetMsrvInvalidated,
+            format!(
+                "The saved MSRV of {}, Rust {}, no longer holds, so the MSRV of {} is searched again",
+                target, msrv, target
+            ),
+        )
+    }
+
+    pub fn predicate_override(
+        predicate: &Utf8Path,
+        version: &semver::Version,
+        compatible: bool,
+    ) -> Self {
+        let (verdict, check) = if compatible {
+            ("compatible", "failed")
+        } else {
+            ("incompatible", "passed")
+        };
+
+        Self::new(
+            WarningCode::PredicateOverride,
+            format!(
+                "The predicate '{}' decided that Rust {} is {}, although its check {}",
+                predicate, version, verdict, check
+            ),
+        )
+    }
+
+    pub fn exit_code_mapped(
+        version: &semver::Version,
+        exit_code: i32,
+        outcome: MappedOutcome,
+    ) -> Self {
+        Self::new(
+            WarningCode::ExitCodeMapped,
+            format!(
+                "The check of Rust {} exited with code {}, which is configured to {}",
+                version, exit_code, outcome
+            ),
+        )
+    }
+
+    pub fn toolchain_unavailable(version: &semver::Version, reason: &str) -> Self {
+        Self::new(
+            WarningCode::ToolchainUnavailable,
+            format!(
+                "Rust {} is not available for this host ({}), so it is skipped",
+                version, reason
+            ),
+        )
+    }
+
+    pub fn host_fallback(version: &semver::Version, reason: &str, host: &str) -> Self {
+        Self::new(
+            WarningCode::HostFallback,
+            format!(
+                "Rust {} is not available for this host ({}), so the toolchain for {} is checked instead",
+                version, reason, host
+            ),
+        )
+    }
+
+    pub fn release_source_failed(source: ReleaseSource, error: &CargoMSRVError) -> Self {
+        Self::new(
+            WarningCode::ReleaseSourceFailed,
+            format!(
+                "The release source '{}' failed, so the next release source is used: {}",
+                source, error
+            ),
+        )
+    }
+
+    pub fn prerelease_only(version: &semver::Version) -> Self {
+        Self::new(
+            WarningCode::PrereleaseOnly,
+            format!(
+                "Only the pre-release Rust {} is compatible, which is not reported as the MSRV",
+                version
+            ),
+        )
+    }
+
+    pub fn release_denied(version: &semver::Version, reason: &str) -> Self {
+        Self::new(
+            WarningCode::ReleaseDenied,
+            format!(
+                "Excluded Rust {} from the search, since {} (include it with '--allow-version {}')",
+                version, reason, version
+            ),
+        )
+    }
+
+    pub fn required_features_enabled(target: &str, features: &[String]) -> Self {
+        Self::new(
+            WarningCode::RequiredFeaturesEnabled,
+            format!(
+                "The target '{}' requires the features '{}', which are enabled for each check",
+                target,
+                features.join(", ")
+            ),
+        )
+    }
+
+    pub fn component_availability_unknown(
+        version: &semver::Version,
+        error: &CargoMSRVError,
+    ) -> Self {
+        Self::new(
+            WarningCode::ComponentAvailabilityUnknown,
+            format!(
+                "Unable to determine whether the required components are available for Rust {}, so it is kept in the search: {}",
+                version, error
+            ),
+        )
+    }
+
+    pub fn code(&self) -> WarningCode {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<Warning> for Event {
+    fn from(it: Warning) -> Self {
+        Message::Warning(it).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use storyteller::EventReporter;
+
+    #[test]
+    fn reported_event() {
+        let reporter = TestReporterWrapper::default();
+        let event = Warning::version_skipped(&semver::Version::new(1, 60, 0), "by request");
+
+        reporter.get().report_event(event.clone()).unwrap();
+
+        assert_eq!(
+            reporter.wait_for_events(),
+            vec![Event::unscoped(Message::Warning(event))]
+        );
+    }
+
+    #[test]
+    fn serialized() {
+        let event = Event::from(Warning::rust_version_below_dependency(
+            &semver::Version::new(1, 56, 0),
+            "serde",
+            &semver::Version::new(1, 0, 200),
+            &semver::Version::new(1, 61, 0),
+        ));
+
+        assert_eq!(
+            serde_json::to_value(event).unwrap(),
+            serde_json::json!({
+                "type": "warning",
+                "code": "rust_version_below_dependency",
+                "message": "The MSRV (Rust 1.56.0) is lower than Rust 1.61.0, which dependency serde 1.0.200 requires",
+            })
+        );
+    }
+}
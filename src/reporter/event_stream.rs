@@ -0,0 +1,279 @@
+//! Runs cargo-msrv on a worker thread, and streams its events to an async consumer, so an async
+//! application which embeds cargo-msrv doesn't have to bridge the blocking handlers itself.
+//!
+//! The events are the same as those of the `json` output format, including the final
+//! `terminate_with_failure` and `terminated` events. With the `async` feature, the stream
+//! implements `futures_core::Stream`; otherwise the events are received with
+//! [`EventStream::next`]. Each works with any async runtime.
+
+use crate::error::{IoError, IoErrorSource};
+use crate::exit_code::ExitCode;
+use crate::reporter::{Event, ReporterSetup, TerminateWithFailure, Terminated};
+use crate::{run_app, CargoMSRVError, Context, TResult};
+use std::collections::VecDeque;
+use std::future::poll_fn;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Poll, Waker};
+use std::thread;
+use std::time::Instant;
+use storyteller::{EventHandler, EventListener, EventReporter, FinishProcessing};
+
+/// The outcome of the run: its result, or the payload of its panic.
+type Outcome = thread::Result<Result<(), CargoMSRVError>>;
+
+/// The events of a run of cargo-msrv, which runs on a worker thread.
+pub struct EventStream {
+    shared: Arc<Shared>,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    events: VecDeque<Event>,
+    /// The consumer which waits for the next event, or for the outcome.
+    waker: Option<Waker>,
+    /// Set once the run is done, after each of its events was queued in `events`.
+    outcome: Option<Outcome>,
+    /// Whether the run is done, also after its outcome was taken.
+    done: bool,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn update(&self, f: impl FnOnce(&mut State)) {
+        let mut state = self.state();
+        f(&mut state);
+
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl EventStream {
+    /// Run cargo-msrv with the given context, on a worker thread.
+    pub fn run(ctx: Context) -> TResult<Self> {
+        let shared = Arc::new(Shared::default());
+        let worker = Arc::clone(&shared);
+        let name = "cargo-msrv".to_string();
+
+        thread::Builder::new()
+            .name(name.clone())
+            .spawn(move || {
+                let outcome = run_reported(&ctx, &worker);
+
+                worker.update(|state| {
+                    state.outcome = Some(outcome);
+                    state.done = true;
+                });
+            })
+            .map_err(|error| IoError {
+                error,
+                source: IoErrorSource::SpawnThread(name),
+            })?;
+
+        Ok(Self { shared })
+    }
+
+    /// The next event, or `None` once the run is done.
+    pub async fn next(&mut self) -> Option<Event> {
+        poll_fn(|cx| self.poll_event(cx.waker())).await
+    }
+
+    /// Wait until the run is done, while the remaining events are discarded, and return its
+    /// result. If the run panicked, the panic is resumed.
+    pub async fn finish(self) -> Result<(), CargoMSRVError> {
+        let outcome = poll_fn(|cx| {
+            let mut state = self.shared.state();
+            state.events.clear();
+
+            match state.outcome.take() {
+                Some(outcome) => Poll::Ready(outcome),
+                None => {
+                    state.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+        .await;
+
+        outcome.unwrap_or_else(|payload| panic::resume_unwind(payload))
+    }
+
+    fn poll_event(&self, waker: &Waker) -> Poll<Option<Event>> {
+        let mut state = self.shared.state();
+
+        if let Some(event) = state.events.pop_front() {
+            Poll::Ready(Some(event))
+        } else if state.done {
+            Poll::Ready(None)
+        } else {
+            state.waker = Some(waker.clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl futures_core::Stream for EventStream {
+    type Item = Event;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.poll_event(cx.waker())
+    }
+}
+
+/// Run cargo-msrv, and report its events to the stream, like the binary reports them to the
+/// user output: a failure and the termination are reported too.
+fn run_reported(ctx: &Context, shared: &Arc<Shared>) -> Outcome {
+    let started = Instant::now();
+
+    let (reporter, listener) = ReporterSetup.create();
+    let finalizer = listener.run_handler(Arc::new(StreamHandler {
+        shared: Arc::clone(shared),
+    }));
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| run_app(ctx, &reporter)));
+
+    let terminated = match &outcome {
+        Ok(Ok(())) => Terminated::new(ExitCode::Success, started.elapsed()),
+        Ok(Err(error)) => {
+            let exit_code = ExitCode::of_error(error);
+            let _ = reporter.report_event(TerminateWithFailure::from(error));
+            Terminated::new(exit_code, started.elapsed())
+        }
+        Err(_) => Terminated::panicked(None, started.elapsed()),
+    };
+
+    // The stream can't receive the events anymore if the channel is gone, but the outcome is still
+    // returned
+    let _ = reporter.report_event(terminated);
+    let _ = reporter.disconnect();
+    let _ = finalizer.finish_processing();
+
+    outcome
+}
+
+struct StreamHandler {
+    shared: Arc<Shared>,
+}
+
+impl EventHandler for StreamHandler {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        self.shared.update(|state| state.events.push_back(event));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::CargoCli;
+    use crate::reporter::Message;
+    use std::future::Future;
+    use std::task::Wake;
+
+    const WITHOUT_MSRV: &str = "[package]\nname = \"a\"\nversion = \"0.1.0\"\n";
+    const WITH_MSRV: &str =
+        "[package]\nname = \"a\"\nversion = \"0.1.0\"\nrust-version = \"1.56\"\n";
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// Poll the future on the current thread, which is parked until the future is woken.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    /// The context of `cargo msrv show`, for a crate with the given manifest.
+    fn show(manifest: &str) -> (assert_fs::TempDir, Context) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), manifest).unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src").join("lib.rs"), "").unwrap();
+
+        let opts = CargoCli::parse_args([
+            "cargo".as_ref(),
+            "msrv".as_ref(),
+            "--path".as_ref(),
+            dir.path().as_os_str(),
+            "show".as_ref(),
+        ])
+        .to_cargo_msrv_cli()
+        .to_opts();
+
+        (dir, Context::try_from(opts).unwrap())
+    }
+
+    fn events(mut stream: EventStream) -> Vec<Event> {
+        block_on(async {
+            let mut events = Vec::new();
+            while let Some(event) = stream.next().await {
+                events.push(event);
+            }
+            events
+        })
+    }
+
+    #[test]
+    fn streams_each_event() {
+        let (_dir, ctx) = show(WITH_MSRV);
+        let events = events(EventStream::run(ctx).unwrap());
+
+        assert!(matches!(events[0].message(), Message::Meta(_)));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event.message(), Message::SubcommandResult(_))));
+        assert!(matches!(
+            events.last().unwrap().message(),
+            Message::Terminated(_)
+        ));
+    }
+
+    #[test]
+    fn failed_run() {
+        let (_dir, ctx) = show(WITHOUT_MSRV);
+        let stream = EventStream::run(ctx).unwrap();
+        let error = block_on(stream.finish()).unwrap_err();
+
+        assert!(matches!(error, CargoMSRVError::SubCommandShow(_)));
+    }
+
+    #[test]
+    fn failure_is_streamed() {
+        let (_dir, ctx) = show(WITHOUT_MSRV);
+        let events = events(EventStream::run(ctx).unwrap());
+
+        assert!(events
+            .iter()
+            .any(|event| matches!(event.message(), Message::TerminateWithFailure(_))));
+    }
+}
@@ -1,9 +1,15 @@
 use once_cell::sync::OnceCell;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tabled::settings::{Margin, Style};
 use tabled::{Table, Tabled};
 
 static TERM_WIDTH: OnceCell<usize> = OnceCell::new();
 
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+static COLOR: AtomicBool = AtomicBool::new(true);
+
 static TABLE_CORRECTION: usize = 4;
 
 pub fn term_width() -> usize {
@@ -35,9 +41,215 @@ macro_rules! table_settings {
 }
 
 pub fn table<T: Tabled>(iter: impl IntoIterator<Item = T>) -> Table {
-    Table::new(iter)
-        .with(Style::modern_rounded())
+    let mut table = Table::new(iter);
+
+    if is_ascii() {
+        table.with(Style::ascii());
+    } else {
+        table.with(Style::modern_rounded());
+    }
+
+    table
         .with(table_settings!())
         .with(Margin::new(2, 0, 1, 0))
         .to_owned()
 }
+
+/// Format a size in bytes, in the largest binary unit in which it is at least one, e.g. `1.5 MiB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Restrict the human output to ASCII characters, for terminals and log viewers which mangle
+/// Unicode.
+pub fn set_ascii(ascii: bool) {
+    ASCII.store(ascii, Ordering::Relaxed);
+}
+
+pub fn is_ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// The symbol which marks the omission of text, or a range.
+pub fn ellipsis() -> &'static str {
+    if is_ascii() {
+        "..."
+    } else {
+        "…"
+    }
+}
+
+/// Replace the non-ASCII characters of the human output by ASCII ones, if the output is restricted
+/// to ASCII.
+pub fn ascii_fallback(text: &str) -> Cow<'_, str> {
+    if is_ascii() && !text.is_ascii() {
+        Cow::Owned(transliterate(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn transliterate(text: &str) -> String {
+    let mut ascii = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            c if c.is_ascii() => ascii.push(c),
+            '…' => ascii.push_str("..."),
+            '≥' => ascii.push_str(">="),
+            '≤' => ascii.push_str("<="),
+            '→' => ascii.push_str("->"),
+            '─' | '━' | '┄' | '┈' => ascii.push('-'),
+            '│' | '┃' | '┆' | '┊' => ascii.push('|'),
+            '╭' | '╮' | '╰' | '╯' | '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' => {
+                ascii.push('+')
+            }
+            '‘' | '’' | '‚' => ascii.push('\''),
+            '“' | '”' | '„' => ascii.push('"'),
+            '–' | '—' => ascii.push('-'),
+            '•' => ascii.push('*'),
+            '\u{a0}' => ascii.push(' '),
+            // Other characters, like those of paths, crate names or translated messages, are
+            // written without their accents, or escaped if they are not a Latin letter
+            c => match latin_letter(c) {
+                Some(letter) => ascii.push_str(letter),
+                None => ascii.extend(c.escape_unicode()),
+            },
+        }
+    }
+
+    ascii
+}
+
+/// The ASCII letters of a letter of the Latin-1 Supplement, like `u` for `ü`, or `ss` for `ß`.
+fn latin_letter(c: char) -> Option<&'static str> {
+    const LATIN_1: [&str; 64] = [
+        "A", "A", "A", "A", "A", "A", "AE", "C", "E", "E", "E", "E", "I", "I", "I", "I", //
+        "D", "N", "O", "O", "O", "O", "O", "x", "O", "U", "U", "U", "U", "Y", "TH", "ss", //
+        "a", "a", "a", "a", "a", "a", "ae", "c", "e", "e", "e", "e", "i", "i", "i", "i", //
+        "d", "n", "o", "o", "o", "o", "o", "/", "o", "u", "u", "u", "u", "y", "th", "y", //
+    ];
+
+    let index = (c as u32).checked_sub(0xc0)?;
+    LATIN_1.get(index as usize).copied()
+}
+
+/// Whether the human output is colored.
+pub fn set_color(color: bool) {
+    COLOR.store(color, Ordering::Relaxed);
+}
+
+/// Remove the colors of the human output, if it should not be colored.
+pub fn color_fallback(text: &str) -> Cow<'_, str> {
+    if COLOR.load(Ordering::Relaxed) || !text.contains('\u{1b}') {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(strip_escape_sequences(text))
+    }
+}
+
+/// Remove the ANSI escape sequences, like the colors of text, or those with which rustup redraws
+/// its progress on a terminal.
+pub(crate) fn strip_escape_sequences(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            stripped.push(c);
+            continue;
+        }
+
+        // Skip the sequence up to and including its final letter
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+
+    stripped
+}
+
+/// Whether the locale of the environment, as given by the `LC_ALL`, `LC_CTYPE` and `LANG`
+/// variables (in order of precedence), uses a Unicode encoding. An environment without a locale
+/// is assumed to support Unicode.
+pub fn locale_supports_unicode(var: impl Fn(&str) -> Option<String>) -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(var)
+        .find(|value| !value.is_empty());
+
+    let Some(locale) = locale else {
+        return true;
+    };
+
+    let locale = locale.to_ascii_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        ascii = { "Is compatible", "Is compatible" },
+        ellipsis = { "Rust 1.56.0 … Rust 1.60.0", "Rust 1.56.0 ... Rust 1.60.0" },
+        hint = { "requires ≥ 1.65", "requires >= 1.65" },
+        table = { "╭───╮\n│ a │\n╰───╯", "+---+\n| a |\n+---+" },
+        latin = { "crate ünïcödé", "crate unicode" },
+        german = { "Kompatibilität mit Straße", "Kompatibilitat mit Strasse" },
+        quotes = { "‘a’ “b” – c", "'a' \"b\" - c" },
+        other = { "☃ snow", "\\u{2603} snow" },
+    )]
+    fn transliterated(text: &str, expected: &str) {
+        let transliterated = transliterate(text);
+
+        assert_eq!(transliterated, expected);
+        assert!(transliterated.is_ascii());
+    }
+
+    #[yare::parameterized(
+        plain = { "Is compatible", "Is compatible" },
+        colored = { "[\u{1b}[92mOK\u{1b}[39m] Is compatible", "[OK] Is compatible" },
+        bold = { "\u{1b}[1mResult:\u{1b}[0m", "Result:" },
+    )]
+    fn stripped(text: &str, expected: &str) {
+        assert_eq!(strip_escape_sequences(text), expected);
+    }
+
+    #[yare::parameterized(
+        unset = { &[], true },
+        utf8 = { &[("LANG", "en_US.UTF-8")], true },
+        utf8_lowercase = { &[("LANG", "nl_NL.utf8")], true },
+        c = { &[("LANG", "C")], false },
+        latin1 = { &[("LC_CTYPE", "en_US.ISO-8859-1"), ("LANG", "en_US.UTF-8")], false },
+        lc_all_first = { &[("LC_ALL", "C.UTF-8"), ("LANG", "C")], true },
+        empty_ignored = { &[("LC_ALL", ""), ("LANG", "POSIX")], false },
+    )]
+    fn locale(vars: &[(&str, &str)], expected: bool) {
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        };
+
+        assert_eq!(locale_supports_unicode(var), expected);
+    }
+}
@@ -0,0 +1,145 @@
+# The messages of the `human` output format, in German.
+
+## Checks
+check-header = Kompatibilitätsprüfung #{ $nth }: Rust { $version }
+check-compatible = Ist kompatibel
+check-incompatible = Ist nicht kompatibel
+check-unable-to-confirm-release = Es konnte nicht überprüft werden, ob die angegebene Version eine existierende Rust-Version ist
+check-downloading = Herunterladen
+check-elapsed = Vergangen
+check-pre-release = Vorabversion
+check-failed-targets = Kompilieren fehlgeschlagen für { $targets }
+check-feature-set-default = Standard-Features
+check-fuzz-project = Fuzz-Projekt { $path }
+saved-run = Lauf '{ $run }' in '{ $path }' gespeichert, anzeigen mit `cargo msrv inspect { $run }`
+
+## Search space, lockfile and changelog
+reduced-search-space = Die Rust-Versionen älter als { $version } werden übersprungen, da ihr cargo { $reason } nicht unterstützt ({ $location })
+changelog-would-add = Würde zum Abschnitt '{ $section }' von { $path } hinzufügen: { $entry }
+changelog-added = Zum Abschnitt '{ $section }' von { $path } hinzugefügt: { $entry }
+lockfile-revision = Die Lockfile von { $revision } wird geprüft
+lockfile-incompatible = Das cargo von Rust { $version } kann Version { $lockfile } der Lockfile nicht lesen, die Rust { $readable } erfordert; { $handling }
+lockfile-as-is = es wird mit der unveränderten Lockfile geprüft
+lockfile-regenerate = es wird mit einer neu erzeugten Lockfile geprüft
+lockfile-downgrade = es wird mit einer herabgestuften Lockfile geprüft
+lockfile-fail = Abbruch
+
+## Output which was saved, or updated
+saved-matrix-state = Die MSRV jedes Ziels wurde in '{ $path }' gespeichert, wiederverwenden mit `cargo msrv find --targets <TARGETS> --resume`
+updated-declaration = { $kind } in { $path }:{ $line } wurde auf die MSRV aktualisiert
+
+## Included crates
+included-crate-unnamed = Crate
+
+## Search control
+search-paused = Sende `resume`, um die Suche fortzusetzen
+search-resumed = Die Suche wird fortgesetzt
+search-skipped = Rust { $version }, das Ergebnis ist unbestimmt
+search-aborted = Die Suche wurde abgebrochen
+
+## Bounds of the MSRV, when the search stopped early
+partial-bounded = Die Suche wurde vorzeitig beendet, die MSRV liegt zwischen Rust { $lower } und Rust { $upper }
+partial-unbounded = Die Suche wurde vorzeitig beendet, die MSRV ist Rust { $lower } oder neuer, falls vorhanden
+saved-state = Der Stand der Suche wurde in '{ $path }' gespeichert, fortsetzen mit `cargo msrv find --resume`
+
+## Summary of the incompatible Rust versions
+failure-summary = Inkompatible Rust-Versionen, mit ihrem ersten Fehler:
+failure-no-error-output = keine Fehlerausgabe
+
+## Result of the search
+result-title = Ergebnis:
+result-considered = Berücksichtigt ({ $min } { $ellipsis } { $max }):
+result-min = min
+result-max = max
+result-search-space = Rust { $min } { $ellipsis } Rust { $max }
+result-search-method = Suchmethode:
+result-msrv = MSRV:
+result-msrv-none = k. A.
+result-target = Ziel:
+result-fingerprint = Fingerabdruck:
+result-shard = Shard:
+result-shard-compatible = alle kompatibel
+result-shard-incompatible = inkompatible Version gefunden
+
+## Difference between the diagnostics around the MSRV
+diff-none = Die Diagnosen von Rust { $failing } und Rust { $passing } unterscheiden sich nicht
+diff-changed = Diagnosen, die sich von Rust { $failing } zu Rust { $passing } (der MSRV) geändert haben:
+
+## Find, for multiple targets
+targets-msrv = Die MSRV über die { $count } Ziele ist Rust { $msrv }, erfordert von { $targets }
+targets-no-msrv = Das Crate hat keine MSRV über die Ziele, da keine Rust-Version im Suchraum mit jedem Ziel kompatibel ist
+
+## Bisect lockfile
+bisect-first-fails = Das Crate schlägt zuerst mit Rust { $version } mit der Lockfile von { $revision } fehl, nach { $checks } Prüfung(en)
+bisect-last-compatible = Die Lockfile von { $revision } ist die letzte, die kompatibel ist
+
+## Cache
+cache-usage = { $size } von { $max } in '{ $path }' belegt
+
+## Check consistency
+consistency-undeclared = Es ist keine MSRV angegeben
+consistency-consistent = Jede der { $count } Angaben der MSRV ist Rust { $msrv }
+consistency-inconsistent = { $conflicts } der { $count } Angaben weichen von der MSRV, Rust { $msrv }, ab
+
+## Clean
+clean-nothing = Nichts zu entfernen
+clean-would-remove = Würde { $size } entfernen
+clean-removed = { $size } entfernt
+
+## Estimate
+estimate-cargo = Das Projekt kann nur vom cargo von Rust { $version } oder neuer gelesen werden
+estimate-msrv = Die MSRV wird auf Rust { $version } geschätzt (nicht durch Kompilieren überprüft)
+estimate-none = Es wurden keine Features gefunden, die eine bestimmte Rust-Version erfordern
+
+## Inspect
+inspect-no-runs = Es sind keine Läufe gespeichert, speichere einen mit `cargo msrv find --save-run` oder `cargo msrv verify --save-run`
+
+## Merge results
+merge-msrv = Die MSRV ist Rust { $version } ({ $shards } Shards)
+merge-no-msrv = Keiner der { $shards } Shards hat eine kompatible Rust-Version gefunden
+
+## Migrate
+migrate-none-declared = Kein Paket gibt package.metadata.msrv an
+migrate-none-migratable = Keines der { $count } Pakete, die package.metadata.msrv angeben, kann migriert werden
+migrate-would-migrate = Würde { $count } Paket(e) zu package.rust-version migrieren
+migrate-migrated = { $count } Paket(e) zu package.rust-version migriert
+
+## Outdated
+outdated-none = Die neueste Version jeder Abhängigkeit ist mit Rust { $msrv } kompatibel
+
+## Show
+show-msrv = Die MSRV ist Rust { $version }
+show-declared-as = Angegeben als { $kind } ({ $path })
+show-path-dependency-newer = Die Pfadabhängigkeit { $name } { $version } erfordert Rust { $requires }, das neuer als die MSRV ist ({ $path })
+show-path-dependency = Die Pfadabhängigkeit { $name } { $version } erfordert Rust { $requires } ({ $path })
+show-path-dependency-unspecified = Die Pfadabhängigkeit { $name } { $version } gibt keine MSRV an ({ $path })
+show-workspace = Die workspace.package.rust-version, die Mitglieder erben können, ist Rust { $version }
+
+## Sync
+sync-consistent = Jede Angabe der MSRV ist Rust { $msrv }
+sync-would-update = Würde { $count } Angabe(n) auf Rust { $msrv } aktualisieren
+sync-updated = { $count } Angabe(n) auf Rust { $msrv } aktualisiert
+
+## Verify
+verify-fresh = Rust { $version } liegt innerhalb von { $tolerance } Minor-Version(en) der MSRV (Rust { $msrv })
+verify-too-low = Rust { $version } ist niedriger als die MSRV (Rust { $msrv })
+verify-too-high = Rust { $version } ist um mehr als { $tolerance } Minor-Version(en) höher als die MSRV (Rust { $msrv })
+verify-release-keeps = Die { $release }-Version erhöht die MSRV (Rust { $version }) nicht
+verify-release-allowed = Die { $release }-Version erhöht die MSRV von Rust { $version } auf Rust { $msrv }, was die MSRV-Richtlinie erlaubt
+verify-release-not-allowed = Die { $release }-Version erhöht die MSRV von Rust { $version } auf Rust { $msrv }, was die MSRV-Richtlinie nicht erlaubt
+verify-slack = Das Crate kompiliert auch mit Rust { $msrv }, { $slack } Minor-Version(en) älter als Rust { $version }; erwäge, die MSRV mit `cargo msrv set { $msrv }` zu senken
+verify-no-slack = Rust { $version } ist die MSRV, das Crate hat keinen Spielraum
+
+## What if
+whatif-add-raises = Das Hinzufügen von { $dependency } erhöht die MSRV von Rust { $before } auf Rust { $after }
+whatif-add-raises-to = Das Hinzufügen von { $dependency } erhöht die MSRV auf Rust { $after }
+whatif-add-keeps = Das Hinzufügen von { $dependency } erhöht die MSRV von Rust { $before } nicht
+whatif-add-keeps-unknown = Das Hinzufügen von { $dependency } erhöht die MSRV nicht
+whatif-update-raises = Das Aktualisieren der Lockfile erhöht die MSRV von Rust { $before } auf Rust { $after }
+whatif-update-raises-to = Das Aktualisieren der Lockfile erhöht die MSRV auf Rust { $after }
+whatif-update-keeps = Das Aktualisieren der Lockfile erhöht die MSRV von Rust { $before } nicht
+whatif-update-keeps-unknown = Das Aktualisieren der Lockfile erhöht die MSRV nicht
+whatif-requires = { $name } { $version } erfordert Rust { $requires }
+whatif-requires-locked = { $name } { $version } (gesperrt auf { $locked }) erfordert Rust { $requires }
+whatif-still-compiles = Das Crate kompiliert weiterhin mit Rust { $version }
+whatif-no-longer-compiles = Das Crate kompiliert nicht mehr mit Rust { $version }
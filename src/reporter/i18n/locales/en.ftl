@@ -0,0 +1,148 @@
+# The messages of the `human` output format, in English.
+#
+# Each message is written as `key = message`, where `{ $name }` is replaced by the value of the
+# argument `name`. Other locales fall back to these messages, for the keys they do not translate.
+
+## Checks
+check-header = Compatibility Check #{ $nth }: Rust { $version }
+check-compatible = Is compatible
+check-incompatible = Is incompatible
+check-unable-to-confirm-release = Unable to verify if provided version is an existing Rust release version
+check-downloading = Downloading
+check-elapsed = Elapsed
+check-pre-release = pre-release
+check-failed-targets = Failed to compile the { $targets }
+check-feature-set-default = default features
+check-fuzz-project = fuzz project { $path }
+saved-run = Saved run '{ $run }' to '{ $path }', browse it with `cargo msrv inspect { $run }`
+
+## Search space, lockfile and changelog
+reduced-search-space = Skipping the Rust versions older than { $version }, since their cargo does not support the { $reason } ({ $location })
+changelog-would-add = Would add to the '{ $section }' section of { $path }: { $entry }
+changelog-added = Added to the '{ $section }' section of { $path }: { $entry }
+lockfile-revision = Checking the lockfile of { $revision }
+lockfile-incompatible = The cargo of Rust { $version } can't read version { $lockfile } of the lockfile, which requires Rust { $readable }; { $handling }
+lockfile-as-is = checking with the lockfile as is
+lockfile-regenerate = checking with a regenerated lockfile
+lockfile-downgrade = checking with a downgraded lockfile
+lockfile-fail = stopping
+
+## Output which was saved, or updated
+saved-matrix-state = Saved the MSRV of each target to '{ $path }', reuse it with `cargo msrv find --targets <TARGETS> --resume`
+updated-declaration = Updated the { $kind } at { $path }:{ $line } to the MSRV
+
+## Included crates
+included-crate-unnamed = crate
+
+## Search control
+search-paused = Send `resume` to continue the search
+search-resumed = Resumed the search
+search-skipped = Rust { $version }, its outcome is inconclusive
+search-aborted = Aborted the search
+
+## Bounds of the MSRV, when the search stopped early
+partial-bounded = The search stopped early, the MSRV is between Rust { $lower } and Rust { $upper }
+partial-unbounded = The search stopped early, the MSRV is Rust { $lower } or more recent, if any
+saved-state = Saved the state of the search to '{ $path }', continue it with `cargo msrv find --resume`
+
+## Summary of the incompatible Rust versions
+failure-summary = Incompatible Rust versions, with their first error:
+failure-no-error-output = no error output
+
+## Result of the search
+result-title = Result:
+result-considered = Considered ({ $min } { $ellipsis } { $max }):
+result-min = min
+result-max = max
+result-search-space = Rust { $min } { $ellipsis } Rust { $max }
+result-search-method = Search method:
+result-msrv = MSRV:
+result-msrv-none = N/A
+result-target = Target:
+result-fingerprint = Fingerprint:
+result-shard = Shard:
+result-shard-compatible = all compatible
+result-shard-incompatible = incompatible release found
+
+## Difference between the diagnostics around the MSRV
+diff-none = The diagnostics of Rust { $failing } and Rust { $passing } do not differ
+diff-changed = Diagnostics which changed from Rust { $failing } to Rust { $passing } (the MSRV):
+
+## Find, for multiple targets
+targets-msrv = The MSRV across the { $count } targets is Rust { $msrv }, required by { $targets }
+targets-no-msrv = The crate has no MSRV across the targets, since no Rust version in the search space is compatible with each target
+
+## Bisect lockfile
+bisect-first-fails = The crate first fails with Rust { $version } with the lockfile of { $revision }, after { $checks } check(s)
+bisect-last-compatible = The lockfile of { $revision } is the last one which is compatible
+
+## Cache
+cache-usage = Using { $size } of { $max } at '{ $path }'
+
+## Check consistency
+consistency-undeclared = No MSRV is declared
+consistency-consistent = Each of the { $count } declarations of the MSRV is Rust { $msrv }
+consistency-inconsistent = { $conflicts } of the { $count } declarations disagree with the MSRV, Rust { $msrv }
+
+## Clean
+clean-nothing = Nothing to remove
+clean-would-remove = Would remove { $size }
+clean-removed = Removed { $size }
+
+## Estimate
+estimate-cargo = The project can only be parsed by the cargo of Rust { $version } or newer
+estimate-msrv = MSRV is estimated to be Rust { $version } (not verified by compiling)
+estimate-none = No features which require a specific Rust version were found
+
+## Inspect
+inspect-no-runs = No runs are stored, store one with `cargo msrv find --save-run` or `cargo msrv verify --save-run`
+
+## Merge results
+merge-msrv = MSRV is Rust { $version } ({ $shards } shards)
+merge-no-msrv = None of the { $shards } shards found a compatible Rust version
+
+## Migrate
+migrate-none-declared = No package declares package.metadata.msrv
+migrate-none-migratable = None of the { $count } package(s) which declare package.metadata.msrv can be migrated
+migrate-would-migrate = Would migrate { $count } package(s) to package.rust-version
+migrate-migrated = Migrated { $count } package(s) to package.rust-version
+
+## Outdated
+outdated-none = The newest release of each dependency is compatible with Rust { $msrv }
+
+## Show
+show-msrv = MSRV is Rust { $version }
+show-declared-as = Declared as { $kind } ({ $path })
+show-path-dependency-newer = Path dependency { $name } { $version } requires Rust { $requires }, which is newer than the MSRV ({ $path })
+show-path-dependency = Path dependency { $name } { $version } requires Rust { $requires } ({ $path })
+show-path-dependency-unspecified = Path dependency { $name } { $version } does not specify an MSRV ({ $path })
+show-workspace = The workspace.package.rust-version, which members can inherit, is Rust { $version }
+
+## Sync
+sync-consistent = Each declaration of the MSRV is Rust { $msrv }
+sync-would-update = Would update { $count } declaration(s) to Rust { $msrv }
+sync-updated = Updated { $count } declaration(s) to Rust { $msrv }
+
+## Verify
+verify-fresh = Rust { $version } is within { $tolerance } minor version(s) of the MSRV (Rust { $msrv })
+verify-too-low = Rust { $version } is lower than the MSRV (Rust { $msrv })
+verify-too-high = Rust { $version } is higher than the MSRV (Rust { $msrv }) by more than { $tolerance } minor version(s)
+verify-release-keeps = The { $release } release does not raise the MSRV (Rust { $version })
+verify-release-allowed = The { $release } release raises the MSRV from Rust { $version } to Rust { $msrv }, which the MSRV policy allows
+verify-release-not-allowed = The { $release } release raises the MSRV from Rust { $version } to Rust { $msrv }, which the MSRV policy does not allow
+verify-slack = The crate also builds with Rust { $msrv }, { $slack } minor release(s) older than Rust { $version }; consider lowering the MSRV with `cargo msrv set { $msrv }`
+verify-no-slack = Rust { $version } is the MSRV, the crate has no slack
+
+## What if
+whatif-add-raises = Adding { $dependency } raises the MSRV from Rust { $before } to Rust { $after }
+whatif-add-raises-to = Adding { $dependency } raises the MSRV to Rust { $after }
+whatif-add-keeps = Adding { $dependency } does not raise the MSRV of Rust { $before }
+whatif-add-keeps-unknown = Adding { $dependency } does not raise the MSRV
+whatif-update-raises = Updating the lockfile raises the MSRV from Rust { $before } to Rust { $after }
+whatif-update-raises-to = Updating the lockfile raises the MSRV to Rust { $after }
+whatif-update-keeps = Updating the lockfile does not raise the MSRV of Rust { $before }
+whatif-update-keeps-unknown = Updating the lockfile does not raise the MSRV
+whatif-requires = { $name } { $version } requires Rust { $requires }
+whatif-requires-locked = { $name } { $version } (locked at { $locked }) requires Rust { $requires }
+whatif-still-compiles = The crate still compiles with Rust { $version }
+whatif-no-longer-compiles = The crate no longer compiles with Rust { $version }
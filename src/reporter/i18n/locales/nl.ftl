@@ -0,0 +1,145 @@
+# The messages of the `human` output format, in Dutch.
+
+## Checks
+check-header = Compatibiliteitscontrole #{ $nth }: Rust { $version }
+check-compatible = Is compatibel
+check-incompatible = Is niet compatibel
+check-unable-to-confirm-release = Kan niet verifiëren of de opgegeven versie een bestaande Rust-release is
+check-downloading = Downloaden
+check-elapsed = Verstreken
+check-pre-release = pre-release
+check-failed-targets = Compileren mislukt voor { $targets }
+check-feature-set-default = standaardfeatures
+check-fuzz-project = fuzz-project { $path }
+saved-run = Uitvoering '{ $run }' opgeslagen in '{ $path }', bekijk deze met `cargo msrv inspect { $run }`
+
+## Search space, lockfile and changelog
+reduced-search-space = De Rust-versies ouder dan { $version } worden overgeslagen, omdat hun cargo { $reason } niet ondersteunt ({ $location })
+changelog-would-add = Zou toevoegen aan de sectie '{ $section }' van { $path }: { $entry }
+changelog-added = Toegevoegd aan de sectie '{ $section }' van { $path }: { $entry }
+lockfile-revision = De lockfile van { $revision } wordt gecontroleerd
+lockfile-incompatible = De cargo van Rust { $version } kan versie { $lockfile } van de lockfile niet lezen, die Rust { $readable } vereist; { $handling }
+lockfile-as-is = er wordt gecontroleerd met de ongewijzigde lockfile
+lockfile-regenerate = er wordt gecontroleerd met een opnieuw gegenereerde lockfile
+lockfile-downgrade = er wordt gecontroleerd met een gedowngradede lockfile
+lockfile-fail = er wordt gestopt
+
+## Output which was saved, or updated
+saved-matrix-state = De MSRV van elk doel is opgeslagen in '{ $path }', hergebruik deze met `cargo msrv find --targets <TARGETS> --resume`
+updated-declaration = De { $kind } in { $path }:{ $line } is bijgewerkt naar de MSRV
+
+## Included crates
+included-crate-unnamed = crate
+
+## Search control
+search-paused = Stuur `resume` om het zoeken te hervatten
+search-resumed = Het zoeken is hervat
+search-skipped = Rust { $version }, de uitkomst is onbeslist
+search-aborted = Het zoeken is afgebroken
+
+## Bounds of the MSRV, when the search stopped early
+partial-bounded = Het zoeken is vroegtijdig gestopt, de MSRV ligt tussen Rust { $lower } en Rust { $upper }
+partial-unbounded = Het zoeken is vroegtijdig gestopt, de MSRV is Rust { $lower } of recenter, indien aanwezig
+saved-state = De stand van het zoeken is opgeslagen in '{ $path }', hervat het met `cargo msrv find --resume`
+
+## Summary of the incompatible Rust versions
+failure-summary = Incompatibele Rust-versies, met hun eerste fout:
+failure-no-error-output = geen foutuitvoer
+
+## Result of the search
+result-title = Resultaat:
+result-considered = Overwogen ({ $min } { $ellipsis } { $max }):
+result-min = min
+result-max = max
+result-search-space = Rust { $min } { $ellipsis } Rust { $max }
+result-search-method = Zoekmethode:
+result-msrv = MSRV:
+result-msrv-none = n.v.t.
+result-target = Doel:
+result-fingerprint = Vingerafdruk:
+result-shard = Shard:
+result-shard-compatible = allemaal compatibel
+result-shard-incompatible = incompatibele release gevonden
+
+## Difference between the diagnostics around the MSRV
+diff-none = De diagnostiek van Rust { $failing } en Rust { $passing } verschilt niet
+diff-changed = Diagnostiek die veranderde van Rust { $failing } naar Rust { $passing } (de MSRV):
+
+## Find, for multiple targets
+targets-msrv = De MSRV over de { $count } doelen is Rust { $msrv }, vereist door { $targets }
+targets-no-msrv = De crate heeft geen MSRV over de doelen, omdat geen Rust-versie in de zoekruimte compatibel is met elk doel
+
+## Bisect lockfile
+bisect-first-fails = De crate faalt eerst met Rust { $version } met de lockfile van { $revision }, na { $checks } controle(s)
+bisect-last-compatible = De lockfile van { $revision } is de laatste die compatibel is
+
+## Cache
+cache-usage = { $size } van { $max } in gebruik in '{ $path }'
+
+## Check consistency
+consistency-undeclared = Er is geen MSRV opgegeven
+consistency-consistent = Elk van de { $count } opgaven van de MSRV is Rust { $msrv }
+consistency-inconsistent = { $conflicts } van de { $count } opgaven wijken af van de MSRV, Rust { $msrv }
+
+## Clean
+clean-nothing = Niets te verwijderen
+clean-would-remove = Zou { $size } verwijderen
+clean-removed = { $size } verwijderd
+
+## Estimate
+estimate-cargo = Het project kan alleen worden gelezen door de cargo van Rust { $version } of nieuwer
+estimate-msrv = De MSRV wordt geschat op Rust { $version } (niet geverifieerd door te compileren)
+estimate-none = Er zijn geen features gevonden die een specifieke Rust-versie vereisen
+
+## Inspect
+inspect-no-runs = Er zijn geen uitvoeringen opgeslagen, sla er een op met `cargo msrv find --save-run` of `cargo msrv verify --save-run`
+
+## Merge results
+merge-msrv = De MSRV is Rust { $version } ({ $shards } shards)
+merge-no-msrv = Geen van de { $shards } shards heeft een compatibele Rust-versie gevonden
+
+## Migrate
+migrate-none-declared = Geen enkel pakket geeft package.metadata.msrv op
+migrate-none-migratable = Geen van de { $count } pakket(ten) die package.metadata.msrv opgeven kan worden gemigreerd
+migrate-would-migrate = Zou { $count } pakket(ten) migreren naar package.rust-version
+migrate-migrated = { $count } pakket(ten) gemigreerd naar package.rust-version
+
+## Outdated
+outdated-none = De nieuwste release van elke afhankelijkheid is compatibel met Rust { $msrv }
+
+## Show
+show-msrv = De MSRV is Rust { $version }
+show-declared-as = Opgegeven als { $kind } ({ $path })
+show-path-dependency-newer = Padafhankelijkheid { $name } { $version } vereist Rust { $requires }, wat nieuwer is dan de MSRV ({ $path })
+show-path-dependency = Padafhankelijkheid { $name } { $version } vereist Rust { $requires } ({ $path })
+show-path-dependency-unspecified = Padafhankelijkheid { $name } { $version } geeft geen MSRV op ({ $path })
+show-workspace = De workspace.package.rust-version, die leden kunnen overerven, is Rust { $version }
+
+## Sync
+sync-consistent = Elke opgave van de MSRV is Rust { $msrv }
+sync-would-update = Zou { $count } opgave(n) bijwerken naar Rust { $msrv }
+sync-updated = { $count } opgave(n) bijgewerkt naar Rust { $msrv }
+
+## Verify
+verify-fresh = Rust { $version } ligt binnen { $tolerance } minor-versie(s) van de MSRV (Rust { $msrv })
+verify-too-low = Rust { $version } is lager dan de MSRV (Rust { $msrv })
+verify-too-high = Rust { $version } is meer dan { $tolerance } minor-versie(s) hoger dan de MSRV (Rust { $msrv })
+verify-release-keeps = De { $release }-release verhoogt de MSRV (Rust { $version }) niet
+verify-release-allowed = De { $release }-release verhoogt de MSRV van Rust { $version } naar Rust { $msrv }, wat het MSRV-beleid toestaat
+verify-release-not-allowed = De { $release }-release verhoogt de MSRV van Rust { $version } naar Rust { $msrv }, wat het MSRV-beleid niet toestaat
+verify-slack = De crate compileert ook met Rust { $msrv }, { $slack } minor-release(s) ouder dan Rust { $version }; overweeg de MSRV te verlagen met `cargo msrv set { $msrv }`
+verify-no-slack = Rust { $version } is de MSRV, de crate heeft geen speling
+
+## What if
+whatif-add-raises = Het toevoegen van { $dependency } verhoogt de MSRV van Rust { $before } naar Rust { $after }
+whatif-add-raises-to = Het toevoegen van { $dependency } verhoogt de MSRV naar Rust { $after }
+whatif-add-keeps = Het toevoegen van { $dependency } verhoogt de MSRV van Rust { $before } niet
+whatif-add-keeps-unknown = Het toevoegen van { $dependency } verhoogt de MSRV niet
+whatif-update-raises = Het bijwerken van de lockfile verhoogt de MSRV van Rust { $before } naar Rust { $after }
+whatif-update-raises-to = Het bijwerken van de lockfile verhoogt de MSRV naar Rust { $after }
+whatif-update-keeps = Het bijwerken van de lockfile verhoogt de MSRV van Rust { $before } niet
+whatif-update-keeps-unknown = Het bijwerken van de lockfile verhoogt de MSRV niet
+whatif-requires = { $name } { $version } vereist Rust { $requires }
+whatif-requires-locked = { $name } { $version } (vastgezet op { $locked }) vereist Rust { $requires }
+whatif-still-compiles = De crate compileert nog steeds met Rust { $version }
+whatif-no-longer-compiles = De crate compileert niet meer met Rust { $version }
@@ -0,0 +1,267 @@
+//! The message catalog of the `human` output format, so its progress and result messages can be
+//! shown in the language of the user. The other output formats are not localized, so their
+//! output stays stable for the programs which consume it.
+//!
+//! The messages of each locale are kept in a `locales/<locale>.ftl` file, in a subset of the
+//! [Fluent](https://projectfluent.org) syntax: one `key = message` per line, where `{ $name }`
+//! is replaced by the value of the argument `name`. Messages which are not translated fall back
+//! to English.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::sync::RwLock;
+
+static LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+static CATALOGS: Lazy<HashMap<Locale, Catalog>> = Lazy::new(|| {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| (locale, Catalog::parse(locale.source())))
+        .collect()
+});
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Nl,
+}
+
+impl Locale {
+    const ALL: [Self; 3] = [Self::En, Self::De, Self::Nl];
+
+    fn source(self) -> &'static str {
+        match self {
+            Self::En => include_str!("locales/en.ftl"),
+            Self::De => include_str!("locales/de.ftl"),
+            Self::Nl => include_str!("locales/nl.ftl"),
+        }
+    }
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+            Self::Nl => "nl",
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = UnsupportedLocale;
+
+    /// Parse a language code, like `nl`, optionally followed by a region and encoding, like
+    /// `nl-BE` or `nl_NL.UTF-8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s
+            .split(['-', '_', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.code() == language)
+            .ok_or_else(|| UnsupportedLocale(s.to_string()))
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Unsupported locale '{0}', expected one of 'en', 'de' or 'nl'")]
+pub struct UnsupportedLocale(String);
+
+/// Show the messages of the `human` output format in the given locale.
+pub fn set_locale(locale: Locale) {
+    *LOCALE
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = locale;
+}
+
+fn locale() -> Locale {
+    *LOCALE
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The message with the given key, in the current locale, with the given arguments filled in.
+pub(crate) fn message(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    message_in(locale(), key, args)
+}
+
+fn message_in(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let template = [locale, Locale::En]
+        .into_iter()
+        .find_map(|locale| CATALOGS[&locale].messages.get(key).copied())
+        // The English catalog has every key, but a missing message should not be an error
+        .unwrap_or(key);
+
+    fill(template, args)
+}
+
+/// Localize a message of the `human` output format, e.g. `tr!("check-header", nth = 1)`.
+macro_rules! tr {
+    ($key:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        $crate::reporter::i18n::message(
+            $key,
+            &[$((stringify!($name), &$value as &dyn std::fmt::Display)),*],
+        )
+    };
+}
+
+pub(crate) use tr;
+
+struct Catalog {
+    messages: HashMap<&'static str, &'static str>,
+}
+
+impl Catalog {
+    fn parse(source: &'static str) -> Self {
+        let messages = source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, message)| (key.trim(), message.trim()))
+            .collect();
+
+        Self { messages }
+    }
+}
+
+/// Replace each `{ $name }` placeholder of the template by the value of its argument.
+/// Placeholders without an argument are left as is.
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+
+        filled.push_str(&rest[..start]);
+
+        let placeholder = &rest[start..=end];
+        let name = placeholder[1..placeholder.len() - 1]
+            .trim()
+            .strip_prefix('$');
+
+        match name.and_then(|name| args.iter().find(|(arg, _)| *arg == name)) {
+            Some((_, value)) => filled.push_str(&value.to_string()),
+            None => filled.push_str(placeholder),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}'))
+            .map(|(name, _)| name.trim())
+            .collect()
+    }
+
+    #[yare::parameterized(
+        en = { "en", Locale::En },
+        region = { "nl-BE", Locale::Nl },
+        posix = { "de_DE.UTF-8", Locale::De },
+        uppercase = { "NL", Locale::Nl },
+    )]
+    fn parse_locale(input: &str, expected: Locale) {
+        assert_eq!(input.parse::<Locale>().unwrap(), expected);
+    }
+
+    #[test]
+    fn unsupported_locale() {
+        assert!("fr".parse::<Locale>().is_err());
+    }
+
+    #[yare::parameterized(
+        en = { Locale::En, "Compatibility Check #2: Rust 1.56.0" },
+        de = { Locale::De, "Kompatibilitätsprüfung #2: Rust 1.56.0" },
+        nl = { Locale::Nl, "Compatibiliteitscontrole #2: Rust 1.56.0" },
+    )]
+    fn localized(locale: Locale, expected: &str) {
+        let message = message_in(
+            locale,
+            "check-header",
+            &[("nth", &2), ("version", &"1.56.0")],
+        );
+
+        assert_eq!(message, expected);
+    }
+
+    #[test]
+    fn fall_back_to_english() {
+        assert_eq!(message_in(Locale::Nl, "result-msrv", &[]), "MSRV:");
+        assert_eq!(message_in(Locale::Nl, "no-such-key", &[]), "no-such-key");
+    }
+
+    #[yare::parameterized(
+        no_placeholders = { "Is compatible", "Is compatible" },
+        spaced = { "Rust { $version }", "Rust 1.56.0" },
+        unspaced = { "Rust {$version}!", "Rust 1.56.0!" },
+        unknown = { "Rust { $other }", "Rust { $other }" },
+        unclosed = { "Rust { $version", "Rust { $version" },
+    )]
+    fn filled(template: &str, expected: &str) {
+        assert_eq!(fill(template, &[("version", &"1.56.0")]), expected);
+    }
+
+    #[test]
+    fn english_messages_are_translated() {
+        let english = &CATALOGS[&Locale::En].messages;
+
+        for locale in [Locale::De, Locale::Nl] {
+            for key in english.keys() {
+                assert!(
+                    CATALOGS[&locale].messages.contains_key(key),
+                    "'{}' is not translated in locale '{}'",
+                    key,
+                    locale
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn translations_match_english() {
+        let english = &CATALOGS[&Locale::En].messages;
+
+        for locale in Locale::ALL {
+            for (key, template) in &CATALOGS[&locale].messages {
+                let Some(english) = english.get(key) else {
+                    panic!("'{}' of locale '{}' is not an English message", key, locale);
+                };
+
+                assert_eq!(
+                    placeholders(template),
+                    placeholders(english),
+                    "placeholders of '{}' of locale '{}'",
+                    key,
+                    locale
+                );
+            }
+        }
+    }
+}
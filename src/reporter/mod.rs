@@ -3,20 +3,36 @@ use storyteller::{event_channel, ChannelEventListener, ChannelReporter, EventLis
 use crate::reporter::event::ScopeCounter;
 use crate::TResult;
 
+pub use ui::AzureHandler;
+pub use ui::BuildkiteAnnotationHandler;
 pub use ui::DiscardOutputHandler;
+pub use ui::EventLogHandler;
+pub use ui::EventSocketHandler;
+pub use ui::GitHubOutputHandler;
+pub use ui::GitLabHandler;
 pub use ui::HumanProgressHandler;
 pub use ui::JsonHandler;
+pub use ui::MetricsHandler;
 pub use ui::MinimalOutputHandler;
+pub use ui::{set_theme, Column, HumanLayout, Theme};
+pub use ui::{MatrixReportFormat, MatrixReportHandler};
+
+pub use event_stream::EventStream;
+pub use formatting::{set_ascii, set_color};
+pub use i18n::{set_locale, Locale, UnsupportedLocale};
 
 pub use event::{
     Event, Marker, Message, Scope, ScopeGenerator, SubcommandResult, SupplyScopeGenerator,
     TerminateWithFailure, /* fixme: Needed by binary crate, how much do we want to expose here? */
+    Terminated, TerminationOutcome,
 };
 
 pub(crate) mod event;
+pub(crate) mod event_stream;
+pub(crate) mod i18n;
 pub(crate) mod ui;
 
-mod formatting;
+pub(crate) mod formatting;
 
 #[cfg(test)]
 mod testing;
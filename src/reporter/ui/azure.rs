@@ -0,0 +1,388 @@
+//! Writes [logging commands] of Azure Pipelines to stdout, for `--output-format azure`, so the
+//! failures of cargo-msrv are shown as issues of the build, and the progress of the search is
+//! shown next to the task.
+//!
+//! Unlike the GitLab code quality report, the commands are written as the events arrive, so the
+//! log of the task shows each checked toolchain while cargo-msrv runs.
+//!
+//! [logging commands]: https://learn.microsoft.com/en-us/azure/devops/pipelines/scripts/logging-commands
+
+use crate::context::SearchMethod;
+use crate::io::SendWriter;
+use crate::reporter::event::{
+    ConsistencyResult, FindResult, Freshness, Message, Progress, SubcommandResult, VerifyResult,
+};
+use crate::reporter::ui::manifest_location::msrv_line;
+use crate::Event;
+use std::io::{self, Stdout};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use storyteller::EventHandler;
+
+pub struct AzureHandler<W: SendWriter> {
+    writer: Mutex<W>,
+    /// The Cargo manifest in which the MSRV is declared, as given on the command line.
+    manifest: PathBuf,
+    search_method: Mutex<Option<SearchMethod>>,
+    /// Whether an error was logged as an issue, so a subsequent failure doesn't repeat it.
+    logged_error: AtomicBool,
+}
+
+impl AzureHandler<Stdout> {
+    pub fn stdout(manifest: PathBuf) -> Self {
+        Self::new(io::stdout(), manifest)
+    }
+}
+
+impl<W: SendWriter> AzureHandler<W> {
+    pub fn new(writer: W, manifest: PathBuf) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            manifest,
+            search_method: Mutex::new(None),
+            logged_error: AtomicBool::new(false),
+        }
+    }
+
+    fn write_line(&self, line: &str) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writeln!(writer, "{}", line);
+        }
+    }
+
+    fn log_issue(&self, issue: IssueType, location: Option<(&str, usize)>, message: &str) {
+        if issue == IssueType::Error {
+            self.logged_error.store(true, Ordering::SeqCst);
+        }
+
+        let mut properties = vec![("type", issue.as_str().to_string())];
+        if let Some((path, line)) = location {
+            properties.push(("sourcepath", path.to_string()));
+            properties.push(("linenumber", line.to_string()));
+        }
+
+        self.write_line(&command("task.logissue", &properties, message));
+    }
+
+    fn log_manifest_issue(&self, issue: IssueType, message: &str) {
+        let path = self.manifest.to_string_lossy();
+        let line = msrv_line(&self.manifest);
+
+        self.log_issue(issue, Some((&path, line)), message);
+    }
+
+    fn set_progress(&self, percentage: u64) {
+        self.write_line(&command(
+            "task.setprogress",
+            &[("value", percentage.to_string())],
+            "Searching for the MSRV",
+        ));
+    }
+
+    fn progress(&self, progress: &Progress) {
+        let method = self
+            .search_method
+            .lock()
+            .ok()
+            .and_then(|method| *method)
+            .unwrap_or(SearchMethod::Bisect);
+
+        self.set_progress(percentage(method, progress));
+    }
+
+    fn find_result(&self, result: &FindResult) {
+        self.set_progress(100);
+
+        match result.msrv() {
+            Some(msrv) => self.write_line(&format!("The MSRV is Rust {}", msrv)),
+            None => self.log_manifest_issue(
+                IssueType::Error,
+                "Unable to find a Rust version which is compatible with the crate",
+            ),
+        }
+    }
+
+    fn verify_result(&self, result: &VerifyResult) {
+        let version = result.toolchain().version();
+
+        if let Some(details) = result.release_details() {
+            match (details.raises_msrv, details.bump_allowed) {
+                (true, false) => self.log_manifest_issue(
+                    IssueType::Error,
+                    &format!(
+                        "The {} release raises the MSRV from Rust {} to Rust {}, which the MSRV policy does not allow",
+                        details.release_type, version, details.msrv
+                    ),
+                ),
+                _ => self.write_line(&format!(
+                    "The MSRV of the {} release is Rust {}",
+                    details.release_type, details.msrv
+                )),
+            }
+            return;
+        }
+
+        if !result.is_compatible() {
+            let mut message = format!("The crate is not compatible with Rust {}", version);
+            if let Some(error) = result.result.first_error() {
+                message.push_str(&format!(": {}", error));
+            }
+
+            self.log_manifest_issue(IssueType::Error, &message);
+            return;
+        }
+
+        match result.freshness_details() {
+            Some(details)
+                if details.tolerance.is_some() && details.freshness == Freshness::TooHigh =>
+            {
+                self.log_manifest_issue(
+                    IssueType::Warning,
+                    &format!(
+                        "The declared MSRV, Rust {}, is {} minor releases higher than the MSRV, Rust {}",
+                        version, details.slack, details.msrv
+                    ),
+                );
+            }
+            _ => self.write_line(&format!("The crate is compatible with Rust {}", version)),
+        }
+    }
+
+    fn consistency_result(&self, result: &ConsistencyResult) {
+        for conflict in result.conflicts() {
+            self.log_issue(
+                IssueType::Error,
+                Some((
+                    conflict.declaration.file.as_str(),
+                    conflict.declaration.line,
+                )),
+                &format!(
+                    "The {} declares Rust {}, but the MSRV is Rust {}",
+                    conflict.declaration.kind, conflict.declaration.version, conflict.expected
+                ),
+            );
+        }
+    }
+}
+
+impl<W: SendWriter> EventHandler for AzureHandler<W> {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        match event.message() {
+            Message::FindMsrv(it) => {
+                if let Ok(mut method) = self.search_method.lock() {
+                    *method = Some(it.search_method());
+                }
+                self.set_progress(0);
+            }
+            Message::Progress(it) => self.progress(it),
+            Message::CheckResult(it) => {
+                let outcome = if it.is_compatible() {
+                    "compatible"
+                } else {
+                    "incompatible"
+                };
+                self.write_line(&format!("Rust {} is {}", it.toolchain().version(), outcome));
+            }
+            Message::Warning(it) => self.log_issue(IssueType::Warning, None, it.message()),
+            Message::SubcommandResult(SubcommandResult::Find(it)) => self.find_result(it),
+            Message::SubcommandResult(SubcommandResult::Verify(it)) => self.verify_result(it),
+            Message::SubcommandResult(SubcommandResult::CheckConsistency(it)) => {
+                self.consistency_result(it)
+            }
+            Message::TerminateWithFailure(it) if !self.logged_error.load(Ordering::SeqCst) => {
+                self.log_issue(IssueType::Error, None, it.as_message())
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum IssueType {
+    Warning,
+    Error,
+}
+
+impl IssueType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// How far the search is, as a percentage. A bisection takes about `log2(n) + 1` steps, so the
+/// iteration is compared against that instead of the size of the search space. Only the result of
+/// the search reports 100.
+fn percentage(method: SearchMethod, progress: &Progress) -> u64 {
+    let size = progress.search_space_size().max(1);
+
+    let steps = match method {
+        SearchMethod::Linear => size,
+        SearchMethod::Bisect
+        | SearchMethod::Hybrid
+        | SearchMethod::Declared
+        | SearchMethod::LastRun => u64::from(size.ilog2()) + 2,
+    };
+
+    (progress.iteration() * 100 / steps).min(99)
+}
+
+/// A logging command, like `##vso[task.logissue type=error;]message`.
+fn command(name: &str, properties: &[(&str, String)], message: &str) -> String {
+    let properties = properties
+        .iter()
+        .map(|(key, value)| format!("{}={};", key, escape_property(value)))
+        .collect::<String>();
+
+    format!("##vso[{} {}]{}", name, properties, escape_message(message))
+}
+
+/// Escape a message, which may not span lines.
+fn escape_message(message: &str) -> String {
+    message
+        .replace('%', "%AZP25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape the value of a property, which may not contain the separators of the command either.
+fn escape_property(value: &str) -> String {
+    escape_message(value)
+        .replace(';', "%3B")
+        .replace(']', "%5D")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::declaration::{conflicts, Declaration, DeclarationKind};
+    use crate::reporter::event::{FindMsrv, TerminateWithFailure};
+    use crate::rust::Toolchain;
+    use crate::semver;
+    use crate::CargoMSRVError;
+    use camino::Utf8PathBuf;
+
+    fn output(events: Vec<Event>, manifest: &str) -> (Vec<String>, PathBuf) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("Cargo.toml");
+        std::fs::write(&path, manifest).unwrap();
+
+        let handler = AzureHandler::new(Vec::new(), path.clone());
+        for event in events {
+            handler.handle(event);
+        }
+        handler.finish();
+
+        let written = handler.writer.into_inner().unwrap();
+        let lines = String::from_utf8(written)
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+
+        (lines, path)
+    }
+
+    #[test]
+    fn failed_verification() {
+        let toolchain = Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]);
+        let event = VerifyResult::incompatible(toolchain, Some("error[E0658]: unstable".into()));
+
+        let (lines, path) = output(
+            vec![
+                event.into(),
+                TerminateWithFailure::new(CargoMSRVError::UnableToFindAnyGoodVersion {
+                    command: "cargo check".into(),
+                })
+                .into(),
+            ],
+            "[package]\nname = \"a\"\nrust-version = \"1.60\"\n",
+        );
+
+        assert_eq!(
+            lines,
+            vec![format!(
+                "##vso[task.logissue type=error;sourcepath={};linenumber=3;]The crate is not compatible with Rust 1.60.0: error[E0658]: unstable",
+                path.display()
+            )]
+        );
+    }
+
+    #[test]
+    fn conflicting_declaration() {
+        let declarations = [
+            (DeclarationKind::RustVersion, "Cargo.toml", "1.60"),
+            (DeclarationKind::ClippyMsrv, "clippy.toml", "1.58"),
+        ]
+        .into_iter()
+        .map(|(kind, file, version)| Declaration {
+            kind,
+            file: Utf8PathBuf::from(file),
+            line: 1,
+            version: version.parse().unwrap(),
+        })
+        .collect::<Vec<_>>();
+        let conflicts = conflicts(&declarations);
+        let event = ConsistencyResult::new(declarations, conflicts);
+
+        let (lines, _) = output(vec![event.into()], "");
+
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0]
+            .starts_with("##vso[task.logissue type=error;sourcepath=clippy.toml;linenumber=1;]"));
+    }
+
+    #[test]
+    fn search_progress() {
+        let (lines, _) = output(
+            vec![
+                FindMsrv::new(SearchMethod::Linear).into(),
+                Progress::new(0, 4, 1).into(),
+                Progress::new(1, 4, 2).into(),
+            ],
+            "",
+        );
+
+        assert_eq!(
+            lines,
+            vec![
+                "##vso[task.setprogress value=0;]Searching for the MSRV",
+                "##vso[task.setprogress value=25;]Searching for the MSRV",
+                "##vso[task.setprogress value=50;]Searching for the MSRV",
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        linear_first = { SearchMethod::Linear, 1, 10, 10 },
+        linear_last = { SearchMethod::Linear, 10, 10, 99 },
+        bisect_first = { SearchMethod::Bisect, 1, 64, 12 },
+        bisect_overrun = { SearchMethod::Bisect, 12, 64, 99 },
+        single = { SearchMethod::Hybrid, 1, 1, 50 },
+    )]
+    fn progress_percentage(method: SearchMethod, iteration: u64, size: u64, expected: u64) {
+        assert_eq!(
+            percentage(method, &Progress::new(0, size, iteration)),
+            expected
+        );
+    }
+
+    #[yare::parameterized(
+        message = { escape_message("100%\r\ndone"), "100%AZP25%0D%0Adone" },
+        property = { escape_property("a;b]c"), "a%3Bb%5Dc" },
+    )]
+    fn escape(escaped: String, expected: &str) {
+        assert_eq!(escaped, expected);
+    }
+}
@@ -0,0 +1,312 @@
+//! Annotates the Buildkite build with the result of cargo-msrv, so it is shown at the top of the
+//! build page. The annotation is markdown, with the outcome and a table of the checked Rust
+//! versions.
+//!
+//! The annotation is added with `buildkite-agent annotate`. If the agent can't be found, it is
+//! written to `cargo-msrv-annotation.md` instead, e.g. to be added by a later command step.
+
+use crate::reporter::event::{Message, SubcommandResult};
+use crate::{semver, Event};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use storyteller::EventHandler;
+
+/// The environment variable which Buildkite sets to `true` in the environment of a job.
+pub const BUILDKITE_ENV_VAR: &str = "BUILDKITE";
+
+/// The file to which the annotation is written when `buildkite-agent` can't be found.
+pub const ANNOTATION_FILE: &str = "cargo-msrv-annotation.md";
+
+/// Identifies the annotation of cargo-msrv, so a rerun replaces it instead of adding another.
+const ANNOTATION_CONTEXT: &str = "cargo-msrv";
+
+pub struct BuildkiteAnnotationHandler {
+    agent: PathBuf,
+    file: PathBuf,
+    summary: Mutex<Summary>,
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    outcome: Option<Outcome>,
+    checked: Vec<Checked>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Outcome {
+    Found(semver::Version),
+    NotFound,
+    Verified(semver::Version),
+    NotVerified(semver::Version, Option<String>),
+    Failed(String),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Checked {
+    version: semver::Version,
+    is_compatible: bool,
+    error: Option<String>,
+}
+
+impl BuildkiteAnnotationHandler {
+    /// The handler for the job in which cargo-msrv runs, if it runs in Buildkite.
+    pub fn from_env() -> Option<Self> {
+        let in_buildkite = std::env::var(BUILDKITE_ENV_VAR).is_ok_and(|value| value == "true");
+
+        in_buildkite.then(|| Self::new(PathBuf::from("buildkite-agent"), ANNOTATION_FILE.into()))
+    }
+
+    pub fn new(agent: PathBuf, file: PathBuf) -> Self {
+        Self {
+            agent,
+            file,
+            summary: Mutex::new(Summary::default()),
+        }
+    }
+
+    fn annotate(&self, style: &str, markdown: &str) -> io::Result<()> {
+        let mut agent = match Command::new(&self.agent)
+            .args([
+                "annotate",
+                "--context",
+                ANNOTATION_CONTEXT,
+                "--style",
+                style,
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+        {
+            Ok(agent) => agent,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return std::fs::write(&self.file, markdown);
+            }
+            Err(error) => return Err(error),
+        };
+
+        if let Some(mut stdin) = agent.stdin.take() {
+            stdin.write_all(markdown.as_bytes())?;
+        }
+
+        let status = agent.wait()?;
+        if !status.success() {
+            return Err(io::Error::other(format!(
+                "buildkite-agent annotate exited with {}",
+                status
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Summary {
+    fn update(&mut self, message: &Message) {
+        match message {
+            Message::CheckResult(it) => {
+                let checked = Checked {
+                    version: it.toolchain().version().clone(),
+                    is_compatible: it.is_compatible(),
+                    error: it.compatibility.first_error().map(String::from),
+                };
+
+                // A version may be checked again, e.g. by `verify --find`
+                self.checked
+                    .retain(|earlier| earlier.version != checked.version);
+                self.checked.push(checked);
+            }
+            Message::SubcommandResult(SubcommandResult::Find(it)) => {
+                self.outcome = Some(match it.msrv() {
+                    Some(msrv) => Outcome::Found(msrv.clone()),
+                    None => Outcome::NotFound,
+                });
+            }
+            Message::SubcommandResult(SubcommandResult::Verify(it)) => {
+                let version = it.toolchain().version().clone();
+
+                self.outcome = Some(if it.is_verified() {
+                    Outcome::Verified(version)
+                } else {
+                    Outcome::NotVerified(version, it.result.first_error().map(String::from))
+                });
+            }
+            Message::TerminateWithFailure(it) if self.outcome.is_none() => {
+                self.outcome = Some(Outcome::Failed(it.as_message().to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    /// The style and markdown of the annotation, or nothing if the subcommand doesn't check
+    /// toolchains.
+    fn render(&self) -> Option<(&'static str, String)> {
+        let outcome = self.outcome.as_ref()?;
+        if matches!(outcome, Outcome::Failed(_)) && self.checked.is_empty() {
+            return None;
+        }
+
+        let (style, headline, error) = match outcome {
+            Outcome::Found(msrv) => ("success", format!("The MSRV is **Rust {}**", msrv), None),
+            Outcome::NotFound => (
+                "error",
+                "Unable to find a Rust version which is compatible with the crate".to_string(),
+                None,
+            ),
+            Outcome::Verified(version) => (
+                "success",
+                format!(
+                    "The crate is compatible with its MSRV, **Rust {}**",
+                    version
+                ),
+                None,
+            ),
+            Outcome::NotVerified(version, error) => (
+                "error",
+                format!(
+                    "The crate is **not** compatible with its MSRV, **Rust {}**",
+                    version
+                ),
+                error.as_deref(),
+            ),
+            Outcome::Failed(reason) => ("error", "cargo-msrv failed".to_string(), Some(&**reason)),
+        };
+
+        let mut markdown = format!("#### cargo-msrv\n\n{}\n", headline);
+
+        if let Some(error) = error {
+            markdown.push_str(&format!("\n```\n{}\n```\n", error));
+        }
+
+        if !self.checked.is_empty() {
+            let mut checked = self.checked.iter().collect::<Vec<_>>();
+            checked.sort_by(|lhs, rhs| rhs.version.cmp(&lhs.version));
+
+            markdown.push_str("\n| Rust version | Result | Error |\n| --- | --- | --- |\n");
+            for check in checked {
+                let result = if check.is_compatible {
+                    "✅ compatible"
+                } else {
+                    "❌ incompatible"
+                };
+                let error = check
+                    .error
+                    .as_deref()
+                    .map(|error| format!("`{}`", error.replace('|', "\\|").replace('`', "'")))
+                    .unwrap_or_default();
+
+                markdown.push_str(&format!("| {} | {} | {} |\n", check.version, result, error));
+            }
+        }
+
+        Some((style, markdown))
+    }
+}
+
+impl EventHandler for BuildkiteAnnotationHandler {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        if let Ok(mut summary) = self.summary.lock() {
+            summary.update(event.message());
+        }
+    }
+
+    fn finish(&self) {
+        let summary = self
+            .summary
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Some((style, markdown)) = summary.render() else {
+            return;
+        };
+
+        // The annotation is a convenience, which should not fail cargo-msrv itself
+        if let Err(error) = self.annotate(style, &markdown) {
+            info!(%error, "unable to annotate the Buildkite build");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SearchMethod;
+    use crate::manifest::bare_version::BareVersion;
+    use crate::reporter::event::{CheckResult, FindResult, VerifyResult};
+    use crate::rust::Toolchain;
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    fn rendered(events: Vec<Event>) -> Option<(&'static str, String)> {
+        let mut summary = Summary::default();
+        for event in events {
+            summary.update(event.message());
+        }
+
+        summary.render()
+    }
+
+    #[test]
+    fn find() {
+        let (style, markdown) = rendered(vec![
+            CheckResult::incompatible(toolchain(60), Some("error[E0658]: `let...else` | x".into()))
+                .into(),
+            CheckResult::compatible(toolchain(65)).into(),
+            FindResult::new_msrv(
+                semver::Version::new(1, 65, 0),
+                "x",
+                BareVersion::TwoComponents(1, 56),
+                BareVersion::TwoComponents(1, 80),
+                SearchMethod::Bisect,
+            )
+            .into(),
+        ])
+        .unwrap();
+
+        assert_eq!(style, "success");
+        assert_eq!(
+            markdown,
+            "#### cargo-msrv\n\nThe MSRV is **Rust 1.65.0**\n\n\
+             | Rust version | Result | Error |\n| --- | --- | --- |\n\
+             | 1.65.0 | ✅ compatible |  |\n\
+             | 1.60.0 | ❌ incompatible | `error[E0658]: 'let...else' \\| x` |\n"
+        );
+    }
+
+    #[test]
+    fn failed_verification() {
+        let (style, markdown) = rendered(vec![
+            CheckResult::incompatible(toolchain(60), Some("error: unstable".into())).into(),
+            VerifyResult::incompatible(toolchain(60), Some("error: unstable".into())).into(),
+        ])
+        .unwrap();
+
+        assert_eq!(style, "error");
+        assert!(markdown.contains("**not** compatible with its MSRV, **Rust 1.60.0**"));
+        assert!(markdown.contains("\n```\nerror: unstable\n```\n"));
+    }
+
+    #[test]
+    fn without_checks() {
+        assert_eq!(rendered(vec![]), None);
+    }
+
+    #[test]
+    fn written_without_agent() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let file = dir.path().join(ANNOTATION_FILE);
+
+        let handler =
+            BuildkiteAnnotationHandler::new(dir.path().join("missing-agent"), file.clone());
+        handler.handle(VerifyResult::compatible(toolchain(60)).into());
+        handler.finish();
+
+        let written = std::fs::read_to_string(file).unwrap();
+        assert!(written.starts_with("#### cargo-msrv\n\nThe crate is compatible"));
+    }
+}
@@ -0,0 +1,90 @@
+//! Records the events, as newline delimited JSON, to a file, in addition to the regular user
+//! output. The reports of `cargo msrv report` can then be rendered afterwards from the recorded
+//! events, without running the checks again.
+
+use crate::Event;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use storyteller::EventHandler;
+
+pub struct EventLogHandler<W: Write + Send = BufWriter<File>> {
+    /// The log, until writing to it failed, e.g. because the disk is full. The remaining events
+    /// are then no longer recorded.
+    log: Mutex<Option<W>>,
+}
+
+impl EventLogHandler<BufWriter<File>> {
+    /// Create the log file at the given path, or truncate it if it exists.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Send> EventLogHandler<W> {
+    pub fn new(log: W) -> Self {
+        Self {
+            log: Mutex::new(Some(log)),
+        }
+    }
+
+    fn write(log: &mut W, event: &Event) -> io::Result<()> {
+        let serialized_event = serde_json::to_string(event)?;
+
+        writeln!(log, "{}", serialized_event)
+    }
+
+    fn with_log(&self, f: impl FnOnce(&mut W) -> io::Result<()>) {
+        let mut guard = self
+            .log
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Some(log) = guard.as_mut() else {
+            return;
+        };
+
+        // A log which can't be written should not stop cargo-msrv itself
+        if let Err(error) = f(log) {
+            info!(%error, "unable to write to the event log, no longer recording events");
+            *guard = None;
+        }
+    }
+}
+
+impl<W: Write + Send> EventHandler for EventLogHandler<W> {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        self.with_log(|log| Self::write(log, &event));
+    }
+
+    fn finish(&self) {
+        self.with_log(|log| log.flush());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::Warning;
+    use crate::semver;
+
+    #[test]
+    fn records_each_event_on_a_line() {
+        let handler = EventLogHandler::new(Vec::new());
+
+        handler.handle(Warning::prerelease_only(&semver::Version::new(1, 80, 0)).into());
+        handler.handle(Warning::prerelease_only(&semver::Version::new(1, 81, 0)).into());
+        handler.finish();
+
+        let log = handler.log.into_inner().unwrap().unwrap();
+        let log = String::from_utf8(log).unwrap();
+
+        assert_eq!(log.lines().count(), 2);
+        assert!(log.lines().all(
+            |line| serde_json::from_str::<serde_json::Value>(line).unwrap()["type"] == "warning"
+        ));
+    }
+}
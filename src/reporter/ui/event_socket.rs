@@ -0,0 +1,158 @@
+//! Streams the events as newline delimited JSON over a local socket, in addition to the regular
+//! user output, so other programs can follow the progress of cargo-msrv while it runs.
+//!
+//! The listening end of the socket is owned by the consuming program: cargo-msrv connects to a
+//! Unix domain socket, or on Windows, to a named pipe like `\\.\pipe\cargo-msrv`.
+
+use crate::io::SendWriter;
+use crate::Event;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use storyteller::EventHandler;
+
+#[cfg(unix)]
+pub type EventSocket = std::os::unix::net::UnixStream;
+
+#[cfg(not(unix))]
+pub type EventSocket = std::fs::File;
+
+impl SendWriter for EventSocket {}
+
+pub struct EventSocketHandler<W: SendWriter = EventSocket> {
+    /// The socket, until writing to it failed, e.g. because the consuming program stopped
+    /// listening. The remaining events are then no longer streamed.
+    socket: Mutex<Option<W>>,
+}
+
+impl EventSocketHandler<EventSocket> {
+    /// Connect to the socket, or named pipe, at the given path.
+    pub fn connect(path: &Path) -> io::Result<Self> {
+        Ok(Self::new(connect(path)?))
+    }
+}
+
+#[cfg(unix)]
+fn connect(path: &Path) -> io::Result<EventSocket> {
+    std::os::unix::net::UnixStream::connect(path)
+}
+
+#[cfg(not(unix))]
+fn connect(path: &Path) -> io::Result<EventSocket> {
+    // A client opens a named pipe like a file
+    std::fs::OpenOptions::new().write(true).open(path)
+}
+
+impl<W: SendWriter> EventSocketHandler<W> {
+    pub fn new(socket: W) -> Self {
+        Self {
+            socket: Mutex::new(Some(socket)),
+        }
+    }
+
+    fn write(socket: &mut W, event: &Event) -> io::Result<()> {
+        let serialized_event = serde_json::to_string(event)?;
+
+        writeln!(socket, "{}", serialized_event)?;
+        socket.flush()
+    }
+}
+
+impl<W: SendWriter> EventHandler for EventSocketHandler<W> {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        let mut guard = self
+            .socket
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Some(socket) = guard.as_mut() else {
+            return;
+        };
+
+        // A consumer which went away should not stop cargo-msrv itself
+        if let Err(error) = Self::write(socket, &event) {
+            info!(%error, "unable to write to the event socket, no longer streaming events");
+            *guard = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::{Message, SubcommandInit};
+
+    struct ClosedSocket;
+
+    impl io::Write for ClosedSocket {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SendWriter for ClosedSocket {}
+
+    fn event() -> Event {
+        Event::unscoped(Message::SubcommandInit(SubcommandInit::new("find")))
+    }
+
+    #[test]
+    fn one_event_per_line() {
+        let handler = EventSocketHandler::new(Vec::new());
+
+        handler.handle(event());
+        handler.handle(event());
+
+        let written = handler.socket.into_inner().unwrap().unwrap();
+        let written = String::from_utf8(written).unwrap();
+        let lines = written.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            r#"{"type":"subcommand_init","subcommand_id":"find"}"#
+        );
+    }
+
+    #[test]
+    fn stop_streaming_when_closed() {
+        let handler = EventSocketHandler::new(ClosedSocket);
+
+        handler.handle(event());
+        assert!(handler.socket.lock().unwrap().is_none());
+
+        handler.handle(event());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stream_over_unix_socket() {
+        use std::io::BufRead;
+        use std::os::unix::net::UnixListener;
+
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("events.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let handler = EventSocketHandler::connect(&path).unwrap();
+        handler.handle(event());
+        drop(handler);
+
+        let (stream, _) = listener.accept().unwrap();
+        let lines = io::BufReader::new(stream)
+            .lines()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            lines,
+            vec![r#"{"type":"subcommand_init","subcommand_id":"find"}"#]
+        );
+    }
+}
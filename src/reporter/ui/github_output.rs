@@ -0,0 +1,180 @@
+//! Writes the result of cargo-msrv as output parameters of the GitHub Actions step, to the file
+//! at `GITHUB_OUTPUT`, so later steps of the workflow can use them, like
+//! `${{ steps.msrv.outputs.msrv }}`.
+//!
+//! The outputs are:
+//!
+//! * `msrv`: the MSRV which was found, or for `verify`, the Rust version which was verified;
+//! * `verified`: `true` or `false`, only for `verify`;
+//! * `checked-versions`: the Rust versions which were checked, in order, as a JSON array.
+
+use crate::reporter::event::{Message, SubcommandResult};
+use crate::{semver, Event};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use storyteller::EventHandler;
+
+/// The environment variable which GitHub Actions sets to the path of the output file of a step.
+pub const GITHUB_OUTPUT_ENV_VAR: &str = "GITHUB_OUTPUT";
+
+pub struct GitHubOutputHandler {
+    path: PathBuf,
+    outputs: Mutex<Outputs>,
+}
+
+#[derive(Debug, Default)]
+struct Outputs {
+    msrv: Option<semver::Version>,
+    verified: Option<bool>,
+    checked_versions: Vec<semver::Version>,
+}
+
+impl GitHubOutputHandler {
+    /// The handler for the step in which cargo-msrv runs, if it runs in GitHub Actions.
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var_os(GITHUB_OUTPUT_ENV_VAR).filter(|path| !path.is_empty())?;
+
+        Some(Self::new(PathBuf::from(path)))
+    }
+
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            outputs: Mutex::new(Outputs::default()),
+        }
+    }
+
+    fn write(&self, outputs: &Outputs) -> io::Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        let mut lines = String::new();
+
+        if let Some(msrv) = &outputs.msrv {
+            lines.push_str(&format!("msrv={}\n", msrv));
+        }
+
+        if let Some(verified) = outputs.verified {
+            lines.push_str(&format!("verified={}\n", verified));
+        }
+
+        let checked_versions = serde_json::to_string(&outputs.checked_versions)?;
+        lines.push_str(&format!("checked-versions={}\n", checked_versions));
+
+        file.write_all(lines.as_bytes())
+    }
+}
+
+impl Outputs {
+    fn update(&mut self, message: &Message) {
+        match message {
+            Message::CheckResult(it) => {
+                let version = it.toolchain().version();
+
+                if !self.checked_versions.contains(version) {
+                    self.checked_versions.push(version.clone());
+                }
+            }
+            Message::SubcommandResult(SubcommandResult::Find(it)) => {
+                if let Some(msrv) = it.msrv() {
+                    self.msrv = Some(msrv.clone());
+                }
+            }
+            Message::SubcommandResult(SubcommandResult::Verify(it)) => {
+                self.verified = Some(it.is_verified());
+
+                // With `--find`, the found MSRV was already set
+                if it.is_compatible() && self.msrv.is_none() {
+                    self.msrv = Some(it.toolchain().version().clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl EventHandler for GitHubOutputHandler {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        if let Ok(mut outputs) = self.outputs.lock() {
+            outputs.update(event.message());
+        }
+    }
+
+    fn finish(&self) {
+        let outputs = self
+            .outputs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        // The outputs are a convenience, which should not fail cargo-msrv itself
+        if let Err(error) = self.write(&outputs) {
+            info!(%error, path = %self.path.display(), "unable to write the GitHub Actions outputs");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SearchMethod;
+    use crate::manifest::bare_version::BareVersion;
+    use crate::reporter::event::{CheckResult, FindResult, VerifyResult};
+    use crate::rust::Toolchain;
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    fn written(events: Vec<Event>) -> String {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("output");
+        std::fs::write(&path, "earlier=step\n").unwrap();
+
+        let handler = GitHubOutputHandler::new(path.clone());
+        for event in events {
+            handler.handle(event);
+        }
+        handler.finish();
+
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn find() {
+        let output = written(vec![
+            CheckResult::incompatible(toolchain(60), None).into(),
+            CheckResult::compatible(toolchain(65)).into(),
+            FindResult::new_msrv(
+                semver::Version::new(1, 65, 0),
+                "x",
+                BareVersion::TwoComponents(1, 56),
+                BareVersion::TwoComponents(1, 80),
+                SearchMethod::Bisect,
+            )
+            .into(),
+        ]);
+
+        assert_eq!(
+            output,
+            "earlier=step\nmsrv=1.65.0\nchecked-versions=[\"1.60.0\",\"1.65.0\"]\n"
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let output = written(vec![
+            CheckResult::incompatible(toolchain(60), None).into(),
+            VerifyResult::incompatible(toolchain(60), None).into(),
+        ]);
+
+        assert_eq!(
+            output,
+            "earlier=step\nverified=false\nchecked-versions=[\"1.60.0\"]\n"
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Writes a [code quality report] which GitLab shows in the widget of a merge request, for
+//! `--output-format gitlab`. The report is written to stdout once cargo-msrv finishes, to be
+//! stored as the `codequality` report artifact of the job.
+//!
+//! Each failed verification, stale MSRV, and declaration which disagrees with the MSRV is one
+//! issue of the report.
+//!
+//! [code quality report]: https://docs.gitlab.com/ee/ci/testing/code_quality.html#implement-a-custom-tool
+
+use crate::io::SendWriter;
+use crate::reporter::event::{
+    ConsistencyResult, Freshness, Message, SubcommandResult, VerifyResult,
+};
+use crate::reporter::ui::manifest_location::msrv_line;
+use crate::Event;
+use std::io::{self, Stdout};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use storyteller::EventHandler;
+
+pub struct GitLabHandler<W: SendWriter> {
+    writer: Mutex<W>,
+    /// The Cargo manifest in which the MSRV is declared, as given on the command line.
+    manifest: PathBuf,
+    issues: Mutex<Vec<Issue>>,
+}
+
+impl GitLabHandler<Stdout> {
+    pub fn stdout(manifest: PathBuf) -> Self {
+        Self::new(io::stdout(), manifest)
+    }
+}
+
+impl<W: SendWriter> GitLabHandler<W> {
+    pub fn new(writer: W, manifest: PathBuf) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            manifest,
+            issues: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn manifest_location(&self) -> Location {
+        Location::new(self.manifest.to_string_lossy(), msrv_line(&self.manifest))
+    }
+
+    fn verify_issues(&self, result: &VerifyResult) -> Vec<Issue> {
+        let version = result.toolchain().version();
+
+        if let Some(details) = result.release_details() {
+            if !result.is_verified() {
+                return vec![Issue::new(
+                    "cargo-msrv/release",
+                    format!(
+                        "The {} release raises the MSRV from Rust {} to Rust {}, which the MSRV policy does not allow",
+                        details.release_type, version, details.msrv
+                    ),
+                    Severity::Major,
+                    self.manifest_location(),
+                )];
+            }
+
+            return Vec::new();
+        }
+
+        if !result.is_compatible() {
+            let mut description = format!("The crate is not compatible with Rust {}", version);
+            if let Some(error) = result.result.first_error() {
+                description.push_str(&format!(": {}", error));
+            }
+
+            return vec![Issue::new(
+                "cargo-msrv/verify",
+                description,
+                Severity::Major,
+                self.manifest_location(),
+            )];
+        }
+
+        match result.freshness_details() {
+            Some(details)
+                if details.tolerance.is_some() && details.freshness == Freshness::TooHigh =>
+            {
+                vec![Issue::new(
+                    "cargo-msrv/freshness",
+                    format!(
+                        "The declared MSRV, Rust {}, is {} minor releases higher than the MSRV, Rust {}",
+                        version, details.slack, details.msrv
+                    ),
+                    Severity::Minor,
+                    self.manifest_location(),
+                )]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn consistency_issues(result: &ConsistencyResult) -> Vec<Issue> {
+    let conflicts = result.conflicts().iter().map(|conflict| {
+        Issue::new(
+            "cargo-msrv/consistency",
+            format!(
+                "The {} declares Rust {}, but the MSRV is Rust {}",
+                conflict.declaration.kind, conflict.declaration.version, conflict.expected
+            ),
+            Severity::Major,
+            Location::new(
+                conflict.declaration.file.as_str(),
+                conflict.declaration.line,
+            ),
+        )
+    });
+
+    let outdated_pins = result.outdated_pins().iter().map(|pin| {
+        Issue::new(
+            "cargo-msrv/outdated-pin",
+            format!(
+                "The CI job is pinned to Rust {}, which is older than the MSRV",
+                pin.version
+            ),
+            Severity::Info,
+            Location::new(pin.file.as_str(), pin.line),
+        )
+    });
+
+    conflicts.chain(outdated_pins).collect()
+}
+
+impl<W: SendWriter> EventHandler for GitLabHandler<W> {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        let issues = match event.message() {
+            Message::SubcommandResult(SubcommandResult::Verify(it)) => self.verify_issues(it),
+            Message::SubcommandResult(SubcommandResult::CheckConsistency(it)) => {
+                consistency_issues(it)
+            }
+            _ => return,
+        };
+
+        if let Ok(mut collected) = self.issues.lock() {
+            collected.extend(issues);
+        }
+    }
+
+    fn finish(&self) {
+        let issues = self
+            .issues
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Ok(mut writer) = self.writer.lock() else {
+            return;
+        };
+
+        // An empty report still replaces the issues of an earlier pipeline
+        if let Ok(report) = serde_json::to_string_pretty(&*issues) {
+            let _ = writeln!(writer, "{}", report);
+        }
+    }
+}
+
+/// An issue of the code quality report.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct Issue {
+    description: String,
+    check_name: &'static str,
+    /// Identifies the issue across pipelines, so GitLab can tell new issues from resolved ones.
+    fingerprint: String,
+    severity: Severity,
+    location: Location,
+}
+
+impl Issue {
+    fn new(
+        check_name: &'static str,
+        description: String,
+        severity: Severity,
+        location: Location,
+    ) -> Self {
+        let fingerprint = fingerprint(&[
+            check_name,
+            &location.path,
+            &location.lines.begin.to_string(),
+            &description,
+        ]);
+
+        Self {
+            description,
+            check_name,
+            fingerprint,
+            severity,
+            location,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Severity {
+    Info,
+    Minor,
+    Major,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct Location {
+    path: String,
+    lines: Lines,
+}
+
+impl Location {
+    fn new(path: impl Into<String>, line: usize) -> Self {
+        Self {
+            path: path.into(),
+            lines: Lines { begin: line },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+struct Lines {
+    begin: usize,
+}
+
+/// A 64-bit FNV-1a hash of the parts, as hex. Unlike the hasher of the standard library, it is
+/// stable across Rust versions, so the fingerprint of an issue doesn't change with the toolchain
+/// with which cargo-msrv was built.
+fn fingerprint(parts: &[&str]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = parts
+        .iter()
+        .flat_map(|part| part.bytes().chain([0]))
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        });
+
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::declaration::{conflicts, Declaration, DeclarationKind};
+    use crate::rust::Toolchain;
+    use crate::semver;
+    use camino::Utf8PathBuf;
+
+    fn report(events: Vec<Event>, manifest: &str) -> serde_json::Value {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = dir.path().join("Cargo.toml");
+        std::fs::write(&path, manifest).unwrap();
+
+        let handler = GitLabHandler::new(Vec::new(), path);
+        for event in events {
+            handler.handle(event);
+        }
+        handler.finish();
+
+        let written = handler.writer.into_inner().unwrap();
+        serde_json::from_slice(&written).unwrap()
+    }
+
+    #[test]
+    fn failed_verification() {
+        let toolchain = Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]);
+        let event = VerifyResult::incompatible(toolchain, Some("error[E0658]: unstable".into()));
+
+        let report = report(
+            vec![event.into()],
+            "[package]\nname = \"a\"\nrust-version = \"1.60\"\n",
+        );
+
+        assert_eq!(report[0]["check_name"], "cargo-msrv/verify");
+        assert_eq!(report[0]["severity"], "major");
+        assert_eq!(
+            report[0]["description"],
+            "The crate is not compatible with Rust 1.60.0: error[E0658]: unstable"
+        );
+        assert_eq!(report[0]["location"]["lines"]["begin"], 3);
+    }
+
+    #[test]
+    fn conflicting_declaration() {
+        let declarations = [
+            (DeclarationKind::RustVersion, "Cargo.toml", "1.60"),
+            (DeclarationKind::ClippyMsrv, "clippy.toml", "1.58"),
+        ]
+        .into_iter()
+        .map(|(kind, file, version)| Declaration {
+            kind,
+            file: Utf8PathBuf::from(file),
+            line: 1,
+            version: version.parse().unwrap(),
+        })
+        .collect::<Vec<_>>();
+        let conflicts = conflicts(&declarations);
+        let event = ConsistencyResult::new(declarations, conflicts);
+
+        let report = report(vec![event.into()], "");
+
+        assert_eq!(report.as_array().unwrap().len(), 1);
+        assert_eq!(report[0]["check_name"], "cargo-msrv/consistency");
+        assert_eq!(report[0]["location"]["path"], "clippy.toml");
+    }
+
+    #[test]
+    fn empty_report() {
+        let toolchain = Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]);
+        let report = report(vec![VerifyResult::compatible(toolchain).into()], "");
+
+        assert_eq!(report, serde_json::json!([]));
+    }
+
+    #[test]
+    fn stable_fingerprint() {
+        assert_eq!(fingerprint(&["a", "b"]), fingerprint(&["a", "b"]));
+        assert_ne!(fingerprint(&["ab", ""]), fingerprint(&["a", "b"]));
+    }
+}
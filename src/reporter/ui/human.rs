@@ -1,16 +1,42 @@
+use crate::context::LockfileStrategy;
 use crate::reporter::event::{
-    CheckResult, CheckToolchain, FindResult, Message, Meta, SubcommandInit, SubcommandResult,
+    AuxiliaryOutputItem, BisectLockfileResult, CheckResult, CheckToolchain, CompilationProgress,
+    DownloadProgress, ErrorDiff, EstimateResult, FeatureSetResult, FindResult, Freshness,
+    FreshnessDetails, FuzzProjectResult, IncludedCrate, MergeResult, Message, Meta, PartialResult,
+    ReleaseDetails, SearchControl, SearchTarget, SubcommandInit, SubcommandResult, VerifyResult,
+    WhatIfResult, WhatIfUpdateResult, WhatIfVerification,
 };
+use crate::reporter::formatting::{
+    ascii_fallback, color_fallback, ellipsis, format_size, is_ascii,
+};
+use crate::reporter::i18n::tr;
+use crate::reporter::ui::human_layout::{features_label, CheckRow, HumanLayout};
+use crate::reporter::ui::theme::Theme;
 use crate::{semver, table_settings, Event};
 use owo_colors::OwoColorize;
 use std::fmt::Display;
+use std::io::Write;
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use storyteller::EventHandler;
 
 pub struct HumanProgressHandler {
     pb: indicatif::ProgressBar,
     sequence_number: AtomicU32,
+    /// The checked Rust versions which were incompatible, with their first error, to be
+    /// summarized at the end of the run.
+    failures: Mutex<Vec<(semver::Version, Option<String>)>>,
+    /// The message of the spinner while a progress bar, of a download or compilation, is shown
+    /// instead; to be restored once the download or compilation has finished.
+    message_before_progress: Mutex<Option<String>>,
+    /// The configured layout of the checks, which replaces the default header and status lines
+    /// with a single line per check.
+    layout: Option<HumanLayout>,
+    /// When the running check started, for the `duration` column of the layout.
+    check_started: Mutex<Option<Instant>>,
+    /// The features of the running check, for the `features` column of the layout.
+    features: Mutex<Option<String>>,
 }
 
 impl Default for HumanProgressHandler {
@@ -20,11 +46,71 @@ impl Default for HumanProgressHandler {
         Self {
             pb: mp,
             sequence_number: AtomicU32::new(1),
+            failures: Mutex::new(Vec::new()),
+            message_before_progress: Mutex::new(None),
+            layout: None,
+            check_started: Mutex::new(None),
+            features: Mutex::new(None),
         }
     }
 }
 
 impl HumanProgressHandler {
+    /// Print a line per check, with the columns of the given layout.
+    pub fn with_layout(layout: HumanLayout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..Self::default()
+        }
+    }
+
+    /// Print the status of a finished check: with the columns of the configured layout, if any, or
+    /// otherwise as the given status line.
+    fn print_check_status(&self, result: &CheckResult, status: String) {
+        let Some(layout) = &self.layout else {
+            self.println(status);
+            return;
+        };
+
+        let duration = self
+            .check_started
+            .lock()
+            .ok()
+            .and_then(|started| started.map(|started| started.elapsed()));
+        let features = self
+            .features
+            .lock()
+            .ok()
+            .and_then(|features| features.clone());
+
+        let row = CheckRow {
+            compatible: result.is_compatible(),
+            toolchain: result.toolchain(),
+            duration,
+            features: features.as_deref(),
+        };
+
+        self.println(layout.format_row(&row));
+    }
+
+    fn println(&self, message: impl Display) {
+        let message = message.to_string();
+        let message = ascii_fallback(&message);
+        self.pb.println(color_fallback(&message));
+    }
+
+    /// Print to stdout, also when it is not a terminal, unlike the lines printed with the progress
+    /// bar; for output which is meant to be redirected, like the report of `cargo msrv report`.
+    fn print_to_stdout(&self, message: impl Display) {
+        let message = message.to_string();
+        let message = ascii_fallback(&message);
+
+        self.pb.suspend(|| {
+            // A closed stdout, like one piped to `head`, should not fail the run
+            let _ = writeln!(std::io::stdout().lock(), "{}", color_fallback(&message));
+        });
+    }
+
     fn start_runner_progress(&self, version: &semver::Version) {
         self.sequence_number.fetch_add(1, Ordering::SeqCst);
         self.pb.reset();
@@ -35,17 +121,104 @@ impl HumanProgressHandler {
         self.pb.finish_and_clear();
     }
 
+    /// Show the download of a component as a progress bar, in place of the spinner.
+    fn show_download_progress(&self, progress: &DownloadProgress) {
+        self.show_progress_bar(Self::download_style());
+
+        self.pb.set_length(progress.total_bytes());
+        self.pb.set_position(progress.downloaded_bytes());
+        let message = progress
+            .component()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| tr!("check-downloading"));
+        self.pb.set_message(ascii_fallback(&message).into_owned());
+    }
+
+    /// Show the compiled units as a progress bar, in place of the spinner, if the number of units
+    /// to compile is known.
+    fn show_compilation_progress(&self, progress: &CompilationProgress) {
+        let Some(total) = progress.total() else {
+            return;
+        };
+
+        self.show_progress_bar(Self::compilation_style());
+
+        self.pb.set_length(total);
+        self.pb.set_position(progress.compiled());
+        self.pb
+            .set_message(ascii_fallback(progress.unit()).into_owned());
+    }
+
+    fn show_progress_bar(&self, style: indicatif::ProgressStyle) {
+        if let Ok(mut message) = self.message_before_progress.lock() {
+            if message.is_none() {
+                *message = Some(self.pb.message());
+            }
+        }
+
+        self.pb.set_style(style);
+    }
+
+    fn finish_progress_bar(&self) {
+        let message = self
+            .message_before_progress
+            .lock()
+            .ok()
+            .and_then(|mut message| message.take());
+
+        if let Some(message) = message {
+            self.pb.set_style(Self::spinner_style());
+            self.pb.set_message(message);
+        }
+    }
+
     fn styled_progress_bar() -> indicatif::ProgressBar {
         let pb = indicatif::ProgressBar::new_spinner();
-        pb.set_style(
-            indicatif::ProgressStyle::default_spinner()
-                .template("{spinner} {msg:<16} Elapsed {elapsed}")
-                .unwrap()
-                .tick_chars("◜◠◝◞◡◟"),
-        );
+        pb.set_style(Self::spinner_style());
         pb.finish_and_clear(); // Hide the spinner on startup
         pb
     }
+
+    fn spinner_style() -> indicatif::ProgressStyle {
+        let template = format!(
+            "{{spinner}} {{msg:<16}} {} {{elapsed}}",
+            tr!("check-elapsed")
+        );
+
+        indicatif::ProgressStyle::default_spinner()
+            .template(&template)
+            .unwrap()
+            .tick_chars(Self::tick_chars())
+    }
+
+    fn tick_chars() -> &'static str {
+        if is_ascii() {
+            "-\\|/-"
+        } else {
+            "◜◠◝◞◡◟"
+        }
+    }
+
+    fn download_style() -> indicatif::ProgressStyle {
+        indicatif::ProgressStyle::default_bar()
+            .template("{spinner} {msg:<16} [{bar:30}] {bytes}/{total_bytes} ({bytes_per_sec})")
+            .unwrap()
+            .tick_chars(Self::tick_chars())
+            .progress_chars("=> ")
+    }
+
+    fn compilation_style() -> indicatif::ProgressStyle {
+        let template = format!(
+            "{{spinner}} {{msg:<16}} [{{bar:30}}] {{pos}}/{{len}} {} {{elapsed}}",
+            tr!("check-elapsed")
+        );
+
+        indicatif::ProgressStyle::default_bar()
+            .template(&template)
+            .unwrap()
+            .tick_chars(Self::tick_chars())
+            .progress_chars("=> ")
+    }
 }
 
 impl EventHandler for HumanProgressHandler {
@@ -56,42 +229,194 @@ impl EventHandler for HumanProgressHandler {
         match event.message() {
             Message::Meta(it) => {
                 let message = it.format_human();
-                self.pb.println(message);
+                self.println(message);
             }
             Message::SubcommandInit(it) if it.should_enable_spinner() => {
                 self.pb.reset(); // We'll reset here to ensure the steady tick call below works
                 self.pb.enable_steady_tick(Duration::from_millis(150));
             }
             Message::UnableToConfirmValidReleaseVersion(_) => {
-                let message = Status::info("Unable to verify if provided version is an existing Rust release version");
-                self.pb.println(message);
+                let message = Status::info(tr!("check-unable-to-confirm-release"));
+                self.println(message);
+            }
+            Message::ReducedSearchSpace(it) => {
+                let message = Status::info(tr!(
+                    "reduced-search-space",
+                    version = it.version(),
+                    reason = it.reason(),
+                    location = it.location(),
+                ));
+                self.println(message);
+            }
+            Message::Unlocks(it) => {
+                for unlock in it.unlocks() {
+                    self.println(Status::info(unlock));
+                }
+            }
+            Message::ChangelogEntry(it) => {
+                let message = if it.is_dry_run() {
+                    tr!(
+                        "changelog-would-add",
+                        section = it.section(),
+                        path = it.path(),
+                        entry = it.entry(),
+                    )
+                } else {
+                    tr!(
+                        "changelog-added",
+                        section = it.section(),
+                        path = it.path(),
+                        entry = it.entry(),
+                    )
+                };
+                let message = Status::info(message);
+                self.println(message);
+            }
+            Message::CheckLockfileRevision(it) => {
+                let message = Status::info(tr!("lockfile-revision", revision = it.revision()));
+                self.println(message);
+            }
+            Message::LockfileIncompatible(it) => {
+                let handling = match it.strategy() {
+                    None => tr!("lockfile-as-is"),
+                    Some(LockfileStrategy::Regenerate) => tr!("lockfile-regenerate"),
+                    Some(LockfileStrategy::Downgrade) => tr!("lockfile-downgrade"),
+                    Some(LockfileStrategy::Fail) => tr!("lockfile-fail"),
+                };
+                let message = Status::info(tr!(
+                    "lockfile-incompatible",
+                    version = it.toolchain().version(),
+                    lockfile = it.lockfile_version(),
+                    readable = it.readable_since(),
+                    handling = handling,
+                ));
+                self.println(message);
             }
             Message::CheckToolchain(it) if event.is_scope_start() => {
-                self.pb.println(it.header(self.sequence_number.load(Ordering::SeqCst)));
+                if self.layout.is_none() {
+                    self.println(it.header(self.sequence_number.load(Ordering::SeqCst)));
+                }
+                if let Ok(mut started) = self.check_started.lock() {
+                    *started = Some(Instant::now());
+                }
                 self.start_runner_progress(it.toolchain.version());
             }
             Message::CheckToolchain(_it) /* is scope end */ => {
+                self.finish_progress_bar();
                 self.finish_runner_progress();
             }
+            Message::CompilationProgress(it) => {
+                self.show_compilation_progress(it);
+            }
+            Message::DownloadProgress(it) => {
+                self.show_download_progress(it);
+            }
+            Message::SetupToolchain(_it) if !event.is_scope_start() => {
+                self.finish_progress_bar();
+            }
             // Message::Compatibility(CheckResult {  compatibility_report: CompatibilityReport::Compatible, toolchain, .. }) => {
-            Message::CheckResult(CheckResult {  compatibility }) if compatibility.is_compatible() => {
-                let message = Status::ok("Is compatible");
-                self.pb.println(message);
+            Message::CheckMethod(it) => {
+                if let Ok(mut features) = self.features.lock() {
+                    *features = features_label(it.method().args());
+                }
             }
-            Message::CheckResult(CheckResult { compatibility }) if !compatibility.is_compatible() => {
-                let message = Status::fail("Is incompatible");
-                self.pb.println(message);
+            Message::CheckResult(result @ CheckResult {  compatibility, .. }) if compatibility.is_compatible() => {
+                self.print_check_status(result, Status::ok(tr!("check-compatible")));
+            }
+            Message::CheckResult(result @ CheckResult { compatibility, stabilization_hint, failed_targets }) if !compatibility.is_compatible() => {
+                self.print_check_status(result, Status::fail(tr!("check-incompatible")));
 
                 if let Some(error_report) = compatibility.error() {
-                    self.pb.println(message_box(error_report));
+                    self.println(message_box(error_report));
+                }
+
+                if !failed_targets.is_empty() {
+                    let targets = failed_targets.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    let message = Status::info(tr!(
+                        "check-failed-targets",
+                        targets = targets.join(", "),
+                    ));
+                    self.println(message);
+                }
+
+                if let Some(hint) = stabilization_hint {
+                    self.println(Status::info(hint));
+                }
+
+                if let Ok(mut failures) = self.failures.lock() {
+                    failures.push((
+                        compatibility.toolchain().version().clone(),
+                        compatibility.first_error().map(ToString::to_string),
+                    ));
                 }
             }
-            Message::SubcommandResult(result) => self.handle_subcommand_result(result),
+            Message::SearchControl(it) => {
+                self.handle_search_control(it);
+            }
+            Message::IncludedCrate(it) => {
+                self.start_included_crate(it);
+            }
+            Message::SearchTarget(it) => {
+                self.start_search_target(it);
+            }
+            Message::Warning(it) => {
+                let lead = "WARN".color(Theme::current().info());
+                self.println(Status::with_lead(lead, it.message()));
+            }
+            Message::AuxiliaryOutput(it) => match it.item() {
+                AuxiliaryOutputItem::RunArtifacts { run_id } => {
+                    let message = Status::info(tr!(
+                        "saved-run",
+                        run = run_id,
+                        path = it.destination().path(),
+                    ));
+                    self.println(message);
+                }
+                AuxiliaryOutputItem::SearchState => {
+                    let message = Status::info(tr!("saved-state", path = it.destination().path()));
+                    self.println(message);
+                }
+                AuxiliaryOutputItem::MatrixState => {
+                    let message = Status::info(tr!(
+                        "saved-matrix-state",
+                        path = it.destination().path(),
+                    ));
+                    self.println(message);
+                }
+                AuxiliaryOutputItem::Declaration { kind, line } => {
+                    let message = Status::info(tr!(
+                        "updated-declaration",
+                        kind = kind,
+                        path = it.destination().path(),
+                        line = line,
+                    ));
+                    self.println(message);
+                }
+                _ => {}
+            },
+            Message::FeatureSetResult(it) => {
+                self.println(it.format_human());
+            }
+            Message::FuzzProjectResult(it) => {
+                self.println(it.format_human());
+            }
+            Message::SubcommandResult(result) => {
+                self.handle_subcommand_result(result);
+
+                // The checks of a bisection of the lockfile are all of the same toolchain
+                if !matches!(result, SubcommandResult::BisectLockfile(_)) {
+                    self.print_failure_summary();
+                }
+            }
+            Message::PartialResult(it) => {
+                self.handle_partial_result(it);
+                self.print_failure_summary();
+            }
             Message::TerminateWithFailure(termination) if termination.should_highlight() => {
-                self.pb.println(format!("\n\n{}", termination.as_message().red()));
+                self.println(format!("\n\n{}", termination.as_message().red()));
             }
             Message::TerminateWithFailure(termination) if !termination.should_highlight() => {
-                self.pb.println(format!("\n\n{}", termination.as_message().dimmed().bold()));
+                self.println(format!("\n\n{}", termination.as_message().dimmed().bold()));
             }
             _ => {}
         };
@@ -99,57 +424,729 @@ impl EventHandler for HumanProgressHandler {
 }
 
 impl HumanProgressHandler {
+    /// Each included crate has its own results, so its checks are numbered and summarized anew.
+    fn start_included_crate(&self, included: &IncludedCrate) {
+        self.sequence_number.store(1, Ordering::SeqCst);
+        if let Ok(mut failures) = self.failures.lock() {
+            failures.clear();
+        }
+
+        let name = included
+            .name()
+            .map(String::from)
+            .unwrap_or_else(|| tr!("included-crate-unnamed"));
+        let message = Status::with_lead(
+            "Crate".color(Theme::current().info()),
+            format_args!("{} ({})", name, included.manifest_path()),
+        );
+        self.println(format!("\n{}", message));
+    }
+
+    /// Each target has its own search, so its checks are numbered and summarized anew.
+    fn start_search_target(&self, target: &SearchTarget) {
+        self.sequence_number.store(1, Ordering::SeqCst);
+        if let Ok(mut failures) = self.failures.lock() {
+            failures.clear();
+        }
+
+        let message = Status::with_lead("Target".color(Theme::current().info()), target.target());
+        self.println(format!("\n{}", message));
+    }
+
+    fn handle_search_control(&self, control: &SearchControl) {
+        let message = match control {
+            SearchControl::Paused => Status::with_lead(
+                "Paused".color(Theme::current().info()),
+                tr!("search-paused"),
+            ),
+            SearchControl::Resumed => Status::info(tr!("search-resumed")),
+            SearchControl::Skipped { version } => {
+                // A skipped check is inconclusive, even if it did fail
+                if let Ok(mut failures) = self.failures.lock() {
+                    failures.retain(|(failed, _)| failed != version);
+                }
+
+                Status::with_lead(
+                    "Skipped".color(Theme::current().info()),
+                    tr!("search-skipped", version = version),
+                )
+            }
+            SearchControl::Aborted => Status::fail(tr!("search-aborted")),
+        };
+
+        self.println(message);
+    }
+
+    fn handle_partial_result(&self, partial: &PartialResult) {
+        let bounds = match partial.upper_bound() {
+            Some(upper) => tr!(
+                "partial-bounded",
+                lower = partial.lower_bound(),
+                upper = upper
+            ),
+            None => tr!("partial-unbounded", lower = partial.lower_bound()),
+        };
+
+        let message = Status::with_lead("Partial".color(Theme::current().info()), bounds);
+        self.println(format!("\n{}", message));
+    }
+
+    /// Print each incompatible Rust version with its first error, so the pattern of failures can
+    /// be seen at a glance.
+    fn print_failure_summary(&self) {
+        let Ok(failures) = self.failures.lock() else {
+            return;
+        };
+
+        if failures.is_empty() {
+            return;
+        }
+
+        let mut lines = vec![Status::with_lead(
+            "Summary".color(Theme::current().info()),
+            tr!("failure-summary"),
+        )];
+
+        lines.extend(failures.iter().map(|(version, error)| {
+            let error = error
+                .clone()
+                .unwrap_or_else(|| tr!("failure-no-error-output"));
+
+            Status::fail(format_args!("Rust {}: {}", version, error))
+        }));
+
+        self.println(lines.join("\n"));
+    }
+
     fn handle_subcommand_result(&self, result: &SubcommandResult) {
         match result {
+            SubcommandResult::BisectLockfile(inner) => {
+                self.println(inner.summary());
+            }
+            SubcommandResult::CacheStats(inner) => {
+                if !inner.is_empty() {
+                    self.println(inner.to_string());
+                }
+
+                let message = tr!(
+                    "cache-usage",
+                    size = format_size(inner.bytes()),
+                    max = format_size(inner.max_size()),
+                    path = inner.path(),
+                );
+                self.println(Status::with_lead(
+                    "Cache".color(Theme::current().success()),
+                    message,
+                ));
+            }
+            SubcommandResult::CheckConsistency(inner) => match inner.msrv() {
+                None => {
+                    self.println(Status::info(tr!("consistency-undeclared")));
+                }
+                Some(msrv) if inner.is_consistent() => {
+                    let message = Status::with_lead(
+                        "Consistent".color(Theme::current().success()),
+                        tr!(
+                            "consistency-consistent",
+                            count = inner.declarations().len(),
+                            msrv = msrv,
+                        ),
+                    );
+                    self.println(message);
+                }
+                Some(msrv) => {
+                    self.println(inner.to_string());
+
+                    let message = Status::with_lead(
+                        "Inconsistent".color(Theme::current().failure()),
+                        tr!(
+                            "consistency-inconsistent",
+                            conflicts = inner.conflicts().len(),
+                            count = inner.declarations().len(),
+                            msrv = msrv,
+                        ),
+                    );
+                    self.println(message);
+                }
+            },
+            SubcommandResult::Clean(inner) if inner.removed().is_empty() => {
+                let message = Status::with_lead(
+                    "Clean".color(Theme::current().success()),
+                    tr!("clean-nothing"),
+                );
+                self.println(message);
+            }
+            SubcommandResult::Clean(inner) => {
+                self.println(inner.to_string());
+
+                let message = if inner.is_dry_run() {
+                    tr!("clean-would-remove", size = format_size(inner.bytes()))
+                } else {
+                    tr!("clean-removed", size = format_size(inner.bytes()))
+                };
+                self.println(Status::with_lead(
+                    "Clean".color(Theme::current().success()),
+                    message,
+                ));
+            }
+            SubcommandResult::Estimate(inner) => {
+                self.println(inner.summary());
+            }
             SubcommandResult::Find(inner) => {
-                self.pb.println(format!("\n{}\n", inner.summary()));
+                self.println(format!("\n{}\n", inner.summary()));
+
+                if let Some(error_diff) = inner.error_diff() {
+                    self.println(error_diff.summary());
+                }
+            }
+            SubcommandResult::FindTargets(inner) => {
+                self.println(format!("\n{}", inner));
+
+                let message = match inner.msrv() {
+                    Some(msrv) => Status::with_lead(
+                        "Finished".color(Theme::current().success()),
+                        tr!(
+                            "targets-msrv",
+                            count = inner.targets().len(),
+                            msrv = msrv,
+                            targets = inner.limiting_targets().collect::<Vec<_>>().join(", "),
+                        ),
+                    ),
+                    None => Status::fail(tr!("targets-no-msrv")),
+                };
+                self.println(message);
+            }
+            SubcommandResult::Graph(inner) => {
+                self.println(inner.graph());
+            }
+            SubcommandResult::Images(inner) => {
+                self.println(inner.to_string());
+            }
+            SubcommandResult::Inspect(inner) if inner.has_no_runs() => {
+                let message = Status::info(tr!("inspect-no-runs"));
+                self.println(message);
+            }
+            SubcommandResult::Inspect(inner) => {
+                self.println(inner.to_string());
             }
             SubcommandResult::List(inner) => {
-                self.pb.println(inner.to_string());
+                self.println(inner.to_string());
+            }
+            SubcommandResult::MergeResults(inner) => {
+                self.println(inner.summary());
+            }
+            SubcommandResult::Migrate(inner) if inner.migrated().is_empty() => {
+                let message = if inner.skipped().is_empty() {
+                    tr!("migrate-none-declared")
+                } else {
+                    tr!("migrate-none-migratable", count = inner.skipped().len())
+                };
+                self.println(Status::with_lead(
+                    "Migrate".color(Theme::current().success()),
+                    message,
+                ));
+            }
+            SubcommandResult::Migrate(inner) => {
+                self.println(inner.to_string());
+
+                let message = if inner.is_dry_run() {
+                    tr!("migrate-would-migrate", count = inner.migrated().len())
+                } else {
+                    tr!("migrate-migrated", count = inner.migrated().len())
+                };
+                self.println(Status::with_lead(
+                    "Migrate".color(Theme::current().success()),
+                    message,
+                ));
+            }
+            SubcommandResult::Outdated(inner) if inner.dependencies().is_empty() => {
+                let message = Status::with_lead(
+                    "Outdated".color(Theme::current().success()),
+                    tr!("outdated-none", msrv = inner.msrv()),
+                );
+                self.println(message);
+            }
+            SubcommandResult::Outdated(inner) => {
+                self.println(inner.to_string());
+            }
+            SubcommandResult::Report(inner) => {
+                self.print_to_stdout(inner.report());
             }
             SubcommandResult::Set(inner) => {
                 let message = Status::with_lead(
-                    "Set".bright_green(),
+                    "Set".color(Theme::current().success()),
                     format_args!("Rust {}", inner.version()),
                 );
-                self.pb.println(message);
+                self.println(message);
             }
             SubcommandResult::Show(inner) => {
                 let message = Status::with_lead(
-                    "Show".bright_green(),
-                    format_args!("MSRV is Rust {}", inner.version()),
+                    "Show".color(Theme::current().success()),
+                    tr!("show-msrv", version = inner.version()),
                 );
-                self.pb.println(message);
+                self.println(message);
+
+                if let Some(source) = inner.source() {
+                    self.println(Status::info(tr!(
+                        "show-declared-as",
+                        kind = source.kind,
+                        path = source.path,
+                    )));
+                }
+
+                let msrv = inner.version().to_semver_version();
+
+                for dependency in inner.path_dependencies() {
+                    let message = match &dependency.rust_version {
+                        Some(rust_version) if rust_version > &msrv => Status::with_lead(
+                            "Show".color(Theme::current().failure()),
+                            tr!(
+                                "show-path-dependency-newer",
+                                name = dependency.name,
+                                version = dependency.version,
+                                requires = rust_version,
+                                path = dependency.manifest_path,
+                            ),
+                        ),
+                        Some(rust_version) => Status::info(tr!(
+                            "show-path-dependency",
+                            name = dependency.name,
+                            version = dependency.version,
+                            requires = rust_version,
+                            path = dependency.manifest_path,
+                        )),
+                        None => Status::info(tr!(
+                            "show-path-dependency-unspecified",
+                            name = dependency.name,
+                            version = dependency.version,
+                            path = dependency.manifest_path,
+                        )),
+                    };
+                    self.println(message);
+                }
             }
-            SubcommandResult::Verify(_inner) => {
-                // tbd.
+            SubcommandResult::ShowWorkspace(inner) => {
+                self.println(inner.to_string());
+
+                if let Some(rust_version) = inner.rust_version() {
+                    self.println(Status::info(tr!("show-workspace", version = rust_version)));
+                }
+            }
+            SubcommandResult::Sync(inner) if inner.changes().is_empty() => {
+                let message = Status::with_lead(
+                    "Sync".color(Theme::current().success()),
+                    tr!("sync-consistent", msrv = inner.msrv()),
+                );
+                self.println(message);
+            }
+            SubcommandResult::Sync(inner) => {
+                self.println(inner.to_string());
+
+                let message = if inner.is_dry_run() {
+                    tr!(
+                        "sync-would-update",
+                        count = inner.changes().len(),
+                        msrv = inner.msrv(),
+                    )
+                } else {
+                    tr!(
+                        "sync-updated",
+                        count = inner.changes().len(),
+                        msrv = inner.msrv(),
+                    )
+                };
+                self.println(Status::with_lead(
+                    "Sync".color(Theme::current().success()),
+                    message,
+                ));
+            }
+            SubcommandResult::Verify(inner) => {
+                if let Some(details) = inner.freshness_details() {
+                    self.println(inner.freshness_summary(details));
+                }
+                if let Some(details) = inner.release_details() {
+                    self.println(inner.release_summary(details));
+                }
+            }
+            SubcommandResult::WhatIf(inner) => {
+                self.println(inner.summary());
+            }
+            SubcommandResult::WhatIfUpdate(inner) => {
+                self.println(inner.summary());
             }
         }
     }
 }
 
+impl VerifyResult {
+    fn freshness_summary(&self, details: &FreshnessDetails) -> String {
+        let version = self.toolchain().version();
+
+        let Some(tolerance) = details.tolerance else {
+            return slack_summary(version, details);
+        };
+
+        match details.freshness {
+            Freshness::Fresh => Status::ok(tr!(
+                "verify-fresh",
+                version = version,
+                tolerance = tolerance,
+                msrv = details.msrv,
+            )),
+            Freshness::TooLow => Status::fail(tr!(
+                "verify-too-low",
+                version = version,
+                msrv = details.msrv,
+            )),
+            Freshness::TooHigh => Status::fail(tr!(
+                "verify-too-high",
+                version = version,
+                msrv = details.msrv,
+                tolerance = tolerance,
+            )),
+        }
+    }
+}
+
+impl VerifyResult {
+    fn release_summary(&self, details: &ReleaseDetails) -> String {
+        let version = self.toolchain().version();
+
+        match (details.raises_msrv, details.bump_allowed) {
+            (false, _) => Status::ok(tr!(
+                "verify-release-keeps",
+                release = details.release_type,
+                version = version,
+            )),
+            (true, true) => Status::ok(tr!(
+                "verify-release-allowed",
+                release = details.release_type,
+                version = version,
+                msrv = details.msrv,
+            )),
+            (true, false) => Status::fail(tr!(
+                "verify-release-not-allowed",
+                release = details.release_type,
+                version = version,
+                msrv = details.msrv,
+            )),
+        }
+    }
+}
+
+/// The slack of the Rust version, and the lower MSRV which could be declared, for `verify --slack`.
+fn slack_summary(version: &semver::Version, details: &FreshnessDetails) -> String {
+    if details.freshness == Freshness::TooLow {
+        return Status::fail(tr!(
+            "verify-too-low",
+            version = version,
+            msrv = details.msrv,
+        ));
+    }
+
+    match details.suggested_msrv() {
+        Some(msrv) => Status::with_lead(
+            "Slack".color(Theme::current().info()),
+            tr!(
+                "verify-slack",
+                msrv = msrv,
+                slack = details.slack,
+                version = version,
+            ),
+        ),
+        None => Status::ok(tr!("verify-no-slack", version = version)),
+    }
+}
+
+impl EstimateResult {
+    fn summary(&self) -> String {
+        let mut lines = self
+            .findings()
+            .iter()
+            .map(|finding| {
+                Status::info(format_args!(
+                    "Rust {}: {} ({})",
+                    finding.version, finding.reason, finding.location
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(version) = self.cargo_version() {
+            lines.push(Status::with_lead(
+                "Cargo".color(Theme::current().info()),
+                tr!("estimate-cargo", version = version),
+            ));
+        }
+
+        let message = match self.version() {
+            Some(version) => tr!("estimate-msrv", version = version),
+            None => tr!("estimate-none"),
+        };
+
+        lines.push(Status::with_lead(
+            "Estimate".color(Theme::current().info()),
+            message,
+        ));
+        lines.join("\n")
+    }
+}
+
+impl MergeResult {
+    fn summary(&self) -> String {
+        match self.msrv() {
+            Some(version) => Status::with_lead(
+                "Merged".color(Theme::current().success()),
+                tr!("merge-msrv", version = version, shards = self.shards()),
+            ),
+            None => Status::with_lead(
+                "Merged".color(Theme::current().failure()),
+                tr!("merge-no-msrv", shards = self.shards()),
+            ),
+        }
+    }
+}
+
+impl BisectLockfileResult {
+    fn summary(&self) -> String {
+        let mut lines = vec![Status::with_lead(
+            "Bisected".color(Theme::current().success()),
+            tr!(
+                "bisect-first-fails",
+                version = self.toolchain(),
+                revision = self.first_incompatible(),
+                checks = self.checks(),
+            ),
+        )];
+
+        lines.push(Status::info(tr!(
+            "bisect-last-compatible",
+            revision = self.last_compatible(),
+        )));
+
+        lines.extend(self.changes().iter().map(Status::info));
+
+        lines.join("\n")
+    }
+}
+
+impl WhatIfResult {
+    fn summary(&self) -> String {
+        let dependency = self.dependency();
+        let added = format!("{} {}", dependency.name, dependency.version);
+
+        let mut lines = vec![
+            match (self.raises_msrv(), self.msrv_before(), self.msrv_after()) {
+                (true, Some(before), Some(after)) => Status::with_lead(
+                    "What if".color(Theme::current().failure()),
+                    tr!(
+                        "whatif-add-raises",
+                        dependency = added,
+                        before = before,
+                        after = after,
+                    ),
+                ),
+                (true, None, Some(after)) => Status::with_lead(
+                    "What if".color(Theme::current().failure()),
+                    tr!("whatif-add-raises-to", dependency = added, after = after),
+                ),
+                (_, Some(before), _) => Status::with_lead(
+                    "What if".color(Theme::current().success()),
+                    tr!("whatif-add-keeps", dependency = added, before = before),
+                ),
+                (_, None, _) => Status::with_lead(
+                    "What if".color(Theme::current().success()),
+                    tr!("whatif-add-keeps-unknown", dependency = added),
+                ),
+            },
+        ];
+
+        lines.extend(self.raised_by().iter().filter_map(|package| {
+            let rust_version = package.rust_version.as_ref()?;
+
+            Some(Status::info(tr!(
+                "whatif-requires",
+                name = package.name,
+                version = package.version,
+                requires = rust_version,
+            )))
+        }));
+
+        lines.extend(self.verification().map(WhatIfVerification::summary));
+
+        lines.join("\n")
+    }
+}
+
+impl WhatIfUpdateResult {
+    fn summary(&self) -> String {
+        let mut lines = vec![
+            match (self.raises_msrv(), self.msrv_locked(), self.msrv_latest()) {
+                (true, Some(locked), Some(latest)) => Status::with_lead(
+                    "What if".color(Theme::current().failure()),
+                    tr!("whatif-update-raises", before = locked, after = latest),
+                ),
+                (true, None, Some(latest)) => Status::with_lead(
+                    "What if".color(Theme::current().failure()),
+                    tr!("whatif-update-raises-to", after = latest),
+                ),
+                (_, Some(locked), _) => Status::with_lead(
+                    "What if".color(Theme::current().success()),
+                    tr!("whatif-update-keeps", before = locked),
+                ),
+                (_, None, _) => Status::with_lead(
+                    "What if".color(Theme::current().success()),
+                    tr!("whatif-update-keeps-unknown"),
+                ),
+            },
+        ];
+
+        lines.extend(self.raised_by().iter().filter_map(|package| {
+            let rust_version = package.rust_version.as_ref()?;
+
+            Some(match &package.locked_version {
+                Some(locked) => Status::info(tr!(
+                    "whatif-requires-locked",
+                    name = package.name,
+                    version = package.version,
+                    locked = locked,
+                    requires = rust_version,
+                )),
+                None => Status::info(tr!(
+                    "whatif-requires",
+                    name = package.name,
+                    version = package.version,
+                    requires = rust_version,
+                )),
+            })
+        }));
+
+        lines.extend(self.verification().map(WhatIfVerification::summary));
+
+        lines.join("\n")
+    }
+}
+
+impl WhatIfVerification {
+    fn summary(&self) -> String {
+        if self.compatible {
+            Status::with_lead(
+                "Verified".color(Theme::current().success()),
+                tr!("whatif-still-compiles", version = self.toolchain),
+            )
+        } else {
+            Status::with_lead(
+                "Verified".color(Theme::current().failure()),
+                tr!("whatif-no-longer-compiles", version = self.toolchain),
+            )
+        }
+    }
+}
+
+impl FeatureSetResult {
+    fn format_human(&self) -> String {
+        let features = if self.features.is_empty() {
+            tr!("check-feature-set-default")
+        } else {
+            self.features.clone()
+        };
+
+        let subject = match &self.package {
+            Some(package) => format!("{} ({})", features, package),
+            None => features,
+        };
+
+        if self.is_compatible {
+            Status::ok(subject)
+        } else {
+            Status::fail(subject)
+        }
+    }
+}
+
+impl FuzzProjectResult {
+    fn format_human(&self) -> String {
+        let subject = tr!("check-fuzz-project", path = self.manifest_path);
+
+        if self.is_compatible {
+            Status::ok(subject)
+        } else {
+            Status::fail(subject)
+        }
+    }
+}
+
 impl CheckToolchain {
     fn header(&self, nth: u32) -> String {
-        format!(
-            "\n{} #{}: Rust {}",
-            "Compatibility Check",
-            nth,
-            self.toolchain.version(),
-        )
-        .bold()
-        .to_string()
+        let custom = match self.toolchain.custom_name() {
+            Some(name) => format!(" ({})", name),
+            None if !self.toolchain.version().pre.is_empty() => {
+                format!(" ({})", tr!("check-pre-release"))
+            }
+            None => String::new(),
+        };
+
+        let version = format!("{}{}", self.toolchain.version(), custom);
+
+        format!("\n{}", tr!("check-header", nth = nth, version = version))
+            .bold()
+            .to_string()
     }
 }
 
 impl FindResult {
     fn summary(&self) -> String {
-        let title = "Result:".bold();
+        let title = tr!("result-title").bold().to_string();
         let table = result_table(self);
 
         format!("{}\n{}", title, table)
     }
 }
 
+impl ErrorDiff {
+    fn summary(&self) -> String {
+        if self.removed.is_empty() && self.added.is_empty() {
+            return Status::with_lead(
+                "Diff".color(Theme::current().info()),
+                tr!(
+                    "diff-none",
+                    failing = self.failing_version,
+                    passing = self.passing_version,
+                ),
+            );
+        }
+
+        let mut lines = vec![Status::with_lead(
+            "Diff".color(Theme::current().info()),
+            tr!(
+                "diff-changed",
+                failing = self.failing_version,
+                passing = self.passing_version,
+            ),
+        )];
+
+        let prefixed = |diagnostic: &str, prefix: &str| {
+            diagnostic
+                .lines()
+                .map(|line| format!("{} {}", prefix, line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        lines.extend(
+            self.removed
+                .iter()
+                .map(|diagnostic| prefixed(diagnostic, "-").red().to_string()),
+        );
+        lines.extend(
+            self.added
+                .iter()
+                .map(|diagnostic| prefixed(diagnostic, "+").green().to_string()),
+        );
+
+        lines.join("\n")
+    }
+}
+
 struct Status;
 
 impl Status {
@@ -159,19 +1156,19 @@ impl Status {
     }
 
     fn ok(message: impl Display) -> String {
-        let lead = format!("[{}]", "OK".bright_green());
+        let lead = format!("[{}]", "OK".color(Theme::current().success()));
 
         status(lead, message)
     }
 
     fn fail(message: impl Display) -> String {
-        let lead = format!("[{}]", "FAIL".bright_red());
+        let lead = format!("[{}]", "FAIL".color(Theme::current().failure()));
 
         status(lead, message)
     }
 
     fn info(message: impl Display) -> String {
-        let lead = format!("[{}]", "INFO".bright_yellow());
+        let lead = format!("[{}]", "INFO".color(Theme::current().info()));
         status(lead, message)
     }
 
@@ -209,10 +1206,15 @@ fn message_box(message: &str) -> String {
 
     let mut table = builder.build();
 
+    if is_ascii() {
+        table.with(Style::ascii());
+    } else {
+        table.with(Style::modern_rounded());
+    }
+
     table
         // The remove_{left, right} is a bit of a hack, because their formatting
         // was often flaky (these vertical lines often had unaligned characters)
-        .with(Style::modern_rounded())
         .with(table_settings!())
         .with(Margin::new(2, 0, 1, 1))
         .to_string()
@@ -226,32 +1228,58 @@ fn result_table(result: &FindResult) -> String {
         result
             .msrv()
             .map(|version| format!("{}", version.green().bold().underline()))
-            .unwrap_or_else(|| format!("{}", "N/A".red()))
+            .unwrap_or_else(|| format!("{}", tr!("result-msrv-none").red()))
     }
 
     let target = result.target.as_str();
     let search_method: &str = result.search_method.into();
 
-    let content = &[
-        &[
-            format!("Considered ({} … {}):", "min".cyan(), "max".yellow()),
-            format!(
-                "Rust {} … Rust {}",
-                result.minimum_version.cyan(),
-                result.maximum_version.yellow()
+    let mut content = vec![
+        [
+            tr!(
+                "result-considered",
+                min = tr!("result-min").cyan(),
+                ellipsis = ellipsis(),
+                max = tr!("result-max").yellow(),
+            ),
+            tr!(
+                "result-search-space",
+                min = result.minimum_version.cyan(),
+                ellipsis = ellipsis(),
+                max = result.maximum_version.yellow(),
             ),
         ],
-        &[
-            "Search method:".to_string(),
+        [
+            tr!("result-search-method"),
             format!("{}", search_method.bright_purple()),
         ],
-        &["MSRV:".to_string(), msrv(result)],
-        &[
-            format!("{}", "Target:".dimmed()),
+        [tr!("result-msrv"), msrv(result)],
+        [
+            format!("{}", tr!("result-target").dimmed()),
             format!("{}", target.dimmed()),
         ],
     ];
 
+    if let Some(fingerprint) = result.fingerprint() {
+        content.push([
+            format!("{}", tr!("result-fingerprint").dimmed()),
+            format!("{}", fingerprint.id.dimmed()),
+        ]);
+    }
+
+    if let Some(shard) = result.shard() {
+        let status = if shard.all_compatible {
+            tr!("result-shard-compatible")
+        } else {
+            tr!("result-shard-incompatible")
+        };
+
+        content.insert(
+            2,
+            [tr!("result-shard"), format!("{} ({})", shard.shard, status)],
+        );
+    }
+
     Table::new(content)
         .with(Disable::row(Rows::first()))
         .with(Style::blank()) // Disables the header
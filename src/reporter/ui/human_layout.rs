@@ -0,0 +1,219 @@
+use crate::reporter::formatting::ellipsis;
+use crate::reporter::ui::theme::Theme;
+use crate::rust::Toolchain;
+use owo_colors::OwoColorize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// The layout of the line which the `human` output format prints for each check, instead of the
+/// default header and status lines.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HumanLayout {
+    /// The columns to show, in order.
+    columns: Vec<Column>,
+    /// The width of a column, in characters. Longer values are truncated, shorter values padded.
+    /// Columns without a width take the width of their value.
+    #[serde(default)]
+    widths: BTreeMap<Column, usize>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Column {
+    /// Whether the toolchain is compatible.
+    Status,
+    /// The Rust version of the toolchain.
+    Version,
+    /// How long the check took, including the setup of the toolchain.
+    Duration,
+    /// The target of the toolchain.
+    Target,
+    /// The features with which the crate was checked.
+    Features,
+}
+
+impl Column {
+    fn default_width(self) -> Option<usize> {
+        match self {
+            Self::Status => Some(4),
+            Self::Version => Some(8),
+            Self::Duration => Some(7),
+            Self::Target | Self::Features => None,
+        }
+    }
+}
+
+/// The values of a finished check, to be shown in the columns of the layout.
+pub(crate) struct CheckRow<'a> {
+    pub compatible: bool,
+    pub toolchain: &'a Toolchain,
+    pub duration: Option<Duration>,
+    pub features: Option<&'a str>,
+}
+
+impl HumanLayout {
+    pub fn new(columns: Vec<Column>, widths: BTreeMap<Column, usize>) -> Self {
+        Self { columns, widths }
+    }
+
+    pub(crate) fn format_row(&self, row: &CheckRow<'_>) -> String {
+        let theme = Theme::current();
+        let cells = self.columns.iter().map(|&column| {
+            let value = match column {
+                Column::Status if row.compatible => "OK".to_string(),
+                Column::Status => "FAIL".to_string(),
+                Column::Version => match row.toolchain.custom_name() {
+                    Some(name) => format!("{} ({})", row.toolchain.version(), name),
+                    None => row.toolchain.version().to_string(),
+                },
+                Column::Duration => row
+                    .duration
+                    .map(|duration| format!("{:.1}s", duration.as_secs_f64()))
+                    .unwrap_or_default(),
+                Column::Target => row.toolchain.target().to_string(),
+                Column::Features => row.features.unwrap_or("default").to_string(),
+            };
+
+            let width = self
+                .widths
+                .get(&column)
+                .copied()
+                .or_else(|| column.default_width());
+            let cell = fit(&value, width, ellipsis());
+
+            match column {
+                Column::Status if row.compatible => cell.color(theme.success()).to_string(),
+                Column::Status => cell.color(theme.failure()).to_string(),
+                _ => cell,
+            }
+        });
+
+        cells.collect::<Vec<_>>().join("  ").trim_end().to_string()
+    }
+}
+
+/// Pad or truncate the value to the given width, if any. A truncated value ends with the given
+/// ellipsis.
+fn fit(value: &str, width: Option<usize>, ellipsis: &str) -> String {
+    let Some(width) = width else {
+        return value.to_string();
+    };
+
+    if value.chars().count() <= width {
+        return format!("{:<width$}", value, width = width);
+    }
+
+    let ellipsis = ellipsis.chars().take(width).collect::<String>();
+    let mut truncated = value
+        .chars()
+        .take(width - ellipsis.chars().count())
+        .collect::<String>();
+    truncated.push_str(&ellipsis);
+    truncated
+}
+
+/// A short description of the features with which a check command is run, e.g. `all` or
+/// `no-default,serde`, or `None` for the default features.
+pub(crate) fn features_label(args: &[String]) -> Option<String> {
+    let mut labels = Vec::new();
+
+    if args.iter().any(|arg| arg == "--no-default-features") {
+        labels.push("no-default".to_string());
+    }
+
+    if args.iter().any(|arg| arg == "--all-features") {
+        labels.push("all".to_string());
+    }
+
+    let features = args
+        .iter()
+        .zip(args.iter().skip(1))
+        .filter(|(flag, _)| *flag == "--features")
+        .map(|(_, features)| features.clone());
+    labels.extend(features);
+
+    (!labels.is_empty()).then(|| labels.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    fn layout(columns: &[Column], widths: &[(Column, usize)]) -> HumanLayout {
+        HumanLayout::new(columns.to_vec(), widths.iter().copied().collect())
+    }
+
+    fn row(toolchain: &Toolchain) -> CheckRow<'_> {
+        CheckRow {
+            compatible: true,
+            toolchain,
+            duration: Some(Duration::from_millis(12_340)),
+            features: Some("serde"),
+        }
+    }
+
+    #[yare::parameterized(
+        version_only = { &[Column::Version], &[], "1.60.0" },
+        reordered = { &[Column::Duration, Column::Version], &[], "12.3s    1.60.0" },
+        target_and_features = { &[Column::Target, Column::Features], &[], "x86_64-unknown-linux-gnu  serde" },
+        truncated = { &[Column::Target], &[(Column::Target, 6)], "x86_6…" },
+        padded = { &[Column::Features, Column::Version], &[(Column::Features, 7)], "serde    1.60.0" },
+    )]
+    fn columns(columns: &[Column], widths: &[(Column, usize)], expected: &str) {
+        let toolchain = Toolchain::new(
+            semver::Version::new(1, 60, 0),
+            "x86_64-unknown-linux-gnu",
+            &[],
+        );
+
+        assert_eq!(
+            layout(columns, widths).format_row(&row(&toolchain)),
+            expected
+        );
+    }
+
+    #[yare::parameterized(
+        unicode = { "x86_64-unknown-linux-gnu", 6, "…", "x86_6…" },
+        ascii = { "x86_64-unknown-linux-gnu", 6, "...", "x86..." },
+        narrower_than_ellipsis = { "x86_64-unknown-linux-gnu", 2, "...", ".." },
+        fits = { "1.60.0", 6, "...", "1.60.0" },
+    )]
+    fn truncate(value: &str, width: usize, ellipsis: &str, expected: &str) {
+        assert_eq!(fit(value, Some(width), ellipsis), expected);
+    }
+
+    #[test]
+    fn configured() {
+        let layout: HumanLayout = serde_json::from_str(
+            r#"{ "columns": ["status", "version", "features"], "widths": { "version": 10 } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            layout,
+            HumanLayout::new(
+                vec![Column::Status, Column::Version, Column::Features],
+                [(Column::Version, 10)].into_iter().collect(),
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_column() {
+        assert!(serde_json::from_str::<HumanLayout>(r#"{ "columns": ["colour"] }"#).is_err());
+    }
+
+    #[yare::parameterized(
+        default = { &["cargo", "check"], None },
+        features = { &["cargo", "check", "--features", "a,b"], Some("a,b") },
+        all = { &["cargo", "check", "--all-features"], Some("all") },
+        no_default = { &["cargo", "check", "--no-default-features", "--features", "a"], Some("no-default,a") },
+    )]
+    fn features(args: &[&str], expected: Option<&str>) {
+        let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(features_label(&args).as_deref(), expected);
+    }
+}
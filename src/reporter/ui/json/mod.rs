@@ -10,6 +10,12 @@ use storyteller::EventHandler;
 #[cfg(test)]
 mod test_find;
 
+#[cfg(test)]
+mod test_format;
+
+#[cfg(test)]
+mod test_list;
+
 #[cfg(test)]
 mod test_set;
 
@@ -21,6 +27,8 @@ mod test_verify;
 
 pub struct JsonHandler<W: SendWriter> {
     writer: Arc<Mutex<W>>,
+    /// Whether each event is pretty printed over multiple lines, instead of on a single line.
+    pretty: bool,
 }
 
 impl<W: SendWriter> JsonHandler<W> {
@@ -37,6 +45,7 @@ impl<W: SendWriter> JsonHandler<W> {
     pub fn new(writer: W) -> Self {
         Self {
             writer: Arc::new(Mutex::new(writer)),
+            pretty: false,
         }
     }
 
@@ -49,16 +58,35 @@ impl JsonHandler<Stderr> {
     pub fn stderr() -> Self {
         Self {
             writer: Arc::new(Mutex::new(io::stderr())),
+            pretty: false,
         }
     }
 }
 
+impl<W: SendWriter> JsonHandler<W> {
+    /// Pretty print each event over multiple lines, for humans who debug the event stream.
+    ///
+    /// By default, each event is written on a single line (newline delimited JSON), so the
+    /// output can be processed line by line.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+}
+
 impl<W: SendWriter> EventHandler for JsonHandler<W> {
     type Event = Event;
 
     fn handle(&self, event: Self::Event) {
         let mut w = self.writer.lock().expect(Self::LOCK_FAILURE_MSG);
-        let serialized_event = serde_json::to_string(&event).expect(Self::SERIALIZE_FAILURE_MSG);
+        // NB: The compact serialization never contains a newline, since serde_json escapes the
+        //  newlines of strings; each event is thus written on a single line.
+        let serialized_event = if self.pretty {
+            serde_json::to_string_pretty(&event)
+        } else {
+            serde_json::to_string(&event)
+        }
+        .expect(Self::SERIALIZE_FAILURE_MSG);
 
         writeln!(&mut w, "{}", &serialized_event).expect(Self::WRITE_FAILURE_MSG);
     }
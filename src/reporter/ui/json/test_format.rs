@@ -0,0 +1,55 @@
+use crate::reporter::event::{CheckResult, Message};
+use crate::reporter::JsonHandler;
+use crate::rust::Toolchain;
+use crate::{semver, Event};
+use storyteller::EventHandler;
+
+fn events() -> Vec<Event> {
+    let toolchain = Toolchain::new(semver::Version::new(1, 56, 0), "x", &[]);
+    // A multi-line error, as reported by the compiler
+    let error = "error[E0658]: use of unstable library feature\n  --> src/main.rs:1:1\n";
+
+    vec![
+        Event::unscoped(Message::CheckResult(CheckResult::incompatible(
+            toolchain.clone(),
+            Some(error.to_string()),
+        ))),
+        Event::unscoped(Message::CheckResult(CheckResult::compatible(toolchain))),
+    ]
+}
+
+fn written(handler: JsonHandler<Vec<u8>>) -> String {
+    for event in events() {
+        handler.handle(event);
+    }
+
+    let buffer = handler.inner_writer();
+    String::from_utf8(buffer.clone()).unwrap()
+}
+
+#[test]
+fn compact_writes_one_event_per_line() {
+    let output = written(JsonHandler::new(Vec::new()));
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 2);
+
+    for line in lines {
+        assert!(serde_json::from_str::<serde_json::Value>(line).is_ok());
+    }
+}
+
+#[test]
+fn pretty_writes_events_over_multiple_lines() {
+    let output = written(JsonHandler::new(Vec::new()).pretty(true));
+
+    assert!(output.lines().count() > 2);
+
+    let events = serde_json::Deserializer::from_str(&output)
+        .into_iter::<serde_json::Value>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1]["type"], "check_result");
+}
@@ -0,0 +1,101 @@
+use crate::context::list::{ListFilter, ListMsrvVariant};
+use crate::dependency_graph::fixture;
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::ListResult;
+use crate::reporter::JsonHandler;
+use storyteller::EventHandler;
+
+#[yare::parameterized(
+    direct_deps = { ListMsrvVariant::DirectDeps },
+    ordered_by_msrv = { ListMsrvVariant::OrderedByMSRV },
+)]
+fn dependencies(variant: ListMsrvVariant) {
+    let graph = fixture::graph();
+    let event = ListResult::new(variant, graph);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    assert_eq!(actual["type"], "subcommand_result");
+    assert_eq!(actual["subcommand_id"], "list");
+    assert_eq!(actual["result"]["schema_version"], 1);
+    assert_eq!(
+        actual["result"]["dependencies"],
+        serde_json::json!([
+            {
+                "name": "b",
+                "version": "0.1.0",
+                "rust_version": "1.60.0",
+                "kind": "direct",
+                "source": "path",
+                "manifest_path": "/b/Cargo.toml",
+                "path": ["a", "b"]
+            },
+            {
+                "name": "c",
+                "version": "0.1.0",
+                "rust_version": null,
+                "kind": "transitive",
+                "source": "path",
+                "manifest_path": "/c/Cargo.toml",
+                "path": ["a", "b", "c"]
+            }
+        ])
+    );
+}
+
+#[yare::parameterized(
+    direct_only = { ListFilter { max_depth: Some(1), min_rust_version: None }, &["b"] },
+    depth = { ListFilter { max_depth: Some(2), min_rust_version: None }, &["b", "c"] },
+    min_rust_version = { ListFilter { max_depth: None, min_rust_version: Some(BareVersion::TwoComponents(1, 56)) }, &["b"] },
+    min_rust_version_above_all = { ListFilter { max_depth: None, min_rust_version: Some(BareVersion::TwoComponents(1, 60)) }, &[] },
+)]
+fn filtered(filter: ListFilter, expected: &[&str]) {
+    let graph = fixture::graph();
+    let event = ListResult::new(ListMsrvVariant::OrderedByMSRV, graph).with_filter(filter);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    let names = actual["result"]["dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dependency| dependency["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn filtered_ordered_by_msrv_list() {
+    let graph = fixture::graph();
+    let filter = ListFilter {
+        max_depth: Some(1),
+        min_rust_version: None,
+    };
+    let event = ListResult::new(ListMsrvVariant::OrderedByMSRV, graph).with_filter(filter);
+
+    let writer = Vec::new();
+    let handler = JsonHandler::new(writer);
+    handler.handle(event.into());
+
+    let buffer = handler.inner_writer();
+    let actual: serde_json::Value = serde_json::from_slice(buffer.as_slice()).unwrap();
+
+    assert_eq!(
+        actual["result"]["list"],
+        serde_json::json!([
+            { "msrv": "1.70.0", "dependencies": ["a"] },
+            { "msrv": "1.60.0", "dependencies": ["b"] },
+        ])
+    );
+}
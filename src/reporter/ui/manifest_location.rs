@@ -0,0 +1,21 @@
+//! The location of the MSRV in the Cargo manifest, for the output formats which annotate files in
+//! CI, like the GitLab code quality report.
+
+use std::path::Path;
+
+/// The line (starting at 1) which declares the `rust-version` or `package.metadata.msrv` in the
+/// manifest, or the first line if the MSRV isn't declared.
+pub(super) fn msrv_line(manifest: &Path) -> usize {
+    std::fs::read_to_string(manifest)
+        .ok()
+        .and_then(|contents| {
+            contents
+                .lines()
+                .position(|line| {
+                    let line = line.trim_start();
+                    line.starts_with("rust-version") || line.starts_with("msrv")
+                })
+                .map(|n| n + 1)
+        })
+        .unwrap_or(1)
+}
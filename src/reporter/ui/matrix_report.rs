@@ -0,0 +1,484 @@
+//! Writes the outcome of each check of a run as a matrix once cargo-msrv finishes: the checked
+//! Rust versions are the rows, and the checked targets and feature sets are the columns. Each
+//! cell is either `pass`, `fail` or `skip`, or empty if the combination was not checked, e.g.
+//! because a bisection didn't need it.
+//!
+//! The targets are the targets given with `find --targets`, and the feature sets are the feature
+//! sets of `cargo hack`. If a target is checked per feature set, it has no column of its own, and
+//! only the columns of its feature sets are shown.
+
+use crate::reporter::event::{Message, SearchControl};
+use crate::reporter::formatting::is_ascii;
+use crate::{semver, Event};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use storyteller::EventHandler;
+
+/// The format of the matrix report.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum MatrixReportFormat {
+    /// A table, like the tables of the human output
+    Human,
+    /// A markdown table, e.g. for a pull request comment or a job summary
+    Markdown,
+    /// A json document, with the columns, and the cells of each Rust version
+    Json,
+}
+
+impl MatrixReportFormat {
+    /// The format for the file at the given path, by its extension: `.md` is markdown, and
+    /// `.json` is json. Other files are written as a table.
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("md" | "markdown") => Self::Markdown,
+            Some("json") => Self::Json,
+            _ => Self::Human,
+        }
+    }
+}
+
+pub struct MatrixReportHandler {
+    file: PathBuf,
+    format: MatrixReportFormat,
+    matrix: Mutex<Matrix>,
+}
+
+impl MatrixReportHandler {
+    pub fn new(file: PathBuf, format: MatrixReportFormat) -> Self {
+        Self {
+            file,
+            format,
+            matrix: Mutex::new(Matrix::default()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Matrix {
+    /// The target of the toolchain which is checked, which a skipped check belongs to.
+    current_target: Option<String>,
+    cells: BTreeMap<(semver::Version, MatrixColumn), Cell>,
+}
+
+/// A target, or a feature set of a target.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Serialize)]
+struct MatrixColumn {
+    target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    features: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Cell {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl Cell {
+    fn checked(is_compatible: bool) -> Self {
+        if is_compatible {
+            Self::Pass
+        } else {
+            Self::Fail
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Skip => "skip",
+        }
+    }
+
+    fn markdown(self) -> &'static str {
+        match self {
+            Self::Pass => "✅ pass",
+            Self::Fail => "❌ fail",
+            Self::Skip => "⏭️ skip",
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct JsonMatrix<'a> {
+    columns: &'a [&'a MatrixColumn],
+    rows: Vec<JsonRow<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct JsonRow<'a> {
+    version: &'a semver::Version,
+    /// The cell of each column, or `null` if it was not checked.
+    cells: Vec<Option<Cell>>,
+}
+
+impl Matrix {
+    fn update(&mut self, message: &Message) {
+        match message {
+            Message::CheckToolchain(it) => {
+                self.current_target = Some(it.toolchain.target().to_string());
+            }
+            // A version may be checked again, e.g. by `verify --find`, and the latest outcome
+            // is kept
+            Message::CheckResult(it) => {
+                let column = MatrixColumn {
+                    target: it.toolchain().target().to_string(),
+                    package: None,
+                    features: None,
+                };
+                let version = it.toolchain().version().clone();
+
+                self.cells
+                    .insert((version, column), Cell::checked(it.is_compatible()));
+            }
+            Message::FeatureSetResult(it) => {
+                let column = MatrixColumn {
+                    target: it.toolchain.target().to_string(),
+                    package: it.package.clone(),
+                    features: Some(it.features.clone()),
+                };
+                let version = it.toolchain.version().clone();
+
+                self.cells
+                    .insert((version, column), Cell::checked(it.is_compatible));
+            }
+            Message::SearchControl(SearchControl::Skipped { version }) => {
+                if let Some(target) = &self.current_target {
+                    let column = MatrixColumn {
+                        target: target.clone(),
+                        package: None,
+                        features: None,
+                    };
+
+                    self.cells.insert((version.clone(), column), Cell::Skip);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The columns, ordered by target. The column of a target is left out if the target has
+    /// columns for its feature sets.
+    fn columns(&self) -> Vec<&MatrixColumn> {
+        let columns = self
+            .cells
+            .keys()
+            .map(|(_, column)| column)
+            .collect::<BTreeSet<_>>();
+
+        let per_feature_set = columns
+            .iter()
+            .filter(|column| column.features.is_some())
+            .map(|column| column.target.as_str())
+            .collect::<BTreeSet<_>>();
+
+        columns
+            .into_iter()
+            .filter(|column| {
+                column.features.is_some() || !per_feature_set.contains(column.target.as_str())
+            })
+            .collect()
+    }
+
+    /// The checked Rust versions, the most recent first.
+    fn versions(&self) -> Vec<&semver::Version> {
+        let versions = self
+            .cells
+            .keys()
+            .map(|(version, _)| version)
+            .collect::<BTreeSet<_>>();
+
+        versions.into_iter().rev().collect()
+    }
+
+    /// The header of each column. The target is left out if only one target was checked.
+    fn labels(&self, columns: &[&MatrixColumn]) -> Vec<String> {
+        let targets = columns
+            .iter()
+            .map(|column| column.target.as_str())
+            .collect::<BTreeSet<_>>();
+
+        columns
+            .iter()
+            .map(|column| {
+                let features = column
+                    .features
+                    .as_ref()
+                    .map(|features| match &column.package {
+                        Some(package) => format!("{}: {}", package, features),
+                        None => features.clone(),
+                    });
+
+                match features {
+                    Some(features) if targets.len() > 1 => {
+                        format!("{} {}", column.target, features)
+                    }
+                    Some(features) => features,
+                    None => column.target.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn cell(&self, version: &semver::Version, column: &MatrixColumn) -> Option<Cell> {
+        self.cells.get(&(version.clone(), column.clone())).copied()
+    }
+
+    /// The matrix in the given format, or nothing if no toolchain was checked.
+    fn render(&self, format: MatrixReportFormat) -> Option<String> {
+        if self.cells.is_empty() {
+            return None;
+        }
+
+        let rendered = match format {
+            MatrixReportFormat::Human => self.render_human(),
+            MatrixReportFormat::Markdown => self.render_markdown(),
+            MatrixReportFormat::Json => self.render_json(),
+        };
+
+        Some(rendered)
+    }
+
+    fn render_human(&self) -> String {
+        use tabled::builder::Builder;
+        use tabled::settings::Style;
+
+        let columns = self.columns();
+
+        let mut builder = Builder::default();
+        builder
+            .push_record(std::iter::once("Rust version".to_string()).chain(self.labels(&columns)));
+
+        for version in self.versions() {
+            let cells = columns.iter().map(|column| {
+                self.cell(version, column)
+                    .map_or("", Cell::as_str)
+                    .to_string()
+            });
+            builder.push_record(std::iter::once(version.to_string()).chain(cells));
+        }
+
+        let mut table = builder.build();
+
+        if is_ascii() {
+            table.with(Style::ascii());
+        } else {
+            table.with(Style::modern_rounded());
+        }
+
+        format!("{}\n", table)
+    }
+
+    fn render_markdown(&self) -> String {
+        let columns = self.columns();
+        let escape = |text: &str| text.replace('|', "\\|");
+
+        let labels = self
+            .labels(&columns)
+            .iter()
+            .map(|label| format!(" {} |", escape(label)))
+            .collect::<String>();
+        let separators = " --- |".repeat(columns.len());
+
+        let mut markdown = format!("| Rust version |{}\n| --- |{}\n", labels, separators);
+
+        for version in self.versions() {
+            let cells = columns
+                .iter()
+                .map(|column| {
+                    let cell = self.cell(version, column).map_or("", Cell::markdown);
+                    format!(" {} |", cell)
+                })
+                .collect::<String>();
+
+            markdown.push_str(&format!("| {} |{}\n", version, cells));
+        }
+
+        markdown
+    }
+
+    fn render_json(&self) -> String {
+        let columns = self.columns();
+
+        let rows = self
+            .versions()
+            .into_iter()
+            .map(|version| JsonRow {
+                version,
+                cells: columns
+                    .iter()
+                    .map(|column| self.cell(version, column))
+                    .collect(),
+            })
+            .collect();
+
+        let matrix = JsonMatrix {
+            columns: &columns,
+            rows,
+        };
+
+        let mut json = serde_json::to_string_pretty(&matrix).expect("the matrix can be serialized");
+        json.push('\n');
+        json
+    }
+}
+
+impl EventHandler for MatrixReportHandler {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        if let Ok(mut matrix) = self.matrix.lock() {
+            matrix.update(event.message());
+        }
+    }
+
+    fn finish(&self) {
+        let matrix = self
+            .matrix
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let Some(report) = matrix.render(self.format) else {
+            info!("no toolchain was checked, so no matrix report is written");
+            return;
+        };
+
+        // The report is a convenience, which should not fail cargo-msrv itself
+        if let Err(error) = std::fs::write(&self.file, report) {
+            info!(%error, file = %self.file.display(), "unable to write the matrix report");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::{CheckResult, CheckToolchain, FeatureSetResult};
+    use crate::rust::Toolchain;
+    use std::path::Path;
+
+    fn toolchain(minor: u64, target: &'static str) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), target, &[])
+    }
+
+    fn matrix(events: Vec<Event>) -> Matrix {
+        let mut matrix = Matrix::default();
+        for event in events {
+            matrix.update(event.message());
+        }
+
+        matrix
+    }
+
+    fn per_target() -> Matrix {
+        matrix(vec![
+            CheckToolchain::new(toolchain(60, "x86_64-unknown-linux-gnu")).into(),
+            CheckResult::compatible(toolchain(60, "x86_64-unknown-linux-gnu")).into(),
+            CheckToolchain::new(toolchain(65, "wasm32-unknown-unknown")).into(),
+            CheckResult::compatible(toolchain(65, "wasm32-unknown-unknown")).into(),
+            CheckToolchain::new(toolchain(60, "wasm32-unknown-unknown")).into(),
+            CheckResult::incompatible(toolchain(60, "wasm32-unknown-unknown"), None).into(),
+            CheckToolchain::new(toolchain(55, "wasm32-unknown-unknown")).into(),
+            SearchControl::Skipped {
+                version: semver::Version::new(1, 55, 0),
+            }
+            .into(),
+        ])
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            per_target().render(MatrixReportFormat::Markdown).unwrap(),
+            "| Rust version | wasm32-unknown-unknown | x86_64-unknown-linux-gnu |\n\
+             | --- | --- | --- |\n\
+             | 1.65.0 | ✅ pass |  |\n\
+             | 1.60.0 | ❌ fail | ✅ pass |\n\
+             | 1.55.0 | ⏭️ skip |  |\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let json = per_target().render(MatrixReportFormat::Json).unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!({
+                "columns": [
+                    { "target": "wasm32-unknown-unknown" },
+                    { "target": "x86_64-unknown-linux-gnu" },
+                ],
+                "rows": [
+                    { "version": "1.65.0", "cells": ["pass", null] },
+                    { "version": "1.60.0", "cells": ["fail", "pass"] },
+                    { "version": "1.55.0", "cells": ["skip", null] },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn feature_sets_replace_the_column_of_the_target() {
+        let matrix = matrix(vec![
+            FeatureSetResult::new(
+                toolchain(60, "x"),
+                Some("a".to_string()),
+                "--no-default-features",
+                true,
+            )
+            .into(),
+            FeatureSetResult::new(
+                toolchain(60, "x"),
+                Some("a".to_string()),
+                "--all-features",
+                false,
+            )
+            .into(),
+            CheckResult::incompatible(toolchain(60, "x"), None).into(),
+        ]);
+
+        assert_eq!(
+            matrix.render(MatrixReportFormat::Markdown).unwrap(),
+            "| Rust version | a: --all-features | a: --no-default-features |\n\
+             | --- | --- | --- |\n\
+             | 1.60.0 | ❌ fail | ✅ pass |\n"
+        );
+    }
+
+    #[test]
+    fn without_checks() {
+        assert_eq!(matrix(vec![]).render(MatrixReportFormat::Human), None);
+    }
+
+    #[yare::parameterized(
+        markdown = { "matrix.md", MatrixReportFormat::Markdown },
+        json = { "matrix.json", MatrixReportFormat::Json },
+        text = { "matrix.txt", MatrixReportFormat::Human },
+        no_extension = { "matrix", MatrixReportFormat::Human },
+    )]
+    fn format_from_path(path: &str, expected: MatrixReportFormat) {
+        assert_eq!(MatrixReportFormat::from_path(Path::new(path)), expected);
+    }
+
+    #[test]
+    fn written_on_finish() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let file = dir.path().join("matrix.txt");
+
+        let handler = MatrixReportHandler::new(file.clone(), MatrixReportFormat::Human);
+        handler.handle(CheckResult::compatible(toolchain(60, "x")).into());
+        handler.finish();
+
+        let written = std::fs::read_to_string(file).unwrap();
+        assert!(written.contains("Rust version"), "{}", written);
+        assert!(written.contains("1.60.0"), "{}", written);
+        assert!(written.contains("pass"), "{}", written);
+    }
+}
@@ -0,0 +1,415 @@
+//! Sends the metrics of a run to a Prometheus Pushgateway and/or a statsd server once cargo-msrv
+//! finishes, so the health of the MSRV checks can be tracked across many repositories. The
+//! endpoints are configured in the `metrics` table of the Cargo manifest.
+//!
+//! The metrics are:
+//!
+//! * `run_duration_seconds`: how long cargo-msrv ran;
+//! * `toolchains_checked`: how many Rust versions were checked;
+//! * `toolchain_cache_hit_ratio`: the fraction of the toolchains which were already installed,
+//!   and so didn't have to be downloaded, if any toolchain was set up;
+//! * `msrv_minor`: the minor version of the MSRV, e.g. `65` for Rust 1.65, if it is known;
+//! * `success`: `1` if cargo-msrv succeeded, `0` otherwise.
+
+use crate::manifest::metrics::MetricsConfig;
+use crate::reporter::event::{Message, SubcommandResult, TerminationOutcome};
+use crate::{semver, Event};
+use std::collections::HashSet;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::Mutex;
+use std::time::Duration;
+use storyteller::EventHandler;
+
+/// How long to wait for an endpoint, so an unreachable endpoint doesn't hold up cargo-msrv.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct MetricsHandler {
+    config: MetricsConfig,
+    metrics: Mutex<Metrics>,
+}
+
+#[derive(Debug, Default)]
+struct Metrics {
+    duration: Option<Duration>,
+    checked: HashSet<semver::Version>,
+    setups: u64,
+    downloads: u64,
+    /// Whether the toolchain which is being set up is downloaded.
+    downloading: bool,
+    msrv: Option<semver::Version>,
+    success: Option<bool>,
+}
+
+/// A single measurement, as sent to the endpoints.
+#[derive(Clone, Debug, PartialEq)]
+struct Measurement {
+    name: &'static str,
+    help: &'static str,
+    value: f64,
+}
+
+impl MetricsHandler {
+    pub fn new(config: MetricsConfig) -> Self {
+        Self {
+            config,
+            metrics: Mutex::new(Metrics::default()),
+        }
+    }
+
+    fn push_to_gateway(&self, url: &str, measurements: &[Measurement]) -> io::Result<()> {
+        let mut path = format!("/metrics/job/{}", self.config.job);
+        if let Some(name) = &self.config.crate_name {
+            path.push_str(&format!("/crate/{}", name));
+        }
+
+        put(url, &path, &exposition(measurements))
+    }
+
+    fn send_to_statsd(&self, address: &str, measurements: &[Measurement]) -> io::Result<()> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect(address)?;
+        socket.send(statsd_lines(&self.config.prefix, measurements).as_bytes())?;
+
+        Ok(())
+    }
+}
+
+impl Metrics {
+    fn update(&mut self, event: &Event) {
+        match event.message() {
+            Message::SetupToolchain(_) if event.is_scope_start() => {
+                self.setups += 1;
+                self.downloading = false;
+            }
+            Message::DownloadProgress(_) if !self.downloading => {
+                self.downloads += 1;
+                self.downloading = true;
+            }
+            Message::CheckResult(it) => {
+                self.checked.insert(it.toolchain().version().clone());
+            }
+            Message::SubcommandResult(SubcommandResult::Find(it)) => {
+                self.msrv = it.msrv().cloned();
+            }
+            // With `--find`, the found MSRV was already set
+            Message::SubcommandResult(SubcommandResult::Verify(it))
+                if it.is_compatible() && self.msrv.is_none() =>
+            {
+                self.msrv = Some(it.toolchain().version().clone());
+            }
+            Message::Terminated(it) => {
+                self.duration = Some(it.duration());
+                self.success = Some(it.outcome() == TerminationOutcome::Success);
+            }
+            _ => {}
+        }
+    }
+
+    fn measurements(&self) -> Vec<Measurement> {
+        let mut measurements = Vec::new();
+
+        if let Some(duration) = self.duration {
+            measurements.push(Measurement {
+                name: "run_duration_seconds",
+                help: "How long cargo-msrv ran",
+                value: duration.as_secs_f64(),
+            });
+        }
+
+        measurements.push(Measurement {
+            name: "toolchains_checked",
+            help: "How many Rust versions were checked",
+            value: self.checked.len() as f64,
+        });
+
+        if self.setups > 0 {
+            measurements.push(Measurement {
+                name: "toolchain_cache_hit_ratio",
+                help: "The fraction of the toolchains which were already installed",
+                value: self.setups.saturating_sub(self.downloads) as f64 / self.setups as f64,
+            });
+        }
+
+        if let Some(msrv) = &self.msrv {
+            measurements.push(Measurement {
+                name: "msrv_minor",
+                help: "The minor version of the MSRV",
+                value: msrv.minor as f64,
+            });
+        }
+
+        if let Some(success) = self.success {
+            measurements.push(Measurement {
+                name: "success",
+                help: "Whether cargo-msrv succeeded",
+                value: if success { 1.0 } else { 0.0 },
+            });
+        }
+
+        measurements
+    }
+}
+
+impl EventHandler for MetricsHandler {
+    type Event = Event;
+
+    fn handle(&self, event: Self::Event) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.update(&event);
+        }
+    }
+
+    fn finish(&self) {
+        let measurements = self
+            .metrics
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .measurements();
+
+        // The metrics are a convenience, which should not fail cargo-msrv itself
+        if let Some(url) = &self.config.pushgateway {
+            if let Err(error) = self.push_to_gateway(url, &measurements) {
+                info!(%error, url, "unable to push the metrics to the Pushgateway");
+            }
+        }
+
+        if let Some(address) = &self.config.statsd {
+            if let Err(error) = self.send_to_statsd(address, &measurements) {
+                info!(%error, address, "unable to send the metrics to statsd");
+            }
+        }
+    }
+}
+
+/// The measurements in the Prometheus text exposition format.
+fn exposition(measurements: &[Measurement]) -> String {
+    measurements
+        .iter()
+        .map(|measurement| {
+            let name = format!("cargo_msrv_{}", measurement.name);
+            format!(
+                "# HELP {name} {}\n# TYPE {name} gauge\n{name} {}\n",
+                measurement.help, measurement.value
+            )
+        })
+        .collect()
+}
+
+/// The measurements as statsd gauges, one per line.
+fn statsd_lines(prefix: &str, measurements: &[Measurement]) -> String {
+    measurements
+        .iter()
+        .map(|measurement| format!("{}.{}:{}|g", prefix, measurement.name, measurement.value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replace the metrics at the given path of the Pushgateway, with a plain HTTP/1.1 request.
+fn put(url: &str, path: &str, body: &str) -> io::Result<()> {
+    let authority_and_base = url.strip_prefix("http://").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "only http:// URLs are supported",
+        )
+    })?;
+    let (authority, base) = match authority_and_base.split_once('/') {
+        Some((authority, base)) if !base.trim_matches('/').is_empty() => {
+            (authority, format!("/{}", base.trim_matches('/')))
+        }
+        Some((authority, _)) => (authority, String::new()),
+        None => (authority_and_base, String::new()),
+    };
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{}:80", authority)
+    };
+
+    let socket_address = address.to_socket_addrs()?.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("unable to resolve '{}'", address),
+        )
+    })?;
+
+    let mut stream = TcpStream::connect_timeout(&socket_address, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let request = format!(
+        "PUT {base}{path} HTTP/1.1\r\nHost: {authority}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    let status = response.split_whitespace().nth(1).unwrap_or_default();
+    if !status.starts_with('2') {
+        return Err(io::Error::other(format!(
+            "the Pushgateway responded with status '{}'",
+            status
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::SearchMethod;
+    use crate::exit_code::ExitCode;
+    use crate::manifest::bare_version::BareVersion;
+    use crate::reporter::event::{
+        CheckResult, DownloadProgress, FindResult, ScopeCounter, SetupToolchain, Terminated,
+    };
+    use crate::rust::Toolchain;
+    use std::net::TcpListener;
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    fn events() -> Vec<Event> {
+        let scopes = ScopeCounter::new();
+        let mut events = Vec::new();
+
+        for (minor, download) in [(60, true), (65, false)] {
+            let (start, end) =
+                Event::from(SetupToolchain::new(toolchain(minor))).into_scoped(&scopes);
+            events.push(start);
+            if download {
+                events.push(DownloadProgress::new(Some("rustc".to_string()), 1, 2).into());
+                events.push(DownloadProgress::new(Some("rustc".to_string()), 2, 2).into());
+            }
+            events.push(end);
+            events.push(if minor == 60 {
+                CheckResult::incompatible(toolchain(minor), None).into()
+            } else {
+                CheckResult::compatible(toolchain(minor)).into()
+            });
+        }
+
+        events.push(
+            FindResult::new_msrv(
+                semver::Version::new(1, 65, 0),
+                "x",
+                BareVersion::TwoComponents(1, 56),
+                BareVersion::TwoComponents(1, 80),
+                SearchMethod::Bisect,
+            )
+            .into(),
+        );
+        events.push(Terminated::new(ExitCode::Success, Duration::from_millis(1500)).into());
+
+        events
+    }
+
+    fn measured(events: Vec<Event>) -> Vec<(&'static str, f64)> {
+        let mut metrics = Metrics::default();
+        for event in events {
+            metrics.update(&event);
+        }
+
+        metrics
+            .measurements()
+            .into_iter()
+            .map(|measurement| (measurement.name, measurement.value))
+            .collect()
+    }
+
+    #[test]
+    fn measurements() {
+        assert_eq!(
+            measured(events()),
+            vec![
+                ("run_duration_seconds", 1.5),
+                ("toolchains_checked", 2.0),
+                ("toolchain_cache_hit_ratio", 0.5),
+                ("msrv_minor", 65.0),
+                ("success", 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn without_checks() {
+        assert_eq!(
+            measured(vec![Terminated::new(
+                ExitCode::Failure,
+                Duration::from_secs(2)
+            )
+            .into()]),
+            vec![
+                ("run_duration_seconds", 2.0),
+                ("toolchains_checked", 0.0),
+                ("success", 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn pushed_to_gateway() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let gateway = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 4096];
+
+            // Read until the whole body arrived, which ends with the last measurement
+            while !String::from_utf8_lossy(&request).ends_with("cargo_msrv_success 1\n") {
+                let n = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+
+            String::from_utf8(request).unwrap()
+        });
+
+        let handler = MetricsHandler::new(MetricsConfig {
+            pushgateway: Some(url),
+            job: "msrv".to_string(),
+            crate_name: Some("a".to_string()),
+            ..MetricsConfig::default()
+        });
+        for event in events() {
+            handler.handle(event);
+        }
+        handler.finish();
+
+        let request = gateway.join().unwrap();
+        assert!(request.starts_with("PUT /metrics/job/msrv/crate/a HTTP/1.1\r\n"));
+        assert!(request.contains("# TYPE cargo_msrv_msrv_minor gauge\ncargo_msrv_msrv_minor 65\n"));
+    }
+
+    #[test]
+    fn sent_to_statsd() {
+        let statsd = UdpSocket::bind("127.0.0.1:0").unwrap();
+        statsd.set_read_timeout(Some(TIMEOUT)).unwrap();
+
+        let handler = MetricsHandler::new(MetricsConfig {
+            statsd: Some(statsd.local_addr().unwrap().to_string()),
+            prefix: "ci.msrv".to_string(),
+            ..MetricsConfig::default()
+        });
+        for event in events() {
+            handler.handle(event);
+        }
+        handler.finish();
+
+        let mut buffer = [0; 4096];
+        let n = statsd.recv(&mut buffer).unwrap();
+
+        assert_eq!(
+            String::from_utf8_lossy(&buffer[..n]),
+            "ci.msrv.run_duration_seconds:1.5|g\nci.msrv.toolchains_checked:2|g\nci.msrv.toolchain_cache_hit_ratio:0.5|g\nci.msrv.msrv_minor:65|g\nci.msrv.success:1|g"
+        );
+    }
+}
@@ -75,27 +75,119 @@ impl<S: SendWriter, F: SendWriter> EventHandler for MinimalOutputHandler<S, F> {
 
         if let Message::SubcommandResult(result) = event.message() {
             match result {
+                SubcommandResult::BisectLockfile(inner) => {
+                    match inner.first_incompatible().commit() {
+                        Some(commit) => success_writeln!("{}", commit.id),
+                        None => success_writeln!("working-tree"),
+                    }
+                }
+                SubcommandResult::CacheStats(inner) => {
+                    success_writeln!("{}", inner.bytes())
+                }
+                SubcommandResult::CheckConsistency(inner) => match inner.msrv() {
+                    Some(v) if inner.is_consistent() => {
+                        success_writeln!("{}", v)
+                    }
+                    Some(v) => failure_writeln!("{}", v),
+                    None => failure_writeln!("{}", "none"),
+                },
+                SubcommandResult::Clean(inner) => {
+                    success_writeln!("{}", inner.bytes())
+                }
+                SubcommandResult::Estimate(inner) => match inner.version() {
+                    Some(v) => {
+                        success_writeln!("{}", v)
+                    }
+                    None => failure_writeln!("{}", "none"),
+                },
                 SubcommandResult::Find(inner) => match inner.msrv() {
                     Some(v) => {
                         success_writeln!("{}", v)
                     }
                     None => failure_writeln!("{}", "none"),
                 },
+                SubcommandResult::FindTargets(inner) => match inner.msrv() {
+                    Some(v) => {
+                        success_writeln!("{}", v)
+                    }
+                    None => failure_writeln!("{}", "none"),
+                },
+                SubcommandResult::Graph(inner) => {
+                    success_writeln!("{}", inner.graph().trim_end())
+                }
+                SubcommandResult::Images(inner) => {
+                    let images = inner.images().iter().map(|image| image.image.as_str());
+                    success_writeln!("{}", images.collect::<Vec<_>>().join(" "))
+                }
+                SubcommandResult::Inspect(inner) => {
+                    success_writeln!("{}", inner.to_string().trim_end())
+                }
                 SubcommandResult::List(_inner) => {
                     failure_writeln!("unsupported")
                 }
+                SubcommandResult::MergeResults(inner) => match inner.msrv() {
+                    Some(v) => {
+                        success_writeln!("{}", v)
+                    }
+                    None => failure_writeln!("{}", "none"),
+                },
+                SubcommandResult::Migrate(inner) => {
+                    success_writeln!("{}", inner.migrated().len())
+                }
+                SubcommandResult::Outdated(_inner) => {
+                    failure_writeln!("unsupported")
+                }
+                SubcommandResult::Report(inner) => {
+                    success_writeln!("{}", inner.report().trim_end())
+                }
                 SubcommandResult::Set(inner) => {
                     success_writeln!("{}", inner.version())
                 }
                 SubcommandResult::Show(inner) => {
                     success_writeln!("{}", inner.version())
                 }
-                SubcommandResult::Verify(inner) if inner.is_compatible() => {
+                SubcommandResult::ShowWorkspace(inner) => {
+                    let members = inner
+                        .members()
+                        .iter()
+                        .map(|member| match &member.version {
+                            Some(version) => format!("{} {}", member.name, version),
+                            None => format!("{} none", member.name),
+                        })
+                        .collect::<Vec<_>>();
+                    success_writeln!("{}", members.join("\n"))
+                }
+                SubcommandResult::Sync(inner) => {
+                    success_writeln!("{}", inner.msrv())
+                }
+                SubcommandResult::Verify(inner) if inner.is_verified() => {
                     success_writeln!("true")
                 }
-                SubcommandResult::Verify(_inner) /* if !inner.is_compatible() */ => {
+                SubcommandResult::Verify(_inner) /* if !inner.is_verified() */ => {
                     failure_writeln!("false")
                 }
+                SubcommandResult::WhatIf(inner) => {
+                    let msrv = inner
+                        .msrv_after()
+                        .map_or_else(|| "none".to_string(), ToString::to_string);
+
+                    if inner.raises_msrv() {
+                        failure_writeln!("{}", msrv)
+                    } else {
+                        success_writeln!("{}", msrv)
+                    }
+                }
+                SubcommandResult::WhatIfUpdate(inner) => {
+                    let msrv = inner
+                        .msrv_latest()
+                        .map_or_else(|| "none".to_string(), ToString::to_string);
+
+                    if inner.raises_msrv() {
+                        failure_writeln!("{}", msrv)
+                    } else {
+                        success_writeln!("{}", msrv)
+                    }
+                }
             }
         }
     }
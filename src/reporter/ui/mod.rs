@@ -1,15 +1,36 @@
+mod azure;
+mod buildkite_annotation;
 mod discard_output;
+mod event_log;
+mod event_socket;
+mod github_output;
+mod gitlab;
 mod human;
+mod human_layout;
 mod json;
+mod manifest_location;
+mod matrix_report;
+mod metrics;
 mod minimal;
+mod theme;
 
 #[cfg(test)]
 mod testing;
 
+pub use azure::AzureHandler;
+pub use buildkite_annotation::BuildkiteAnnotationHandler;
 pub use discard_output::DiscardOutputHandler;
+pub use event_log::EventLogHandler;
+pub use event_socket::EventSocketHandler;
+pub use github_output::GitHubOutputHandler;
+pub use gitlab::GitLabHandler;
 pub use human::HumanProgressHandler;
+pub use human_layout::{Column, HumanLayout};
 pub use json::JsonHandler;
+pub use matrix_report::{MatrixReportFormat, MatrixReportHandler};
+pub use metrics::MetricsHandler;
 pub use minimal::MinimalOutputHandler;
+pub use theme::{set_theme, Theme};
 
 #[cfg(test)]
 pub use testing::TestingHandler;
@@ -0,0 +1,115 @@
+use owo_colors::{AnsiColors, DynColors};
+use std::fmt;
+use std::sync::RwLock;
+
+static THEME: RwLock<Theme> = RwLock::new(Theme::DEFAULT);
+
+/// The colors of the `human` output format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    /// The color of compatible checks, and of other successful outcomes.
+    success: ThemeColor,
+    /// The color of incompatible checks, and of other failed outcomes.
+    failure: ThemeColor,
+    /// The color of informational messages, like summaries.
+    info: ThemeColor,
+}
+
+impl Theme {
+    const DEFAULT: Self = Self {
+        success: ThemeColor(DynColors::Ansi(AnsiColors::BrightGreen)),
+        failure: ThemeColor(DynColors::Ansi(AnsiColors::BrightRed)),
+        info: ThemeColor(DynColors::Ansi(AnsiColors::BrightYellow)),
+    };
+
+    /// The theme of the output, as set by [`set_theme`].
+    pub(crate) fn current() -> Self {
+        *THEME
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn success(&self) -> DynColors {
+        self.success.0
+    }
+
+    pub(crate) fn failure(&self) -> DynColors {
+        self.failure.0
+    }
+
+    pub(crate) fn info(&self) -> DynColors {
+        self.info.0
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Use the given colors for the `human` output format.
+pub fn set_theme(theme: Theme) {
+    *THEME
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = theme;
+}
+
+/// A color name, like `green` or `bright blue`, or a hex RGB color, like `#2e8b57`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(try_from = "String")]
+struct ThemeColor(DynColors);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = UnknownColor;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .trim()
+            .to_ascii_lowercase()
+            .parse::<DynColors>()
+            .map(Self)
+            .map_err(|_| UnknownColor(value))
+    }
+}
+
+#[derive(Debug)]
+struct UnknownColor(String);
+
+impl fmt::Display for UnknownColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown color '{}', expected a color name like 'green' or 'bright blue', or a hex RGB color like '#2e8b57'",
+            self.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        name = { r#"{ "success": "green" }"#, DynColors::Ansi(AnsiColors::Green) },
+        bright = { r#"{ "success": "Bright Blue" }"#, DynColors::Ansi(AnsiColors::BrightBlue) },
+        rgb = { r##"{ "success": "#2e8b57" }"##, DynColors::Rgb(0x2e, 0x8b, 0x57) },
+    )]
+    fn configured(config: &str, expected: DynColors) {
+        let theme: Theme = serde_json::from_str(config).unwrap();
+
+        assert_eq!(theme.success(), expected);
+        // Colors which are not configured keep their default
+        assert_eq!(theme.failure(), Theme::default().failure());
+    }
+
+    #[yare::parameterized(
+        unknown_color = { r#"{ "info": "grey" }"# },
+        short_rgb = { r##"{ "info": "#fff" }"## },
+        unknown_field = { r#"{ "warning": "red" }"# },
+    )]
+    fn invalid(config: &str) {
+        assert!(serde_json::from_str::<Theme>(config).is_err());
+    }
+}
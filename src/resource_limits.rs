@@ -0,0 +1,96 @@
+//! Limits of the memory and CPU time which the processes of a check may use, see
+//! `--memory-limit` and `--cpu-limit`.
+//!
+//! On Linux, each check with limits runs in a cgroup (v2) of its own, and on Windows, its Job
+//! Object is limited. A check which is killed since it exceeded its memory limit is inconclusive,
+//! rather than incompatible, since an old toolchain which needs too much memory to build the
+//! crate doesn't tell whether the crate is compatible with it.
+
+use crate::error::CargoMSRVError;
+use crate::TResult;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ResourceLimits {
+    /// The memory which the processes of a check may use together, in bytes.
+    pub memory: Option<u64>,
+    /// The number of CPUs of which the processes of a check may use the time together.
+    pub cpus: Option<f64>,
+}
+
+impl ResourceLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.memory.is_none() && self.cpus.is_none()
+    }
+
+    /// Make sure the limits can be applied to the checks, before the first check runs.
+    pub fn prepare(&self) -> TResult<()> {
+        if self.is_unlimited() {
+            return Ok(());
+        }
+
+        prepare_platform().map_err(|error| CargoMSRVError::UnableToLimitResources { error })
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn prepare_platform() -> std::io::Result<()> {
+    crate::external_command::cgroup::prepare().map(|_| ())
+}
+
+#[cfg(windows)]
+fn prepare_platform() -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn prepare_platform() -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "resource limits are only supported on Linux and Windows",
+    ))
+}
+
+/// Parse the number of CPUs of `--cpu-limit`, like `2` or `0.5`.
+pub fn parse_cpus(input: &str) -> Result<f64, ParseCpusError> {
+    match input.trim().parse::<f64>() {
+        Ok(cpus) if cpus.is_finite() && cpus > 0.0 => Ok(cpus),
+        _ => Err(ParseCpusError {
+            input: input.to_string(),
+        }),
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("'{input}' is not a valid number of CPUs, expected a positive number like '2' or '0.5'")]
+pub struct ParseCpusError {
+    input: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        whole = { "2", Some(2.0) },
+        fraction = { "0.5", Some(0.5) },
+        padded = { " 1.5 ", Some(1.5) },
+        zero = { "0", None },
+        negative = { "-1", None },
+        infinite = { "inf", None },
+        not_a_number = { "two", None },
+    )]
+    fn cpus(input: &str, expected: Option<f64>) {
+        assert_eq!(parse_cpus(input).ok(), expected);
+    }
+
+    #[test]
+    fn unlimited() {
+        assert!(ResourceLimits::default().is_unlimited());
+        assert!(ResourceLimits::default().prepare().is_ok());
+        assert!(!ResourceLimits {
+            memory: Some(1 << 30),
+            cpus: None
+        }
+        .is_unlimited());
+    }
+}
@@ -0,0 +1,341 @@
+//! Structured artifacts of a `find` or `verify` run: the output of each check, the diagnostics
+//! parsed from it, and how long each check took.
+//!
+//! The artifacts of a run are stored under `.cargo-msrv/runs/<run-id>/`, in the root of the crate,
+//! and can be browsed afterwards with `cargo msrv inspect`. The summary of the run (`run.json`)
+//! is rewritten after every check, so the information gathered so far is kept, even if the run
+//! is interrupted.
+
+use crate::check::diagnostics::diagnostics;
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::reporter::event::{AuxiliaryOutput, AuxiliaryOutputItem, Destination};
+use crate::reporter::Reporter;
+use crate::{semver, Outcome};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The directory, relative to the root of the crate, in which the runs are stored.
+pub const RUNS_DIR: &str = ".cargo-msrv/runs";
+
+/// The name of the file which holds the [`Run`] summary, within the directory of a run.
+pub const SUMMARY_FILE: &str = "run.json";
+
+/// The summary of a run, as stored in its `run.json`.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Run {
+    pub run_id: String,
+    /// The subcommand which was run, i.e. `find` or `verify`.
+    pub subcommand: String,
+    pub started_at: String,
+    /// Not set if the run did not finish, e.g. because it was interrupted.
+    pub finished_at: Option<String>,
+    /// The MSRV, if it was found by the run.
+    pub msrv: Option<semver::Version>,
+    /// The checks, in the order in which they ran.
+    pub checks: Vec<CheckArtifact>,
+}
+
+/// The artifacts of a single check within a [`Run`].
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CheckArtifact {
+    pub version: semver::Version,
+    pub target: String,
+    pub compatible: bool,
+    pub duration_ms: u64,
+    /// The file with the complete output of the check, relative to the directory of the run.
+    pub log: String,
+    /// The diagnostics emitted by the check, without cargo's status lines.
+    pub diagnostics: Vec<String>,
+}
+
+/// Writes the artifacts of a run, as the checks complete.
+pub struct RunArtifacts {
+    dir: Utf8PathBuf,
+    run: Mutex<Run>,
+}
+
+impl RunArtifacts {
+    /// Create the directory of a new run of the given `subcommand`, within the crate at `root`.
+    ///
+    /// The run is identified by the (UTC) time at which it started.
+    pub fn create(root: &Utf8Path, subcommand: &str) -> TResult<Self> {
+        let now = Timestamp::from(SystemTime::now());
+        let runs = root.join(RUNS_DIR);
+
+        create_dir_all(&runs)?;
+        ignore_artifacts(&runs)?;
+
+        let (run_id, dir) = create_run_dir(&runs, &now.compact())?;
+
+        let run = Run {
+            run_id,
+            subcommand: subcommand.to_string(),
+            started_at: now.to_string(),
+            finished_at: None,
+            msrv: None,
+            checks: Vec::new(),
+        };
+
+        write_summary(&dir, &run)?;
+
+        Ok(Self {
+            dir,
+            run: Mutex::new(run),
+        })
+    }
+
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+
+    /// Store the output of a completed check, and add it to the summary of the run.
+    pub fn record(&self, outcome: &Outcome, duration: Duration) -> TResult<()> {
+        let mut run = self.run.lock().expect("run artifacts lock is not poisoned");
+
+        let toolchain = outcome.toolchain_spec();
+        let log = format!("{:03}-{}.log", run.checks.len() + 1, toolchain.version());
+        write(&self.dir.join(&log), outcome.output())?;
+
+        run.checks.push(CheckArtifact {
+            version: toolchain.version().clone(),
+            target: toolchain.target().to_string(),
+            compatible: outcome.is_success(),
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            log,
+            diagnostics: diagnostics(outcome.output()),
+        });
+
+        write_summary(&self.dir, &run)
+    }
+
+    /// Mark the run as finished, with the MSRV if one was found, and report where the artifacts
+    /// are stored.
+    pub fn finish(&self, reporter: &impl Reporter, msrv: Option<&semver::Version>) -> TResult<()> {
+        let run_id = {
+            let mut run = self.run.lock().expect("run artifacts lock is not poisoned");
+            run.finished_at = Some(Timestamp::from(SystemTime::now()).to_string());
+            run.msrv = msrv.cloned();
+            write_summary(&self.dir, &run)?;
+
+            run.run_id.clone()
+        };
+
+        reporter.report_event(AuxiliaryOutput::new(
+            Destination::directory(self.dir.clone()),
+            AuxiliaryOutputItem::run_artifacts(run_id),
+        ))?;
+
+        Ok(())
+    }
+}
+
+fn write_summary(dir: &Utf8Path, run: &Run) -> TResult<()> {
+    let contents = serde_json::to_string_pretty(run).expect("a run can be serialized");
+    write(&dir.join(SUMMARY_FILE), &contents)
+}
+
+/// Create the directory of a run. If a run with the same id exists already, e.g. because two
+/// runs started within the same second, a counter is appended to the id.
+fn create_run_dir(runs: &Utf8Path, id: &str) -> TResult<(String, Utf8PathBuf)> {
+    let mut run_id = id.to_string();
+    let mut attempt = 1;
+
+    loop {
+        let dir = runs.join(&run_id);
+
+        match std::fs::create_dir(&dir) {
+            Ok(()) => return Ok((run_id, dir)),
+            Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {
+                attempt += 1;
+                run_id = format!("{}-{}", id, attempt);
+            }
+            Err(error) => {
+                return Err(IoError {
+                    error,
+                    source: IoErrorSource::CreateDir(dir),
+                }
+                .into())
+            }
+        }
+    }
+}
+
+/// Keep the artifacts out of version control, like Cargo does for its `target` directory. The
+/// `.cargo-msrv` directory is ignored as a whole, by a `.gitignore` next to the given path.
+pub(crate) fn ignore_artifacts(within: &Utf8Path) -> TResult<()> {
+    let Some(parent) = within.parent() else {
+        return Ok(());
+    };
+
+    let gitignore = parent.join(".gitignore");
+
+    if gitignore.exists() {
+        return Ok(());
+    }
+
+    write(&gitignore, "*\n")
+}
+
+fn create_dir_all(path: &Utf8Path) -> TResult<()> {
+    std::fs::create_dir_all(path).map_err(|error| {
+        IoError {
+            error,
+            source: IoErrorSource::CreateDir(path.to_path_buf()),
+        }
+        .into()
+    })
+}
+
+fn write(path: &Utf8Path, contents: &str) -> TResult<()> {
+    std::fs::write(path, contents).map_err(|error| {
+        IoError {
+            error,
+            source: IoErrorSource::WriteFile(path.to_path_buf()),
+        }
+        .into()
+    })
+}
+
+/// A UTC date and time, with a precision of seconds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Timestamp {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+}
+
+impl Timestamp {
+    /// The timestamp without separators, e.g. `20240131T120000Z`, to be used in paths.
+    fn compact(&self) -> String {
+        format!(
+            "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+
+        let (days, seconds_of_day) = (seconds / 86_400, seconds % 86_400);
+
+        // The civil date of a day since the Unix epoch, after Howard Hinnant's `civil_from_days`
+        let z = days as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year,
+            month: month as u32,
+            day: day as u32,
+            hour: (seconds_of_day / 3600) as u32,
+            minute: (seconds_of_day % 3600 / 60) as u32,
+            second: (seconds_of_day % 60) as u32,
+        }
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::TestReporterWrapper;
+    use crate::rust::Toolchain;
+
+    #[yare::parameterized(
+        epoch = { 0, "1970-01-01T00:00:00Z", "19700101T000000Z" },
+        leap_day = { 951_782_400, "2000-02-29T00:00:00Z", "20000229T000000Z" },
+        end_of_year = { 1_704_067_199, "2023-12-31T23:59:59Z", "20231231T235959Z" },
+        afternoon = { 1_718_458_245, "2024-06-15T13:30:45Z", "20240615T133045Z" },
+    )]
+    fn timestamp(seconds: u64, expected: &str, compact: &str) {
+        let timestamp = Timestamp::from(UNIX_EPOCH + Duration::from_secs(seconds));
+
+        assert_eq!(timestamp.to_string(), expected);
+        assert_eq!(timestamp.compact(), compact);
+    }
+
+    #[test]
+    fn record_run() {
+        let root = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(root.path()).unwrap();
+        let reporter = TestReporterWrapper::default();
+
+        let artifacts = RunArtifacts::create(root, "find").unwrap();
+
+        let failure = Outcome::new_failure(
+            Toolchain::new(semver::Version::new(1, 60, 0), "x", &[]),
+            "error[E0658]: use of unstable library feature\n".to_string(),
+        );
+        let success =
+            Outcome::new_success(Toolchain::new(semver::Version::new(1, 61, 0), "x", &[]));
+
+        artifacts
+            .record(&failure, Duration::from_millis(1500))
+            .unwrap();
+        artifacts
+            .record(&success, Duration::from_millis(800))
+            .unwrap();
+        artifacts
+            .finish(reporter.get(), Some(&semver::Version::new(1, 61, 0)))
+            .unwrap();
+
+        let contents = std::fs::read_to_string(artifacts.dir().join(SUMMARY_FILE)).unwrap();
+        let run: Run = serde_json::from_str(&contents).unwrap();
+
+        assert_eq!(run.subcommand, "find");
+        assert!(run.finished_at.is_some());
+        assert_eq!(run.msrv, Some(semver::Version::new(1, 61, 0)));
+        assert_eq!(run.checks.len(), 2);
+        assert_eq!(run.checks[0].log, "001-1.60.0.log");
+        assert_eq!(run.checks[0].duration_ms, 1500);
+        assert!(!run.checks[0].compatible);
+        assert_eq!(
+            run.checks[0].diagnostics,
+            vec!["error[E0658]: use of unstable library feature"]
+        );
+        assert!(run.checks[1].compatible);
+
+        let log = std::fs::read_to_string(artifacts.dir().join("001-1.60.0.log")).unwrap();
+        assert_eq!(log, failure.output());
+        assert!(root.join(".cargo-msrv/.gitignore").exists());
+    }
+
+    #[test]
+    fn runs_started_within_the_same_second() {
+        let root = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(root.path()).unwrap();
+        let runs = root.join(RUNS_DIR);
+        create_dir_all(&runs).unwrap();
+
+        let (first, _) = create_run_dir(&runs, "20240615T133045Z").unwrap();
+        let (second, _) = create_run_dir(&runs, "20240615T133045Z").unwrap();
+
+        assert_eq!(first, "20240615T133045Z");
+        assert_eq!(second, "20240615T133045Z-2");
+    }
+}
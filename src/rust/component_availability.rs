@@ -0,0 +1,239 @@
+use crate::cache::GlobalCache;
+use crate::error::{CargoMSRVError, TResult};
+use crate::reporter::event::Warning;
+use crate::reporter::Reporter;
+use crate::rust::dist_server;
+use crate::semver;
+use rust_releases::Release;
+use rust_releases_io::{CachedClient, ResourceFile, RustReleasesClient};
+use std::time::Duration;
+use toml_edit::{DocumentMut, Item};
+
+// The channel manifest of a stable release does not change after it has been published,
+// so we can cache it for a long time.
+const CHANNEL_MANIFEST_CACHE_TIMEOUT: Duration = Duration::from_secs(30 * 86_400);
+
+/// The section of the global cache which holds the channel manifests.
+const CACHE_SECTION: &str = "channel-manifests";
+
+/// Determines whether a set of toolchain components was distributed for a certain target, based
+/// on the channel manifests of Rust releases.
+pub struct ComponentAvailability<'ctx> {
+    cache: GlobalCache,
+    client: CachedClient,
+    target: &'ctx str,
+    components: &'ctx [&'ctx str],
+}
+
+impl<'ctx> ComponentAvailability<'ctx> {
+    pub fn new(target: &'ctx str, components: &'ctx [&'ctx str]) -> TResult<Self> {
+        let cache = GlobalCache::new().ok_or(CargoMSRVError::UnableToCacheChannelManifest)?;
+        let client = CachedClient::new(
+            cache.section(CACHE_SECTION).into(),
+            CHANNEL_MANIFEST_CACHE_TIMEOUT,
+        );
+
+        Ok(Self {
+            cache,
+            client,
+            target,
+            components,
+        })
+    }
+
+    /// Whether all components are available for the given Rust version.
+    pub fn is_available(&self, version: &semver::Version) -> TResult<bool> {
+        let manifest = self.fetch_channel_manifest(version)?;
+
+        Ok(self
+            .components
+            .iter()
+            .all(|component| is_component_available(&manifest, component, self.target)))
+    }
+
+    /// Only keep the releases for which all components are available. A release of which the
+    /// channel manifest can't be fetched is kept, since its availability is unknown, with a
+    /// warning.
+    pub fn filter(
+        &self,
+        reporter: &impl Reporter,
+        releases: Vec<Release>,
+    ) -> TResult<Vec<Release>> {
+        filter_releases(reporter, releases, |version| self.is_available(version))
+    }
+
+    fn fetch_channel_manifest(&self, version: &semver::Version) -> TResult<DocumentMut> {
+        let name = format!("channel-rust-{}.toml", version);
+        let url = dist_server::get().dist_url(&name);
+
+        let document = self
+            .client
+            .fetch(ResourceFile::new(&url, &name))
+            .map_err(|error| CargoMSRVError::FetchChannelManifest {
+                version: version.clone(),
+                error,
+            })?;
+
+        self.cache.touch(CACHE_SECTION, &name);
+
+        let contents = String::from_utf8_lossy(document.into_document().buffer()).into_owned();
+
+        contents
+            .parse::<DocumentMut>()
+            .map_err(CargoMSRVError::ParseToml)
+    }
+}
+
+fn filter_releases(
+    reporter: &impl Reporter,
+    releases: Vec<Release>,
+    is_available: impl Fn(&semver::Version) -> TResult<bool>,
+) -> TResult<Vec<Release>> {
+    let mut available = Vec::with_capacity(releases.len());
+
+    for release in releases {
+        match is_available(release.version()) {
+            Ok(true) => available.push(release),
+            Ok(false) => {
+                info!(
+                    version = %release.version(),
+                    "excluded release, because the required components are not available"
+                );
+            }
+            Err(error) => {
+                reporter.report_event(Warning::component_availability_unknown(
+                    release.version(),
+                    &error,
+                ))?;
+                available.push(release);
+            }
+        }
+    }
+
+    Ok(available)
+}
+
+/// Whether the given component is available for the target, according to the channel manifest.
+///
+/// Components may have been distributed under a different name in the past, for example
+/// `clippy-preview` instead of `clippy`. Such names are resolved via the `renames` table of
+/// the manifest, or with the `-preview` suffix for releases before these renames were recorded.
+fn is_component_available(manifest: &DocumentMut, component: &str, target: &str) -> bool {
+    let Some(packages) = manifest.get("pkg") else {
+        return false;
+    };
+
+    let renamed = manifest
+        .get("renames")
+        .and_then(|renames| renames.get(component))
+        .and_then(|rename| rename.get("to"))
+        .and_then(Item::as_str);
+
+    let preview = format!("{}-preview", component);
+
+    let package = renamed
+        .and_then(|name| packages.get(name))
+        .or_else(|| packages.get(component))
+        .or_else(|| packages.get(&preview));
+
+    let Some(targets) = package.and_then(|package| package.get("target")) else {
+        return false;
+    };
+
+    // Target independent components, like `rust-src`, are listed under the `*` target.
+    [target, "*"].iter().any(|target| {
+        targets
+            .get(target)
+            .and_then(|target| target.get("available"))
+            .and_then(Item::as_bool)
+            .unwrap_or_default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::event::{Message, WarningCode};
+    use crate::reporter::TestReporterWrapper;
+
+    const MANIFEST: &str = r#"
+[pkg.clippy-preview]
+version = "0.1.70"
+[pkg.clippy-preview.target.x86_64-unknown-linux-gnu]
+available = true
+
+[pkg.clippy-preview.target.aarch64-unknown-linux-gnu]
+available = false
+
+[pkg.rust-src]
+version = "1.70.0"
+[pkg.rust-src.target."*"]
+available = true
+
+[pkg.rustfmt-preview]
+version = "1.5.2"
+[pkg.rustfmt-preview.target.x86_64-unknown-linux-gnu]
+available = true
+
+[pkg.miri-preview]
+version = "0.1.0"
+[pkg.miri-preview.target.x86_64-unknown-linux-gnu]
+available = false
+
+[renames.clippy]
+to = "clippy-preview"
+"#;
+
+    fn manifest() -> DocumentMut {
+        MANIFEST.parse().unwrap()
+    }
+
+    #[yare::parameterized(
+        renamed = { "clippy", "x86_64-unknown-linux-gnu" },
+        by_package_name = { "clippy-preview", "x86_64-unknown-linux-gnu" },
+        preview_suffix = { "rustfmt", "x86_64-unknown-linux-gnu" },
+        any_target = { "rust-src", "aarch64-unknown-linux-gnu" },
+    )]
+    fn available(component: &str, target: &str) {
+        assert!(is_component_available(&manifest(), component, target));
+    }
+
+    #[yare::parameterized(
+        not_available_for_target = { "clippy", "aarch64-unknown-linux-gnu" },
+        unknown_target = { "rustfmt", "x86_64-pc-windows-msvc" },
+        marked_unavailable = { "miri", "x86_64-unknown-linux-gnu" },
+        unknown_component = { "rust-analyzer", "x86_64-unknown-linux-gnu" },
+    )]
+    fn unavailable(component: &str, target: &str) {
+        assert!(!is_component_available(&manifest(), component, target));
+    }
+
+    #[test]
+    fn unknown_availability_is_kept() {
+        let reporter = TestReporterWrapper::default();
+        let releases = [60, 61, 62]
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0)))
+            .to_vec();
+
+        let kept = filter_releases(reporter.get(), releases, |version| match version.minor {
+            60 => Ok(false),
+            61 => Err(CargoMSRVError::UnableToCacheChannelManifest),
+            _ => Ok(true),
+        })
+        .unwrap();
+
+        assert_eq!(
+            kept.iter().map(Release::version).collect::<Vec<_>>(),
+            [
+                &semver::Version::new(1, 61, 0),
+                &semver::Version::new(1, 62, 0)
+            ]
+        );
+
+        let events = reporter.wait_for_events();
+        assert!(matches!(
+            events[0].message(),
+            Message::Warning(warning) if warning.code() == WarningCode::ComponentAvailabilityUnknown
+        ));
+    }
+}
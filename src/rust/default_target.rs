@@ -6,9 +6,12 @@ use crate::external_command::rustup_command::RustupCommand;
 pub fn default_target() -> TResult<String> {
     let output = RustupCommand::new().with_stdout().show()?;
 
-    let stdout = output.stdout();
+    parse_default_target(output.stdout())
+}
 
-    stdout
+/// Parse the default target from the output of `rustup show`.
+pub fn parse_default_target(rustup_show: &str) -> TResult<String> {
+    rustup_show
         .lines()
         .next()
         .ok_or(CargoMSRVError::DefaultHostTripleNotFound)
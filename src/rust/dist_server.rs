@@ -0,0 +1,228 @@
+//! The server from which rustup downloads the toolchains, and from which cargo-msrv fetches the
+//! channel manifests of the releases, e.g. a mirror of `static.rust-lang.org` for regions where
+//! it is slow or blocked.
+//!
+//! The server is, from the highest to the lowest precedence: `--dist-server`, the
+//! `RUSTUP_DIST_SERVER` environment variable, or the `mirror` table of the `cargo-msrv` metadata
+//! (see [`crate::manifest::mirror`]). The mirror is passed to each rustup command, so a run can
+//! use another mirror than the one of the environment.
+
+use crate::context::ToolchainProvider;
+use crate::error::{CargoMSRVError, TResult};
+use crate::manifest::mirror::MirrorConfig;
+use crate::rust::{installed_toolchains, is_installed, rustup_home};
+use crate::semver;
+use once_cell::sync::OnceCell;
+use std::process::Command;
+use std::time::Duration;
+
+/// The environment variable of rustup which supplies the dist server.
+pub const DIST_SERVER_ENV_VAR: &str = "RUSTUP_DIST_SERVER";
+
+/// The environment variable of rustup which supplies the server of its own updates.
+pub const UPDATE_ROOT_ENV_VAR: &str = "RUSTUP_UPDATE_ROOT";
+
+pub const DEFAULT_DIST_SERVER: &str = "https://static.rust-lang.org";
+
+/// How long the preflight, or a probe for a release, waits for the dist server to respond.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+static MIRROR: OnceCell<Mirror> = OnceCell::new();
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Mirror {
+    dist_server: Option<String>,
+    update_root: Option<String>,
+}
+
+impl Mirror {
+    /// The mirror of `--dist-server`, the environment, or the configuration, in that order, per
+    /// server.
+    pub fn resolve(
+        dist_server: Option<String>,
+        env: impl Fn(&str) -> Option<String>,
+        config: Option<MirrorConfig>,
+    ) -> Self {
+        let config = config.unwrap_or_default();
+        let non_empty = |value: String| (!value.is_empty()).then_some(value);
+
+        Self {
+            dist_server: dist_server
+                .or_else(|| env(DIST_SERVER_ENV_VAR).and_then(non_empty))
+                .or(config.dist_server)
+                .map(|url| url.trim_end_matches('/').to_string()),
+            update_root: env(UPDATE_ROOT_ENV_VAR)
+                .and_then(non_empty)
+                .or(config.update_root),
+        }
+    }
+
+    /// The dist server, without a trailing slash.
+    pub fn dist_server(&self) -> &str {
+        self.dist_server.as_deref().unwrap_or(DEFAULT_DIST_SERVER)
+    }
+
+    /// The URL of a file in the `dist` directory of the dist server, like a channel manifest.
+    pub fn dist_url(&self, file: &str) -> String {
+        format!("{}/dist/{}", self.dist_server(), file)
+    }
+
+    /// Pass the mirror to a rustup command, so it is used, also when the environment configures
+    /// another one.
+    pub fn configure(&self, command: &mut Command) {
+        if let Some(dist_server) = &self.dist_server {
+            command.env(DIST_SERVER_ENV_VAR, dist_server);
+        }
+
+        if let Some(update_root) = &self.update_root {
+            command.env(UPDATE_ROOT_ENV_VAR, update_root);
+        }
+    }
+
+    /// Whether the dist server responds, which is checked before the first toolchain is
+    /// installed, so a server which can't be reached fails the run right away, rather than with
+    /// the download error of rustup.
+    pub fn check_connectivity(&self) -> Result<(), String> {
+        // A small file, which every dist server has
+        let url = self.dist_url("channel-rust-stable.toml.sha256");
+
+        agent()
+            .head(&url)
+            .call()
+            .map(drop)
+            .map_err(|error| error.to_string())
+    }
+
+    /// Whether the Rust release was published to the dist server, by the checksum of its channel
+    /// manifest, which is much smaller than the manifest itself.
+    pub fn is_published(&self, version: &semver::Version) -> Result<bool, String> {
+        let url = self.dist_url(&format!("channel-rust-{}.toml.sha256", version));
+
+        match agent().head(&url).call() {
+            Ok(_) => Ok(true),
+            // A bucket may deny access to a file which does not exist, rather than report it missing
+            Err(ureq::Error::Status(403 | 404, _)) => Ok(false),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(REQUEST_TIMEOUT)
+        .try_proxy_from_env(true)
+        .build()
+}
+
+/// Check whether the dist server of the run responds, before the first check, if a toolchain of
+/// the given Rust versions may have to be installed with rustup.
+pub fn preflight<'v>(
+    provider: &ToolchainProvider,
+    candidates: impl IntoIterator<Item = &'v semver::Version>,
+) -> TResult<()> {
+    // The other providers don't download toolchains from the dist server, or not from this machine
+    if !matches!(provider, ToolchainProvider::Rustup) {
+        return Ok(());
+    }
+
+    let installed = rustup_home()
+        .map(|home| installed_toolchains(&home.join("toolchains")))
+        .unwrap_or_default();
+
+    if candidates
+        .into_iter()
+        .all(|version| is_installed(&installed, version))
+    {
+        return Ok(());
+    }
+
+    let mirror = get();
+    mirror
+        .check_connectivity()
+        .map_err(|error| CargoMSRVError::DistServerUnreachable {
+            url: mirror.dist_server().to_string(),
+            error,
+        })
+}
+
+/// Use the mirror for the rest of the run. Only the first mirror which is installed is used.
+pub fn install(mirror: Mirror) {
+    let _ = MIRROR.set(mirror);
+}
+
+/// The mirror of the run, or `static.rust-lang.org` if none was installed.
+pub fn get() -> &'static Mirror {
+    MIRROR.get_or_init(Mirror::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirror_config(dist_server: &str) -> Option<MirrorConfig> {
+        Some(MirrorConfig {
+            dist_server: Some(dist_server.to_string()),
+            update_root: Some("https://config.example/rustup".to_string()),
+        })
+    }
+
+    #[yare::parameterized(
+        flag = { Some("https://flag.example"), Some("https://env.example"), "https://flag.example" },
+        env = { None, Some("https://env.example/"), "https://env.example" },
+        empty_env = { None, Some(""), "https://config.example" },
+        config = { None, None, "https://config.example" },
+    )]
+    fn dist_server_precedence(flag: Option<&str>, env: Option<&str>, expected: &str) {
+        let mirror = Mirror::resolve(
+            flag.map(String::from),
+            |var| {
+                (var == DIST_SERVER_ENV_VAR)
+                    .then(|| env.map(String::from))
+                    .flatten()
+            },
+            mirror_config("https://config.example"),
+        );
+
+        assert_eq!(mirror.dist_server(), expected);
+    }
+
+    #[test]
+    fn default_dist_server() {
+        let mirror = Mirror::resolve(None, |_| None, None);
+
+        assert_eq!(mirror, Mirror::default());
+        assert_eq!(
+            mirror.dist_url("channel-rust-1.70.0.toml"),
+            "https://static.rust-lang.org/dist/channel-rust-1.70.0.toml"
+        );
+    }
+
+    #[test]
+    fn configured_for_rustup() {
+        let mirror = Mirror::resolve(
+            Some("https://flag.example".to_string()),
+            |_| None,
+            mirror_config("https://config.example"),
+        );
+        let mut command = Command::new("rustup");
+        mirror.configure(&mut command);
+
+        let envs = command
+            .get_envs()
+            .map(|(key, value)| {
+                (
+                    key.to_str().unwrap(),
+                    value.and_then(|value| value.to_str()),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            envs,
+            vec![
+                (DIST_SERVER_ENV_VAR, Some("https://flag.example")),
+                (UPDATE_ROOT_ENV_VAR, Some("https://config.example/rustup")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! A `rustc` and `cargo` pair which is not managed by rustup, like the Rust toolchain packaged by
+//! a Linux distribution.
+
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use crate::rust::Toolchain;
+use crate::semver;
+use camino::{Utf8Path, Utf8PathBuf};
+use std::process::Command;
+
+/// A `rustc` and `cargo` pair, given by their paths.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionToolchain {
+    rustc: Utf8PathBuf,
+    cargo: Utf8PathBuf,
+    version: semver::Version,
+    host: String,
+}
+
+impl DistributionToolchain {
+    pub fn new(
+        rustc: Utf8PathBuf,
+        cargo: Utf8PathBuf,
+        version: semver::Version,
+        host: impl Into<String>,
+    ) -> Self {
+        Self {
+            rustc,
+            cargo,
+            version,
+            host: host.into(),
+        }
+    }
+
+    /// Determine the version and host target of the given `rustc`, by running `rustc -vV`.
+    pub fn probe(rustc: Utf8PathBuf, cargo: Utf8PathBuf) -> TResult<Self> {
+        let output = Command::new(&rustc)
+            .arg("-vV")
+            .output()
+            .map_err(|error| IoError {
+                error,
+                source: IoErrorSource::SpawnProcess(rustc.as_os_str().to_owned()),
+            })?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        Self::from_verbose_version(rustc.clone(), cargo, &stdout)
+            .ok_or(CargoMSRVError::UnableToProbeRustc { rustc })
+    }
+
+    fn from_verbose_version(
+        rustc: Utf8PathBuf,
+        cargo: Utf8PathBuf,
+        verbose_version: &str,
+    ) -> Option<Self> {
+        let field = |name: &str| {
+            verbose_version
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+                .map(str::trim)
+        };
+
+        // Pre-release channels (e.g. `1.80.0-beta.2` or `1.81.0-nightly`) are checked as the
+        // release they precede.
+        let mut version = semver::Version::parse(field("release")?).ok()?;
+        version.pre = semver::Prerelease::EMPTY;
+
+        Some(Self {
+            rustc,
+            cargo,
+            version,
+            host: field("host")?.to_string(),
+        })
+    }
+
+    pub fn rustc(&self) -> &Utf8Path {
+        &self.rustc
+    }
+
+    pub fn cargo(&self) -> &Utf8Path {
+        &self.cargo
+    }
+
+    /// The Rust version of `rustc`.
+    pub fn version(&self) -> &semver::Version {
+        &self.version
+    }
+
+    /// The host target of `rustc`.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// The toolchain, named after the path of `rustc`, as it is reported.
+    pub fn toolchain(&self, target: &'static str) -> Toolchain {
+        Toolchain::custom(self.rustc.as_str(), self.version.clone(), target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        stable = { "rustc 1.63.0\nbinary: rustc\ncommit-hash: unknown\ncommit-date: unknown\nhost: x86_64-unknown-linux-gnu\nrelease: 1.63.0\nLLVM version: 14.0.6\n", semver::Version::new(1, 63, 0) },
+        beta = { "rustc 1.80.0-beta.2\nhost: aarch64-unknown-linux-gnu\nrelease: 1.80.0-beta.2\n", semver::Version::new(1, 80, 0) },
+    )]
+    fn parse_verbose_version(verbose_version: &str, expected: semver::Version) {
+        let toolchain = DistributionToolchain::from_verbose_version(
+            Utf8PathBuf::from("/usr/bin/rustc"),
+            Utf8PathBuf::from("/usr/bin/cargo"),
+            verbose_version,
+        )
+        .unwrap();
+
+        assert_eq!(toolchain.version(), &expected);
+        assert!(toolchain.host().ends_with("-unknown-linux-gnu"));
+    }
+
+    #[test]
+    fn parse_verbose_version_without_release() {
+        let toolchain = DistributionToolchain::from_verbose_version(
+            Utf8PathBuf::from("/usr/bin/rustc"),
+            Utf8PathBuf::from("/usr/bin/cargo"),
+            "rustc 1.63.0\nhost: x86_64-unknown-linux-gnu\n",
+        );
+
+        assert!(toolchain.is_none());
+    }
+
+    #[test]
+    fn toolchain_named_after_rustc() {
+        let toolchain = DistributionToolchain::from_verbose_version(
+            Utf8PathBuf::from("/usr/bin/rustc"),
+            Utf8PathBuf::from("/usr/bin/cargo"),
+            "host: x86_64-unknown-linux-gnu\nrelease: 1.63.0\n",
+        )
+        .unwrap()
+        .toolchain("x86_64-unknown-linux-gnu");
+
+        assert_eq!(toolchain.custom_name(), Some("/usr/bin/rustc"));
+        assert_eq!(toolchain.version(), &semver::Version::new(1, 63, 0));
+    }
+}
@@ -0,0 +1,115 @@
+//! Parses the progress of the downloads, from the output which rustup writes to stderr while it
+//! installs a toolchain, target or component.
+
+use crate::cache::parse_size;
+use crate::reporter::event::DownloadProgress;
+use crate::reporter::formatting::strip_escape_sequences;
+
+/// Makes rustup report the progress of its downloads, even if stderr is not a terminal.
+pub(crate) const PROGRESS_ENV_VAR: &str = "RUSTUP_TERM_PROGRESS_WHEN";
+
+#[derive(Debug, Default)]
+pub(crate) struct DownloadProgressParser {
+    /// The component which is being downloaded, as announced by rustup.
+    component: Option<String>,
+    last: Option<DownloadProgress>,
+}
+
+impl DownloadProgressParser {
+    /// Parse a line of rustup's output. Returns the progress of the download, if the line reports
+    /// progress which differs from the previously reported progress.
+    pub(crate) fn parse_line(&mut self, line: &str) -> Option<DownloadProgress> {
+        let line = strip_escape_sequences(line);
+        let line = line.trim();
+
+        // E.g. "info: downloading component 'rustc'"
+        if let Some(component) = line
+            .strip_prefix("info: downloading component ")
+            .map(|name| name.trim_matches(|c| c == '\'' || c == '`'))
+        {
+            self.component = Some(component.to_string());
+            return None;
+        }
+
+        let (downloaded, total, component) = parse_progress(line)?;
+        let component = component.or_else(|| self.component.clone());
+        let progress = DownloadProgress::new(component, downloaded, total);
+
+        if self.last.as_ref() == Some(&progress) {
+            return None;
+        }
+
+        self.last = Some(progress.clone());
+        Some(progress)
+    }
+}
+
+/// Parse a progress line, e.g. "  8.7 MiB /  12.1 MiB ( 72 %)   5.0 MiB/s in  1s ETA:  0s",
+/// optionally preceded by the name of the component.
+fn parse_progress(line: &str) -> Option<(u64, u64, Option<String>)> {
+    let (before, after) = line.split_once(" / ")?;
+
+    let before = before.split_whitespace().collect::<Vec<_>>();
+    let [prefix @ .., number, unit] = before.as_slice() else {
+        return None;
+    };
+
+    let mut after = after.split_whitespace();
+    let total = format!("{} {}", after.next()?, after.next()?);
+
+    let downloaded = parse_size(&format!("{} {}", number, unit)).ok()?;
+    let total = parse_size(&total).ok()?;
+
+    let component = match prefix {
+        [] => None,
+        [name] => Some(name.trim_end_matches(':').to_string()),
+        _ => return None,
+    };
+
+    Some((downloaded, total, component))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        rustup_1_27 = { "  8.7 MiB /  12.0 MiB ( 72 %)   5.0 MiB/s in  1s ETA:  0s", None, 9122611, 12582912 },
+        complete = { " 63.3 MiB /  63.3 MiB (100 %)  19.3 MiB/s in  3s ETA:  0s", None, 66374860, 66374860 },
+        bytes = { "512 B / 1.0 KiB ( 50 %)", None, 512, 1024 },
+        with_component = { "rustc  12.0 MiB / 63.3 MiB ( 19 %)  19.3 MiB/s", Some("rustc"), 12582912, 66374860 },
+        redrawn = { "\u{1b}[1A\u{1b}[2K  1.0 MiB /   2.0 MiB ( 50 %)", None, 1048576, 2097152 },
+    )]
+    fn progress(line: &str, component: Option<&str>, downloaded: u64, total: u64) {
+        let progress = DownloadProgressParser::default().parse_line(line).unwrap();
+
+        assert_eq!(progress.component(), component);
+        assert_eq!(progress.downloaded_bytes(), downloaded);
+        assert_eq!(progress.total_bytes(), total);
+    }
+
+    #[yare::parameterized(
+        info = { "info: installing component 'rustc'" },
+        installed = { "  1.60.0-x86_64-unknown-linux-gnu installed - rustc 1.60.0 (7737e0b5c 2022-04-04)" },
+        empty = { "" },
+        no_size = { "a / b" },
+    )]
+    fn no_progress(line: &str) {
+        assert!(DownloadProgressParser::default().parse_line(line).is_none());
+    }
+
+    #[test]
+    fn announced_component() {
+        let mut parser = DownloadProgressParser::default();
+
+        assert!(parser
+            .parse_line("info: downloading component 'cargo'")
+            .is_none());
+
+        let progress = parser.parse_line("  1.0 MiB /   2.0 MiB ( 50 %)").unwrap();
+        assert_eq!(progress.component(), Some("cargo"));
+
+        // Unchanged progress is not reported again
+        assert!(parser.parse_line("  1.0 MiB /   2.0 MiB ( 50 %)").is_none());
+    }
+}
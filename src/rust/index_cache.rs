@@ -0,0 +1,283 @@
+//! The release index of each release source, cached in the `release-index` section of the global
+//! cache, so runs in rapid succession don't each fetch the index again.
+//!
+//! A cached index is used until it is older than the TTL of `--index-ttl`. With `--refresh-index`,
+//! the index is always fetched anew, and with `--frozen-index`, it is never fetched, so only the
+//! cached index, or a release snapshot, can be used.
+//!
+//! A cached index which is older than the TTL is updated, rather than fetched anew: only the
+//! releases which were published after the most recent cached release are looked up, on the dist
+//! server (see [`crate::rust::dist_server`]). The index is only fetched anew if that fails, or with
+//! `--refresh-index`.
+
+use crate::cache::GlobalCache;
+use crate::context::{IndexRefresh, ReleaseSource};
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::semver;
+use rust_releases::{Release, ReleaseIndex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The section of the global cache which holds the release indices.
+const CACHE_SECTION: &str = "release-index";
+
+/// How long a cached release index is used, when no `--index-ttl` is given.
+pub const DEFAULT_INDEX_TTL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct CachedIndex {
+    /// The moment the index was fetched, in seconds since the Unix epoch.
+    fetched_at: u64,
+    releases: Vec<semver::Version>,
+}
+
+pub struct IndexCache {
+    cache: GlobalCache,
+}
+
+impl IndexCache {
+    /// The cache of release indices in the global cache, if it can be located.
+    pub fn new() -> Option<Self> {
+        GlobalCache::new().map(Self::in_cache)
+    }
+
+    pub fn in_cache(cache: GlobalCache) -> Self {
+        Self { cache }
+    }
+
+    /// The cached index of the first source of the chain which has one which may be used, and the
+    /// age of that index.
+    pub fn lookup(
+        &self,
+        sources: impl IntoIterator<Item = ReleaseSource>,
+        refresh: IndexRefresh,
+    ) -> Option<(ReleaseSource, ReleaseIndex, Duration)> {
+        sources.into_iter().find_map(|source| {
+            let (index, age) = match refresh {
+                IndexRefresh::Always => return None,
+                IndexRefresh::Stale { ttl } => self.load(source).filter(|(_, age)| *age < ttl)?,
+                IndexRefresh::Never => self.load(source)?,
+            };
+
+            Some((source, index, age))
+        })
+    }
+
+    /// The cached index of the source, and its age, if any. An index which can't be read is
+    /// treated like an index which isn't cached.
+    fn load(&self, source: ReleaseSource) -> Option<(ReleaseIndex, Duration)> {
+        let name = file_name(source);
+        let contents =
+            std::fs::read_to_string(self.cache.section(CACHE_SECTION).join(&name)).ok()?;
+
+        let cached = match serde_json::from_str::<CachedIndex>(&contents) {
+            Ok(cached) => cached,
+            Err(error) => {
+                info!(%error, %source, "ignored the unreadable cached release index");
+                return None;
+            }
+        };
+
+        self.cache.touch(CACHE_SECTION, &name);
+
+        let age = Duration::from_secs(now().saturating_sub(cached.fetched_at));
+        let index = ReleaseIndex::from_iter(cached.releases.into_iter().map(Release::new_stable));
+
+        Some((index, age))
+    }
+
+    /// Cache the index which the source served just now. A snapshot is read from a local file, so
+    /// it is not cached.
+    pub fn store(&self, source: ReleaseSource, index: &ReleaseIndex) -> TResult<()> {
+        if source == ReleaseSource::Snapshot {
+            return Ok(());
+        }
+
+        let dir = self.cache.section(CACHE_SECTION);
+        std::fs::create_dir_all(&dir).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::CreateDir(dir.clone()),
+        })?;
+
+        let cached = CachedIndex {
+            fetched_at: now(),
+            releases: index
+                .releases()
+                .iter()
+                .map(|release| release.version().clone())
+                .collect(),
+        };
+
+        let name = file_name(source);
+        let path = dir.join(&name);
+        let contents = serde_json::to_string(&cached).expect("a release index can be serialized");
+
+        std::fs::write(&path, contents).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::WriteFile(path),
+        })?;
+
+        self.cache.touch(CACHE_SECTION, &name);
+
+        Ok(())
+    }
+}
+
+/// The releases which were published after the most recent release of the cached index, from the
+/// least to the most recent. These are found by probing the successors of the most recent release,
+/// its next patch release and the first release of its next minor version, until neither was
+/// published. A patch release of an older minor version, published after a more recent release,
+/// is not found, but such releases are rare.
+pub fn newer_releases(
+    cached: &ReleaseIndex,
+    is_published: impl Fn(&semver::Version) -> Result<bool, String>,
+) -> Result<Vec<semver::Version>, String> {
+    let Some(mut latest) = cached
+        .releases()
+        .iter()
+        .map(|release| release.version().clone())
+        .max()
+    else {
+        return Err("the cached index has no releases".to_string());
+    };
+
+    let mut newer = Vec::new();
+
+    'probe: loop {
+        let successors = [
+            semver::Version::new(latest.major, latest.minor, latest.patch + 1),
+            semver::Version::new(latest.major, latest.minor + 1, 0),
+        ];
+
+        for successor in successors {
+            if is_published(&successor)? {
+                newer.push(successor.clone());
+                latest = successor;
+                continue 'probe;
+            }
+        }
+
+        return Ok(newer);
+    }
+}
+
+/// The cached index, with the newer releases added.
+pub fn with_newer_releases(cached: &ReleaseIndex, newer: &[semver::Version]) -> ReleaseIndex {
+    let newer = newer.iter().rev().cloned().map(Release::new_stable);
+    let cached = cached.releases().iter().cloned();
+
+    ReleaseIndex::from_iter(newer.chain(cached))
+}
+
+fn file_name(source: ReleaseSource) -> String {
+    format!("{}.json", source)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use camino::Utf8Path;
+
+    fn index_cache(dir: &assert_fs::TempDir) -> IndexCache {
+        let dir = Utf8Path::from_path(dir.path()).unwrap().to_path_buf();
+        IndexCache::in_cache(GlobalCache::at(dir))
+    }
+
+    fn index() -> ReleaseIndex {
+        ReleaseIndex::from_iter(vec![
+            Release::new_stable(semver::Version::new(1, 80, 1)),
+            Release::new_stable(semver::Version::new(1, 80, 0)),
+        ])
+    }
+
+    #[test]
+    fn stored_and_loaded() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = index_cache(&dir);
+
+        assert!(cache.load(ReleaseSource::RustChangelog).is_none());
+
+        cache.store(ReleaseSource::RustChangelog, &index()).unwrap();
+
+        let (loaded, age) = cache.load(ReleaseSource::RustChangelog).unwrap();
+        assert_eq!(loaded.releases(), index().releases());
+        assert!(age < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn snapshot_not_stored() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = index_cache(&dir);
+
+        cache.store(ReleaseSource::Snapshot, &index()).unwrap();
+
+        assert!(cache.load(ReleaseSource::Snapshot).is_none());
+    }
+
+    #[test]
+    fn newer_releases_probed() {
+        let published = [
+            semver::Version::new(1, 80, 2),
+            semver::Version::new(1, 81, 0),
+            semver::Version::new(1, 82, 0),
+            semver::Version::new(1, 82, 1),
+        ];
+
+        let newer = newer_releases(&index(), |version| Ok(published.contains(version))).unwrap();
+        assert_eq!(newer, published);
+
+        let updated = with_newer_releases(&index(), &newer);
+        let versions = updated
+            .releases()
+            .iter()
+            .map(|release| release.version().clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            versions,
+            vec![
+                semver::Version::new(1, 82, 1),
+                semver::Version::new(1, 82, 0),
+                semver::Version::new(1, 81, 0),
+                semver::Version::new(1, 80, 2),
+                semver::Version::new(1, 80, 1),
+                semver::Version::new(1, 80, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn newer_releases_unreachable() {
+        let newer = newer_releases(&index(), |_| Err("unreachable".to_string()));
+
+        assert!(newer.is_err());
+    }
+
+    #[yare::parameterized(
+        fresh = { IndexRefresh::Stale { ttl: Duration::from_secs(3600) }, true },
+        stale = { IndexRefresh::Stale { ttl: Duration::ZERO }, false },
+        refreshed = { IndexRefresh::Always, false },
+        frozen = { IndexRefresh::Never, true },
+    )]
+    fn lookup(refresh: IndexRefresh, expected: bool) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = index_cache(&dir);
+
+        cache.store(ReleaseSource::RustChangelog, &index()).unwrap();
+
+        let found = cache.lookup(
+            [ReleaseSource::Snapshot, ReleaseSource::RustChangelog],
+            refresh,
+        );
+
+        assert_eq!(
+            found.map(|(source, _, _)| source),
+            expected.then_some(ReleaseSource::RustChangelog)
+        );
+    }
+}
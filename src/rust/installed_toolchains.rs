@@ -0,0 +1,147 @@
+//! A record of the toolchains which were installed by cargo-msrv, so they can be uninstalled
+//! again by `cargo msrv clean --toolchains`, without touching the toolchains installed by the
+//! user.
+
+use crate::error::{IoError, IoErrorSource, TResult};
+use camino::Utf8PathBuf;
+
+/// The file, within the local data directory of cargo-msrv, which holds the record.
+const RECORD_FILE: &str = "installed-toolchains";
+
+pub struct InstalledToolchains {
+    path: Utf8PathBuf,
+}
+
+impl InstalledToolchains {
+    /// The record in the local data directory of cargo-msrv, if it can be located.
+    pub fn new() -> Option<Self> {
+        let dir = dirs::data_local_dir()?.join("cargo-msrv");
+        let dir = Utf8PathBuf::from_path_buf(dir).ok()?;
+
+        Some(Self::at(dir.join(RECORD_FILE)))
+    }
+
+    pub fn at(path: Utf8PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// The names of the recorded toolchains, e.g. `1.60.0-x86_64-unknown-linux-gnu`.
+    pub fn names(&self) -> TResult<Vec<String>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = std::fs::read_to_string(&self.path).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadFile(self.path.clone()),
+        })?;
+
+        Ok(contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    /// Add a toolchain to the record, unless it was recorded before.
+    pub fn record(&self, name: &str) -> TResult<()> {
+        let mut names = self.names()?;
+
+        if names.iter().any(|recorded| recorded == name) {
+            return Ok(());
+        }
+
+        names.push(name.to_string());
+        self.write(&names)
+    }
+
+    /// Remove the given toolchains from the record.
+    pub fn forget(&self, forgotten: &[String]) -> TResult<()> {
+        let mut names = self.names()?;
+        names.retain(|name| !forgotten.contains(name));
+
+        self.write(&names)
+    }
+
+    fn write(&self, names: &[String]) -> TResult<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::CreateDir(parent.to_path_buf()),
+            })?;
+        }
+
+        let mut contents = names.join("\n");
+        contents.push('\n');
+
+        std::fs::write(&self.path, contents).map_err(|error| {
+            IoError {
+                error,
+                source: IoErrorSource::WriteFile(self.path.clone()),
+            }
+            .into()
+        })
+    }
+}
+
+/// The name of the installed toolchain of the given Rust version for the given host, as listed by
+/// `rustup toolchain list`, e.g. `1.60.0-x86_64-unknown-linux-gnu (default)`.
+pub fn listed_toolchain<'l>(toolchain_list: &'l str, version: &str, host: &str) -> Option<&'l str> {
+    let name = format!("{}-{}", version, host);
+
+    toolchain_list
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .find(|listed| *listed == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use camino::Utf8Path;
+
+    #[test]
+    fn record_and_forget() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let dir = Utf8Path::from_path(dir.path()).unwrap();
+        let record = InstalledToolchains::at(dir.join("nested").join(RECORD_FILE));
+
+        assert!(record.names().unwrap().is_empty());
+
+        record.record("1.60.0-x86_64-unknown-linux-gnu").unwrap();
+        record.record("1.61.0-x86_64-unknown-linux-gnu").unwrap();
+        record.record("1.60.0-x86_64-unknown-linux-gnu").unwrap();
+
+        assert_eq!(
+            record.names().unwrap(),
+            vec![
+                "1.60.0-x86_64-unknown-linux-gnu",
+                "1.61.0-x86_64-unknown-linux-gnu"
+            ]
+        );
+
+        record
+            .forget(&["1.60.0-x86_64-unknown-linux-gnu".to_string()])
+            .unwrap();
+
+        assert_eq!(
+            record.names().unwrap(),
+            vec!["1.61.0-x86_64-unknown-linux-gnu"]
+        );
+    }
+
+    #[yare::parameterized(
+        listed = { "stable-x86_64-unknown-linux-gnu (default)\n1.60.0-x86_64-unknown-linux-gnu\n", "1.60.0", Some("1.60.0-x86_64-unknown-linux-gnu") },
+        not_listed = { "stable-x86_64-unknown-linux-gnu (default)\n", "1.60.0", None },
+        other_patch = { "1.60.10-x86_64-unknown-linux-gnu\n", "1.60.1", None },
+        other_host = { "1.60.0-i686-unknown-linux-gnu\n", "1.60.0", None },
+        both_hosts = { "1.60.0-i686-unknown-linux-gnu\n1.60.0-x86_64-unknown-linux-gnu\n", "1.60.0", Some("1.60.0-x86_64-unknown-linux-gnu") },
+    )]
+    fn listed(toolchain_list: &str, version: &str, expected: Option<&str>) {
+        assert_eq!(
+            listed_toolchain(toolchain_list, version, "x86_64-unknown-linux-gnu"),
+            expected
+        );
+    }
+}
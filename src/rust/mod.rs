@@ -1,9 +1,69 @@
+pub(crate) mod component_availability;
 pub(crate) mod default_target;
+pub mod dist_server;
+pub mod distribution_toolchain;
+pub(crate) mod download_progress;
+pub(crate) mod index_cache;
+pub(crate) mod installed_toolchains;
+pub(crate) mod prerelease;
 mod release;
 pub mod release_index;
 pub(crate) mod releases_filter;
 pub(crate) mod setup_toolchain;
+pub mod tarball_toolchain;
 mod toolchain;
 
 pub use release::RustRelease;
 pub use toolchain::Toolchain;
+
+use crate::semver;
+use camino::{Utf8Path, Utf8PathBuf};
+
+/// The home directory of rustup, in which it installs the toolchains, if it can be located.
+pub(crate) fn rustup_home() -> Option<Utf8PathBuf> {
+    let home = std::env::var_os("RUSTUP_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".rustup")))?;
+
+    Utf8PathBuf::from_path_buf(home).ok()
+}
+
+/// Whether a toolchain of the Rust version is installed, for any target, given the names of the
+/// directories in the toolchains directory of rustup, e.g. `1.70.0-x86_64-unknown-linux-gnu`.
+pub(crate) fn is_installed(installed: &[String], version: &semver::Version) -> bool {
+    let prefix = format!("{}-", version);
+    installed.iter().any(|name| name.starts_with(&prefix))
+}
+
+/// The names of the directories in the toolchains directory of rustup.
+pub(crate) fn installed_toolchains(toolchains_dir: &Utf8Path) -> Vec<String> {
+    toolchains_dir
+        .read_dir_utf8()
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.file_name().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        installed = { "1.70.0", true },
+        other_patch = { "1.70.1", false },
+        prefix_of_other = { "1.7.0", false },
+    )]
+    fn installed_toolchain(version: &str, expected: bool) {
+        let installed = [
+            "1.70.0-x86_64-unknown-linux-gnu".to_string(),
+            "stable-x86_64-unknown-linux-gnu".to_string(),
+        ];
+        let version = semver::Version::parse(version).unwrap();
+
+        assert_eq!(is_installed(&installed, &version), expected);
+    }
+}
@@ -0,0 +1,71 @@
+//! The pre-release of Rust which `--include-prerelease` adds to the search space: the current
+//! build of the beta channel, like `1.83.0-beta.3`, taken from the channel manifest of the beta
+//! channel on the dist server (see [`crate::rust::dist_server`]).
+//!
+//! A pre-release is only a candidate: it is checked like any release, but it is never reported,
+//! or written, as the MSRV.
+
+use crate::cache::GlobalCache;
+use crate::error::{CargoMSRVError, TResult};
+use crate::rust::dist_server;
+use crate::semver;
+use rust_releases_io::{CachedClient, ResourceFile, RustReleasesClient};
+use std::time::Duration;
+use toml_edit::DocumentMut;
+
+/// The beta channel is rebuilt regularly, so its manifest is cached only briefly.
+const BETA_MANIFEST_CACHE_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// The section of the global cache which holds the channel manifests.
+const CACHE_SECTION: &str = "channel-manifests";
+
+const BETA_MANIFEST: &str = "channel-rust-beta.toml";
+
+/// The Rust version of the current build of the beta channel.
+pub fn beta_version() -> TResult<semver::Version> {
+    let cache = GlobalCache::new().ok_or(CargoMSRVError::UnableToCacheChannelManifest)?;
+    let client = CachedClient::new(
+        cache.section(CACHE_SECTION).into(),
+        BETA_MANIFEST_CACHE_TIMEOUT,
+    );
+
+    let url = dist_server::get().dist_url(BETA_MANIFEST);
+    let document = client
+        .fetch(ResourceFile::new(&url, BETA_MANIFEST))
+        .map_err(|error| CargoMSRVError::FetchBetaChannelManifest { error })?;
+
+    cache.touch(CACHE_SECTION, BETA_MANIFEST);
+
+    let contents = String::from_utf8_lossy(document.into_document().buffer()).into_owned();
+
+    beta_version_of_manifest(&contents).ok_or(CargoMSRVError::InvalidBetaChannelManifest)
+}
+
+/// The version of the `rust` package of the channel manifest, like `1.83.0-beta.3 (...)`.
+fn beta_version_of_manifest(manifest: &str) -> Option<semver::Version> {
+    let document = manifest.parse::<DocumentMut>().ok()?;
+    let version = document.get("pkg")?.get("rust")?.get("version")?.as_str()?;
+    let version = version.split_whitespace().next()?;
+
+    semver::Version::parse(version)
+        .ok()
+        .filter(|version| !version.pre.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        beta = { "[pkg.rust]\nversion = \"1.83.0-beta.3 (0f8db1ae2 2024-10-12)\"\n", Some("1.83.0-beta.3") },
+        stable = { "[pkg.rust]\nversion = \"1.82.0 (f6e511eec 2024-10-15)\"\n", None },
+        missing = { "[pkg.cargo]\nversion = \"0.84.0-beta.3\"\n", None },
+        not_toml = { "version = ", None },
+    )]
+    fn version_of_manifest(manifest: &str, expected: Option<&str>) {
+        assert_eq!(
+            beta_version_of_manifest(manifest),
+            expected.map(|version| semver::Version::parse(version).unwrap())
+        );
+    }
+}
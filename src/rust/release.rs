@@ -1,4 +1,5 @@
 use crate::rust::Toolchain;
+use crate::semver;
 
 /// A `cargo-msrv` Rust release.
 ///
@@ -11,6 +12,8 @@ pub struct RustRelease {
     release: rust_releases::Release,
     target: &'static str,
     components: &'static [&'static str],
+    /// The name of a custom toolchain which is based on this release.
+    custom_name: Option<String>,
 }
 
 impl RustRelease {
@@ -23,13 +26,37 @@ impl RustRelease {
             release,
             target,
             components,
+            custom_name: None,
         }
     }
 
+    /// A custom toolchain, linked with `rustup toolchain link <name>`, which is based on the
+    /// given Rust version.
+    pub fn custom(name: impl Into<String>, version: semver::Version, target: &'static str) -> Self {
+        Self {
+            custom_name: Some(name.into()),
+            ..Self::new(rust_releases::Release::new_stable(version), target, &[])
+        }
+    }
+
+    /// Whether this is a custom toolchain.
+    pub fn is_custom(&self) -> bool {
+        self.custom_name.is_some()
+    }
+
+    /// The Rust version of the release.
+    pub fn version(&self) -> &semver::Version {
+        self.release.version()
+    }
+
     /// Get the [`Toolchain`] for the given Rust release.
     pub fn to_toolchain_spec(&self) -> Toolchain {
         let version = self.release.version();
-        Toolchain::new(version.clone(), self.target, self.components)
+
+        match &self.custom_name {
+            Some(name) => Toolchain::custom(name, version.clone(), self.target),
+            None => Toolchain::new(version.clone(), self.target, self.components),
+        }
     }
 }
 
@@ -1,27 +1,421 @@
-use crate::context::ReleaseSource;
-use crate::error::TResult;
-use crate::reporter::event::FetchIndex;
-use crate::reporter::Reporter;
+use crate::context::{ChainedReleaseSource, IndexRefresh, ReleaseSource, RustReleasesContext};
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use crate::reporter::event::{FetchIndex, Warning};
+use crate::reporter::{Event, Reporter};
+use crate::rust::dist_server;
+use crate::rust::index_cache::{newer_releases, with_newer_releases, IndexCache};
+use crate::semver;
+use camino::Utf8Path;
 #[cfg(feature = "rust-releases-dist-source")]
 use rust_releases::RustDist;
-use rust_releases::{Channel, FetchResources, ReleaseIndex, RustChangelog, Source};
+use rust_releases::{Channel, FetchResources, Release, ReleaseIndex, RustChangelog, Source};
+use std::collections::BTreeSet;
+use std::sync::mpsc::{self, RecvTimeoutError};
 
+/// Fetch the release index from the first release source of the chain which serves it, unless
+/// the cache holds a recent index of one of the sources, or a cached index can be updated with
+/// the releases published since, see [`crate::rust::index_cache`]. The
+/// `FetchIndex` event which ends the scope of the fetch reports which source that was.
+#[instrument(skip_all)]
 pub fn fetch_index(
     reporter: &impl Reporter,
-    release_source: ReleaseSource,
+    rust_releases: &RustReleasesContext,
 ) -> TResult<ReleaseIndex> {
-    reporter.run_scoped_event(FetchIndex::new(release_source), || {
-        let source: &'static str = release_source.into();
-        info!(source = source, "fetching index");
+    fetch_index_with_cache(
+        reporter,
+        rust_releases,
+        IndexCache::new().as_ref(),
+        |version| dist_server::get().is_published(version),
+    )
+}
+
+fn fetch_index_with_cache(
+    reporter: &impl Reporter,
+    rust_releases: &RustReleasesContext,
+    cache: Option<&IndexCache>,
+    is_published: impl Fn(&semver::Version) -> Result<bool, String>,
+) -> TResult<ReleaseIndex> {
+    let chain = match rust_releases.release_sources.as_slice() {
+        [] => vec![ChainedReleaseSource::default()],
+        sources => sources.to_vec(),
+    };
+
+    let event = FetchIndex::new(chain[0].source)
+        .with_fallbacks(chain[1..].iter().map(|chained| chained.source));
+    let (start_event, end_event) =
+        Event::from(event.clone()).into_scoped(reporter.scope_generator());
+
+    reporter.report_event(start_event)?;
+
+    let refresh = rust_releases.index_refresh;
+    let cached =
+        cache.and_then(|cache| cache.lookup(chain.iter().map(|chained| chained.source), refresh));
+
+    if let Some((source, index, age)) = cached {
+        info!(%source, age = age.as_secs(), "using the cached index");
+
+        let event = event.served_by(Some(source)).with_cache_age(Some(age));
+        reporter.report_event(end_event.with_message(event))?;
+
+        return Ok(index);
+    }
+
+    // A cached index of which the TTL expired is updated with the releases published since
+    let previous = cache.and_then(|cache| {
+        cache.lookup(
+            chain.iter().map(|chained| chained.source),
+            IndexRefresh::Never,
+        )
+    });
+
+    if let (IndexRefresh::Stale { .. }, Some(cache), Some((source, index, _))) =
+        (refresh, cache, &previous)
+    {
+        match newer_releases(index, &is_published) {
+            Ok(newer) => {
+                info!(%source, new_releases = newer.len(), "updated the cached index");
+
+                let updated = with_newer_releases(index, &newer);
+                store(cache, *source, &updated);
+
+                let event = event
+                    .served_by(Some(*source))
+                    .with_new_releases(true, Some(newer.len()));
+                reporter.report_event(end_event.with_message(event))?;
+
+                return Ok(updated);
+            }
+            Err(error) => info!(%error, "unable to update the cached index, fetching it anew"),
+        }
+    }
+
+    // A frozen index may still be read from a snapshot, since it is local
+    let chain = match refresh {
+        IndexRefresh::Never => chain
+            .into_iter()
+            .filter(|chained| chained.source == ReleaseSource::Snapshot)
+            .collect(),
+        _ => chain,
+    };
+
+    let result = if chain.is_empty() {
+        Err(CargoMSRVError::FrozenIndexNotCached)
+    } else {
+        fetch_from_chain(reporter, &chain, rust_releases.release_snapshot.as_deref())
+    };
 
-        let index = match release_source {
-            ReleaseSource::RustChangelog => {
-                RustChangelog::fetch_channel(Channel::Stable)?.build_index()?
+    if let (Some(cache), Ok((source, index))) = (cache, &result) {
+        store(cache, *source, index);
+    }
+
+    let event = match &result {
+        Ok((source, index)) => {
+            let new_releases = previous
+                .as_ref()
+                .map(|(_, previous, _)| count_new_releases(previous, index));
+
+            event
+                .served_by(Some(*source))
+                .with_new_releases(false, new_releases)
+        }
+        Err(_) => event,
+    };
+    reporter.report_event(end_event.with_message(event))?;
+
+    result.map(|(_, index)| index)
+}
+
+/// Caching is best effort: without it, the next run fetches the index again.
+fn store(cache: &IndexCache, source: ReleaseSource, index: &ReleaseIndex) {
+    if let Err(error) = cache.store(source, index) {
+        info!(%error, "unable to cache the release index");
+    }
+}
+
+/// The number of releases of the index which are not in the previous index.
+fn count_new_releases(previous: &ReleaseIndex, index: &ReleaseIndex) -> usize {
+    let previous = previous
+        .releases()
+        .iter()
+        .map(Release::version)
+        .collect::<BTreeSet<_>>();
+
+    index
+        .releases()
+        .iter()
+        .filter(|release| !previous.contains(release.version()))
+        .count()
+}
+
+/// Fetch the release index from each source of the chain in turn, until a source serves it.
+fn fetch_from_chain(
+    reporter: &impl Reporter,
+    chain: &[ChainedReleaseSource],
+    snapshot: Option<&Utf8Path>,
+) -> TResult<(ReleaseSource, ReleaseIndex)> {
+    let (last, preferred) = chain.split_last().expect("the chain has a release source");
+
+    for chained in preferred {
+        match fetch_with_timeout(*chained, snapshot) {
+            Ok(index) => return Ok((chained.source, index)),
+            Err(error) => {
+                info!(source = %chained.source, %error, "release source failed");
+                reporter.report_event(Warning::release_source_failed(chained.source, &error))?;
             }
-            #[cfg(feature = "rust-releases-dist-source")]
-            ReleaseSource::RustDist => RustDist::fetch_channel(Channel::Stable)?.build_index()?,
+        }
+    }
+
+    fetch_with_timeout(*last, snapshot).map(|index| (last.source, index))
+}
+
+fn fetch_with_timeout(
+    chained: ChainedReleaseSource,
+    snapshot: Option<&Utf8Path>,
+) -> TResult<ReleaseIndex> {
+    let Some(timeout) = chained.timeout else {
+        return fetch_source(chained.source, snapshot);
+    };
+
+    let snapshot = snapshot.map(Utf8Path::to_path_buf);
+    let (sender, receiver) = mpsc::channel();
+
+    // A fetch can't be cancelled, so a fetch which takes too long is left to finish by itself
+    std::thread::spawn(move || {
+        let _ = sender.send(fetch_source(chained.source, snapshot.as_deref()));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(CargoMSRVError::ReleaseSourceTimeout {
+            release_source: chained.source,
+            timeout,
+        }),
+        Err(RecvTimeoutError::Disconnected) => {
+            panic!("the fetch of release source '{}' panicked", chained.source)
+        }
+    }
+}
+
+fn fetch_source(source: ReleaseSource, snapshot: Option<&Utf8Path>) -> TResult<ReleaseIndex> {
+    info!(%source, "fetching index");
+
+    let index = match source {
+        ReleaseSource::RustChangelog => {
+            RustChangelog::fetch_channel(Channel::Stable)?.build_index()?
+        }
+        #[cfg(feature = "rust-releases-dist-source")]
+        ReleaseSource::RustDist => RustDist::fetch_channel(Channel::Stable)?.build_index()?,
+        ReleaseSource::Snapshot => {
+            read_snapshot(snapshot.ok_or(CargoMSRVError::ReleaseSnapshotMissing)?)?
+        }
+    };
+
+    Ok(index)
+}
+
+/// Read the Rust releases of a snapshot file, with a Rust version on each line.
+fn read_snapshot(path: &Utf8Path) -> TResult<ReleaseIndex> {
+    let contents = std::fs::read_to_string(path).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    parse_snapshot(path, &contents)
+}
+
+fn parse_snapshot(path: &Utf8Path, contents: &str) -> TResult<ReleaseIndex> {
+    let versions = contents
+        .lines()
+        .enumerate()
+        .map(|(number, line)| (number + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            semver::Version::parse(line).map_err(|error| CargoMSRVError::InvalidReleaseSnapshot {
+                path: path.to_path_buf(),
+                line: number,
+                error,
+            })
+        })
+        .collect::<TResult<BTreeSet<_>>>()?;
+
+    Ok(ReleaseIndex::from_iter(
+        versions.into_iter().rev().map(Release::new_stable),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::GlobalCache;
+    use crate::reporter::event::Message;
+    use crate::reporter::TestReporterWrapper;
+    use camino::Utf8PathBuf;
+    use std::time::Duration;
+
+    fn unpublished(_: &semver::Version) -> Result<bool, String> {
+        Ok(false)
+    }
+
+    fn snapshot(dir: &assert_fs::TempDir, contents: &str) -> Utf8PathBuf {
+        let path = Utf8Path::from_path(dir.path())
+            .unwrap()
+            .join("releases.txt");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn parsed_snapshot() {
+        let index = parse_snapshot(
+            Utf8Path::new("releases.txt"),
+            "# stable releases\n1.70.0\n\n1.80.1\n  1.75.0  \n1.70.0\n",
+        )
+        .unwrap();
+
+        let versions = index
+            .releases()
+            .iter()
+            .map(|release| release.version().clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            versions,
+            vec![
+                semver::Version::new(1, 80, 1),
+                semver::Version::new(1, 75, 0),
+                semver::Version::new(1, 70, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_snapshot() {
+        let result = parse_snapshot(Utf8Path::new("releases.txt"), "1.80.1\n1.79\n");
+
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::InvalidReleaseSnapshot { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn reported_serving_source() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = snapshot(&dir, "1.80.1\n");
+        let reporter = TestReporterWrapper::default();
+
+        let rust_releases = RustReleasesContext {
+            release_sources: vec![ChainedReleaseSource {
+                source: ReleaseSource::Snapshot,
+                timeout: None,
+            }],
+            release_snapshot: Some(path),
+            ..RustReleasesContext::default()
+        };
+
+        let index =
+            fetch_index_with_cache(reporter.get(), &rust_releases, None, unpublished).unwrap();
+        assert_eq!(index.releases().len(), 1);
+
+        let events = reporter.wait_for_events();
+        assert_eq!(
+            events.last().unwrap().message(),
+            &Message::FetchIndex(
+                FetchIndex::new(ReleaseSource::Snapshot).served_by(Some(ReleaseSource::Snapshot))
+            )
+        );
+    }
+
+    #[yare::parameterized(
+        cached = { true, true },
+        not_cached = { false, false },
+    )]
+    fn frozen(cached: bool, expected: bool) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = IndexCache::in_cache(GlobalCache::at(
+            Utf8Path::from_path(dir.path()).unwrap().to_path_buf(),
+        ));
+        if cached {
+            let index =
+                ReleaseIndex::from_iter(vec![Release::new_stable(semver::Version::new(1, 80, 1))]);
+            cache.store(ReleaseSource::RustChangelog, &index).unwrap();
+        }
+
+        let reporter = TestReporterWrapper::default();
+        let rust_releases = RustReleasesContext {
+            index_refresh: IndexRefresh::Never,
+            ..RustReleasesContext::default()
         };
 
-        Ok(index)
-    })
+        let result =
+            fetch_index_with_cache(reporter.get(), &rust_releases, Some(&cache), unpublished);
+
+        assert_eq!(result.is_ok(), expected);
+        if !expected {
+            assert!(matches!(result, Err(CargoMSRVError::FrozenIndexNotCached)));
+        }
+    }
+
+    #[test]
+    fn delta_update() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let cache = IndexCache::in_cache(GlobalCache::at(
+            Utf8Path::from_path(dir.path()).unwrap().to_path_buf(),
+        ));
+        let index =
+            ReleaseIndex::from_iter(vec![Release::new_stable(semver::Version::new(1, 80, 1))]);
+        cache.store(ReleaseSource::RustChangelog, &index).unwrap();
+
+        let reporter = TestReporterWrapper::default();
+        let rust_releases = RustReleasesContext {
+            index_refresh: IndexRefresh::Stale {
+                ttl: Duration::ZERO,
+            },
+            ..RustReleasesContext::default()
+        };
+
+        let updated = fetch_index_with_cache(reporter.get(), &rust_releases, Some(&cache), |v| {
+            Ok(*v == semver::Version::new(1, 81, 0))
+        })
+        .unwrap();
+        assert_eq!(updated.releases().len(), 2);
+
+        let events = reporter.wait_for_events();
+        assert_eq!(
+            events.last().unwrap().message(),
+            &Message::FetchIndex(
+                FetchIndex::new(ReleaseSource::RustChangelog)
+                    .served_by(Some(ReleaseSource::RustChangelog))
+                    .with_new_releases(true, Some(1))
+            )
+        );
+    }
+
+    #[test]
+    fn chain_exhausted() {
+        let reporter = TestReporterWrapper::default();
+        let chain = [
+            ChainedReleaseSource {
+                source: ReleaseSource::Snapshot,
+                timeout: None,
+            },
+            ChainedReleaseSource {
+                source: ReleaseSource::Snapshot,
+                timeout: Some(std::time::Duration::from_secs(10)),
+            },
+        ];
+
+        let result = fetch_from_chain(reporter.get(), &chain, None);
+        assert!(matches!(
+            result,
+            Err(CargoMSRVError::ReleaseSnapshotMissing)
+        ));
+
+        let warnings = reporter
+            .wait_for_events()
+            .into_iter()
+            .filter(|event| matches!(event.message(), Message::Warning(_)))
+            .count();
+        assert_eq!(warnings, 1);
+    }
 }
@@ -24,6 +24,11 @@ impl<'ctx> ReleasesFilter<'ctx> {
         }
     }
 
+    /// Whether the given version is within the `[min-version:max-version]` range of the filter.
+    pub fn includes(&self, version: &semver::Version) -> bool {
+        include_version(version, self.minimum_version, self.maximum_version)
+    }
+
     /// Filter the given slice of releases, based on the options set for the filter.
     pub fn filter(&self, releases: &[Release]) -> Vec<Release> {
         let releases = if self.include_all_patch_releases {
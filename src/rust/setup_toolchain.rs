@@ -2,7 +2,11 @@ use crate::error::{
     RustupAddComponentError, RustupAddTargetError, RustupError, RustupInstallError,
 };
 use crate::external_command::rustup_command::RustupCommand;
+use crate::external_command::ssh_command::SshCommand;
 use crate::reporter::event::SetupToolchain as SetupToolchainEvent;
+use crate::rust::default_target::default_target;
+use crate::rust::download_progress::DownloadProgressParser;
+use crate::rust::installed_toolchains::{listed_toolchain, InstalledToolchains};
 use crate::rust::Toolchain;
 use crate::{CargoMSRVError, Reporter, TResult};
 
@@ -22,15 +26,20 @@ impl<'reporter, R: Reporter> SetupRustupToolchain<'reporter, R> {
 }
 
 impl<'reporter, R: Reporter> SetupToolchain for SetupRustupToolchain<'reporter, R> {
-    #[instrument(skip(self, toolchain))]
+    #[instrument(skip(self, toolchain), fields(toolchain = toolchain.spec()))]
     fn download(&self, toolchain: &Toolchain) -> TResult<()> {
         self.reporter
             .run_scoped_event(SetupToolchainEvent::new(toolchain.to_owned()), || {
-                install_toolchain(toolchain)
-                    .and_then(|_| add_target(toolchain))
+                // Custom toolchains can't be installed by rustup, they must have been linked
+                if let Some(name) = toolchain.custom_name() {
+                    return ensure_linked(name);
+                }
+
+                install_toolchain(self.reporter, toolchain)
+                    .and_then(|_| add_target(self.reporter, toolchain))
                     .and_then(|_| {
                         if !toolchain.components().is_empty() {
-                            add_components(toolchain)
+                            add_components(self.reporter, toolchain)
                         } else {
                             Ok(())
                         }
@@ -39,15 +48,33 @@ impl<'reporter, R: Reporter> SetupToolchain for SetupRustupToolchain<'reporter,
     }
 }
 
-#[instrument(skip(toolchain))]
-fn install_toolchain(toolchain: &Toolchain) -> TResult<()> {
+/// Report the progress of the downloads of rustup, as parsed from its output.
+fn report_download_progress(reporter: &impl Reporter) -> impl FnMut(&str) + '_ {
+    let mut parser = DownloadProgressParser::default();
+
+    move |line| {
+        if let Some(progress) = parser.parse_line(line) {
+            // The progress is informative only, so it should not fail the setup
+            if let Err(error) = reporter.report_event(progress) {
+                debug!(%error, "unable to report download progress");
+            }
+        }
+    }
+}
+
+#[instrument(skip(reporter, toolchain))]
+fn install_toolchain(reporter: &impl Reporter, toolchain: &Toolchain) -> TResult<()> {
     info!(toolchain = toolchain.spec(), "installing host toolchain");
 
+    // Unless the toolchains can be listed, it's unknown whether the toolchain was installed
+    // before, by the user, so it's not recorded as installed by cargo-msrv
+    let toolchain_list_before = toolchain_list();
+
     let rustup = RustupCommand::new()
         .with_stdout()
         .with_stderr()
-        .with_args(["--profile", "minimal", &format!("{}", toolchain.version())])
-        .install()?;
+        .with_args(["--profile", "minimal", &toolchain.rustup_toolchain()])
+        .install(report_download_progress(reporter))?;
 
     let status = rustup.exit_status();
 
@@ -59,6 +86,13 @@ fn install_toolchain(toolchain: &Toolchain) -> TResult<()> {
             "rustup failed to install toolchain"
         );
 
+        if let Some(reason) = unavailable_for_host(rustup.stderr()) {
+            return Err(CargoMSRVError::ToolchainUnavailable {
+                version: toolchain.version().clone(),
+                reason: reason.to_string(),
+            });
+        }
+
         return Err(CargoMSRVError::RustupError(RustupError::Install(
             RustupInstallError {
                 toolchain_spec: toolchain.spec().to_string(),
@@ -67,11 +101,118 @@ fn install_toolchain(toolchain: &Toolchain) -> TResult<()> {
         )));
     }
 
+    if is_newly_installed(toolchain_list_before.as_deref(), toolchain) {
+        record_installed(toolchain);
+    }
+
     Ok(())
 }
 
-#[instrument(skip(toolchain))]
-fn add_target(toolchain: &Toolchain) -> TResult<()> {
+/// The output of `rustup toolchain list`, unless it fails.
+fn toolchain_list() -> Option<String> {
+    let rustup = RustupCommand::new()
+        .with_stdout()
+        .with_args(["list"])
+        .toolchain()
+        .ok()?;
+
+    rustup
+        .exit_status()
+        .success()
+        .then(|| rustup.stdout().to_string())
+}
+
+/// Whether the toolchain was not installed before, according to the toolchains which were
+/// listed before it was installed. If they could not be listed, it may have been installed
+/// before, so it's not newly installed.
+fn is_newly_installed(toolchain_list_before: Option<&str>, toolchain: &Toolchain) -> bool {
+    toolchain_list_before.is_some_and(|toolchain_list| {
+        default_host(toolchain).is_some_and(|host| {
+            listed_toolchain(toolchain_list, &toolchain.version().to_string(), &host).is_none()
+        })
+    })
+}
+
+/// The host of the toolchain: the native host, unless the toolchain is for another host.
+fn default_host(toolchain: &Toolchain) -> Option<String> {
+    match toolchain.host() {
+        Some(host) => Some(host.to_string()),
+        None => default_target().ok(),
+    }
+}
+
+/// The name of the installed toolchain of the Rust version and the host of the given toolchain,
+/// if any. Toolchains of the same version, but for another host, are different toolchains.
+fn installed_toolchain(toolchain: &Toolchain) -> Option<String> {
+    let host = default_host(toolchain)?;
+    let toolchain_list = toolchain_list()?;
+
+    listed_toolchain(&toolchain_list, &toolchain.version().to_string(), &host).map(String::from)
+}
+
+/// Remember that the toolchain was installed by cargo-msrv, so `cargo msrv clean --toolchains`
+/// may uninstall it. Failing to do so does not fail the check.
+fn record_installed(toolchain: &Toolchain) {
+    let (Some(record), Some(name)) = (InstalledToolchains::new(), installed_toolchain(toolchain))
+    else {
+        return;
+    };
+
+    if let Err(error) = record.record(&name) {
+        info!(
+            ?error,
+            toolchain = name,
+            "unable to record installed toolchain"
+        );
+    }
+}
+
+#[instrument]
+fn ensure_linked(name: &str) -> TResult<()> {
+    info!(toolchain = name, "checking that custom toolchain is linked");
+
+    let rustup = RustupCommand::new()
+        .with_stdout()
+        .with_stderr()
+        .with_args(["list"])
+        .toolchain()?;
+
+    if !is_listed(rustup.stdout(), name) {
+        return Err(CargoMSRVError::CustomToolchainNotLinked {
+            name: name.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Whether a toolchain is part of the output of `rustup toolchain list`, in which each line
+/// starts with a toolchain name, optionally followed by a remark like `(default)`.
+fn is_listed(toolchain_list: &str, name: &str) -> bool {
+    toolchain_list
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .any(|listed| listed == name)
+}
+
+/// The reason why rustup can't install the toolchain, if it was never published for the host,
+/// like a tier 2 host of which the older releases were not built: the line of the output of
+/// rustup which says so.
+fn unavailable_for_host(stderr: &str) -> Option<&str> {
+    const UNAVAILABLE: [&str; 3] = [
+        "is unavailable for download",
+        "is not installable",
+        "does not contain component",
+    ];
+
+    stderr
+        .lines()
+        .map(|line| line.trim().trim_start_matches("error: "))
+        .find(|line| UNAVAILABLE.iter().any(|marker| line.contains(marker)))
+}
+
+#[instrument(skip(reporter, toolchain))]
+fn add_target(reporter: &impl Reporter, toolchain: &Toolchain) -> TResult<()> {
     info!(
         toolchain = toolchain.spec(),
         target = toolchain.target(),
@@ -84,10 +225,10 @@ fn add_target(toolchain: &Toolchain) -> TResult<()> {
         .with_args([
             "add",
             "--toolchain",
-            &format!("{}", toolchain.version()),
+            &toolchain.rustup_toolchain(),
             toolchain.target(),
         ])
-        .target()?;
+        .target(report_download_progress(reporter))?;
 
     let status = rustup.exit_status();
 
@@ -111,8 +252,8 @@ fn add_target(toolchain: &Toolchain) -> TResult<()> {
     Ok(())
 }
 
-#[instrument(skip(toolchain))]
-fn add_components(toolchain: &Toolchain) -> TResult<()> {
+#[instrument(skip(reporter, toolchain))]
+fn add_components(reporter: &impl Reporter, toolchain: &Toolchain) -> TResult<()> {
     info!(
         toolchain = toolchain.spec(),
         target = toolchain.target(),
@@ -123,7 +264,7 @@ fn add_components(toolchain: &Toolchain) -> TResult<()> {
     let base_arguments = [
         "add",
         "--toolchain",
-        &format!("{}", toolchain.version()),
+        &toolchain.rustup_toolchain(),
         "--target",
         toolchain.target(),
     ];
@@ -132,7 +273,7 @@ fn add_components(toolchain: &Toolchain) -> TResult<()> {
         .with_stdout()
         .with_stderr()
         .with_args(base_arguments.iter().chain(toolchain.components().iter()))
-        .component()?;
+        .component(report_download_progress(reporter))?;
 
     let status = rustup.exit_status();
 
@@ -155,3 +296,125 @@ fn add_components(toolchain: &Toolchain) -> TResult<()> {
 
     Ok(())
 }
+
+/// Installs toolchains on a remote builder, by running rustup over `ssh`.
+#[derive(Debug)]
+pub struct SetupRemoteToolchain<'reporter, 'ssh, R: Reporter> {
+    reporter: &'reporter R,
+    ssh: &'ssh SshCommand<'ssh>,
+}
+
+impl<'reporter, 'ssh, R: Reporter> SetupRemoteToolchain<'reporter, 'ssh, R> {
+    pub fn new(reporter: &'reporter R, ssh: &'ssh SshCommand<'ssh>) -> Self {
+        Self { reporter, ssh }
+    }
+
+    fn rustup(&self, args: &[&str]) -> TResult<Result<(), String>> {
+        let rustup = std::iter::once("rustup").chain(args.iter().copied());
+        let output = self.ssh.run(None, &rustup.collect::<Vec<_>>())?;
+
+        if output.status.success() {
+            Ok(Ok(()))
+        } else {
+            Ok(Err(String::from_utf8_lossy(&output.stderr).into_owned()))
+        }
+    }
+}
+
+impl<'reporter, 'ssh, R: Reporter> SetupToolchain for SetupRemoteToolchain<'reporter, 'ssh, R> {
+    #[instrument(skip(self, toolchain))]
+    fn download(&self, toolchain: &Toolchain) -> TResult<()> {
+        self.reporter
+            .run_scoped_event(SetupToolchainEvent::new(toolchain.to_owned()), || {
+                info!(toolchain = toolchain.spec(), "installing remote toolchain");
+
+                let version = toolchain.version().to_string();
+                let toolchain_spec = || toolchain.spec().to_string();
+
+                self.rustup(&["install", "--profile", "minimal", &version])?
+                    .map_err(|stderr| {
+                        RustupError::Install(RustupInstallError {
+                            toolchain_spec: toolchain_spec(),
+                            stderr,
+                        })
+                    })?;
+
+                self.rustup(&["target", "add", "--toolchain", &version, toolchain.target()])?
+                    .map_err(|stderr| {
+                        RustupError::AddTarget(RustupAddTargetError {
+                            targets: toolchain.target().to_string(),
+                            toolchain_spec: toolchain_spec(),
+                            stderr,
+                        })
+                    })?;
+
+                if !toolchain.components().is_empty() {
+                    let mut args = vec![
+                        "component",
+                        "add",
+                        "--toolchain",
+                        &version,
+                        "--target",
+                        toolchain.target(),
+                    ];
+                    args.extend(toolchain.components());
+
+                    self.rustup(&args)?.map_err(|stderr| {
+                        RustupError::AddComponent(RustupAddComponentError {
+                            components: toolchain.components().join(", "),
+                            toolchain_spec: toolchain_spec(),
+                            stderr,
+                        })
+                    })?;
+                }
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_listed, is_newly_installed, unavailable_for_host};
+    use crate::rust::Toolchain;
+    use crate::semver;
+
+    #[yare::parameterized(
+        listed = { "stable-x86_64-unknown-linux-gnu (default)\nstage1\n", "stage1", true },
+        listed_with_remark = { "stage1 (default)\n", "stage1", true },
+        not_listed = { "stable-x86_64-unknown-linux-gnu (default)\n", "stage1", false },
+        prefix_only = { "stage1-old\n", "stage1", false },
+    )]
+    fn toolchain_list(output: &str, name: &str, expected: bool) {
+        assert_eq!(is_listed(output, name), expected);
+    }
+
+    #[yare::parameterized(
+        not_listed = { Some("stable-x86_64-unknown-linux-gnu (default)\n"), true },
+        listed = { Some("1.60.0-x86_64-unknown-linux-gnu\n"), false },
+        other_host = { Some("1.60.0-i686-unknown-linux-gnu\n"), true },
+        list_failed = { None, false },
+    )]
+    fn newly_installed(toolchain_list_before: Option<&str>, expected: bool) {
+        let toolchain = Toolchain::new(
+            semver::Version::new(1, 60, 0),
+            "x86_64-unknown-linux-gnu",
+            &[],
+        )
+        .on_host("x86_64-unknown-linux-gnu", "x86_64-unknown-linux-gnu");
+
+        assert_eq!(
+            is_newly_installed(toolchain_list_before, &toolchain),
+            expected
+        );
+    }
+
+    #[yare::parameterized(
+        unavailable_for_download = { "info: syncing channel updates for '1.40.0-aarch64-pc-windows-msvc'\nerror: component 'rust-std' for target 'aarch64-pc-windows-msvc' is unavailable for download for channel '1.40.0'\n", Some("component 'rust-std' for target 'aarch64-pc-windows-msvc' is unavailable for download for channel '1.40.0'") },
+        not_installable = { "error: toolchain '1.40.0-aarch64-pc-windows-msvc' is not installable\n", Some("toolchain '1.40.0-aarch64-pc-windows-msvc' is not installable") },
+        network = { "error: could not download file from 'https://static.rust-lang.org/dist/channel-rust-1.40.0.toml.sha256'\n", None },
+    )]
+    fn unavailable(stderr: &str, expected: Option<&str>) {
+        assert_eq!(unavailable_for_host(stderr), expected);
+    }
+}
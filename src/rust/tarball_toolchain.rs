@@ -0,0 +1,477 @@
+//! Toolchains which are installed from a directory of the official standalone tarballs, like
+//! `rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz`, instead of being downloaded by rustup, for build
+//! machines without network access.
+//!
+//! Each tarball must be accompanied by its `.sha256` file, as published next to it on
+//! `static.rust-lang.org`, against which it is verified before it is installed. A tarball is
+//! installed once, by merging the components it contains (except for the documentation) into a
+//! toolchain directory in the local data directory of the user, from which it is used like a
+//! `--rustc` and `--cargo` pair.
+
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use crate::rust::distribution_toolchain::DistributionToolchain;
+use crate::semver;
+use camino::{Utf8Path, Utf8PathBuf};
+use rust_releases::{Release, ReleaseIndex};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::process::Command;
+
+/// The extensions of the tarballs, from the most to the least preferred.
+const EXTENSIONS: [&str; 2] = [".tar.xz", ".tar.gz"];
+
+/// The components which are not installed, since the checks don't need them.
+const SKIPPED_COMPONENTS: [&str; 1] = ["rust-docs"];
+
+/// The file of a component which lists its files, rather than being one of them.
+const COMPONENT_MANIFEST: &str = "manifest.in";
+
+/// A directory of standalone tarballs, and the directory to which they are installed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TarballToolchains {
+    dir: Utf8PathBuf,
+    install_dir: Utf8PathBuf,
+}
+
+/// A standalone tarball of a Rust release, for a target.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Tarball {
+    path: Utf8PathBuf,
+    version: semver::Version,
+    target: String,
+}
+
+impl TarballToolchains {
+    /// The tarballs of the given directory, which are installed to the local data directory of the
+    /// user.
+    pub fn new(dir: Utf8PathBuf) -> TResult<Self> {
+        if !dir.is_dir() {
+            return Err(CargoMSRVError::TarballDirNotFound(dir));
+        }
+
+        let install_dir = dirs::data_local_dir()
+            .and_then(|dir| Utf8PathBuf::from_path_buf(dir).ok())
+            .ok_or(CargoMSRVError::UnableToLocateTarballInstallDir)?
+            .join("cargo-msrv")
+            .join("toolchains");
+
+        Ok(Self::at(dir, install_dir))
+    }
+
+    pub fn at(dir: Utf8PathBuf, install_dir: Utf8PathBuf) -> Self {
+        Self { dir, install_dir }
+    }
+
+    pub fn dir(&self) -> &Utf8Path {
+        &self.dir
+    }
+
+    /// The Rust releases of which the directory has a tarball for the given target, which are the
+    /// search space, since no other toolchain can be installed.
+    pub fn release_index(&self, target: &str) -> TResult<ReleaseIndex> {
+        let versions = self
+            .tarballs()?
+            .into_iter()
+            .filter(|tarball| tarball.target == target)
+            .map(|tarball| tarball.version)
+            .collect::<BTreeSet<_>>();
+
+        Ok(ReleaseIndex::from_iter(
+            versions.into_iter().rev().map(Release::new_stable),
+        ))
+    }
+
+    /// The target of the tarballs, if they are all of the same target.
+    pub fn target(&self) -> TResult<Option<String>> {
+        let targets = self
+            .tarballs()?
+            .into_iter()
+            .map(|tarball| tarball.target)
+            .collect::<BTreeSet<_>>();
+
+        Ok((targets.len() == 1)
+            .then(|| targets.into_iter().next())
+            .flatten())
+    }
+
+    /// Install the toolchain of the given Rust version and target from its tarball, unless it was
+    /// installed before.
+    pub fn install(
+        &self,
+        version: &semver::Version,
+        target: &str,
+    ) -> TResult<DistributionToolchain> {
+        let name = format!("{}-{}", version, target);
+        let toolchain_dir = self.install_dir.join(&name);
+
+        if !binary(&toolchain_dir, "rustc").is_file() {
+            let tarball = self
+                .tarballs()?
+                .into_iter()
+                .filter(|tarball| &tarball.version == version && tarball.target == target)
+                .min_by_key(|tarball| extension_rank(&tarball.path))
+                .ok_or_else(|| CargoMSRVError::TarballNotFound {
+                    dir: self.dir.clone(),
+                    toolchain: name.clone(),
+                })?;
+
+            verify_checksum(&tarball.path)?;
+            self.install_tarball(&tarball, &toolchain_dir)?;
+        }
+
+        Ok(DistributionToolchain::new(
+            binary(&toolchain_dir, "rustc"),
+            binary(&toolchain_dir, "cargo"),
+            version.clone(),
+            target,
+        ))
+    }
+
+    fn tarballs(&self) -> TResult<Vec<Tarball>> {
+        let entries = self.dir.read_dir_utf8().map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadDir(self.dir.clone()),
+        })?;
+
+        Ok(entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let (version, target) = parse_file_name(entry.file_name())?;
+
+                Some(Tarball {
+                    path: entry.into_path(),
+                    version,
+                    target,
+                })
+            })
+            .collect())
+    }
+
+    /// Extract the tarball next to the toolchain directory, merge its components into a staged
+    /// toolchain directory, and move the latter in place, so a toolchain directory is never
+    /// partially installed.
+    fn install_tarball(&self, tarball: &Tarball, toolchain_dir: &Utf8Path) -> TResult<()> {
+        info!(tarball = %tarball.path, dir = %toolchain_dir, "installing toolchain from tarball");
+
+        fs::create_dir_all(&self.install_dir).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::CreateDir(self.install_dir.clone()),
+        })?;
+
+        let temp_dir = tempfile::Builder::new()
+            .prefix(".extract-")
+            .tempdir_in(&self.install_dir)
+            .map_err(|error| IoError {
+                error,
+                source: IoErrorSource::CreateTempDir,
+            })?;
+        let extract_dir = Utf8Path::from_path(temp_dir.path())
+            .expect("the install directory is valid UTF-8")
+            .to_path_buf();
+
+        extract(&tarball.path, &extract_dir)?;
+
+        let root = extract_dir.join(archive_name(&tarball.path));
+        let components_file = root.join("components");
+        let components = fs::read_to_string(&components_file).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadFile(components_file),
+        })?;
+
+        let staged = extract_dir.join("toolchain");
+        for component in components
+            .lines()
+            .map(str::trim)
+            .filter(|component| !component.is_empty() && !SKIPPED_COMPONENTS.contains(component))
+        {
+            merge_dir(&root.join(component), &staged)?;
+        }
+
+        match fs::rename(&staged, toolchain_dir) {
+            Ok(()) => Ok(()),
+            // Installed by a concurrent run in the meantime
+            Err(_) if binary(toolchain_dir, "rustc").is_file() => Ok(()),
+            Err(error) => Err(IoError {
+                error,
+                source: IoErrorSource::RenameFile(staged),
+            }
+            .into()),
+        }
+    }
+}
+
+/// The Rust version and target of a standalone tarball, from its file name, like
+/// `rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz`. The tarballs of a single component, like
+/// `rust-std-1.70.0-x86_64-unknown-linux-gnu.tar.xz`, and of the beta and nightly channels, are not
+/// standalone tarballs of a release.
+fn parse_file_name(file_name: &str) -> Option<(semver::Version, String)> {
+    let name = EXTENSIONS
+        .iter()
+        .find_map(|extension| file_name.strip_suffix(extension))?;
+    let (version, target) = name.strip_prefix("rust-")?.split_once('-')?;
+    let version = semver::Version::parse(version).ok()?;
+
+    (version.pre.is_empty() && !target.is_empty()).then(|| (version, target.to_string()))
+}
+
+fn extension_rank(path: &Utf8Path) -> usize {
+    EXTENSIONS
+        .iter()
+        .position(|extension| path.as_str().ends_with(extension))
+        .unwrap_or(EXTENSIONS.len())
+}
+
+/// The name of the directory in the tarball, which is its file name without the extension.
+fn archive_name(path: &Utf8Path) -> &str {
+    let file_name = path.file_name().unwrap_or_default();
+
+    EXTENSIONS
+        .iter()
+        .find_map(|extension| file_name.strip_suffix(extension))
+        .unwrap_or(file_name)
+}
+
+/// The path of a binary of the toolchain, like `bin/rustc`.
+fn binary(toolchain_dir: &Utf8Path, name: &str) -> Utf8PathBuf {
+    toolchain_dir
+        .join("bin")
+        .join(format!("{}{}", name, std::env::consts::EXE_SUFFIX))
+}
+
+/// Verify the tarball against the SHA-256 checksum of its `.sha256` file, which has the format of
+/// `sha256sum`.
+fn verify_checksum(path: &Utf8Path) -> TResult<()> {
+    let checksum_file = Utf8PathBuf::from(format!("{}.sha256", path));
+    let checksum = fs::read_to_string(&checksum_file).map_err(|_| {
+        CargoMSRVError::TarballChecksumNotFound {
+            path: checksum_file.clone(),
+        }
+    })?;
+    let expected = checksum
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let mut file = fs::File::open(path).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::OpenFile(path.to_path_buf()),
+    })?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+    let actual = hex(&hasher.finalize());
+
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(CargoMSRVError::TarballChecksumMismatch {
+            path: path.to_path_buf(),
+            expected,
+            actual,
+        })
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Extract the tarball with `tar`, which detects its compression.
+fn extract(tarball: &Utf8Path, dir: &Utf8Path) -> TResult<()> {
+    let output = Command::new("tar")
+        .arg("-xf")
+        .arg(tarball)
+        .arg("-C")
+        .arg(dir)
+        .output()
+        .map_err(|error| IoError {
+            error,
+            source: IoErrorSource::SpawnProcess("tar".into()),
+        })?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(CargoMSRVError::UnableToExtractTarball {
+            path: tarball.to_path_buf(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// Move the files of a component into the toolchain directory, leaving out its manifest.
+fn merge_dir(from: &Utf8Path, to: &Utf8Path) -> TResult<()> {
+    fs::create_dir_all(to).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::CreateDir(to.to_path_buf()),
+    })?;
+
+    let entries = from.read_dir_utf8().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadDir(from.to_path_buf()),
+    })?;
+
+    for entry in entries {
+        let entry = entry.map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadDir(from.to_path_buf()),
+        })?;
+        let target = to.join(entry.file_name());
+
+        if entry.path().is_dir() {
+            merge_dir(entry.path(), &target)?;
+        } else if entry.file_name() != COMPONENT_MANIFEST {
+            fs::rename(entry.path(), &target).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::RenameFile(entry.path().to_path_buf()),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        xz = { "rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz", Some((semver::Version::new(1, 70, 0), "x86_64-unknown-linux-gnu")) },
+        gz = { "rust-1.56.1-aarch64-apple-darwin.tar.gz", Some((semver::Version::new(1, 56, 1), "aarch64-apple-darwin")) },
+        component = { "rust-std-1.70.0-x86_64-unknown-linux-gnu.tar.xz", None },
+        nightly = { "rust-nightly-x86_64-unknown-linux-gnu.tar.xz", None },
+        checksum = { "rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz.sha256", None },
+        installer = { "rust-1.70.0-x86_64-pc-windows-msvc.msi", None },
+    )]
+    fn file_name(file_name: &str, expected: Option<(semver::Version, &str)>) {
+        assert_eq!(
+            parse_file_name(file_name),
+            expected.map(|(version, target)| (version, target.to_string()))
+        );
+    }
+
+    fn tarballs(files: &[(&str, &[u8])]) -> (assert_fs::TempDir, TarballToolchains) {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        for (name, contents) in files {
+            fs::write(root.join(name), contents).unwrap();
+        }
+
+        let toolchains = TarballToolchains::at(root.to_path_buf(), root.join("installed"));
+        (dir, toolchains)
+    }
+
+    #[test]
+    fn release_index_of_target() {
+        let (_dir, toolchains) = tarballs(&[
+            ("rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz", b""),
+            ("rust-1.70.0-x86_64-unknown-linux-gnu.tar.gz", b""),
+            ("rust-1.60.0-x86_64-unknown-linux-gnu.tar.gz", b""),
+            ("rust-1.65.0-aarch64-unknown-linux-gnu.tar.gz", b""),
+        ]);
+
+        let index = toolchains
+            .release_index("x86_64-unknown-linux-gnu")
+            .unwrap();
+        let versions = index
+            .releases()
+            .iter()
+            .map(|release| release.version().clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            versions,
+            vec![
+                semver::Version::new(1, 70, 0),
+                semver::Version::new(1, 60, 0)
+            ]
+        );
+        assert_eq!(toolchains.target().unwrap(), None);
+    }
+
+    #[test]
+    fn target_of_tarballs() {
+        let (_dir, toolchains) = tarballs(&[
+            ("rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz", b""),
+            ("rust-1.60.0-x86_64-unknown-linux-gnu.tar.gz", b""),
+        ]);
+
+        assert_eq!(
+            toolchains.target().unwrap().as_deref(),
+            Some("x86_64-unknown-linux-gnu")
+        );
+    }
+
+    #[test]
+    fn checksum() {
+        let (_dir, toolchains) = tarballs(&[
+            ("rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz", b"abc"),
+            (
+                "rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz.sha256",
+                b"BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD  rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz\n",
+            ),
+            ("rust-1.60.0-x86_64-unknown-linux-gnu.tar.xz", b"abd"),
+            (
+                "rust-1.60.0-x86_64-unknown-linux-gnu.tar.xz.sha256",
+                b"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  rust-1.60.0-x86_64-unknown-linux-gnu.tar.xz\n",
+            ),
+            ("rust-1.50.0-x86_64-unknown-linux-gnu.tar.xz", b"abc"),
+        ]);
+        let tarball = |version: &str| {
+            toolchains
+                .dir()
+                .join(format!("rust-{}-x86_64-unknown-linux-gnu.tar.xz", version))
+        };
+
+        assert!(verify_checksum(&tarball("1.70.0")).is_ok());
+        assert!(matches!(
+            verify_checksum(&tarball("1.60.0")),
+            Err(CargoMSRVError::TarballChecksumMismatch { .. })
+        ));
+        assert!(matches!(
+            verify_checksum(&tarball("1.50.0")),
+            Err(CargoMSRVError::TarballChecksumNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn install_without_tarball() {
+        let (_dir, toolchains) = tarballs(&[("rust-1.70.0-x86_64-unknown-linux-gnu.tar.xz", b"")]);
+
+        assert!(matches!(
+            toolchains.install(&semver::Version::new(1, 60, 0), "x86_64-unknown-linux-gnu"),
+            Err(CargoMSRVError::TarballNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn merge_components() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+        let rustc = root.join("rustc");
+        let std = root.join("rust-std-x86_64-unknown-linux-gnu");
+
+        fs::create_dir_all(rustc.join("bin")).unwrap();
+        fs::create_dir_all(rustc.join("lib")).unwrap();
+        fs::write(rustc.join("bin/rustc"), "").unwrap();
+        fs::write(rustc.join("lib/librustc_driver.so"), "").unwrap();
+        fs::write(rustc.join(COMPONENT_MANIFEST), "file:bin/rustc\n").unwrap();
+        fs::create_dir_all(std.join("lib/rustlib")).unwrap();
+        fs::write(std.join("lib/rustlib/libstd.rlib"), "").unwrap();
+
+        let toolchain = root.join("toolchain");
+        merge_dir(&rustc, &toolchain).unwrap();
+        merge_dir(&std, &toolchain).unwrap();
+
+        assert!(toolchain.join("bin/rustc").is_file());
+        assert!(toolchain.join("lib/librustc_driver.so").is_file());
+        assert!(toolchain.join("lib/rustlib/libstd.rlib").is_file());
+        assert!(!toolchain.join(COMPONENT_MANIFEST).exists());
+    }
+}
@@ -7,6 +7,12 @@ pub struct Toolchain {
     version: semver::Version,
     target: &'static str,
     components: &'static [&'static str],
+    /// The name of a custom toolchain, linked with `rustup toolchain link`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The host of the toolchain, if it is not the native host, see `--fallback-host`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    host: Option<&'static str>,
     #[serde(skip)]
     spec: OnceCell<String>,
 }
@@ -21,13 +27,54 @@ impl Toolchain {
             version,
             target,
             components,
+            name: None,
+            host: None,
             spec: OnceCell::new(),
         }
     }
 
+    /// The toolchain of the same Rust version for the given host, instead of the native host,
+    /// which checks the given target.
+    pub fn on_host(&self, host: &'static str, target: &'static str) -> Self {
+        Self {
+            host: Some(host),
+            ..Self::new(self.version.clone(), target, self.components)
+        }
+    }
+
+    /// A custom toolchain, linked with `rustup toolchain link <name>`, which is based on
+    /// the given Rust version.
+    pub fn custom(name: impl Into<String>, version: semver::Version, target: &'static str) -> Self {
+        Self {
+            name: Some(name.into()),
+            ..Self::new(version, target, &[])
+        }
+    }
+
     pub fn spec(&self) -> &str {
-        self.spec
-            .get_or_init(|| make_toolchain_spec(&self.version, self.target))
+        self.spec.get_or_init(|| match &self.name {
+            Some(name) => name.clone(),
+            None => make_toolchain_spec(&self.version, self.target),
+        })
+    }
+
+    /// The name of the toolchain, if it is a custom toolchain.
+    pub fn custom_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The host of the toolchain, if it is not the native host.
+    pub fn host(&self) -> Option<&'static str> {
+        self.host
+    }
+
+    /// The toolchain argument of `rustup run <toolchain>`.
+    pub fn rustup_toolchain(&self) -> String {
+        match (&self.name, self.host) {
+            (Some(name), _) => name.clone(),
+            (None, Some(host)) => format!("{}-{}", self.version, host),
+            (None, None) => self.version.to_string(),
+        }
     }
 
     pub fn version(&self) -> &semver::Version {
@@ -38,7 +85,7 @@ impl Toolchain {
         self.components
     }
 
-    pub fn target(&self) -> &str {
+    pub fn target(&self) -> &'static str {
         self.target
     }
 }
@@ -81,6 +128,28 @@ mod tests_toolchain_spec {
         assert_eq!(toolchain.target(), "x");
     }
 
+    #[test]
+    fn custom() {
+        let version = semver::Version::new(1, 66, 0);
+        let toolchain = Toolchain::custom("stage1", version, "x");
+
+        assert_eq!(toolchain.spec(), "stage1");
+        assert_eq!(toolchain.custom_name(), Some("stage1"));
+        assert_eq!(toolchain.rustup_toolchain(), "stage1");
+        assert_eq!(toolchain.version(), &semver::Version::new(1, 66, 0));
+    }
+
+    #[test]
+    fn on_host() {
+        let version = semver::Version::new(1, 40, 0);
+        let toolchain = Toolchain::new(version, "aarch64-apple-darwin", &[])
+            .on_host("x86_64-apple-darwin", "x86_64-apple-darwin");
+
+        assert_eq!(toolchain.host(), Some("x86_64-apple-darwin"));
+        assert_eq!(toolchain.rustup_toolchain(), "1.40.0-x86_64-apple-darwin");
+        assert_eq!(toolchain.spec(), "1.40.0-x86_64-apple-darwin");
+    }
+
     #[test]
     fn get_components() {
         let version = semver::Version::new(1, 2, 3);
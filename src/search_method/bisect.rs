@@ -2,7 +2,7 @@ use bisector::{Bisector, ConvergeTo, Indices, Step};
 
 use crate::check::Check;
 use crate::context::SearchMethod;
-use crate::error::NoToolchainsToTryError;
+use crate::error::{CargoMSRVError, NoToolchainsToTryError};
 use crate::msrv::MinimumSupportedRustVersion;
 use crate::outcome::{FailureOutcome, Outcome, SuccessOutcome};
 use crate::reporter::event::{FindMsrv, Progress};
@@ -39,80 +39,163 @@ impl<'runner, R: Check> Bisect<'runner, R> {
     fn show_progress(
         iteration: u64,
         total: u64,
-        indices: Indices,
+        current: usize,
         reporter: &impl Reporter,
     ) -> TResult<()> {
-        let current = indices.middle() as u64;
-
-        reporter.report_event(Progress::new(current, total, iteration))?;
+        reporter.report_event(Progress::new(current as u64, total, iteration))?;
 
         Ok(())
     }
 }
 
-impl<'runner, R: Check> FindMinimalSupportedRustVersion for Bisect<'runner, R> {
-    fn find_toolchain(
+impl<'runner, R: Check> Bisect<'runner, R> {
+    /// Bisect the given window, which holds indices into the search space.
+    fn bisect(
         &self,
         search_space: &[RustRelease],
+        window: &[usize],
+        iteration: &mut u64,
         reporter: &impl Reporter,
-    ) -> TResult<MinimumSupportedRustVersion> {
-        info!(?search_space);
-
-        reporter.run_scoped_event(FindMsrv::new(SearchMethod::Bisect), || {
-            let searcher = Bisector::new(search_space);
+    ) -> TResult<Bisection> {
+        let searcher = Bisector::new(window);
 
-            let total = search_space.len() as u64;
-            let mut iteration = 0_u64;
-            let mut indices = Indices::try_from_bisector(&searcher)
-                .map_err(|_| NoToolchainsToTryError::new_empty())?;
+        let total = search_space.len() as u64;
+        let mut indices = Indices::try_from_bisector(&searcher)
+            .map_err(|_| NoToolchainsToTryError::new_empty())?;
 
-            let mut last_compatible_index = None;
+        let mut last_compatible_index = None;
 
-            while let Step {
-                indices: next_indices,
-                result: Some(step),
-            } = searcher.try_bisect(
-                |release| Self::run_check(self.runner, release, reporter),
+        loop {
+            let step = searcher.try_bisect(
+                |&i| Self::run_check(self.runner, &search_space[i], reporter),
                 indices,
-            )? {
-                iteration += 1;
+            );
 
-                info!(?indices, ?next_indices);
+            let (next_indices, step) = match step {
+                Ok(Step {
+                    indices: next_indices,
+                    result: Some(step),
+                }) => (next_indices, step),
+                Ok(Step { result: None, .. }) => break,
+                Err(
+                    CargoMSRVError::CheckSkipped { .. }
+                    | CargoMSRVError::MemoryLimitExceeded { .. },
+                ) => {
+                    return Ok(Bisection::Skipped {
+                        remaining: without_middle(window, indices),
+                        last_compatible: last_compatible_index.map(|i: Indices| window[i.middle()]),
+                    });
+                }
+                Err(err) => return Err(err),
+            };
 
-                Self::show_progress(iteration, total, indices, reporter)?;
+            *iteration += 1;
 
-                match step {
-                    ConvergeTo::Left(_outcome) => {}
-                    ConvergeTo::Right(_outcome) => {
-                        last_compatible_index = Some(indices);
-                    }
+            info!(?indices, ?next_indices);
+
+            Self::show_progress(*iteration, total, window[indices.middle()], reporter)?;
+
+            match step {
+                ConvergeTo::Left(_outcome) => {}
+                ConvergeTo::Right(_outcome) => {
+                    last_compatible_index = Some(indices);
                 }
+            }
 
-                indices = next_indices;
+            indices = next_indices;
+        }
+
+        let last_compatible = last_compatible_index.map(|i| window[i.middle()]);
+        let converged_to = window[indices.middle()];
+
+        // Work-around for regression:
+        // https://github.com/foresterre/cargo-msrv/issues/288
+        let msrv = if indices.middle() == window.len() - 1 {
+            *iteration += 1;
+            Self::show_progress(*iteration, total, converged_to, reporter)?;
+
+            match Self::run_check(self.runner, &search_space[converged_to], reporter) {
+                Ok(ConvergeTo::Left(_outcome)) => last_compatible,
+                Ok(ConvergeTo::Right(_outcome)) => Some(converged_to),
+                // Nothing remains to be bisected, since the release was the last candidate
+                Err(
+                    CargoMSRVError::CheckSkipped { .. }
+                    | CargoMSRVError::MemoryLimitExceeded { .. },
+                ) => last_compatible,
+                Err(err) => return Err(err),
             }
+        } else {
+            last_compatible
+        };
 
-            let converged_to_release = &search_space[indices.middle()];
+        Ok(Bisection::Converged(msrv))
+    }
+}
 
-            // Work-around for regression:
-            // https://github.com/foresterre/cargo-msrv/issues/288
-            let msrv = if indices.middle() == search_space.len() - 1 {
-                Self::show_progress(iteration + 1, total, indices, reporter)?;
+impl<'runner, R: Check> FindMinimalSupportedRustVersion for Bisect<'runner, R> {
+    fn find_toolchain(
+        &self,
+        search_space: &[RustRelease],
+        reporter: &impl Reporter,
+    ) -> TResult<MinimumSupportedRustVersion> {
+        info!(?search_space);
+
+        reporter.run_scoped_event(FindMsrv::new(SearchMethod::Bisect), || {
+            if search_space.is_empty() {
+                return Err(NoToolchainsToTryError::new_empty().into());
+            }
 
-                match Self::run_check(self.runner, converged_to_release, reporter)? {
-                    ConvergeTo::Left(_outcome) => {
-                        last_compatible_index.map(|i| &search_space[i.middle()])
+            let mut window = (0..search_space.len()).collect::<Vec<_>>();
+            let mut iteration = 0_u64;
+            let mut msrv = None;
+
+            // A skipped release is inconclusive: it is left out, and the releases which were
+            // still to be bisected, are bisected again
+            while !window.is_empty() {
+                match self.bisect(search_space, &window, &mut iteration, reporter)? {
+                    Bisection::Converged(found) => {
+                        msrv = found.or(msrv);
+                        break;
+                    }
+                    Bisection::Skipped {
+                        remaining,
+                        last_compatible,
+                    } => {
+                        msrv = last_compatible.or(msrv);
+                        window = remaining;
                     }
-                    ConvergeTo::Right(_outcome) => Some(converged_to_release),
                 }
-            } else {
-                last_compatible_index.map(|i| &search_space[i.middle()])
-            };
+            }
 
-            Ok(MinimumSupportedRustVersion::from_option(msrv))
+            Ok(MinimumSupportedRustVersion::from_option(
+                msrv.map(|i| &search_space[i]),
+            ))
         })
     }
 }
 
+enum Bisection {
+    /// The bisection converged to the least recent compatible release, if any.
+    Converged(Option<usize>),
+    /// The check of a release was skipped, before the bisection converged.
+    Skipped {
+        /// The releases which were still to be bisected, without the skipped release.
+        remaining: Vec<usize>,
+        /// The least recent compatible release found so far, if any.
+        last_compatible: Option<usize>,
+    },
+}
+
+/// The window between the given indices, without its middle.
+fn without_middle(window: &[usize], indices: Indices) -> Vec<usize> {
+    let middle = indices.middle();
+
+    (indices.left..=indices.right)
+        .filter(|&i| i != middle)
+        .map(|i| window[i])
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use rust_releases::Release;
@@ -332,4 +415,40 @@ mod tests {
 
         assert_eq!(result.unwrap_version(), expected_msrv);
     }
+
+    #[yare::parameterized(
+        skip_middle = { &[semver::Version::new(1, 56, 0)], semver::Version::new(1, 55, 0) },
+        skip_msrv = { &[semver::Version::new(1, 55, 0)], semver::Version::new(1, 56, 0) },
+        skip_failing = { &[semver::Version::new(1, 54, 0)], semver::Version::new(1, 55, 0) },
+        skip_several = {
+            &[semver::Version::new(1, 56, 0), semver::Version::new(1, 55, 0)],
+            semver::Version::new(1, 57, 0)
+        },
+    )]
+    fn skipped_release_is_inconclusive(skip: &[semver::Version], expected_msrv: semver::Version) {
+        let releases = [
+            Release::new_stable(semver::Version::new(1, 58, 0)),
+            Release::new_stable(semver::Version::new(1, 57, 0)),
+            Release::new_stable(semver::Version::new(1, 56, 0)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+            Release::new_stable(semver::Version::new(1, 54, 0)),
+        ];
+
+        let runner =
+            TestRunner::with_ok("x", releases[..4].iter().map(Release::version)).with_skipped(skip);
+        let bisect = Bisect::new(&runner);
+
+        let reporter = TestReporterWrapper::default();
+
+        let search_space = releases
+            .iter()
+            .map(|r| RustRelease::new(r.clone(), "x", &[]))
+            .collect::<Vec<_>>();
+
+        let result = bisect
+            .find_toolchain(&search_space, reporter.get())
+            .unwrap();
+
+        assert_eq!(result.unwrap_version(), expected_msrv);
+    }
 }
@@ -0,0 +1,131 @@
+use crate::context::{FindContext, SearchMethod};
+use crate::estimate::estimate;
+use crate::manifest::msrv_sources::{declared_msrv, msrv_sources};
+use crate::matrix_state;
+use crate::run_artifacts::RUNS_DIR;
+use crate::semver;
+use crate::stabilization::StabilizationIndex;
+use crate::sub_command::inspect::stored_runs;
+use crate::TResult;
+use cargo_metadata::MetadataCommand;
+
+/// Guesses the MSRV before any release is checked, so the [`Hybrid`] search method can start
+/// from the guess. If the guess is right, only the guessed release and the release right before it
+/// are checked. A heuristic which can't make a guess returns `None`, and the least recent release
+/// is assumed to be the MSRV instead.
+///
+/// [`Hybrid`]: crate::search_method::Hybrid
+pub trait Heuristic {
+    /// The search method which starts from the guess of this heuristic, as it is reported.
+    fn search_method(&self) -> SearchMethod;
+
+    /// The guessed MSRV of the crate of the context, if any.
+    fn guess(&self, ctx: &FindContext) -> Option<semver::Version>;
+}
+
+/// The MSRV as estimated by the static analysis, see [`crate::estimate`].
+pub struct StaticEstimate;
+
+impl Heuristic for StaticEstimate {
+    fn search_method(&self) -> SearchMethod {
+        SearchMethod::Hybrid
+    }
+
+    fn guess(&self, ctx: &FindContext) -> Option<semver::Version> {
+        match estimate(&ctx.environment, &StabilizationIndex::load()) {
+            Ok(estimate) => {
+                info!(estimate = ?estimate.version(), findings = ?estimate.findings());
+                estimate.version().cloned()
+            }
+            Err(error) => {
+                info!(%error, "unable to estimate the MSRV, assuming the least recent release");
+                None
+            }
+        }
+    }
+}
+
+/// The declared MSRV, like `package.rust-version`, taken from the configured MSRV sources (see
+/// [`crate::manifest::msrv_sources`]). Useful to confirm that the declared MSRV is still the MSRV.
+pub struct DeclaredRustVersion;
+
+impl DeclaredRustVersion {
+    fn declared(ctx: &FindContext) -> TResult<Option<semver::Version>> {
+        let metadata = MetadataCommand::new()
+            .manifest_path(ctx.environment.manifest())
+            .no_deps()
+            .exec()?;
+        let sources = msrv_sources(&metadata)?;
+        let declared = declared_msrv(&metadata, ctx.environment.root(), &sources)?;
+
+        Ok(declared.map(|declared| declared.version.to_semver_version()))
+    }
+}
+
+impl Heuristic for DeclaredRustVersion {
+    fn search_method(&self) -> SearchMethod {
+        SearchMethod::Declared
+    }
+
+    fn guess(&self, ctx: &FindContext) -> Option<semver::Version> {
+        match Self::declared(ctx) {
+            Ok(declared) => {
+                info!(?declared, "the declared MSRV");
+                declared
+            }
+            Err(error) => {
+                info!(%error, "unable to read the declared MSRV, assuming the least recent release");
+                None
+            }
+        }
+    }
+}
+
+/// The MSRV which an earlier run found for the target: the MSRV saved by `find --targets`, or
+/// otherwise the MSRV of the most recent run stored with `--save-run`. Useful in CI, where the
+/// MSRV usually doesn't change between runs.
+pub struct LastRun;
+
+impl LastRun {
+    fn last_msrv(ctx: &FindContext) -> TResult<Option<semver::Version>> {
+        let root = ctx.environment.root();
+        let target = ctx.toolchain.target;
+
+        if let Some(msrv) = matrix_state::load(root)?
+            .as_ref()
+            .and_then(|state| state.msrv(target))
+        {
+            return Ok(Some(msrv.clone()));
+        }
+
+        let runs = stored_runs(&root.join(RUNS_DIR))?;
+        let msrv = runs
+            .into_iter()
+            .rev()
+            .filter(|run| run.subcommand == "find")
+            // The runs of other targets found the MSRV of another target
+            .filter(|run| run.checks.iter().all(|check| check.target == target))
+            .find_map(|run| run.msrv);
+
+        Ok(msrv)
+    }
+}
+
+impl Heuristic for LastRun {
+    fn search_method(&self) -> SearchMethod {
+        SearchMethod::LastRun
+    }
+
+    fn guess(&self, ctx: &FindContext) -> Option<semver::Version> {
+        match Self::last_msrv(ctx) {
+            Ok(last) => {
+                info!(?last, "the MSRV of the last run");
+                last
+            }
+            Err(error) => {
+                info!(%error, "unable to read the last run, assuming the least recent release");
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use crate::check::Check;
+use crate::context::{FindContext, SearchMethod};
+use crate::error::{CargoMSRVError, NoToolchainsToTryError};
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::outcome::Outcome;
+use crate::reporter::event::{FindMsrv, Progress};
+use crate::reporter::Reporter;
+use crate::rust::RustRelease;
+use crate::search_method::{Bisect, FindMinimalSupportedRustVersion, Heuristic};
+use crate::{semver, TResult};
+
+pub struct Hybrid<'runner, R: Check> {
+    runner: &'runner R,
+    estimate: Option<semver::Version>,
+    method: SearchMethod,
+}
+
+impl<'runner, R: Check> Hybrid<'runner, R> {
+    /// Confirm the `estimate` of the static analysis (see [`crate::estimate`]).
+    ///
+    /// If no estimate could be made, the least recent release is assumed to be the MSRV.
+    pub fn new(runner: &'runner R, estimate: Option<semver::Version>) -> Self {
+        Self {
+            runner,
+            estimate,
+            method: SearchMethod::Hybrid,
+        }
+    }
+
+    /// Confirm the guess of the given heuristic, instead of the static estimate.
+    pub fn from_heuristic(
+        runner: &'runner R,
+        heuristic: &impl Heuristic,
+        ctx: &FindContext,
+    ) -> Self {
+        Self {
+            runner,
+            estimate: heuristic.guess(ctx),
+            method: heuristic.search_method(),
+        }
+    }
+
+    fn run_check(runner: &R, release: &RustRelease, _reporter: &impl Reporter) -> TResult<Outcome> {
+        let toolchain = release.to_toolchain_spec();
+        runner.check(&toolchain)
+    }
+
+    /// The index of the least recent release which is at least as recent as the estimate.
+    fn boundary(&self, search_space: &[RustRelease]) -> usize {
+        let last = search_space.len() - 1;
+
+        match &self.estimate {
+            Some(estimate) => search_space
+                .iter()
+                .rposition(|release| release.version() >= estimate)
+                .unwrap_or(0),
+            None => last,
+        }
+    }
+
+    /// Check the release at the estimated boundary, and the release right before it.
+    fn confirm(&self, search_space: &[RustRelease], reporter: &impl Reporter) -> TResult<Confirm> {
+        let boundary = self.boundary(search_space);
+        let total = search_space.len() as u64;
+
+        info!(estimate = ?self.estimate, boundary = %search_space[boundary].version());
+
+        reporter.report_event(Progress::new(boundary as u64, total, 1))?;
+
+        if !Self::run_check(self.runner, &search_space[boundary], reporter)?.is_success() {
+            return Ok(Confirm::TooEarly { boundary });
+        }
+
+        let Some(previous) = search_space.get(boundary + 1) else {
+            return Ok(Confirm::Confirmed { boundary });
+        };
+
+        reporter.report_event(Progress::new(boundary as u64 + 1, total, 2))?;
+
+        if !Self::run_check(self.runner, previous, reporter)?.is_success() {
+            Ok(Confirm::Confirmed { boundary })
+        } else {
+            Ok(Confirm::TooLate { boundary })
+        }
+    }
+}
+
+/// The outcome of confirming the estimated boundary.
+enum Confirm {
+    /// The release at the boundary is compatible, the release right before it is not.
+    Confirmed { boundary: usize },
+    /// The release at the boundary is not compatible; the MSRV is more recent.
+    TooEarly { boundary: usize },
+    /// The release right before the boundary is compatible as well; the MSRV is less recent.
+    TooLate { boundary: usize },
+    /// A check was skipped, so the boundary could neither be confirmed nor rejected.
+    Inconclusive,
+}
+
+impl<'runner, R: Check> FindMinimalSupportedRustVersion for Hybrid<'runner, R> {
+    fn find_toolchain(
+        &self,
+        search_space: &[RustRelease],
+        reporter: &impl Reporter,
+    ) -> TResult<MinimumSupportedRustVersion> {
+        info!(?search_space);
+
+        if search_space.is_empty() {
+            return Err(NoToolchainsToTryError::new_empty().into());
+        }
+
+        let event = FindMsrv::new(self.method).with_estimate(self.estimate.clone());
+        let confirmed = match reporter
+            .run_scoped_event(event, || self.confirm(search_space, reporter))
+        {
+            Ok(confirmed) => confirmed,
+            Err(
+                CargoMSRVError::CheckSkipped { .. } | CargoMSRVError::MemoryLimitExceeded { .. },
+            ) => Confirm::Inconclusive,
+            Err(err) => return Err(err),
+        };
+
+        // The estimate proved wrong, or could not be confirmed: fall back to a bisection of the remaining releases, which
+        // are known to be respectively incompatible, or compatible.
+        let remaining = match confirmed {
+            Confirm::Confirmed { boundary } => {
+                return Ok(MinimumSupportedRustVersion::from_option(
+                    search_space.get(boundary),
+                ));
+            }
+            Confirm::TooEarly { boundary: 0 } => {
+                return Ok(MinimumSupportedRustVersion::NoCompatibleToolchain);
+            }
+            Confirm::TooEarly { boundary } => &search_space[..boundary],
+            Confirm::TooLate { boundary } => &search_space[boundary + 1..],
+            Confirm::Inconclusive => search_space,
+        };
+
+        info!(
+            estimate = ?self.estimate,
+            "estimate could not be confirmed, falling back to bisection"
+        );
+
+        Bisect::new(self.runner).find_toolchain(remaining, reporter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hybrid;
+    use crate::check::TestRunner;
+    use crate::msrv::MinimumSupportedRustVersion;
+    use crate::reporter::TestReporterWrapper;
+    use crate::rust::RustRelease;
+    use crate::search_method::FindMinimalSupportedRustVersion;
+    use crate::semver;
+    use rust_releases::Release;
+
+    fn search_space() -> Vec<RustRelease> {
+        (50..=58)
+            .rev()
+            .map(|minor| {
+                let release = Release::new_stable(semver::Version::new(1, minor, 0));
+                RustRelease::new(release, "x", &[])
+            })
+            .collect()
+    }
+
+    fn accept_from(minor: u64) -> Vec<semver::Version> {
+        (minor..=58)
+            .map(|m| semver::Version::new(1, m, 0))
+            .collect()
+    }
+
+    #[yare::parameterized(
+        correct_estimate = { Some(semver::Version::new(1, 54, 0)), 54, 2 },
+        correct_estimate_oldest = { Some(semver::Version::new(1, 50, 0)), 50, 1 },
+        correct_estimate_between_releases = { Some(semver::Version::new(1, 53, 5)), 54, 2 },
+        no_estimate_oldest = { None, 50, 1 },
+        estimate_too_early = { Some(semver::Version::new(1, 52, 0)), 55, 4 },
+        estimate_too_late = { Some(semver::Version::new(1, 57, 0)), 51, 5 },
+        estimate_beyond_newest = { Some(semver::Version::new(1, 70, 0)), 58, 2 },
+    )]
+    fn find_toolchain_with_hybrid(
+        estimate: Option<semver::Version>,
+        msrv_minor: u64,
+        expected_checks: usize,
+    ) {
+        let accept = accept_from(msrv_minor);
+        let runner = TestRunner::with_ok("x", &accept);
+        let hybrid = Hybrid::new(&runner, estimate);
+
+        let reporter = TestReporterWrapper::default();
+
+        let result = hybrid
+            .find_toolchain(&search_space(), reporter.get())
+            .unwrap();
+
+        assert_eq!(
+            result.unwrap_version(),
+            semver::Version::new(1, msrv_minor, 0)
+        );
+        assert_eq!(runner.checked().len(), expected_checks);
+    }
+
+    #[test]
+    fn no_compatible_toolchain() {
+        let runner = TestRunner::with_ok("x", &[]);
+        let hybrid = Hybrid::new(&runner, Some(semver::Version::new(1, 58, 0)));
+
+        let reporter = TestReporterWrapper::default();
+
+        let result = hybrid
+            .find_toolchain(&search_space(), reporter.get())
+            .unwrap();
+
+        assert!(matches!(
+            result,
+            MinimumSupportedRustVersion::NoCompatibleToolchain
+        ));
+    }
+
+    #[test]
+    fn skipped_confirmation_falls_back_to_bisection() {
+        let accept = accept_from(54);
+        let runner =
+            TestRunner::with_ok("x", &accept).with_skipped(&[semver::Version::new(1, 53, 0)]);
+        let hybrid = Hybrid::new(&runner, Some(semver::Version::new(1, 54, 0)));
+
+        let reporter = TestReporterWrapper::default();
+
+        let result = hybrid
+            .find_toolchain(&search_space(), reporter.get())
+            .unwrap();
+
+        // Rust 1.53 is inconclusive, so 1.54 is the least recent release which is known to be
+        // compatible
+        assert_eq!(result.unwrap_version(), semver::Version::new(1, 54, 0));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::check::Check;
 use crate::context::SearchMethod;
-use crate::error::NoToolchainsToTryError;
+use crate::error::{CargoMSRVError, NoToolchainsToTryError};
 use crate::msrv::MinimumSupportedRustVersion;
 use crate::outcome::Outcome;
 use crate::reporter::event::{FindMsrv, Progress};
@@ -44,7 +44,16 @@ impl<'runner, R: Check> FindMinimalSupportedRustVersion for Linear<'runner, R> {
                 let current = i as u64;
                 reporter.report_event(Progress::new(current, total, current + 1))?;
 
-                let outcome = Self::run_check(self.runner, release, reporter)?;
+                let outcome = match Self::run_check(self.runner, release, reporter) {
+                    Ok(outcome) => outcome,
+                    // A skipped release is inconclusive, so it neither ends the search, nor is it
+                    // a candidate for the MSRV
+                    Err(
+                        CargoMSRVError::CheckSkipped { .. }
+                        | CargoMSRVError::MemoryLimitExceeded { .. },
+                    ) => continue,
+                    Err(err) => return Err(err),
+                };
 
                 match outcome {
                     Outcome::Failure(_outcome) => {
@@ -214,4 +223,33 @@ mod tests {
         let expected = MinimumSupportedRustVersion::NoCompatibleToolchain;
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn skipped_release_is_inconclusive() {
+        let reporter = TestReporterWrapper::default();
+
+        let releases = vec![
+            Release::new_stable(semver::Version::new(1, 56, 0)),
+            Release::new_stable(semver::Version::new(1, 55, 0)),
+            Release::new_stable(semver::Version::new(1, 54, 0)),
+            Release::new_stable(semver::Version::new(1, 53, 0)),
+        ];
+
+        let runner = TestRunner::with_ok("x", releases[..3].iter().map(Release::version))
+            .with_skipped(&[semver::Version::new(1, 55, 0)]);
+        let index = ReleaseIndex::from_iter(releases);
+
+        let linear_search = Linear::new(&runner);
+
+        let search_space = to_rust_releases(index.releases());
+        let actual = linear_search
+            .find_toolchain(&search_space, reporter.get())
+            .unwrap();
+
+        let expected = MinimumSupportedRustVersion::Toolchain {
+            toolchain: Toolchain::new(semver::Version::new(1, 54, 0), "x", &[]),
+        };
+
+        assert_eq!(actual, expected);
+    }
 }
@@ -1,4 +1,10 @@
-pub use {bisect::Bisect, linear::Linear};
+pub use {
+    bisect::Bisect,
+    heuristic::{DeclaredRustVersion, Heuristic, LastRun, StaticEstimate},
+    hybrid::Hybrid,
+    linear::Linear,
+    shard::Shard,
+};
 
 use crate::msrv::MinimumSupportedRustVersion;
 use crate::reporter::Reporter;
@@ -8,10 +14,19 @@ use crate::TResult;
 /// Use a bisection method to find the MSRV. By using a binary search, we halve our search space each
 /// step, making this an efficient search function.
 pub mod bisect;
+/// Guesses of the MSRV, like the declared MSRV, from which the hybrid search method starts.
+pub mod heuristic;
+/// Confirm the MSRV estimated by the static analysis, or guessed by another heuristic, by checking
+/// only the estimated release and the release right before it. Falls back to bisection if the
+/// estimate proves wrong.
+pub mod hybrid;
 /// Find the MSRV by stepping through the most-recent to least-recent version, one-by-one. This is
 /// not very efficient, but is useful as a baseline, or if you're certain the MSRV is very close to
 /// the head.
 pub mod linear;
+/// Split the search space of a linear search into deterministic slices, so the checks can be
+/// distributed over multiple jobs.
+pub mod shard;
 
 pub trait FindMinimalSupportedRustVersion {
     /// Method to find the minimum capable toolchain.
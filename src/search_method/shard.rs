@@ -0,0 +1,146 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A deterministic slice of the search space, so the checks of a linear search can be distributed
+/// over multiple CI jobs.
+///
+/// The search space, ordered from most to least recent, is split into `count` contiguous slices,
+/// which differ at most one in length, where shard `1` takes the most recent releases. The results
+/// of all shards can be combined with `cargo msrv merge-results`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Shard {
+    /// The 1-based index of this shard.
+    index: u32,
+    /// The total amount of shards.
+    count: u32,
+}
+
+impl Shard {
+    pub fn new(index: u32, count: u32) -> Result<Self, ParseShardError> {
+        if count == 0 || index == 0 || index > count {
+            return Err(ParseShardError::OutOfRange { index, count });
+        }
+
+        Ok(Self { index, count })
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// The slice of the search space which is checked by this shard.
+    pub fn slice<'a, T>(&self, search_space: &'a [T]) -> &'a [T] {
+        let count = self.count as usize;
+        let nth = self.index as usize - 1;
+
+        // The first `remainder` shards take one release more than the others
+        let size = search_space.len() / count;
+        let remainder = search_space.len() % count;
+
+        let start = nth * size + nth.min(remainder);
+        let end = start + size + usize::from(nth < remainder);
+
+        &search_space[start..end]
+    }
+}
+
+impl Default for Shard {
+    /// The search space as a whole.
+    fn default() -> Self {
+        Self { index: 1, count: 1 }
+    }
+}
+
+impl FromStr for Shard {
+    type Err = ParseShardError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseShardError::Format(input.to_string());
+
+        let (index, count) = input.split_once('/').ok_or_else(invalid)?;
+        let index = index.trim().parse().map_err(|_| invalid())?;
+        let count = count.trim().parse().map_err(|_| invalid())?;
+
+        Self::new(index, count)
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseShardError {
+    #[error("Shard '{0}' is not of the form 'i/n', e.g. '1/3'")]
+    Format(String),
+
+    #[error("Shard {index}/{count} is out of range, expected 1 <= {index} <= {count}")]
+    OutOfRange { index: u32, count: u32 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        first = { "1/3", Shard { index: 1, count: 3 } },
+        last = { "3/3", Shard { index: 3, count: 3 } },
+        single = { "1/1", Shard { index: 1, count: 1 } },
+    )]
+    fn parse(input: &str, expected: Shard) {
+        assert_eq!(input.parse::<Shard>().unwrap(), expected);
+    }
+
+    #[yare::parameterized(
+        no_slash = { "1" },
+        not_a_number = { "a/3" },
+        zero_index = { "0/3" },
+        zero_count = { "0/0" },
+        index_exceeds_count = { "4/3" },
+    )]
+    fn parse_invalid(input: &str) {
+        assert!(input.parse::<Shard>().is_err());
+    }
+
+    #[yare::parameterized(
+        even_1 = { 1, 3, &[1, 2] },
+        even_2 = { 2, 3, &[3, 4] },
+        even_3 = { 3, 3, &[5, 6] },
+        uneven_1 = { 1, 4, &[1, 2] },
+        uneven_2 = { 2, 4, &[3, 4] },
+        uneven_3 = { 3, 4, &[5] },
+        uneven_4 = { 4, 4, &[6] },
+        whole = { 1, 1, &[1, 2, 3, 4, 5, 6] },
+    )]
+    fn slice(index: u32, count: u32, expected: &[u32]) {
+        let search_space = [1, 2, 3, 4, 5, 6];
+        let shard = Shard::new(index, count).unwrap();
+
+        assert_eq!(shard.slice(&search_space), expected);
+    }
+
+    #[test]
+    fn slices_cover_search_space() {
+        let search_space = (0..10).collect::<Vec<_>>();
+
+        let covered = (1..=4)
+            .flat_map(|index| Shard::new(index, 4).unwrap().slice(&search_space).to_vec())
+            .collect::<Vec<_>>();
+
+        assert_eq!(covered, search_space);
+    }
+
+    #[test]
+    fn more_shards_than_releases() {
+        let search_space = [1, 2];
+
+        assert_eq!(Shard::new(2, 3).unwrap().slice(&search_space), &[2]);
+        assert!(Shard::new(3, 3).unwrap().slice(&search_space).is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+//! The state of a search which stopped early, e.g. since it was interrupted, or exceeded
+//! `--max-duration`, so it can be continued with `cargo msrv find --resume`.
+//!
+//! The state is saved to `.cargo-msrv/state.json`, in the root of the crate, and holds the outcome
+//! of each Rust version which was checked conclusively. A resumed search takes these outcomes as
+//! they are, instead of checking the Rust versions again. The state is removed once a search
+//! completes.
+
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::reporter::event::CheckedVersion;
+use crate::run_artifacts::ignore_artifacts;
+use crate::semver;
+use camino::{Utf8Path, Utf8PathBuf};
+
+/// The file, relative to the root of the crate, to which the state of a search is saved.
+pub const STATE_FILE: &str = ".cargo-msrv/state.json";
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchState {
+    /// The target for which the Rust versions were checked.
+    pub target: String,
+    /// The Rust versions which were checked conclusively, in the order in which they were checked.
+    pub checked: Vec<CheckedVersion>,
+}
+
+impl SearchState {
+    /// Whether the given Rust version is compatible, if it was checked for the given target.
+    pub fn outcome(&self, version: &semver::Version, target: &str) -> Option<bool> {
+        if self.target != target {
+            return None;
+        }
+
+        self.checked
+            .iter()
+            .find(|checked| &checked.version == version)
+            .map(|checked| checked.compatible)
+    }
+}
+
+/// Save the state of the search of the crate at `root`, and return the path of the state file.
+pub fn save(root: &Utf8Path, state: &SearchState) -> TResult<Utf8PathBuf> {
+    let path = root.join(STATE_FILE);
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::CreateDir(dir.to_path_buf()),
+        })?;
+    }
+    ignore_artifacts(&path)?;
+
+    let contents = serde_json::to_string_pretty(state).expect("a search state can be serialized");
+    std::fs::write(&path, contents).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::WriteFile(path.clone()),
+    })?;
+
+    Ok(path)
+}
+
+/// Load the saved state of the search of the crate at `root`, if any.
+pub fn load(root: &Utf8Path) -> TResult<Option<SearchState>> {
+    let path = root.join(STATE_FILE);
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(IoError {
+                error,
+                source: IoErrorSource::ReadFile(path),
+            }
+            .into())
+        }
+    };
+
+    // A state which can't be read, e.g. of another version of cargo-msrv, is searched anew
+    match serde_json::from_str(&contents) {
+        Ok(state) => Ok(Some(state)),
+        Err(error) => {
+            info!(%error, %path, "ignored the unreadable search state");
+            Ok(None)
+        }
+    }
+}
+
+/// Remove the saved state of the search of the crate at `root`, if any.
+pub fn remove(root: &Utf8Path) {
+    let path = root.join(STATE_FILE);
+
+    if let Err(error) = std::fs::remove_file(&path) {
+        if error.kind() != std::io::ErrorKind::NotFound {
+            info!(%error, %path, "unable to remove the search state");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> SearchState {
+        SearchState {
+            target: "x86_64-unknown-linux-gnu".to_string(),
+            checked: vec![
+                CheckedVersion {
+                    version: semver::Version::new(1, 68, 0),
+                    compatible: true,
+                },
+                CheckedVersion {
+                    version: semver::Version::new(1, 62, 0),
+                    compatible: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn saved_and_loaded() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        assert_eq!(load(root).unwrap(), None);
+
+        let path = save(root, &state()).unwrap();
+        assert_eq!(path, root.join(STATE_FILE));
+        assert_eq!(load(root).unwrap(), Some(state()));
+
+        remove(root);
+        assert_eq!(load(root).unwrap(), None);
+    }
+
+    #[yare::parameterized(
+        compatible = { 68, "x86_64-unknown-linux-gnu", Some(true) },
+        incompatible = { 62, "x86_64-unknown-linux-gnu", Some(false) },
+        not_checked = { 65, "x86_64-unknown-linux-gnu", None },
+        other_target = { 68, "aarch64-apple-darwin", None },
+    )]
+    fn outcome(minor: u64, target: &str, expected: Option<bool>) {
+        assert_eq!(
+            state().outcome(&semver::Version::new(1, minor, 0), target),
+            expected
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! Stop gracefully on Ctrl-C (SIGINT) or SIGTERM.
+//!
+//! On the first signal, the check which is running is interrupted, and the search stops: the
+//! bounds of the MSRV narrowed down so far are reported, the state of the search is saved so it
+//! can be continued with `--resume`, and the output is finished as usual. `bisect-lockfile` stops
+//! likewise, so it restores the lockfile. On a second signal, cargo-msrv exits right away. Other
+//! subcommands exit right away on the first signal.
+//!
+//! The processes spawned by cargo-msrv run in process trees of their own, which don't receive
+//! the signal of a terminal, so these are killed, see [`process_tree`].
+//!
+//! [`process_tree`]: crate::external_command::process_tree
+
+use crate::exit_code::ExitCode;
+use crate::external_command::interrupt::interrupt_running_commands;
+use crate::external_command::process_tree;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Install the handler of Ctrl-C and SIGTERM (on Windows, of Ctrl-C and Ctrl-Break). If the
+/// subcommand doesn't `search`, cargo-msrv exits on the first signal.
+pub fn install_handler(search: bool) -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(move || {
+        process_tree::kill_all();
+
+        if INTERRUPTED.swap(true, Ordering::SeqCst) || !search {
+            std::process::exit(ExitCode::Interrupted.into());
+        }
+
+        interrupt_running_commands();
+    })
+}
+
+/// Whether cargo-msrv received a signal to stop, as a flag which can be handed to a
+/// [`SignalCheck`].
+///
+/// [`SignalCheck`]: crate::check::SignalCheck
+pub fn interrupted() -> &'static AtomicBool {
+    &INTERRUPTED
+}
@@ -0,0 +1,148 @@
+//! Extracts the items and features from compiler output, which may be looked up in the
+//! stabilization index.
+
+/// Something referred to by a compiler error, which may not have been stable (or have existed)
+/// in the Rust version which was used to compile the crate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Needle<'output> {
+    /// An item, like a type, function, macro or method.
+    ///
+    /// The `scope` is the type or module in which the item was expected to be found, if known.
+    Item {
+        name: &'output str,
+        scope: Option<&'output str>,
+    },
+    /// An unstable library feature.
+    Feature(&'output str),
+}
+
+/// Collect the needles from the error lines of compiler output.
+pub(crate) fn needles(output: &str) -> Vec<Needle<'_>> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("error"))
+        .filter_map(needle)
+        .collect()
+}
+
+fn needle(line: &str) -> Option<Needle<'_>> {
+    if line.contains("use of unstable library feature") {
+        return unstable_feature(line).map(Needle::Feature);
+    }
+
+    let ticks = backticked(line);
+
+    if line.contains("no method named") || line.contains("no function or associated item named") {
+        let name = *ticks.first()?;
+        let scope = ticks.get(1).map(|ty| base_type(ty));
+
+        return Some(Needle::Item { name, scope });
+    }
+
+    if line.contains("unresolved import")
+        || line.contains("cannot find")
+        || line.contains("could not find")
+    {
+        let path = *ticks.first()?;
+        let mut segments = path.rsplit("::");
+        let name = segments.next()?;
+        let scope = segments
+            .next()
+            .or_else(|| ticks.get(1).and_then(|module| module.rsplit("::").next()));
+
+        return Some(Needle::Item { name, scope });
+    }
+
+    None
+}
+
+/// The name of an unstable feature, which is either quoted by backticks (recent compilers),
+/// or single quotes (older compilers).
+fn unstable_feature(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once("feature ")?;
+    let rest = rest.strip_prefix(['`', '\''])?;
+    let end = rest.find(['`', '\''])?;
+
+    Some(&rest[..end])
+}
+
+fn backticked(line: &str) -> Vec<&str> {
+    line.split('`').skip(1).step_by(2).collect()
+}
+
+/// The unqualified name of a type, without references, lifetimes or generic arguments.
+///
+/// For example, `&'a mut std::option::Option<T>` becomes `Option`.
+fn base_type(ty: &str) -> &str {
+    let ty = ty.trim_start_matches('&');
+    let ty = match ty.strip_prefix('\'') {
+        Some(lifetime) => lifetime
+            .split_once(' ')
+            .map(|(_, rest)| rest)
+            .unwrap_or(lifetime),
+        None => ty,
+    };
+    let ty = ty.strip_prefix("mut ").unwrap_or(ty);
+    let ty = ty.split('<').next().unwrap_or(ty);
+
+    ty.rsplit("::").next().unwrap_or(ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        unresolved_import = { "error[E0432]: unresolved import `std::sync::OnceLock`", "OnceLock", Some("sync") },
+        cannot_find_in_module = { "error[E0412]: cannot find type `OnceLock` in module `std::sync`", "OnceLock", Some("sync") },
+        could_not_find = { "error[E0433]: failed to resolve: could not find `OnceLock` in `sync`", "OnceLock", Some("sync") },
+        cannot_find_macro = { "error: cannot find macro `matches` in this scope", "matches", None },
+        no_method = { "error[E0599]: no method named `is_some_and` found for enum `Option<u32>` in the current scope", "is_some_and", Some("Option") },
+        no_method_reference = { "error[E0599]: no method named `strip_prefix` found for reference `&'static str` in the current scope", "strip_prefix", Some("str") },
+        no_associated_function = { "error[E0599]: no function or associated item named `from_fn` found for struct `Foo` in the current scope", "from_fn", Some("Foo") },
+    )]
+    fn item(line: &str, name: &str, scope: Option<&str>) {
+        assert_eq!(needles(line), vec![Needle::Item { name, scope }]);
+    }
+
+    #[yare::parameterized(
+        backticks = { "error[E0658]: use of unstable library feature `once_cell`" },
+        single_quotes = { "error[E0658]: use of unstable library feature 'once_cell'" },
+        with_reason = { "error[E0658]: use of unstable library feature 'once_cell': recently added" },
+    )]
+    fn feature(line: &str) {
+        assert_eq!(needles(line), vec![Needle::Feature("once_cell")]);
+    }
+
+    #[test]
+    fn ignores_non_error_lines() {
+        let output = "warning: unused import: `std::sync::OnceLock`\n  --> src/lib.rs:1:5";
+
+        assert!(needles(output).is_empty());
+    }
+
+    #[test]
+    fn multiple() {
+        let output = r#"
+error[E0432]: unresolved import `std::sync::OnceLock`
+ --> src/lib.rs:1:5
+  |
+1 | use std::sync::OnceLock;
+  |     ^^^^^^^^^^^^^^^^^^^ no `OnceLock` in `sync`
+
+error[E0658]: use of unstable library feature 'lazy_cell'
+"#;
+
+        assert_eq!(
+            needles(output),
+            vec![
+                Needle::Item {
+                    name: "OnceLock",
+                    scope: Some("sync")
+                },
+                Needle::Feature("lazy_cell"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,509 @@
+//! A lookup from language and library features to the Rust version in which they were stabilized.
+//!
+//! It is used to annotate failed compatibility checks with a hint about the Rust version which is
+//! at least required to compile a crate, e.g. "requires ≥ 1.70 (`OnceLock` stabilized)".
+
+use crate::error::{CargoMSRVError, TResult};
+use crate::semver;
+use crate::stabilization::diagnostics::Needle;
+use std::fmt;
+use toml_edit::{DocumentMut, Item, Table};
+
+mod diagnostics;
+mod release_notes;
+
+/// The lookup which is shipped with cargo-msrv.
+const SHIPPED: &str = include_str!("stabilized.toml");
+
+/// A lookup from items, features and compiler messages to the Rust version in which they were
+/// stabilized.
+#[derive(Debug, Default)]
+pub struct StabilizationIndex {
+    items: Vec<ItemEntry>,
+    features: Vec<FeatureEntry>,
+    messages: Vec<MessageEntry>,
+    language: Vec<FeatureEntry>,
+}
+
+impl StabilizationIndex {
+    /// The stabilization index which is shipped with cargo-msrv.
+    pub fn shipped() -> Self {
+        Self::from_toml(SHIPPED).expect("Unable to parse the shipped stabilization index")
+    }
+
+    /// The shipped stabilization index, supplemented by the cached Rust release notes, if the
+    /// `rust-changelog` release source has been used before.
+    pub fn load() -> Self {
+        let mut index = Self::shipped();
+
+        let cached_release_notes = rust_releases_io::base_cache_dir()
+            .ok()
+            .map(|dir| dir.join("source_rust_changelog").join("RELEASES.md"));
+
+        if let Some(release_notes) = cached_release_notes.filter(|path| path.is_file()) {
+            match std::fs::read_to_string(&release_notes) {
+                Ok(contents) => index.extend_from_release_notes(&contents),
+                Err(error) => {
+                    info!(?error, path = ?release_notes, "unable to read cached release notes")
+                }
+            }
+        }
+
+        index
+    }
+
+    /// Parse an index from its `TOML` representation.
+    pub fn from_toml(contents: &str) -> TResult<Self> {
+        let document = contents
+            .parse::<DocumentMut>()
+            .map_err(CargoMSRVError::ParseToml)?;
+
+        let items = entries(&document, "item", |table| {
+            Some(ItemEntry {
+                path: table.get("path")?.as_str()?.to_string(),
+                name: None,
+                version: version(table)?,
+            })
+        })?;
+
+        let features = entries(&document, "feature", |table| {
+            Some(FeatureEntry {
+                name: table.get("name")?.as_str()?.to_string(),
+                version: version(table)?,
+                description: table.get("description")?.as_str()?.to_string(),
+            })
+        })?;
+
+        let messages = entries(&document, "message", |table| {
+            Some(MessageEntry {
+                contains: table.get("contains")?.as_str()?.to_string(),
+                version: version(table)?,
+                description: table.get("description")?.as_str()?.to_string(),
+            })
+        })?;
+
+        let language = entries(&document, "language", |table| {
+            Some(FeatureEntry {
+                name: table.get("name")?.as_str()?.to_string(),
+                version: version(table)?,
+                description: table.get("description")?.as_str()?.to_string(),
+            })
+        })?;
+
+        Ok(Self {
+            items,
+            features,
+            messages,
+            language,
+        })
+    }
+
+    /// Add the stabilized APIs listed in the Rust release notes (`RELEASES.md`).
+    ///
+    /// Items which are already part of the index are not replaced.
+    pub fn extend_from_release_notes(&mut self, release_notes: &str) {
+        for entry in release_notes::parse(release_notes) {
+            if !self.items.iter().any(|item| item.path == entry.path) {
+                self.items.push(entry);
+            }
+        }
+    }
+
+    /// All hints which could be derived from the compiler output of a failed check.
+    pub fn hints(&self, output: &str) -> Vec<StabilizationHint> {
+        let mut hints = Vec::new();
+
+        for needle in diagnostics::needles(output) {
+            match needle {
+                Needle::Item { name, scope } => hints.extend(self.item_hint(name, scope)),
+                Needle::Feature(name) => hints.extend(
+                    self.features
+                        .iter()
+                        .find(|feature| feature.name == name)
+                        .map(|feature| {
+                            StabilizationHint::new(feature.version.clone(), &feature.description)
+                        }),
+                ),
+            }
+        }
+
+        hints.extend(
+            self.messages
+                .iter()
+                .filter(|message| output.contains(&message.contains))
+                .map(|message| {
+                    StabilizationHint::new(message.version.clone(), &message.description)
+                }),
+        );
+
+        hints
+    }
+
+    /// The hint for a library item, like a type, function or method, if it is part of the index.
+    ///
+    /// The `scope` is the type or module which contains the item, e.g. `sync` for
+    /// `std::sync::OnceLock`.
+    pub fn item_hint(&self, name: &str, scope: Option<&str>) -> Option<StabilizationHint> {
+        self.items
+            .iter()
+            .find(|item| item.matches(name, scope))
+            .map(ItemEntry::hint)
+    }
+
+    /// The hint for a language feature, like `let_else`, if it is part of the index.
+    pub fn language_hint(&self, name: &str) -> Option<StabilizationHint> {
+        self.language
+            .iter()
+            .find(|feature| feature.name == name)
+            .map(|feature| StabilizationHint::new(feature.version.clone(), &feature.description))
+    }
+
+    /// The most restrictive hint which could be derived from the compiler output of a failed
+    /// check, i.e. the hint requiring the most recent Rust version.
+    pub fn hint(&self, output: &str) -> Option<StabilizationHint> {
+        self.hints(output)
+            .into_iter()
+            .max_by(|lhs, rhs| lhs.version.cmp(&rhs.version))
+    }
+
+    /// The editions and language features which become available when the MSRV is raised beyond
+    /// `msrv`, up to and including `latest`, per Rust version, from the oldest to the newest.
+    pub fn unlocks(&self, msrv: &semver::Version, latest: &semver::Version) -> Vec<Unlock> {
+        let mut unlocks: Vec<Unlock> = Vec::new();
+
+        let mut language = self
+            .language
+            .iter()
+            .filter(|feature| &feature.version > msrv && &feature.version <= latest)
+            .collect::<Vec<_>>();
+        language.sort_by(|lhs, rhs| lhs.version.cmp(&rhs.version));
+
+        for feature in language {
+            let unlock = match unlocks.last_mut() {
+                Some(unlock) if unlock.version == feature.version => unlock,
+                _ => {
+                    unlocks.push(Unlock {
+                        version: feature.version.clone(),
+                        edition: None,
+                        features: Vec::new(),
+                    });
+                    unlocks.last_mut().expect("an unlock was just added")
+                }
+            };
+
+            match feature.name.strip_prefix("edition") {
+                Some(edition) => unlock.edition = Some(edition.to_string()),
+                None => unlock.features.push(feature.description.clone()),
+            }
+        }
+
+        unlocks
+    }
+}
+
+/// The edition and language features which were stabilized in a Rust version, and thus become
+/// available to a crate which raises its MSRV to that version.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Unlock {
+    /// The Rust version in which the edition or features were stabilized.
+    pub version: semver::Version,
+    /// The edition which was stabilized, e.g. `2021`.
+    pub edition: Option<String>,
+    /// The language features which were stabilized, e.g. "let-else statements".
+    pub features: Vec<String>,
+}
+
+impl fmt::Display for Unlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unlocked = self
+            .edition
+            .iter()
+            .map(|edition| format!("edition {}", edition))
+            .chain(self.features.iter().cloned())
+            .collect::<Vec<_>>();
+
+        let list = match unlocked.as_slice() {
+            [] => String::new(),
+            [only] => only.clone(),
+            [init @ .., last] => format!("{} and {}", init.join(", "), last),
+        };
+
+        write!(
+            f,
+            "Bumping to {}.{} unlocks {}",
+            self.version.major, self.version.minor, list
+        )
+    }
+}
+
+fn entries<T>(
+    document: &DocumentMut,
+    key: &str,
+    parse: impl Fn(&Table) -> Option<T>,
+) -> TResult<Vec<T>> {
+    let Some(item) = document.get(key) else {
+        return Ok(Vec::new());
+    };
+
+    let tables = item.as_array_of_tables().ok_or_else(|| {
+        CargoMSRVError::InvalidConfig(format!(
+            "Expected '{}' to be an array of tables in the stabilization index",
+            key
+        ))
+    })?;
+
+    tables
+        .iter()
+        .map(|table| {
+            parse(table).ok_or_else(|| {
+                CargoMSRVError::InvalidConfig(format!(
+                    "Invalid '{}' entry in the stabilization index: {}",
+                    key, table
+                ))
+            })
+        })
+        .collect()
+}
+
+fn version(table: &Table) -> Option<semver::Version> {
+    table
+        .get("version")
+        .and_then(Item::as_str)
+        .and_then(|v| semver::Version::parse(v).ok())
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ItemEntry {
+    /// The path to the item, e.g. `std::sync::OnceLock` or `u32::ilog2`.
+    pub(crate) path: String,
+    /// How the item is presented to the user; if absent, derived from the path.
+    pub(crate) name: Option<String>,
+    pub(crate) version: semver::Version,
+}
+
+impl ItemEntry {
+    fn matches(&self, name: &str, scope: Option<&str>) -> bool {
+        let mut segments = self.path.rsplit("::");
+
+        if segments.next() != Some(name) {
+            return false;
+        }
+
+        match scope {
+            Some(scope) => segments.next() == Some(scope),
+            None => true,
+        }
+    }
+
+    fn hint(&self) -> StabilizationHint {
+        let name = match &self.name {
+            Some(name) => name.clone(),
+            None => self.display_name(),
+        };
+
+        StabilizationHint::new(self.version.clone(), format!("`{}`", name))
+    }
+
+    /// Members of types are shown together with their type, e.g. `Option::is_some_and`, other
+    /// items just by their name, e.g. `OnceLock`.
+    fn display_name(&self) -> String {
+        let segments = self.path.split("::").collect::<Vec<_>>();
+
+        match segments.as_slice() {
+            [.., parent, member] if is_type(parent, segments.len()) => {
+                format!("{}::{}", parent, member)
+            }
+            [.., item] => item.to_string(),
+            [] => self.path.clone(),
+        }
+    }
+}
+
+/// Whether the parent segment of an item path is a type, rather than a module or crate.
+/// Primitive types, like `u32` in `u32::ilog2`, are not part of a module path.
+fn is_type(segment: &str, path_len: usize) -> bool {
+    let is_crate = ["std", "core", "alloc"].contains(&segment);
+
+    segment.starts_with(char::is_uppercase) || (path_len == 2 && !is_crate)
+}
+
+#[derive(Debug)]
+struct FeatureEntry {
+    name: String,
+    version: semver::Version,
+    description: String,
+}
+
+#[derive(Debug)]
+struct MessageEntry {
+    contains: String,
+    version: semver::Version,
+    description: String,
+}
+
+/// A hint about the Rust version which is at least required, because a language or library
+/// feature was only stabilized in that version.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct StabilizationHint {
+    /// The Rust version in which the feature was stabilized.
+    pub version: semver::Version,
+    /// What was stabilized, e.g. "`OnceLock` stabilized".
+    pub reason: String,
+}
+
+impl StabilizationHint {
+    fn new(version: semver::Version, what: impl fmt::Display) -> Self {
+        Self {
+            version,
+            reason: format!("{} stabilized", what),
+        }
+    }
+}
+
+impl fmt::Display for StabilizationHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.version.patch == 0 {
+            write!(
+                f,
+                "requires ≥ {}.{} ({})",
+                self.version.major, self.version.minor, self.reason
+            )
+        } else {
+            write!(f, "requires ≥ {} ({})", self.version, self.reason)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_index_is_valid() {
+        let index = StabilizationIndex::from_toml(SHIPPED).unwrap();
+
+        assert!(!index.items.is_empty());
+        assert!(!index.features.is_empty());
+        assert!(!index.messages.is_empty());
+        assert!(!index.language.is_empty());
+    }
+
+    #[test]
+    fn unlocks_after_msrv() {
+        let index = StabilizationIndex::shipped();
+        let unlocks = index.unlocks(
+            &semver::Version::new(1, 62, 0),
+            &semver::Version::new(1, 75, 0),
+        );
+
+        assert_eq!(
+            unlocks
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "Bumping to 1.65 unlocks let-else statements, generic associated types and `break` from labeled blocks",
+                "Bumping to 1.75 unlocks `async fn` in traits and `impl Trait` in trait method return types",
+            ]
+        );
+    }
+
+    #[test]
+    fn unlocks_edition() {
+        let index = StabilizationIndex::shipped();
+        let unlocks = index.unlocks(
+            &semver::Version::new(1, 84, 1),
+            &semver::Version::new(1, 85, 0),
+        );
+
+        assert_eq!(
+            unlocks,
+            vec![Unlock {
+                version: semver::Version::new(1, 85, 0),
+                edition: Some("2024".to_string()),
+                features: vec!["async closures".to_string()],
+            }]
+        );
+        assert_eq!(
+            unlocks[0].to_string(),
+            "Bumping to 1.85 unlocks edition 2024 and async closures"
+        );
+    }
+
+    #[test]
+    fn language_hint() {
+        let index = StabilizationIndex::shipped();
+        let hint = index.language_hint("let_else").unwrap();
+
+        assert_eq!(
+            hint.to_string(),
+            "requires ≥ 1.65 (let-else statements stabilized)"
+        );
+    }
+
+    #[yare::parameterized(
+        unresolved_import = { "error[E0432]: unresolved import `std::sync::OnceLock`", "requires ≥ 1.70 (`OnceLock` stabilized)" },
+        method = { "error[E0599]: no method named `is_some_and` found for enum `Option` in the current scope", "requires ≥ 1.70 (`Option::is_some_and` stabilized)" },
+        primitive_method = { "error[E0599]: no method named `then_some` found for type `bool` in the current scope", "requires ≥ 1.62 (`bool::then_some` stabilized)" },
+        feature = { "error[E0658]: use of unstable library feature 'lazy_cell'", "requires ≥ 1.80 (`LazyCell` and `LazyLock` stabilized)" },
+        message = { "error[E0658]: let...else statements are unstable", "requires ≥ 1.65 (let-else statements stabilized)" },
+        cargo_edition = { "error: failed to parse manifest\n\nCaused by:\n  feature `edition2021` is required", "requires ≥ 1.56 (edition 2021 stabilized)" },
+    )]
+    fn shipped_hint(output: &str, expected: &str) {
+        let index = StabilizationIndex::shipped();
+        let hint = index.hint(output).unwrap();
+
+        assert_eq!(hint.to_string(), expected);
+    }
+
+    #[test]
+    fn most_recent_hint() {
+        let index = StabilizationIndex::shipped();
+        let output = "error[E0432]: unresolved import `std::sync::LazyLock`\nerror[E0432]: unresolved import `std::sync::OnceLock`";
+
+        let hint = index.hint(output).unwrap();
+
+        assert_eq!(hint.version, semver::Version::new(1, 80, 0));
+        assert_eq!(index.hints(output).len(), 2);
+    }
+
+    #[test]
+    fn method_requires_matching_type() {
+        let index = StabilizationIndex::shipped();
+        let output =
+            "error[E0599]: no method named `inspect` found for struct `Foo` in the current scope";
+
+        assert!(index.hint(output).is_none());
+    }
+
+    #[test]
+    fn no_hint() {
+        let index = StabilizationIndex::shipped();
+
+        assert!(index.hint("error[E0308]: mismatched types").is_none());
+    }
+
+    #[test]
+    fn extended_from_release_notes() {
+        let mut index = StabilizationIndex::default();
+        index.extend_from_release_notes(
+            r#"
+Version 1.80.0 (2024-07-25)
+==========================
+
+Stabilized APIs
+---------------
+
+- [`LazyLock`](https://doc.rust-lang.org/stable/std/sync/struct.LazyLock.html)
+"#,
+        );
+
+        let hint = index
+            .hint("error[E0432]: unresolved import `std::sync::LazyLock`")
+            .unwrap();
+
+        assert_eq!(hint.to_string(), "requires ≥ 1.80 (`LazyLock` stabilized)");
+    }
+}
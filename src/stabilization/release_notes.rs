@@ -0,0 +1,181 @@
+//! Parses the "Stabilized APIs" sections of the Rust release notes (`RELEASES.md`), so the
+//! stabilization index can be kept up to date with the latest Rust releases.
+
+use crate::semver;
+use crate::stabilization::ItemEntry;
+
+const VERSION_HEADER: &str = "Version ";
+const STABILIZED_APIS_HEADER: &str = "Stabilized APIs";
+const DOCS_PREFIX: &str = "https://doc.rust-lang.org/";
+
+/// Collect the stabilized library items from the release notes.
+pub(crate) fn parse(release_notes: &str) -> Vec<ItemEntry> {
+    let mut entries = Vec::new();
+
+    let mut version: Option<semver::Version> = None;
+    let mut in_stabilized_apis = false;
+
+    for line in release_notes.lines().map(str::trim) {
+        if let Some(header) = line.strip_prefix(VERSION_HEADER) {
+            // e.g. `Version 1.70.0 (2023-06-01)`
+            version = header
+                .split_whitespace()
+                .next()
+                .and_then(|v| semver::Version::parse(v).ok());
+            in_stabilized_apis = false;
+        } else if line == STABILIZED_APIS_HEADER {
+            in_stabilized_apis = true;
+        } else if in_stabilized_apis && is_section_end(line) {
+            in_stabilized_apis = false;
+        } else if in_stabilized_apis {
+            if let Some(version) = &version {
+                entries.extend(parse_api(line, version));
+            }
+        }
+    }
+
+    entries
+}
+
+/// The list of stabilized APIs ends at the next section, or the list of APIs which have been
+/// made stable in const contexts.
+fn is_section_end(line: &str) -> bool {
+    let is_list_item = line.starts_with('-') || line.starts_with('*');
+
+    !line.is_empty() && !is_list_item
+}
+
+/// Parse an API list item, like:
+/// ``- [`OnceLock`](https://doc.rust-lang.org/stable/std/sync/struct.OnceLock.html)``
+fn parse_api(line: &str, version: &semver::Version) -> Option<ItemEntry> {
+    let (_, rest) = line.split_once('[')?;
+    let (name, rest) = rest.split_once("](")?;
+    let (url, _) = rest.split_once(')')?;
+
+    let name = name.trim_matches('`');
+    let path = path_from_docs_url(url)?;
+
+    Some(ItemEntry {
+        path,
+        name: Some(name.to_string()),
+        version: version.clone(),
+    })
+}
+
+/// Derives the path to an item from its documentation url, for example:
+///
+/// * `https://doc.rust-lang.org/stable/std/sync/struct.OnceLock.html` becomes `std::sync::OnceLock`
+/// * `https://doc.rust-lang.org/stable/std/primitive.u32.html#method.ilog2` becomes `u32::ilog2`
+///
+/// Returns `None` for links which do not point to a single item, like trait implementations.
+fn path_from_docs_url(url: &str) -> Option<String> {
+    let url = url.strip_prefix(DOCS_PREFIX)?;
+    let url = url
+        .strip_prefix("stable/")
+        .or_else(|| url.strip_prefix("nightly/"))
+        .unwrap_or(url);
+
+    let (page, fragment) = match url.split_once('#') {
+        Some((page, fragment)) => (page, Some(fragment)),
+        None => (url, None),
+    };
+
+    let mut segments = page
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    let item = segments.pop()?.strip_suffix(".html")?;
+    let (kind, item) = item.split_once('.')?;
+
+    let mut path = if kind == "primitive" {
+        vec![item]
+    } else {
+        segments.push(item);
+        segments
+    };
+
+    if let Some(fragment) = fragment {
+        let member = ["method.", "tymethod.", "associatedconstant."]
+            .iter()
+            .find_map(|prefix| fragment.strip_prefix(prefix))?;
+
+        path.push(member);
+    }
+
+    Some(path.join("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE_NOTES: &str = r#"
+Version 1.70.0 (2023-06-01)
+==========================
+
+<a id="1.70.0-Language"></a>
+
+Language
+--------
+- [Relax ordering rules for `asm!` operands](https://github.com/rust-lang/rust/pull/105798/)
+
+<a id="1.70.0-Stabilized-APIs"></a>
+
+Stabilized APIs
+---------------
+
+- [`OnceLock`](https://doc.rust-lang.org/stable/std/sync/struct.OnceLock.html)
+- [`Option::is_some_and`](https://doc.rust-lang.org/stable/std/option/enum.Option.html#method.is_some_and)
+- [`Default for std::collections::binary_heap::IntoIter`](https://doc.rust-lang.org/stable/std/collections/binary_heap/struct.IntoIter.html#impl-Default-for-IntoIter%3CT%3E)
+
+These APIs are now stable in const contexts:
+
+- [`NonNull::slice_from_raw_parts`](https://doc.rust-lang.org/stable/std/ptr/struct.NonNull.html#method.slice_from_raw_parts)
+
+Version 1.67.0 (2023-01-26)
+==========================
+
+Stabilized APIs
+---------------
+
+- [`{integer}::ilog2`](https://doc.rust-lang.org/stable/std/primitive.u32.html#method.ilog2)
+"#;
+
+    #[test]
+    fn stabilized_apis() {
+        let entries = parse(RELEASE_NOTES);
+
+        assert_eq!(
+            entries,
+            vec![
+                ItemEntry {
+                    path: "std::sync::OnceLock".to_string(),
+                    name: Some("OnceLock".to_string()),
+                    version: semver::Version::new(1, 70, 0),
+                },
+                ItemEntry {
+                    path: "std::option::Option::is_some_and".to_string(),
+                    name: Some("Option::is_some_and".to_string()),
+                    version: semver::Version::new(1, 70, 0),
+                },
+                ItemEntry {
+                    path: "u32::ilog2".to_string(),
+                    name: Some("{integer}::ilog2".to_string()),
+                    version: semver::Version::new(1, 67, 0),
+                },
+            ]
+        );
+    }
+
+    #[yare::parameterized(
+        module_item = { "https://doc.rust-lang.org/stable/std/sync/struct.OnceLock.html", Some("std::sync::OnceLock") },
+        function = { "https://doc.rust-lang.org/std/hint/fn.black_box.html", Some("std::hint::black_box") },
+        method = { "https://doc.rust-lang.org/stable/std/option/enum.Option.html#method.is_some_and", Some("std::option::Option::is_some_and") },
+        primitive_method = { "https://doc.rust-lang.org/stable/std/primitive.u32.html#method.ilog2", Some("u32::ilog2") },
+        trait_impl = { "https://doc.rust-lang.org/stable/std/vec/struct.Vec.html#impl-From%3C%5BT;+N%5D%3E-for-Vec%3CT%3E", None },
+        not_docs = { "https://github.com/rust-lang/rust/pull/105798/", None },
+    )]
+    fn docs_url(url: &str, expected: Option<&str>) {
+        assert_eq!(path_from_docs_url(url).as_deref(), expected);
+    }
+}
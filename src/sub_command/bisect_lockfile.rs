@@ -0,0 +1,409 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::MetadataCommand;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::check::{
+    Check, ExitCodeCheck, LockfileCheck, PredicateCheck, ProviderCheck, SignalCheck,
+};
+use crate::context::{BisectLockfileContext, LockfileStrategy, SearchMethod, ToolchainProvider};
+use crate::disk_space;
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::external_command::git_command::GitCommand;
+use crate::lockfile;
+use crate::manifest::exit_codes::configured_exit_codes;
+use crate::reporter::event::{
+    BisectLockfileResult, CheckLockfileRevision, DependencyChange, LockfileRevision,
+};
+use crate::reporter::Reporter;
+use crate::rust::{dist_server, Toolchain};
+use crate::{semver, signal, SubCommand};
+
+/// Find the update of the lockfile which first made the crate fail with a toolchain, with a binary
+/// search over the committed revisions of the lockfile.
+///
+/// The revisions are written to the lockfile of the workspace in turn, so the checks share the
+/// target directory of the crate. The lockfile is restored afterwards, also when the bisection
+/// fails, or is interrupted by Ctrl-C.
+#[derive(Default)]
+pub struct BisectLockfile;
+
+impl SubCommand for BisectLockfile {
+    type Context = BisectLockfileContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let metadata = MetadataCommand::new()
+            .manifest_path(ctx.environment.manifest())
+            .no_deps()
+            .exec()?;
+
+        let path = metadata.workspace_root.join("Cargo.lock");
+        let original =
+            std::fs::read_to_string(&path).map_err(|_| Error::NoLockfile(path.clone()))?;
+        let guard = LockfileGuard::new(&path, &original);
+
+        let git = GitCommand::new(&metadata.workspace_root);
+        let relative_path = repository_path(&git.toplevel()?, &path)?;
+        let history = History::new(&git, &relative_path, ctx.good.as_deref(), &original)?;
+
+        let toolchain = Toolchain::new(
+            ctx.toolchain_version.clone(),
+            ctx.toolchain.target,
+            ctx.toolchain.components,
+        );
+
+        if ctx.toolchain.dist_server_check {
+            dist_server::preflight(&ToolchainProvider::Rustup, [toolchain.version()])?;
+        }
+
+        if ctx.toolchain.disk_preflight {
+            disk_space::preflight(
+                reporter,
+                &ctx.environment,
+                &ToolchainProvider::Rustup,
+                [toolchain.version()],
+                disk_space::planned_checks(SearchMethod::Bisect, history.len() as u64),
+            )?;
+        }
+
+        // Lockfiles which the cargo of the toolchain can't read are checked downgraded
+        let runner = LockfileCheck::new(
+            reporter,
+            ProviderCheck::new(
+                reporter,
+                false,
+                ctx.no_check_feedback,
+                &ctx.environment,
+                ctx.run_command(),
+                ToolchainProvider::Rustup,
+            )
+            .with_fallback_host(ctx.toolchain.fallback_host),
+            Some(path.clone()),
+            Some(LockfileStrategy::Downgrade),
+        );
+        let exit_codes = configured_exit_codes(&ctx.environment)?;
+        let runner = ExitCodeCheck::new(reporter, runner, &exit_codes);
+        let runner = PredicateCheck::new(reporter, runner, ctx.check_cmd.predicate.as_deref());
+        let runner = SignalCheck::new(runner, signal::interrupted());
+
+        let mut bisection = Bisection {
+            reporter,
+            runner: &runner,
+            toolchain: &toolchain,
+            path: &path,
+            history: &history,
+            checks: 0,
+        };
+        let outcome = bisection.run();
+
+        // Restored even if a check failed
+        guard.restore()?;
+
+        let (first_incompatible, last_compatible) = outcome?;
+
+        let changes = changes(
+            &lockfile::packages(&history.contents(last_compatible)?),
+            &lockfile::packages(&history.contents(first_incompatible)?),
+        );
+
+        reporter.report_event(BisectLockfileResult::new(
+            toolchain.version().clone(),
+            history.revisions[first_incompatible].clone(),
+            history.revisions[last_compatible].clone(),
+            changes,
+            bisection.checks,
+        ))?;
+
+        Ok(())
+    }
+}
+
+/// The revisions of the lockfile, newest first: the lockfile of the working tree, if it differs
+/// from the newest committed revision, and the commits which changed it. The oldest revision is
+/// the commit given by `--good`, if any.
+struct History<'g> {
+    git: &'g GitCommand<'g>,
+    path: &'g Utf8Path,
+    revisions: Vec<LockfileRevision>,
+    working_tree: &'g str,
+}
+
+impl<'g> History<'g> {
+    fn new(
+        git: &'g GitCommand<'g>,
+        path: &'g Utf8Path,
+        good: Option<&str>,
+        working_tree: &'g str,
+    ) -> TResult<Self> {
+        let commits = match good {
+            Some(good) => {
+                let good = git.commit(good)?;
+                let mut commits = git.log(&format!("{}..HEAD", good.id), path)?;
+                commits.push(good);
+                commits
+            }
+            None => git.log("HEAD", path)?,
+        };
+
+        if commits.is_empty() {
+            return Err(Error::NoCommittedRevisions(path.to_path_buf()).into());
+        }
+
+        let mut history = Self {
+            git,
+            path,
+            revisions: commits.into_iter().map(LockfileRevision::Commit).collect(),
+            working_tree,
+        };
+
+        if history.contents(0)? != working_tree {
+            history.revisions.insert(0, LockfileRevision::WorkingTree);
+        }
+
+        Ok(history)
+    }
+
+    fn len(&self) -> usize {
+        self.revisions.len()
+    }
+
+    fn contents(&self, index: usize) -> TResult<String> {
+        match &self.revisions[index] {
+            LockfileRevision::WorkingTree => Ok(self.working_tree.to_string()),
+            LockfileRevision::Commit(commit) => self.git.show(&commit.id, self.path),
+        }
+    }
+}
+
+struct Bisection<'a, R: Reporter, C: Check> {
+    reporter: &'a R,
+    runner: &'a C,
+    toolchain: &'a Toolchain,
+    path: &'a Utf8Path,
+    history: &'a History<'a>,
+    checks: u64,
+}
+
+impl<R: Reporter, C: Check> Bisection<'_, R, C> {
+    /// The indices of the first incompatible and the last compatible revision.
+    fn run(&mut self) -> TResult<(usize, usize)> {
+        let (mut incompatible, mut compatible) = (0, self.history.len() - 1);
+
+        if self.is_compatible(incompatible)? {
+            return Err(Error::NewestCompatible {
+                toolchain: self.toolchain.version().clone(),
+                revision: self.history.revisions[incompatible].to_string(),
+            }
+            .into());
+        }
+
+        if compatible == incompatible || !self.is_compatible(compatible)? {
+            return Err(Error::OldestIncompatible {
+                toolchain: self.toolchain.version().clone(),
+                revision: self.history.revisions[compatible].to_string(),
+            }
+            .into());
+        }
+
+        while compatible - incompatible > 1 {
+            let middle = incompatible + (compatible - incompatible) / 2;
+
+            if self.is_compatible(middle)? {
+                compatible = middle;
+            } else {
+                incompatible = middle;
+            }
+        }
+
+        Ok((incompatible, compatible))
+    }
+
+    fn is_compatible(&mut self, index: usize) -> TResult<bool> {
+        let revision = &self.history.revisions[index];
+        info!(%revision, "checking lockfile revision");
+
+        self.reporter
+            .report_event(CheckLockfileRevision::new(revision.clone()))?;
+
+        let contents = self.history.contents(index)?;
+        std::fs::write(self.path, contents).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::WriteFile(self.path.to_path_buf()),
+        })?;
+
+        self.checks += 1;
+        Ok(self.runner.check(self.toolchain)?.is_success())
+    }
+}
+
+/// Restores the original contents of the lockfile: explicitly, to report a failure to restore it,
+/// or otherwise when dropped, e.g. when the bisection returns early with an error.
+struct LockfileGuard<'a> {
+    path: &'a Utf8Path,
+    original: &'a str,
+    restored: bool,
+}
+
+impl<'a> LockfileGuard<'a> {
+    fn new(path: &'a Utf8Path, original: &'a str) -> Self {
+        Self {
+            path,
+            original,
+            restored: false,
+        }
+    }
+
+    fn restore(mut self) -> TResult<()> {
+        self.restored = true;
+
+        std::fs::write(self.path, self.original).map_err(|error| {
+            IoError {
+                error,
+                source: IoErrorSource::WriteFile(self.path.to_path_buf()),
+            }
+            .into()
+        })
+    }
+}
+
+impl Drop for LockfileGuard<'_> {
+    fn drop(&mut self) {
+        if self.restored {
+            return;
+        }
+
+        if let Err(error) = std::fs::write(self.path, self.original) {
+            error!(%error, path = %self.path, "unable to restore the lockfile");
+        }
+    }
+}
+
+/// The path of the lockfile relative to the root of the working tree of the repository.
+fn repository_path(toplevel: &Utf8Path, path: &Utf8Path) -> TResult<Utf8PathBuf> {
+    let canonical = |path: &Utf8Path| {
+        path.canonicalize_utf8().map_err(|error| IoError {
+            error,
+            source: IoErrorSource::Canonicalize(path.to_path_buf()),
+        })
+    };
+
+    let toplevel = canonical(toplevel)?;
+    let path = canonical(path)?;
+
+    match path.strip_prefix(&toplevel) {
+        Ok(relative) => Ok(relative.to_path_buf()),
+        Err(_) => Err(Error::NotInRepository(path.clone()).into()),
+    }
+}
+
+/// The packages of which the locked versions differ between the lockfiles. A package of which a
+/// single version was replaced by another is an update; otherwise, each version which is no longer
+/// locked is removed, and each version which is newly locked is added.
+fn changes(
+    before: &BTreeMap<String, BTreeSet<semver::Version>>,
+    after: &BTreeMap<String, BTreeSet<semver::Version>>,
+) -> Vec<DependencyChange> {
+    let none = BTreeSet::new();
+    let names = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+
+    names
+        .into_iter()
+        .flat_map(|name| {
+            let from = before.get(name).unwrap_or(&none);
+            let to = after.get(name).unwrap_or(&none);
+
+            let removed = from.difference(to).cloned().collect::<Vec<_>>();
+            let added = to.difference(from).cloned().collect::<Vec<_>>();
+
+            let change = |from, to| DependencyChange {
+                name: name.clone(),
+                from,
+                to,
+            };
+
+            match (removed.as_slice(), added.as_slice()) {
+                ([from], [to]) => vec![change(Some(from.clone()), Some(to.clone()))],
+                _ => removed
+                    .into_iter()
+                    .map(|version| change(Some(version), None))
+                    .chain(added.into_iter().map(|version| change(None, Some(version))))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No lockfile found at '{0}'")]
+    NoLockfile(Utf8PathBuf),
+
+    #[error("The lockfile '{0}' is not located in a git repository")]
+    NotInRepository(Utf8PathBuf),
+
+    #[error("The lockfile '{0}' has not been committed, so it has no revisions to bisect")]
+    NoCommittedRevisions(Utf8PathBuf),
+
+    #[error("The crate compiles with Rust {toolchain} with the lockfile of {revision}, so there is no update to find")]
+    NewestCompatible {
+        toolchain: semver::Version,
+        revision: String,
+    },
+
+    #[error("The crate does not compile with Rust {toolchain} with the lockfile of {revision} either; give an older commit of which the lockfile is compatible with '--good'")]
+    OldestIncompatible {
+        toolchain: semver::Version,
+        revision: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The locked packages, given as `name@version`.
+    fn packages(packages: &[&str]) -> BTreeMap<String, BTreeSet<semver::Version>> {
+        let mut locked = BTreeMap::<String, BTreeSet<semver::Version>>::new();
+
+        for package in packages {
+            let (name, version) = package.split_once('@').unwrap();
+            locked
+                .entry(name.to_string())
+                .or_default()
+                .insert(semver::Version::parse(version).unwrap());
+        }
+
+        locked
+    }
+
+    #[test]
+    fn lockfile_restored_when_dropped() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(dir.path().join("Cargo.lock")).unwrap();
+        std::fs::write(&path, "original").unwrap();
+
+        {
+            let _guard = LockfileGuard::new(&path, "original");
+            std::fs::write(&path, "revision").unwrap();
+        }
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "original");
+    }
+
+    #[yare::parameterized(
+        updated = { &["a@1.0.0"], &["a@1.1.0"], &["a 1.0.0 -> 1.1.0"] },
+        added = { &[], &["a@1.0.0"], &["a 1.0.0 (added)"] },
+        removed = { &["a@1.0.0"], &[], &["a 1.0.0 (removed)"] },
+        unchanged = { &["a@1.0.0", "b@0.1.0"], &["a@1.0.0", "b@0.1.0"], &[] },
+        second_version = { &["a@1.0.0"], &["a@1.0.0", "a@2.0.0"], &["a 2.0.0 (added)"] },
+        several_replaced = { &["a@1.0.0", "a@2.0.0"], &["a@1.1.0", "a@2.1.0"], &["a 1.0.0 (removed)", "a 2.0.0 (removed)", "a 1.1.0 (added)", "a 2.1.0 (added)"] },
+    )]
+    fn changed(before: &[&str], after: &[&str], expected: &[&str]) {
+        let changes = changes(&packages(before), &packages(after));
+
+        assert_eq!(
+            changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use crate::cache::GlobalCache;
+use crate::cli::CacheAction;
+use crate::context::CacheContext;
+use crate::error::{CargoMSRVError, TResult};
+use crate::reporter::event::CacheStatsResult;
+use crate::reporter::Reporter;
+use crate::SubCommand;
+
+/// Inspect the global cache of cargo-msrv.
+#[derive(Default)]
+pub struct Cache;
+
+impl SubCommand for Cache {
+    type Context = CacheContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let cache = GlobalCache::new().ok_or(CargoMSRVError::UnableToLocateCache)?;
+
+        match ctx.action {
+            CacheAction::Stats => {
+                let entries = cache.entries()?;
+
+                reporter.report_event(CacheStatsResult::new(
+                    cache.dir().to_path_buf(),
+                    ctx.environment.max_cache_size,
+                    &entries,
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+}
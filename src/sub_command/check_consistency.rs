@@ -0,0 +1,101 @@
+use cargo_metadata::MetadataCommand;
+
+use crate::context::{CheckConsistencyContext, EnvironmentContext};
+use crate::declaration::{self, DeclarationKind};
+use crate::error::TResult;
+use crate::manifest::sync_rules::sync_rules;
+use crate::reporter::event::{ConsistencyResult, Warning};
+use crate::reporter::Reporter;
+use crate::SubCommand;
+
+#[derive(Default)]
+pub struct CheckConsistency;
+
+impl SubCommand for CheckConsistency {
+    type Context = CheckConsistencyContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let result = check_declarations(&ctx.environment, RequiredConsistency::All, reporter)?;
+        reporter.report_event(result)?;
+
+        Ok(())
+    }
+}
+
+/// Which declarations of the MSRV must agree with the Cargo manifest.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RequiredConsistency {
+    /// Each declaration.
+    All,
+    /// The declarations in the README, like an MSRV badge.
+    Readme,
+}
+
+impl RequiredConsistency {
+    fn includes(self, kind: DeclarationKind) -> bool {
+        match self {
+            Self::All => true,
+            Self::Readme => kind.is_readme(),
+        }
+    }
+}
+
+/// Compare the declarations of the MSRV of the crate and its workspace, and with
+/// [`RequiredConsistency::All`], the Rust versions to which its CI jobs are pinned. Each pin
+/// which is older than the MSRV is reported as a warning.
+pub(crate) fn check_declarations(
+    env: &EnvironmentContext,
+    required: RequiredConsistency,
+    reporter: &impl Reporter,
+) -> TResult<ConsistencyResult> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(env.manifest())
+        .no_deps()
+        .exec()?;
+    let workspace_root = &metadata.workspace_root;
+
+    let declarations = declaration::scan(env.root(), workspace_root, &sync_rules(&metadata)?)?;
+    let mut conflicts = declaration::conflicts(&declarations);
+    conflicts.retain(|conflict| required.includes(conflict.declaration.kind));
+
+    let pins = match required {
+        RequiredConsistency::All => declaration::scan_pins(workspace_root)?,
+        RequiredConsistency::Readme => Vec::new(),
+    };
+
+    let result = ConsistencyResult::new(declarations, conflicts).with_pins(pins);
+
+    if let Some(msrv) = result.msrv() {
+        for pin in result.outdated_pins() {
+            reporter.report_event(Warning::outdated_ci_pin(pin, msrv))?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Verify that the required declarations of the MSRV agree with the Cargo manifest. If they
+/// don't, the conflicts are reported.
+pub(crate) fn require_consistency(
+    env: &EnvironmentContext,
+    required: RequiredConsistency,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    let result = check_declarations(env, required, reporter)?;
+
+    if result.is_consistent() {
+        return Ok(());
+    }
+
+    let conflicts = result.conflicts().len();
+    reporter.report_event(result)?;
+
+    Err(Error::Inconsistent { conflicts }.into())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{conflicts} declaration(s) of the MSRV disagree with the Cargo manifest")]
+    Inconsistent { conflicts: usize },
+}
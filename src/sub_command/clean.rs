@@ -0,0 +1,271 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::MetadataCommand;
+
+use crate::context::CleanContext;
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::external_command::rustup_command::RustupCommand;
+use crate::log_file::LOG_FILE_NAME;
+use crate::reporter::event::{CleanCategory, CleanResult, CleanedItem};
+use crate::reporter::Reporter;
+use crate::run_artifacts::RUNS_DIR;
+use crate::rust::installed_toolchains::InstalledToolchains;
+use crate::rust::rustup_home;
+use crate::SubCommand;
+
+/// The directory within the target directory which is used by the container checks.
+const CONTAINER_TARGET_DIR: &str = "cargo-msrv-container";
+
+/// Remove the caches, state files and target directories of cargo-msrv, and optionally the
+/// toolchains it installed.
+#[derive(Default)]
+pub struct Clean;
+
+impl SubCommand for Clean {
+    type Context = CleanContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let record = InstalledToolchains::new();
+
+        let mut paths = cargo_msrv_paths(ctx);
+
+        if ctx.toolchains {
+            if let Some(record) = &record {
+                paths.extend(toolchain_paths(&record.names()?));
+            }
+        }
+
+        let items = paths
+            .into_iter()
+            .filter(|(_, path)| path.exists())
+            .map(|(category, path)| {
+                let bytes = size_of(&path)?;
+                Ok(CleanedItem {
+                    category,
+                    path,
+                    bytes,
+                })
+            })
+            .collect::<TResult<Vec<_>>>()?;
+
+        if !ctx.dry_run {
+            remove(&items, record.as_ref())?;
+        }
+
+        reporter.report_event(CleanResult::new(ctx.dry_run, items))?;
+
+        Ok(())
+    }
+}
+
+/// The files and directories which are written by cargo-msrv, regardless of whether they exist.
+fn cargo_msrv_paths(ctx: &CleanContext) -> Vec<(CleanCategory, Utf8PathBuf)> {
+    let mut paths = Vec::new();
+
+    let caches = [
+        dirs::cache_dir().map(|dir| dir.join("cargo-msrv")),
+        rust_releases_io::base_cache_dir().ok(),
+    ];
+
+    paths.extend(
+        caches
+            .into_iter()
+            .flatten()
+            .filter_map(|dir| Utf8PathBuf::from_path_buf(dir).ok())
+            .map(|dir| (CleanCategory::Cache, dir)),
+    );
+
+    let state = ctx
+        .environment
+        .root()
+        .join(RUNS_DIR)
+        .parent()
+        .map(Utf8Path::to_path_buf);
+
+    paths.extend(state.map(|dir| (CleanCategory::State, dir)));
+
+    let log_dir = dirs::data_local_dir()
+        .map(|dir| dir.join("cargo-msrv"))
+        .and_then(|dir| Utf8PathBuf::from_path_buf(dir).ok());
+
+    paths.extend(
+        log_dir
+            .iter()
+            .flat_map(|dir| log_files(dir))
+            .map(|file| (CleanCategory::Logs, file)),
+    );
+
+    let target_dir = MetadataCommand::new()
+        .manifest_path(ctx.environment.manifest())
+        .no_deps()
+        .exec()
+        .map(|metadata| metadata.target_directory)
+        .unwrap_or_else(|_| ctx.environment.root().join("target"));
+
+    paths.push((CleanCategory::Target, target_dir.join(CONTAINER_TARGET_DIR)));
+
+    paths
+}
+
+/// The log files within the log directory, which may also hold other files, such as the record
+/// of installed toolchains.
+fn log_files(log_dir: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let Ok(entries) = log_dir.read_dir_utf8() else {
+        return Vec::new();
+    };
+
+    let mut files = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().starts_with(LOG_FILE_NAME))
+        .map(|entry| entry.into_path())
+        .collect::<Vec<_>>();
+
+    files.sort();
+    files
+}
+
+/// The directories of the recorded toolchains, within the rustup home directory.
+fn toolchain_paths(names: &[String]) -> Vec<(CleanCategory, Utf8PathBuf)> {
+    let Some(rustup_home) = rustup_home() else {
+        return Vec::new();
+    };
+
+    names
+        .iter()
+        .map(|name| {
+            (
+                CleanCategory::Toolchains,
+                rustup_home.join("toolchains").join(name),
+            )
+        })
+        .collect()
+}
+
+/// The size of the file, or of all files within the directory, in bytes. Symbolic links are not
+/// followed.
+fn size_of(path: &Utf8Path) -> TResult<u64> {
+    let metadata = path.symlink_metadata().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let entries = path.read_dir_utf8().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadDir(path.to_path_buf()),
+    })?;
+
+    let mut size = 0;
+
+    for entry in entries {
+        let entry = entry.map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadDir(path.to_path_buf()),
+        })?;
+
+        size += size_of(entry.path())?;
+    }
+
+    Ok(size)
+}
+
+fn remove(items: &[CleanedItem], record: Option<&InstalledToolchains>) -> TResult<()> {
+    let mut uninstalled = Vec::new();
+
+    for item in items {
+        match item.category {
+            CleanCategory::Toolchains => {
+                let name = item.path.file_name().unwrap_or(item.path.as_str());
+                uninstall(name)?;
+                uninstalled.push(name.to_string());
+            }
+            _ => remove_path(&item.path)?,
+        }
+    }
+
+    match record {
+        Some(record) if !uninstalled.is_empty() => record.forget(&uninstalled),
+        _ => Ok(()),
+    }
+}
+
+fn uninstall(name: &str) -> TResult<()> {
+    info!(toolchain = name, "uninstalling toolchain");
+
+    let rustup = RustupCommand::new()
+        .with_stderr()
+        .with_args(["uninstall", name])
+        .toolchain()?;
+
+    if !rustup.exit_status().success() {
+        return Err(Error::UninstallToolchain {
+            name: name.to_string(),
+            stderr: rustup.stderr().to_string(),
+        }
+        .into());
+    }
+
+    Ok(())
+}
+
+fn remove_path(path: &Utf8Path) -> TResult<()> {
+    let removed = if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    };
+
+    removed.map_err(|error| {
+        IoError {
+            error,
+            source: IoErrorSource::RemoveFile(path.to_path_buf()),
+        }
+        .into()
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Unable to uninstall toolchain '{name}': {stderr}")]
+    UninstallToolchain { name: String, stderr: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_of_directory() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let dir = Utf8Path::from_path(dir.path()).unwrap();
+
+        std::fs::create_dir_all(dir.join("a").join("b")).unwrap();
+        std::fs::write(dir.join("one"), [0; 100]).unwrap();
+        std::fs::write(dir.join("a").join("two"), [0; 20]).unwrap();
+        std::fs::write(dir.join("a").join("b").join("three"), [0; 3]).unwrap();
+
+        assert_eq!(size_of(dir).unwrap(), 123);
+        assert_eq!(size_of(&dir.join("one")).unwrap(), 100);
+    }
+
+    #[test]
+    fn only_log_files() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let dir = Utf8Path::from_path(dir.path()).unwrap();
+
+        std::fs::write(dir.join("cargo-msrv-log.2024-06-15"), "").unwrap();
+        std::fs::write(dir.join("cargo-msrv-log.2024-06-14"), "").unwrap();
+        std::fs::write(dir.join("installed-toolchains"), "").unwrap();
+
+        assert_eq!(
+            log_files(dir),
+            vec![
+                dir.join("cargo-msrv-log.2024-06-14"),
+                dir.join("cargo-msrv-log.2024-06-15")
+            ]
+        );
+    }
+}
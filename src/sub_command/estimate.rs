@@ -0,0 +1,24 @@
+use crate::context::EstimateContext;
+use crate::error::TResult;
+use crate::estimate::estimate;
+use crate::reporter::event::EstimateResult;
+use crate::reporter::Reporter;
+use crate::stabilization::StabilizationIndex;
+use crate::SubCommand;
+
+#[derive(Default)]
+pub struct Estimate;
+
+impl SubCommand for Estimate {
+    type Context = EstimateContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let index = StabilizationIndex::load();
+        let estimate = estimate(&ctx.environment, &index)?;
+
+        reporter.report_event(EstimateResult::new(estimate))?;
+
+        Ok(())
+    }
+}
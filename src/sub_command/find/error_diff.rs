@@ -0,0 +1,174 @@
+use std::sync::Mutex;
+
+use crate::check::diagnostics::diagnostics;
+use crate::check::Check;
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::reporter::event::ErrorDiff;
+use crate::rust::Toolchain;
+use crate::{semver, Outcome, TResult};
+
+/// Records the outcome of each check, so the output of the checks at the MSRV boundary can be
+/// compared once the search completes, or the bounds of the MSRV can be reported if it stops
+/// early.
+pub(super) struct RecordingCheck<'c, C: Check> {
+    inner: &'c C,
+    outcomes: Mutex<Vec<Outcome>>,
+}
+
+impl<'c, C: Check> RecordingCheck<'c, C> {
+    pub fn new(inner: &'c C) -> Self {
+        Self {
+            inner,
+            outcomes: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Compare the output of the most recent incompatible release which was checked and is older
+    /// than the MSRV, with the output of the MSRV.
+    ///
+    /// Returns `None` if no MSRV was found, or no older release was checked, e.g. when the MSRV
+    /// is the least recent release of the search space.
+    pub fn error_diff(&self, msrv: &MinimumSupportedRustVersion) -> Option<ErrorDiff> {
+        let MinimumSupportedRustVersion::Toolchain { toolchain } = msrv else {
+            return None;
+        };
+
+        let outcomes = self.outcomes.lock().ok()?;
+
+        let passing = outcomes
+            .iter()
+            .find(|outcome| outcome.is_success() && outcome.version() == toolchain.version())?;
+
+        let failing = outcomes
+            .iter()
+            .filter(|outcome| !outcome.is_success() && outcome.version() < toolchain.version())
+            .max_by(|lhs, rhs| lhs.version().cmp(rhs.version()))?;
+
+        Some(diff(failing, passing))
+    }
+
+    /// The Rust versions which were checked, with whether they are compatible.
+    pub fn checked(&self) -> Vec<(semver::Version, bool)> {
+        self.outcomes
+            .lock()
+            .map(|outcomes| {
+                outcomes
+                    .iter()
+                    .map(|outcome| (outcome.version().clone(), outcome.is_success()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl<C: Check> Check for RecordingCheck<'_, C> {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        let outcome = self.inner.check(toolchain)?;
+
+        if let Ok(mut outcomes) = self.outcomes.lock() {
+            outcomes.push(outcome.clone());
+        }
+
+        Ok(outcome)
+    }
+}
+
+fn diff(failing: &Outcome, passing: &Outcome) -> ErrorDiff {
+    let failing_diagnostics = diagnostics(failing.output());
+    let passing_diagnostics = diagnostics(passing.output());
+
+    let removed = failing_diagnostics
+        .iter()
+        .filter(|diagnostic| !passing_diagnostics.contains(diagnostic))
+        .cloned()
+        .collect();
+
+    let added = passing_diagnostics
+        .iter()
+        .filter(|diagnostic| !failing_diagnostics.contains(diagnostic))
+        .cloned()
+        .collect();
+
+    ErrorDiff {
+        failing_version: failing.version().clone(),
+        passing_version: passing.version().clone(),
+        removed,
+        added,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semver;
+
+    const FAILING: &str = r#"    Checking a v0.1.0 (/a)
+error[E0658]: use of unstable library feature 'once_cell'
+ --> src/lib.rs:1:5
+  |
+1 | use std::sync::OnceLock;
+  |     ^^^^^^^^^^^^^^^^^^^
+
+warning: unused variable: `x`
+ --> src/lib.rs:3:9
+
+error: could not compile `a` (lib) due to 1 previous error; 1 warning emitted
+"#;
+
+    const PASSING: &str = r#"    Checking a v0.1.0 (/a)
+warning: unused variable: `x`
+ --> src/lib.rs:3:9
+
+warning: `a` (lib) generated 1 warning
+    Finished `dev` profile [unoptimized + debuginfo] target(s) in 0.20s
+"#;
+
+    fn toolchain(minor: u64) -> Toolchain {
+        Toolchain::new(semver::Version::new(1, minor, 0), "x", &[])
+    }
+
+    #[test]
+    fn removed_diagnostic() {
+        let failing = Outcome::new_failure(toolchain(69), FAILING.to_string());
+        let passing = Outcome::new_success_with_output(toolchain(70), PASSING.to_string());
+
+        let diff = diff(&failing, &passing);
+
+        assert_eq!(diff.failing_version, semver::Version::new(1, 69, 0));
+        assert_eq!(diff.passing_version, semver::Version::new(1, 70, 0));
+        assert_eq!(diff.removed.len(), 1);
+        assert!(diff.removed[0].starts_with("error[E0658]"));
+        assert!(diff.added.is_empty());
+    }
+
+    #[yare::parameterized(
+        adjacent = { &[70, 69, 68], Some(69) },
+        bisected = { &[72, 66, 69, 71, 70], Some(69) },
+        least_recent = { &[70], None },
+    )]
+    fn failing_boundary(checked: &[u64], expected: Option<u64>) {
+        let runner = crate::check::TestRunner::with_ok(
+            "x",
+            &[
+                semver::Version::new(1, 70, 0),
+                semver::Version::new(1, 71, 0),
+                semver::Version::new(1, 72, 0),
+            ],
+        );
+        let recording = RecordingCheck::new(&runner);
+
+        for &minor in checked {
+            recording.check(&toolchain(minor)).unwrap();
+        }
+
+        let msrv = MinimumSupportedRustVersion::Toolchain {
+            toolchain: toolchain(70),
+        };
+        let diff = recording.error_diff(&msrv);
+
+        assert_eq!(
+            diff.map(|diff| diff.failing_version),
+            expected.map(|minor| semver::Version::new(1, minor, 0))
+        );
+    }
+}
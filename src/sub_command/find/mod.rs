@@ -2,17 +2,35 @@ use rust_releases::{Release, ReleaseIndex};
 
 use crate::check::Check;
 use crate::context::{FindContext, SearchMethod};
+use crate::disk_space;
 use crate::error::{CargoMSRVError, NoToolchainsToTryError, TResult};
+use crate::estimate::{cargo_gates, Finding};
+use crate::fingerprint::Fingerprint;
 use crate::manifest::bare_version::BareVersion;
+use crate::manifest::custom_toolchains::CustomToolchain;
 use crate::msrv::MinimumSupportedRustVersion;
-use crate::reporter::event::FindResult;
+use crate::reporter::event::{
+    AuxiliaryOutput, AuxiliaryOutputItem, Destination, FindResult, PartialResultReason,
+    ReducedSearchSpace, Unlocks, Warning,
+};
 use crate::reporter::Reporter;
+use crate::rust::component_availability::ComponentAvailability;
+use crate::rust::dist_server;
+use crate::rust::prerelease;
 use crate::rust::releases_filter::ReleasesFilter;
 use crate::rust::RustRelease;
-use crate::search_method::{Bisect, FindMinimalSupportedRustVersion, Linear};
+use crate::search_method::{
+    Bisect, DeclaredRustVersion, FindMinimalSupportedRustVersion, Hybrid, LastRun, Linear,
+    StaticEstimate,
+};
+use crate::search_state::{self, SearchState};
+use crate::stabilization::StabilizationIndex;
+use crate::sub_command::sync::sync_declarations;
 use crate::writer::toolchain_file::write_toolchain_file;
 use crate::writer::write_msrv::write_msrv;
 use crate::{semver, SubCommand};
+use error_diff::RecordingCheck;
+use partial_result::partial_result;
 
 pub struct Find<'index, C: Check> {
     release_index: &'index ReleaseIndex,
@@ -26,6 +44,11 @@ impl<'index, C: Check> Find<'index, C> {
             runner,
         }
     }
+
+    #[cfg(test)]
+    fn runner(&self) -> &C {
+        &self.runner
+    }
 }
 
 impl<'index, C: Check> SubCommand for Find<'index, C> {
@@ -67,35 +90,209 @@ fn find_msrv(
                 write_toolchain_file(reporter, version, crate_root)?;
             }
 
+            // When searching over patch releases, the patch version is part of the MSRV.
+            let msrv = if ctx.rust_releases.consider_patch_releases {
+                BareVersion::from(version)
+            } else {
+                BareVersion::two_component_from_semver(version)
+            };
+
             if ctx.write_msrv {
                 let environment_ctx = ctx.environment.clone();
                 let rust_releases_ctx = ctx.rust_releases.clone();
 
                 write_msrv(
                     reporter,
-                    BareVersion::two_component_from_semver(toolchain.version()),
+                    msrv.clone(),
                     Some(release_index), // Re-use the already obtained index
                     environment_ctx,
                     rust_releases_ctx,
+                    ctx.changelog,
                 )?;
             }
 
+            if ctx.sync {
+                sync_declarations(&ctx.environment, Some(msrv), false, reporter)?;
+            }
+
+            if ctx.report_unlocks {
+                report_unlocks(reporter, version, release_index)?;
+            }
+
             Ok(version.clone())
         }
     }
 }
 
+/// Report what becomes available at the releases after the MSRV, see [`StabilizationIndex::unlocks`].
+fn report_unlocks(
+    reporter: &impl Reporter,
+    msrv: &semver::Version,
+    release_index: &ReleaseIndex,
+) -> TResult<()> {
+    let Some(latest) = release_index.releases().iter().map(Release::version).max() else {
+        return Ok(());
+    };
+
+    let unlocks = StabilizationIndex::load().unlocks(msrv, latest);
+    reporter.report_event(Unlocks::new(msrv.clone(), unlocks))?;
+
+    Ok(())
+}
+
 fn search(
     ctx: &FindContext,
     reporter: &impl Reporter,
     index: &ReleaseIndex,
     runner: &impl Check,
 ) -> TResult<MinimumSupportedRustVersion> {
+    let (included_releases, included_custom_toolchains, gate) =
+        included_search_space(ctx, index, reporter)?;
+
+    let candidates = included_releases.iter().map(Release::version);
+    preflight(ctx, candidates, ctx.search_method, reporter)?;
+
+    let recording = RecordingCheck::new(runner);
+    let minimum_capable = match run_with_search_method(
+        ctx,
+        &included_releases,
+        &included_custom_toolchains,
+        reporter,
+        &recording,
+    )
+    .map_err(|error| with_clues(error, ctx, gate))
+    {
+        Ok(minimum_capable) => minimum_capable,
+        Err(error) => {
+            let checked = recording.checked();
+
+            // The search stopped early, so report where it stood, unless nothing was searched yet
+            let reason = match &error {
+                CargoMSRVError::MaxDurationExceeded { .. } => {
+                    Some(PartialResultReason::MaxDurationExceeded)
+                }
+                CargoMSRVError::SearchAborted => Some(PartialResultReason::Aborted),
+                CargoMSRVError::Interrupted => Some(PartialResultReason::Interrupted),
+                _ if !checked.is_empty() => Some(PartialResultReason::Failed),
+                _ => None,
+            };
+
+            if let Some(partial) =
+                reason.and_then(|reason| partial_result(reason, &checked, &included_releases))
+            {
+                let state = SearchState {
+                    target: ctx.toolchain.target.to_string(),
+                    checked: partial.checked().to_vec(),
+                };
+                reporter.report_event(partial)?;
+                save_search_state(ctx, &state, reporter)?;
+            }
+
+            return Err(error);
+        }
+    };
+
+    // The search completed, so there is nothing left to resume
+    search_state::remove(ctx.environment.root());
+
+    let minimum_capable = stable_msrv(minimum_capable, reporter)?;
+
+    let error_diff = ctx
+        .diff_errors
+        .then(|| recording.error_diff(&minimum_capable))
+        .flatten();
+
+    let result = find_result(&minimum_capable, &included_releases, index, ctx)?;
+    let result = match error_diff {
+        Some(error_diff) => result.with_error_diff(error_diff),
+        None => result,
+    };
+    reporter.report_event(result)?;
+
+    Ok(minimum_capable)
+}
+
+/// A pre-release is never the MSRV: if only a pre-release is compatible, no release is.
+fn stable_msrv(
+    minimum_capable: MinimumSupportedRustVersion,
+    reporter: &impl Reporter,
+) -> TResult<MinimumSupportedRustVersion> {
+    match &minimum_capable {
+        MinimumSupportedRustVersion::Toolchain { toolchain }
+            if !toolchain.version().pre.is_empty() =>
+        {
+            reporter.report_event(Warning::prerelease_only(toolchain.version()))?;
+
+            Ok(MinimumSupportedRustVersion::NoCompatibleToolchain)
+        }
+        _ => Ok(minimum_capable),
+    }
+}
+
+/// Check whether the dist server responds, and estimate the disk space which the checks of the
+/// search need, before the first check, see [`dist_server::preflight`] and
+/// [`disk_space::preflight`].
+fn preflight<'v>(
+    ctx: &FindContext,
+    candidates: impl ExactSizeIterator<Item = &'v semver::Version> + Clone,
+    search_method: SearchMethod,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    if ctx.toolchain.dist_server_check {
+        dist_server::preflight(&ctx.toolchain_provider, candidates.clone())?;
+    }
+
+    if ctx.toolchain.disk_preflight {
+        let checks = disk_space::planned_checks(search_method, candidates.len() as u64);
+
+        disk_space::preflight(
+            reporter,
+            &ctx.environment,
+            &ctx.toolchain_provider,
+            candidates,
+            checks,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Save the state of the search which stopped early, so it can be continued with `--resume`.
+/// The state is a convenience, so a state which can't be saved doesn't fail the search.
+fn save_search_state(
+    ctx: &FindContext,
+    state: &SearchState,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    match search_state::save(ctx.environment.root(), state) {
+        Ok(path) => reporter.report_event(AuxiliaryOutput::new(
+            Destination::file(path),
+            AuxiliaryOutputItem::search_state(),
+        ))?,
+        Err(error) => info!(%error, "unable to save the state of the search"),
+    }
+
+    Ok(())
+}
+
+/// The releases and custom toolchains which are included in the search space, after filtering by
+/// the user given constraints, and the cargo gate which raised the minimum of the search space,
+/// if any.
+fn included_search_space(
+    ctx: &FindContext,
+    index: &ReleaseIndex,
+    reporter: &impl Reporter,
+) -> TResult<(Vec<Release>, Vec<CustomToolchain>, Option<Finding>)> {
     let releases = index.releases();
 
     let min = ctx
         .rust_releases
         .resolve_minimum_version(&ctx.environment)?;
+    let gate = most_restrictive_cargo_gate(ctx, min.as_ref(), reporter)?;
+    let min = gate
+        .as_ref()
+        .map(|gate| BareVersion::from(&gate.version))
+        .or(min);
 
     let releases_filter = ReleasesFilter::new(
         ctx.rust_releases.consider_patch_releases,
@@ -104,12 +301,131 @@ fn search(
     );
 
     let included_releases = releases_filter.filter(releases);
-    run_with_search_method(ctx, &included_releases, reporter, runner)
+    let included_releases = filter_required_components(ctx, reporter, included_releases)?;
+    let included_releases = exclude_denied(ctx, included_releases, reporter)?;
+    let included_releases = match ctx.rust_releases.include_prerelease {
+        true => with_prerelease(included_releases, &releases_filter)?,
+        false => included_releases,
+    };
+
+    let (included_custom_toolchains, excluded_custom_toolchains) = ctx
+        .custom_toolchains
+        .iter()
+        .cloned()
+        .partition::<Vec<_>, _>(|custom| releases_filter.includes(&custom.version));
+
+    info!(
+        included = ?included_custom_toolchains,
+        excluded = ?excluded_custom_toolchains,
+        "custom toolchains"
+    );
+
+    Ok((included_releases, included_custom_toolchains, gate))
+}
+
+/// Exclude the releases which are on the denylist, unless they were allowed with
+/// `--allow-version`. Each excluded release is reported.
+fn exclude_denied(
+    ctx: &FindContext,
+    releases: Vec<Release>,
+    reporter: &impl Reporter,
+) -> TResult<Vec<Release>> {
+    let mut included = Vec::with_capacity(releases.len());
+
+    for release in releases {
+        let denied = ctx.denylist.denied(
+            release.version(),
+            ctx.toolchain.target,
+            &ctx.rust_releases.allowed_versions,
+        );
+
+        match denied {
+            Some(denied) => {
+                reporter.report_event(Warning::release_denied(release.version(), &denied.reason))?
+            }
+            None => included.push(release),
+        }
+    }
+
+    Ok(included)
+}
+
+/// Add the current beta release to the search space, which is ordered from most to least recent,
+/// if it is more recent than the included releases, and within the `--min` and `--max` range.
+fn with_prerelease(
+    mut releases: Vec<Release>,
+    releases_filter: &ReleasesFilter,
+) -> TResult<Vec<Release>> {
+    let beta = prerelease::beta_version()?;
+
+    let is_newer = releases
+        .first()
+        .map_or(true, |latest| latest.version() < &beta);
+
+    if is_newer && releases_filter.includes(&beta) {
+        info!(%beta, "included the pre-release");
+        releases.insert(0, Release::new_stable(beta));
+    }
+
+    Ok(releases)
+}
+
+/// The gate which raises the minimum of the search space to the most recent Rust version of which
+/// cargo is required to parse the project, e.g. for edition 2021 or `dep:` features. Each gate
+/// which excludes Rust versions is reported.
+fn most_restrictive_cargo_gate(
+    ctx: &FindContext,
+    min: Option<&BareVersion>,
+    reporter: &impl Reporter,
+) -> TResult<Option<Finding>> {
+    if !ctx.cargo_gates {
+        return Ok(None);
+    }
+
+    // The gates only save checks, so the search continues without them
+    let gates = match cargo_gates(&ctx.environment) {
+        Ok(gates) => gates,
+        Err(error) => {
+            info!(%error, "unable to find the cargo gates of the project");
+            return Ok(None);
+        }
+    };
+
+    let min_version = min.map(BareVersion::to_semver_version);
+    let excluding = gates
+        .into_iter()
+        .filter(|gate| min_version.as_ref().map_or(true, |min| *min < gate.version))
+        .collect::<Vec<_>>();
+
+    let most_restrictive = excluding.first().cloned();
+
+    for gate in excluding {
+        reporter.report_event(ReducedSearchSpace::new(gate))?;
+    }
+
+    Ok(most_restrictive)
+}
+
+/// Exclude the releases for which the required components were not distributed.
+fn filter_required_components(
+    ctx: &FindContext,
+    reporter: &impl Reporter,
+    releases: Vec<Release>,
+) -> TResult<Vec<Release>> {
+    let required_components = ctx.toolchain.required_components;
+
+    if required_components.is_empty() {
+        return Ok(releases);
+    }
+
+    ComponentAvailability::new(ctx.toolchain.target, required_components)?
+        .filter(reporter, releases)
 }
 
 fn run_with_search_method(
     ctx: &FindContext,
     included_releases: &[Release],
+    custom_toolchains: &[CustomToolchain],
     reporter: &impl Reporter,
     runner: &impl Check,
 ) -> TResult<MinimumSupportedRustVersion> {
@@ -118,11 +434,31 @@ fn run_with_search_method(
 
     // Run a linear or binary search depending on the configuration
     match search_method {
-        SearchMethod::Linear => {
-            run_searcher(&Linear::new(runner), included_releases, ctx, reporter)
+        SearchMethod::Linear => run_searcher(
+            &Linear::new(runner),
+            included_releases,
+            custom_toolchains,
+            ctx,
+            reporter,
+        ),
+        SearchMethod::Bisect => run_searcher(
+            &Bisect::new(runner),
+            included_releases,
+            custom_toolchains,
+            ctx,
+            reporter,
+        ),
+        SearchMethod::Hybrid => {
+            let hybrid = Hybrid::from_heuristic(runner, &StaticEstimate, ctx);
+            run_searcher(&hybrid, included_releases, custom_toolchains, ctx, reporter)
         }
-        SearchMethod::Bisect => {
-            run_searcher(&Bisect::new(runner), included_releases, ctx, reporter)
+        SearchMethod::Declared => {
+            let hybrid = Hybrid::from_heuristic(runner, &DeclaredRustVersion, ctx);
+            run_searcher(&hybrid, included_releases, custom_toolchains, ctx, reporter)
+        }
+        SearchMethod::LastRun => {
+            let hybrid = Hybrid::from_heuristic(runner, &LastRun, ctx);
+            run_searcher(&hybrid, included_releases, custom_toolchains, ctx, reporter)
         }
     }
 }
@@ -130,38 +466,71 @@ fn run_with_search_method(
 fn run_searcher(
     method: &impl FindMinimalSupportedRustVersion,
     releases: &[Release],
+    custom_toolchains: &[CustomToolchain],
     ctx: &FindContext,
     reporter: &impl Reporter,
 ) -> TResult<MinimumSupportedRustVersion> {
-    let searchable_releases = releases
+    let searchable_releases = searchable_releases(releases, custom_toolchains, ctx);
+
+    method.find_toolchain(&searchable_releases, reporter)
+}
+
+/// Explain an empty search space by the constraints of the user, and the cargo gate which raised
+/// its minimum, if any.
+fn with_clues(error: CargoMSRVError, ctx: &FindContext, gate: Option<Finding>) -> CargoMSRVError {
+    match error {
+        CargoMSRVError::NoToolchainsToTry(inner) if !inner.has_clues() => {
+            let user_min = ctx.rust_releases.minimum_rust_version.clone();
+            let user_max = ctx.rust_releases.maximum_rust_version.clone();
+
+            CargoMSRVError::NoToolchainsToTry(NoToolchainsToTryError::with_clues(
+                user_min, user_max, gate,
+            ))
+        }
+        _ => error,
+    }
+}
+
+/// The search space, ordered from most to least recent.
+fn searchable_releases(
+    releases: &[Release],
+    custom_toolchains: &[CustomToolchain],
+    ctx: &FindContext,
+) -> Vec<RustRelease> {
+    let mut searchable_releases = releases
         .iter()
         .map(|r| RustRelease::new(r.clone(), ctx.toolchain.target, ctx.toolchain.components))
         .collect::<Vec<_>>();
-    let minimum_capable = method
-        .find_toolchain(&searchable_releases, reporter)
-        .map_err(|err| match err {
-            CargoMSRVError::NoToolchainsToTry(inner) if !inner.has_clues() => {
-                let user_min = ctx.rust_releases.minimum_rust_version.clone();
-                let user_max = ctx.rust_releases.maximum_rust_version.clone();
-
-                CargoMSRVError::NoToolchainsToTry(NoToolchainsToTryError::with_clues(
-                    user_min, user_max,
-                ))
-            }
-            _ => err,
-        })?;
+    insert_custom_toolchains(&mut searchable_releases, custom_toolchains, ctx);
 
-    report_outcome(&minimum_capable, releases, ctx, reporter)?;
+    searchable_releases
+}
 
-    Ok(minimum_capable)
+/// Insert the custom toolchains into the search space (which is ordered from most to least
+/// recent), right before the official release of the version they're based on.
+fn insert_custom_toolchains(
+    search_space: &mut Vec<RustRelease>,
+    custom_toolchains: &[CustomToolchain],
+    ctx: &FindContext,
+) {
+    for custom in custom_toolchains {
+        let position = search_space
+            .iter()
+            .position(|release| release.version() <= &custom.version)
+            .unwrap_or(search_space.len());
+
+        let release =
+            RustRelease::custom(&custom.name, custom.version.clone(), ctx.toolchain.target);
+        search_space.insert(position, release);
+    }
 }
 
-fn report_outcome(
+fn find_result(
     minimum_capable: &MinimumSupportedRustVersion,
     releases: &[Release],
+    index: &ReleaseIndex,
     ctx: &FindContext,
-    reporter: &impl Reporter,
-) -> TResult<()> {
+) -> TResult<FindResult> {
     let (min, max) = min_max_releases(releases)?;
 
     let minimum_considered = ctx
@@ -179,29 +548,25 @@ fn report_outcome(
     let target = ctx.toolchain.target;
     let search_method = ctx.search_method;
 
-    match minimum_capable {
-        MinimumSupportedRustVersion::Toolchain { toolchain } => {
-            let version = toolchain.version();
-
-            reporter.report_event(FindResult::new_msrv(
-                version.clone(),
-                target,
-                minimum_considered,
-                maximum_considered,
-                search_method,
-            ))?;
-        }
-        MinimumSupportedRustVersion::NoCompatibleToolchain => {
-            reporter.report_event(FindResult::none(
-                target,
-                minimum_considered,
-                maximum_considered,
-                search_method,
-            ))?;
-        }
-    }
-
-    Ok(())
+    let result = match minimum_capable {
+        MinimumSupportedRustVersion::Toolchain { toolchain } => FindResult::new_msrv(
+            toolchain.version().clone(),
+            target,
+            minimum_considered,
+            maximum_considered,
+            search_method,
+        ),
+        MinimumSupportedRustVersion::NoCompatibleToolchain => FindResult::none(
+            target,
+            minimum_considered,
+            maximum_considered,
+            search_method,
+        ),
+    };
+
+    let fingerprint = Fingerprint::new(&ctx.environment, target, &ctx.check_cmd, index);
+
+    Ok(result.with_fingerprint(fingerprint))
 }
 
 fn min_max_releases(rust_releases: &[Release]) -> TResult<(BareVersion, BareVersion)> {
@@ -217,5 +582,9 @@ fn min_max_releases(rust_releases: &[Release]) -> TResult<(BareVersion, BareVers
     Ok((min.into(), max.into()))
 }
 
+mod error_diff;
+mod partial_result;
+pub mod shard;
+
 #[cfg(test)]
 mod tests;
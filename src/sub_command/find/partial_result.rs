@@ -0,0 +1,102 @@
+use rust_releases::Release;
+
+use crate::reporter::event::{CheckedVersion, PartialResult, PartialResultReason};
+use crate::semver;
+
+/// The bounds of the MSRV, narrowed down by the Rust versions which were checked before the
+/// search stopped. The releases are ordered from most to least recent.
+///
+/// Returns `None` if there are no releases at all.
+pub(super) fn partial_result(
+    reason: PartialResultReason,
+    checked: &[(semver::Version, bool)],
+    releases: &[Release],
+) -> Option<PartialResult> {
+    let least_recent = releases.last()?.version();
+
+    let upper_bound = checked
+        .iter()
+        .filter(|(_, is_compatible)| *is_compatible)
+        .map(|(version, _)| version)
+        .min();
+
+    // Only the incompatible versions older than the least recent compatible one narrow the
+    // bounds; later failures are irrelevant to the MSRV
+    let most_recent_incompatible = checked
+        .iter()
+        .filter(|(version, is_compatible)| {
+            !is_compatible && upper_bound.map_or(true, |upper| version < upper)
+        })
+        .map(|(version, _)| version)
+        .max();
+
+    let lower_bound = match most_recent_incompatible {
+        Some(incompatible) => releases
+            .iter()
+            .rev()
+            .map(Release::version)
+            .find(|version| *version > incompatible)
+            .unwrap_or(incompatible),
+        None => least_recent,
+    };
+
+    let checked = checked
+        .iter()
+        .map(|(version, is_compatible)| CheckedVersion {
+            version: version.clone(),
+            compatible: *is_compatible,
+        })
+        .collect();
+
+    Some(PartialResult::new(
+        reason,
+        lower_bound.clone(),
+        upper_bound.cloned(),
+        checked,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(minor: u64) -> semver::Version {
+        semver::Version::new(1, minor, 0)
+    }
+
+    fn releases() -> Vec<Release> {
+        (56..=70)
+            .rev()
+            .map(|minor| Release::new_stable(version(minor)))
+            .collect()
+    }
+
+    #[yare::parameterized(
+        narrowed = { &[(70, true), (62, false), (68, true), (64, false), (72, false)], 65, Some(68) },
+        nothing_checked = { &[], 56, None },
+        only_compatible = { &[(63, true)], 56, Some(63) },
+        only_incompatible = { &[(63, false)], 64, None },
+    )]
+    fn bounds(checked: &[(u64, bool)], lower: u64, upper: Option<u64>) {
+        let checked = checked
+            .iter()
+            .map(|&(minor, is_compatible)| (version(minor), is_compatible))
+            .collect::<Vec<_>>();
+
+        let partial = partial_result(
+            PartialResultReason::MaxDurationExceeded,
+            &checked,
+            &releases(),
+        )
+        .unwrap();
+
+        assert_eq!(partial.lower_bound(), &version(lower));
+        assert_eq!(partial.upper_bound(), upper.map(version).as_ref());
+        assert_eq!(partial.checked().len(), checked.len());
+    }
+
+    #[test]
+    fn without_releases() {
+        assert!(partial_result(PartialResultReason::MaxDurationExceeded, &[], &[]).is_none());
+    }
+}
@@ -0,0 +1,79 @@
+use rust_releases::ReleaseIndex;
+
+use super::{find_result, included_search_space, preflight, searchable_releases};
+use crate::check::Check;
+use crate::context::{FindContext, SearchMethod};
+use crate::error::TResult;
+use crate::msrv::MinimumSupportedRustVersion;
+use crate::reporter::event::ShardResult;
+use crate::reporter::Reporter;
+use crate::rust::RustRelease;
+use crate::search_method::{FindMinimalSupportedRustVersion, Linear};
+use crate::sub_command::SubCommand;
+
+/// Find the MSRV within a single shard of the search space, with a linear search.
+///
+/// Unlike [`Find`](super::Find), which concludes the MSRV, the result of each shard is only
+/// a part of the conclusion: the results of all shards are combined by `cargo msrv merge-results`.
+pub struct FindShard<'index, C: Check> {
+    release_index: &'index ReleaseIndex,
+    runner: C,
+}
+
+impl<'index, C: Check> FindShard<'index, C> {
+    pub fn new(release_index: &'index ReleaseIndex, runner: C) -> Self {
+        Self {
+            release_index,
+            runner,
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn runner(&self) -> &C {
+        &self.runner
+    }
+}
+
+impl<'index, C: Check> SubCommand for FindShard<'index, C> {
+    type Context = FindContext;
+    type Output = ShardResult;
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let shard = ctx.shard.unwrap_or_default();
+        info!(%shard);
+
+        let (releases, custom_toolchains, _) =
+            included_search_space(ctx, self.release_index, reporter)?;
+        let search_space = searchable_releases(&releases, &custom_toolchains, ctx);
+        let slice = shard.slice(&search_space);
+
+        let candidates = slice.iter().map(RustRelease::version);
+        preflight(ctx, candidates, SearchMethod::Linear, reporter)?;
+
+        // With more shards than releases, the least recent shards have nothing to check
+        let minimum_capable = if slice.is_empty() && !search_space.is_empty() {
+            MinimumSupportedRustVersion::NoCompatibleToolchain
+        } else {
+            Linear::new(&self.runner).find_toolchain(slice, reporter)?
+        };
+
+        let all_compatible = match (&minimum_capable, slice.last()) {
+            (MinimumSupportedRustVersion::Toolchain { toolchain }, Some(least_recent)) => {
+                toolchain == &least_recent.to_toolchain_spec()
+            }
+            (_, None) => true,
+            _ => false,
+        };
+
+        let shard_result = ShardResult {
+            shard,
+            all_compatible,
+        };
+
+        let result = find_result(&minimum_capable, &releases, self.release_index, ctx)?
+            .with_shard(shard_result);
+        reporter.report_event(result)?;
+
+        Ok(shard_result)
+    }
+}
@@ -1,15 +1,27 @@
+use super::shard::FindShard;
 use super::*;
+use crate::cache::DEFAULT_MAX_CACHE_SIZE;
 use crate::check::TestRunner;
 use crate::context::{
-    CheckCommandContext, EnvironmentContext, ReleaseSource, RustReleasesContext, ToolchainContext,
-    WorkspacePackages,
+    ChainedReleaseSource, CheckCommandContext, CheckCommandPreset, EnvironmentContext,
+    IndexRefresh, RustReleasesContext, ToolchainContext, ToolchainProvider, WorkspacePackages,
 };
+use crate::fingerprint::Fingerprint;
 use crate::manifest::bare_version::BareVersion;
+use crate::manifest::custom_toolchains::CustomToolchain;
+use crate::manifest::denylist::{DeniedRelease, Denylist};
+use crate::outcome::Outcome;
+use crate::reporter::event::{CheckedVersion, ErrorDiff, Message, PartialResult, ShardResult};
 use crate::reporter::TestReporterWrapper;
+use crate::resource_limits::ResourceLimits;
+use crate::rust::Toolchain;
+use crate::search_method::{DeclaredRustVersion, Heuristic, LastRun, Shard};
 use crate::Event;
 use camino::Utf8PathBuf;
 use rust_releases::semver;
 use std::iter::FromIterator;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 #[test]
 fn bisect_find_only_last() {
@@ -57,6 +69,7 @@ fn bisect_find_only_last() {
         BareVersion::ThreeComponents(1, 56, 0),
         SearchMethod::Bisect,
     )
+    .with_fingerprint(fingerprint(&context, &index))
     .into()];
 
     phenomenon::contains_at_least_ordered(events, expected).assert_this();
@@ -102,6 +115,7 @@ fn bisect_find_all_compatible() {
         BareVersion::ThreeComponents(1, 56, 0),
         SearchMethod::Bisect,
     )
+    .with_fingerprint(fingerprint(&ctx, &index))
     .into()];
 
     phenomenon::contains_at_least_ordered(events, expected).assert_this();
@@ -137,15 +151,552 @@ fn bisect_none_compatible() {
         BareVersion::ThreeComponents(1, 56, 0),
         SearchMethod::Bisect,
     )
+    .with_fingerprint(fingerprint(&ctx, &index))
     .into()];
 
     phenomenon::contains_at_least_ordered(events, expected).assert_this();
 }
 
+#[test]
+fn hybrid_find() {
+    let index = ReleaseIndex::from_iter(
+        (56..=80)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+
+    let accept = (60..=80)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let runner = TestRunner::with_ok("x", &accept);
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.search_method = SearchMethod::Hybrid;
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 56, 0));
+
+    // Whether the estimate of our own crate is right or not, the result must be the same.
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 60, 0));
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![FindResult::new_msrv(
+        semver::Version::new(1, 60, 0),
+        "x",
+        BareVersion::ThreeComponents(1, 56, 0),
+        BareVersion::ThreeComponents(1, 80, 0),
+        SearchMethod::Hybrid,
+    )
+    .with_fingerprint(fingerprint(&ctx, &index))
+    .into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+#[test]
+fn diff_errors_at_boundary() {
+    let index = ReleaseIndex::from_iter(
+        (56..=70)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+
+    let accept = (60..=70)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let runner = TestRunner::with_ok("x", &accept);
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.diff_errors = true;
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 56, 0));
+
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 60, 0));
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![FindResult::new_msrv(
+        semver::Version::new(1, 60, 0),
+        "x",
+        BareVersion::ThreeComponents(1, 56, 0),
+        BareVersion::ThreeComponents(1, 70, 0),
+        SearchMethod::Bisect,
+    )
+    .with_error_diff(ErrorDiff {
+        failing_version: semver::Version::new(1, 59, 0),
+        passing_version: semver::Version::new(1, 60, 0),
+        removed: Vec::new(),
+        added: Vec::new(),
+    })
+    .with_fingerprint(fingerprint(&ctx, &index))
+    .into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+#[test]
+fn report_unlocks_after_msrv() {
+    let index = ReleaseIndex::from_iter(
+        (56..=70)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+
+    let accept = (60..=70)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let runner = TestRunner::with_ok("x", &accept);
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.report_unlocks = true;
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 56, 0));
+
+    cmd.run(&ctx, reporter.get()).unwrap();
+
+    let events = reporter.wait_for_events();
+    let unlocks = events
+        .iter()
+        .find_map(|event| match event.message() {
+            Message::Unlocks(it) => Some(it),
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(unlocks.msrv(), &semver::Version::new(1, 60, 0));
+    assert_eq!(
+        unlocks
+            .unlocks()
+            .iter()
+            .map(|unlock| unlock.version.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            semver::Version::new(1, 62, 0),
+            semver::Version::new(1, 65, 0)
+        ]
+    );
+}
+
+/// Stops the search with the given error, after the given number of checks.
+struct StopAfter {
+    inner: TestRunner,
+    checks: AtomicUsize,
+    error: fn() -> CargoMSRVError,
+}
+
+impl Check for StopAfter {
+    fn check(&self, toolchain: &Toolchain) -> TResult<Outcome> {
+        if self.checks.fetch_sub(1, Ordering::SeqCst) == 0 {
+            return Err((self.error)());
+        }
+
+        self.inner.check(toolchain)
+    }
+}
+
+#[yare::parameterized(
+    max_duration_exceeded = {
+        || CargoMSRVError::MaxDurationExceeded { budget: Duration::from_secs(60) },
+        PartialResultReason::MaxDurationExceeded,
+    },
+    aborted = { || CargoMSRVError::SearchAborted, PartialResultReason::Aborted },
+    failed = { || CargoMSRVError::RustupRunWithCommandFailed, PartialResultReason::Failed },
+)]
+fn stopped_early(error: fn() -> CargoMSRVError, reason: PartialResultReason) {
+    let index = ReleaseIndex::from_iter(
+        (56..=70)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+
+    let accept = (60..=70)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let runner = StopAfter {
+        inner: TestRunner::with_ok("x", &accept),
+        checks: AtomicUsize::new(2),
+        error,
+    };
+
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 56, 0));
+    ctx.environment.root_crate_path = root.clone();
+
+    assert!(cmd.run(&ctx, reporter.get()).is_err());
+
+    // Rust 1.63 was compatible, and Rust 1.59 was not
+    assert_eq!(
+        cmd.runner().inner.checked(),
+        vec![
+            semver::Version::new(1, 63, 0),
+            semver::Version::new(1, 59, 0)
+        ]
+    );
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![PartialResult::new(
+        reason,
+        semver::Version::new(1, 60, 0),
+        Some(semver::Version::new(1, 63, 0)),
+        vec![
+            CheckedVersion {
+                version: semver::Version::new(1, 63, 0),
+                compatible: true,
+            },
+            CheckedVersion {
+                version: semver::Version::new(1, 59, 0),
+                compatible: false,
+            },
+        ],
+    )
+    .into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+
+    // The search can be resumed from the saved state
+    let state = search_state::load(&root).unwrap().unwrap();
+    assert_eq!(state.target, "x");
+    assert_eq!(state.checked.len(), 2);
+}
+
+#[yare::parameterized(
+    latest_patch_releases_only = { false, semver::Version::new(1, 56, 1) },
+    all_patch_releases = { true, semver::Version::new(1, 56, 0) },
+)]
+fn patch_release_granularity(consider_patch_releases: bool, expected: semver::Version) {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 57, 0)),
+        Release::new_stable(semver::Version::new(1, 56, 1)),
+        Release::new_stable(semver::Version::new(1, 56, 0)),
+        Release::new_stable(semver::Version::new(1, 55, 0)),
+    ]);
+
+    let reporter = TestReporterWrapper::default();
+    let runner = TestRunner::with_ok(
+        "x",
+        &[
+            semver::Version::new(1, 57, 0),
+            semver::Version::new(1, 56, 1),
+            semver::Version::new(1, 56, 0),
+        ],
+    );
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.rust_releases.consider_patch_releases = consider_patch_releases;
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 55, 0));
+
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, expected);
+}
+
+#[yare::parameterized(
+    most_recent = { 1, true, Some(semver::Version::new(1, 66, 0)), &[(1, 68), (1, 67), (1, 66)] },
+    with_incompatible = { 2, false, Some(semver::Version::new(1, 65, 0)), &[(1, 65), (1, 64)] },
+    least_recent = { 3, false, None, &[(1, 63)] },
+)]
+fn shard(
+    index: u32,
+    all_compatible: bool,
+    expected_msrv: Option<semver::Version>,
+    expected_checked: &[(u64, u64)],
+) {
+    // 7 releases, which are split into shards of 3, 2 and 2 releases
+    let index_of_releases = ReleaseIndex::from_iter(
+        (62..=68)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+
+    let reporter = TestReporterWrapper::default();
+    let compatible = (65..=68)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+    let runner = TestRunner::with_ok("x", &compatible);
+
+    let cmd = FindShard::new(&index_of_releases, runner);
+    let mut ctx = create_test_context();
+    ctx.search_method = SearchMethod::Linear;
+    ctx.shard = Some(Shard::new(index, 3).unwrap());
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 62, 0));
+
+    let result = cmd.run(&ctx, reporter.get()).unwrap();
+    let expected_result = ShardResult {
+        shard: Shard::new(index, 3).unwrap(),
+        all_compatible,
+    };
+    assert_eq!(result, expected_result);
+
+    let checked = expected_checked
+        .iter()
+        .map(|&(major, minor)| semver::Version::new(major, minor, 0))
+        .collect::<Vec<_>>();
+    assert_eq!(cmd.runner().checked(), checked);
+
+    let min = BareVersion::ThreeComponents(1, 62, 0);
+    let max = BareVersion::ThreeComponents(1, 68, 0);
+    let find_result = match expected_msrv {
+        Some(msrv) => FindResult::new_msrv(msrv, "x", min, max, SearchMethod::Linear),
+        None => FindResult::none("x", min, max, SearchMethod::Linear),
+    };
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![find_result
+        .with_shard(expected_result)
+        .with_fingerprint(fingerprint(&ctx, &index_of_releases))
+        .into()];
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+#[test]
+fn custom_toolchains_in_search_space() {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 67, 0)),
+        Release::new_stable(semver::Version::new(1, 66, 0)),
+        Release::new_stable(semver::Version::new(1, 65, 0)),
+    ]);
+
+    let reporter = TestReporterWrapper::default();
+    let runner = TestRunner::with_ok(
+        "x",
+        &[
+            semver::Version::new(1, 67, 0),
+            semver::Version::new(1, 66, 0),
+        ],
+    );
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.search_method = SearchMethod::Linear;
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 65, 0));
+    ctx.custom_toolchains = vec![
+        CustomToolchain {
+            name: "stage1".to_string(),
+            version: semver::Version::new(1, 66, 0),
+        },
+        CustomToolchain {
+            name: "out-of-range".to_string(),
+            version: semver::Version::new(1, 40, 0),
+        },
+    ];
+
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 66, 0));
+
+    // The custom toolchain is checked right before the official release it's based on.
+    assert_eq!(
+        cmd.runner().checked(),
+        vec![
+            semver::Version::new(1, 67, 0),
+            semver::Version::new(1, 66, 0),
+            semver::Version::new(1, 66, 0),
+            semver::Version::new(1, 65, 0),
+        ]
+    );
+}
+
+#[test]
+fn prerelease_never_msrv() {
+    let beta = semver::Version::parse("1.67.0-beta.3").unwrap();
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(beta.clone()),
+        Release::new_stable(semver::Version::new(1, 66, 0)),
+        Release::new_stable(semver::Version::new(1, 65, 0)),
+    ]);
+
+    let reporter = TestReporterWrapper::default();
+    let runner = TestRunner::with_ok("x", [&beta]);
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.search_method = SearchMethod::Linear;
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 65, 0));
+
+    let result = cmd.run(&ctx, reporter.get());
+    assert!(matches!(
+        result,
+        Err(CargoMSRVError::UnableToFindAnyGoodVersion { .. })
+    ));
+
+    let events = reporter.wait_for_events();
+    let expected: Vec<Event> = vec![Warning::prerelease_only(&beta).into()];
+
+    phenomenon::contains_at_least_ordered(events, expected).assert_this();
+}
+
+#[yare::parameterized(
+    denied = { &[], 67 },
+    allowed = { &[BareVersion::TwoComponents(1, 66)], 66 },
+)]
+fn denied_release_excluded(allowed: &[BareVersion], expected: u64) {
+    let index = ReleaseIndex::from_iter(vec![
+        Release::new_stable(semver::Version::new(1, 67, 0)),
+        Release::new_stable(semver::Version::new(1, 66, 0)),
+        Release::new_stable(semver::Version::new(1, 65, 0)),
+    ]);
+
+    let reporter = TestReporterWrapper::default();
+    let runner = TestRunner::with_ok(
+        "x",
+        &[
+            semver::Version::new(1, 67, 0),
+            semver::Version::new(1, 66, 0),
+        ],
+    );
+
+    let cmd = Find::new(&index, runner);
+    let mut ctx = create_test_context();
+    ctx.search_method = SearchMethod::Linear;
+    ctx.rust_releases.minimum_rust_version = Some(BareVersion::ThreeComponents(1, 65, 0));
+    ctx.rust_releases.allowed_versions = allowed.to_vec();
+    ctx.denylist = Denylist::default().with([DeniedRelease {
+        version: semver::Version::new(1, 66, 0),
+        reason: "it is broken".to_string(),
+        hosts: Vec::new(),
+    }]);
+
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, semver::Version::new(1, expected, 0));
+}
+
 // These test cases cover the case that the minimum is set to be a strictly more recent
 // Rust release compared to the maximum set.
 // https://github.com/foresterre/cargo-msrv/issues/369
 #[cfg(test)]
+#[yare::parameterized(
+    holds = { &[58, 59, 60], Some(58) },
+    lowered = { &[57, 58, 59, 60], Some(57) },
+    raised = { &[59, 60], None },
+)]
+fn target_boundary(compatible: &[u64], expected: Option<u64>) {
+    let index = ReleaseIndex::from_iter(
+        (55..=60)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+    let compatible = compatible
+        .iter()
+        .map(|&minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let cmd = Find::new(&index, TestRunner::with_ok("x", &compatible));
+
+    // Only the saved MSRV and the release before it are checked
+    let ctx = create_test_context().for_target_boundary("x", &semver::Version::new(1, 58, 0));
+    let found = cmd.run(&ctx, reporter.get()).ok();
+
+    assert_eq!(
+        found,
+        expected.map(|minor| semver::Version::new(1, minor, 0))
+    );
+
+    let checked = cmd.runner().checked();
+    assert!(
+        checked
+            .iter()
+            .all(|version| (57..=58).contains(&version.minor)),
+        "{:?}",
+        checked
+    );
+}
+
+#[test]
+fn declared_rust_version_heuristic() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"a\"\nversion = \"0.1.0\"\nrust-version = \"1.58\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir(root.join("src")).unwrap();
+    std::fs::write(root.join("src/lib.rs"), "").unwrap();
+
+    let mut ctx = create_test_context();
+    ctx.environment.root_crate_path = root;
+
+    assert_eq!(
+        DeclaredRustVersion.guess(&ctx),
+        Some(semver::Version::new(1, 58, 0))
+    );
+}
+
+#[test]
+fn last_run_heuristic() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+
+    let mut ctx = create_test_context();
+    ctx.environment.root_crate_path = root.clone();
+
+    assert_eq!(LastRun.guess(&ctx), None);
+
+    let mut state = crate::matrix_state::MatrixState::default();
+    state.set("x", Some(semver::Version::new(1, 58, 0)));
+    crate::matrix_state::save(&root, &state).unwrap();
+
+    assert_eq!(LastRun.guess(&ctx), Some(semver::Version::new(1, 58, 0)));
+}
+
+#[test]
+fn search_from_heuristic() {
+    struct Guess;
+
+    impl Heuristic for Guess {
+        fn search_method(&self) -> SearchMethod {
+            SearchMethod::Declared
+        }
+
+        fn guess(&self, _ctx: &FindContext) -> Option<semver::Version> {
+            Some(semver::Version::new(1, 58, 0))
+        }
+    }
+
+    let index = ReleaseIndex::from_iter(
+        (50..=60)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+    let compatible = (58..=60)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+    let runner = TestRunner::with_ok("x", &compatible);
+
+    let ctx = create_test_context();
+    let releases = index
+        .releases()
+        .iter()
+        .map(|release| crate::rust::RustRelease::new(release.clone(), "x", &[]))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let found = Hybrid::from_heuristic(&runner, &Guess, &ctx)
+        .find_toolchain(&releases, reporter.get())
+        .unwrap();
+
+    assert_eq!(found, MinimumSupportedRustVersion::toolchain(&releases[2]));
+    assert_eq!(
+        runner.checked(),
+        vec![
+            semver::Version::new(1, 58, 0),
+            semver::Version::new(1, 57, 0)
+        ]
+    );
+}
+
 mod issue_369_min_more_recent_than_max {
     use super::*;
 
@@ -240,32 +791,228 @@ mod issue_369_min_more_recent_than_max {
     }
 }
 
+#[test]
+fn cargo_gates_exclude_older_releases() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\nlog = { version = \"0.4\", optional = true }\n\n[features]\nlogging = [\"dep:log\"]\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src").join("lib.rs"), "").unwrap();
+
+    let index = ReleaseIndex::from_iter(
+        (50..=80)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+    let accept = (50..=80)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let cmd = Find::new(&index, TestRunner::with_ok("x", &accept));
+    let mut ctx = create_test_context();
+    ctx.cargo_gates = true;
+    ctx.environment.root_crate_path = root;
+
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 60, 0));
+    assert!(cmd
+        .runner()
+        .checked()
+        .iter()
+        .all(|version| version >= &semver::Version::new(1, 60, 0)));
+
+    let gates = reporter
+        .wait_for_events()
+        .into_iter()
+        .filter_map(|event| match event.message() {
+            Message::ReducedSearchSpace(gate) => Some(gate.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // The namespaced feature is the most restrictive gate, so it is reported first
+    assert_eq!(gates[0].version(), &semver::Version::new(1, 60, 0));
+    assert_eq!(gates[0].reason(), "namespaced dependency features (`dep:`)");
+    assert_eq!(gates[0].location(), "Cargo.toml:10");
+}
+
+#[test]
+fn lockfile_version_is_not_a_cargo_gate() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"a\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("Cargo.lock"),
+        "version = 4\n\n[[package]]\nname = \"a\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src").join("lib.rs"), "").unwrap();
+
+    let index = ReleaseIndex::from_iter(
+        (70..=80)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+    let accept = (70..=80)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let cmd = Find::new(&index, TestRunner::with_ok("x", &accept));
+    let mut ctx = create_test_context();
+    ctx.cargo_gates = true;
+    ctx.environment.root_crate_path = root;
+
+    // Lockfile version 4 is readable from Rust 1.78, but older versions of cargo still build a
+    // crate of which the lockfile lists no dependencies
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 70, 0));
+}
+
+#[test]
+fn cargo_gate_empties_search_space() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"a\"\nversion = \"0.1.0\"\n\n[dependencies]\nlog = { version = \"0.4\", optional = true }\n\n[features]\nlogging = [\"dep:log\"]\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src").join("lib.rs"), "").unwrap();
+
+    let index = ReleaseIndex::from_iter(
+        (50..=59)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+
+    let reporter = TestReporterWrapper::default();
+    let cmd = Find::new(&index, TestRunner::with_ok("x", &[]));
+    let mut ctx = create_test_context();
+    ctx.cargo_gates = true;
+    ctx.environment.root_crate_path = root;
+
+    let error = cmd.run(&ctx, reporter.get()).unwrap_err();
+
+    assert!(matches!(error, CargoMSRVError::NoToolchainsToTry(_)));
+    assert!(error
+        .to_string()
+        .contains("limited by the namespaced dependency features (`dep:`) (Cargo.toml:9) to min Rust '1.60.0'"));
+}
+
+#[test]
+fn lints_table_is_not_a_cargo_gate() {
+    let dir = assert_fs::TempDir::new().unwrap();
+    let root = Utf8PathBuf::from_path_buf(dir.path().to_path_buf()).unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[lints.rust]\nunsafe_code = \"forbid\"\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src").join("lib.rs"), "").unwrap();
+
+    let index = ReleaseIndex::from_iter(
+        (70..=80)
+            .rev()
+            .map(|minor| Release::new_stable(semver::Version::new(1, minor, 0))),
+    );
+    let accept = (70..=80)
+        .map(|minor| semver::Version::new(1, minor, 0))
+        .collect::<Vec<_>>();
+
+    let reporter = TestReporterWrapper::default();
+    let cmd = Find::new(&index, TestRunner::with_ok("x", &accept));
+    let mut ctx = create_test_context();
+    ctx.cargo_gates = true;
+    ctx.environment.root_crate_path = root;
+
+    // Older versions of cargo only warn about the unused manifest key of the `[lints]` table
+    let found = cmd.run(&ctx, reporter.get()).unwrap();
+    assert_eq!(found, semver::Version::new(1, 70, 0));
+
+    assert!(!reporter
+        .wait_for_events()
+        .iter()
+        .any(|event| matches!(event.message(), Message::ReducedSearchSpace(_))));
+}
+
+/// The fingerprint which `find` reports with its result, for the target of the test runner.
+fn fingerprint(ctx: &FindContext, index: &ReleaseIndex) -> Fingerprint {
+    Fingerprint::new(&ctx.environment, "x", &ctx.check_cmd, index)
+}
+
 fn create_test_context() -> FindContext {
     FindContext {
+        changelog: None,
         search_method: SearchMethod::Bisect,
+        shard: None,
         write_toolchain_file: false,
         ignore_lockfile: false,
+        lockfile_strategy: None,
         no_check_feedback: false,
         write_msrv: false,
+        sync: false,
+        diff_errors: false,
+        report_unlocks: false,
+        cargo_gates: false,
+        save_run: false,
+        also: Vec::new(),
+        targets: Vec::new(),
+        control: None,
+        deadline: None,
+        resume: false,
         rust_releases: RustReleasesContext {
             minimum_rust_version: None,
             maximum_rust_version: None,
             consider_patch_releases: false,
-            release_source: ReleaseSource::RustChangelog,
+            include_prerelease: false,
+            allowed_versions: Vec::new(),
+            release_sources: vec![ChainedReleaseSource::default()],
+            release_snapshot: None,
+            index_refresh: IndexRefresh::default(),
         },
         toolchain: ToolchainContext {
             target: "x",
             components: &[],
+            required_components: &[],
+            limits: ResourceLimits::default(),
+            disk_preflight: false,
+            dist_server_check: false,
+            fallback_host: None,
         },
         check_cmd: CheckCommandContext {
             cargo_features: None,
             cargo_all_features: false,
             cargo_no_default_features: false,
+            preset: CheckCommandPreset::CargoCheck,
             rustup_command: None,
+            check_examples: false,
+            check_benches: false,
+            check_fuzz: false,
+            bin: None,
+            lib: false,
+            vendor: false,
+            predicate: None,
         },
+        toolchain_provider: ToolchainProvider::Rustup,
+        custom_toolchains: Vec::new(),
+        denylist: Denylist::default(),
         environment: EnvironmentContext {
             root_crate_path: Utf8PathBuf::new(),
             workspace_packages: WorkspacePackages::default(),
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
         },
     }
 }
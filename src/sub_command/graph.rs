@@ -0,0 +1,23 @@
+use crate::context::GraphContext;
+use crate::dependency_graph::resolver::{CargoMetadataResolver, DependencyResolver};
+use crate::error::TResult;
+use crate::reporter::event::GraphResult;
+use crate::reporter::Reporter;
+use crate::SubCommand;
+
+#[derive(Default)]
+pub struct Graph;
+
+impl SubCommand for Graph {
+    type Context = GraphContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let resolver = CargoMetadataResolver::from_manifest_path(&ctx.environment.manifest());
+        let graph = resolver.resolve()?;
+
+        reporter.report_event(GraphResult::new(ctx.format, &graph))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use crate::context::ImagesContext;
+use crate::error::TResult;
+use crate::manifest::bare_version::BareVersion;
+use crate::reporter::event::{ContainerImage, DistributionPackage, ImagesResult};
+use crate::reporter::Reporter;
+use crate::{semver, SubCommand};
+
+/// The variants of the official `rust` container image, by the suffix of their tag.
+const IMAGE_VARIANTS: [(&str, &str); 3] = [
+    ("", "Debian"),
+    (
+        "-slim",
+        "Debian, without build tools beyond the Rust toolchain",
+    ),
+    ("-alpine", "Alpine Linux, with musl"),
+];
+
+/// The Rust version packaged by the supported releases of common Linux distributions, i.e. the
+/// version of `rustc` which `apt install rustc` or `apk add rust` installs.
+const DISTRIBUTION_PACKAGES: [(&str, &str, (u64, u64, u64)); 9] = [
+    ("Debian", "11 (bullseye)", (1, 48, 0)),
+    ("Debian", "12 (bookworm)", (1, 63, 0)),
+    ("Debian", "13 (trixie)", (1, 85, 0)),
+    ("Ubuntu", "22.04 (jammy)", (1, 75, 0)),
+    ("Ubuntu", "24.04 (noble)", (1, 75, 0)),
+    ("Alpine", "3.19", (1, 72, 1)),
+    ("Alpine", "3.20", (1, 78, 0)),
+    ("Alpine", "3.21", (1, 83, 0)),
+    ("Alpine", "3.22", (1, 87, 0)),
+];
+
+/// Suggest the container images and distribution packages which provide the MSRV, for use in
+/// container based CI.
+#[derive(Default)]
+pub struct Images;
+
+impl SubCommand for Images {
+    type Context = ImagesContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let msrv = ctx.rust_version.version();
+
+        reporter.report_event(ImagesResult::new(
+            msrv.clone(),
+            container_images(msrv),
+            distribution_packages(&msrv.to_semver_version()),
+        ))?;
+
+        Ok(())
+    }
+}
+
+/// The tags of the official `rust` image for the MSRV. A two component MSRV, like `1.70`, maps
+/// to the tag which follows the latest patch release of `1.70`.
+fn container_images(msrv: &BareVersion) -> Vec<ContainerImage> {
+    IMAGE_VARIANTS
+        .iter()
+        .map(|(suffix, base)| ContainerImage {
+            image: format!("rust:{}{}", msrv, suffix),
+            base: base.to_string(),
+        })
+        .collect()
+}
+
+/// The Rust version packaged by each distribution release, and whether it is at least the MSRV.
+fn distribution_packages(msrv: &semver::Version) -> Vec<DistributionPackage> {
+    DISTRIBUTION_PACKAGES
+        .iter()
+        .map(|(distribution, release, (major, minor, patch))| {
+            let rustc = semver::Version::new(*major, *minor, *patch);
+
+            DistributionPackage {
+                distribution: distribution.to_string(),
+                release: release.to_string(),
+                compatible: rustc >= *msrv,
+                rustc,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        two_components = { "1.70", "rust:1.70-slim" },
+        three_components = { "1.70.1", "rust:1.70.1-slim" },
+    )]
+    fn images(msrv: &str, slim: &str) {
+        let images = container_images(&msrv.parse().unwrap());
+
+        assert_eq!(images.len(), IMAGE_VARIANTS.len());
+        assert_eq!(images[1].image, slim);
+    }
+
+    #[test]
+    fn packages() {
+        let packages = distribution_packages(&semver::Version::new(1, 75, 0));
+
+        let compatible = packages
+            .iter()
+            .filter(|package| package.compatible)
+            .map(|package| format!("{} {}", package.distribution, package.release))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            compatible,
+            vec![
+                "Debian 13 (trixie)",
+                "Ubuntu 22.04 (jammy)",
+                "Ubuntu 24.04 (noble)",
+                "Alpine 3.20",
+                "Alpine 3.21",
+                "Alpine 3.22",
+            ]
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::context::InspectContext;
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::reporter::event::InspectResult;
+use crate::reporter::Reporter;
+use crate::run_artifacts::{Run, RUNS_DIR, SUMMARY_FILE};
+use crate::{semver, SubCommand};
+
+/// The run id which refers to the most recent run.
+const LATEST: &str = "latest";
+
+/// Browse the runs stored by `cargo msrv find --save-run` and `cargo msrv verify --save-run`.
+#[derive(Default)]
+pub struct Inspect;
+
+impl SubCommand for Inspect {
+    type Context = InspectContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let runs_dir = ctx.environment.root().join(RUNS_DIR);
+        let runs = stored_runs(&runs_dir)?;
+
+        let Some(run_id) = &ctx.run_id else {
+            reporter.report_event(InspectResult::runs(&runs))?;
+            return Ok(());
+        };
+
+        let run = select_run(runs, run_id, &runs_dir)?;
+        let dir = runs_dir.join(&run.run_id);
+
+        let Some(version) = &ctx.log else {
+            reporter.report_event(InspectResult::run(dir, run))?;
+            return Ok(());
+        };
+
+        let check = run
+            .checks
+            .iter()
+            .rev()
+            .find(|check| &check.version == version)
+            .ok_or_else(|| Error::NoCheckOfVersion {
+                run_id: run.run_id.clone(),
+                version: version.clone(),
+            })?;
+
+        let path = dir.join(&check.log);
+        let log = std::fs::read_to_string(&path).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadFile(path.clone()),
+        })?;
+
+        reporter.report_event(InspectResult::log(
+            run.run_id.clone(),
+            version.clone(),
+            path,
+            log,
+        ))?;
+
+        Ok(())
+    }
+}
+
+/// The runs stored in the given directory, ordered from the least to the most recent.
+///
+/// Directories without a run summary are skipped.
+pub(crate) fn stored_runs(runs_dir: &Utf8Path) -> TResult<Vec<Run>> {
+    if !runs_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = runs_dir.read_dir_utf8().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadDir(runs_dir.to_path_buf()),
+    })?;
+
+    let mut runs = Vec::new();
+
+    for entry in entries {
+        let entry = entry.map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadDir(runs_dir.to_path_buf()),
+        })?;
+
+        let summary = entry.path().join(SUMMARY_FILE);
+
+        if !summary.is_file() {
+            continue;
+        }
+
+        let contents = std::fs::read_to_string(&summary).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadFile(summary.clone()),
+        })?;
+
+        let run = serde_json::from_str::<Run>(&contents).map_err(|error| Error::InvalidRun {
+            path: summary.clone(),
+            error,
+        })?;
+
+        runs.push(run);
+    }
+
+    runs.sort_by(|lhs, rhs| by_start(lhs).cmp(&by_start(rhs)));
+
+    Ok(runs)
+}
+
+/// Runs are ordered by the time at which they started, and runs started within the same second,
+/// by the counter which was appended to their id.
+fn by_start(run: &Run) -> (&str, usize, &str) {
+    (&run.started_at, run.run_id.len(), &run.run_id)
+}
+
+fn select_run(runs: Vec<Run>, run_id: &str, runs_dir: &Utf8Path) -> Result<Run, Error> {
+    let run = if run_id == LATEST {
+        runs.into_iter().last()
+    } else {
+        runs.into_iter().find(|run| run.run_id == run_id)
+    };
+
+    run.ok_or_else(|| Error::RunNotFound {
+        run_id: run_id.to_string(),
+        runs_dir: runs_dir.to_path_buf(),
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(
+        "No run '{run_id}' found in '{runs_dir}', run `cargo msrv inspect` to list the stored runs"
+    )]
+    RunNotFound {
+        run_id: String,
+        runs_dir: Utf8PathBuf,
+    },
+
+    #[error("Unable to read the run summary in '{path}': {error}")]
+    InvalidRun {
+        path: Utf8PathBuf,
+        error: serde_json::Error,
+    },
+
+    #[error("Rust {version} was not checked during run '{run_id}'")]
+    NoCheckOfVersion {
+        run_id: String,
+        version: semver::Version,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(run_id: &str, started_at: &str) -> Run {
+        Run {
+            run_id: run_id.to_string(),
+            subcommand: "find".to_string(),
+            started_at: started_at.to_string(),
+            finished_at: None,
+            msrv: None,
+            checks: Vec::new(),
+        }
+    }
+
+    fn store(runs_dir: &Utf8Path, run: &Run) {
+        let dir = runs_dir.join(&run.run_id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(SUMMARY_FILE), serde_json::to_string(run).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn ordered_runs() {
+        let root = assert_fs::TempDir::new().unwrap();
+        let runs_dir = Utf8Path::from_path(root.path()).unwrap();
+
+        store(runs_dir, &run("20240615T133045Z-2", "2024-06-15T13:30:45Z"));
+        store(runs_dir, &run("20240615T133045Z", "2024-06-15T13:30:45Z"));
+        store(runs_dir, &run("20240101T000000Z", "2024-01-01T00:00:00Z"));
+        std::fs::create_dir_all(runs_dir.join("unrelated")).unwrap();
+
+        let runs = stored_runs(runs_dir).unwrap();
+        let ids = runs
+            .iter()
+            .map(|run| run.run_id.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ids,
+            vec!["20240101T000000Z", "20240615T133045Z", "20240615T133045Z-2"]
+        );
+
+        let latest = select_run(runs, LATEST, runs_dir).unwrap();
+        assert_eq!(latest.run_id, "20240615T133045Z-2");
+    }
+
+    #[test]
+    fn no_runs_stored() {
+        let root = assert_fs::TempDir::new().unwrap();
+        let runs_dir = Utf8Path::from_path(root.path()).unwrap().join(RUNS_DIR);
+
+        let runs = stored_runs(&runs_dir).unwrap();
+        assert!(runs.is_empty());
+
+        let error = select_run(runs, LATEST, &runs_dir).unwrap_err();
+        assert!(matches!(error, Error::RunNotFound { .. }));
+    }
+}
@@ -0,0 +1,251 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use std::collections::BTreeMap;
+
+use crate::context::MergeResultsContext;
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use crate::reporter::event::{MergeResult, ShardResult};
+use crate::reporter::Reporter;
+use crate::search_method::Shard;
+use crate::{semver, SubCommand};
+
+/// Combine the results of a sharded linear search, as reported by `cargo msrv find --shard i/n`,
+/// into one MSRV.
+#[derive(Default)]
+pub struct MergeResults;
+
+impl SubCommand for MergeResults {
+    type Context = MergeResultsContext;
+    type Output = semver::Version;
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let mut results = Vec::new();
+
+        for path in &ctx.files {
+            let contents = std::fs::read_to_string(path).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::ReadFile(path.clone()),
+            })?;
+
+            results.extend(parse_shard_results(path, &contents)?);
+        }
+
+        let shards = results
+            .first()
+            .map_or(0, |result| result.shard.shard.count());
+        let msrv = merge(results)?;
+
+        reporter.report_event(MergeResult::new(msrv.clone(), shards))?;
+
+        msrv.ok_or(CargoMSRVError::SubCommandMergeResults(
+            Error::NoCompatibleToolchain { shards },
+        ))
+    }
+}
+
+/// The result of one shard, as read from its JSON output.
+#[derive(Clone, Debug, PartialEq)]
+struct ShardedFindResult {
+    shard: ShardResult,
+    msrv: Option<semver::Version>,
+}
+
+/// The fields of a `find` subcommand result, which are required to merge it.
+#[derive(serde::Deserialize)]
+struct FindResultLine {
+    result: FindResultDetails,
+    shard: ShardResult,
+}
+
+#[derive(serde::Deserialize)]
+struct FindResultDetails {
+    version: Option<semver::Version>,
+}
+
+/// Collect the sharded `find` results from the JSON lines output of cargo-msrv. Other events are
+/// ignored.
+fn parse_shard_results(path: &Utf8Path, contents: &str) -> TResult<Vec<ShardedFindResult>> {
+    let mut results = Vec::new();
+
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        // Not every line is necessarily an event, e.g. when stdout and stderr were combined
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+
+        let is_find_result = event["type"] == "subcommand_result"
+            && event["subcommand_id"] == "find"
+            && !event["shard"].is_null();
+
+        if !is_find_result {
+            continue;
+        }
+
+        let line = serde_json::from_value::<FindResultLine>(event).map_err(|error| {
+            Error::InvalidShardResult {
+                path: path.to_path_buf(),
+                error,
+            }
+        })?;
+
+        results.push(ShardedFindResult {
+            shard: line.shard,
+            msrv: line.result.version,
+        });
+    }
+
+    if results.is_empty() {
+        return Err(Error::NoShardResult(path.to_path_buf()).into());
+    }
+
+    Ok(results)
+}
+
+/// Conclude the MSRV from the results of all shards.
+///
+/// Shards are ordered from the most to the least recent slice of the search space. Just like a
+/// linear search over the search space as a whole, the MSRV is the release right before the first
+/// incompatible release.
+fn merge(results: Vec<ShardedFindResult>) -> Result<Option<semver::Version>, Error> {
+    let Some(count) = results.first().map(|result| result.shard.shard.count()) else {
+        return Ok(None);
+    };
+
+    let mut shards = BTreeMap::new();
+
+    for result in results {
+        let shard = result.shard.shard;
+
+        if shard.count() != count {
+            return Err(Error::ShardCountMismatch {
+                expected: count,
+                found: shard,
+            });
+        }
+
+        if shards.insert(shard.index(), result).is_some() {
+            return Err(Error::DuplicateShard(shard));
+        }
+    }
+
+    if let Some(index) = (1..=count).find(|index| !shards.contains_key(index)) {
+        let shard = Shard::new(index, count).expect("index is within range");
+        return Err(Error::MissingShard(shard));
+    }
+
+    let mut msrv = None;
+
+    for result in shards.into_values() {
+        if result.msrv.is_some() {
+            msrv = result.msrv;
+        }
+
+        if !result.shard.all_compatible {
+            break;
+        }
+    }
+
+    Ok(msrv)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No result of 'cargo msrv find --shard i/n --output-format json' found in '{0}'")]
+    NoShardResult(Utf8PathBuf),
+
+    #[error("Unable to read the shard result in '{path}': {error}")]
+    InvalidShardResult {
+        path: Utf8PathBuf,
+        error: serde_json::Error,
+    },
+
+    #[error("Expected the results of {expected} shards, but found the result of shard {found}")]
+    ShardCountMismatch { expected: u32, found: Shard },
+
+    #[error("The result of shard {0} was given more than once")]
+    DuplicateShard(Shard),
+
+    #[error("The result of shard {0} is missing")]
+    MissingShard(Shard),
+
+    #[error("None of the {shards} shards found a compatible Rust version")]
+    NoCompatibleToolchain { shards: u32 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(index: u32, count: u32, msrv: Option<(u64, u64)>, all: bool) -> ShardedFindResult {
+        ShardedFindResult {
+            shard: ShardResult {
+                shard: Shard::new(index, count).unwrap(),
+                all_compatible: all,
+            },
+            msrv: msrv.map(|(major, minor)| semver::Version::new(major, minor, 0)),
+        }
+    }
+
+    #[yare::parameterized(
+        in_first_shard = { vec![result(1, 3, Some((1, 70)), false), result(2, 3, None, false), result(3, 3, None, false)], Some((1, 70)) },
+        in_middle_shard = { vec![result(1, 3, Some((1, 66)), true), result(2, 3, Some((1, 63)), false), result(3, 3, None, false)], Some((1, 63)) },
+        at_shard_boundary = { vec![result(1, 3, Some((1, 66)), true), result(2, 3, None, false), result(3, 3, None, false)], Some((1, 66)) },
+        all_compatible = { vec![result(1, 2, Some((1, 60)), true), result(2, 2, Some((1, 56)), true)], Some((1, 56)) },
+        none_compatible = { vec![result(1, 2, None, false), result(2, 2, None, false)], None },
+        empty_last_shard = { vec![result(1, 2, Some((1, 60)), true), result(2, 2, None, true)], Some((1, 60)) },
+        unordered = { vec![result(2, 2, Some((1, 56)), true), result(1, 2, Some((1, 60)), true)], Some((1, 56)) },
+    )]
+    fn merged(results: Vec<ShardedFindResult>, expected: Option<(u64, u64)>) {
+        let expected = expected.map(|(major, minor)| semver::Version::new(major, minor, 0));
+
+        assert_eq!(merge(results).unwrap(), expected);
+    }
+
+    #[test]
+    fn missing_shard() {
+        let results = vec![result(1, 3, Some((1, 66)), true), result(3, 3, None, false)];
+
+        let error = merge(results).unwrap_err();
+        assert!(matches!(error, Error::MissingShard(shard) if shard.index() == 2));
+    }
+
+    #[test]
+    fn duplicate_shard() {
+        let results = vec![result(1, 2, None, false), result(1, 2, None, false)];
+
+        let error = merge(results).unwrap_err();
+        assert!(matches!(error, Error::DuplicateShard(shard) if shard.index() == 1));
+    }
+
+    #[test]
+    fn shard_count_mismatch() {
+        let results = vec![result(1, 2, None, false), result(2, 3, None, false)];
+
+        let error = merge(results).unwrap_err();
+        assert!(matches!(
+            error,
+            Error::ShardCountMismatch { expected: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn parse_json_lines() {
+        let contents = r#"{"type":"meta","instance":"cargo-msrv","version":"0.16.2"}
+{"type":"subcommand_result","subcommand_id":"find","result":{"version":"1.63.0","success":true},"shard":{"index":2,"count":3,"all_compatible":false}}
+"#;
+
+        let results = parse_shard_results(Utf8Path::new("shard-2.json"), contents).unwrap();
+
+        assert_eq!(results, vec![result(2, 3, Some((1, 63)), false)]);
+    }
+
+    #[test]
+    fn parse_without_shard_result() {
+        let contents = r#"{"type":"subcommand_result","subcommand_id":"find","result":{"version":"1.63.0","success":true}}"#;
+
+        let error = parse_shard_results(Utf8Path::new("find.json"), contents).unwrap_err();
+        assert!(matches!(
+            error,
+            CargoMSRVError::SubCommandMergeResults(Error::NoShardResult(_))
+        ));
+    }
+}
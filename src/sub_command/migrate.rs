@@ -0,0 +1,473 @@
+use camino::Utf8Path;
+use cargo_metadata::{MetadataCommand, Package};
+use rust_releases::semver;
+use toml_edit::{value, DocumentMut, Item, RawString, Table};
+
+use crate::context::{EnvironmentContext, MigrateContext};
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::{CargoManifestParser, ManifestParseError, TomlParser};
+use crate::reporter::event::{
+    AuxiliaryOutput, AuxiliaryOutputItem, Destination, Edit, EditLocation, MigrateResult,
+    Migration, MigrationKind, MsrvKind, SkipReason, SkippedMigration, Warning,
+};
+use crate::reporter::Reporter;
+use crate::sub_command::set::{msrv_values, remove_metadata_msrv, RUST_VERSION_SUPPORTED_SINCE};
+use crate::SubCommand;
+
+#[derive(Default)]
+pub struct Migrate;
+
+impl SubCommand for Migrate {
+    type Context = MigrateContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        migrate(&ctx.environment, ctx.dry_run, reporter)
+    }
+}
+
+/// Replace the `package.metadata.msrv` of each member of the workspace by `package.rust-version`.
+/// A `package.metadata.msrv` which can't be migrated is reported as a warning, and left as is.
+fn migrate(env: &EnvironmentContext, dry_run: bool, reporter: &impl Reporter) -> TResult<()> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(env.manifest())
+        .no_deps()
+        .exec()?;
+
+    let workspace_manifest = read_manifest(&metadata.workspace_root.join("Cargo.toml"))?;
+    let workspace_rust_version = workspace_rust_version(&workspace_manifest)?;
+
+    let mut migrated = Vec::new();
+    let mut skipped = Vec::new();
+
+    for package in metadata.workspace_packages() {
+        let Some(msrv) = legacy_msrv(package)? else {
+            continue;
+        };
+        let manifest_path = &package.manifest_path;
+
+        let mut manifest = read_manifest(manifest_path)?;
+        let before = msrv_values(&manifest);
+
+        let rust_version = package.rust_version.as_ref();
+        let kind = match migrate_manifest(
+            &mut manifest,
+            &msrv,
+            rust_version,
+            workspace_rust_version.as_ref(),
+        ) {
+            Ok(kind) => kind,
+            Err(reason) => {
+                let skip = SkippedMigration {
+                    package: package.name.to_string(),
+                    manifest_path: manifest_path.clone(),
+                    msrv,
+                    reason,
+                };
+                reporter.report_event(Warning::msrv_not_migrated(&skip))?;
+                skipped.push(skip);
+                continue;
+            }
+        };
+
+        let after = msrv_values(&manifest);
+
+        if !dry_run {
+            std::fs::write(manifest_path, manifest.to_string()).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::WriteFile(manifest_path.clone()),
+            })?;
+
+            reporter.report_event(AuxiliaryOutput::new(
+                Destination::file(manifest_path.clone()),
+                AuxiliaryOutputItem::msrv(MsrvKind::RustVersion),
+            ))?;
+
+            for ((path, old), (_, new)) in before.iter().zip(after.iter()) {
+                if old != new {
+                    let location = EditLocation::TomlPath {
+                        path: path.to_string(),
+                    };
+                    reporter.report_event(Edit::new(
+                        manifest_path.clone(),
+                        location,
+                        old.clone(),
+                        new.clone(),
+                    ))?;
+                }
+            }
+        }
+
+        let [(_, rust_version), ..] = after;
+        migrated.push(Migration {
+            package: package.name.to_string(),
+            manifest_path: manifest_path.clone(),
+            msrv,
+            rust_version: rust_version.unwrap_or_default(),
+            kind,
+        });
+    }
+
+    reporter.report_event(MigrateResult::new(dry_run, migrated, skipped))?;
+
+    Ok(())
+}
+
+pub(crate) fn read_manifest(path: &Utf8Path) -> TResult<DocumentMut> {
+    let contents = std::fs::read_to_string(path).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(path.to_path_buf()),
+    })?;
+
+    Ok(CargoManifestParser.parse::<DocumentMut>(&contents)?)
+}
+
+/// The `package.metadata.msrv` of the package, if it is a version.
+fn legacy_msrv(package: &Package) -> TResult<Option<BareVersion>> {
+    let msrv = package
+        .metadata
+        .get("msrv")
+        .and_then(|msrv| msrv.as_str())
+        .map(BareVersion::try_from)
+        .transpose()
+        .map_err(ManifestParseError::from)?;
+
+    Ok(msrv)
+}
+
+/// The `workspace.package.rust-version` of the workspace manifest, which members can inherit.
+pub(crate) fn workspace_rust_version(manifest: &DocumentMut) -> TResult<Option<BareVersion>> {
+    let rust_version = manifest
+        .as_table()
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"))
+        .and_then(|package| package.get("rust-version"))
+        .and_then(Item::as_str)
+        .map(BareVersion::try_from)
+        .transpose()
+        .map_err(ManifestParseError::from)?;
+
+    Ok(rust_version)
+}
+
+/// Replace the `package.metadata.msrv` of the manifest, which is `msrv`, by `package.rust-version`.
+/// The `rust_version` is the `package.rust-version` which the package already declares, if any,
+/// resolved from the workspace if it is inherited.
+fn migrate_manifest(
+    manifest: &mut DocumentMut,
+    msrv: &BareVersion,
+    rust_version: Option<&semver::Version>,
+    workspace_rust_version: Option<&BareVersion>,
+) -> Result<MigrationKind, SkipReason> {
+    let version = msrv.to_semver_version();
+
+    let kind = match rust_version {
+        Some(rust_version) if rust_version == &version => MigrationKind::AlreadyDeclared,
+        Some(rust_version) => {
+            return Err(SkipReason::ConflictingRustVersion {
+                rust_version: BareVersion::from(rust_version),
+            })
+        }
+        None if version < RUST_VERSION_SUPPORTED_SINCE => {
+            return Err(SkipReason::PredatesRustVersion)
+        }
+        None if workspace_rust_version.map(BareVersion::to_semver_version) == Some(version) => {
+            // Written as `rust-version.workspace = true`, like Cargo documents it
+            let mut inherited = Table::new();
+            inherited.set_dotted(true);
+            inherited.insert("workspace", value(true));
+            manifest["package"]["rust-version"] = Item::Table(inherited);
+
+            MigrationKind::Inherited
+        }
+        None => {
+            manifest["package"]["rust-version"] = value(msrv.to_string());
+
+            MigrationKind::Added
+        }
+    };
+
+    let comments = removed_comments(manifest);
+    remove_metadata_msrv(manifest);
+
+    // The comments are kept above `package.rust-version`, which replaces what they comment on
+    if let Some(package) = manifest["package"].as_table_mut() {
+        prepend_comments(package, "rust-version", &comments);
+    }
+
+    Ok(kind)
+}
+
+/// Prepend the comments to the line of the key. The line of a dotted key, like
+/// `rust-version.workspace = true`, starts with the decor of its innermost key.
+fn prepend_comments(table: &mut Table, key: &str, comments: &str) {
+    if comments.is_empty() {
+        return;
+    }
+
+    if let Some(Item::Table(dotted)) = table.get_mut(key) {
+        let first = dotted.iter().next().map(|(key, _)| key.to_string());
+        if let Some(first) = first.filter(|_| dotted.is_dotted()) {
+            return prepend_comments(dotted, &first, comments);
+        }
+    }
+
+    if let Some(mut key) = table.key_mut(key) {
+        let prefix = key
+            .leaf_decor()
+            .prefix()
+            .and_then(RawString::as_str)
+            .unwrap_or_default();
+        let prefix = format!("{}{}", comments, prefix);
+        key.leaf_decor_mut().set_prefix(prefix);
+    }
+}
+
+/// The comments which would be removed with `package.metadata.msrv`: those above and after the
+/// key, and those above the `[package.metadata]` table, if the table is removed with it.
+fn removed_comments(manifest: &DocumentMut) -> String {
+    let Some(metadata) = manifest["package"].get("metadata").and_then(Item::as_table) else {
+        return String::new();
+    };
+
+    let table = (metadata.len() == 1).then(|| metadata.decor().prefix());
+    let key = metadata.key("msrv").map(|key| key.leaf_decor().prefix());
+    let suffix = metadata
+        .get("msrv")
+        .and_then(Item::as_value)
+        .map(|msrv| msrv.decor().suffix());
+
+    [table, key, suffix]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(RawString::as_str)
+        .flat_map(str::lines)
+        .map(str::trim)
+        .filter(|line| line.starts_with('#'))
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrated(
+        input: &str,
+        rust_version: Option<&str>,
+        workspace_rust_version: Option<&str>,
+    ) -> Result<(MigrationKind, String), SkipReason> {
+        let mut manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+        let msrv = manifest["package"]["metadata"]["msrv"]
+            .as_str()
+            .unwrap()
+            .parse::<BareVersion>()
+            .unwrap();
+        let rust_version = rust_version.map(|version| semver::Version::parse(version).unwrap());
+        let workspace_rust_version =
+            workspace_rust_version.map(|version| version.parse::<BareVersion>().unwrap());
+
+        let kind = migrate_manifest(
+            &mut manifest,
+            &msrv,
+            rust_version.as_ref(),
+            workspace_rust_version.as_ref(),
+        )?;
+
+        Ok((kind, manifest.to_string()))
+    }
+
+    #[test]
+    fn added() {
+        let input = r#"[package]
+name = "a"
+version = "0.1.0" # the version
+
+[package.metadata]
+msrv = "1.60"
+
+[dependencies]
+"#;
+
+        let (kind, output) = migrated(input, None, None).unwrap();
+
+        assert_eq!(kind, MigrationKind::Added);
+        assert_eq!(
+            output,
+            r#"[package]
+name = "a"
+version = "0.1.0" # the version
+rust-version = "1.60"
+
+[dependencies]
+"#
+        );
+    }
+
+    #[test]
+    fn comments_are_kept() {
+        let input = r#"[package]
+name = "a"
+version = "0.1.0"
+
+# keep me
+[package.metadata]
+# and me
+msrv = "1.60" # me too
+
+[dependencies]
+"#;
+
+        let (kind, output) = migrated(input, None, None).unwrap();
+
+        assert_eq!(kind, MigrationKind::Added);
+        assert_eq!(
+            output,
+            r#"[package]
+name = "a"
+version = "0.1.0"
+# keep me
+# and me
+# me too
+rust-version = "1.60"
+
+[dependencies]
+"#
+        );
+    }
+
+    #[test]
+    fn comments_of_kept_metadata_table() {
+        let input = r#"[package]
+name = "a"
+version = "0.1.0"
+rust-version.workspace = true
+
+# keep me here
+[package.metadata]
+docs = true
+# keep me
+msrv = "1.60"
+"#;
+
+        let (kind, output) = migrated(input, Some("1.60.0"), Some("1.60")).unwrap();
+
+        assert_eq!(kind, MigrationKind::AlreadyDeclared);
+        assert_eq!(
+            output,
+            r#"[package]
+name = "a"
+version = "0.1.0"
+# keep me
+rust-version.workspace = true
+
+# keep me here
+[package.metadata]
+docs = true
+"#
+        );
+    }
+
+    #[test]
+    fn other_metadata_is_kept() {
+        let input = r#"[package]
+name = "a"
+version = "0.1.0"
+metadata = { msrv = "1.60.0", docs = true }
+"#;
+
+        let (kind, output) = migrated(input, None, None).unwrap();
+
+        assert_eq!(kind, MigrationKind::Added);
+        assert_eq!(
+            output,
+            r#"[package]
+name = "a"
+version = "0.1.0"
+metadata = { docs = true }
+rust-version = "1.60.0"
+"#
+        );
+    }
+
+    #[yare::parameterized(
+        same = { Some("1.60"), MigrationKind::Inherited },
+        different = { Some("1.65"), MigrationKind::Added },
+        none = { None, MigrationKind::Added },
+    )]
+    fn workspace(workspace_rust_version: Option<&str>, expected: MigrationKind) {
+        let input = r#"[package]
+name = "a"
+version = "0.1.0"
+
+[package.metadata]
+msrv = "1.60"
+"#;
+
+        let (kind, output) = migrated(input, None, workspace_rust_version).unwrap();
+
+        assert_eq!(kind, expected);
+
+        let expected_line = match expected {
+            MigrationKind::Inherited => "rust-version.workspace = true",
+            _ => r#"rust-version = "1.60""#,
+        };
+        assert!(output.contains(expected_line), "{}", output);
+        assert!(!output.contains("msrv"), "{}", output);
+    }
+
+    #[yare::parameterized(
+        declared = { r#"rust-version = "1.60""# },
+        inherited = { "rust-version.workspace = true" },
+    )]
+    fn already_declared(rust_version: &str) {
+        let input = format!(
+            r#"[package]
+name = "a"
+version = "0.1.0"
+{}
+
+[package.metadata]
+msrv = "1.60"
+"#,
+            rust_version
+        );
+
+        // The version is resolved from the workspace by Cargo, if it is inherited
+        let (kind, output) = migrated(&input, Some("1.60.0"), Some("1.60")).unwrap();
+
+        assert_eq!(kind, MigrationKind::AlreadyDeclared);
+        assert_eq!(
+            output,
+            format!(
+                r#"[package]
+name = "a"
+version = "0.1.0"
+{}
+"#,
+                rust_version
+            )
+        );
+    }
+
+    #[yare::parameterized(
+        conflicting = { "1.60", Some("1.65.0"), SkipReason::ConflictingRustVersion { rust_version: BareVersion::ThreeComponents(1, 65, 0) } },
+        predates_rust_version = { "1.55", None, SkipReason::PredatesRustVersion },
+    )]
+    fn skipped(msrv: &str, rust_version: Option<&str>, expected: SkipReason) {
+        let input = format!(
+            r#"[package]
+name = "a"
+version = "0.1.0"
+
+[package.metadata]
+msrv = "{}"
+"#,
+            msrv
+        );
+
+        assert_eq!(migrated(&input, rust_version, None), Err(expected));
+    }
+}
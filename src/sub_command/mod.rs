@@ -1,3 +1,38 @@
+/// Find the update of the lockfile which first made the crate fail with a given toolchain.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv bisect-lockfile --toolchain 1.63`
+pub use bisect_lockfile::BisectLockfile;
+
+/// Inspect the global cache of cargo-msrv.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv cache stats`
+pub use cache::Cache;
+
+/// Check whether the declarations of the MSRV in a repository agree with each other.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv check-consistency`
+pub use check_consistency::CheckConsistency;
+
+/// Remove the caches, state files and target directories of cargo-msrv.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv clean --dry-run`
+pub use clean::Clean;
+
+/// Estimate the MSRV of a Rust package, without compiling it.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv estimate`
+pub use estimate::Estimate;
+
 /// Find the MSRV of a Rust package.
 ///
 /// # Example (CLI)
@@ -5,6 +40,27 @@
 /// `cargo msrv`
 pub use find::Find;
 
+/// Render the dependency graph, with the MSRV's of dependencies.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv graph --format dot`
+pub use graph::Graph;
+
+/// Suggest the container images and distribution packages which provide the MSRV.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv images`
+pub use images::Images;
+
+/// Browse the runs stored by `cargo msrv find --save-run` and `cargo msrv verify --save-run`.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv inspect latest`
+pub use inspect::Inspect;
+
 /// List the MSRV's of libraries you depend on.
 ///
 /// # Example (CLI)
@@ -12,6 +68,34 @@ pub use find::Find;
 /// `cargo msrv list`
 pub use list::List;
 
+/// Combine the results of sharded MSRV searches into one MSRV.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv merge-results shard-1.json shard-2.json`
+pub use merge_results::MergeResults;
+
+/// Migrate the legacy `package.metadata.msrv` of each workspace member to `package.rust-version`.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv migrate --dry-run`
+pub use migrate::Migrate;
+
+/// List the dependencies which can not be upgraded without raising the MSRV.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv outdated`
+pub use outdated::Outdated;
+
+/// Render a report of a run from its recorded events.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv report --from events.jsonl --format junit`
+pub use report::Report;
+
 /// Check whether the MSRV of a crate is valid as an MSRV.
 ///
 /// # Use case
@@ -37,15 +121,42 @@ pub use set::Set;
 ///
 /// `cargo msrv show`
 pub use show::Show;
+/// Update each declaration of the MSRV in a repository to the MSRV.
+///
+/// # Example (CLI)
+///
+/// `cargo msrv sync --dry-run`
+pub use sync::SyncDeclarations;
+
+/// Analyse whether adding a dependency would raise the MSRV
+///
+/// # Example (CLI)
+///
+/// `cargo msrv whatif add serde@1.0`
+pub use whatif::WhatIf;
 
 use crate::reporter::Reporter;
 use crate::TResult;
 
+pub mod bisect_lockfile;
+pub mod cache;
+pub mod check_consistency;
+pub mod clean;
+pub mod estimate;
 pub mod find;
+pub mod graph;
+pub mod images;
+pub mod inspect;
 pub mod list;
+pub mod merge_results;
+pub mod migrate;
+pub mod outdated;
+pub mod report;
 pub mod set;
 pub mod show;
+pub mod sync;
 pub mod verify;
+pub mod whatif;
 
 /// A sub-command of `cargo-msrv`.
 ///
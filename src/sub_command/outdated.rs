@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use crate::context::OutdatedContext;
+use crate::dependency_graph::crates_index::{CratesIndex, IndexRelease};
+use crate::dependency_graph::resolver::{CargoMetadataResolver, DependencyResolver};
+use crate::dependency_graph::DependencyGraph;
+use crate::error::TResult;
+use crate::reporter::event::{OutdatedDependency, OutdatedResult};
+use crate::reporter::Reporter;
+use crate::{semver, SubCommand};
+
+/// Provides the published releases of a crate.
+pub trait CrateReleases {
+    fn releases(&self, name: &str) -> TResult<Vec<IndexRelease>>;
+}
+
+impl CrateReleases for CratesIndex {
+    fn releases(&self, name: &str) -> TResult<Vec<IndexRelease>> {
+        CratesIndex::releases(self, name)
+    }
+}
+
+/// List the dependencies which can not be upgraded to their newest release, without raising the
+/// MSRV.
+pub struct Outdated<I: CrateReleases> {
+    index: I,
+}
+
+impl<I: CrateReleases> Outdated<I> {
+    pub fn new(index: I) -> Self {
+        Self { index }
+    }
+}
+
+impl<I: CrateReleases> SubCommand for Outdated<I> {
+    type Context = OutdatedContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let resolver = CargoMetadataResolver::from_manifest_path(&ctx.environment.manifest());
+        let graph = resolver.resolve()?;
+        let msrv = ctx.rust_version.version().to_semver_version();
+
+        let mut dependencies = Vec::new();
+
+        for (name, version) in crates_io_dependencies(&graph) {
+            let releases = self.index.releases(name)?;
+
+            if let Some(dependency) = outdated_dependency(name, version, &releases, &msrv) {
+                dependencies.push(dependency);
+            }
+        }
+
+        reporter.report_event(OutdatedResult::new(msrv, dependencies))?;
+
+        Ok(())
+    }
+}
+
+/// The dependencies published to crates.io, by name. If multiple versions of a crate are
+/// depended upon, only the newest is considered.
+fn crates_io_dependencies(graph: &DependencyGraph) -> BTreeMap<&str, &semver::Version> {
+    let packages = graph.packages();
+    let mut dependencies = BTreeMap::new();
+
+    for nx in graph.shortest_path_parents().into_keys() {
+        let package = &packages[nx];
+
+        let is_crates_io = package.source.as_ref().is_some_and(|source| {
+            source.is_crates_io() || source.repr == "sparse+https://index.crates.io/"
+        });
+
+        if !is_crates_io {
+            continue;
+        }
+
+        let version = dependencies
+            .entry(package.name.as_str())
+            .or_insert(&package.version);
+
+        if *version < &package.version {
+            *version = &package.version;
+        }
+    }
+
+    dependencies
+}
+
+/// Compares the current `version` of a dependency with its published `releases`.
+///
+/// Returns `None` if the newest release is compatible with the `msrv`, or its current version is
+/// already the newest release. Yanked releases and pre-releases are not considered. Releases
+/// without a declared Rust version are assumed to be compatible.
+fn outdated_dependency(
+    name: &str,
+    version: &semver::Version,
+    releases: &[IndexRelease],
+    msrv: &semver::Version,
+) -> Option<OutdatedDependency> {
+    let candidates = releases
+        .iter()
+        .filter(|release| !release.yanked && release.version.pre.is_empty());
+
+    let latest = candidates
+        .clone()
+        .max_by(|lhs, rhs| lhs.version.cmp(&rhs.version))?;
+    let latest_rust_version = latest.rust_version.as_ref()?;
+
+    if latest.version <= *version || latest_rust_version <= msrv {
+        return None;
+    }
+
+    let newest_compatible = candidates
+        .filter(|release| release.rust_version.as_ref().map_or(true, |rv| rv <= msrv))
+        .map(|release| &release.version)
+        .max()
+        .cloned();
+
+    Some(OutdatedDependency {
+        name: name.to_string(),
+        version: version.clone(),
+        latest: latest.version.clone(),
+        latest_rust_version: latest_rust_version.clone(),
+        newest_compatible,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(version: &str, rust_version: Option<&str>, yanked: bool) -> IndexRelease {
+        IndexRelease {
+            version: semver::Version::parse(version).unwrap(),
+            rust_version: rust_version.map(|v| semver::Version::parse(v).unwrap()),
+            yanked,
+        }
+    }
+
+    fn releases() -> Vec<IndexRelease> {
+        vec![
+            release("1.0.0", None, false),
+            release("1.1.0", Some("1.60.0"), false),
+            release("1.2.0", Some("1.65.0"), false),
+            release("1.3.0", Some("1.68.0"), true),
+            release("1.4.0", Some("1.70.0"), false),
+            release("2.0.0-alpha.1", Some("1.80.0"), false),
+        ]
+    }
+
+    #[yare::parameterized(
+        newest_compatible_is_newer = { "1.0.0", "1.65.0", Some("1.2.0") },
+        newest_compatible_is_current = { "1.1.0", "1.60.0", Some("1.1.0") },
+        newest_compatible_is_older = { "1.2.0", "1.56.0", Some("1.0.0") },
+    )]
+    fn outdated(version: &str, msrv: &str, newest_compatible: Option<&str>) {
+        let version = semver::Version::parse(version).unwrap();
+        let msrv = semver::Version::parse(msrv).unwrap();
+
+        let dependency = outdated_dependency("a", &version, &releases(), &msrv).unwrap();
+
+        assert_eq!(dependency.latest, semver::Version::new(1, 4, 0));
+        assert_eq!(
+            dependency.latest_rust_version,
+            semver::Version::new(1, 70, 0)
+        );
+        assert_eq!(
+            dependency.newest_compatible,
+            newest_compatible.map(|v| semver::Version::parse(v).unwrap())
+        );
+    }
+
+    #[yare::parameterized(
+        latest_is_compatible = { "1.0.0", "1.70.0" },
+        current_is_latest = { "1.4.0", "1.56.0" },
+    )]
+    fn not_outdated(version: &str, msrv: &str) {
+        let version = semver::Version::parse(version).unwrap();
+        let msrv = semver::Version::parse(msrv).unwrap();
+
+        assert!(outdated_dependency("a", &version, &releases(), &msrv).is_none());
+    }
+
+    #[test]
+    fn latest_without_rust_version() {
+        let releases = vec![
+            release("1.0.0", Some("1.70.0"), false),
+            release("1.1.0", None, false),
+        ];
+        let version = semver::Version::new(1, 0, 0);
+        let msrv = semver::Version::new(1, 56, 0);
+
+        assert!(outdated_dependency("a", &version, &releases, &msrv).is_none());
+    }
+}
@@ -0,0 +1,50 @@
+use camino::Utf8PathBuf;
+
+use crate::context::ReportContext;
+use crate::error::{IoError, IoErrorSource, TResult};
+use crate::recorded_run::{InvalidEvent, RecordedRun};
+use crate::reporter::event::ReportResult;
+use crate::reporter::Reporter;
+use crate::SubCommand;
+
+/// Render a report of a run of cargo-msrv, from the events which were recorded with
+/// `--log-events`.
+#[derive(Default)]
+pub struct Report;
+
+impl SubCommand for Report {
+    type Context = ReportContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let contents = std::fs::read_to_string(&ctx.from).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadFile(ctx.from.clone()),
+        })?;
+
+        let run = RecordedRun::from_event_log(&contents).map_err(|error| Error::InvalidEvent {
+            path: ctx.from.clone(),
+            error,
+        })?;
+
+        if run.is_empty() {
+            return Err(Error::NoRecordedRun(ctx.from.clone()).into());
+        }
+
+        reporter.report_event(ReportResult::new(ctx.format, &run))?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No events of a run found in '{0}', record them with '--log-events <path>'")]
+    NoRecordedRun(Utf8PathBuf),
+
+    #[error("Unable to read the event log '{path}', since {error}")]
+    InvalidEvent {
+        path: Utf8PathBuf,
+        error: InvalidEvent,
+    },
+}
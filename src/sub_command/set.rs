@@ -1,20 +1,24 @@
 use std::io::Write;
 
+use cargo_metadata::MetadataCommand;
 use rust_releases::{semver, Release, ReleaseIndex};
 use toml_edit::{table, value, DocumentMut, Item, Value};
 
 use crate::context::SetContext;
+use crate::declaration::{self, Declaration, DeclarationKind};
 use crate::error::{InvalidMsrvSetError, IoError, IoErrorSource, SetMsrvError};
 use crate::manifest::bare_version::BareVersion;
+use crate::manifest::sync_rules::sync_rules;
 use crate::manifest::{CargoManifestParser, TomlParser};
 use crate::reporter::event::{
-    AuxiliaryOutput, AuxiliaryOutputItem, Destination, MsrvKind, SetResult,
+    AuxiliaryOutput, AuxiliaryOutputItem, Destination, Edit, EditLocation, MsrvKind, SetResult,
     UnableToConfirmValidReleaseVersion,
 };
 use crate::reporter::Reporter;
+use crate::writer::changelog::ChangelogUpdate;
 use crate::{CargoMSRVError, SubCommand, TResult};
 
-const RUST_VERSION_SUPPORTED_SINCE: semver::Version = semver::Version::new(1, 56, 0);
+pub(crate) const RUST_VERSION_SUPPORTED_SINCE: semver::Version = semver::Version::new(1, 56, 0);
 
 pub struct Set<'index> {
     release_index: Option<&'index ReleaseIndex>,
@@ -77,10 +81,24 @@ fn set_msrv(ctx: &SetContext, reporter: &impl Reporter, msrv: &BareVersion) -> T
 
     // Parse the Cargo manifest contents, in particular the MSRV value
     let mut manifest = CargoManifestParser.parse::<DocumentMut>(&contents)?;
-    check_workspace(&manifest)?;
+
+    // Prepare the changelog entry, so an unusable changelog fails before anything is written
+    let changelog = match ctx.changelog {
+        Some(mode) => {
+            ChangelogUpdate::prepare(&ctx.environment, current_msrv(&manifest).as_ref(), msrv)?
+                .map(|update| (update, mode))
+        }
+        None => None,
+    };
 
     // Set the MSRV
-    set_or_override_msrv(&mut manifest, msrv)?;
+    let before = msrv_values(&manifest);
+    if is_virtual_manifest(&manifest) {
+        set_workspace_rust_version(&mut manifest, msrv)?;
+    } else {
+        set_or_override_msrv(&mut manifest, msrv)?;
+    }
+    let after = msrv_values(&manifest);
 
     // Open the Cargo manifest file with write permissions and truncate the current its contents
     let mut file = std::fs::OpenOptions::new()
@@ -103,20 +121,161 @@ fn set_msrv(ctx: &SetContext, reporter: &impl Reporter, msrv: &BareVersion) -> T
         AuxiliaryOutputItem::msrv(MsrvKind::RustVersion),
     ))?;
 
+    for ((path, old), (_, new)) in before.into_iter().zip(after) {
+        if old != new {
+            let location = EditLocation::TomlPath {
+                path: path.to_string(),
+            };
+            reporter.report_event(Edit::new(cargo_toml.clone(), location, old, new))?;
+        }
+    }
+
+    if let Some((update, mode)) = changelog {
+        update.write(reporter, mode)?;
+    }
+
+    let synced = if ctx.sync {
+        sync_declarations(ctx, reporter, msrv)?
+    } else {
+        Vec::new()
+    };
+
     // Report that the MSRV was set
-    reporter.report_event(SetResult::new(msrv.clone(), cargo_toml))?;
+    reporter.report_event(SetResult::new(msrv.clone(), cargo_toml).with_synced(synced))?;
 
     Ok(())
 }
 
-fn check_workspace(manifest: &DocumentMut) -> TResult<()> {
-    if manifest.as_table().get("package").is_none()
-        && manifest.as_table().get("workspace").is_some()
-    {
-        Err(CargoMSRVError::WorkspaceFound)
-    } else {
-        Ok(())
+/// Update the declarations of the MSRV outside the Cargo manifest, like the badge of the README.
+fn sync_declarations(
+    ctx: &SetContext,
+    reporter: &impl Reporter,
+    msrv: &BareVersion,
+) -> TResult<Vec<Declaration>> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(ctx.environment.manifest())
+        .no_deps()
+        .exec()?;
+    let workspace_root = &metadata.workspace_root;
+
+    let rules = sync_rules(&metadata)?;
+    let mut declarations = declaration::scan(ctx.environment.root(), workspace_root, &rules)?;
+    declarations.retain(|declaration| {
+        declaration.kind.is_readme() || declaration.kind == DeclarationKind::SyncRule
+    });
+
+    let synced = declaration::rewrite(workspace_root, &declarations, msrv)?;
+
+    for declaration in &synced {
+        let file = workspace_root.join(&declaration.file);
+
+        reporter.report_event(AuxiliaryOutput::new(
+            Destination::file(file.clone()),
+            AuxiliaryOutputItem::declaration(declaration.kind, declaration.line),
+        ))?;
+        reporter.report_event(Edit::new(
+            file,
+            EditLocation::Line {
+                line: declaration.line,
+            },
+            Some(declaration.version.to_string()),
+            Some(msrv.to_string()),
+        ))?;
     }
+
+    Ok(synced)
+}
+
+/// Whether the manifest is the root of a virtual workspace, i.e. one without a `[package]`.
+fn is_virtual_manifest(manifest: &DocumentMut) -> bool {
+    manifest.as_table().get("package").is_none() && manifest.as_table().get("workspace").is_some()
+}
+
+/// Set the `workspace.package.rust-version` of a virtual workspace, which members can inherit
+/// with `rust-version.workspace = true`.
+fn set_workspace_rust_version(manifest: &mut DocumentMut, msrv: &BareVersion) -> TResult<()> {
+    let package_item = &mut manifest["workspace"]["package"];
+
+    match package_item {
+        Item::None => {
+            // Explicitly create the table, otherwise it would default to an inline table instead
+            *package_item = table();
+            package_item["rust-version"] = value(msrv.to_string());
+        }
+        Item::Value(Value::InlineTable(table)) => {
+            table.insert("rust-version", msrv.to_string().into());
+            // Otherwise, the separator is spaced after the decor of the previous value
+            table.fmt();
+        }
+        Item::Table(table) => {
+            table.insert("rust-version", value(msrv.to_string()));
+        }
+        _ => return Err(CargoMSRVError::SetMsrv(SetMsrvError::NotATable)),
+    }
+
+    Ok(())
+}
+
+/// The MSRV which the manifest declares, as `package.rust-version`, or otherwise as
+/// `package.metadata.msrv`, or for a workspace, as `workspace.package.rust-version`.
+fn current_msrv(manifest: &DocumentMut) -> Option<BareVersion> {
+    let manifest = manifest.as_table();
+    let package = manifest.get("package");
+    let workspace_package = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("package"));
+
+    package
+        .and_then(|package| package.get("rust-version"))
+        .and_then(Item::as_str)
+        .or_else(|| package?.get("metadata")?.get("msrv").and_then(Item::as_str))
+        .or_else(|| {
+            workspace_package?
+                .get("rust-version")
+                .and_then(Item::as_str)
+        })
+        .and_then(|version| version.parse().ok())
+}
+
+/// The keys of the manifest which declare the MSRV, with their values, if any.
+pub(crate) fn msrv_values(manifest: &DocumentMut) -> [(&'static str, Option<String>); 3] {
+    let package = manifest.as_table().get("package");
+    // A value other than a version, like `{ workspace = true }`, is given as written inline
+    let value = |item: Option<&Item>| {
+        item.map(|item| match (item.as_str(), item.clone().into_value()) {
+            (Some(version), _) => version.to_string(),
+            (None, Ok(mut value)) => {
+                value.decor_mut().clear();
+                value.to_string()
+            }
+            (None, Err(item)) => item.to_string().trim().to_string(),
+        })
+    };
+
+    [
+        (
+            "package.rust-version",
+            value(package.and_then(|package| package.get("rust-version"))),
+        ),
+        (
+            "package.metadata.msrv",
+            value(
+                package
+                    .and_then(|package| package.get("metadata"))
+                    .and_then(|metadata| metadata.get("msrv")),
+            ),
+        ),
+        (
+            "workspace.package.rust-version",
+            value(
+                manifest
+                    .as_table()
+                    .get("workspace")
+                    .and_then(|workspace| workspace.get("package"))
+                    .and_then(|package| package.get("rust-version")),
+            ),
+        ),
+    ]
 }
 
 /// Override MSRV if it is already set, otherwise, simply set it
@@ -172,17 +331,6 @@ fn insert_new_msrv(manifest: &mut DocumentMut, msrv: &BareVersion) -> TResult<()
 
 /// Removes the minimum supported Rust version (MSRV) from `Cargo.toml` manifest, if it exists
 fn discard_current_msrv(document: &mut DocumentMut) {
-    fn get_package(document: &mut DocumentMut) -> Option<&mut Item> {
-        document.as_table_mut().get_mut("package")
-    }
-
-    fn get_metadata(document: &DocumentMut) -> Option<&Item> {
-        document
-            .as_table()
-            .get("package")
-            .and_then(|package| package.get("metadata"))
-    }
-
     /// Removes the `MSRV` as supported by Cargo since Rust 1.56.0
     ///
     /// [`Cargo`]: https://doc.rust-lang.org/cargo/reference/manifest.html#the-rust-version-field
@@ -192,25 +340,6 @@ fn discard_current_msrv(document: &mut DocumentMut) {
             .and_then(|package| package.remove("rust-version"));
     }
 
-    /// Removes the MSRV as supported by `cargo-msrv`, since prior to the release of Rust
-    /// 1.56.0
-    fn remove_metadata_msrv(document: &mut DocumentMut) {
-        get_package(document)
-            .and_then(|package| package.get_mut("metadata"))
-            .and_then(Item::as_table_like_mut)
-            .and_then(|metadata| metadata.remove("msrv"));
-
-        // remove residual metadata table if now empty
-        if let Some(true) = get_metadata(document)
-            .and_then(Item::as_table_like)
-            .map(|metadata| metadata.is_empty())
-        {
-            get_package(document)
-                .and_then(Item::as_table_like_mut)
-                .map(|package| package.remove("metadata"));
-        }
-    }
-
     // First remove the rust-version
     remove_rust_version(document);
 
@@ -218,6 +347,36 @@ fn discard_current_msrv(document: &mut DocumentMut) {
     remove_metadata_msrv(document);
 }
 
+fn get_package(document: &mut DocumentMut) -> Option<&mut Item> {
+    document.as_table_mut().get_mut("package")
+}
+
+fn get_metadata(document: &DocumentMut) -> Option<&Item> {
+    document
+        .as_table()
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+}
+
+/// Removes the MSRV as supported by `cargo-msrv`, since prior to the release of Rust
+/// 1.56.0
+pub(crate) fn remove_metadata_msrv(document: &mut DocumentMut) {
+    get_package(document)
+        .and_then(|package| package.get_mut("metadata"))
+        .and_then(Item::as_table_like_mut)
+        .and_then(|metadata| metadata.remove("msrv"));
+
+    // remove residual metadata table if now empty
+    if let Some(true) = get_metadata(document)
+        .and_then(Item::as_table_like)
+        .map(|metadata| metadata.is_empty())
+    {
+        get_package(document)
+            .and_then(Item::as_table_like_mut)
+            .map(|package| package.remove("metadata"));
+    }
+}
+
 #[cfg(test)]
 mod set_or_override_msrv_tests {
     use toml_edit::DocumentMut;
@@ -961,3 +1120,116 @@ mod valid_release_tests {
         assert!(!has_release(&bare, &index))
     }
 }
+
+#[cfg(test)]
+mod current_msrv_tests {
+    use toml_edit::DocumentMut;
+
+    use crate::manifest::bare_version::BareVersion;
+    use crate::manifest::{CargoManifestParser, TomlParser};
+    use crate::sub_command::set::current_msrv;
+
+    #[yare::parameterized(
+        rust_version = { "[package]\nrust-version = \"1.58.0\"\n", Some(BareVersion::ThreeComponents(1, 58, 0)) },
+        metadata_msrv = { "[package]\nmetadata.msrv = \"1.40\"\n", Some(BareVersion::TwoComponents(1, 40)) },
+        rust_version_first = { "[package]\nrust-version = \"1.60\"\nmetadata.msrv = \"1.40\"\n", Some(BareVersion::TwoComponents(1, 60)) },
+        inherited = { "[package]\nrust-version.workspace = true\n", None },
+        none = { "[package]\nname = \"a\"\n", None },
+        workspace = { "[workspace]\npackage.rust-version = \"1.62\"\n", Some(BareVersion::TwoComponents(1, 62)) },
+    )]
+    fn declared(input: &str, expected: Option<BareVersion>) {
+        let manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+
+        assert_eq!(current_msrv(&manifest), expected);
+    }
+}
+
+#[cfg(test)]
+mod msrv_values_tests {
+    use toml_edit::DocumentMut;
+
+    use crate::manifest::bare_version::BareVersion;
+    use crate::manifest::{CargoManifestParser, TomlParser};
+    use crate::sub_command::set::{msrv_values, set_or_override_msrv};
+
+    #[test]
+    fn replaced_metadata_msrv() {
+        let input = "[package]\nname = \"a\"\n\n[package.metadata]\nmsrv = \"1.40\"\n";
+        let mut manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+
+        let before = msrv_values(&manifest);
+        set_or_override_msrv(&mut manifest, &BareVersion::TwoComponents(1, 60)).unwrap();
+        let after = msrv_values(&manifest);
+
+        assert_eq!(
+            before,
+            [
+                ("package.rust-version", None),
+                ("package.metadata.msrv", Some("1.40".to_string())),
+                ("workspace.package.rust-version", None),
+            ]
+        );
+        assert_eq!(
+            after,
+            [
+                ("package.rust-version", Some("1.60".to_string())),
+                ("package.metadata.msrv", None),
+                ("workspace.package.rust-version", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn inherited_rust_version() {
+        let input = "[package]\nrust-version.workspace = true\n";
+        let manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+
+        assert_eq!(
+            msrv_values(&manifest)[0],
+            (
+                "package.rust-version",
+                Some("{ workspace = true }".to_string())
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod set_workspace_rust_version_tests {
+    use toml_edit::DocumentMut;
+
+    use crate::manifest::bare_version::BareVersion;
+    use crate::manifest::{CargoManifestParser, TomlParser};
+    use crate::sub_command::set::{is_virtual_manifest, set_workspace_rust_version};
+
+    #[yare::parameterized(
+        without_package_table = {
+            "[workspace]\nmembers = [\"a\"]\n",
+            "[workspace]\nmembers = [\"a\"]\n\n[workspace.package]\nrust-version = \"1.60\"\n"
+        },
+        with_package_table = {
+            "[workspace]\nmembers = [\"a\"]\n\n[workspace.package]\nedition = \"2021\"\nrust-version = \"1.56\" # msrv\n",
+            "[workspace]\nmembers = [\"a\"]\n\n[workspace.package]\nedition = \"2021\"\nrust-version = \"1.60\"\n"
+        },
+        with_inline_package_table = {
+            "[workspace]\npackage = { edition = \"2021\" }\n",
+            "[workspace]\npackage = { edition = \"2021\", rust-version = \"1.60\" }\n"
+        },
+    )]
+    fn set(input: &str, expected: &str) {
+        let mut manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+        assert!(is_virtual_manifest(&manifest));
+
+        set_workspace_rust_version(&mut manifest, &BareVersion::TwoComponents(1, 60)).unwrap();
+
+        assert_eq!(manifest.to_string(), expected);
+    }
+
+    #[test]
+    fn package_is_not_virtual() {
+        let input = "[package]\nname = \"a\"\n\n[workspace]\n";
+        let manifest = CargoManifestParser.parse::<DocumentMut>(input).unwrap();
+
+        assert!(!is_virtual_manifest(&manifest));
+    }
+}
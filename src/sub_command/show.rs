@@ -1,14 +1,20 @@
 use camino::Utf8PathBuf;
-use cargo_metadata::MetadataCommand;
-use std::convert::TryFrom;
+use cargo_metadata::{Metadata, MetadataCommand};
 
 use crate::context::ShowContext;
+use crate::dependency_graph::resolver::graph_from_metadata;
+use crate::dependency_graph::DependencyGraph;
 use crate::error::TResult;
 
-use crate::manifest::CargoManifest;
-use crate::reporter::event::ShowResult;
+use crate::manifest::msrv_sources::{
+    declared_msrv, msrv_sources, not_declared, package_declared_msrv, MsrvSource,
+};
+use crate::reporter::event::{
+    package_msrv, MemberMsrv, ShowResult, ShowSource, ShowWorkspaceResult, Warning,
+};
 use crate::reporter::Reporter;
-use crate::SubCommand;
+use crate::sub_command::migrate::{read_manifest, workspace_rust_version};
+use crate::{semver, SubCommand};
 
 #[derive(Default)]
 pub struct Show;
@@ -23,18 +29,104 @@ impl SubCommand for Show {
 }
 
 fn show_msrv(ctx: &ShowContext, reporter: &impl Reporter) -> TResult<()> {
-    // TODO: Add support for workspaces, but take care to also still support raw `rustup run`.
-
     let cargo_toml = ctx.environment.manifest();
 
     let metadata = MetadataCommand::new().manifest_path(&cargo_toml).exec()?;
-    let manifest = CargoManifest::try_from(metadata)?;
+    let sources = msrv_sources(&metadata)?;
+
+    // A virtual workspace has no MSRV of its own, so the MSRV of each member is shown instead
+    if metadata.root_package().is_none() {
+        return show_workspace_msrv(&metadata, &sources, cargo_toml, reporter);
+    }
+
+    let graph = graph_from_metadata(metadata.clone())?;
+
+    let Some(declared) = declared_msrv(&metadata, ctx.environment.root(), &sources)? else {
+        return Err(not_declared(sources, &cargo_toml, || {
+            Error::NoMSRVInCargoManifest(cargo_toml.to_path_buf()).into()
+        }));
+    };
+
+    report_dependencies_requiring_newer_rust(
+        &graph,
+        &declared.version.to_semver_version(),
+        reporter,
+    )?;
+
+    let result = ShowResult::new(declared.version, cargo_toml.clone())
+        .with_source(declared.source, declared.file)
+        .with_path_dependencies(&graph);
+    reporter.report_event(result)?;
+
+    Ok(())
+}
+
+/// Show the effective MSRV of each member of a virtual workspace, and the
+/// `workspace.package.rust-version` which members can inherit.
+fn show_workspace_msrv(
+    metadata: &Metadata,
+    sources: &[MsrvSource],
+    cargo_toml: Utf8PathBuf,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    let mut members = Vec::new();
 
-    let msrv = manifest
-        .minimum_rust_version()
-        .ok_or_else(|| Error::NoMSRVInCargoManifest(cargo_toml.to_path_buf()))?;
+    for package in metadata.workspace_packages() {
+        let crate_root = package
+            .manifest_path
+            .parent()
+            .unwrap_or(&metadata.workspace_root);
+        let declared = package_declared_msrv(metadata, package, crate_root, sources)?;
 
-    reporter.report_event(ShowResult::new(msrv.clone(), cargo_toml.clone()))?;
+        members.push(MemberMsrv {
+            name: package.name.to_string(),
+            manifest_path: package.manifest_path.clone(),
+            version: declared.as_ref().map(|declared| declared.version.clone()),
+            source: declared.map(|declared| ShowSource {
+                kind: declared.source,
+                path: declared.file,
+            }),
+        });
+    }
+    members.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+
+    let rust_version = workspace_rust_version(&read_manifest(&cargo_toml)?)?;
+
+    reporter.report_event(ShowWorkspaceResult::new(cargo_toml, rust_version, members))?;
+
+    Ok(())
+}
+
+/// Warn about each dependency which requires a newer Rust version than the MSRV.
+fn report_dependencies_requiring_newer_rust(
+    graph: &DependencyGraph,
+    msrv: &semver::Version,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    let packages = graph.packages();
+
+    let mut newer = graph
+        .shortest_path_parents()
+        .into_keys()
+        .map(|nx| &packages[nx])
+        .filter(|package| &package.id != graph.root_crate())
+        .filter_map(|package| {
+            package_msrv(package)
+                .filter(|required| required > msrv)
+                .map(|required| (package, required))
+        })
+        .collect::<Vec<_>>();
+    newer.sort_by(|(lhs, _), (rhs, _)| (&lhs.name, &lhs.version).cmp(&(&rhs.name, &rhs.version)));
+    newer.dedup_by(|(lhs, _), (rhs, _)| lhs.name == rhs.name && lhs.version == rhs.version);
+
+    for (package, required) in newer {
+        reporter.report_event(Warning::rust_version_below_dependency(
+            msrv,
+            &package.name,
+            &package.version,
+            &required,
+        ))?;
+    }
 
     Ok(())
 }
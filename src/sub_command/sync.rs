@@ -0,0 +1,65 @@
+use cargo_metadata::MetadataCommand;
+
+use crate::context::{EnvironmentContext, SyncContext};
+use crate::declaration::{self, Update};
+use crate::error::TResult;
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::sync_rules::sync_rules;
+use crate::reporter::event::SyncResult;
+use crate::reporter::Reporter;
+use crate::SubCommand;
+
+#[derive(Default)]
+pub struct SyncDeclarations;
+
+impl SubCommand for SyncDeclarations {
+    type Context = SyncContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        sync_declarations(&ctx.environment, ctx.msrv.clone(), ctx.dry_run, reporter)
+    }
+}
+
+/// Update each declaration of the MSRV of the crate and its workspace to the given MSRV, or by
+/// default, to the MSRV which takes precedence, i.e. the MSRV of the Cargo manifest. The changes
+/// are reported, whether these were written or, with a dry run, not.
+pub(crate) fn sync_declarations(
+    env: &EnvironmentContext,
+    msrv: Option<BareVersion>,
+    dry_run: bool,
+    reporter: &impl Reporter,
+) -> TResult<()> {
+    let metadata = MetadataCommand::new()
+        .manifest_path(env.manifest())
+        .no_deps()
+        .exec()?;
+    let workspace_root = &metadata.workspace_root;
+
+    let declarations = declaration::scan(env.root(), workspace_root, &sync_rules(&metadata)?)?;
+
+    let msrv = msrv
+        .or_else(|| {
+            declarations
+                .first()
+                .map(|declaration| declaration.version.clone())
+        })
+        .ok_or(Error::NoMsrv)?;
+
+    let update = Update::new(workspace_root, &declarations, &msrv)?;
+    let changes = if dry_run {
+        update.changes().to_vec()
+    } else {
+        update.write()?
+    };
+
+    reporter.report_event(SyncResult::new(msrv, dry_run, changes))?;
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No MSRV is declared, give the MSRV to update the declarations to, or set it with `cargo msrv set`")]
+    NoMsrv,
+}
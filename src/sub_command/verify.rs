@@ -1,18 +1,24 @@
 use camino::Utf8PathBuf;
 use cargo_metadata::MetadataCommand;
-use std::convert::TryFrom;
 
 use rust_releases::{Release, ReleaseIndex};
 
 use crate::check::Check;
-use crate::context::{EnvironmentContext, VerifyContext};
+use crate::context::verify::FindMsrv;
+use crate::context::{EnvironmentContext, ToolchainProvider, VerifyContext};
+use crate::disk_space;
 use crate::error::{CargoMSRVError, TResult};
 use crate::manifest::bare_version::BareVersion;
-use crate::manifest::CargoManifest;
+use crate::manifest::msrv_policy::{configured_msrv_policy, ReleaseType};
+use crate::manifest::msrv_sources::{declared_msrv, msrv_sources, not_declared, MsrvSource};
 use crate::outcome::Outcome;
-use crate::reporter::event::VerifyResult;
+use crate::reporter::event::{Freshness, VerifyResult};
 use crate::reporter::Reporter;
+use crate::rust::component_availability::ComponentAvailability;
+use crate::rust::dist_server;
+use crate::rust::distribution_toolchain::DistributionToolchain;
 use crate::rust::Toolchain;
+use crate::semver;
 use crate::sub_command::SubCommand;
 
 /// Verifier which determines whether a given Rust version is deemed compatible or not.
@@ -68,8 +74,36 @@ fn verify_msrv(
         bare_version.try_to_semver(release_index.releases().iter().map(Release::version))?;
 
     let target = ctx.toolchain.target;
-    let components = ctx.toolchain.components;
-    let toolchain = Toolchain::new(version.clone(), target, components);
+    let required_components = ctx.toolchain.required_components;
+
+    if !required_components.is_empty()
+        && !ComponentAvailability::new(target, required_components)?.is_available(version)?
+    {
+        return Err(CargoMSRVError::RequiredComponentsUnavailable {
+            version: version.clone(),
+            target: target.to_string(),
+            components: required_components.iter().map(|c| c.to_string()).collect(),
+        });
+    }
+
+    let toolchain = match &ctx.toolchain_provider {
+        ToolchainProvider::Distribution(distribution) => distribution.toolchain(target),
+        _ => Toolchain::new(version.clone(), target, ctx.toolchain.components),
+    };
+
+    if ctx.toolchain.dist_server_check {
+        dist_server::preflight(&ctx.toolchain_provider, [toolchain.version()])?;
+    }
+
+    if ctx.toolchain.disk_preflight {
+        disk_space::preflight(
+            reporter,
+            &ctx.environment,
+            &ctx.toolchain_provider,
+            [toolchain.version()],
+            1,
+        )?;
+    }
 
     match runner.check(&toolchain)? {
         Outcome::Success(_) => success(reporter, toolchain),
@@ -97,6 +131,88 @@ fn failure(
     )))
 }
 
+/// Compare the Rust version to the MSRV which was found, for `verify --find` or `verify --slack`.
+/// Fails if the Rust version is lower than the MSRV, or, with `--find`, higher by more than
+/// `tolerance` minor versions.
+pub(crate) fn verify_freshness(
+    reporter: &impl Reporter,
+    ctx: &VerifyContext,
+    msrv: semver::Version,
+    mode: FindMsrv,
+) -> TResult<()> {
+    let rust_version = ctx.rust_version.clone();
+    let toolchain = Toolchain::new(
+        rust_version.version().to_semver_version(),
+        ctx.toolchain.target,
+        ctx.toolchain.components,
+    );
+
+    let tolerance = match mode {
+        FindMsrv::Strict { tolerance } => Some(tolerance),
+        FindMsrv::Slack => None,
+    };
+
+    let result = VerifyResult::freshness(toolchain, msrv.clone(), tolerance);
+    let is_verified = result.is_verified();
+    let freshness = result.freshness_details().map(|details| details.freshness);
+    reporter.report_event(result)?;
+
+    if is_verified {
+        return Ok(());
+    }
+
+    let failed = VerifyFailed::from(rust_version);
+    let error = match (freshness, tolerance) {
+        (Some(Freshness::TooHigh), Some(tolerance)) => Error::AboveMsrv {
+            failed,
+            msrv,
+            tolerance,
+        },
+        _ => Error::BelowMsrv { failed, msrv },
+    };
+
+    Err(CargoMSRVError::SubCommandVerify(error))
+}
+
+/// Compare the MSRV which was found to the Rust version, the MSRV of the previous release, for
+/// `verify --release-type`. Fails if the MSRV is raised by a type of release which the MSRV policy
+/// doesn't allow to raise it.
+pub(crate) fn verify_release(
+    reporter: &impl Reporter,
+    ctx: &VerifyContext,
+    msrv: semver::Version,
+    release_type: ReleaseType,
+) -> TResult<()> {
+    let policy = configured_msrv_policy(&ctx.environment)?;
+    let rust_version = ctx.rust_version.clone();
+    let toolchain = Toolchain::new(
+        rust_version.version().to_semver_version(),
+        ctx.toolchain.target,
+        ctx.toolchain.components,
+    );
+
+    let result = VerifyResult::release(
+        toolchain,
+        msrv.clone(),
+        release_type,
+        policy.allows_bump(release_type),
+    );
+    let is_verified = result.is_verified();
+    reporter.report_event(result)?;
+
+    if is_verified {
+        return Ok(());
+    }
+
+    Err(CargoMSRVError::SubCommandVerify(
+        Error::MsrvBumpNotAllowed {
+            failed: VerifyFailed::from(rust_version),
+            msrv,
+            release_type,
+        },
+    ))
+}
+
 /// Error which can be returned if the verifier deemed the tested Rust version incompatible.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -104,6 +220,35 @@ pub enum Error {
         "Crate source was found to be incompatible with Rust version '{}' specified {}", .0.rust_version, .0.source
     )]
     VerifyFailed(VerifyFailed),
+
+    #[error(
+        "Rust version '{}' specified {} is lower than the MSRV which was found (Rust {}), so the crate does not build with it",
+        .failed.rust_version, .failed.source, .msrv
+    )]
+    BelowMsrv {
+        failed: VerifyFailed,
+        msrv: semver::Version,
+    },
+
+    #[error(
+        "Rust version '{}' specified {} is higher than the MSRV which was found (Rust {}) by more than {} minor version(s)",
+        .failed.rust_version, .failed.source, .msrv, .tolerance
+    )]
+    AboveMsrv {
+        failed: VerifyFailed,
+        msrv: semver::Version,
+        tolerance: u64,
+    },
+
+    #[error(
+        "The MSRV which was found (Rust {}) is higher than Rust version '{}' specified {}, but the MSRV policy does not allow {} releases to raise the MSRV",
+        .msrv, .failed.rust_version, .failed.source, .release_type
+    )]
+    MsrvBumpNotAllowed {
+        failed: VerifyFailed,
+        msrv: semver::Version,
+        release_type: ReleaseType,
+    },
 }
 
 /// Data structure which contains information about which version failed to verify, and where
@@ -126,6 +271,10 @@ impl From<RustVersion> for VerifyFailed {
     }
 }
 
+/// The environment variable which supplies the Rust version to verify against, when the
+/// `--rust-version` argument is not given.
+pub const RUST_VERSION_ENV_VAR: &str = "MSRV";
+
 /// A combination of a bare (two- or three component) Rust version and the source which was used to
 /// locate this version.
 #[derive(Clone, Debug)]
@@ -142,19 +291,63 @@ impl RustVersion {
         }
     }
 
+    pub fn from_distribution(distribution: &DistributionToolchain) -> Self {
+        Self {
+            rust_version: BareVersion::from(distribution.version()),
+            source: RustVersionSource::Distribution(distribution.rustc().to_path_buf()),
+        }
+    }
+
+    /// Read the Rust version from the [`RUST_VERSION_ENV_VAR`] environment variable, if it is set
+    /// and not empty.
+    pub fn try_from_env_var() -> TResult<Option<Self>> {
+        Self::try_from_env_value(std::env::var(RUST_VERSION_ENV_VAR).ok().as_deref())
+    }
+
+    fn try_from_env_value(value: Option<&str>) -> TResult<Option<Self>> {
+        let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+            return Ok(None);
+        };
+
+        Ok(Some(Self {
+            rust_version: value.parse()?,
+            source: RustVersionSource::EnvVar,
+        }))
+    }
+
     pub fn try_from_environment(env: &EnvironmentContext) -> TResult<Self> {
         let manifest_path = env.manifest();
 
         let metadata = MetadataCommand::new()
             .manifest_path(&manifest_path)
             .exec()?;
-        CargoManifest::try_from(metadata)?
-            .minimum_rust_version()
-            .ok_or_else(|| CargoMSRVError::NoMSRVKeyInCargoToml(manifest_path.clone()))
-            .map(|v| RustVersion {
-                rust_version: v.clone(),
-                source: RustVersionSource::Manifest(manifest_path.clone()),
-            })
+        let sources = msrv_sources(&metadata)?;
+
+        let Some(declared) = declared_msrv(&metadata, env.root(), &sources)? else {
+            return Err(not_declared(sources, &manifest_path, || {
+                CargoMSRVError::NoMSRVKeyInCargoToml(manifest_path.clone())
+            }));
+        };
+
+        let source = match declared.source {
+            MsrvSource::RustVersion | MsrvSource::MetadataMsrv => {
+                RustVersionSource::Manifest(manifest_path.clone())
+            }
+            source => RustVersionSource::Declaration(source, declared.file),
+        };
+
+        Ok(RustVersion {
+            rust_version: declared.version,
+            source,
+        })
+    }
+
+    /// Whether the Rust version is the MSRV declared by the crate, as found in its MSRV sources.
+    pub fn is_declared(&self) -> bool {
+        matches!(
+            self.source,
+            RustVersionSource::Manifest(_) | RustVersionSource::Declaration(..)
+        )
     }
 
     /// Get the bare (two- or three component) version specifying the Rust version.
@@ -174,6 +367,48 @@ enum RustVersionSource {
     #[error("as --rust-version argument")]
     Arg,
 
+    #[error("as {} environment variable", RUST_VERSION_ENV_VAR)]
+    EnvVar,
+
     #[error("as MSRV in the Cargo manifest located at '{0}'")]
     Manifest(Utf8PathBuf),
+
+    #[error("as {0} in '{1}'")]
+    Declaration(MsrvSource, Utf8PathBuf),
+
+    #[error("as version of the rustc located at '{0}'")]
+    Distribution(Utf8PathBuf),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        two_components = { "1.65", BareVersion::TwoComponents(1, 65) },
+        three_components = { "1.65.1", BareVersion::ThreeComponents(1, 65, 1) },
+        surrounding_whitespace = { " 1.65\n", BareVersion::TwoComponents(1, 65) },
+    )]
+    fn env_value(value: &str, expected: BareVersion) {
+        let rust_version = RustVersion::try_from_env_value(Some(value))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(rust_version.version(), &expected);
+        assert!(matches!(rust_version.source, RustVersionSource::EnvVar));
+    }
+
+    #[yare::parameterized(
+        unset = { None },
+        empty = { Some("") },
+        blank = { Some("  ") },
+    )]
+    fn env_value_absent(value: Option<&str>) {
+        assert!(RustVersion::try_from_env_value(value).unwrap().is_none());
+    }
+
+    #[test]
+    fn env_value_invalid() {
+        assert!(RustVersion::try_from_env_value(Some("one")).is_err());
+    }
 }
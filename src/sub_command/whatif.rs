@@ -0,0 +1,414 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::{Metadata, MetadataCommand};
+use toml_edit::{table, value, DocumentMut};
+
+use crate::check::{Check, ExitCodeCheck, PredicateCheck, ProviderCheck};
+use crate::context::whatif::{NewDependency, WhatIfChange};
+use crate::context::{EnvironmentContext, ToolchainProvider, WhatIfContext, WorkspacePackages};
+use crate::dependency_graph::resolver::{
+    graph_from_metadata, CargoMetadataResolver, DependencyResolver,
+};
+use crate::disk_space;
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use crate::manifest::exit_codes::configured_exit_codes;
+use crate::manifest::{CargoManifestParser, TomlParser};
+use crate::reporter::event::{WhatIfResult, WhatIfUpdateResult, WhatIfVerification};
+use crate::reporter::Reporter;
+use crate::rust::{dist_server, Toolchain};
+use crate::{semver, SubCommand};
+
+/// Determine whether a change to the dependencies would raise the MSRV, by making the change in a
+/// temporary copy of the workspace.
+#[derive(Default)]
+pub struct WhatIf;
+
+impl SubCommand for WhatIf {
+    type Context = WhatIfContext;
+    type Output = ();
+
+    fn run(&self, ctx: &Self::Context, reporter: &impl Reporter) -> TResult<Self::Output> {
+        let mut metadata_command = MetadataCommand::new();
+        metadata_command.manifest_path(ctx.environment.manifest());
+
+        // The comparison is only meaningful for the versions pinned by the committed lockfile
+        if ctx.change == WhatIfChange::Update {
+            metadata_command.other_options(vec!["--locked".to_string()]);
+        }
+
+        let metadata = metadata_command.exec()?;
+
+        let package_root = metadata
+            .root_package()
+            .and_then(|package| package.manifest_path.parent())
+            .map(Utf8Path::to_path_buf)
+            .ok_or(CargoMSRVError::NoCrateRootFound)?;
+
+        let workspace_root = metadata.workspace_root.clone();
+        let excluded = [
+            metadata.target_directory.clone(),
+            workspace_root.join(".git"),
+        ];
+        let external = external_path_dependencies(&metadata);
+
+        let before = graph_from_metadata(metadata)?;
+
+        let temp_dir = tempfile::Builder::new()
+            .prefix("cargo-msrv-whatif-")
+            .tempdir()
+            .map_err(|error| IoError {
+                error,
+                source: IoErrorSource::CreateTempDir,
+            })?;
+        let copy_root = Utf8PathBuf::try_from(temp_dir.path().to_path_buf())
+            .map_err(|_| Error::NonUtf8TempDir)?;
+
+        // Path dependencies outside the workspace are copied as well. The copies keep their
+        // location relative to the workspace, so relative `path` keys still resolve.
+        let base = common_ancestor(std::iter::once(&workspace_root).chain(&external));
+        let copy_of = |path: &Utf8Path| match path.strip_prefix(&base) {
+            Ok(relative) => copy_root.join(relative),
+            Err(_) => copy_root.clone(),
+        };
+
+        let copied_workspace_root = copy_of(&workspace_root);
+        copy_dir(&workspace_root, &copied_workspace_root, &excluded)?;
+
+        for package_root in &external {
+            let excluded = [package_root.join("target"), package_root.join(".git")];
+            copy_dir(package_root, &copy_of(package_root), &excluded)?;
+        }
+
+        // The package may be a member of a workspace, located in a subdirectory
+        let copied_package_root = copy_of(&package_root);
+        let copied_manifest = copied_package_root.join("Cargo.toml");
+
+        match &ctx.change {
+            WhatIfChange::Add(dependency) => {
+                add_dependency(&copied_manifest, dependency)?;
+
+                info!(%dependency, manifest = %copied_manifest, "resolving with added dependency");
+                let after =
+                    CargoMetadataResolver::from_manifest_path(&copied_manifest).resolve()?;
+
+                let result = WhatIfResult::new(&dependency.name, &before, &after)
+                    .ok_or_else(|| Error::NotResolved(dependency.name.clone()))?;
+
+                let result = match verify(ctx, reporter, result.msrv_before(), copied_package_root)?
+                {
+                    Some(verification) => result.with_verification(verification),
+                    None => result,
+                };
+
+                reporter.report_event(result)?;
+            }
+            WhatIfChange::Update => {
+                remove_lockfile(&copied_workspace_root.join("Cargo.lock"))?;
+
+                info!(manifest = %copied_manifest, "resolving with a fresh lockfile");
+                let metadata = MetadataCommand::new()
+                    .manifest_path(&copied_manifest)
+                    // Resolve the newest versions, rather than the newest versions compatible
+                    // with the declared rust-version, as a plain `cargo update` may do
+                    .env("CARGO_RESOLVER_INCOMPATIBLE_RUST_VERSIONS", "allow")
+                    .exec()?;
+                let after = graph_from_metadata(metadata)?;
+
+                let result = WhatIfUpdateResult::new(&before, &after);
+
+                let result = match verify(ctx, reporter, result.msrv_locked(), copied_package_root)?
+                {
+                    Some(verification) => result.with_verification(verification),
+                    None => result,
+                };
+
+                reporter.report_event(result)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// If requested, check whether the changed copy of the crate, located at `package_root`, still
+/// compiles with the toolchain of the current `msrv`.
+fn verify(
+    ctx: &WhatIfContext,
+    reporter: &impl Reporter,
+    msrv: Option<&semver::Version>,
+    package_root: Utf8PathBuf,
+) -> TResult<Option<WhatIfVerification>> {
+    if !ctx.verify {
+        return Ok(None);
+    }
+
+    let version = msrv.cloned().ok_or(Error::NoMsrvToVerify)?;
+    let environment = EnvironmentContext {
+        root_crate_path: package_root,
+        workspace_packages: WorkspacePackages::default(),
+        max_cache_size: ctx.environment.max_cache_size,
+    };
+
+    if ctx.toolchain.dist_server_check {
+        dist_server::preflight(&ToolchainProvider::Rustup, [&version])?;
+    }
+
+    if ctx.toolchain.disk_preflight {
+        disk_space::preflight(
+            reporter,
+            &environment,
+            &ToolchainProvider::Rustup,
+            [&version],
+            1,
+        )?;
+    }
+
+    let runner = ProviderCheck::new(
+        reporter,
+        false,
+        ctx.no_check_feedback,
+        &environment,
+        ctx.run_command(),
+        ToolchainProvider::Rustup,
+    )
+    .with_fallback_host(ctx.toolchain.fallback_host);
+    let exit_codes = configured_exit_codes(&ctx.environment)?;
+    let runner = ExitCodeCheck::new(reporter, runner, &exit_codes);
+    let runner = PredicateCheck::new(reporter, runner, ctx.check_cmd.predicate.as_deref());
+
+    let toolchain = Toolchain::new(
+        version.clone(),
+        ctx.toolchain.target,
+        ctx.toolchain.components,
+    );
+    let outcome = runner.check(&toolchain)?;
+
+    Ok(Some(WhatIfVerification {
+        toolchain: version,
+        compatible: outcome.is_success(),
+    }))
+}
+
+/// The root directories of the path dependencies which are located outside the workspace. Path
+/// dependencies which are located within another external path dependency are not included
+/// separately.
+fn external_path_dependencies(metadata: &Metadata) -> Vec<Utf8PathBuf> {
+    let mut roots = metadata
+        .packages
+        .iter()
+        .filter(|package| package.source.is_none())
+        .filter_map(|package| package.manifest_path.parent())
+        .filter(|root| !root.starts_with(&metadata.workspace_root))
+        .map(Utf8Path::to_path_buf)
+        .collect::<Vec<_>>();
+
+    // Parents are ordered before their subdirectories
+    roots.sort();
+    roots.dedup();
+
+    let mut external: Vec<Utf8PathBuf> = Vec::new();
+
+    for root in roots {
+        if !external.iter().any(|parent| root.starts_with(parent)) {
+            external.push(root);
+        }
+    }
+
+    external
+}
+
+/// The deepest directory which contains all of the given paths.
+fn common_ancestor<'p>(mut paths: impl Iterator<Item = &'p Utf8PathBuf>) -> Utf8PathBuf {
+    let Some(first) = paths.next() else {
+        return Utf8PathBuf::new();
+    };
+
+    let mut ancestor = first.clone();
+
+    for path in paths {
+        while !path.starts_with(&ancestor) {
+            if !ancestor.pop() {
+                break;
+            }
+        }
+    }
+
+    ancestor
+}
+
+/// Recursively copy the directory `from` to `to`, except for the `excluded` paths.
+fn copy_dir(from: &Utf8Path, to: &Utf8Path, excluded: &[Utf8PathBuf]) -> TResult<()> {
+    std::fs::create_dir_all(to).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::CreateDir(to.to_path_buf()),
+    })?;
+
+    let entries = from.read_dir_utf8().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadDir(from.to_path_buf()),
+    })?;
+
+    for entry in entries {
+        let entry = entry.map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadDir(from.to_path_buf()),
+        })?;
+
+        let path = entry.path();
+
+        if excluded.iter().any(|excluded| excluded == path) {
+            continue;
+        }
+
+        let destination = to.join(entry.file_name());
+
+        if path.is_dir() {
+            copy_dir(path, &destination, excluded)?;
+        } else {
+            std::fs::copy(path, &destination).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::CopyFile(path.to_path_buf()),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Add the dependency to the `[dependencies]` of the given Cargo manifest. Like `cargo add`, an
+/// existing dependency with the same name is replaced.
+fn add_dependency(manifest_path: &Utf8Path, dependency: &NewDependency) -> TResult<()> {
+    let contents = std::fs::read_to_string(manifest_path).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::ReadFile(manifest_path.to_path_buf()),
+    })?;
+
+    let mut manifest = CargoManifestParser.parse::<DocumentMut>(&contents)?;
+    insert_dependency(&mut manifest, dependency);
+
+    std::fs::write(manifest_path, manifest.to_string()).map_err(|error| IoError {
+        error,
+        source: IoErrorSource::WriteFile(manifest_path.to_path_buf()),
+    })?;
+
+    Ok(())
+}
+
+fn remove_lockfile(path: &Utf8Path) -> TResult<()> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(IoError {
+            error,
+            source: IoErrorSource::RemoveFile(path.to_path_buf()),
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+fn insert_dependency(manifest: &mut DocumentMut, dependency: &NewDependency) {
+    let version = dependency
+        .version
+        .as_ref()
+        .map_or_else(|| "*".to_string(), ToString::to_string);
+
+    let dependencies = manifest
+        .entry("dependencies")
+        .or_insert_with(table)
+        .as_table_like_mut();
+
+    if let Some(dependencies) = dependencies {
+        dependencies.insert(&dependency.name, value(version));
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("The path of the temporary copy of the workspace is not valid UTF-8")]
+    NonUtf8TempDir,
+
+    #[error("Dependency '{0}' was added, but is not found in the resolved dependency graph")]
+    NotResolved(String),
+
+    #[error("Unable to verify: neither the crate nor its dependencies specify an MSRV")]
+    NoMsrvToVerify,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        without_version = { "serde", "serde = \"*\"" },
+        with_version = { "serde@1.0", "serde = \"^1.0\"" },
+    )]
+    fn insert(dependency: &str, expected: &str) {
+        let mut manifest = "[package]\nname = \"a\"\n\n[dependencies]\nlog = \"0.4\"\n"
+            .parse::<DocumentMut>()
+            .unwrap();
+
+        insert_dependency(&mut manifest, &dependency.parse().unwrap());
+
+        let expected = format!(
+            "[package]\nname = \"a\"\n\n[dependencies]\nlog = \"0.4\"\n{}\n",
+            expected
+        );
+        assert_eq!(manifest.to_string(), expected);
+    }
+
+    #[test]
+    fn insert_without_dependencies_table() {
+        let mut manifest = "[package]\nname = \"a\"\n".parse::<DocumentMut>().unwrap();
+
+        insert_dependency(&mut manifest, &"serde".parse().unwrap());
+
+        assert_eq!(
+            manifest.to_string(),
+            "[package]\nname = \"a\"\n\n[dependencies]\nserde = \"*\"\n"
+        );
+    }
+
+    #[test]
+    fn replace_existing_dependency() {
+        let mut manifest = "[dependencies]\nserde = \"1.0\"\n"
+            .parse::<DocumentMut>()
+            .unwrap();
+
+        insert_dependency(&mut manifest, &"serde@=1.0.100".parse().unwrap());
+
+        assert_eq!(
+            manifest.to_string(),
+            "[dependencies]\nserde = \"=1.0.100\"\n"
+        );
+    }
+
+    #[yare::parameterized(
+        single = { &["/a/b"], "/a/b" },
+        sibling = { &["/a/b", "/a/c"], "/a" },
+        nested = { &["/a/b", "/a/b/c"], "/a/b" },
+        root = { &["/a", "/b"], "/" },
+    )]
+    fn ancestor(paths: &[&str], expected: &str) {
+        let paths = paths.iter().map(Utf8PathBuf::from).collect::<Vec<_>>();
+
+        assert_eq!(common_ancestor(paths.iter()), Utf8PathBuf::from(expected));
+    }
+
+    #[test]
+    fn copy_excludes_paths() {
+        let from = assert_fs::TempDir::new().unwrap();
+        let to = assert_fs::TempDir::new().unwrap();
+        let from_path = Utf8Path::from_path(from.path()).unwrap();
+        let to_path = Utf8Path::from_path(to.path()).unwrap();
+
+        std::fs::create_dir_all(from_path.join("src")).unwrap();
+        std::fs::create_dir_all(from_path.join("target/debug")).unwrap();
+        std::fs::write(from_path.join("Cargo.toml"), "[package]").unwrap();
+        std::fs::write(from_path.join("src/lib.rs"), "").unwrap();
+        std::fs::write(from_path.join("target/debug/a"), "").unwrap();
+
+        copy_dir(from_path, to_path, &[from_path.join("target")]).unwrap();
+
+        assert!(to_path.join("Cargo.toml").is_file());
+        assert!(to_path.join("src/lib.rs").is_file());
+        assert!(!to_path.join("target").exists());
+    }
+}
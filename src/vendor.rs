@@ -0,0 +1,136 @@
+//! The dependencies of the crate, vendored with `cargo vendor` for `--vendor`, so each check
+//! builds offline from the same sources, regardless of the toolchain and of the network.
+//!
+//! The dependencies are vendored once, with the cargo on the `PATH`, into a temporary directory,
+//! which lives as long as the search. The checks use a cargo home in the same directory, of which
+//! the configuration replaces the sources of the dependencies with the vendored ones, and makes
+//! cargo work offline. The configuration file is named `config`, rather than `config.toml`, since
+//! cargo only reads `config.toml` since Rust 1.39.
+
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, IoError, IoErrorSource, TResult};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::process::Command;
+
+/// Makes the cargo of each check work offline, besides the vendored sources.
+const OFFLINE_CONFIG: &str = "\n[net]\noffline = true\n";
+
+pub struct VendoredSources {
+    // Removed when the search is done
+    _temp_dir: tempfile::TempDir,
+    cargo_home: Utf8PathBuf,
+}
+
+impl VendoredSources {
+    /// Vendor the dependencies of the crate of the given environment, and of the cargo-fuzz
+    /// projects of `--check-fuzz`, which are checked as well.
+    pub fn create(
+        environment: &EnvironmentContext,
+        fuzz_projects: &[EnvironmentContext],
+    ) -> TResult<Self> {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("cargo-msrv-vendor-")
+            .tempdir()
+            .map_err(|error| IoError {
+                error,
+                source: IoErrorSource::CreateTempDir,
+            })?;
+        let dir = Utf8Path::from_path(temp_dir.path())
+            .ok_or_else(|| CargoMSRVError::UnableToVendor {
+                stderr: "the temporary directory is not valid UTF-8".to_string(),
+            })?
+            .to_path_buf();
+
+        let synced = fuzz_projects
+            .iter()
+            .map(EnvironmentContext::manifest)
+            .collect::<Vec<_>>();
+        let config = vendor(&environment.manifest(), &synced, &dir.join("vendor"))?;
+
+        let cargo_home = dir.join("cargo-home");
+        std::fs::create_dir_all(&cargo_home).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::CreateDir(cargo_home.clone()),
+        })?;
+
+        let config_path = cargo_home.join("config");
+        std::fs::write(&config_path, format!("{}{}", config, OFFLINE_CONFIG)).map_err(|error| {
+            IoError {
+                error,
+                source: IoErrorSource::WriteFile(config_path.clone()),
+            }
+        })?;
+
+        info!(%dir, "vendored the dependencies");
+
+        Ok(Self {
+            _temp_dir: temp_dir,
+            cargo_home,
+        })
+    }
+
+    /// The cargo home of the checks, which is configured to use the vendored sources.
+    pub fn cargo_home(&self) -> &Utf8Path {
+        &self.cargo_home
+    }
+}
+
+/// Vendor the dependencies of the manifest, and of the synced manifests, into the given directory,
+/// and return the cargo configuration which `cargo vendor` prints, which replaces the sources of
+/// the dependencies.
+fn vendor(manifest: &Utf8Path, synced: &[Utf8PathBuf], dir: &Utf8Path) -> TResult<String> {
+    let mut command = Command::new("cargo");
+    command
+        .args(["vendor", "--quiet", "--manifest-path"])
+        .arg(manifest);
+
+    for manifest in synced {
+        command.arg("--sync").arg(manifest);
+    }
+
+    command.arg(dir);
+
+    let output = command.output().map_err(|error| IoError {
+        error,
+        source: IoErrorSource::SpawnProcess(command.get_program().to_os_string()),
+    })?;
+
+    if !output.status.success() {
+        return Err(CargoMSRVError::UnableToVendor {
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WorkspacePackages;
+
+    #[test]
+    fn offline_cargo_home() {
+        let dir = assert_fs::TempDir::new().unwrap();
+        let root = Utf8Path::from_path(dir.path()).unwrap();
+
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"a\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src").join("lib.rs"), "").unwrap();
+
+        let environment = EnvironmentContext {
+            root_crate_path: root.to_path_buf(),
+            workspace_packages: WorkspacePackages::default(),
+            max_cache_size: 0,
+        };
+
+        let vendored = VendoredSources::create(&environment, &[]).unwrap();
+        let config = std::fs::read_to_string(vendored.cargo_home().join("config")).unwrap();
+
+        assert!(config.contains("offline = true"));
+    }
+}
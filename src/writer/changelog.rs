@@ -0,0 +1,240 @@
+use crate::context::EnvironmentContext;
+use crate::error::{CargoMSRVError, IoError, IoErrorSource};
+use crate::manifest::bare_version::BareVersion;
+use crate::manifest::changelog::changelog_config;
+use crate::reporter::event::ChangelogEntry;
+use crate::reporter::Reporter;
+use crate::TResult;
+use camino::Utf8PathBuf;
+use cargo_metadata::MetadataCommand;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Whether the entry on the change of the MSRV is written to the changelog.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChangelogMode {
+    Write,
+    /// Only report the entry, without writing it (`--changelog-dry-run`)
+    DryRun,
+}
+
+impl ChangelogMode {
+    /// The mode given by the `--changelog` and `--changelog-dry-run` flags, if any.
+    pub fn from_flags(changelog: bool, dry_run: bool) -> Option<Self> {
+        match (changelog, dry_run) {
+            (_, true) => Some(Self::DryRun),
+            (true, false) => Some(Self::Write),
+            (false, false) => None,
+        }
+    }
+}
+
+/// The entry on the change of the MSRV, and the changelog with the entry appended, which is
+/// prepared before the MSRV is written, so an unusable changelog fails before anything is written.
+#[derive(Debug)]
+pub struct ChangelogUpdate {
+    path: Utf8PathBuf,
+    section: String,
+    entry: String,
+    updated: String,
+}
+
+impl ChangelogUpdate {
+    /// Prepare the entry on the change of the MSRV, from `old` to `new`, for the configured section
+    /// of the changelog. Returns `None` if the MSRV did not change.
+    pub fn prepare(
+        environment: &EnvironmentContext,
+        old: Option<&BareVersion>,
+        new: &BareVersion,
+    ) -> TResult<Option<Self>> {
+        if old == Some(new) {
+            return Ok(None);
+        }
+
+        let metadata = MetadataCommand::new()
+            .manifest_path(environment.manifest())
+            .no_deps()
+            .exec()?;
+        let config = changelog_config(&metadata)?;
+        let path = metadata.workspace_root.join(&config.file);
+
+        let contents = std::fs::read_to_string(&path).map_err(|error| IoError {
+            error,
+            source: IoErrorSource::ReadFile(path.clone()),
+        })?;
+
+        let entry = render(&config.template, old, new, &today());
+        let Some(updated) = append_entry(&contents, &config.section, &entry) else {
+            return Err(CargoMSRVError::ChangelogSectionNotFound {
+                section: config.section,
+                path,
+            });
+        };
+
+        Ok(Some(Self {
+            path,
+            section: config.section,
+            entry,
+            updated,
+        }))
+    }
+
+    /// Write the changelog with the entry appended, unless this is a dry run, and report the entry.
+    pub fn write(self, reporter: &impl Reporter, mode: ChangelogMode) -> TResult<()> {
+        if mode == ChangelogMode::Write {
+            std::fs::write(&self.path, &self.updated).map_err(|error| IoError {
+                error,
+                source: IoErrorSource::WriteFile(self.path.clone()),
+            })?;
+        }
+
+        reporter.report_event(ChangelogEntry::new(
+            self.path,
+            self.section,
+            self.entry,
+            mode == ChangelogMode::DryRun,
+        ))?;
+
+        Ok(())
+    }
+}
+
+fn render(template: &str, old: Option<&BareVersion>, new: &BareVersion, date: &str) -> String {
+    let old = old.map_or_else(|| "none".to_string(), ToString::to_string);
+
+    template
+        .replace("{old}", &old)
+        .replace("{new}", &new.to_string())
+        .replace("{date}", date)
+}
+
+/// Append the entry to the section of the changelog with the given heading, after its last line,
+/// before the next heading. Returns `None` if no such section exists.
+fn append_entry(contents: &str, section: &str, entry: &str) -> Option<String> {
+    let lines = contents.lines().collect::<Vec<_>>();
+    let heading = lines
+        .iter()
+        .position(|line| line.trim() == section.trim())?;
+
+    let next_heading = lines
+        .iter()
+        .skip(heading + 1)
+        .position(|line| line.starts_with('#'))
+        .map_or(lines.len(), |offset| heading + 1 + offset);
+
+    let last = (heading..next_heading)
+        .rev()
+        .find(|&index| !lines[index].trim().is_empty())
+        .unwrap_or(heading);
+
+    let mut updated = lines[..=last].to_vec();
+    if last == heading {
+        updated.push("");
+    }
+    updated.push(entry);
+
+    let rest = &lines[last + 1..];
+    if rest.first().is_some_and(|line| !line.trim().is_empty()) {
+        updated.push("");
+    }
+    updated.extend_from_slice(rest);
+
+    let mut updated = updated.join("\n");
+    if contents.ends_with('\n') || rest.is_empty() {
+        updated.push('\n');
+    }
+
+    Some(updated)
+}
+
+/// The current date in UTC, as `YYYY-MM-DD`.
+fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    let (year, month, day) = civil_from_days(seconds / 86_400);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// The year, month and day of the given number of days since 1970-01-01, in the proleptic
+/// Gregorian calendar.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Shifted to start at 0000-03-01, so the leap day is the last day of the year
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[yare::parameterized(
+        epoch = { 0, (1970, 1, 1) },
+        march = { 19_417, (2023, 3, 1) },
+        leap_year = { 19_782, (2024, 2, 29) },
+        end_of_year = { 20_088, (2024, 12, 31) },
+    )]
+    fn civil_date(days: u64, expected: (u64, u64, u64)) {
+        assert_eq!(civil_from_days(days), expected);
+    }
+
+    #[yare::parameterized(
+        with_old = { Some(BareVersion::TwoComponents(1, 60)), "* 1.60 -> 1.65 (2024-05-01)" },
+        without_old = { None, "* none -> 1.65 (2024-05-01)" },
+    )]
+    fn rendered(old: Option<BareVersion>, expected: &str) {
+        let entry = render(
+            "* {old} -> {new} ({date})",
+            old.as_ref(),
+            &BareVersion::TwoComponents(1, 65),
+            "2024-05-01",
+        );
+
+        assert_eq!(entry, expected);
+    }
+
+    #[yare::parameterized(
+        after_entries = {
+            "# Changelog\n\n## [Unreleased]\n\n* Fix\n\n## [1.0.0]\n\n* Init\n",
+            "# Changelog\n\n## [Unreleased]\n\n* Fix\n* MSRV\n\n## [1.0.0]\n\n* Init\n"
+        },
+        empty_section = {
+            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0]\n",
+            "# Changelog\n\n## [Unreleased]\n\n* MSRV\n\n## [1.0.0]\n"
+        },
+        heading_directly_followed = {
+            "## [Unreleased]\n## [1.0.0]\n",
+            "## [Unreleased]\n\n* MSRV\n\n## [1.0.0]\n"
+        },
+        last_section = {
+            "## [Unreleased]\n\n* Fix",
+            "## [Unreleased]\n\n* Fix\n* MSRV\n"
+        },
+    )]
+    fn appended(contents: &str, expected: &str) {
+        assert_eq!(
+            append_entry(contents, "## [Unreleased]", "* MSRV").as_deref(),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn missing_section() {
+        assert!(append_entry("# Changelog\n\n## [1.0.0]\n", "## [Unreleased]", "* MSRV").is_none());
+    }
+}
@@ -1,2 +1,3 @@
+pub mod changelog;
 pub mod toolchain_file;
 pub mod write_msrv;
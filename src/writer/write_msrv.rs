@@ -1,6 +1,7 @@
 use crate::context::{EnvironmentContext, RustReleasesContext, SetContext};
 use crate::manifest::bare_version::BareVersion;
 use crate::reporter::Reporter;
+use crate::writer::changelog::ChangelogMode;
 use crate::{Set, SubCommand, TResult};
 use rust_releases::ReleaseIndex;
 
@@ -13,11 +14,14 @@ pub fn write_msrv(
     release_index: Option<&ReleaseIndex>,
     environment: EnvironmentContext,
     rust_releases: RustReleasesContext,
+    changelog: Option<ChangelogMode>,
 ) -> TResult<()> {
     let context = SetContext {
         msrv,
         environment,
         rust_releases,
+        sync: false,
+        changelog,
     };
 
     Set::new(release_index).run(&context, reporter)?;
@@ -27,6 +31,7 @@ pub fn write_msrv(
 
 #[cfg(test)]
 mod tests {
+    use crate::cache::DEFAULT_MAX_CACHE_SIZE;
     use crate::context::{EnvironmentContext, RustReleasesContext, WorkspacePackages};
     use crate::error::CargoMSRVError;
     use crate::manifest::bare_version::BareVersion;
@@ -53,6 +58,7 @@ mod tests {
         let env = EnvironmentContext {
             root_crate_path: root.to_path_buf(),
             workspace_packages: WorkspacePackages::default(),
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
         };
 
         let index = ReleaseIndex::from_iter(vec![rust_releases::Release::new_stable(
@@ -65,6 +71,7 @@ mod tests {
             Some(&index),
             env,
             RustReleasesContext::default(),
+            None,
         )
         .unwrap();
 
@@ -88,6 +95,7 @@ mod tests {
         let env = EnvironmentContext {
             root_crate_path: root.to_path_buf(),
             workspace_packages: WorkspacePackages::default(),
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
         };
 
         let index = ReleaseIndex::from_iter(vec![]);
@@ -98,6 +106,7 @@ mod tests {
             Some(&index),
             env,
             RustReleasesContext::default(),
+            None,
         )
         .unwrap_err();
 
@@ -120,6 +129,7 @@ mod tests {
         let env = EnvironmentContext {
             root_crate_path: root.to_path_buf(),
             workspace_packages: WorkspacePackages::default(),
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
         };
 
         write_msrv(
@@ -128,6 +138,7 @@ mod tests {
             None,
             env,
             RustReleasesContext::default(),
+            None,
         )
         .unwrap();
 
@@ -166,6 +166,26 @@ fn msrv_with_release_source(release_source: &str, folder: &str, expected_version
     assert_eq!(actual_version, expected_version);
 }
 
+#[test]
+fn msrv_without_lockfile() {
+    let fixture = Fixture::new("1.36.0");
+    std::fs::remove_file(fixture.tmp_path("Cargo.lock")).unwrap();
+
+    let with_args = vec![
+        "cargo",
+        "msrv",
+        "find",
+        "--linear",
+        "--path",
+        fixture.to_str(),
+    ];
+
+    // The lockfile which cargo-msrv generates, with the cargo of the host, doesn't exclude the
+    // older Rust versions
+    let result = find_msrv(with_args).unwrap();
+    assert_eq!(result.unwrap(), semver::Version::new(1, 36, 0));
+}
+
 #[test]
 fn msrv_with_old_lockfile() {
     let fixture = Fixture::new("1.29.2");
@@ -39,3 +39,40 @@ fn expect_no_user_output() {
     assert_eq!(stdout.as_ref(), "");
     assert_eq!(stderr.as_ref(), "");
 }
+
+#[test]
+fn report_is_written_to_redirected_stdout() {
+    let cargo_msrv_manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let dir = assert_fs::TempDir::new().unwrap();
+    let event_log = dir.path().join("events.jsonl");
+    std::fs::write(
+        &event_log,
+        r#"{"type":"subcommand_init","subcommand_id":"find"}
+{"type":"check_result","toolchain":{"version":"1.60.0","target":"x86_64-unknown-linux-gnu"},"is_compatible":true}
+{"type":"subcommand_result","subcommand_id":"find","result":{"version":"1.60.0","success":true}}
+"#,
+    )
+    .unwrap();
+
+    // The stdout of the process is a pipe, not a terminal, like for `cargo msrv report > file`
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--manifest-path",
+            cargo_msrv_manifest.to_str().unwrap(),
+            "--",
+            "report",
+            "--from",
+            event_log.to_str().unwrap(),
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .expect("Unable to run cargo-msrv via cargo in test");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success());
+    assert!(stdout.contains("1.60.0"), "{}", stdout);
+}